@@ -21,6 +21,8 @@ import {SuperchainConfig} from "src/L1/SuperchainConfig.sol";
 import {DisputeGameFactory} from "src/dispute/DisputeGameFactory.sol";
 import {ERC1967Proxy} from "@openzeppelin/contracts/proxy/ERC1967/ERC1967Proxy.sol";
 import {OPSuccinctFaultDisputeGame} from "src/fp/OPSuccinctFaultDisputeGame.sol";
+import {VkeyCommitment} from "src/lib/VkeyCommitment.sol";
+import {CURRENT_AGGREGATION_OUTPUTS_VERSION} from "src/lib/Types.sol";
 import {SP1MockVerifier} from "@sp1-contracts/src/SP1MockVerifier.sol";
 
 // Utils
@@ -97,7 +99,15 @@ contract DeployOPSuccinctFDG is Script {
 
             rollupConfigHash = vm.envBytes32("ROLLUP_CONFIG_HASH");
             aggregationVkey = vm.envBytes32("AGGREGATION_VKEY");
-            rangeVkeyCommitment = vm.envBytes32("RANGE_VKEY_COMMITMENT");
+            // The commitment is computed from the range vkeys `vkeys` prints if they are set, so it can't be derived
+            // from the wrong digest of the vkey.
+            bytes32 rangeVkey = vm.envOr("RANGE_VKEY", bytes32(0));
+            if (rangeVkey != bytes32(0)) {
+                rangeVkeyCommitment =
+                    VkeyCommitment.rangeVkeyCommitment(rangeVkey, vm.envOr("SECONDARY_RANGE_VKEY", bytes32(0)));
+            } else {
+                rangeVkeyCommitment = vm.envBytes32("RANGE_VKEY_COMMITMENT");
+            }
         }
 
         OPSuccinctFaultDisputeGame gameImpl = new OPSuccinctFaultDisputeGame(
@@ -107,6 +117,8 @@ contract DeployOPSuccinctFDG is Script {
             ISP1Verifier(sp1VerifierAddress),
             rollupConfigHash,
             aggregationVkey,
+            // Games of an aggregation program built before the version byte are deployed with version 0.
+            uint8(vm.envOr("AGGREGATION_OUTPUTS_VERSION", uint256(CURRENT_AGGREGATION_OUTPUTS_VERSION))),
             rangeVkeyCommitment,
             vm.envOr("PROOF_REWARD", uint256(0.01 ether)),
             IAnchorStateRegistry(address(registry)),
@@ -3,6 +3,7 @@ pragma solidity ^0.8.15;
 
 import {Script} from "forge-std/Script.sol";
 import {OPSuccinctL2OutputOracle} from "../../src/validity/OPSuccinctL2OutputOracle.sol";
+import {CURRENT_AGGREGATION_OUTPUTS_VERSION} from "../../src/lib/Types.sol";
 import {Utils} from "../../test/helpers/Utils.sol";
 import {Proxy} from "@optimism/src/universal/Proxy.sol";
 import {console} from "forge-std/console.sol";
@@ -14,6 +15,7 @@ contract OPSuccinctParameterUpdater is Script, Utils {
     function run() public {
         vm.startBroadcast();
 
+        // With `RANGE_VKEY` set, the range vkey commitment is computed from the range vkeys with `VkeyCommitment`.
         Config memory cfg = readJson("opsuccinctl2ooconfig.json");
 
         address l2OutputOracleProxy = vm.envAddress("L2OO_ADDRESS");
@@ -21,12 +23,19 @@ contract OPSuccinctParameterUpdater is Script, Utils {
 
         OPSuccinctL2OutputOracle oracleImpl = OPSuccinctL2OutputOracle(l2OutputOracleProxy);
 
-        if (cfg.aggregationVkey != oracleImpl.aggregationVkey()) {
+        // The aggregation vkey in the config is of the aggregation program of this release, which commits to the current
+        // version of the public values.
+        if (
+            cfg.aggregationVkey != oracleImpl.aggregationVkey()
+                || oracleImpl.aggregationOutputsVersion() != CURRENT_AGGREGATION_OUTPUTS_VERSION
+        ) {
             if (executeUpgradeCall) {
-                oracleImpl.updateAggregationVkey(cfg.aggregationVkey);
+                oracleImpl.updateAggregationVkey(cfg.aggregationVkey, CURRENT_AGGREGATION_OUTPUTS_VERSION);
             } else {
-                bytes memory aggregationVkeyCalldata =
-                    abi.encodeWithSelector(OPSuccinctL2OutputOracle.updateAggregationVkey.selector, cfg.aggregationVkey);
+                // `updateAggregationVkey` is overloaded, so the selector is taken from its signature.
+                bytes memory aggregationVkeyCalldata = abi.encodeWithSignature(
+                    "updateAggregationVkey(bytes32,uint8)", cfg.aggregationVkey, CURRENT_AGGREGATION_OUTPUTS_VERSION
+                );
                 console.log("The calldata for upgrading the aggregationVkey is:");
                 console.logBytes(aggregationVkeyCalldata);
             }
@@ -32,7 +32,7 @@ import {
     UnexpectedRootClaim
 } from "src/dispute/lib/Errors.sol";
 import "src/fp/lib/Errors.sol";
-import {AggregationOutputs} from "src/lib/Types.sol";
+import {AggregationOutputs, encodeAggregationOutputs, isKnownAggregationOutputsVersion} from "src/lib/Types.sol";
 
 // Interfaces
 import {ISemver} from "interfaces/universal/ISemver.sol";
@@ -118,6 +118,9 @@ contract OPSuccinctFaultDisputeGame is Clone, ISemver, IDisputeGame {
     /// @notice The vkey for the aggregation program.
     bytes32 internal immutable AGGREGATION_VKEY;
 
+    /// @notice The version of the public values the aggregation program commits to.
+    uint8 internal immutable AGGREGATION_OUTPUTS_VERSION;
+
     /// @notice The 32 byte commitment to the BabyBear representation of the verification key of the range SP1 program. Specifically,
     /// this verification is the output of converting the [u32; 8] range BabyBear verification key to a [u8; 32] array.
     bytes32 internal immutable RANGE_VKEY_COMMITMENT;
@@ -173,6 +176,7 @@ contract OPSuccinctFaultDisputeGame is Clone, ISemver, IDisputeGame {
     /// @param _sp1Verifier The address of the SP1 verifier that verifies the proof for the aggregation program.
     /// @param _rollupConfigHash The rollup config hash for the L2 network.
     /// @param _aggregationVkey The vkey for the aggregation program.
+    /// @param _aggregationOutputsVersion The version of the public values the aggregation program commits to.
     /// @param _rangeVkeyCommitment The commitment to the range vkey.
     /// @param _proofReward The proof reward for the game.
     /// @param _anchorStateRegistry The anchor state registry for the L2 network.
@@ -183,11 +187,15 @@ contract OPSuccinctFaultDisputeGame is Clone, ISemver, IDisputeGame {
         ISP1Verifier _sp1Verifier,
         bytes32 _rollupConfigHash,
         bytes32 _aggregationVkey,
+        uint8 _aggregationOutputsVersion,
         bytes32 _rangeVkeyCommitment,
         uint256 _proofReward,
         IAnchorStateRegistry _anchorStateRegistry,
         AccessManager _accessManager
     ) {
+        // INVARIANT: The proofs of the aggregation program must be verifiable against its public values.
+        if (!isKnownAggregationOutputsVersion(_aggregationOutputsVersion)) revert UnknownAggregationOutputsVersion();
+
         // Set up initial game state.
         GAME_TYPE = GameType.wrap(42);
         MAX_CHALLENGE_DURATION = _maxChallengeDuration;
@@ -196,6 +204,7 @@ contract OPSuccinctFaultDisputeGame is Clone, ISemver, IDisputeGame {
         SP1_VERIFIER = _sp1Verifier;
         ROLLUP_CONFIG_HASH = _rollupConfigHash;
         AGGREGATION_VKEY = _aggregationVkey;
+        AGGREGATION_OUTPUTS_VERSION = _aggregationOutputsVersion;
         RANGE_VKEY_COMMITMENT = _rangeVkeyCommitment;
         PROOF_REWARD = _proofReward;
         ANCHOR_STATE_REGISTRY = _anchorStateRegistry;
@@ -373,10 +382,13 @@ contract OPSuccinctFaultDisputeGame is Clone, ISemver, IDisputeGame {
             claimRoot: rootClaim().raw(),
             claimBlockNum: l2BlockNumber(),
             rollupConfigHash: ROLLUP_CONFIG_HASH,
-            rangeVkeyCommitment: RANGE_VKEY_COMMITMENT
+            rangeVkeyCommitment: RANGE_VKEY_COMMITMENT,
+            checkpointOverridesHash: bytes32(0)
         });
 
-        SP1_VERIFIER.verifyProof(AGGREGATION_VKEY, abi.encode(publicValues), proofBytes);
+        SP1_VERIFIER.verifyProof(
+            AGGREGATION_VKEY, encodeAggregationOutputs(AGGREGATION_OUTPUTS_VERSION, publicValues), proofBytes
+        );
 
         // Update the prover address
         claimData.prover = msg.sender;
@@ -19,3 +19,6 @@ error ParentGameNotResolved();
 
 /// @notice Thrown when the claim has already been proven.
 error AlreadyProven();
+
+/// @notice Thrown when the aggregation program's public values are of an unknown version.
+error UnknownAggregationOutputsVersion();
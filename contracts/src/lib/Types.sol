@@ -9,4 +9,39 @@ struct AggregationOutputs {
     uint256 claimBlockNum;
     bytes32 rollupConfigHash;
     bytes32 rangeVkeyCommitment;
+    bytes32 checkpointOverridesHash;
+}
+
+/// @notice The public values encoding of aggregation programs built before the version byte: the ABI-encoded outputs
+///         without the checkpoint overrides hash, which those programs didn't commit to.
+uint8 constant LEGACY_AGGREGATION_OUTPUTS_VERSION = 0;
+
+/// @notice The version byte the aggregation program of this release prefixes its public values with.
+uint8 constant CURRENT_AGGREGATION_OUTPUTS_VERSION = 1;
+
+/// @notice Whether aggregation proofs with public values of the given version can be verified.
+function isKnownAggregationOutputsVersion(uint8 _version) pure returns (bool) {
+    return _version == LEGACY_AGGREGATION_OUTPUTS_VERSION || _version == CURRENT_AGGREGATION_OUTPUTS_VERSION;
+}
+
+/// @notice Encodes the public values of an aggregation proof as an aggregation program of the given version commits to
+///         them: the version byte followed by the ABI-encoded outputs, or the legacy encoding without either the
+///         version byte or the checkpoint overrides hash.
+function encodeAggregationOutputs(uint8 _version, AggregationOutputs memory _outputs) pure returns (bytes memory) {
+    if (_version == LEGACY_AGGREGATION_OUTPUTS_VERSION) {
+        require(
+            _outputs.checkpointOverridesHash == bytes32(0),
+            "AggregationOutputs: legacy aggregation programs can't rely on checkpoint overrides"
+        );
+        return abi.encode(
+            _outputs.l1Head,
+            _outputs.l2PreRoot,
+            _outputs.claimRoot,
+            _outputs.claimBlockNum,
+            _outputs.rollupConfigHash,
+            _outputs.rangeVkeyCommitment
+        );
+    }
+    require(_version == CURRENT_AGGREGATION_OUTPUTS_VERSION, "AggregationOutputs: unknown version");
+    return abi.encodePacked(_version, abi.encode(_outputs));
 }
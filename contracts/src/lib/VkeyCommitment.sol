@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.15;
+
+/// @notice The commitment to the range program vkeys that the aggregation program commits to.
+/// @dev Must match `range_vkey_commitment` in `utils/client/src/types.rs`, which the aggregation program
+///      computes. Both are tested against `test/fixtures/vkey_commitments.json`.
+library VkeyCommitment {
+    /// @notice Computes the range vkey commitment the L2OO must be configured with.
+    /// @param _rangeVkey The `hash_u32` digest of the range program's vkey, as big-endian words.
+    /// @param _secondaryRangeVkey The digest of the secondary range program's vkey in quorum mode, or zero.
+    /// @return The range vkey itself, or the hash of both vkeys in quorum mode.
+    function rangeVkeyCommitment(bytes32 _rangeVkey, bytes32 _secondaryRangeVkey) internal pure returns (bytes32) {
+        if (_secondaryRangeVkey == bytes32(0)) {
+            return _rangeVkey;
+        }
+        return keccak256(abi.encodePacked(_rangeVkey, _secondaryRangeVkey));
+    }
+}
@@ -2,9 +2,15 @@
 pragma solidity ^0.8.15;
 
 import {Initializable} from "@openzeppelin/contracts/proxy/utils/Initializable.sol";
+import {Multicall} from "@openzeppelin/contracts/utils/Multicall.sol";
 import {ISemver} from "interfaces/universal/ISemver.sol";
 import {Types} from "@optimism/src/libraries/Types.sol";
-import {AggregationOutputs} from "../lib/Types.sol";
+import {
+    AggregationOutputs,
+    CURRENT_AGGREGATION_OUTPUTS_VERSION,
+    encodeAggregationOutputs,
+    isKnownAggregationOutputsVersion
+} from "../lib/Types.sol";
 import {Constants} from "@optimism/src/libraries/Constants.sol";
 import {ISP1Verifier} from "@sp1-contracts/src/ISP1Verifier.sol";
 
@@ -14,7 +20,10 @@ import {ISP1Verifier} from "@sp1-contracts/src/ISP1Verifier.sol";
 ///         commitment to the state of the L2 chain. Other contracts like the OptimismPortal use
 ///         these outputs to verify information about the state of L2. The outputs posted to this contract
 ///         are proved to be valid with `op-succinct`.
-contract OPSuccinctL2OutputOracle is Initializable, ISemver {
+/// @dev Inherits `Multicall` so the proposer can checkpoint an L1 block hash and propose an output in a
+///      single transaction. `multicall` uses `delegatecall`, so `msg.sender` is preserved for the
+///      approved proposer check.
+contract OPSuccinctL2OutputOracle is Initializable, ISemver, Multicall {
     /// @notice Parameters to initialize the OPSuccinctL2OutputOracle contract.
     struct InitParams {
         address challenger;
@@ -88,6 +97,14 @@ contract OPSuccinctL2OutputOracle is Initializable, ISemver {
     /// @notice Activate optimistic mode. When true, the contract will accept outputs without verification.
     bool public optimisticMode;
 
+    /// @notice The hashes of the checkpoint overrides the owner approved for emergency catch-up. An approval is
+    ///         consumed by the proposal relying on it.
+    mapping(bytes32 => bool) public approvedCheckpointOverrides;
+
+    /// @notice The version of the public values the aggregation program of `aggregationVkey` commits to. Contracts
+    ///         upgraded from before the version byte keep the legacy version 0 until the aggregation vkey is updated.
+    uint8 public aggregationOutputsVersion;
+
     ////////////////////////////////////////////////////////////
     //                         Events                         //
     ////////////////////////////////////////////////////////////
@@ -111,6 +128,11 @@ contract OPSuccinctL2OutputOracle is Initializable, ISemver {
     /// @param newAggregationVkey The new aggregation verification key.
     event AggregationVkeyUpdated(bytes32 indexed oldAggregationVkey, bytes32 indexed newAggregationVkey);
 
+    /// @notice Emitted when the version of the aggregation program's public values is updated.
+    /// @param oldAggregationOutputsVersion The old version.
+    /// @param newAggregationOutputsVersion The new version.
+    event AggregationOutputsVersionUpdated(uint8 oldAggregationOutputsVersion, uint8 newAggregationOutputsVersion);
+
     /// @notice Emitted when the range verification key commitment is updated.
     /// @param oldRangeVkeyCommitment The old range verification key commitment.
     /// @param newRangeVkeyCommitment The new range verification key commitment.
@@ -146,6 +168,11 @@ contract OPSuccinctL2OutputOracle is Initializable, ISemver {
     /// @param finalizationPeriodSeconds The new finalization period in seconds.
     event OptimisticModeToggled(bool indexed enabled, uint256 finalizationPeriodSeconds);
 
+    /// @notice Emitted when checkpoint overrides are approved.
+    /// @param checkpointOverridesHash The hash of the approved checkpoint overrides.
+    /// @param justification The justification for skipping the gaps the overrides bridge.
+    event CheckpointOverridesApproved(bytes32 indexed checkpointOverridesHash, string justification);
+
     ////////////////////////////////////////////////////////////
     //                         Errors                         //
     ////////////////////////////////////////////////////////////
@@ -158,8 +185,8 @@ contract OPSuccinctL2OutputOracle is Initializable, ISemver {
     error L1BlockHashNotCheckpointed();
 
     /// @notice Semantic version.
-    /// @custom:semver v1.0.0
-    string public constant version = "v1.0.0";
+    /// @custom:semver v1.1.0
+    string public constant version = "v1.1.0";
 
     /// @notice The version of the initializer on the contract. Used for managing upgrades.
     uint8 public constant initializerVersion = 1;
@@ -228,6 +255,7 @@ contract OPSuccinctL2OutputOracle is Initializable, ISemver {
 
         // OP Succinct initialization parameters.
         aggregationVkey = _initParams.aggregationVkey;
+        aggregationOutputsVersion = CURRENT_AGGREGATION_OUTPUTS_VERSION;
         rangeVkeyCommitment = _initParams.rangeVkeyCommitment;
         verifier = _initParams.verifier;
         rollupConfigHash = _initParams.rollupConfigHash;
@@ -315,6 +343,40 @@ contract OPSuccinctL2OutputOracle is Initializable, ISemver {
         payable
         whenNotOptimistic
     {
+        _proposeL2Output(_outputRoot, _l2BlockNumber, _l1BlockNumber, _proof, bytes32(0));
+    }
+
+    /// @notice Accepts an outputRoot proven by an aggregation proof over gaps force-resolved by governance. The
+    ///         owner must have approved the checkpoint overrides bridging the gaps, and the approval is consumed.
+    /// @param _outputRoot              The L2 output of the checkpoint block.
+    /// @param _l2BlockNumber           The L2 block number that resulted in _outputRoot.
+    /// @param _l1BlockNumber           The block number with the specified block hash.
+    /// @param _proof                   The aggregation proof.
+    /// @param _checkpointOverridesHash The hash of the checkpoint overrides the proof relies on.
+    function proposeL2OutputWithCheckpointOverrides(
+        bytes32 _outputRoot,
+        uint256 _l2BlockNumber,
+        uint256 _l1BlockNumber,
+        bytes memory _proof,
+        bytes32 _checkpointOverridesHash
+    ) external payable whenNotOptimistic {
+        require(
+            approvedCheckpointOverrides[_checkpointOverridesHash],
+            "L2OutputOracle: checkpoint overrides must be approved by the owner"
+        );
+        approvedCheckpointOverrides[_checkpointOverridesHash] = false;
+
+        _proposeL2Output(_outputRoot, _l2BlockNumber, _l1BlockNumber, _proof, _checkpointOverridesHash);
+    }
+
+    /// @notice Verifies the aggregation proof of an output and appends it.
+    function _proposeL2Output(
+        bytes32 _outputRoot,
+        uint256 _l2BlockNumber,
+        uint256 _l1BlockNumber,
+        bytes memory _proof,
+        bytes32 _checkpointOverridesHash
+    ) internal {
         // The proposer must be explicitly approved, or the zero address must be approved (permissionless proposing).
         require(
             approvedProposers[msg.sender] || approvedProposers[address(0)],
@@ -344,10 +406,13 @@ contract OPSuccinctL2OutputOracle is Initializable, ISemver {
             claimRoot: _outputRoot,
             claimBlockNum: _l2BlockNumber,
             rollupConfigHash: rollupConfigHash,
-            rangeVkeyCommitment: rangeVkeyCommitment
+            rangeVkeyCommitment: rangeVkeyCommitment,
+            checkpointOverridesHash: _checkpointOverridesHash
         });
 
-        ISP1Verifier(verifier).verifyProof(aggregationVkey, abi.encode(publicValues), _proof);
+        ISP1Verifier(verifier).verifyProof(
+            aggregationVkey, encodeAggregationOutputs(aggregationOutputsVersion, publicValues), _proof
+        );
 
         emit OutputProposed(_outputRoot, nextOutputIndex(), _l2BlockNumber, block.timestamp);
 
@@ -515,11 +580,30 @@ contract OPSuccinctL2OutputOracle is Initializable, ISemver {
         submissionInterval = _submissionInterval;
     }
 
-    /// @notice Updates the aggregation verification key.
+    /// @notice Updates the aggregation verification key to that of an aggregation program committing to the current
+    ///         version of the public values. Kept for callers from before the version was stored with the vkey.
     /// @param _aggregationVkey The new aggregation verification key.
     function updateAggregationVkey(bytes32 _aggregationVkey) external onlyOwner {
+        _updateAggregationVkey(_aggregationVkey, CURRENT_AGGREGATION_OUTPUTS_VERSION);
+    }
+
+    /// @notice Updates the aggregation verification key, and the version of the public values its program commits to.
+    /// @param _aggregationVkey The new aggregation verification key.
+    /// @param _aggregationOutputsVersion The version of the public values of the new aggregation program.
+    function updateAggregationVkey(bytes32 _aggregationVkey, uint8 _aggregationOutputsVersion) external onlyOwner {
+        _updateAggregationVkey(_aggregationVkey, _aggregationOutputsVersion);
+    }
+
+    /// @notice Updates the aggregation verification key and the version of the public values its program commits to.
+    function _updateAggregationVkey(bytes32 _aggregationVkey, uint8 _aggregationOutputsVersion) internal {
+        require(
+            isKnownAggregationOutputsVersion(_aggregationOutputsVersion),
+            "L2OutputOracle: unknown aggregation outputs version"
+        );
         emit AggregationVkeyUpdated(aggregationVkey, _aggregationVkey);
+        emit AggregationOutputsVersionUpdated(aggregationOutputsVersion, _aggregationOutputsVersion);
         aggregationVkey = _aggregationVkey;
+        aggregationOutputsVersion = _aggregationOutputsVersion;
     }
 
     /// @notice Updates the range verification key commitment.
@@ -557,6 +641,19 @@ contract OPSuccinctL2OutputOracle is Initializable, ISemver {
         emit ProposerUpdated(_proposer, true);
     }
 
+    /// @notice Approves checkpoint overrides for a single proposal, for emergency catch-up after governance
+    ///         force-resolved a stretch of blocks that can't be proven.
+    /// @param _checkpointOverridesHash The hash of the checkpoint overrides.
+    /// @param _justification The justification for skipping the gaps the overrides bridge.
+    function approveCheckpointOverrides(bytes32 _checkpointOverridesHash, string calldata _justification)
+        external
+        onlyOwner
+    {
+        require(_checkpointOverridesHash != bytes32(0), "L2OutputOracle: checkpoint overrides hash cannot be zero");
+        approvedCheckpointOverrides[_checkpointOverridesHash] = true;
+        emit CheckpointOverridesApproved(_checkpointOverridesHash, _justification);
+    }
+
     /// @notice Removes a proposer address.
     /// @param _proposer The proposer address to remove.
     function removeProposer(address _proposer) external onlyOwner {
@@ -17,8 +17,18 @@ import {
     GameNotResolved,
     GameNotFinalized
 } from "src/dispute/lib/Errors.sol";
-import {ParentGameNotResolved, InvalidParentGame, ClaimAlreadyChallenged, AlreadyProven} from "src/fp/lib/Errors.sol";
-import {AggregationOutputs} from "src/lib/Types.sol";
+import {
+    ParentGameNotResolved,
+    InvalidParentGame,
+    ClaimAlreadyChallenged,
+    AlreadyProven,
+    UnknownAggregationOutputsVersion
+} from "src/fp/lib/Errors.sol";
+import {
+    AggregationOutputs,
+    CURRENT_AGGREGATION_OUTPUTS_VERSION,
+    LEGACY_AGGREGATION_OUTPUTS_VERSION
+} from "src/lib/Types.sol";
 
 // Contracts
 import {DisputeGameFactory} from "src/dispute/DisputeGameFactory.sol";
@@ -53,6 +63,8 @@ contract OPSuccinctFaultDisputeGameTest is Test {
     OPSuccinctFaultDisputeGame game;
 
     AnchorStateRegistry anchorStateRegistry;
+    SP1MockVerifier sp1Verifier;
+    AccessManager accessManager;
 
     address proposer = address(0x123);
     address challenger = address(0x456);
@@ -88,7 +100,7 @@ contract OPSuccinctFaultDisputeGameTest is Test {
         factory = DisputeGameFactory(address(factoryProxy));
 
         // Create a mock verifier.
-        SP1MockVerifier sp1Verifier = new SP1MockVerifier();
+        sp1Verifier = new SP1MockVerifier();
 
         // Create an anchor state registry.
         SuperchainConfig superchainConfig = new SuperchainConfig();
@@ -109,29 +121,12 @@ contract OPSuccinctFaultDisputeGameTest is Test {
         );
         anchorStateRegistry = AnchorStateRegistry(address(proxy));
 
-        AccessManager accessManager = new AccessManager();
+        accessManager = new AccessManager();
         accessManager.setProposer(proposer, true);
         accessManager.setChallenger(challenger, true);
 
-        // Parameters for the OPSuccinctFaultDisputeGame.
-        bytes32 rollupConfigHash = bytes32(0);
-        bytes32 aggregationVkey = bytes32(0);
-        bytes32 rangeVkeyCommitment = bytes32(0);
-        uint256 proofReward = 1 ether;
-
         // Deploy the reference implementation of OPSuccinctFaultDisputeGame.
-        gameImpl = new OPSuccinctFaultDisputeGame(
-            maxChallengeDuration,
-            maxProveDuration,
-            IDisputeGameFactory(address(factory)),
-            ISP1Verifier(address(sp1Verifier)),
-            rollupConfigHash,
-            aggregationVkey,
-            rangeVkeyCommitment,
-            proofReward,
-            IAnchorStateRegistry(address(anchorStateRegistry)),
-            accessManager
-        );
+        gameImpl = newGameImpl(CURRENT_AGGREGATION_OUTPUTS_VERSION);
 
         // Set the init bond on the factory for the OPSuccinctFDG specific GameType.
         factory.setInitBond(gameType, 1 ether);
@@ -182,6 +177,37 @@ contract OPSuccinctFaultDisputeGameTest is Test {
         vm.stopPrank();
     }
 
+    // Deploy an implementation of OPSuccinctFaultDisputeGame for an aggregation program committing to public values of
+    // the given version.
+    function newGameImpl(uint8 _aggregationOutputsVersion) internal returns (OPSuccinctFaultDisputeGame) {
+        return new OPSuccinctFaultDisputeGame(
+            maxChallengeDuration,
+            maxProveDuration,
+            IDisputeGameFactory(address(factory)),
+            ISP1Verifier(address(sp1Verifier)),
+            bytes32(0), // rollupConfigHash
+            bytes32(0), // aggregationVkey
+            _aggregationOutputsVersion,
+            bytes32(0), // rangeVkeyCommitment
+            1 ether, // proofReward
+            IAnchorStateRegistry(address(anchorStateRegistry)),
+            accessManager
+        );
+    }
+
+    // The public values the aggregation proof of a game commits to.
+    function aggregationOutputs(OPSuccinctFaultDisputeGame _game) internal view returns (AggregationOutputs memory) {
+        return AggregationOutputs({
+            l1Head: Hash.unwrap(_game.l1Head()),
+            l2PreRoot: Hash.unwrap(_game.startingRootHash()),
+            claimRoot: _game.rootClaim().raw(),
+            claimBlockNum: _game.l2BlockNumber(),
+            rollupConfigHash: bytes32(0),
+            rangeVkeyCommitment: bytes32(0),
+            checkpointOverridesHash: bytes32(0)
+        });
+    }
+
     // =========================================
     // Test: Basic initialization checks
     // =========================================
@@ -670,4 +696,56 @@ contract OPSuccinctFaultDisputeGameTest is Test {
 
         vm.stopPrank();
     }
+
+    // =========================================
+    // Test: The proof is verified against the public values of the aggregation program's version
+    // =========================================
+    function testProveVerifiesVersionedPublicValues() public {
+        AggregationOutputs memory outputs = aggregationOutputs(game);
+        vm.expectCall(
+            address(sp1Verifier),
+            abi.encodeCall(
+                ISP1Verifier.verifyProof,
+                (bytes32(0), abi.encodePacked(CURRENT_AGGREGATION_OUTPUTS_VERSION, abi.encode(outputs)), bytes(""))
+            )
+        );
+        vm.prank(prover);
+        game.prove(bytes(""));
+    }
+
+    function testProveVerifiesLegacyPublicValues() public {
+        // Games of an aggregation program built before the version byte.
+        factory.setImplementation(gameType, IDisputeGame(address(newGameImpl(LEGACY_AGGREGATION_OUTPUTS_VERSION))));
+        vm.deal(proposer, 1 ether);
+        vm.prank(proposer);
+        OPSuccinctFaultDisputeGame legacyGame = OPSuccinctFaultDisputeGame(
+            address(
+                factory.create{value: 1 ether}(
+                    gameType, Claim.wrap(keccak256("legacy")), abi.encodePacked(uint256(3000), type(uint32).max)
+                )
+            )
+        );
+
+        // The legacy public values have neither the version byte nor the checkpoint overrides hash.
+        AggregationOutputs memory outputs = aggregationOutputs(legacyGame);
+        bytes memory publicValues = abi.encode(
+            outputs.l1Head,
+            outputs.l2PreRoot,
+            outputs.claimRoot,
+            outputs.claimBlockNum,
+            outputs.rollupConfigHash,
+            outputs.rangeVkeyCommitment
+        );
+        assertEq(publicValues.length, 6 * 32);
+        vm.expectCall(
+            address(sp1Verifier), abi.encodeCall(ISP1Verifier.verifyProof, (bytes32(0), publicValues, bytes("")))
+        );
+        vm.prank(prover);
+        legacyGame.prove(bytes(""));
+    }
+
+    function testCannotDeployWithUnknownAggregationOutputsVersion() public {
+        vm.expectRevert(UnknownAggregationOutputsVersion.selector);
+        newGameImpl(CURRENT_AGGREGATION_OUTPUTS_VERSION + 1);
+    }
 }
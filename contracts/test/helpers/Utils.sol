@@ -7,6 +7,7 @@ import {Strings} from "@openzeppelin/contracts/utils/Strings.sol";
 import {Proxy} from "@optimism/src/universal/Proxy.sol";
 import {ProxyAdmin} from "@optimism/src/universal/ProxyAdmin.sol";
 import {OPSuccinctL2OutputOracle} from "../../src/validity/OPSuccinctL2OutputOracle.sol";
+import {VkeyCommitment} from "../../src/lib/VkeyCommitment.sol";
 
 contract Utils is Test, JSONDecoder {
     function deployWithConfig(Config memory cfg) public returns (address) {
@@ -81,6 +82,25 @@ contract Utils is Test, JSONDecoder {
         string memory path = string.concat(root, "/", filepath);
         string memory json = vm.readFile(path);
         bytes memory data = vm.parseJson(json);
-        return abi.decode(data, (Config));
+        Config memory cfg = abi.decode(data, (Config));
+        cfg.rangeVkeyCommitment = rangeVkeyCommitmentFromEnv(cfg.rangeVkeyCommitment);
+        return cfg;
+    }
+
+    // The range vkey commitment to deploy or update the contracts with. If `RANGE_VKEY` is set, and
+    // `SECONDARY_RANGE_VKEY` in quorum mode, as printed by `vkeys`, the commitment is computed from them with
+    // `VkeyCommitment`, and must match the given commitment unless it is zero. Otherwise, the given commitment is used.
+    function rangeVkeyCommitmentFromEnv(bytes32 configured) public view returns (bytes32) {
+        bytes32 rangeVkey = vm.envOr("RANGE_VKEY", bytes32(0));
+        if (rangeVkey == bytes32(0)) {
+            return configured;
+        }
+        bytes32 commitment =
+            VkeyCommitment.rangeVkeyCommitment(rangeVkey, vm.envOr("SECONDARY_RANGE_VKEY", bytes32(0)));
+        require(
+            configured == bytes32(0) || configured == commitment,
+            "Utils: range vkey commitment doesn't match RANGE_VKEY and SECONDARY_RANGE_VKEY"
+        );
+        return commitment;
     }
 }
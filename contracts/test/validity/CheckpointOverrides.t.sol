@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.15;
+
+import {Test, console} from "forge-std/Test.sol";
+import {Utils} from "../helpers/Utils.sol";
+import {OPSuccinctL2OutputOracle} from "../../src/validity/OPSuccinctL2OutputOracle.sol";
+import {
+    AggregationOutputs,
+    CURRENT_AGGREGATION_OUTPUTS_VERSION,
+    LEGACY_AGGREGATION_OUTPUTS_VERSION
+} from "../../src/lib/Types.sol";
+import {ISP1Verifier} from "@sp1-contracts/src/ISP1Verifier.sol";
+import {SP1MockVerifier} from "@sp1-contracts/src/SP1MockVerifier.sol";
+
+contract CheckpointOverridesTest is Test, Utils {
+    address constant OWNER = address(0x1234);
+    address constant PROPOSER = address(0x5678);
+
+    bytes32 constant STARTING_OUTPUT_ROOT = keccak256("starting output root");
+    bytes32 constant OUTPUT_ROOT = keccak256("output root");
+    bytes32 constant CHECKPOINT_OVERRIDES_HASH = keccak256("checkpoint overrides");
+    string constant JUSTIFICATION = "Blocks 100-200 force-resolved by governance";
+
+    // The mock verifier accepts an empty proof for any public values.
+    bytes constant PROOF = "";
+
+    OPSuccinctL2OutputOracle l2oo;
+    uint256 l1BlockNumber;
+
+    event CheckpointOverridesApproved(bytes32 indexed checkpointOverridesHash, string justification);
+
+    function setUp() public {
+        vm.warp(1_000_000);
+        vm.roll(1000);
+
+        vm.startBroadcast();
+        Config memory config = Config({
+            challenger: address(0),
+            finalizationPeriod: 0,
+            l2BlockTime: 2,
+            owner: OWNER,
+            proposer: PROPOSER,
+            rollupConfigHash: keccak256("rollup config"),
+            startingBlockNumber: 0,
+            startingOutputRoot: STARTING_OUTPUT_ROOT,
+            startingTimestamp: block.timestamp - 10_000,
+            submissionInterval: 10,
+            verifier: address(new SP1MockVerifier()),
+            aggregationVkey: keccak256("aggregation vkey"),
+            rangeVkeyCommitment: keccak256("range vkey commitment"),
+            proxyAdmin: address(0),
+            opSuccinctL2OutputOracleImpl: address(0)
+        });
+        l2oo = OPSuccinctL2OutputOracle(deployWithConfig(config));
+        vm.stopBroadcast();
+
+        l1BlockNumber = block.number - 1;
+        l2oo.checkpointBlockHash(l1BlockNumber);
+    }
+
+    // The public values the current aggregation program commits to for the next output, relying on the given
+    // checkpoint overrides.
+    function publicValues(bytes32 _checkpointOverridesHash) internal view returns (bytes memory) {
+        return abi.encodePacked(
+            CURRENT_AGGREGATION_OUTPUTS_VERSION,
+            abi.encode(
+                AggregationOutputs({
+                    l1Head: l2oo.historicBlockHashes(l1BlockNumber),
+                    l2PreRoot: STARTING_OUTPUT_ROOT,
+                    claimRoot: OUTPUT_ROOT,
+                    claimBlockNum: l2oo.nextBlockNumber(),
+                    rollupConfigHash: l2oo.rollupConfigHash(),
+                    rangeVkeyCommitment: l2oo.rangeVkeyCommitment(),
+                    checkpointOverridesHash: _checkpointOverridesHash
+                })
+            )
+        );
+    }
+
+    // The public values an aggregation program built before the version byte commits to for the next output.
+    function legacyPublicValues() internal view returns (bytes memory) {
+        return abi.encode(
+            l2oo.historicBlockHashes(l1BlockNumber),
+            STARTING_OUTPUT_ROOT,
+            OUTPUT_ROOT,
+            l2oo.nextBlockNumber(),
+            l2oo.rollupConfigHash(),
+            l2oo.rangeVkeyCommitment()
+        );
+    }
+
+    function proposeWithOverrides(uint256 l2BlockNumber) internal {
+        vm.prank(PROPOSER);
+        l2oo.proposeL2OutputWithCheckpointOverrides(
+            OUTPUT_ROOT, l2BlockNumber, l1BlockNumber, PROOF, CHECKPOINT_OVERRIDES_HASH
+        );
+    }
+
+    function testApproveCheckpointOverrides() public {
+        vm.expectEmit(true, false, false, true, address(l2oo));
+        emit CheckpointOverridesApproved(CHECKPOINT_OVERRIDES_HASH, JUSTIFICATION);
+        vm.prank(OWNER);
+        l2oo.approveCheckpointOverrides(CHECKPOINT_OVERRIDES_HASH, JUSTIFICATION);
+
+        assertTrue(l2oo.approvedCheckpointOverrides(CHECKPOINT_OVERRIDES_HASH));
+    }
+
+    function testApproveCheckpointOverridesRejectsNonOwner() public {
+        vm.expectRevert("L2OutputOracle: caller is not the owner");
+        vm.prank(PROPOSER);
+        l2oo.approveCheckpointOverrides(CHECKPOINT_OVERRIDES_HASH, JUSTIFICATION);
+
+        assertFalse(l2oo.approvedCheckpointOverrides(CHECKPOINT_OVERRIDES_HASH));
+    }
+
+    function testApproveCheckpointOverridesRejectsZeroHash() public {
+        vm.expectRevert("L2OutputOracle: checkpoint overrides hash cannot be zero");
+        vm.prank(OWNER);
+        l2oo.approveCheckpointOverrides(bytes32(0), JUSTIFICATION);
+    }
+
+    function testProposeWithCheckpointOverridesConsumesApproval() public {
+        vm.prank(OWNER);
+        l2oo.approveCheckpointOverrides(CHECKPOINT_OVERRIDES_HASH, JUSTIFICATION);
+
+        // The proof must commit to the approved overrides.
+        uint256 l2BlockNumber = l2oo.nextBlockNumber();
+        vm.expectCall(
+            l2oo.verifier(),
+            abi.encodeCall(
+                ISP1Verifier.verifyProof, (l2oo.aggregationVkey(), publicValues(CHECKPOINT_OVERRIDES_HASH), PROOF)
+            )
+        );
+        proposeWithOverrides(l2BlockNumber);
+
+        assertEq(l2oo.getL2Output(l2oo.latestOutputIndex()).outputRoot, OUTPUT_ROOT);
+        assertFalse(l2oo.approvedCheckpointOverrides(CHECKPOINT_OVERRIDES_HASH));
+    }
+
+    function testProposeWithCheckpointOverridesRejectsReuse() public {
+        vm.prank(OWNER);
+        l2oo.approveCheckpointOverrides(CHECKPOINT_OVERRIDES_HASH, JUSTIFICATION);
+        proposeWithOverrides(l2oo.nextBlockNumber());
+
+        uint256 l2BlockNumber = l2oo.nextBlockNumber();
+        vm.expectRevert("L2OutputOracle: checkpoint overrides must be approved by the owner");
+        proposeWithOverrides(l2BlockNumber);
+    }
+
+    function testProposeWithCheckpointOverridesRejectsUnapproved() public {
+        uint256 l2BlockNumber = l2oo.nextBlockNumber();
+        vm.expectRevert("L2OutputOracle: checkpoint overrides must be approved by the owner");
+        proposeWithOverrides(l2BlockNumber);
+    }
+
+    function testProposeWithCheckpointOverridesRejectsNonProposer() public {
+        vm.prank(OWNER);
+        l2oo.approveCheckpointOverrides(CHECKPOINT_OVERRIDES_HASH, JUSTIFICATION);
+
+        uint256 l2BlockNumber = l2oo.nextBlockNumber();
+        vm.expectRevert("L2OutputOracle: only approved proposers can propose new outputs");
+        vm.prank(OWNER);
+        l2oo.proposeL2OutputWithCheckpointOverrides(
+            OUTPUT_ROOT, l2BlockNumber, l1BlockNumber, PROOF, CHECKPOINT_OVERRIDES_HASH
+        );
+
+        // A rejected proposal doesn't consume the approval.
+        assertTrue(l2oo.approvedCheckpointOverrides(CHECKPOINT_OVERRIDES_HASH));
+    }
+
+    function testProposeWithoutCheckpointOverridesCommitsToZero() public {
+        uint256 l2BlockNumber = l2oo.nextBlockNumber();
+        vm.expectCall(
+            l2oo.verifier(),
+            abi.encodeCall(ISP1Verifier.verifyProof, (l2oo.aggregationVkey(), publicValues(bytes32(0)), PROOF))
+        );
+        vm.prank(PROPOSER);
+        l2oo.proposeL2Output(OUTPUT_ROOT, l2BlockNumber, l1BlockNumber, PROOF);
+
+        assertEq(l2oo.getL2Output(l2oo.latestOutputIndex()).outputRoot, OUTPUT_ROOT);
+    }
+
+    function testInitializeUsesCurrentAggregationOutputsVersion() public view {
+        assertEq(l2oo.aggregationOutputsVersion(), CURRENT_AGGREGATION_OUTPUTS_VERSION);
+    }
+
+    function testProposeWithLegacyAggregationProgram() public {
+        bytes32 legacyVkey = keccak256("legacy aggregation vkey");
+        vm.prank(OWNER);
+        l2oo.updateAggregationVkey(legacyVkey, LEGACY_AGGREGATION_OUTPUTS_VERSION);
+        assertEq(l2oo.aggregationOutputsVersion(), LEGACY_AGGREGATION_OUTPUTS_VERSION);
+
+        uint256 l2BlockNumber = l2oo.nextBlockNumber();
+        bytes memory expected = legacyPublicValues();
+        assertEq(expected.length, 6 * 32);
+        vm.expectCall(l2oo.verifier(), abi.encodeCall(ISP1Verifier.verifyProof, (legacyVkey, expected, PROOF)));
+        vm.prank(PROPOSER);
+        l2oo.proposeL2Output(OUTPUT_ROOT, l2BlockNumber, l1BlockNumber, PROOF);
+
+        assertEq(l2oo.getL2Output(l2oo.latestOutputIndex()).outputRoot, OUTPUT_ROOT);
+    }
+
+    function testProposeWithCheckpointOverridesRejectsLegacyAggregationProgram() public {
+        vm.startPrank(OWNER);
+        l2oo.updateAggregationVkey(keccak256("legacy aggregation vkey"), LEGACY_AGGREGATION_OUTPUTS_VERSION);
+        l2oo.approveCheckpointOverrides(CHECKPOINT_OVERRIDES_HASH, JUSTIFICATION);
+        vm.stopPrank();
+
+        uint256 l2BlockNumber = l2oo.nextBlockNumber();
+        vm.expectRevert("AggregationOutputs: legacy aggregation programs can't rely on checkpoint overrides");
+        proposeWithOverrides(l2BlockNumber);
+    }
+
+    function testUpdateAggregationVkeyWithoutVersionUsesCurrentVersion() public {
+        vm.startPrank(OWNER);
+        l2oo.updateAggregationVkey(keccak256("legacy aggregation vkey"), LEGACY_AGGREGATION_OUTPUTS_VERSION);
+        bytes32 vkey = keccak256("aggregation vkey");
+        l2oo.updateAggregationVkey(vkey);
+        vm.stopPrank();
+
+        assertEq(l2oo.aggregationVkey(), vkey);
+        assertEq(l2oo.aggregationOutputsVersion(), CURRENT_AGGREGATION_OUTPUTS_VERSION);
+    }
+
+    function testUpdateAggregationVkeyRejectsUnknownVersion() public {
+        vm.expectRevert("L2OutputOracle: unknown aggregation outputs version");
+        vm.prank(OWNER);
+        l2oo.updateAggregationVkey(keccak256("aggregation vkey"), CURRENT_AGGREGATION_OUTPUTS_VERSION + 1);
+    }
+}
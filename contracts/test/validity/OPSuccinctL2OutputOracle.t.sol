@@ -4,6 +4,7 @@ pragma solidity ^0.8.15;
 import {Test, console} from "forge-std/Test.sol";
 import {Utils} from "../helpers/Utils.sol";
 import {OPSuccinctL2OutputOracle} from "../../src/validity/OPSuccinctL2OutputOracle.sol";
+import {LEGACY_AGGREGATION_OUTPUTS_VERSION} from "../../src/lib/Types.sol";
 
 contract OPSuccinctL2OutputOracleTest is Test, Utils {
     // Example proof data for the BoB testnet. Tx: https://sepolia.etherscan.io/tx/0x3910121f57c2e81ac98f5154eba7a2845f7ed27caf57a73e516ca606ad9d9aab
@@ -16,6 +17,9 @@ contract OPSuccinctL2OutputOracleTest is Test, Utils {
     // The owner of the L2OO.
     address OWNER = 0xDEd0000E32f8F40414d3ab3a830f735a3553E18e;
 
+    // keccak256("eip1967.proxy.implementation") - 1
+    bytes32 constant EIP1967_IMPLEMENTATION_SLOT = 0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc;
+
     OPSuccinctL2OutputOracle l2oo;
 
     function setUp() public {
@@ -30,4 +34,32 @@ contract OPSuccinctL2OutputOracleTest is Test, Utils {
         vm.prank(OWNER);
         l2oo.proposeL2Output(claimedOutputRoot, claimedL2BlockNum, checkpointedL1BlockNum, proof);
     }
+
+    // Test checkpointing the block hash and proposing the output in a single multicall.
+    function testOPSuccinctL2OOMulticallFork() public {
+        l2oo = OPSuccinctL2OutputOracle(0x83EBf366f868784c91d49fBEe67651F7a3de74C5);
+
+        // Replace the deployed implementation with the current one, which supports multicall.
+        address impl = address(uint160(uint256(vm.load(address(l2oo), EIP1967_IMPLEMENTATION_SLOT))));
+        vm.etch(impl, address(new OPSuccinctL2OutputOracle()).code);
+
+        // The proxy was initialized before the version byte, so it keeps verifying the example proof against the legacy
+        // public values of its aggregation program.
+        assertEq(l2oo.aggregationOutputsVersion(), LEGACY_AGGREGATION_OUTPUTS_VERSION);
+
+        bytes[] memory calls = new bytes[](2);
+        calls[0] = abi.encodeCall(OPSuccinctL2OutputOracle.checkpointBlockHash, (checkpointedL1BlockNum));
+        calls[1] = abi.encodeWithSignature(
+            "proposeL2Output(bytes32,uint256,uint256,bytes)",
+            claimedOutputRoot,
+            claimedL2BlockNum,
+            checkpointedL1BlockNum,
+            proof
+        );
+
+        vm.prank(OWNER);
+        l2oo.multicall(calls);
+
+        assertEq(l2oo.getL2Output(l2oo.latestOutputIndex()).outputRoot, claimedOutputRoot);
+    }
 }
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.15;
+
+import {Test} from "forge-std/Test.sol";
+import {VkeyCommitment} from "../../src/lib/VkeyCommitment.sol";
+import {Utils} from "../helpers/Utils.sol";
+
+contract VkeyCommitmentTest is Test, Utils {
+    // Note: The fields should be in alphabetical order for Solidity to parse it correctly.
+    struct Case {
+        bytes32 commitment;
+        bytes32 rangeVkey;
+        bytes32 secondaryRangeVkey;
+    }
+
+    // The fixtures are shared with the Rust tests of `range_vkey_commitment`, so both implementations compute the
+    // same commitments.
+    function testRangeVkeyCommitmentFixtures() public view {
+        string memory json = vm.readFile(string.concat(vm.projectRoot(), "/test/fixtures/vkey_commitments.json"));
+        Case[] memory cases = abi.decode(vm.parseJson(json, ".cases"), (Case[]));
+        assertGt(cases.length, 0);
+
+        for (uint256 i = 0; i < cases.length; i++) {
+            assertEq(
+                VkeyCommitment.rangeVkeyCommitment(cases[i].rangeVkey, cases[i].secondaryRangeVkey), cases[i].commitment
+            );
+        }
+    }
+
+    function testRangeVkeyCommitmentFromEnv() public {
+        bytes32 configured = keccak256("configured");
+        bytes32 rangeVkey = keccak256("range vkey");
+        bytes32 secondaryRangeVkey = keccak256("secondary range vkey");
+
+        // Without `RANGE_VKEY`, the configured commitment is used.
+        vm.setEnv("RANGE_VKEY", vm.toString(bytes32(0)));
+        vm.setEnv("SECONDARY_RANGE_VKEY", vm.toString(bytes32(0)));
+        assertEq(rangeVkeyCommitmentFromEnv(configured), configured);
+
+        vm.setEnv("RANGE_VKEY", vm.toString(rangeVkey));
+        assertEq(rangeVkeyCommitmentFromEnv(bytes32(0)), rangeVkey);
+        assertEq(rangeVkeyCommitmentFromEnv(rangeVkey), rangeVkey);
+
+        vm.setEnv("SECONDARY_RANGE_VKEY", vm.toString(secondaryRangeVkey));
+        assertEq(
+            rangeVkeyCommitmentFromEnv(bytes32(0)), VkeyCommitment.rangeVkeyCommitment(rangeVkey, secondaryRangeVkey)
+        );
+
+        vm.expectRevert("Utils: range vkey commitment doesn't match RANGE_VKEY and SECONDARY_RANGE_VKEY");
+        this.rangeVkeyCommitmentFromEnv(rangeVkey);
+    }
+}
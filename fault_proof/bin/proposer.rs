@@ -1,4 +1,8 @@
-use std::{env, time::Duration};
+use std::{
+    env,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 
 use alloy_eips::BlockNumberOrTag;
 use alloy_network::Ethereum;
@@ -9,6 +13,7 @@ use alloy_sol_types::SolValue;
 use alloy_transport_http::reqwest::Url;
 use anyhow::{Context, Result};
 use clap::Parser;
+use futures::future::join_all;
 use op_alloy_network::EthereumWallet;
 use sp1_sdk::{
     network::FulfillmentStrategy, NetworkProver, Prover, ProverClient, SP1ProvingKey,
@@ -22,11 +27,12 @@ use fault_proof::{
         DisputeGameFactory, DisputeGameFactory::DisputeGameFactoryInstance,
         OPSuccinctFaultDisputeGame,
     },
+    lag::{LagController, LagTarget},
     utils::setup_logging,
     FactoryTrait, L1ProviderWithWallet, L2Provider, L2ProviderTrait, Mode, NUM_CONFIRMATIONS,
     TIMEOUT_SECONDS,
 };
-use op_succinct_client_utils::boot::BootInfoStruct;
+use op_succinct_client_utils::public_values::{decode_boot_info, decode_intermediate_outputs};
 use op_succinct_host_utils::{
     fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
     get_agg_proof_stdin, get_proof_stdin, start_server_and_native_client, ProgramType,
@@ -59,6 +65,8 @@ where
     factory: DisputeGameFactoryInstance<(), L1ProviderWithWallet<F, P>>,
     init_bond: U256,
     prover: SP1Prover,
+    /// Set when targeting a finality lag.
+    lag_controller: Option<Mutex<LagController>>,
 }
 
 impl<F, P> OPSuccinctProposer<F, P>
@@ -77,6 +85,16 @@ where
         let (range_pk, range_vk) = network_prover.setup(RANGE_ELF);
         let (agg_pk, _) = network_prover.setup(AGG_ELF);
 
+        let lag_controller = config.target_finality_lag_secs.map(|target_lag_secs| {
+            Mutex::new(LagController::new(LagTarget {
+                target_lag_secs,
+                min_range_size: config.proposal_interval_in_blocks,
+                max_range_size: config.max_proposal_interval_in_blocks,
+                max_concurrency: config.max_concurrent_proposals,
+                congestion_threshold: Duration::from_secs(config.proof_congestion_threshold_secs),
+            }))
+        });
+
         Ok(Self {
             config: config.clone(),
             l1_provider_with_wallet: l1_provider_with_wallet.clone(),
@@ -89,14 +107,15 @@ where
                 range_vk,
                 agg_pk,
             },
+            lag_controller,
         })
     }
 
-    /// Creates a new game with the given parameters.
+    /// Creates a new game with the given parameters, returning its address.
     ///
     /// `l2_block_number`: the L2 block number we are proposing the output root for.
     /// `parent_game_index`: the index of the parent game.
-    async fn create_game(&self, l2_block_number: U256, parent_game_index: u32) -> Result<()> {
+    async fn create_game(&self, l2_block_number: U256, parent_game_index: u32) -> Result<Address> {
         tracing::info!(
             "Creating game at L2 block number: {:?}, with parent game index: {:?}",
             l2_block_number,
@@ -132,119 +151,190 @@ where
             receipt.transaction_hash
         );
 
-        if self.config.fast_finality_mode {
-            tracing::info!("Fast finality mode enabled: Generating proof for the game immediately");
-
-            let fetcher = match OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await
-            {
-                Ok(f) => f,
-                Err(e) => {
-                    tracing::error!("Failed to create data fetcher: {}", e);
-                    return Err(anyhow::anyhow!("Failed to create data fetcher: {}", e));
-                }
-            };
-
-            let game =
-                OPSuccinctFaultDisputeGame::new(game_address, self.l1_provider_with_wallet.clone());
-            let l1_head_hash = game.l1Head().call().await?.l1Head_;
-            tracing::debug!("L1 head hash: {:?}", hex::encode(l1_head_hash));
-
-            let host_args = match fetcher
-                .get_host_args(
-                    l2_block_number.to::<u64>() - self.config.proposal_interval_in_blocks,
-                    l2_block_number.to::<u64>(),
-                    Some(l1_head_hash),
-                    ProgramType::Multi,
-                    CacheMode::DeleteCache,
-                )
-                .await
-            {
-                Ok(cli) => cli,
-                Err(e) => {
-                    tracing::error!("Failed to get host CLI args: {}", e);
-                    return Err(anyhow::anyhow!("Failed to get host CLI args: {}", e));
-                }
-            };
-
-            let mem_kv_store = start_server_and_native_client(host_args).await?;
-
-            let sp1_stdin = match get_proof_stdin(mem_kv_store) {
-                Ok(stdin) => stdin,
-                Err(e) => {
-                    tracing::error!("Failed to get proof stdin: {}", e);
-                    return Err(anyhow::anyhow!("Failed to get proof stdin: {}", e));
-                }
-            };
-
-            tracing::info!("Generating Range Proof");
-            let range_proof = self
-                .prover
-                .network_prover
-                .prove(&self.prover.range_pk, &sp1_stdin)
-                .compressed()
-                .strategy(FulfillmentStrategy::Hosted)
-                .skip_simulation(true)
-                .cycle_limit(1_000_000_000_000)
-                .run_async()
-                .await?;
+        Ok(game_address)
+    }
 
-            tracing::info!("Preparing Stdin for Agg Proof");
-            let proof = range_proof.proof.clone();
-            let mut public_values = range_proof.public_values.clone();
-            let boot_info: BootInfoStruct = public_values.read();
-
-            let headers = match fetcher
-                .get_header_preimages(&vec![boot_info.clone()], boot_info.clone().l1Head)
-                .await
-            {
-                Ok(headers) => headers,
-                Err(e) => {
-                    tracing::error!("Failed to get header preimages: {}", e);
-                    return Err(anyhow::anyhow!("Failed to get header preimages: {}", e));
-                }
-            };
-
-            let sp1_stdin = match get_agg_proof_stdin(
-                vec![proof],
-                vec![boot_info.clone()],
-                headers,
-                &self.prover.range_vk,
-                boot_info.l1Head,
-            ) {
-                Ok(s) => s,
-                Err(e) => {
-                    tracing::error!("Failed to get agg proof stdin: {}", e);
-                    return Err(anyhow::anyhow!("Failed to get agg proof stdin: {}", e));
-                }
-            };
-
-            tracing::info!("Generating Agg Proof");
-            let agg_proof = self
-                .prover
-                .network_prover
-                .prove(&self.prover.agg_pk, &sp1_stdin)
-                .groth16()
-                .run_async()
-                .await?;
+    /// Generates the proof for a game proposing the output root at `l2_end_block`, from its
+    /// parent's output root at `l2_start_block`, and submits it.
+    async fn prove_game(
+        &self,
+        game_address: Address,
+        l2_start_block: u64,
+        l2_end_block: u64,
+    ) -> Result<()> {
+        tracing::info!("Fast finality mode enabled: Generating proof for the game immediately");
+
+        let fetcher = match OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await {
+            Ok(f) => f,
+            Err(e) => {
+                tracing::error!("Failed to create data fetcher: {}", e);
+                return Err(anyhow::anyhow!("Failed to create data fetcher: {}", e));
+            }
+        };
 
-            let receipt = game
-                .prove(agg_proof.bytes().into())
-                .send()
-                .await?
-                .get_receipt()
-                .await?;
+        let game =
+            OPSuccinctFaultDisputeGame::new(game_address, self.l1_provider_with_wallet.clone());
+        let l1_head_hash = game.l1Head().call().await?.l1Head_;
+        tracing::debug!("L1 head hash: {:?}", hex::encode(l1_head_hash));
+
+        let host_args = match fetcher
+            .get_host_args(
+                l2_start_block,
+                l2_end_block,
+                Some(l1_head_hash),
+                ProgramType::Multi,
+                CacheMode::DeleteCache,
+            )
+            .await
+        {
+            Ok(cli) => cli,
+            Err(e) => {
+                tracing::error!("Failed to get host CLI args: {}", e);
+                return Err(anyhow::anyhow!("Failed to get host CLI args: {}", e));
+            }
+        };
 
-            tracing::info!(
-                "\x1b[1mSuccessfully proved game {:?} with tx {:?}\x1b[0m",
-                game_address,
-                receipt.transaction_hash
-            );
-        }
+        let mem_kv_store = start_server_and_native_client(host_args).await?;
+
+        let sp1_stdin = match get_proof_stdin(mem_kv_store) {
+            Ok(stdin) => stdin,
+            Err(e) => {
+                tracing::error!("Failed to get proof stdin: {}", e);
+                return Err(anyhow::anyhow!("Failed to get proof stdin: {}", e));
+            }
+        };
+
+        tracing::info!("Generating Range Proof");
+        let range_proof = self
+            .prover
+            .network_prover
+            .prove(&self.prover.range_pk, &sp1_stdin)
+            .compressed()
+            .strategy(FulfillmentStrategy::Hosted)
+            .skip_simulation(true)
+            .cycle_limit(1_000_000_000_000)
+            .run_async()
+            .await?;
+
+        tracing::info!("Preparing Stdin for Agg Proof");
+        let proof = range_proof.proof.clone();
+        let (range_public_values_version, boot_info) =
+            decode_boot_info(range_proof.public_values.as_slice())?;
+        let range_intermediate_outputs =
+            decode_intermediate_outputs(range_proof.public_values.as_slice())?
+                .map(|intermediate_outputs| vec![intermediate_outputs]);
+
+        let headers = match fetcher
+            .get_header_preimages(&vec![boot_info.clone()], boot_info.clone().l1Head)
+            .await
+        {
+            Ok(headers) => headers,
+            Err(e) => {
+                tracing::error!("Failed to get header preimages: {}", e);
+                return Err(anyhow::anyhow!("Failed to get header preimages: {}", e));
+            }
+        };
+
+        let sp1_stdin = match get_agg_proof_stdin(
+            vec![proof],
+            vec![boot_info.clone()],
+            range_public_values_version,
+            range_intermediate_outputs,
+            headers,
+            &self.prover.range_vk,
+            boot_info.l1Head,
+            Vec::new(),
+        ) {
+            Ok(s) => s,
+            Err(e) => {
+                tracing::error!("Failed to get agg proof stdin: {}", e);
+                return Err(anyhow::anyhow!("Failed to get agg proof stdin: {}", e));
+            }
+        };
+
+        tracing::info!("Generating Agg Proof");
+        let agg_proof = self
+            .prover
+            .network_prover
+            .prove(&self.prover.agg_pk, &sp1_stdin)
+            .groth16()
+            .run_async()
+            .await?;
+
+        let receipt = game
+            .prove(agg_proof.bytes().into())
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        tracing::info!(
+            "\x1b[1mSuccessfully proved game {:?} with tx {:?}\x1b[0m",
+            game_address,
+            receipt.transaction_hash
+        );
 
         Ok(())
     }
 
-    /// Handles the creation of a new game if conditions are met.
+    /// Returns the L2 block number of the latest valid proposal, and the index of its game.
+    ///
+    /// Without a valid proposal (first game or all existing games being faulty), this is the
+    /// anchor L2 block number and u32::MAX (special value indicating no parent).
+    async fn latest_proposal(&self) -> Result<(U256, u32)> {
+        match self
+            .factory
+            .get_latest_valid_proposal(self.l2_provider.clone())
+            .await?
+        {
+            Some((latest_block, latest_game_idx)) => {
+                Ok((latest_block, latest_game_idx.to::<u32>()))
+            }
+            None => {
+                let anchor_l2_block_number = self
+                    .factory
+                    .get_anchor_l2_block_number(self.config.game_type)
+                    .await?;
+                tracing::info!("Anchor L2 block number: {:?}", anchor_l2_block_number);
+                Ok((anchor_l2_block_number, u32::MAX))
+            }
+        }
+    }
+
+    /// Feeds the lag between the latest proposed block and the L2 finalized head to the lag
+    /// controller, and returns the number of blocks in each game and the number of games to
+    /// propose in this interval.
+    async fn schedule(&self, latest_block: U256) -> Result<(u64, usize)> {
+        let Some(lag_controller) = &self.lag_controller else {
+            return Ok((self.config.proposal_interval_in_blocks, 1));
+        };
+
+        let finalized_timestamp = self
+            .l2_provider
+            .get_l2_block_by_number(BlockNumberOrTag::Finalized)
+            .await?
+            .header
+            .timestamp;
+        let latest_timestamp = self
+            .l2_provider
+            .get_l2_block_by_number(BlockNumberOrTag::Number(latest_block.to::<u64>()))
+            .await?
+            .header
+            .timestamp;
+        let lag_secs = finalized_timestamp.saturating_sub(latest_timestamp);
+
+        let mut lag_controller = lag_controller.lock().unwrap();
+        lag_controller.observe_lag(lag_secs);
+        tracing::info!(
+            "Finality lag: {}s, proposing up to {} games of {} blocks",
+            lag_secs,
+            lag_controller.concurrency(),
+            lag_controller.range_size()
+        );
+        Ok((lag_controller.range_size(), lag_controller.concurrency()))
+    }
+
+    /// Handles the creation of new games if conditions are met.
     async fn handle_game_creation(&self) -> Result<()> {
         let _span = tracing::info_span!("[[Proposing]]").entered();
 
@@ -257,50 +347,69 @@ where
             .number;
         tracing::debug!("Safe L2 head block number: {:?}", safe_l2_head_block_number);
 
-        // Get the latest valid proposal.
-        let latest_valid_proposal = self
-            .factory
-            .get_latest_valid_proposal(self.l2_provider.clone())
-            .await?;
+        // The next game proposes the latest valid proposal's block (or the anchor L2 block) plus
+        // the range size, with the latest valid game (or no game) as its parent. When targeting a
+        // finality lag, further games are chained onto the ones created in this interval.
+        let (mut latest_block, mut parent_game_index) = self.latest_proposal().await?;
+        let (range_size, concurrency) = self.schedule(latest_block).await?;
+
+        let mut games = Vec::new();
+        for _ in 0..concurrency {
+            let next_l2_block_number_for_proposal =
+                latest_block.checked_add(U256::from(range_size)).unwrap();
+
+            // There's always a new game to propose, as the chain is always moving forward from the genesis block set for the game type.
+            // Only create a new game if the safe L2 head block number is greater than the next L2 block number for proposal.
+            if U256::from(safe_l2_head_block_number) <= next_l2_block_number_for_proposal {
+                break;
+            }
 
-        // Determine next block number and parent game index.
-        //
-        // Two cases based on the result of `get_latest_valid_proposal`:
-        // 1. With existing valid proposal:
-        //    - Block number = latest valid proposal's block + proposal interval.
-        //    - Parent = latest valid game's index.
-        //
-        // 2. Without valid proposal (first game or all existing games being faulty):
-        //    - Block number = anchor L2 block number + proposal interval.
-        //    - Parent = u32::MAX (special value indicating no parent).
-        let (next_l2_block_number_for_proposal, parent_game_index) = match latest_valid_proposal {
-            Some((latest_block, latest_game_idx)) => (
-                latest_block + U256::from(self.config.proposal_interval_in_blocks),
-                latest_game_idx.to::<u32>(),
-            ),
-            None => {
-                let anchor_l2_block_number = self
+            let game_address = self
+                .create_game(next_l2_block_number_for_proposal, parent_game_index)
+                .await?;
+            games.push((
+                game_address,
+                latest_block.to::<u64>(),
+                next_l2_block_number_for_proposal.to::<u64>(),
+            ));
+
+            if games.len() < concurrency {
+                parent_game_index = self
                     .factory
-                    .get_anchor_l2_block_number(self.config.game_type)
-                    .await?;
-                tracing::info!("Anchor L2 block number: {:?}", anchor_l2_block_number);
-                (
-                    anchor_l2_block_number
-                        .checked_add(U256::from(self.config.proposal_interval_in_blocks))
-                        .unwrap(),
-                    u32::MAX,
-                )
+                    .fetch_latest_game_index()
+                    .await?
+                    .context("No game after creating one")?
+                    .to::<u32>();
+                latest_block = next_l2_block_number_for_proposal;
             }
-        };
+        }
 
-        // There's always a new game to propose, as the chain is always moving forward from the genesis block set for the game type.
-        // Only create a new game if the safe L2 head block number is greater than the next L2 block number for proposal.
-        if U256::from(safe_l2_head_block_number) > next_l2_block_number_for_proposal {
-            self.create_game(next_l2_block_number_for_proposal, parent_game_index)
-                .await?;
+        if !self.config.fast_finality_mode {
+            return Ok(());
         }
 
-        Ok(())
+        let results = join_all(
+            games
+                .into_iter()
+                .map(|(game_address, start, end)| async move {
+                    let started = Instant::now();
+                    let result = self.prove_game(game_address, start, end).await;
+                    if let Err(e) = &result {
+                        tracing::warn!("Failed to prove game {:?}: {:?}", game_address, e);
+                    }
+                    (result, started.elapsed())
+                }),
+        )
+        .await;
+
+        if let Some(lag_controller) = &self.lag_controller {
+            let mut lag_controller = lag_controller.lock().unwrap();
+            for (result, elapsed) in &results {
+                lag_controller.observe_proof(result.as_ref().ok().map(|_| *elapsed));
+            }
+        }
+
+        results.into_iter().map(|(result, _)| result).collect()
     }
 
     /// Handles the resolution of all eligible unchallenged games.
@@ -40,12 +40,33 @@ pub struct ProposerConfig {
     /// When game resolution is enabled, the proposer will attempt to resolve games that are
     /// unchallenged up to `max_games_to_check_for_resolution` games behind the latest game.
     pub max_games_to_check_for_resolution: u64,
+
+    /// The target lag in seconds between the latest proposed block and the L2 finalized head.
+    /// When set, the proposer adjusts the number of blocks in each game, from
+    /// `proposal_interval_in_blocks` up to `max_proposal_interval_in_blocks`, and the number of
+    /// games proposed in each interval, up to `max_concurrent_proposals`, to stay within it.
+    pub target_finality_lag_secs: Option<u64>,
+
+    /// The largest number of blocks in one game when targeting a finality lag.
+    pub max_proposal_interval_in_blocks: u64,
+
+    /// The most games proposed in one interval when targeting a finality lag. In fast finality
+    /// mode, their proofs are generated concurrently.
+    pub max_concurrent_proposals: usize,
+
+    /// In fast finality mode, a proof that takes longer than this many seconds, or fails, means
+    /// the prover network is congested, and the proposer backs off to fewer, larger games.
+    pub proof_congestion_threshold_secs: u64,
 }
 
 impl ProposerConfig {
     pub fn from_env() -> Result<Self> {
         dotenv::from_filename(".env.proposer").ok();
 
+        let proposal_interval_in_blocks = env::var("PROPOSAL_INTERVAL_IN_BLOCKS")
+            .unwrap_or("1800".to_string())
+            .parse()?;
+
         Ok(Self {
             l1_rpc: env::var("L1_RPC")?.parse().expect("L1_RPC not set"),
             l2_rpc: env::var("L2_RPC")?.parse().expect("L2_RPC not set"),
@@ -55,9 +76,7 @@ impl ProposerConfig {
             fast_finality_mode: env::var("FAST_FINALITY_MODE")
                 .unwrap_or("false".to_string())
                 .parse()?,
-            proposal_interval_in_blocks: env::var("PROPOSAL_INTERVAL_IN_BLOCKS")
-                .unwrap_or("1800".to_string())
-                .parse()?,
+            proposal_interval_in_blocks,
             fetch_interval: env::var("FETCH_INTERVAL")
                 .unwrap_or("30".to_string())
                 .parse()?,
@@ -68,6 +87,20 @@ impl ProposerConfig {
             max_games_to_check_for_resolution: env::var("MAX_GAMES_TO_CHECK_FOR_RESOLUTION")
                 .unwrap_or("100".to_string())
                 .parse()?,
+            target_finality_lag_secs: env::var("TARGET_FINALITY_LAG_SECS")
+                .ok()
+                .map(|lag| lag.parse())
+                .transpose()?,
+            max_proposal_interval_in_blocks: match env::var("MAX_PROPOSAL_INTERVAL_IN_BLOCKS") {
+                Ok(max) => max.parse()?,
+                Err(_) => proposal_interval_in_blocks * 4,
+            },
+            max_concurrent_proposals: env::var("MAX_CONCURRENT_PROPOSALS")
+                .unwrap_or("4".to_string())
+                .parse()?,
+            proof_congestion_threshold_secs: env::var("PROOF_CONGESTION_THRESHOLD_SECS")
+                .unwrap_or("3600".to_string())
+                .parse()?,
         })
     }
 }
@@ -116,12 +116,4 @@ sol! {
         ProposalStatus status;
         Timestamp deadline;
     }
-
-    /// @notice The `L2Output` struct represents the L2 output.
-    struct L2Output {
-        uint64 zero;
-        bytes32 l2_state_root;
-        bytes32 l2_storage_hash;
-        bytes32 l2_claim_hash;
-    }
 }
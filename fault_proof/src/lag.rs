@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+/// Settings for keeping the proposer within a target lag of the L2 finalized head.
+#[derive(Debug, Clone)]
+pub struct LagTarget {
+    /// The target lag in seconds between the latest proposed block and the L2 finalized head.
+    pub target_lag_secs: u64,
+    /// The smallest number of blocks proposed in one game, and the one the proposer starts with.
+    pub min_range_size: u64,
+    /// The largest number of blocks proposed in one game.
+    pub max_range_size: u64,
+    /// The most games proposed, and proven in fast finality mode, in one interval.
+    pub max_concurrency: usize,
+    /// A proof that takes longer than this, or fails, means the prover network is congested.
+    pub congestion_threshold: Duration,
+}
+
+/// Adjusts the size of the proposed ranges and the number of games proposed at once, to keep the
+/// latest proposal within the target lag of the L2 finalized head.
+///
+/// While the proposer is behind the target, it first proposes more games at once, and once at the
+/// maximum concurrency, larger ranges. Once it is well within the target, it steps back down, first
+/// to smaller ranges. While the prover network is congested, concurrency is halved and catching up
+/// is done with larger ranges, so fewer proofs are requested.
+#[derive(Debug, Clone)]
+pub struct LagController {
+    target: LagTarget,
+    range_size: u64,
+    concurrency: usize,
+    congested: bool,
+}
+
+impl LagController {
+    pub fn new(target: LagTarget) -> Self {
+        Self {
+            range_size: target.min_range_size,
+            concurrency: 1,
+            congested: false,
+            target,
+        }
+    }
+
+    /// The number of blocks to propose in each game.
+    pub fn range_size(&self) -> u64 {
+        self.range_size
+    }
+
+    /// The number of games to propose in one interval.
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Adjust to the lag in seconds between the latest proposed block and the L2 finalized head.
+    pub fn observe_lag(&mut self, lag_secs: u64) {
+        let target = &self.target;
+        if lag_secs > target.target_lag_secs {
+            if !self.congested && self.concurrency < target.max_concurrency {
+                self.concurrency += 1;
+            } else {
+                self.range_size = (self.range_size * 2).min(target.max_range_size);
+            }
+        } else if lag_secs < target.target_lag_secs / 2 {
+            if self.range_size > target.min_range_size {
+                self.range_size = (self.range_size / 2).max(target.min_range_size);
+            } else if self.concurrency > 1 {
+                self.concurrency -= 1;
+            }
+        }
+    }
+
+    /// Record how long a proof took, or `None` if it failed.
+    pub fn observe_proof(&mut self, duration: Option<Duration>) {
+        self.congested =
+            duration.is_none_or(|duration| duration > self.target.congestion_threshold);
+        if self.congested {
+            self.concurrency = (self.concurrency / 2).max(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controller() -> LagController {
+        LagController::new(LagTarget {
+            target_lag_secs: 600,
+            min_range_size: 100,
+            max_range_size: 800,
+            max_concurrency: 3,
+            congestion_threshold: Duration::from_secs(1800),
+        })
+    }
+
+    #[test]
+    fn test_catches_up_with_concurrency_then_range_size() {
+        let mut lag = controller();
+        assert_eq!((lag.concurrency(), lag.range_size()), (1, 100));
+
+        // At the target, nothing changes.
+        lag.observe_lag(600);
+        assert_eq!((lag.concurrency(), lag.range_size()), (1, 100));
+
+        lag.observe_lag(601);
+        lag.observe_lag(601);
+        assert_eq!((lag.concurrency(), lag.range_size()), (3, 100));
+        lag.observe_lag(601);
+        lag.observe_lag(601);
+        assert_eq!((lag.concurrency(), lag.range_size()), (3, 400));
+        lag.observe_lag(601);
+        lag.observe_lag(601);
+        assert_eq!((lag.concurrency(), lag.range_size()), (3, 800));
+    }
+
+    #[test]
+    fn test_steps_down_well_within_target() {
+        let mut lag = controller();
+        for _ in 0..4 {
+            lag.observe_lag(1000);
+        }
+        assert_eq!((lag.concurrency(), lag.range_size()), (3, 400));
+
+        // Between half the target and the target, nothing changes.
+        lag.observe_lag(300);
+        lag.observe_lag(599);
+        assert_eq!((lag.concurrency(), lag.range_size()), (3, 400));
+
+        // Well within the target, ranges shrink first, then concurrency.
+        lag.observe_lag(299);
+        assert_eq!((lag.concurrency(), lag.range_size()), (3, 200));
+        lag.observe_lag(0);
+        lag.observe_lag(0);
+        assert_eq!((lag.concurrency(), lag.range_size()), (2, 100));
+        lag.observe_lag(0);
+        lag.observe_lag(0);
+        assert_eq!((lag.concurrency(), lag.range_size()), (1, 100));
+    }
+
+    #[test]
+    fn test_congestion() {
+        let mut lag = controller();
+        lag.observe_lag(1000);
+        lag.observe_lag(1000);
+        assert_eq!((lag.concurrency(), lag.range_size()), (3, 100));
+
+        // A failed or slow proof halves concurrency, and catching up grows the ranges instead.
+        lag.observe_proof(None);
+        assert_eq!(lag.concurrency(), 1);
+        lag.observe_lag(1000);
+        assert_eq!((lag.concurrency(), lag.range_size()), (1, 200));
+        lag.observe_proof(Some(Duration::from_secs(1801)));
+        lag.observe_lag(1000);
+        assert_eq!((lag.concurrency(), lag.range_size()), (1, 400));
+
+        // A proof within the threshold clears the congestion.
+        lag.observe_proof(Some(Duration::from_secs(1800)));
+        lag.observe_lag(1000);
+        assert_eq!((lag.concurrency(), lag.range_size()), (2, 400));
+    }
+}
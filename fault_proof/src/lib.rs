@@ -1,24 +1,25 @@
 pub mod config;
 pub mod contract;
+pub mod lag;
 pub mod utils;
 
 use alloy_eips::BlockNumberOrTag;
 use alloy_network::Ethereum;
-use alloy_primitives::{address, keccak256, Address, FixedBytes, B256, U256};
+use alloy_primitives::{address, Address, FixedBytes, B256, U256};
 use alloy_provider::{
     fillers::{FillProvider, TxFiller},
     Provider, RootProvider,
 };
 use alloy_rpc_types_eth::Block;
-use alloy_sol_types::SolValue;
 use anyhow::{bail, Result};
 use async_trait::async_trait;
 use op_alloy_network::{primitives::BlockTransactionsKind, Optimism};
 use op_alloy_rpc_types::Transaction;
+use op_succinct_client_utils::types::{compute_output_root, OUTPUT_ROOT_VERSION_V0};
 use tokio::time::Duration;
 
 use crate::contract::{
-    AnchorStateRegistry, DisputeGameFactory::DisputeGameFactoryInstance, GameStatus, L2Output,
+    AnchorStateRegistry, DisputeGameFactory::DisputeGameFactoryInstance, GameStatus,
     OPSuccinctFaultDisputeGame, ProposalStatus,
 };
 
@@ -105,14 +106,12 @@ impl L2ProviderTrait for L2Provider {
             )
             .await?;
 
-        let l2_claim_encoded = L2Output {
-            zero: 0,
-            l2_state_root: l2_state_root.0.into(),
-            l2_storage_hash: l2_storage_root.0.into(),
-            l2_claim_hash: l2_claim_hash.0.into(),
-        };
-        let l2_output_root = keccak256(l2_claim_encoded.abi_encode());
-        Ok(l2_output_root)
+        Ok(compute_output_root(
+            OUTPUT_ROOT_VERSION_V0,
+            l2_state_root,
+            l2_storage_root,
+            l2_claim_hash,
+        ))
     }
 }
 
@@ -6,15 +6,24 @@ sp1_zkvm::entrypoint!(main);
 
 use alloy_consensus::Header;
 use alloy_primitives::B256;
-use alloy_sol_types::SolValue;
 use op_succinct_client_utils::{
     boot::BootInfoStruct,
-    types::{u32_to_u8, AggregationInputs, AggregationOutputs},
+    panic_report::install_panic_hook,
+    public_values::{
+        encode_aggregation_outputs, encode_range_public_values, CURRENT_PUBLIC_VALUES_VERSION,
+    },
+    types::{
+        checkpoint_overrides_hash, link_spans, range_vkey_commitment, AggregationInputs,
+        AggregationOutputs,
+    },
 };
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
 pub fn main() {
+    // Report panics to the host, which otherwise only sees that execution failed.
+    install_panic_hook(sp1_zkvm::io::write);
+
     // Read in the public values corresponding to each range proof.
     let agg_inputs = sp1_zkvm::io::read::<AggregationInputs>();
     // Note: The headers are in order from start to end. We use serde_cbor as bincode serialization
@@ -23,28 +32,51 @@ pub fn main() {
     let headers: Vec<Header> = serde_cbor::from_slice(&headers_bytes).unwrap();
     assert!(!agg_inputs.boot_infos.is_empty());
 
-    // Confirm that the boot infos are sequential.
+    // Confirm that the boot infos are sequential: the claimed block of the previous boot info must
+    // be the L2 output root of the current boot, unless a governance-approved checkpoint override
+    // bridges them. The overrides are committed to, so the L2OO only accepts the proof if they
+    // were approved.
+    let l2_pre_root = link_spans(&agg_inputs.boot_infos, &agg_inputs.checkpoint_overrides)
+        .unwrap_or_else(|e| panic!("{e}"));
+
+    // The rollup config must be the same for all the boot infos, to ensure they're from the same
+    // chain and span batch range.
     agg_inputs.boot_infos.windows(2).for_each(|pair| {
-        let (prev_boot_info, boot_info) = (&pair[0], &pair[1]);
+        assert_eq!(pair[0].rollupConfigHash, pair[1].rollupConfigHash);
+    });
 
-        // The claimed block of the previous boot info must be the L2 output root of the current
-        // boot.
-        assert_eq!(prev_boot_info.l2PostRoot, boot_info.l2PreRoot);
+    // With a secondary range program, every span is proven by both programs, which must be
+    // different.
+    if let Some(secondary_vkey) = &agg_inputs.secondary_multi_block_vkey {
+        assert_ne!(secondary_vkey, &agg_inputs.multi_block_vkey);
+    }
 
-        // The rollup config must be the same for all the boot infos, to ensure they're
-        // from the same chain and span batch range.
-        assert_eq!(prev_boot_info.rollupConfigHash, boot_info.rollupConfigHash);
-    });
+    if let Some(intermediate_outputs) = &agg_inputs.range_intermediate_outputs {
+        assert_eq!(intermediate_outputs.len(), agg_inputs.boot_infos.len());
+    }
 
-    // Verify each range program proof.
-    agg_inputs.boot_infos.iter().for_each(|boot_info| {
-        // In the range program, the public values digest is just the hash of the ABI encoded
-        // boot info.
-        let serialized_boot_info = bincode::serialize(&boot_info).unwrap();
-        let pv_digest = Sha256::digest(serialized_boot_info);
+    // Verify each range program proof. With a secondary range program, the proofs of each span are
+    // verified in order: first the range program's, then the secondary range program's.
+    for (index, boot_info) in agg_inputs.boot_infos.iter().enumerate() {
+        // The public values digest of a range proof is the hash of its encoded boot info, in the
+        // version the range program commits to, followed by its intermediate outputs if the range
+        // program commits to them.
+        let intermediate_outputs = agg_inputs
+            .range_intermediate_outputs
+            .as_ref()
+            .map(|intermediate_outputs| intermediate_outputs[index].as_slice());
+        let public_values = encode_range_public_values(
+            agg_inputs.range_public_values_version,
+            boot_info,
+            intermediate_outputs,
+        );
+        let pv_digest = Sha256::digest(public_values);
 
         sp1_lib::verify::verify_sp1_proof(&agg_inputs.multi_block_vkey, &pv_digest.into());
-    });
+        if let Some(secondary_vkey) = &agg_inputs.secondary_multi_block_vkey {
+            sp1_lib::verify::verify_sp1_proof(secondary_vkey, &pv_digest.into());
+        }
+    }
 
     // Create a map of each l1 head in the [`BootInfoStruct`]'s to booleans
     let mut l1_heads_map: HashMap<B256, bool> = agg_inputs
@@ -76,20 +108,23 @@ pub fn main() {
         );
     }
 
-    let first_boot_info = &agg_inputs.boot_infos[0];
     let last_boot_info = &agg_inputs.boot_infos[agg_inputs.boot_infos.len() - 1];
     // Consolidate the boot info into a single BootInfo struct that represents the range proven.
     let final_boot_info = BootInfoStruct {
-        // The first boot info's L2 output root is the L2 output root of the range.
-        l2PreRoot: first_boot_info.l2PreRoot,
+        // The first boot info's L2 output root is the L2 output root of the range, unless a
+        // checkpoint override bridges the gap from the latest output on-chain to it.
+        l2PreRoot: l2_pre_root,
         l2BlockNumber: last_boot_info.l2BlockNumber,
         l2PostRoot: last_boot_info.l2PostRoot,
         l1Head: agg_inputs.latest_l1_checkpoint_head,
         rollupConfigHash: last_boot_info.rollupConfigHash,
     };
 
-    // Convert the range vkey to a B256.
-    let multi_block_vkey_b256 = B256::from(u32_to_u8(agg_inputs.multi_block_vkey));
+    // Commit to the range vkey, or to both range vkeys with a secondary range program.
+    let multi_block_vkey_b256 = range_vkey_commitment(
+        agg_inputs.multi_block_vkey,
+        agg_inputs.secondary_multi_block_vkey,
+    );
 
     let agg_outputs = AggregationOutputs {
         l1Head: final_boot_info.l1Head,
@@ -98,8 +133,12 @@ pub fn main() {
         l2BlockNumber: final_boot_info.l2BlockNumber,
         rollupConfigHash: final_boot_info.rollupConfigHash,
         multiBlockVKey: multi_block_vkey_b256,
+        checkpointOverridesHash: checkpoint_overrides_hash(&agg_inputs.checkpoint_overrides),
     };
 
     // Commit to the aggregated [`AggregationOutputs`].
-    sp1_zkvm::io::commit_slice(&agg_outputs.abi_encode());
+    sp1_zkvm::io::commit_slice(&encode_aggregation_outputs(
+        CURRENT_PUBLIC_VALUES_VERSION,
+        &agg_outputs,
+    ));
 }
@@ -7,20 +7,48 @@
 //! the data is supplied by the host binary to the verifiable program.
 
 #![no_main]
+#[cfg(not(feature = "block-arena"))]
 sp1_zkvm::entrypoint!(main);
 
+// The same entrypoint as `sp1_zkvm::entrypoint!`, with the zkVM's allocator wrapped in a
+// block-scoped arena.
+#[cfg(feature = "block-arena")]
+#[global_allocator]
+static HEAP: op_succinct_client_utils::arena::BlockArena<sp1_zkvm::heap::SimpleAlloc> =
+    op_succinct_client_utils::arena::BlockArena::new(sp1_zkvm::heap::SimpleAlloc);
+
+#[cfg(feature = "block-arena")]
+mod zkvm_generated_main {
+    #[no_mangle]
+    fn main() {
+        super::main()
+    }
+}
+
 extern crate alloc;
 
 use alloc::sync::Arc;
 
+#[cfg(not(feature = "intermediate-outputs"))]
+use op_succinct_client_utils::client::run_opsuccinct_client;
+#[cfg(feature = "intermediate-outputs")]
+use op_succinct_client_utils::client::run_opsuccinct_client_with_intermediate_outputs;
 use op_succinct_client_utils::{
-    boot::BootInfoStruct, client::run_opsuccinct_client, precompiles::zkvm_handle_register,
+    block_on::block_on,
+    boot::BootInfoStruct,
+    panic_report::install_panic_hook,
+    precompiles::zkvm_handle_register,
+    public_values::{encode_range_public_values, CURRENT_PUBLIC_VALUES_VERSION},
+    witness::WitnessFormat,
 };
 
-use alloc::vec::Vec;
-use op_succinct_client_utils::InMemoryOracle;
-
 fn main() {
+    // Report panics to the host, which otherwise only sees that execution failed.
+    install_panic_hook(sp1_zkvm::io::write);
+
+    #[cfg(feature = "block-arena")]
+    op_succinct_client_utils::arena::set_end_block_hook(|| HEAP.end_block());
+
     #[cfg(feature = "tracing-subscriber")]
     {
         use anyhow::anyhow;
@@ -34,21 +62,53 @@ fn main() {
             .unwrap();
     }
 
-    kona_proof::block_on(async move {
+    block_on(async move {
         ////////////////////////////////////////////////////////////////
         //                          PROLOGUE                          //
         ////////////////////////////////////////////////////////////////
-        let in_memory_oracle_bytes: Vec<u8> = sp1_zkvm::io::read_vec();
-        let oracle = Arc::new(InMemoryOracle::from_raw_bytes(in_memory_oracle_bytes));
+        let witness_format =
+            WitnessFormat::from_tag(sp1_zkvm::io::read::<u8>()).expect("unknown witness format");
+        let oracle = Arc::new(
+            witness_format
+                .deserialize(sp1_zkvm::io::read_vec)
+                .expect("failed to deserialize"),
+        );
 
         println!("cycle-tracker-report-start: oracle-verify");
         oracle.verify().expect("key value verification failed");
         println!("cycle-tracker-report-end: oracle-verify");
 
-        let boot_info = run_opsuccinct_client(oracle, Some(zkvm_handle_register))
-            .await
-            .expect("failed to run client");
+        #[cfg(not(feature = "intermediate-outputs"))]
+        {
+            let boot_info = run_opsuccinct_client(oracle, Some(zkvm_handle_register))
+                .await
+                .expect("failed to run client");
+
+            sp1_zkvm::io::commit_slice(&encode_range_public_values(
+                CURRENT_PUBLIC_VALUES_VERSION,
+                &BootInfoStruct::from(boot_info),
+                None,
+            ));
+        }
+
+        // Additionally commit the output root of every block in the range, with the fields it
+        // commits to, after the boot info, so withdrawals can be proven against intermediate
+        // outputs.
+        #[cfg(feature = "intermediate-outputs")]
+        {
+            let (boot_info, intermediate_outputs) =
+                run_opsuccinct_client_with_intermediate_outputs(oracle, Some(zkvm_handle_register))
+                    .await
+                    .expect("failed to run client");
+
+            sp1_zkvm::io::commit_slice(&encode_range_public_values(
+                CURRENT_PUBLIC_VALUES_VERSION,
+                &BootInfoStruct::from(boot_info),
+                Some(&intermediate_outputs),
+            ));
+        }
 
-        sp1_zkvm::io::commit(&BootInfoStruct::from(boot_info));
+        #[cfg(feature = "block-arena")]
+        println!("block-arena: {:?}", HEAP.stats());
     });
 }
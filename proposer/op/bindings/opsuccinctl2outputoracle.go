@@ -55,35 +55,13 @@ type TypesOutputProposal struct {
 
 // OPSuccinctL2OutputOracleMetaData contains all meta data concerning the OPSuccinctL2OutputOracle contract.
 var OPSuccinctL2OutputOracleMetaData = &bind.MetaData{
-	ABI: "[{\"type\":\"constructor\",\"inputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"CHALLENGER\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"FINALIZATION_PERIOD_SECONDS\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"L2_BLOCK_TIME\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"PROPOSER\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"SUBMISSION_INTERVAL\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"addProposer\",\"inputs\":[{\"name\":\"_proposer\",\"type\":\"address\",\"internalType\":\"address\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"aggregationVkey\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"approvedProposers\",\"inputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"outputs\":[{\"name\":\"\",\"type\":\"bool\",\"internalType\":\"bool\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"challenger\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"checkpointBlockHash\",\"inputs\":[{\"name\":\"_blockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"computeL2Timestamp\",\"inputs\":[{\"name\":\"_l2BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"deleteL2Outputs\",\"inputs\":[{\"name\":\"_l2OutputIndex\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"finalizationPeriodSeconds\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"getL2Output\",\"inputs\":[{\"name\":\"_l2OutputIndex\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[{\"name\":\"\",\"type\":\"tuple\",\"internalType\":\"structTypes.OutputProposal\",\"components\":[{\"name\":\"outputRoot\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"timestamp\",\"type\":\"uint128\",\"internalType\":\"uint128\"},{\"name\":\"l2BlockNumber\",\"type\":\"uint128\",\"internalType\":\"uint128\"}]}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"getL2OutputAfter\",\"inputs\":[{\"name\":\"_l2BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[{\"name\":\"\",\"type\":\"tuple\",\"internalType\":\"structTypes.OutputProposal\",\"components\":[{\"name\":\"outputRoot\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"timestamp\",\"type\":\"uint128\",\"internalType\":\"uint128\"},{\"name\":\"l2BlockNumber\",\"type\":\"uint128\",\"internalType\":\"uint128\"}]}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"getL2OutputIndexAfter\",\"inputs\":[{\"name\":\"_l2BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"historicBlockHashes\",\"inputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[{\"name\":\"\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"initialize\",\"inputs\":[{\"name\":\"_initParams\",\"type\":\"tuple\",\"internalType\":\"structOPSuccinctL2OutputOracle.InitParams\",\"components\":[{\"name\":\"challenger\",\"type\":\"address\",\"internalType\":\"address\"},{\"name\":\"proposer\",\"type\":\"address\",\"internalType\":\"address\"},{\"name\":\"owner\",\"type\":\"address\",\"internalType\":\"address\"},{\"name\":\"finalizationPeriodSeconds\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"l2BlockTime\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"aggregationVkey\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"rangeVkeyCommitment\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"rollupConfigHash\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"startingOutputRoot\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"startingBlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"startingTimestamp\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"submissionInterval\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"verifier\",\"type\":\"address\",\"internalType\":\"address\"}]}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"initializerVersion\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint8\",\"internalType\":\"uint8\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"l2BlockTime\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"latestBlockNumber\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"latestOutputIndex\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"nextBlockNumber\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"nextOutputIndex\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"owner\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"proposeL2Output\",\"inputs\":[{\"name\":\"_outputRoot\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"_l2BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"_l1BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"_proof\",\"type\":\"bytes\",\"internalType\":\"bytes\"}],\"outputs\":[],\"stateMutability\":\"payable\"},{\"type\":\"function\",\"name\":\"proposer\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"rangeVkeyCommitment\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"removeProposer\",\"inputs\":[{\"name\":\"_proposer\",\"type\":\"address\",\"internalType\":\"address\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"rollupConfigHash\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"startingBlockNumber\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"startingTimestamp\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"submissionInterval\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"transferOwnership\",\"inputs\":[{\"name\":\"_owner\",\"type\":\"address\",\"internalType\":\"address\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"updateAggregationVkey\",\"inputs\":[{\"name\":\"_aggregationVkey\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"updateRangeVkeyCommitment\",\"inputs\":[{\"name\":\"_rangeVkeyCommitment\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"updateRollupConfigHash\",\"inputs\":[{\"name\":\"_rollupConfigHash\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"updateSubmissionInterval\",\"inputs\":[{\"name\":\"_submissionInterval\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"updateVerifier\",\"inputs\":[{\"name\":\"_verifier\",\"type\":\"address\",\"internalType\":\"address\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"verifier\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"version\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"string\",\"internalType\":\"string\"}],\"stateMutability\":\"view\"},{\"type\":\"event\",\"name\":\"AggregationVkeyUpdated\",\"inputs\":[{\"name\":\"oldAggregationVkey\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"},{\"name\":\"newAggregationVkey\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"Initialized\",\"inputs\":[{\"name\":\"version\",\"type\":\"uint8\",\"indexed\":false,\"internalType\":\"uint8\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"OutputProposed\",\"inputs\":[{\"name\":\"outputRoot\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"},{\"name\":\"l2OutputIndex\",\"type\":\"uint256\",\"indexed\":true,\"internalType\":\"uint256\"},{\"name\":\"l2BlockNumber\",\"type\":\"uint256\",\"indexed\":true,\"internalType\":\"uint256\"},{\"name\":\"l1Timestamp\",\"type\":\"uint256\",\"indexed\":false,\"internalType\":\"uint256\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"OutputsDeleted\",\"inputs\":[{\"name\":\"prevNextOutputIndex\",\"type\":\"uint256\",\"indexed\":true,\"internalType\":\"uint256\"},{\"name\":\"newNextOutputIndex\",\"type\":\"uint256\",\"indexed\":true,\"internalType\":\"uint256\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"OwnershipTransferred\",\"inputs\":[{\"name\":\"previousOwner\",\"type\":\"address\",\"indexed\":true,\"internalType\":\"address\"},{\"name\":\"newOwner\",\"type\":\"address\",\"indexed\":true,\"internalType\":\"address\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"ProposerUpdated\",\"inputs\":[{\"name\":\"proposer\",\"type\":\"address\",\"indexed\":true,\"internalType\":\"address\"},{\"name\":\"added\",\"type\":\"bool\",\"indexed\":false,\"internalType\":\"bool\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"RangeVkeyCommitmentUpdated\",\"inputs\":[{\"name\":\"oldRangeVkeyCommitment\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"},{\"name\":\"newRangeVkeyCommitment\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"RollupConfigHashUpdated\",\"inputs\":[{\"name\":\"oldRollupConfigHash\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"},{\"name\":\"newRollupConfigHash\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"SubmissionIntervalUpdated\",\"inputs\":[{\"name\":\"oldSubmissionInterval\",\"type\":\"uint256\",\"indexed\":false,\"internalType\":\"uint256\"},{\"name\":\"newSubmissionInterval\",\"type\":\"uint256\",\"indexed\":false,\"internalType\":\"uint256\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"VerifierUpdated\",\"inputs\":[{\"name\":\"oldVerifier\",\"type\":\"address\",\"indexed\":true,\"internalType\":\"address\"},{\"name\":\"newVerifier\",\"type\":\"address\",\"indexed\":true,\"internalType\":\"address\"}],\"anonymous\":false},{\"type\":\"error\",\"name\":\"L1BlockHashNotAvailable\",\"inputs\":[]},{\"type\":\"error\",\"name\":\"L1BlockHashNotCheckpointed\",\"inputs\":[]}]",
-	Bin: "0x608060405234801561001057600080fd5b5061001961001e565b6100de565b600054610100900460ff161561008a5760405162461bcd60e51b815260206004820152602760248201527f496e697469616c697a61626c653a20636f6e747261637420697320696e697469604482015266616c697a696e6760c81b606482015260840160405180910390fd5b60005460ff90811610156100dc576000805460ff191660ff9081179091556040519081527f7f26b83ff96e1f2b6a682f133852f6798a09c465da95921460cefb38474024989060200160405180910390a15b565b611c57806100ed6000396000f3fe6080604052600436106102455760003560e01c80638da5cb5b11610139578063c32e4e3e116100b6578063d46512761161007a578063d4651276146106b0578063db1470f5146106f0578063dcec334814610710578063e1a41bcf14610725578063f2fde38b1461073b578063f4daa2911461075b57600080fd5b8063c32e4e3e14610624578063c4cb03ec1461063a578063ce5db8d61461065a578063cf8e5cf014610670578063d1de856c1461069057600080fd5b8063a25ae557116100fd578063a25ae55714610553578063a8e4fb90146105a6578063b03cd418146105c6578063bc91ce33146105e6578063bffa7f0f1461060657600080fd5b80638da5cb5b146104bd57806393991af3146104dd57806397fc007c146104f35780639ad8488014610513578063a196b5251461052657600080fd5b806354fd4d50116101c757806370872aa51161018b57806370872aa51461042a5780637f006420146104405780637f01ea6814610460578063887862721461048757806389c44cbb1461049d57600080fd5b806354fd4d501461038857806369f16eec146103cc5780636abcf563146103e15780636b4d98dd146103f65780636d9a1c8b1461041457600080fd5b80632b7ac3f31161020e5780632b7ac3f3146102e6578063336c9e811461031e5780634599c7881461033e578063529933df14610353578063534db0e21461036857600080fd5b80622134cc1461024a57806309d632d31461026e5780631bdd450c146102905780631e856800146102b05780632b31841e146102d0575b600080fd5b34801561025657600080fd5b506005545b6040519081526020015b60405180910390f35b34801561027a57600080fd5b5061028e610289366004611888565b610770565b005b34801561029c57600080fd5b5061028e6102ab3660046118aa565b6107f9565b3480156102bc57600080fd5b5061028e6102cb3660046118aa565b610857565b3480156102dc57600080fd5b5061025b600a5481565b3480156102f257600080fd5b50600b54610306906001600160a01b031681565b6040516001600160a01b039091168152602001610265565b34801561032a57600080fd5b5061028e6103393660046118aa565b610889565b34801561034a57600080fd5b5061025b6108f4565b34801561035f57600080fd5b5060045461025b565b34801561037457600080fd5b50600654610306906001600160a01b031681565b34801561039457600080fd5b506103bf6040518060400160405280600b81526020016a0626574612d76312e302e360ac1b81525081565b6040516102659190611910565b3480156103d857600080fd5b5061025b610951565b3480156103ed57600080fd5b5060035461025b565b34801561040257600080fd5b506006546001600160a01b0316610306565b34801561042057600080fd5b5061025b600c5481565b34801561043657600080fd5b5061025b60015481565b34801561044c57600080fd5b5061025b61045b3660046118aa565b610963565b34801561046c57600080fd5b50610475600181565b60405160ff9091168152602001610265565b34801561049357600080fd5b5061025b60025481565b3480156104a957600080fd5b5061028e6104b83660046118aa565b610b01565b3480156104c957600080fd5b50600d54610306906001600160a01b031681565b3480156104e957600080fd5b5061025b60055481565b3480156104ff57600080fd5b5061028e61050e366004611888565b610d06565b61028e610521366004611994565b610d8c565b34801561053257600080fd5b5061025b6105413660046118aa565b600f6020526000908152604090205481565b34801561055f57600080fd5b5061057361056e3660046118aa565b6111fe565b60408051825181526020808401516001600160801b03908116918301919091529282015190921690820152606001610265565b3480156105b257600080fd5b50600754610306906001600160a01b031681565b3480156105d257600080fd5b5061028e6105e1366004611888565b61127c565b3480156105f257600080fd5b5061028e6106013660046118aa565b6112fd565b34801561061257600080fd5b506007546001600160a01b0316610306565b34801561063057600080fd5b5061025b60095481565b34801561064657600080fd5b5061028e6106553660046118aa565b61135b565b34801561066657600080fd5b5061025b60085481565b34801561067c57600080fd5b5061057361068b3660046118aa565b6113b9565b34801561069c57600080fd5b5061025b6106ab3660046118aa565b6113f1565b3480156106bc57600080fd5b506106e06106cb366004611888565b600e6020526000908152604090205460ff1681565b6040519015158152602001610265565b3480156106fc57600080fd5b5061028e61070b366004611a46565b611421565b34801561071c57600080fd5b5061025b6117cf565b34801561073157600080fd5b5061025b60045481565b34801561074757600080fd5b5061028e610756366004611888565b6117e6565b34801561076757600080fd5b5060085461025b565b600d546001600160a01b031633146107a35760405162461bcd60e51b815260040161079a90611b09565b60405180910390fd5b6001600160a01b0381166000818152600e60209081526040808320805460ff19169055519182527f5df38d395edc15b669d646569bd015513395070b5b4deb8a16300abb060d1b5a91015b60405180910390a250565b600d546001600160a01b031633146108235760405162461bcd60e51b815260040161079a90611b09565b600c546040518291907f5d9ebe9f09b0810b3546b30781ba9a51092b37dd6abada4b830ce54a41ac6a4b90600090a3600c55565b804080610877576040516321301a1960e21b815260040160405180910390fd5b6000918252600f602052604090912055565b600d546001600160a01b031633146108b35760405162461bcd60e51b815260040161079a90611b09565b60045460408051918252602082018390527fc1bf9abfb57ea01ed9ecb4f45e9cefa7ba44b2e6778c3ce7281409999f1af1b2910160405180910390a1600455565b60035460009015610948576003805461090f90600190611b66565b8154811061091f5761091f611b7d565b6000918252602090912060029091020160010154600160801b90046001600160801b0316919050565b6001545b905090565b60035460009061094c90600190611b66565b600061096d6108f4565b8211156109f35760405162461bcd60e51b815260206004820152604860248201527f4c324f75747075744f7261636c653a2063616e6e6f7420676574206f7574707560448201527f7420666f72206120626c6f636b207468617420686173206e6f74206265656e206064820152671c1c9bdc1bdcd95960c21b608482015260a40161079a565b600354610a775760405162461bcd60e51b815260206004820152604660248201527f4c324f75747075744f7261636c653a2063616e6e6f7420676574206f7574707560448201527f74206173206e6f206f7574707574732068617665206265656e2070726f706f736064820152651959081e595d60d21b608482015260a40161079a565b6003546000905b80821015610afa5760006002610a948385611b93565b610a9e9190611bab565b90508460038281548110610ab457610ab4611b7d565b6000918252602090912060029091020160010154600160801b90046001600160801b03161015610af057610ae9816001611b93565b9250610af4565b8091505b50610a7e565b5092915050565b6006546001600160a01b03163314610b815760405162461bcd60e51b815260206004820152603e60248201527f4c324f75747075744f7261636c653a206f6e6c7920746865206368616c6c656e60448201527f67657220616464726573732063616e2064656c657465206f7574707574730000606482015260840161079a565b6003548110610c045760405162461bcd60e51b815260206004820152604360248201527f4c324f75747075744f7261636c653a2063616e6e6f742064656c657465206f7560448201527f747075747320616674657220746865206c6174657374206f757470757420696e6064820152620c8caf60eb1b608482015260a40161079a565b60085460038281548110610c1a57610c1a611b7d565b6000918252602090912060016002909202010154610c41906001600160801b031642611b66565b10610cc35760405162461bcd60e51b815260206004820152604660248201527f4c324f75747075744f7261636c653a2063616e6e6f742064656c657465206f7560448201527f74707574732074686174206861766520616c7265616479206265656e2066696e606482015265185b1a5e995960d21b608482015260a40161079a565b6000610cce60035490565b90508160035581817f4ee37ac2c786ec85e87592d3c5c8a1dd66f8496dda3f125d9ea8ca5f657629b660405160405180910390a35050565b600d546001600160a01b03163314610d305760405162461bcd60e51b815260040161079a90611b09565b600b546040516001600160a01b038084169216907f0243549a92b2412f7a3caf7a2e56d65b8821b91345363faa5f57195384065fcc90600090a3600b80546001600160a01b0319166001600160a01b0392909216919091179055565b336000908152600e602052604090205460ff1680610dd4575060008052600e6020527fe710864318d4a32f37d6ce54cb3fadbef648dd12d8dbdf53973564d56b7f881c5460ff165b610e465760405162461bcd60e51b815260206004820152603f60248201527f4c324f75747075744f7261636c653a206f6e6c7920617070726f76656420707260448201527f6f706f736572732063616e2070726f706f7365206e6577206f75747075747300606482015260840161079a565b610e4e6117cf565b831015610ee95760405162461bcd60e51b815260206004820152605860248201527f4c324f75747075744f7261636c653a20626c6f636b206e756d626572206d757360448201527f742062652067726561746572207468616e206f7220657175616c20746f206e6560648201527f787420657870656374656420626c6f636b206e756d6265720000000000000000608482015260a40161079a565b42610ef3846113f1565b10610f5f5760405162461bcd60e51b815260206004820152603660248201527f4c324f75747075744f7261636c653a2063616e6e6f742070726f706f7365204c60448201527532206f757470757420696e207468652066757475726560501b606482015260840161079a565b83610fd25760405162461bcd60e51b815260206004820152603a60248201527f4c324f75747075744f7261636c653a204c32206f75747075742070726f706f7360448201527f616c2063616e6e6f7420626520746865207a65726f2068617368000000000000606482015260840161079a565b6000828152600f602052604090205480610fff57604051630455475360e31b815260040160405180910390fd5b60006040518060c00160405280838152602001600361101c610951565b8154811061102c5761102c611b7d565b60009182526020918290206002909102015482528181018990526040808301899052600c54606080850191909152600a54608094850152600b5460095483518751818701529487015185850152928601518483015290850151838501529284015160a08084019190915284015160c08301529293506001600160a01b03909116916341493c609160e001604051602081830303815290604052866040518463ffffffff1660e01b81526004016110e493929190611bcd565b60006040518083038186803b1580156110fc57600080fd5b505afa158015611110573d6000803e3d6000fd5b505050508461111e60035490565b877fa7aaf2512769da4e444e3de247be2564225c2e7a8f74cfe528e46e17d24868e24260405161115091815260200190565b60405180910390a45050604080516060810182529485526001600160801b034281166020870190815294811691860191825260038054600181018255600091909152955160029096027fc2575a0e9e593c00f959f8c92f12db2869c3395a3b0502d05e2516446f71f85b810196909655935190518416600160801b029316929092177fc2575a0e9e593c00f959f8c92f12db2869c3395a3b0502d05e2516446f71f85c909301929092555050565b60408051606081018252600080825260208201819052918101919091526003828154811061122e5761122e611b7d565b600091825260209182902060408051606081018252600290930290910180548352600101546001600160801b0380821694840194909452600160801b90049092169181019190915292915050565b600d546001600160a01b031633146112a65760405162461bcd60e51b815260040161079a90611b09565b6001600160a01b0381166000818152600e6020908152604091829020805460ff1916600190811790915591519182527f5df38d395edc15b669d646569bd015513395070b5b4deb8a16300abb060d1b5a91016107ee565b600d546001600160a01b031633146113275760405162461bcd60e51b815260040161079a90611b09565b600a546040518291907fbf8cab6317796bfa97fea82b6d27c9542a08fa0821813cf2a57e7cff7fdc815690600090a3600a55565b600d546001600160a01b031633146113855760405162461bcd60e51b815260040161079a90611b09565b6009546040518291907f390b73b2b067afcef04d30b573e4590c6e565519e370927dd777ca0ce8a55db090600090a3600955565b604080516060810182526000808252602082018190529181019190915260036113e183610963565b8154811061122e5761122e611b7d565b6000600554600154836114049190611b66565b61140e9190611c02565b60025461141b9190611b93565b92915050565b600054600190610100900460ff16158015611443575060005460ff8083169116105b6114a65760405162461bcd60e51b815260206004820152602e60248201527f496e697469616c697a61626c653a20636f6e747261637420697320616c72656160448201526d191e481a5b9a5d1a585b1a5e995960921b606482015260840161079a565b6000805461ffff191660ff8316176101001790556101608201516115325760405162461bcd60e51b815260206004820152603a60248201527f4c324f75747075744f7261636c653a207375626d697373696f6e20696e74657260448201527f76616c206d7573742062652067726561746572207468616e2030000000000000606482015260840161079a565b60008260800151116115a35760405162461bcd60e51b815260206004820152603460248201527f4c324f75747075744f7261636c653a204c3220626c6f636b2074696d65206d75604482015273073742062652067726561746572207468616e20360641b606482015260840161079a565b42826101400151111561162c5760405162461bcd60e51b8152602060048201526044602482018190527f4c324f75747075744f7261636c653a207374617274696e67204c322074696d65908201527f7374616d70206d757374206265206c657373207468616e2063757272656e742060648201526374696d6560e01b608482015260a40161079a565b610160820151600455608082015160055560035460000361170357604080516060810182526101008401518152610140840180516001600160801b03908116602084019081526101208701805183169585019586526003805460018181018355600092909252955160029687027fc2575a0e9e593c00f959f8c92f12db2869c3395a3b0502d05e2516446f71f85b810191909155925196518416600160801b0296909316959095177fc2575a0e9e593c00f959f8c92f12db2869c3395a3b0502d05e2516446f71f85c909101559251909255905190555b8151600680546001600160a01b03199081166001600160a01b0393841617909155606084015160085560208085015183166000908152600e82526040808220805460ff1916600117905560a087015160095560c0870151600a55610180870151600b8054861691871691909117905560e0870151600c5580870151600d8054909516951694909417909255815461ff001916909155905160ff831681527f7f26b83ff96e1f2b6a682f133852f6798a09c465da95921460cefb3847402498910160405180910390a15050565b60006004546117dc6108f4565b61094c9190611b93565b600d546001600160a01b031633146118105760405162461bcd60e51b815260040161079a90611b09565b600d546040516001600160a01b038084169216907f8be0079c531659141344cd1fd0a4f28419497f9722a3daafe3b4186f6b6457e090600090a3600d80546001600160a01b0319166001600160a01b0392909216919091179055565b80356001600160a01b038116811461188357600080fd5b919050565b60006020828403121561189a57600080fd5b6118a38261186c565b9392505050565b6000602082840312156118bc57600080fd5b5035919050565b6000815180845260005b818110156118e9576020818501810151868301820152016118cd565b818111156118fb576000602083870101525b50601f01601f19169290920160200192915050565b6020815260006118a360208301846118c3565b634e487b7160e01b600052604160045260246000fd5b6040516101a0810167ffffffffffffffff8111828210171561195d5761195d611923565b60405290565b604051601f8201601f1916810167ffffffffffffffff8111828210171561198c5761198c611923565b604052919050565b600080600080608085870312156119aa57600080fd5b84359350602080860135935060408601359250606086013567ffffffffffffffff808211156119d857600080fd5b818801915088601f8301126119ec57600080fd5b8135818111156119fe576119fe611923565b611a10601f8201601f19168501611963565b91508082528984828501011115611a2657600080fd5b808484018584013760008482840101525080935050505092959194509250565b60006101a08284031215611a5957600080fd5b611a61611939565b611a6a8361186c565b8152611a786020840161186c565b6020820152611a896040840161186c565b6040820152606083013560608201526080830135608082015260a083013560a082015260c083013560c082015260e083013560e0820152610100808401358183015250610120808401358183015250610140808401358183015250610160808401358183015250610180611afe81850161186c565b908201529392505050565b60208082526027908201527f4c324f75747075744f7261636c653a2063616c6c6572206973206e6f74207468604082015266329037bbb732b960c91b606082015260800190565b634e487b7160e01b600052601160045260246000fd5b600082821015611b7857611b78611b50565b500390565b634e487b7160e01b600052603260045260246000fd5b60008219821115611ba657611ba6611b50565b500190565b600082611bc857634e487b7160e01b600052601260045260246000fd5b500490565b838152606060208201526000611be660608301856118c3565b8281036040840152611bf881856118c3565b9695505050505050565b6000816000190483118215151615611c1c57611c1c611b50565b50029056fea2646970667358221220960a86e7fc8731a6278de5d7bf30cbec12bba95789c30f9d10a7cbc950c3179364736f6c634300080f0033",
+	ABI: "[{\"type\":\"constructor\",\"inputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"CHALLENGER\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"FINALIZATION_PERIOD_SECONDS\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"L2_BLOCK_TIME\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"PROPOSER\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"SUBMISSION_INTERVAL\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"addProposer\",\"inputs\":[{\"name\":\"_proposer\",\"type\":\"address\",\"internalType\":\"address\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"aggregationOutputsVersion\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint8\",\"internalType\":\"uint8\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"aggregationVkey\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"approveCheckpointOverrides\",\"inputs\":[{\"name\":\"_checkpointOverridesHash\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"_justification\",\"type\":\"string\",\"internalType\":\"string\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"approvedCheckpointOverrides\",\"inputs\":[{\"name\":\"\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"outputs\":[{\"name\":\"\",\"type\":\"bool\",\"internalType\":\"bool\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"approvedProposers\",\"inputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"outputs\":[{\"name\":\"\",\"type\":\"bool\",\"internalType\":\"bool\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"challenger\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"checkpointBlockHash\",\"inputs\":[{\"name\":\"_blockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"computeL2Timestamp\",\"inputs\":[{\"name\":\"_l2BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"deleteL2Outputs\",\"inputs\":[{\"name\":\"_l2OutputIndex\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"disableOptimisticMode\",\"inputs\":[{\"name\":\"_finalizationPeriodSeconds\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"enableOptimisticMode\",\"inputs\":[{\"name\":\"_finalizationPeriodSeconds\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"finalizationPeriodSeconds\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"getL2Output\",\"inputs\":[{\"name\":\"_l2OutputIndex\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[{\"name\":\"\",\"type\":\"tuple\",\"internalType\":\"structTypes.OutputProposal\",\"components\":[{\"name\":\"outputRoot\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"timestamp\",\"type\":\"uint128\",\"internalType\":\"uint128\"},{\"name\":\"l2BlockNumber\",\"type\":\"uint128\",\"internalType\":\"uint128\"}]}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"getL2OutputAfter\",\"inputs\":[{\"name\":\"_l2BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[{\"name\":\"\",\"type\":\"tuple\",\"internalType\":\"structTypes.OutputProposal\",\"components\":[{\"name\":\"outputRoot\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"timestamp\",\"type\":\"uint128\",\"internalType\":\"uint128\"},{\"name\":\"l2BlockNumber\",\"type\":\"uint128\",\"internalType\":\"uint128\"}]}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"getL2OutputIndexAfter\",\"inputs\":[{\"name\":\"_l2BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"historicBlockHashes\",\"inputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[{\"name\":\"\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"initialize\",\"inputs\":[{\"name\":\"_initParams\",\"type\":\"tuple\",\"internalType\":\"structOPSuccinctL2OutputOracle.InitParams\",\"components\":[{\"name\":\"challenger\",\"type\":\"address\",\"internalType\":\"address\"},{\"name\":\"proposer\",\"type\":\"address\",\"internalType\":\"address\"},{\"name\":\"owner\",\"type\":\"address\",\"internalType\":\"address\"},{\"name\":\"finalizationPeriodSeconds\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"l2BlockTime\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"aggregationVkey\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"rangeVkeyCommitment\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"rollupConfigHash\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"startingOutputRoot\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"startingBlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"startingTimestamp\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"submissionInterval\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"verifier\",\"type\":\"address\",\"internalType\":\"address\"}]}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"initializerVersion\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint8\",\"internalType\":\"uint8\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"l2BlockTime\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"latestBlockNumber\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"latestOutputIndex\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"multicall\",\"inputs\":[{\"name\":\"data\",\"type\":\"bytes[]\",\"internalType\":\"bytes[]\"}],\"outputs\":[{\"name\":\"results\",\"type\":\"bytes[]\",\"internalType\":\"bytes[]\"}],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"nextBlockNumber\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"nextOutputIndex\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"optimisticMode\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"bool\",\"internalType\":\"bool\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"owner\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"proposeL2Output\",\"inputs\":[{\"name\":\"_outputRoot\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"_l2BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"_l1BlockHash\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"_l1BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[],\"stateMutability\":\"payable\"},{\"type\":\"function\",\"name\":\"proposeL2Output\",\"inputs\":[{\"name\":\"_outputRoot\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"_l2BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"_l1BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"_proof\",\"type\":\"bytes\",\"internalType\":\"bytes\"}],\"outputs\":[],\"stateMutability\":\"payable\"},{\"type\":\"function\",\"name\":\"proposeL2OutputWithCheckpointOverrides\",\"inputs\":[{\"name\":\"_outputRoot\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"_l2BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"_l1BlockNumber\",\"type\":\"uint256\",\"internalType\":\"uint256\"},{\"name\":\"_proof\",\"type\":\"bytes\",\"internalType\":\"bytes\"},{\"name\":\"_checkpointOverridesHash\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"outputs\":[],\"stateMutability\":\"payable\"},{\"type\":\"function\",\"name\":\"proposer\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"rangeVkeyCommitment\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"removeProposer\",\"inputs\":[{\"name\":\"_proposer\",\"type\":\"address\",\"internalType\":\"address\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"rollupConfigHash\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"startingBlockNumber\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"startingTimestamp\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"submissionInterval\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"transferOwnership\",\"inputs\":[{\"name\":\"_owner\",\"type\":\"address\",\"internalType\":\"address\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"updateAggregationVkey\",\"inputs\":[{\"name\":\"_aggregationVkey\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"},{\"name\":\"_aggregationOutputsVersion\",\"type\":\"uint8\",\"internalType\":\"uint8\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"updateAggregationVkey\",\"inputs\":[{\"name\":\"_aggregationVkey\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"updateRangeVkeyCommitment\",\"inputs\":[{\"name\":\"_rangeVkeyCommitment\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"updateRollupConfigHash\",\"inputs\":[{\"name\":\"_rollupConfigHash\",\"type\":\"bytes32\",\"internalType\":\"bytes32\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"updateSubmissionInterval\",\"inputs\":[{\"name\":\"_submissionInterval\",\"type\":\"uint256\",\"internalType\":\"uint256\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"updateVerifier\",\"inputs\":[{\"name\":\"_verifier\",\"type\":\"address\",\"internalType\":\"address\"}],\"outputs\":[],\"stateMutability\":\"nonpayable\"},{\"type\":\"function\",\"name\":\"verifier\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"address\",\"internalType\":\"address\"}],\"stateMutability\":\"view\"},{\"type\":\"function\",\"name\":\"version\",\"inputs\":[],\"outputs\":[{\"name\":\"\",\"type\":\"string\",\"internalType\":\"string\"}],\"stateMutability\":\"view\"},{\"type\":\"event\",\"name\":\"AggregationOutputsVersionUpdated\",\"inputs\":[{\"name\":\"oldAggregationOutputsVersion\",\"type\":\"uint8\",\"indexed\":false,\"internalType\":\"uint8\"},{\"name\":\"newAggregationOutputsVersion\",\"type\":\"uint8\",\"indexed\":false,\"internalType\":\"uint8\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"AggregationVkeyUpdated\",\"inputs\":[{\"name\":\"oldAggregationVkey\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"},{\"name\":\"newAggregationVkey\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"CheckpointOverridesApproved\",\"inputs\":[{\"name\":\"checkpointOverridesHash\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"},{\"name\":\"justification\",\"type\":\"string\",\"indexed\":false,\"internalType\":\"string\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"Initialized\",\"inputs\":[{\"name\":\"version\",\"type\":\"uint8\",\"indexed\":false,\"internalType\":\"uint8\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"OptimisticModeToggled\",\"inputs\":[{\"name\":\"enabled\",\"type\":\"bool\",\"indexed\":true,\"internalType\":\"bool\"},{\"name\":\"finalizationPeriodSeconds\",\"type\":\"uint256\",\"indexed\":false,\"internalType\":\"uint256\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"OutputProposed\",\"inputs\":[{\"name\":\"outputRoot\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"},{\"name\":\"l2OutputIndex\",\"type\":\"uint256\",\"indexed\":true,\"internalType\":\"uint256\"},{\"name\":\"l2BlockNumber\",\"type\":\"uint256\",\"indexed\":true,\"internalType\":\"uint256\"},{\"name\":\"l1Timestamp\",\"type\":\"uint256\",\"indexed\":false,\"internalType\":\"uint256\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"OutputsDeleted\",\"inputs\":[{\"name\":\"prevNextOutputIndex\",\"type\":\"uint256\",\"indexed\":true,\"internalType\":\"uint256\"},{\"name\":\"newNextOutputIndex\",\"type\":\"uint256\",\"indexed\":true,\"internalType\":\"uint256\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"OwnershipTransferred\",\"inputs\":[{\"name\":\"previousOwner\",\"type\":\"address\",\"indexed\":true,\"internalType\":\"address\"},{\"name\":\"newOwner\",\"type\":\"address\",\"indexed\":true,\"internalType\":\"address\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"ProposerUpdated\",\"inputs\":[{\"name\":\"proposer\",\"type\":\"address\",\"indexed\":true,\"internalType\":\"address\"},{\"name\":\"added\",\"type\":\"bool\",\"indexed\":false,\"internalType\":\"bool\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"RangeVkeyCommitmentUpdated\",\"inputs\":[{\"name\":\"oldRangeVkeyCommitment\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"},{\"name\":\"newRangeVkeyCommitment\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"RollupConfigHashUpdated\",\"inputs\":[{\"name\":\"oldRollupConfigHash\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"},{\"name\":\"newRollupConfigHash\",\"type\":\"bytes32\",\"indexed\":true,\"internalType\":\"bytes32\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"SubmissionIntervalUpdated\",\"inputs\":[{\"name\":\"oldSubmissionInterval\",\"type\":\"uint256\",\"indexed\":false,\"internalType\":\"uint256\"},{\"name\":\"newSubmissionInterval\",\"type\":\"uint256\",\"indexed\":false,\"internalType\":\"uint256\"}],\"anonymous\":false},{\"type\":\"event\",\"name\":\"VerifierUpdated\",\"inputs\":[{\"name\":\"oldVerifier\",\"type\":\"address\",\"indexed\":true,\"internalType\":\"address\"},{\"name\":\"newVerifier\",\"type\":\"address\",\"indexed\":true,\"internalType\":\"address\"}],\"anonymous\":false},{\"type\":\"error\",\"name\":\"L1BlockHashNotAvailable\",\"inputs\":[]},{\"type\":\"error\",\"name\":\"L1BlockHashNotCheckpointed\",\"inputs\":[]}]",
 }
 
 // OPSuccinctL2OutputOracleABI is the input ABI used to generate the binding from.
 // Deprecated: Use OPSuccinctL2OutputOracleMetaData.ABI instead.
 var OPSuccinctL2OutputOracleABI = OPSuccinctL2OutputOracleMetaData.ABI
 
-// OPSuccinctL2OutputOracleBin is the compiled bytecode used for deploying new contracts.
-// Deprecated: Use OPSuccinctL2OutputOracleMetaData.Bin instead.
-var OPSuccinctL2OutputOracleBin = OPSuccinctL2OutputOracleMetaData.Bin
-
-// DeployOPSuccinctL2OutputOracle deploys a new Ethereum contract, binding an instance of OPSuccinctL2OutputOracle to it.
-func DeployOPSuccinctL2OutputOracle(auth *bind.TransactOpts, backend bind.ContractBackend) (common.Address, *types.Transaction, *OPSuccinctL2OutputOracle, error) {
-	parsed, err := OPSuccinctL2OutputOracleMetaData.GetAbi()
-	if err != nil {
-		return common.Address{}, nil, nil, err
-	}
-	if parsed == nil {
-		return common.Address{}, nil, nil, errors.New("GetABI returned nil")
-	}
-
-	address, tx, contract, err := bind.DeployContract(auth, *parsed, common.FromHex(OPSuccinctL2OutputOracleBin), backend)
-	if err != nil {
-		return common.Address{}, nil, nil, err
-	}
-	return address, tx, &OPSuccinctL2OutputOracle{OPSuccinctL2OutputOracleCaller: OPSuccinctL2OutputOracleCaller{contract: contract}, OPSuccinctL2OutputOracleTransactor: OPSuccinctL2OutputOracleTransactor{contract: contract}, OPSuccinctL2OutputOracleFilterer: OPSuccinctL2OutputOracleFilterer{contract: contract}}, nil
-}
-
 // OPSuccinctL2OutputOracle is an auto generated Go binding around an Ethereum contract.
 type OPSuccinctL2OutputOracle struct {
 	OPSuccinctL2OutputOracleCaller     // Read-only binding to the contract
@@ -381,6 +359,37 @@ func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleCallerSession) SUBMISSI
 	return _OPSuccinctL2OutputOracle.Contract.SUBMISSIONINTERVAL(&_OPSuccinctL2OutputOracle.CallOpts)
 }
 
+// AggregationOutputsVersion is a free data retrieval call binding the contract method 0x1cfe3027.
+//
+// Solidity: function aggregationOutputsVersion() view returns(uint8)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleCaller) AggregationOutputsVersion(opts *bind.CallOpts) (uint8, error) {
+	var out []interface{}
+	err := _OPSuccinctL2OutputOracle.contract.Call(opts, &out, "aggregationOutputsVersion")
+
+	if err != nil {
+		return *new(uint8), err
+	}
+
+	out0 := *abi.ConvertType(out[0], new(uint8)).(*uint8)
+
+	return out0, err
+
+}
+
+// AggregationOutputsVersion is a free data retrieval call binding the contract method 0x1cfe3027.
+//
+// Solidity: function aggregationOutputsVersion() view returns(uint8)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) AggregationOutputsVersion() (uint8, error) {
+	return _OPSuccinctL2OutputOracle.Contract.AggregationOutputsVersion(&_OPSuccinctL2OutputOracle.CallOpts)
+}
+
+// AggregationOutputsVersion is a free data retrieval call binding the contract method 0x1cfe3027.
+//
+// Solidity: function aggregationOutputsVersion() view returns(uint8)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleCallerSession) AggregationOutputsVersion() (uint8, error) {
+	return _OPSuccinctL2OutputOracle.Contract.AggregationOutputsVersion(&_OPSuccinctL2OutputOracle.CallOpts)
+}
+
 // AggregationVkey is a free data retrieval call binding the contract method 0xc32e4e3e.
 //
 // Solidity: function aggregationVkey() view returns(bytes32)
@@ -412,6 +421,37 @@ func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleCallerSession) Aggregat
 	return _OPSuccinctL2OutputOracle.Contract.AggregationVkey(&_OPSuccinctL2OutputOracle.CallOpts)
 }
 
+// ApprovedCheckpointOverrides is a free data retrieval call binding the contract method 0x49b16044.
+//
+// Solidity: function approvedCheckpointOverrides(bytes32 ) view returns(bool)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleCaller) ApprovedCheckpointOverrides(opts *bind.CallOpts, arg0 [32]byte) (bool, error) {
+	var out []interface{}
+	err := _OPSuccinctL2OutputOracle.contract.Call(opts, &out, "approvedCheckpointOverrides", arg0)
+
+	if err != nil {
+		return *new(bool), err
+	}
+
+	out0 := *abi.ConvertType(out[0], new(bool)).(*bool)
+
+	return out0, err
+
+}
+
+// ApprovedCheckpointOverrides is a free data retrieval call binding the contract method 0x49b16044.
+//
+// Solidity: function approvedCheckpointOverrides(bytes32 ) view returns(bool)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) ApprovedCheckpointOverrides(arg0 [32]byte) (bool, error) {
+	return _OPSuccinctL2OutputOracle.Contract.ApprovedCheckpointOverrides(&_OPSuccinctL2OutputOracle.CallOpts, arg0)
+}
+
+// ApprovedCheckpointOverrides is a free data retrieval call binding the contract method 0x49b16044.
+//
+// Solidity: function approvedCheckpointOverrides(bytes32 ) view returns(bool)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleCallerSession) ApprovedCheckpointOverrides(arg0 [32]byte) (bool, error) {
+	return _OPSuccinctL2OutputOracle.Contract.ApprovedCheckpointOverrides(&_OPSuccinctL2OutputOracle.CallOpts, arg0)
+}
+
 // ApprovedProposers is a free data retrieval call binding the contract method 0xd4651276.
 //
 // Solidity: function approvedProposers(address ) view returns(bool)
@@ -846,6 +886,37 @@ func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleCallerSession) NextOutp
 	return _OPSuccinctL2OutputOracle.Contract.NextOutputIndex(&_OPSuccinctL2OutputOracle.CallOpts)
 }
 
+// OptimisticMode is a free data retrieval call binding the contract method 0x60caf7a0.
+//
+// Solidity: function optimisticMode() view returns(bool)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleCaller) OptimisticMode(opts *bind.CallOpts) (bool, error) {
+	var out []interface{}
+	err := _OPSuccinctL2OutputOracle.contract.Call(opts, &out, "optimisticMode")
+
+	if err != nil {
+		return *new(bool), err
+	}
+
+	out0 := *abi.ConvertType(out[0], new(bool)).(*bool)
+
+	return out0, err
+
+}
+
+// OptimisticMode is a free data retrieval call binding the contract method 0x60caf7a0.
+//
+// Solidity: function optimisticMode() view returns(bool)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) OptimisticMode() (bool, error) {
+	return _OPSuccinctL2OutputOracle.Contract.OptimisticMode(&_OPSuccinctL2OutputOracle.CallOpts)
+}
+
+// OptimisticMode is a free data retrieval call binding the contract method 0x60caf7a0.
+//
+// Solidity: function optimisticMode() view returns(bool)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleCallerSession) OptimisticMode() (bool, error) {
+	return _OPSuccinctL2OutputOracle.Contract.OptimisticMode(&_OPSuccinctL2OutputOracle.CallOpts)
+}
+
 // Owner is a free data retrieval call binding the contract method 0x8da5cb5b.
 //
 // Solidity: function owner() view returns(address)
@@ -1146,6 +1217,27 @@ func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) AddP
 	return _OPSuccinctL2OutputOracle.Contract.AddProposer(&_OPSuccinctL2OutputOracle.TransactOpts, _proposer)
 }
 
+// ApproveCheckpointOverrides is a paid mutator transaction binding the contract method 0xcef35e71.
+//
+// Solidity: function approveCheckpointOverrides(bytes32 _checkpointOverridesHash, string _justification) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactor) ApproveCheckpointOverrides(opts *bind.TransactOpts, _checkpointOverridesHash [32]byte, _justification string) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.contract.Transact(opts, "approveCheckpointOverrides", _checkpointOverridesHash, _justification)
+}
+
+// ApproveCheckpointOverrides is a paid mutator transaction binding the contract method 0xcef35e71.
+//
+// Solidity: function approveCheckpointOverrides(bytes32 _checkpointOverridesHash, string _justification) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) ApproveCheckpointOverrides(_checkpointOverridesHash [32]byte, _justification string) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.ApproveCheckpointOverrides(&_OPSuccinctL2OutputOracle.TransactOpts, _checkpointOverridesHash, _justification)
+}
+
+// ApproveCheckpointOverrides is a paid mutator transaction binding the contract method 0xcef35e71.
+//
+// Solidity: function approveCheckpointOverrides(bytes32 _checkpointOverridesHash, string _justification) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) ApproveCheckpointOverrides(_checkpointOverridesHash [32]byte, _justification string) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.ApproveCheckpointOverrides(&_OPSuccinctL2OutputOracle.TransactOpts, _checkpointOverridesHash, _justification)
+}
+
 // CheckpointBlockHash is a paid mutator transaction binding the contract method 0x1e856800.
 //
 // Solidity: function checkpointBlockHash(uint256 _blockNumber) returns()
@@ -1188,6 +1280,48 @@ func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) Dele
 	return _OPSuccinctL2OutputOracle.Contract.DeleteL2Outputs(&_OPSuccinctL2OutputOracle.TransactOpts, _l2OutputIndex)
 }
 
+// DisableOptimisticMode is a paid mutator transaction binding the contract method 0x4ab309ac.
+//
+// Solidity: function disableOptimisticMode(uint256 _finalizationPeriodSeconds) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactor) DisableOptimisticMode(opts *bind.TransactOpts, _finalizationPeriodSeconds *big.Int) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.contract.Transact(opts, "disableOptimisticMode", _finalizationPeriodSeconds)
+}
+
+// DisableOptimisticMode is a paid mutator transaction binding the contract method 0x4ab309ac.
+//
+// Solidity: function disableOptimisticMode(uint256 _finalizationPeriodSeconds) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) DisableOptimisticMode(_finalizationPeriodSeconds *big.Int) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.DisableOptimisticMode(&_OPSuccinctL2OutputOracle.TransactOpts, _finalizationPeriodSeconds)
+}
+
+// DisableOptimisticMode is a paid mutator transaction binding the contract method 0x4ab309ac.
+//
+// Solidity: function disableOptimisticMode(uint256 _finalizationPeriodSeconds) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) DisableOptimisticMode(_finalizationPeriodSeconds *big.Int) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.DisableOptimisticMode(&_OPSuccinctL2OutputOracle.TransactOpts, _finalizationPeriodSeconds)
+}
+
+// EnableOptimisticMode is a paid mutator transaction binding the contract method 0x2c697961.
+//
+// Solidity: function enableOptimisticMode(uint256 _finalizationPeriodSeconds) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactor) EnableOptimisticMode(opts *bind.TransactOpts, _finalizationPeriodSeconds *big.Int) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.contract.Transact(opts, "enableOptimisticMode", _finalizationPeriodSeconds)
+}
+
+// EnableOptimisticMode is a paid mutator transaction binding the contract method 0x2c697961.
+//
+// Solidity: function enableOptimisticMode(uint256 _finalizationPeriodSeconds) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) EnableOptimisticMode(_finalizationPeriodSeconds *big.Int) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.EnableOptimisticMode(&_OPSuccinctL2OutputOracle.TransactOpts, _finalizationPeriodSeconds)
+}
+
+// EnableOptimisticMode is a paid mutator transaction binding the contract method 0x2c697961.
+//
+// Solidity: function enableOptimisticMode(uint256 _finalizationPeriodSeconds) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) EnableOptimisticMode(_finalizationPeriodSeconds *big.Int) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.EnableOptimisticMode(&_OPSuccinctL2OutputOracle.TransactOpts, _finalizationPeriodSeconds)
+}
+
 // Initialize is a paid mutator transaction binding the contract method 0xdb1470f5.
 //
 // Solidity: function initialize((address,address,address,uint256,uint256,bytes32,bytes32,bytes32,bytes32,uint256,uint256,uint256,address) _initParams) returns()
@@ -1209,25 +1343,88 @@ func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) Init
 	return _OPSuccinctL2OutputOracle.Contract.Initialize(&_OPSuccinctL2OutputOracle.TransactOpts, _initParams)
 }
 
-// ProposeL2Output is a paid mutator transaction binding the contract method 0x9ad84880.
+// Multicall is a paid mutator transaction binding the contract method 0xac9650d8.
+//
+// Solidity: function multicall(bytes[] data) returns(bytes[] results)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactor) Multicall(opts *bind.TransactOpts, data [][]byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.contract.Transact(opts, "multicall", data)
+}
+
+// Multicall is a paid mutator transaction binding the contract method 0xac9650d8.
+//
+// Solidity: function multicall(bytes[] data) returns(bytes[] results)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) Multicall(data [][]byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.Multicall(&_OPSuccinctL2OutputOracle.TransactOpts, data)
+}
+
+// Multicall is a paid mutator transaction binding the contract method 0xac9650d8.
+//
+// Solidity: function multicall(bytes[] data) returns(bytes[] results)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) Multicall(data [][]byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.Multicall(&_OPSuccinctL2OutputOracle.TransactOpts, data)
+}
+
+// ProposeL2Output is a paid mutator transaction binding the contract method 0x9aaab648.
+//
+// Solidity: function proposeL2Output(bytes32 _outputRoot, uint256 _l2BlockNumber, bytes32 _l1BlockHash, uint256 _l1BlockNumber) payable returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactor) ProposeL2Output(opts *bind.TransactOpts, _outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockHash [32]byte, _l1BlockNumber *big.Int) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.contract.Transact(opts, "proposeL2Output", _outputRoot, _l2BlockNumber, _l1BlockHash, _l1BlockNumber)
+}
+
+// ProposeL2Output is a paid mutator transaction binding the contract method 0x9aaab648.
+//
+// Solidity: function proposeL2Output(bytes32 _outputRoot, uint256 _l2BlockNumber, bytes32 _l1BlockHash, uint256 _l1BlockNumber) payable returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) ProposeL2Output(_outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockHash [32]byte, _l1BlockNumber *big.Int) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.ProposeL2Output(&_OPSuccinctL2OutputOracle.TransactOpts, _outputRoot, _l2BlockNumber, _l1BlockHash, _l1BlockNumber)
+}
+
+// ProposeL2Output is a paid mutator transaction binding the contract method 0x9aaab648.
+//
+// Solidity: function proposeL2Output(bytes32 _outputRoot, uint256 _l2BlockNumber, bytes32 _l1BlockHash, uint256 _l1BlockNumber) payable returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) ProposeL2Output(_outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockHash [32]byte, _l1BlockNumber *big.Int) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.ProposeL2Output(&_OPSuccinctL2OutputOracle.TransactOpts, _outputRoot, _l2BlockNumber, _l1BlockHash, _l1BlockNumber)
+}
+
+// ProposeL2Output0 is a paid mutator transaction binding the contract method 0x9ad84880.
 //
 // Solidity: function proposeL2Output(bytes32 _outputRoot, uint256 _l2BlockNumber, uint256 _l1BlockNumber, bytes _proof) payable returns()
-func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactor) ProposeL2Output(opts *bind.TransactOpts, _outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockNumber *big.Int, _proof []byte) (*types.Transaction, error) {
-	return _OPSuccinctL2OutputOracle.contract.Transact(opts, "proposeL2Output", _outputRoot, _l2BlockNumber, _l1BlockNumber, _proof)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactor) ProposeL2Output0(opts *bind.TransactOpts, _outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockNumber *big.Int, _proof []byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.contract.Transact(opts, "proposeL2Output0", _outputRoot, _l2BlockNumber, _l1BlockNumber, _proof)
 }
 
-// ProposeL2Output is a paid mutator transaction binding the contract method 0x9ad84880.
+// ProposeL2Output0 is a paid mutator transaction binding the contract method 0x9ad84880.
 //
 // Solidity: function proposeL2Output(bytes32 _outputRoot, uint256 _l2BlockNumber, uint256 _l1BlockNumber, bytes _proof) payable returns()
-func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) ProposeL2Output(_outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockNumber *big.Int, _proof []byte) (*types.Transaction, error) {
-	return _OPSuccinctL2OutputOracle.Contract.ProposeL2Output(&_OPSuccinctL2OutputOracle.TransactOpts, _outputRoot, _l2BlockNumber, _l1BlockNumber, _proof)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) ProposeL2Output0(_outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockNumber *big.Int, _proof []byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.ProposeL2Output0(&_OPSuccinctL2OutputOracle.TransactOpts, _outputRoot, _l2BlockNumber, _l1BlockNumber, _proof)
 }
 
-// ProposeL2Output is a paid mutator transaction binding the contract method 0x9ad84880.
+// ProposeL2Output0 is a paid mutator transaction binding the contract method 0x9ad84880.
 //
 // Solidity: function proposeL2Output(bytes32 _outputRoot, uint256 _l2BlockNumber, uint256 _l1BlockNumber, bytes _proof) payable returns()
-func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) ProposeL2Output(_outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockNumber *big.Int, _proof []byte) (*types.Transaction, error) {
-	return _OPSuccinctL2OutputOracle.Contract.ProposeL2Output(&_OPSuccinctL2OutputOracle.TransactOpts, _outputRoot, _l2BlockNumber, _l1BlockNumber, _proof)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) ProposeL2Output0(_outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockNumber *big.Int, _proof []byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.ProposeL2Output0(&_OPSuccinctL2OutputOracle.TransactOpts, _outputRoot, _l2BlockNumber, _l1BlockNumber, _proof)
+}
+
+// ProposeL2OutputWithCheckpointOverrides is a paid mutator transaction binding the contract method 0xe78bd273.
+//
+// Solidity: function proposeL2OutputWithCheckpointOverrides(bytes32 _outputRoot, uint256 _l2BlockNumber, uint256 _l1BlockNumber, bytes _proof, bytes32 _checkpointOverridesHash) payable returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactor) ProposeL2OutputWithCheckpointOverrides(opts *bind.TransactOpts, _outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockNumber *big.Int, _proof []byte, _checkpointOverridesHash [32]byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.contract.Transact(opts, "proposeL2OutputWithCheckpointOverrides", _outputRoot, _l2BlockNumber, _l1BlockNumber, _proof, _checkpointOverridesHash)
+}
+
+// ProposeL2OutputWithCheckpointOverrides is a paid mutator transaction binding the contract method 0xe78bd273.
+//
+// Solidity: function proposeL2OutputWithCheckpointOverrides(bytes32 _outputRoot, uint256 _l2BlockNumber, uint256 _l1BlockNumber, bytes _proof, bytes32 _checkpointOverridesHash) payable returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) ProposeL2OutputWithCheckpointOverrides(_outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockNumber *big.Int, _proof []byte, _checkpointOverridesHash [32]byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.ProposeL2OutputWithCheckpointOverrides(&_OPSuccinctL2OutputOracle.TransactOpts, _outputRoot, _l2BlockNumber, _l1BlockNumber, _proof, _checkpointOverridesHash)
+}
+
+// ProposeL2OutputWithCheckpointOverrides is a paid mutator transaction binding the contract method 0xe78bd273.
+//
+// Solidity: function proposeL2OutputWithCheckpointOverrides(bytes32 _outputRoot, uint256 _l2BlockNumber, uint256 _l1BlockNumber, bytes _proof, bytes32 _checkpointOverridesHash) payable returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) ProposeL2OutputWithCheckpointOverrides(_outputRoot [32]byte, _l2BlockNumber *big.Int, _l1BlockNumber *big.Int, _proof []byte, _checkpointOverridesHash [32]byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.ProposeL2OutputWithCheckpointOverrides(&_OPSuccinctL2OutputOracle.TransactOpts, _outputRoot, _l2BlockNumber, _l1BlockNumber, _proof, _checkpointOverridesHash)
 }
 
 // RemoveProposer is a paid mutator transaction binding the contract method 0x09d632d3.
@@ -1272,25 +1469,46 @@ func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) Tran
 	return _OPSuccinctL2OutputOracle.Contract.TransferOwnership(&_OPSuccinctL2OutputOracle.TransactOpts, _owner)
 }
 
-// UpdateAggregationVkey is a paid mutator transaction binding the contract method 0xc4cb03ec.
+// UpdateAggregationVkey is a paid mutator transaction binding the contract method 0x0b79db45.
+//
+// Solidity: function updateAggregationVkey(bytes32 _aggregationVkey, uint8 _aggregationOutputsVersion) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactor) UpdateAggregationVkey(opts *bind.TransactOpts, _aggregationVkey [32]byte, _aggregationOutputsVersion uint8) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.contract.Transact(opts, "updateAggregationVkey", _aggregationVkey, _aggregationOutputsVersion)
+}
+
+// UpdateAggregationVkey is a paid mutator transaction binding the contract method 0x0b79db45.
+//
+// Solidity: function updateAggregationVkey(bytes32 _aggregationVkey, uint8 _aggregationOutputsVersion) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) UpdateAggregationVkey(_aggregationVkey [32]byte, _aggregationOutputsVersion uint8) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.UpdateAggregationVkey(&_OPSuccinctL2OutputOracle.TransactOpts, _aggregationVkey, _aggregationOutputsVersion)
+}
+
+// UpdateAggregationVkey is a paid mutator transaction binding the contract method 0x0b79db45.
+//
+// Solidity: function updateAggregationVkey(bytes32 _aggregationVkey, uint8 _aggregationOutputsVersion) returns()
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) UpdateAggregationVkey(_aggregationVkey [32]byte, _aggregationOutputsVersion uint8) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.UpdateAggregationVkey(&_OPSuccinctL2OutputOracle.TransactOpts, _aggregationVkey, _aggregationOutputsVersion)
+}
+
+// UpdateAggregationVkey0 is a paid mutator transaction binding the contract method 0xc4cb03ec.
 //
 // Solidity: function updateAggregationVkey(bytes32 _aggregationVkey) returns()
-func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactor) UpdateAggregationVkey(opts *bind.TransactOpts, _aggregationVkey [32]byte) (*types.Transaction, error) {
-	return _OPSuccinctL2OutputOracle.contract.Transact(opts, "updateAggregationVkey", _aggregationVkey)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactor) UpdateAggregationVkey0(opts *bind.TransactOpts, _aggregationVkey [32]byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.contract.Transact(opts, "updateAggregationVkey0", _aggregationVkey)
 }
 
-// UpdateAggregationVkey is a paid mutator transaction binding the contract method 0xc4cb03ec.
+// UpdateAggregationVkey0 is a paid mutator transaction binding the contract method 0xc4cb03ec.
 //
 // Solidity: function updateAggregationVkey(bytes32 _aggregationVkey) returns()
-func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) UpdateAggregationVkey(_aggregationVkey [32]byte) (*types.Transaction, error) {
-	return _OPSuccinctL2OutputOracle.Contract.UpdateAggregationVkey(&_OPSuccinctL2OutputOracle.TransactOpts, _aggregationVkey)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleSession) UpdateAggregationVkey0(_aggregationVkey [32]byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.UpdateAggregationVkey0(&_OPSuccinctL2OutputOracle.TransactOpts, _aggregationVkey)
 }
 
-// UpdateAggregationVkey is a paid mutator transaction binding the contract method 0xc4cb03ec.
+// UpdateAggregationVkey0 is a paid mutator transaction binding the contract method 0xc4cb03ec.
 //
 // Solidity: function updateAggregationVkey(bytes32 _aggregationVkey) returns()
-func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) UpdateAggregationVkey(_aggregationVkey [32]byte) (*types.Transaction, error) {
-	return _OPSuccinctL2OutputOracle.Contract.UpdateAggregationVkey(&_OPSuccinctL2OutputOracle.TransactOpts, _aggregationVkey)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) UpdateAggregationVkey0(_aggregationVkey [32]byte) (*types.Transaction, error) {
+	return _OPSuccinctL2OutputOracle.Contract.UpdateAggregationVkey0(&_OPSuccinctL2OutputOracle.TransactOpts, _aggregationVkey)
 }
 
 // UpdateRangeVkeyCommitment is a paid mutator transaction binding the contract method 0xbc91ce33.
@@ -1377,6 +1595,141 @@ func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleTransactorSession) Upda
 	return _OPSuccinctL2OutputOracle.Contract.UpdateVerifier(&_OPSuccinctL2OutputOracle.TransactOpts, _verifier)
 }
 
+// OPSuccinctL2OutputOracleAggregationOutputsVersionUpdatedIterator is returned from FilterAggregationOutputsVersionUpdated and is used to iterate over the raw logs and unpacked data for AggregationOutputsVersionUpdated events raised by the OPSuccinctL2OutputOracle contract.
+type OPSuccinctL2OutputOracleAggregationOutputsVersionUpdatedIterator struct {
+	Event *OPSuccinctL2OutputOracleAggregationOutputsVersionUpdated // Event containing the contract specifics and raw log
+
+	contract *bind.BoundContract // Generic contract to use for unpacking event data
+	event    string              // Event name to use for unpacking event data
+
+	logs chan types.Log        // Log channel receiving the found contract events
+	sub  ethereum.Subscription // Subscription for errors, completion and termination
+	done bool                  // Whether the subscription completed delivering logs
+	fail error                 // Occurred error to stop iteration
+}
+
+// Next advances the iterator to the subsequent event, returning whether there
+// are any more events found. In case of a retrieval or parsing error, false is
+// returned and Error() can be queried for the exact failure.
+func (it *OPSuccinctL2OutputOracleAggregationOutputsVersionUpdatedIterator) Next() bool {
+	// If the iterator failed, stop iterating
+	if it.fail != nil {
+		return false
+	}
+	// If the iterator completed, deliver directly whatever's available
+	if it.done {
+		select {
+		case log := <-it.logs:
+			it.Event = new(OPSuccinctL2OutputOracleAggregationOutputsVersionUpdated)
+			if err := it.contract.UnpackLog(it.Event, it.event, log); err != nil {
+				it.fail = err
+				return false
+			}
+			it.Event.Raw = log
+			return true
+
+		default:
+			return false
+		}
+	}
+	// Iterator still in progress, wait for either a data or an error event
+	select {
+	case log := <-it.logs:
+		it.Event = new(OPSuccinctL2OutputOracleAggregationOutputsVersionUpdated)
+		if err := it.contract.UnpackLog(it.Event, it.event, log); err != nil {
+			it.fail = err
+			return false
+		}
+		it.Event.Raw = log
+		return true
+
+	case err := <-it.sub.Err():
+		it.done = true
+		it.fail = err
+		return it.Next()
+	}
+}
+
+// Error returns any retrieval or parsing error occurred during filtering.
+func (it *OPSuccinctL2OutputOracleAggregationOutputsVersionUpdatedIterator) Error() error {
+	return it.fail
+}
+
+// Close terminates the iteration process, releasing any pending underlying
+// resources.
+func (it *OPSuccinctL2OutputOracleAggregationOutputsVersionUpdatedIterator) Close() error {
+	it.sub.Unsubscribe()
+	return nil
+}
+
+// OPSuccinctL2OutputOracleAggregationOutputsVersionUpdated represents a AggregationOutputsVersionUpdated event raised by the OPSuccinctL2OutputOracle contract.
+type OPSuccinctL2OutputOracleAggregationOutputsVersionUpdated struct {
+	OldAggregationOutputsVersion uint8
+	NewAggregationOutputsVersion uint8
+	Raw                          types.Log // Blockchain specific contextual infos
+}
+
+// FilterAggregationOutputsVersionUpdated is a free log retrieval operation binding the contract event 0xee492749fb97065380bf97796581979002f746ba4b79149eb6f47fba574d36ef.
+//
+// Solidity: event AggregationOutputsVersionUpdated(uint8 oldAggregationOutputsVersion, uint8 newAggregationOutputsVersion)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleFilterer) FilterAggregationOutputsVersionUpdated(opts *bind.FilterOpts) (*OPSuccinctL2OutputOracleAggregationOutputsVersionUpdatedIterator, error) {
+
+	logs, sub, err := _OPSuccinctL2OutputOracle.contract.FilterLogs(opts, "AggregationOutputsVersionUpdated")
+	if err != nil {
+		return nil, err
+	}
+	return &OPSuccinctL2OutputOracleAggregationOutputsVersionUpdatedIterator{contract: _OPSuccinctL2OutputOracle.contract, event: "AggregationOutputsVersionUpdated", logs: logs, sub: sub}, nil
+}
+
+// WatchAggregationOutputsVersionUpdated is a free log subscription operation binding the contract event 0xee492749fb97065380bf97796581979002f746ba4b79149eb6f47fba574d36ef.
+//
+// Solidity: event AggregationOutputsVersionUpdated(uint8 oldAggregationOutputsVersion, uint8 newAggregationOutputsVersion)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleFilterer) WatchAggregationOutputsVersionUpdated(opts *bind.WatchOpts, sink chan<- *OPSuccinctL2OutputOracleAggregationOutputsVersionUpdated) (event.Subscription, error) {
+
+	logs, sub, err := _OPSuccinctL2OutputOracle.contract.WatchLogs(opts, "AggregationOutputsVersionUpdated")
+	if err != nil {
+		return nil, err
+	}
+	return event.NewSubscription(func(quit <-chan struct{}) error {
+		defer sub.Unsubscribe()
+		for {
+			select {
+			case log := <-logs:
+				// New log arrived, parse the event and forward to the user
+				event := new(OPSuccinctL2OutputOracleAggregationOutputsVersionUpdated)
+				if err := _OPSuccinctL2OutputOracle.contract.UnpackLog(event, "AggregationOutputsVersionUpdated", log); err != nil {
+					return err
+				}
+				event.Raw = log
+
+				select {
+				case sink <- event:
+				case err := <-sub.Err():
+					return err
+				case <-quit:
+					return nil
+				}
+			case err := <-sub.Err():
+				return err
+			case <-quit:
+				return nil
+			}
+		}
+	}), nil
+}
+
+// ParseAggregationOutputsVersionUpdated is a log parse operation binding the contract event 0xee492749fb97065380bf97796581979002f746ba4b79149eb6f47fba574d36ef.
+//
+// Solidity: event AggregationOutputsVersionUpdated(uint8 oldAggregationOutputsVersion, uint8 newAggregationOutputsVersion)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleFilterer) ParseAggregationOutputsVersionUpdated(log types.Log) (*OPSuccinctL2OutputOracleAggregationOutputsVersionUpdated, error) {
+	event := new(OPSuccinctL2OutputOracleAggregationOutputsVersionUpdated)
+	if err := _OPSuccinctL2OutputOracle.contract.UnpackLog(event, "AggregationOutputsVersionUpdated", log); err != nil {
+		return nil, err
+	}
+	event.Raw = log
+	return event, nil
+}
+
 // OPSuccinctL2OutputOracleAggregationVkeyUpdatedIterator is returned from FilterAggregationVkeyUpdated and is used to iterate over the raw logs and unpacked data for AggregationVkeyUpdated events raised by the OPSuccinctL2OutputOracle contract.
 type OPSuccinctL2OutputOracleAggregationVkeyUpdatedIterator struct {
 	Event *OPSuccinctL2OutputOracleAggregationVkeyUpdated // Event containing the contract specifics and raw log
@@ -1530,6 +1883,151 @@ func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleFilterer) ParseAggregat
 	return event, nil
 }
 
+// OPSuccinctL2OutputOracleCheckpointOverridesApprovedIterator is returned from FilterCheckpointOverridesApproved and is used to iterate over the raw logs and unpacked data for CheckpointOverridesApproved events raised by the OPSuccinctL2OutputOracle contract.
+type OPSuccinctL2OutputOracleCheckpointOverridesApprovedIterator struct {
+	Event *OPSuccinctL2OutputOracleCheckpointOverridesApproved // Event containing the contract specifics and raw log
+
+	contract *bind.BoundContract // Generic contract to use for unpacking event data
+	event    string              // Event name to use for unpacking event data
+
+	logs chan types.Log        // Log channel receiving the found contract events
+	sub  ethereum.Subscription // Subscription for errors, completion and termination
+	done bool                  // Whether the subscription completed delivering logs
+	fail error                 // Occurred error to stop iteration
+}
+
+// Next advances the iterator to the subsequent event, returning whether there
+// are any more events found. In case of a retrieval or parsing error, false is
+// returned and Error() can be queried for the exact failure.
+func (it *OPSuccinctL2OutputOracleCheckpointOverridesApprovedIterator) Next() bool {
+	// If the iterator failed, stop iterating
+	if it.fail != nil {
+		return false
+	}
+	// If the iterator completed, deliver directly whatever's available
+	if it.done {
+		select {
+		case log := <-it.logs:
+			it.Event = new(OPSuccinctL2OutputOracleCheckpointOverridesApproved)
+			if err := it.contract.UnpackLog(it.Event, it.event, log); err != nil {
+				it.fail = err
+				return false
+			}
+			it.Event.Raw = log
+			return true
+
+		default:
+			return false
+		}
+	}
+	// Iterator still in progress, wait for either a data or an error event
+	select {
+	case log := <-it.logs:
+		it.Event = new(OPSuccinctL2OutputOracleCheckpointOverridesApproved)
+		if err := it.contract.UnpackLog(it.Event, it.event, log); err != nil {
+			it.fail = err
+			return false
+		}
+		it.Event.Raw = log
+		return true
+
+	case err := <-it.sub.Err():
+		it.done = true
+		it.fail = err
+		return it.Next()
+	}
+}
+
+// Error returns any retrieval or parsing error occurred during filtering.
+func (it *OPSuccinctL2OutputOracleCheckpointOverridesApprovedIterator) Error() error {
+	return it.fail
+}
+
+// Close terminates the iteration process, releasing any pending underlying
+// resources.
+func (it *OPSuccinctL2OutputOracleCheckpointOverridesApprovedIterator) Close() error {
+	it.sub.Unsubscribe()
+	return nil
+}
+
+// OPSuccinctL2OutputOracleCheckpointOverridesApproved represents a CheckpointOverridesApproved event raised by the OPSuccinctL2OutputOracle contract.
+type OPSuccinctL2OutputOracleCheckpointOverridesApproved struct {
+	CheckpointOverridesHash [32]byte
+	Justification           string
+	Raw                     types.Log // Blockchain specific contextual infos
+}
+
+// FilterCheckpointOverridesApproved is a free log retrieval operation binding the contract event 0x0bd43090376aeb493a3dd82c51c32bf021eb7670f80e713acc4a3335ca5bf179.
+//
+// Solidity: event CheckpointOverridesApproved(bytes32 indexed checkpointOverridesHash, string justification)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleFilterer) FilterCheckpointOverridesApproved(opts *bind.FilterOpts, checkpointOverridesHash [][32]byte) (*OPSuccinctL2OutputOracleCheckpointOverridesApprovedIterator, error) {
+
+	var checkpointOverridesHashRule []interface{}
+	for _, checkpointOverridesHashItem := range checkpointOverridesHash {
+		checkpointOverridesHashRule = append(checkpointOverridesHashRule, checkpointOverridesHashItem)
+	}
+
+	logs, sub, err := _OPSuccinctL2OutputOracle.contract.FilterLogs(opts, "CheckpointOverridesApproved", checkpointOverridesHashRule)
+	if err != nil {
+		return nil, err
+	}
+	return &OPSuccinctL2OutputOracleCheckpointOverridesApprovedIterator{contract: _OPSuccinctL2OutputOracle.contract, event: "CheckpointOverridesApproved", logs: logs, sub: sub}, nil
+}
+
+// WatchCheckpointOverridesApproved is a free log subscription operation binding the contract event 0x0bd43090376aeb493a3dd82c51c32bf021eb7670f80e713acc4a3335ca5bf179.
+//
+// Solidity: event CheckpointOverridesApproved(bytes32 indexed checkpointOverridesHash, string justification)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleFilterer) WatchCheckpointOverridesApproved(opts *bind.WatchOpts, sink chan<- *OPSuccinctL2OutputOracleCheckpointOverridesApproved, checkpointOverridesHash [][32]byte) (event.Subscription, error) {
+
+	var checkpointOverridesHashRule []interface{}
+	for _, checkpointOverridesHashItem := range checkpointOverridesHash {
+		checkpointOverridesHashRule = append(checkpointOverridesHashRule, checkpointOverridesHashItem)
+	}
+
+	logs, sub, err := _OPSuccinctL2OutputOracle.contract.WatchLogs(opts, "CheckpointOverridesApproved", checkpointOverridesHashRule)
+	if err != nil {
+		return nil, err
+	}
+	return event.NewSubscription(func(quit <-chan struct{}) error {
+		defer sub.Unsubscribe()
+		for {
+			select {
+			case log := <-logs:
+				// New log arrived, parse the event and forward to the user
+				event := new(OPSuccinctL2OutputOracleCheckpointOverridesApproved)
+				if err := _OPSuccinctL2OutputOracle.contract.UnpackLog(event, "CheckpointOverridesApproved", log); err != nil {
+					return err
+				}
+				event.Raw = log
+
+				select {
+				case sink <- event:
+				case err := <-sub.Err():
+					return err
+				case <-quit:
+					return nil
+				}
+			case err := <-sub.Err():
+				return err
+			case <-quit:
+				return nil
+			}
+		}
+	}), nil
+}
+
+// ParseCheckpointOverridesApproved is a log parse operation binding the contract event 0x0bd43090376aeb493a3dd82c51c32bf021eb7670f80e713acc4a3335ca5bf179.
+//
+// Solidity: event CheckpointOverridesApproved(bytes32 indexed checkpointOverridesHash, string justification)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleFilterer) ParseCheckpointOverridesApproved(log types.Log) (*OPSuccinctL2OutputOracleCheckpointOverridesApproved, error) {
+	event := new(OPSuccinctL2OutputOracleCheckpointOverridesApproved)
+	if err := _OPSuccinctL2OutputOracle.contract.UnpackLog(event, "CheckpointOverridesApproved", log); err != nil {
+		return nil, err
+	}
+	event.Raw = log
+	return event, nil
+}
+
 // OPSuccinctL2OutputOracleInitializedIterator is returned from FilterInitialized and is used to iterate over the raw logs and unpacked data for Initialized events raised by the OPSuccinctL2OutputOracle contract.
 type OPSuccinctL2OutputOracleInitializedIterator struct {
 	Event *OPSuccinctL2OutputOracleInitialized // Event containing the contract specifics and raw log
@@ -1664,6 +2162,151 @@ func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleFilterer) ParseInitiali
 	return event, nil
 }
 
+// OPSuccinctL2OutputOracleOptimisticModeToggledIterator is returned from FilterOptimisticModeToggled and is used to iterate over the raw logs and unpacked data for OptimisticModeToggled events raised by the OPSuccinctL2OutputOracle contract.
+type OPSuccinctL2OutputOracleOptimisticModeToggledIterator struct {
+	Event *OPSuccinctL2OutputOracleOptimisticModeToggled // Event containing the contract specifics and raw log
+
+	contract *bind.BoundContract // Generic contract to use for unpacking event data
+	event    string              // Event name to use for unpacking event data
+
+	logs chan types.Log        // Log channel receiving the found contract events
+	sub  ethereum.Subscription // Subscription for errors, completion and termination
+	done bool                  // Whether the subscription completed delivering logs
+	fail error                 // Occurred error to stop iteration
+}
+
+// Next advances the iterator to the subsequent event, returning whether there
+// are any more events found. In case of a retrieval or parsing error, false is
+// returned and Error() can be queried for the exact failure.
+func (it *OPSuccinctL2OutputOracleOptimisticModeToggledIterator) Next() bool {
+	// If the iterator failed, stop iterating
+	if it.fail != nil {
+		return false
+	}
+	// If the iterator completed, deliver directly whatever's available
+	if it.done {
+		select {
+		case log := <-it.logs:
+			it.Event = new(OPSuccinctL2OutputOracleOptimisticModeToggled)
+			if err := it.contract.UnpackLog(it.Event, it.event, log); err != nil {
+				it.fail = err
+				return false
+			}
+			it.Event.Raw = log
+			return true
+
+		default:
+			return false
+		}
+	}
+	// Iterator still in progress, wait for either a data or an error event
+	select {
+	case log := <-it.logs:
+		it.Event = new(OPSuccinctL2OutputOracleOptimisticModeToggled)
+		if err := it.contract.UnpackLog(it.Event, it.event, log); err != nil {
+			it.fail = err
+			return false
+		}
+		it.Event.Raw = log
+		return true
+
+	case err := <-it.sub.Err():
+		it.done = true
+		it.fail = err
+		return it.Next()
+	}
+}
+
+// Error returns any retrieval or parsing error occurred during filtering.
+func (it *OPSuccinctL2OutputOracleOptimisticModeToggledIterator) Error() error {
+	return it.fail
+}
+
+// Close terminates the iteration process, releasing any pending underlying
+// resources.
+func (it *OPSuccinctL2OutputOracleOptimisticModeToggledIterator) Close() error {
+	it.sub.Unsubscribe()
+	return nil
+}
+
+// OPSuccinctL2OutputOracleOptimisticModeToggled represents a OptimisticModeToggled event raised by the OPSuccinctL2OutputOracle contract.
+type OPSuccinctL2OutputOracleOptimisticModeToggled struct {
+	Enabled                   bool
+	FinalizationPeriodSeconds *big.Int
+	Raw                       types.Log // Blockchain specific contextual infos
+}
+
+// FilterOptimisticModeToggled is a free log retrieval operation binding the contract event 0x1f5c872f1ea93c57e43112ea449ee19ef5754488b87627b4c52456b0e5a4109a.
+//
+// Solidity: event OptimisticModeToggled(bool indexed enabled, uint256 finalizationPeriodSeconds)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleFilterer) FilterOptimisticModeToggled(opts *bind.FilterOpts, enabled []bool) (*OPSuccinctL2OutputOracleOptimisticModeToggledIterator, error) {
+
+	var enabledRule []interface{}
+	for _, enabledItem := range enabled {
+		enabledRule = append(enabledRule, enabledItem)
+	}
+
+	logs, sub, err := _OPSuccinctL2OutputOracle.contract.FilterLogs(opts, "OptimisticModeToggled", enabledRule)
+	if err != nil {
+		return nil, err
+	}
+	return &OPSuccinctL2OutputOracleOptimisticModeToggledIterator{contract: _OPSuccinctL2OutputOracle.contract, event: "OptimisticModeToggled", logs: logs, sub: sub}, nil
+}
+
+// WatchOptimisticModeToggled is a free log subscription operation binding the contract event 0x1f5c872f1ea93c57e43112ea449ee19ef5754488b87627b4c52456b0e5a4109a.
+//
+// Solidity: event OptimisticModeToggled(bool indexed enabled, uint256 finalizationPeriodSeconds)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleFilterer) WatchOptimisticModeToggled(opts *bind.WatchOpts, sink chan<- *OPSuccinctL2OutputOracleOptimisticModeToggled, enabled []bool) (event.Subscription, error) {
+
+	var enabledRule []interface{}
+	for _, enabledItem := range enabled {
+		enabledRule = append(enabledRule, enabledItem)
+	}
+
+	logs, sub, err := _OPSuccinctL2OutputOracle.contract.WatchLogs(opts, "OptimisticModeToggled", enabledRule)
+	if err != nil {
+		return nil, err
+	}
+	return event.NewSubscription(func(quit <-chan struct{}) error {
+		defer sub.Unsubscribe()
+		for {
+			select {
+			case log := <-logs:
+				// New log arrived, parse the event and forward to the user
+				event := new(OPSuccinctL2OutputOracleOptimisticModeToggled)
+				if err := _OPSuccinctL2OutputOracle.contract.UnpackLog(event, "OptimisticModeToggled", log); err != nil {
+					return err
+				}
+				event.Raw = log
+
+				select {
+				case sink <- event:
+				case err := <-sub.Err():
+					return err
+				case <-quit:
+					return nil
+				}
+			case err := <-sub.Err():
+				return err
+			case <-quit:
+				return nil
+			}
+		}
+	}), nil
+}
+
+// ParseOptimisticModeToggled is a log parse operation binding the contract event 0x1f5c872f1ea93c57e43112ea449ee19ef5754488b87627b4c52456b0e5a4109a.
+//
+// Solidity: event OptimisticModeToggled(bool indexed enabled, uint256 finalizationPeriodSeconds)
+func (_OPSuccinctL2OutputOracle *OPSuccinctL2OutputOracleFilterer) ParseOptimisticModeToggled(log types.Log) (*OPSuccinctL2OutputOracleOptimisticModeToggled, error) {
+	event := new(OPSuccinctL2OutputOracleOptimisticModeToggled)
+	if err := _OPSuccinctL2OutputOracle.contract.UnpackLog(event, "OptimisticModeToggled", log); err != nil {
+		return nil, err
+	}
+	event.Raw = log
+	return event, nil
+}
+
 // OPSuccinctL2OutputOracleOutputProposedIterator is returned from FilterOutputProposed and is used to iterate over the raw logs and unpacked data for OutputProposed events raised by the OPSuccinctL2OutputOracle contract.
 type OPSuccinctL2OutputOracleOutputProposedIterator struct {
 	Event *OPSuccinctL2OutputOracleOutputProposed // Event containing the contract specifics and raw log
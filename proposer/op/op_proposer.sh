@@ -20,6 +20,11 @@
     --op-succinct-server-url=${OP_SUCCINCT_SERVER_URL:-http://op-succinct-server:3000} \
     --max-block-range-per-span-proof=${MAX_BLOCK_RANGE_PER_SPAN_PROOF:-300} \
     --use-cached-db=${USE_CACHED_DB:-false} \
+    --fulfilled-retention-days=${FULFILLED_RETENTION_DAYS:-0} \
+    --failed-retention-days=${FAILED_RETENTION_DAYS:-0} \
+    --prune-archive-dir=${PRUNE_ARCHIVE_DIR:-} \
     --metrics.enabled=${METRICS_ENABLED:-true} \
     --metrics.port=${METRICS_PORT:-7300} \
+    --query-api-addr=${QUERY_API_ADDR:-} \
+    --query-api-port=${QUERY_API_PORT:-0} \
     --mock=${OP_SUCCINCT_MOCK:-false}
\ No newline at end of file
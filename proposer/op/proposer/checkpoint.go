@@ -0,0 +1,225 @@
+package proposer
+
+import (
+	"context"
+	"fmt"
+	"math/big"
+	"sync"
+	"time"
+
+	"github.com/ethereum-optimism/optimism/op-service/txmgr"
+	"github.com/ethereum/go-ethereum/accounts/abi/bind"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/core/types"
+)
+
+// BlockhashWindow is the number of most recent L1 blocks whose hashes the BLOCKHASH opcode returns. The L2OO's
+// checkpointBlockHash reverts for older blocks, so a block hash can only be checkpointed within this many blocks of its
+// block.
+const BlockhashWindow = 256
+
+// CheckpointExpiryMargin is how many blocks before the end of its BlockhashWindow a block hash that is not checkpointed
+// on-chain is given up on, and a newer block hash checkpointed instead. Checkpointing it again this late risks the
+// transaction being included after the window closes.
+const CheckpointExpiryMargin = 64
+
+// Checkpoint is an L1 block hash the proposer checkpointed on the L2OO for AGG proofs to commit to.
+type Checkpoint struct {
+	BlockNumber uint64
+	BlockHash   common.Hash
+	// BlockTime is the timestamp of the L1 block.
+	BlockTime uint64
+	// CheckpointedAt is when the checkpoint transaction was included, and TxHash its hash.
+	CheckpointedAt time.Time
+	TxHash         common.Hash
+}
+
+// ExpiresAt is the first L1 block at which the block hash can no longer be checkpointed.
+func (c Checkpoint) ExpiresAt() uint64 {
+	return c.BlockNumber + BlockhashWindow
+}
+
+// CheckpointTracker records the block hashes the proposer checkpointed, so later AGG proofs can commit to them instead
+// of checkpointing a new one each.
+type CheckpointTracker struct {
+	mu          sync.Mutex
+	checkpoints map[uint64]Checkpoint
+}
+
+func NewCheckpointTracker() *CheckpointTracker {
+	return &CheckpointTracker{checkpoints: make(map[uint64]Checkpoint)}
+}
+
+// Record records a checkpoint, replacing an earlier one of the same block.
+func (t *CheckpointTracker) Record(c Checkpoint) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	t.checkpoints[c.BlockNumber] = c
+}
+
+// Get returns the checkpoint of an L1 block, if it was recorded.
+func (t *CheckpointTracker) Get(blockNumber uint64) (Checkpoint, bool) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	c, ok := t.checkpoints[blockNumber]
+	return c, ok
+}
+
+// Latest returns the checkpoint of the newest L1 block, if any was recorded.
+func (t *CheckpointTracker) Latest() (Checkpoint, bool) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	var latest Checkpoint
+	found := false
+	for _, c := range t.checkpoints {
+		if !found || c.BlockNumber > latest.BlockNumber {
+			latest, found = c, true
+		}
+	}
+	return latest, found
+}
+
+// checkpointAction is what to do with a candidate L1 block hash for an AGG proof.
+type checkpointAction int
+
+const (
+	// checkpointUse commits to the block hash, which is checkpointed on-chain.
+	checkpointUse checkpointAction = iota
+	// checkpointRenew checkpoints the block hash again, as its checkpoint isn't on-chain, e.g. because the
+	// transaction reverted or was reorged out, but the block is still within the BLOCKHASH window.
+	checkpointRenew
+	// checkpointReplace gives up on the block hash, and checkpoints a newer one instead.
+	checkpointReplace
+)
+
+// candidateCheckpoint is what the proposer knows of a candidate L1 block hash for an AGG proof.
+type candidateCheckpoint struct {
+	Checkpoint
+	// OnChain is the block hash the L2OO has checkpointed for the block, zero if none.
+	OnChain common.Hash
+	// Canonical is whether the block hash is still the L1 chain's at its block number.
+	Canonical bool
+}
+
+// decideCheckpoint decides what to do with a candidate L1 block hash for an AGG proof, given the current L1 head. The
+// block must be from no earlier than minBlockTime, so it is not older than the L1 heads of the span proofs the AGG proof
+// aggregates.
+func decideCheckpoint(candidate candidateCheckpoint, l1Head, minBlockTime uint64) checkpointAction {
+	if candidate.BlockTime < minBlockTime {
+		return checkpointReplace
+	}
+	if candidate.OnChain == candidate.BlockHash {
+		return checkpointUse
+	}
+	// A reorged block's number now has another hash, which is what checkpointing it again would store.
+	if !candidate.Canonical || candidate.OnChain != (common.Hash{}) {
+		return checkpointReplace
+	}
+	if l1Head+CheckpointExpiryMargin < candidate.ExpiresAt() {
+		return checkpointRenew
+	}
+	return checkpointReplace
+}
+
+// selectCheckpoint returns the L1 block an AGG proof commits to, from the candidates in order of preference: the first
+// one checkpointed on-chain, or that can still be checkpointed again. If none can, a new block hash is checkpointed.
+// minBlockTime is the earliest timestamp of a usable block, see decideCheckpoint.
+func (l *L2OutputSubmitter) selectCheckpoint(ctx context.Context, candidates []Checkpoint, minBlockTime uint64) (Checkpoint, error) {
+	l1Head, err := l.L1Client.BlockNumber(ctx)
+	if err != nil {
+		return Checkpoint{}, fmt.Errorf("failed to get L1 head: %w", err)
+	}
+
+	for _, c := range candidates {
+		candidate, err := l.inspectCheckpoint(ctx, c)
+		if err != nil {
+			return Checkpoint{}, err
+		}
+		switch decideCheckpoint(candidate, l1Head, minBlockTime) {
+		case checkpointUse:
+			l.Log.Info("using checkpointed L1 block hash", "block", c.BlockNumber, "hash", c.BlockHash)
+			return candidate.Checkpoint, nil
+		case checkpointRenew:
+			l.Log.Warn("L1 block hash is not checkpointed on-chain, checkpointing it again", "block", c.BlockNumber, "hash", c.BlockHash, "expiresAt", c.ExpiresAt())
+			header, err := l.L1Client.HeaderByNumber(ctx, new(big.Int).SetUint64(c.BlockNumber))
+			if err != nil {
+				return Checkpoint{}, err
+			}
+			return l.sendCheckpoint(ctx, header)
+		case checkpointReplace:
+			l.Log.Info("not using L1 block hash for AGG proof", "block", c.BlockNumber, "hash", c.BlockHash, "onChain", candidate.OnChain, "canonical", candidate.Canonical)
+		}
+	}
+	return l.checkpointBlockHash(ctx)
+}
+
+// inspectCheckpoint looks up the block hash the L2OO checkpointed for a candidate's block, and whether the candidate is
+// still canonical.
+func (l *L2OutputSubmitter) inspectCheckpoint(ctx context.Context, c Checkpoint) (candidateCheckpoint, error) {
+	blockNumber := new(big.Int).SetUint64(c.BlockNumber)
+	onChain, err := l.l2ooContract.HistoricBlockHashes(&bind.CallOpts{Context: ctx}, blockNumber)
+	if err != nil {
+		return candidateCheckpoint{}, fmt.Errorf("failed to read checkpointed block hash of L1 block %d: %w", c.BlockNumber, err)
+	}
+	header, err := l.L1Client.HeaderByNumber(ctx, blockNumber)
+	if err != nil {
+		return candidateCheckpoint{}, fmt.Errorf("failed to get L1 block %d: %w", c.BlockNumber, err)
+	}
+	c.BlockTime = header.Time
+	return candidateCheckpoint{
+		Checkpoint: c,
+		OnChain:    onChain,
+		Canonical:  header.Hash() == c.BlockHash,
+	}, nil
+}
+
+// checkpointBlockHash checkpoints the block hash of the block before the current L1 head on the L2OO contract for the
+// aggregation proof. The L1 head itself is not used, as its block hash isn't available to BLOCKHASH in gas estimation.
+func (l *L2OutputSubmitter) checkpointBlockHash(ctx context.Context) (Checkpoint, error) {
+	cCtx, cancel := context.WithTimeout(ctx, 10*time.Minute)
+	defer cancel()
+
+	currBlockNum, err := l.L1Client.BlockNumber(cCtx)
+	if err != nil {
+		return Checkpoint{}, err
+	}
+	header, err := l.L1Client.HeaderByNumber(cCtx, new(big.Int).SetUint64(currBlockNum-1))
+	if err != nil {
+		return Checkpoint{}, err
+	}
+	return l.sendCheckpoint(cCtx, header)
+}
+
+// sendCheckpoint sends a transaction checkpointing the block hash of an L1 block, and records the checkpoint once it is
+// included.
+func (l *L2OutputSubmitter) sendCheckpoint(ctx context.Context, header *types.Header) (Checkpoint, error) {
+	data, err := l.CheckpointBlockHashTxData(header.Number)
+	if err != nil {
+		return Checkpoint{}, err
+	}
+
+	// TODO: This currently blocks the loop while it waits for the transaction to be confirmed. Up to 3 minutes.
+	receipt, err := l.Txmgr.Send(ctx, txmgr.TxCandidate{
+		TxData:   data,
+		To:       l.Cfg.L2OutputOracleAddr,
+		GasLimit: 0,
+	})
+	if err != nil {
+		return Checkpoint{}, err
+	}
+	if receipt.Status == types.ReceiptStatusFailed {
+		l.Log.Error("checkpoint blockhash tx successfully published but reverted", "tx_hash", receipt.TxHash)
+		return Checkpoint{}, fmt.Errorf("checkpoint of L1 block %d reverted in tx %s", header.Number, receipt.TxHash)
+	}
+	l.Log.Info("checkpoint blockhash tx successfully published", "tx_hash", receipt.TxHash, "block", header.Number, "hash", header.Hash())
+
+	checkpoint := Checkpoint{
+		BlockNumber:    header.Number.Uint64(),
+		BlockHash:      header.Hash(),
+		BlockTime:      header.Time,
+		CheckpointedAt: time.Now(),
+		TxHash:         receipt.TxHash,
+	}
+	l.checkpoints.Record(checkpoint)
+	return checkpoint, nil
+}
@@ -0,0 +1,122 @@
+package proposer
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"math/big"
+	"os"
+	"strings"
+
+	"github.com/ethereum/go-ethereum/accounts/abi"
+	"github.com/ethereum/go-ethereum/accounts/abi/bind"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/crypto"
+)
+
+// LoadCheckpointOverrides reads the checkpoint overrides to aggregate over for emergency catch-up from a JSON file, in
+// the format of the op-succinct-server's `checkpoint_overrides`.
+func LoadCheckpointOverrides(path string) (*CheckpointOverrides, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read checkpoint overrides: %w", err)
+	}
+	overrides, err := parseCheckpointOverrides(string(data))
+	if err != nil {
+		return nil, fmt.Errorf("invalid checkpoint overrides in %s: %w", path, err)
+	}
+	if len(overrides.Gaps) == 0 {
+		return nil, fmt.Errorf("checkpoint overrides in %s have no gaps", path)
+	}
+	if strings.TrimSpace(overrides.Justification) == "" {
+		return nil, fmt.Errorf("checkpoint overrides in %s have no justification", path)
+	}
+	return overrides, nil
+}
+
+// parseCheckpointOverrides parses the checkpoint overrides recorded with an AGG proof request, or returns nil if it
+// has none.
+func parseCheckpointOverrides(recorded string) (*CheckpointOverrides, error) {
+	if recorded == "" {
+		return nil, nil
+	}
+	var overrides CheckpointOverrides
+	if err := json.Unmarshal([]byte(recorded), &overrides); err != nil {
+		return nil, err
+	}
+	return &overrides, nil
+}
+
+// checkpointOverridesArgs is the ABI of a `CheckpointOverride[]`, as the aggregation program hashes it.
+var checkpointOverridesArgs = func() abi.Arguments {
+	overridesType, err := abi.NewType("tuple[]", "", []abi.ArgumentMarshaling{
+		{Name: "fromRoot", Type: "bytes32"},
+		{Name: "toRoot", Type: "bytes32"},
+		{Name: "justificationHash", Type: "bytes32"},
+	})
+	if err != nil {
+		panic(err)
+	}
+	return abi.Arguments{{Type: overridesType}}
+}()
+
+// Hash returns the hash the aggregation proof commits to, and the L2OO's owner approves: the keccak256 hash of the ABI
+// encoding of the gaps as a `CheckpointOverride[]`, each with the keccak256 hash of the justification.
+func (o *CheckpointOverrides) Hash() (common.Hash, error) {
+	type checkpointOverride struct {
+		FromRoot          [32]byte
+		ToRoot            [32]byte
+		JustificationHash [32]byte
+	}
+	justificationHash := crypto.Keccak256Hash([]byte(o.Justification))
+	overrides := make([]checkpointOverride, len(o.Gaps))
+	for i, gap := range o.Gaps {
+		overrides[i] = checkpointOverride{FromRoot: gap.FromRoot, ToRoot: gap.ToRoot, JustificationHash: justificationHash}
+	}
+	encoded, err := checkpointOverridesArgs.Pack(overrides)
+	if err != nil {
+		return common.Hash{}, fmt.Errorf("failed to encode checkpoint overrides: %w", err)
+	}
+	return crypto.Keccak256Hash(encoded), nil
+}
+
+// catchUpCheckpointOverrides returns the configured checkpoint overrides, as recorded with an AGG proof request, if
+// their first gap starts at the L2OO's latest output, i.e. the catch-up they are for hasn't been proposed yet. Returns
+// "" otherwise.
+func (l *L2OutputSubmitter) catchUpCheckpointOverrides(ctx context.Context) (string, error) {
+	overrides := l.Cfg.CheckpointOverrides
+	if overrides == nil {
+		return "", nil
+	}
+
+	callOpts := &bind.CallOpts{Context: ctx}
+	latestIndex, err := l.l2ooContract.LatestOutputIndex(callOpts)
+	if err != nil {
+		return "", fmt.Errorf("failed to get latest output index: %w", err)
+	}
+	latest, err := l.l2ooContract.GetL2Output(callOpts, latestIndex)
+	if err != nil {
+		return "", fmt.Errorf("failed to get latest output: %w", err)
+	}
+	if common.Hash(latest.OutputRoot) != overrides.Gaps[0].FromRoot {
+		return "", nil
+	}
+
+	recorded, err := json.Marshal(overrides)
+	if err != nil {
+		return "", fmt.Errorf("failed to marshal checkpoint overrides: %w", err)
+	}
+	return string(recorded), nil
+}
+
+// proposeL2OutputWithCheckpointOverridesTxData creates the transaction data proposing an output proven by an AGG proof
+// over the checkpoint overrides with the given hash, which the L2OO accepts once its owner approved them.
+func (l *L2OutputSubmitter) proposeL2OutputWithCheckpointOverridesTxData(p outputProposal) ([]byte, error) {
+	return l.l2ooABI.Pack(
+		"proposeL2OutputWithCheckpointOverrides",
+		p.output.OutputRoot,
+		new(big.Int).SetUint64(p.output.BlockRef.Number),
+		new(big.Int).SetUint64(p.l1BlockNum),
+		p.proof,
+		p.checkpointOverridesHash)
+}
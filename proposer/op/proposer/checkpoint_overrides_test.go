@@ -0,0 +1,90 @@
+package proposer
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/ethereum-optimism/optimism/op-service/eth"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/stretchr/testify/require"
+	opsuccinctbindings "github.com/succinctlabs/op-succinct-go/bindings"
+)
+
+const testJustification = "Blocks 100-200 force-resolved by governance"
+
+func TestCheckpointOverridesHash(t *testing.T) {
+	overrides := &CheckpointOverrides{
+		Gaps: []CheckpointGap{{
+			FromRoot: common.HexToHash("0x1111111111111111111111111111111111111111111111111111111111111111"),
+			ToRoot:   common.HexToHash("0x2222222222222222222222222222222222222222222222222222222222222222"),
+		}},
+		Justification: testJustification,
+	}
+	hash, err := overrides.Hash()
+	require.NoError(t, err)
+	require.Equal(t, common.HexToHash("0x88e0bc970824e92a3ee9c64d0d08648cb99beecb81431a036ae24ca72c2cf469"), hash)
+
+	overrides.Gaps = append(overrides.Gaps, CheckpointGap{
+		FromRoot: common.HexToHash("0x3333333333333333333333333333333333333333333333333333333333333333"),
+		ToRoot:   common.HexToHash("0x4444444444444444444444444444444444444444444444444444444444444444"),
+	})
+	hash, err = overrides.Hash()
+	require.NoError(t, err)
+	require.Equal(t, common.HexToHash("0x1d72927446af855968052fa37eb8eb919e9e7a13d57cedd3264c2ebdf0bd474b"), hash)
+}
+
+func TestLoadCheckpointOverrides(t *testing.T) {
+	write := func(contents string) string {
+		path := filepath.Join(t.TempDir(), "overrides.json")
+		require.NoError(t, os.WriteFile(path, []byte(contents), 0o600))
+		return path
+	}
+
+	overrides, err := LoadCheckpointOverrides(write(`{
+		"gaps": [{
+			"from_root": "0x1111111111111111111111111111111111111111111111111111111111111111",
+			"to_root": "0x2222222222222222222222222222222222222222222222222222222222222222"
+		}],
+		"justification": "` + testJustification + `"
+	}`))
+	require.NoError(t, err)
+	require.Len(t, overrides.Gaps, 1)
+	require.Equal(t, common.HexToHash("0x2222222222222222222222222222222222222222222222222222222222222222"), overrides.Gaps[0].ToRoot)
+	require.Equal(t, testJustification, overrides.Justification)
+
+	_, err = LoadCheckpointOverrides(write(`{"gaps": [], "justification": "` + testJustification + `"}`))
+	require.ErrorContains(t, err, "no gaps")
+
+	_, err = LoadCheckpointOverrides(write(`{
+		"gaps": [{
+			"from_root": "0x1111111111111111111111111111111111111111111111111111111111111111",
+			"to_root": "0x2222222222222222222222222222222222222222222222222222222222222222"
+		}],
+		"justification": " "
+	}`))
+	require.ErrorContains(t, err, "no justification")
+}
+
+func TestParseCheckpointOverridesWithoutOverrides(t *testing.T) {
+	overrides, err := parseCheckpointOverrides("")
+	require.NoError(t, err)
+	require.Nil(t, overrides)
+}
+
+func TestProposalTxDataWithCheckpointOverrides(t *testing.T) {
+	p := outputProposal{
+		output: &eth.OutputResponse{
+			OutputRoot: eth.Bytes32{0x01},
+			BlockRef:   eth.L2BlockRef{Number: 100},
+		},
+		proof:                   []byte{0x02},
+		l1BlockNum:              200,
+		checkpointOverridesHash: common.Hash{0x03},
+	}
+	l2ooAbi, err := opsuccinctbindings.OPSuccinctL2OutputOracleMetaData.GetAbi()
+	require.NoError(t, err)
+	data, err := (&L2OutputSubmitter{l2ooABI: l2ooAbi}).proposalTxData(p)
+	require.NoError(t, err)
+	require.Equal(t, l2ooAbi.Methods["proposeL2OutputWithCheckpointOverrides"].ID, data[:4])
+}
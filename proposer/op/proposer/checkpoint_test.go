@@ -0,0 +1,33 @@
+package proposer
+
+import (
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/stretchr/testify/require"
+)
+
+func TestDecideCheckpoint(t *testing.T) {
+	hash := common.Hash{0x01}
+	candidate := func(onChain common.Hash, canonical bool) candidateCheckpoint {
+		return candidateCheckpoint{
+			Checkpoint: Checkpoint{BlockNumber: 1000, BlockHash: hash, BlockTime: 500},
+			OnChain:    onChain,
+			Canonical:  canonical,
+		}
+	}
+
+	// A block hash checkpointed on-chain is used, even once it can't be checkpointed again.
+	require.Equal(t, checkpointUse, decideCheckpoint(candidate(hash, true), 2000, 400))
+	// Unless the block is older than the L1 heads of the span proofs.
+	require.Equal(t, checkpointReplace, decideCheckpoint(candidate(hash, true), 1001, 600))
+
+	// A block hash that isn't on-chain is checkpointed again while it's far enough from expiry.
+	require.Equal(t, checkpointRenew, decideCheckpoint(candidate(common.Hash{}, true), 1001, 400))
+	require.Equal(t, checkpointRenew, decideCheckpoint(candidate(common.Hash{}, true), 1000+BlockhashWindow-CheckpointExpiryMargin-1, 400))
+	require.Equal(t, checkpointReplace, decideCheckpoint(candidate(common.Hash{}, true), 1000+BlockhashWindow-CheckpointExpiryMargin, 400))
+
+	// A reorged block hash is replaced.
+	require.Equal(t, checkpointReplace, decideCheckpoint(candidate(common.Hash{}, false), 1001, 400))
+	require.Equal(t, checkpointReplace, decideCheckpoint(candidate(common.Hash{0x02}, true), 1001, 400))
+}
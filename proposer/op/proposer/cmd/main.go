@@ -36,6 +36,7 @@ func main() {
 			Name:        "doc",
 			Subcommands: doc.NewSubcommands(metrics.NewMetrics("default")),
 		},
+		proposer.StatusCommand,
 	}
 
 	err := app.Run(os.Args)
@@ -92,6 +92,24 @@ type CLIConfig struct {
 	MaxConcurrentProofRequests uint64
 	// Mock is a flag to use the mock OP Succinct server.
 	Mock bool
+	// The days to keep completed proof requests after the L2OO is past them. 0 keeps them forever.
+	FulfilledRetentionDays uint64
+	// The days to keep failed proof requests. 0 keeps them forever.
+	FailedRetentionDays uint64
+	// The directory pruned proof requests are archived to.
+	PruneArchiveDir string
+	// How frequently to poll the rollup node for its finalized head. 0 disables it.
+	SafeHeadPollInterval time.Duration
+	// The windows during which no outputs are proposed. See SubmissionWindow.
+	SubmissionWindows string
+	// The address to serve the query API on. Empty uses the RPC server's listen address.
+	QueryApiAddr string
+	// The port to serve the query API on. 0 disables it.
+	QueryApiPort int
+	// The max number of sequential outputs to propose in one transaction.
+	MaxProposalsPerTx uint64
+	// The path to the checkpoint overrides to aggregate over for emergency catch-up.
+	CheckpointOverridesFile string
 }
 
 func (c *CLIConfig) Check() error {
@@ -111,6 +129,18 @@ func (c *CLIConfig) Check() error {
 	if c.L2OOAddress == "" && c.DGFAddress == "" {
 		return errors.New("one of the `DisputeGameFactory` or `L2OutputOracle` address must be provided")
 	}
+	if _, err := ParseSubmissionWindows(c.SubmissionWindows); err != nil {
+		return err
+	}
+	if c.MaxProposalsPerTx == 0 {
+		return errors.New("the max proposals per transaction must be at least 1")
+	}
+	if c.MaxProposalsPerTx > 1 && c.DGFAddress != "" {
+		return errors.New("proposing several outputs per transaction is only supported with the `L2OutputOracle`")
+	}
+	if c.CheckpointOverridesFile != "" && c.DGFAddress != "" {
+		return errors.New("checkpoint overrides are only supported with the `L2OutputOracle`")
+	}
 
 	return nil
 }
@@ -131,6 +161,11 @@ func NewConfig(ctx *cli.Context) *CLIConfig {
 	dbPath := ctx.String(flags.DbPathFlag.Name)
 	dbPath = filepath.Join(dbPath, fmt.Sprintf("%d", rollupConfig.L2ChainID.Uint64()), "proofs.db")
 
+	pruneArchiveDir := ctx.String(flags.PruneArchiveDirFlag.Name)
+	if pruneArchiveDir == "" {
+		pruneArchiveDir = filepath.Join(filepath.Dir(dbPath), "archive")
+	}
+
 	return &CLIConfig{
 		// Required Flags
 		L1EthRpc:     ctx.String(flags.L1EthRpcFlag.Name),
@@ -159,6 +194,15 @@ func NewConfig(ctx *cli.Context) *CLIConfig {
 		MaxConcurrentProofRequests:   ctx.Uint64(flags.MaxConcurrentProofRequestsFlag.Name),
 		Mock:                         ctx.Bool(flags.MockFlag.Name),
 		DGFAddress:                   ctx.String(flags.DGFAddressFlag.Name),
+		FulfilledRetentionDays:       ctx.Uint64(flags.FulfilledRetentionDaysFlag.Name),
+		FailedRetentionDays:          ctx.Uint64(flags.FailedRetentionDaysFlag.Name),
+		PruneArchiveDir:              pruneArchiveDir,
+		SafeHeadPollInterval:         ctx.Duration(flags.SafeHeadPollIntervalFlag.Name),
+		SubmissionWindows:            ctx.String(flags.SubmissionWindowsFlag.Name),
+		QueryApiAddr:                 ctx.String(flags.QueryApiAddrFlag.Name),
+		QueryApiPort:                 ctx.Int(flags.QueryApiPortFlag.Name),
+		MaxProposalsPerTx:            ctx.Uint64(flags.MaxProposalsPerTxFlag.Name),
+		CheckpointOverridesFile:      ctx.String(flags.CheckpointOverridesFileFlag.Name),
 
 		// NOTE(fakedev9999): GameType 6 is the game type for the op-succinct proof system.
 		// See https://github.com/ethereum-optimism/optimism/blob/develop/op-challenger/game/fault/types/types.go#L33
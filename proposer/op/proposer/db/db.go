@@ -3,20 +3,29 @@ package db
 import (
 	"context"
 	"encoding/hex"
+	"errors"
 	"fmt"
 	"os"
 	"path/filepath"
 	"runtime"
+	"strconv"
+	"strings"
 	"time"
 
 	"entgo.io/ent/dialect/sql"
 
 	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/cycleusage"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/predicate"
 	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
 
 	_ "github.com/mattn/go-sqlite3"
 )
 
+// ErrNotPending is returned when a proof request was failed before its prover request ID was recorded, e.g. because
+// it was invalidated while its proof was requested.
+var ErrNotPending = errors.New("proof request is no longer pending")
+
 type ProofDB struct {
 	writeClient *ent.Client
 	readClient  *ent.Client
@@ -106,6 +115,32 @@ func (db *ProofDB) NewEntry(proofType proofrequest.Type, start, end uint64) erro
 	return nil
 }
 
+// NewRetryEntry creates a new proof request entry retrying a failed one over the same range. A retried SPAN proof keeps
+// the hash of the witness it was proven from, so the server can reuse its cached witness if it is unchanged, and a
+// retried AGG proof the checkpoint overrides it aggregates over.
+func (db *ProofDB) NewRetryEntry(req *ent.ProofRequest) error {
+	now := uint64(time.Now().Unix())
+	create := db.writeClient.ProofRequest.
+		Create().
+		SetType(req.Type).
+		SetStartBlock(req.StartBlock).
+		SetEndBlock(req.EndBlock).
+		SetStatus(proofrequest.StatusUNREQ).
+		SetRequestAddedTime(now).
+		SetLastUpdatedTime(now)
+	if req.WitnessSha256 != "" {
+		create.SetWitnessSha256(req.WitnessSha256)
+	}
+	if req.CheckpointOverrides != "" {
+		create.SetCheckpointOverrides(req.CheckpointOverrides)
+	}
+	if _, err := create.Save(context.Background()); err != nil {
+		return fmt.Errorf("failed to create retry entry: %w", err)
+	}
+
+	return nil
+}
+
 // UpdateProofStatus updates the status of a proof request in the database.
 func (db *ProofDB) UpdateProofStatus(id int, proofStatus proofrequest.Status) error {
 	_, err := db.writeClient.ProofRequest.Update().
@@ -117,27 +152,159 @@ func (db *ProofDB) UpdateProofStatus(id int, proofStatus proofrequest.Status) er
 	return err
 }
 
-// SetProverRequestID sets the prover request ID for a proof request in the database.
-func (db *ProofDB) SetProverRequestID(id int, proverRequestID []byte) error {
-	// Convert the []byte to a hex string.
-	proverRequestIDHex := hex.EncodeToString(proverRequestID)
-
-	_, err := db.writeClient.ProofRequest.Update().
+// SetFailed sets the status of a proof request to FAILED, recording the error code of why it failed if known.
+func (db *ProofDB) SetFailed(id int, errorCode string) error {
+	update := db.writeClient.ProofRequest.Update().
 		Where(proofrequest.ID(id)).
-		SetProverRequestID(proverRequestIDHex).
-		SetProofRequestTime(uint64(time.Now().Unix())).
+		SetStatus(proofrequest.StatusFAILED).
+		SetLastUpdatedTime(uint64(time.Now().Unix()))
+	if errorCode != "" {
+		update.SetErrorCode(errorCode)
+	}
+	_, err := update.Save(context.Background())
+
+	return err
+}
+
+// Requeue moves a proof request in witness generation back to UNREQ without recording a failure, for a request the
+// server turned away without attempting it. An AGG proof's session is cleared, so it records its span proofs again
+// when it is requested. Returns ErrNotPending if the request is no longer in witness generation.
+func (db *ProofDB) Requeue(id int) error {
+	updated, err := db.writeClient.ProofRequest.Update().
+		Where(
+			proofrequest.ID(id),
+			proofrequest.StatusEQ(proofrequest.StatusWITNESSGEN),
+		).
+		SetStatus(proofrequest.StatusUNREQ).
+		ClearSubproofs().
 		SetLastUpdatedTime(uint64(time.Now().Unix())).
 		Save(context.Background())
 
 	if err != nil {
-		return fmt.Errorf("failed to set prover network id: %w", err)
+		return fmt.Errorf("failed to requeue proof request: %w", err)
+	}
+	if updated == 0 {
+		return fmt.Errorf("proof request %d: %w", id, ErrNotPending)
 	}
 
 	return nil
 }
 
-// AddFulfilledProof adds a proof to a proof request in the database and sets the status to COMPLETE.
-func (db *ProofDB) AddFulfilledProof(id int, proof []byte) error {
+// SetProving sets the status of a proof request to PROVING along with its prover request ID, in a single update so a
+// crash never leaves a request PROVING without the ID to poll. witnessSha256 is the hash of the witness a SPAN proof is
+// proven from, and is empty for AGG proofs. Returns ErrNotPending if the request is no longer unrequested or in witness
+// generation.
+func (db *ProofDB) SetProving(id int, proverRequestID []byte, witnessSha256 string) error {
+	now := uint64(time.Now().Unix())
+	update := db.writeClient.ProofRequest.Update().
+		Where(
+			proofrequest.ID(id),
+			proofrequest.StatusIn(proofrequest.StatusUNREQ, proofrequest.StatusWITNESSGEN),
+		).
+		SetStatus(proofrequest.StatusPROVING).
+		SetProverRequestID(hex.EncodeToString(proverRequestID)).
+		SetProofRequestTime(now).
+		SetLastUpdatedTime(now)
+	if witnessSha256 != "" {
+		update.SetWitnessSha256(witnessSha256)
+	}
+	updated, err := update.Save(context.Background())
+
+	if err != nil {
+		return fmt.Errorf("failed to set proof status to proving: %w", err)
+	}
+	if updated == 0 {
+		return fmt.Errorf("proof request %d: %w", id, ErrNotPending)
+	}
+
+	return nil
+}
+
+// InvalidateProofs fails the proofs of the given types that end after `latestOutput`, the L2OO's latest block, and are
+// being proven or are proven but not yet submitted, recording `errorCode`. Each failed SPAN proof is queued to be
+// proven again, without the witness hash of the failed one, in the same transaction. Queued AGG proofs are failed too,
+// and the aggregate stage queues them again once their span proofs are proven. Returns the failed proofs.
+func (db *ProofDB) InvalidateProofs(types []proofrequest.Type, latestOutput uint64, errorCode string) ([]*ent.ProofRequest, error) {
+	ctx := context.Background()
+	tx, err := db.writeClient.Tx(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to start transaction: %w", err)
+	}
+	defer tx.Rollback()
+
+	proofs, err := tx.ProofRequest.Query().
+		Where(
+			proofrequest.TypeIn(types...),
+			proofrequest.EndBlockGT(latestOutput),
+			proofrequest.Or(
+				proofrequest.StatusIn(proofrequest.StatusWITNESSGEN, proofrequest.StatusPROVING, proofrequest.StatusCOMPLETE),
+				proofrequest.And(
+					proofrequest.TypeEQ(proofrequest.TypeAGG),
+					proofrequest.StatusEQ(proofrequest.StatusUNREQ),
+				),
+			),
+		).
+		Order(ent.Asc(proofrequest.FieldStartBlock)).
+		All(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to query proofs to invalidate: %w", err)
+	}
+	if len(proofs) == 0 {
+		return nil, nil
+	}
+
+	now := uint64(time.Now().Unix())
+	ids := make([]int, len(proofs))
+	var retries []*ent.ProofRequestCreate
+	for i, proof := range proofs {
+		ids[i] = proof.ID
+		if proof.Type == proofrequest.TypeSPAN {
+			retries = append(retries, tx.ProofRequest.Create().
+				SetType(proof.Type).
+				SetStartBlock(proof.StartBlock).
+				SetEndBlock(proof.EndBlock).
+				SetStatus(proofrequest.StatusUNREQ).
+				SetRequestAddedTime(now).
+				SetLastUpdatedTime(now))
+		}
+	}
+
+	_, err = tx.ProofRequest.Update().
+		Where(proofrequest.IDIn(ids...)).
+		SetStatus(proofrequest.StatusFAILED).
+		SetErrorCode(errorCode).
+		SetLastUpdatedTime(now).
+		Save(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to fail invalidated proofs: %w", err)
+	}
+	if len(retries) > 0 {
+		if _, err := tx.ProofRequest.CreateBulk(retries...).Save(ctx); err != nil {
+			return nil, fmt.Errorf("failed to create retry entries: %w", err)
+		}
+	}
+
+	if err := tx.Commit(); err != nil {
+		return nil, fmt.Errorf("failed to commit transaction: %w", err)
+	}
+	return proofs, nil
+}
+
+// FulfilledUsage is the zkVM cycles the prover network billed a fulfilled proof for.
+type FulfilledUsage struct {
+	ChainID     uint64
+	Cycles      uint64
+	FulfilledAt time.Time
+}
+
+// UsageDay returns the start of the UTC day cycle usage at t is totalled in.
+func UsageDay(t time.Time) time.Time {
+	return t.UTC().Truncate(24 * time.Hour)
+}
+
+// AddFulfilledProof adds a proof to a proof request in the database and sets the status to COMPLETE. If usage is set,
+// the proof's cycles are added to the daily cycle usage of its chain in the same transaction, so they are counted once.
+func (db *ProofDB) AddFulfilledProof(id int, proof []byte, usage *FulfilledUsage) error {
 	// Start a transaction
 	tx, err := db.writeClient.Tx(context.Background())
 	if err != nil {
@@ -176,6 +343,12 @@ func (db *ProofDB) AddFulfilledProof(id int, proof []byte) error {
 		return fmt.Errorf("failed to update proof and status: %w", err)
 	}
 
+	if usage != nil {
+		if err := addCycleUsage(tx, existingProof.Type, *usage); err != nil {
+			return err
+		}
+	}
+
 	// Commit the transaction
 	if err := tx.Commit(); err != nil {
 		return fmt.Errorf("failed to commit transaction: %w", err)
@@ -184,6 +357,56 @@ func (db *ProofDB) AddFulfilledProof(id int, proof []byte) error {
 	return nil
 }
 
+// addCycleUsage adds a fulfilled proof to the cycle usage of its chain and type on the day it was fulfilled.
+func addCycleUsage(tx *ent.Tx, proofType proofrequest.Type, usage FulfilledUsage) error {
+	day := uint64(UsageDay(usage.FulfilledAt).Unix())
+	usageType := cycleusage.Type(proofType)
+	updated, err := tx.CycleUsage.Update().
+		Where(
+			cycleusage.ChainID(usage.ChainID),
+			cycleusage.Day(day),
+			cycleusage.TypeEQ(usageType),
+		).
+		AddProofs(1).
+		AddCycles(int64(usage.Cycles)).
+		Save(context.Background())
+	if err != nil {
+		return fmt.Errorf("failed to update cycle usage: %w", err)
+	}
+	if updated > 0 {
+		return nil
+	}
+
+	_, err = tx.CycleUsage.Create().
+		SetType(usageType).
+		SetChainID(usage.ChainID).
+		SetDay(day).
+		SetProofs(1).
+		SetCycles(usage.Cycles).
+		Save(context.Background())
+	if err != nil {
+		return fmt.Errorf("failed to create cycle usage: %w", err)
+	}
+
+	return nil
+}
+
+// GetCycleUsage returns the daily cycle usage of the UTC days from from to to, inclusive, ordered by day.
+func (db *ProofDB) GetCycleUsage(from, to time.Time) ([]*ent.CycleUsage, error) {
+	usage, err := db.readClient.CycleUsage.Query().
+		Where(
+			cycleusage.DayGTE(uint64(UsageDay(from).Unix())),
+			cycleusage.DayLTE(uint64(UsageDay(to).Unix())),
+		).
+		Order(ent.Asc(cycleusage.FieldDay), ent.Asc(cycleusage.FieldChainID), ent.Asc(cycleusage.FieldType)).
+		All(context.Background())
+	if err != nil {
+		return nil, fmt.Errorf("failed to query cycle usage: %w", err)
+	}
+
+	return usage, nil
+}
+
 // GetNumberOfProofsWithStatuses returns the number of proofs with the given status(es).
 func (db *ProofDB) GetNumberOfRequestsWithStatuses(statuses ...proofrequest.Status) (int, error) {
 	count, err := db.readClient.ProofRequest.Query().
@@ -314,42 +537,50 @@ func (db *ProofDB) GetNextUnrequestedProof() (*ent.ProofRequest, error) {
 	return spanProof, nil
 }
 
-// GetAllCompletedAggProofs returns all completed AGG proofs for a given start block.
-func (db *ProofDB) GetAllCompletedAggProofs(startBlock uint64) ([]*ent.ProofRequest, error) {
+// GetUnrequestedProofs returns up to `limit` unrequested proofs of the given type, lowest start block first.
+func (db *ProofDB) GetUnrequestedProofs(proofType proofrequest.Type, limit int) ([]*ent.ProofRequest, error) {
 	proofs, err := db.readClient.ProofRequest.Query().
 		Where(
-			proofrequest.TypeEQ(proofrequest.TypeAGG),
-			proofrequest.StartBlockEQ(startBlock),
-			proofrequest.StatusEQ(proofrequest.StatusCOMPLETE),
+			proofrequest.StatusEQ(proofrequest.StatusUNREQ),
+			proofrequest.TypeEQ(proofType),
 		).
+		Order(ent.Asc(proofrequest.FieldStartBlock)).
+		Limit(limit).
 		All(context.Background())
 
 	if err != nil {
-		if ent.IsNotFound(err) {
-			return nil, nil
-		}
-		return nil, fmt.Errorf("failed to query completed AGG proof: %w", err)
+		return nil, fmt.Errorf("failed to query unrequested %s proofs: %w", proofType, err)
 	}
 
 	return proofs, nil
 }
 
-// TryCreateAggProofFromSpanProofs tries to create an AGG proof from the span proofs that cover the range [from, minTo).
-// Returns true if a new AGG proof was created, false otherwise.
-func (db *ProofDB) TryCreateAggProofFromSpanProofs(from, minTo uint64) (bool, uint64, error) {
-	// If there's already an AGG proof in progress/completed with the same start block, return.
-	count, err := db.readClient.ProofRequest.Query().
+// GetCompletedAggProofsFrom returns all completed AGG proofs starting at or after a block.
+func (db *ProofDB) GetCompletedAggProofsFrom(startBlock uint64) ([]*ent.ProofRequest, error) {
+	proofs, err := db.readClient.ProofRequest.Query().
 		Where(
 			proofrequest.TypeEQ(proofrequest.TypeAGG),
-			proofrequest.StartBlockEQ(from),
-			proofrequest.StatusNEQ(proofrequest.StatusFAILED),
+			proofrequest.StartBlockGTE(startBlock),
+			proofrequest.StatusEQ(proofrequest.StatusCOMPLETE),
 		).
-		Count(context.Background())
+		All(context.Background())
+	if err != nil {
+		return nil, fmt.Errorf("failed to query completed AGG proofs: %w", err)
+	}
+
+	return proofs, nil
+}
+
+// TryCreateAggProofFromSpanProofs tries to create an AGG proof from the span proofs that cover the range [from, minTo).
+// The checkpoint overrides it aggregates over, if not empty, are recorded with it. Returns true if a new AGG proof was
+// created, false otherwise.
+func (db *ProofDB) TryCreateAggProofFromSpanProofs(from, minTo uint64, checkpointOverrides string) (bool, uint64, error) {
+	// If there's already an AGG proof in progress/completed with the same start block, return.
+	exists, err := db.HasAggProof(from)
 	if err != nil {
-		return false, 0, fmt.Errorf("failed to query DB for AGG proof with start block %d: %w", from, err)
+		return false, 0, err
 	}
-	if count > 0 {
-		// There's already an AGG proof in progress with the same start block.
+	if exists {
 		return false, 0, nil
 	}
 
@@ -365,8 +596,19 @@ func (db *ProofDB) TryCreateAggProofFromSpanProofs(from, minTo uint64) (bool, ui
 	}
 
 	// Create a new AGG proof request
-	err = db.NewEntry("AGG", from, maxContigousEnd)
-	if err != nil {
+	now := uint64(time.Now().Unix())
+	create := db.writeClient.ProofRequest.
+		Create().
+		SetType(proofrequest.TypeAGG).
+		SetStartBlock(from).
+		SetEndBlock(maxContigousEnd).
+		SetStatus(proofrequest.StatusUNREQ).
+		SetRequestAddedTime(now).
+		SetLastUpdatedTime(now)
+	if checkpointOverrides != "" {
+		create.SetCheckpointOverrides(checkpointOverrides)
+	}
+	if _, err := create.Save(context.Background()); err != nil {
 		return false, 0, fmt.Errorf("failed to insert AGG proof request: %w", err)
 	}
 
@@ -374,36 +616,60 @@ func (db *ProofDB) TryCreateAggProofFromSpanProofs(from, minTo uint64) (bool, ui
 }
 
 // GetMaxContiguousSpanProofRange returns the start and end of the contiguous span proof chain.
-// TODO: The logic here is not resilient to manually inserted requests where two completed proof requests overlap in a
-// non-contiguous way.
 func (db *ProofDB) GetMaxContiguousSpanProofRange(start uint64) (uint64, error) {
-	ctx := context.Background()
+	spans, err := db.GetContiguousSpanProofs(start)
+	if err != nil {
+		return 0, err
+	}
 
-	query := db.readClient.ProofRequest.Query().
+	// The end is at minimum the start block, and at maximum the end block of the last span proof.
+	if len(spans) == 0 {
+		return start, nil
+	}
+	return spans[len(spans)-1].EndBlock, nil
+}
+
+// GetContiguousSpanProofs returns the chain of completed span proofs starting at the given block, each starting at the
+// end block of the previous one.
+// TODO: The logic here is not resilient to manually inserted requests where two completed proof requests overlap in a
+// non-contiguous way.
+func (db *ProofDB) GetContiguousSpanProofs(start uint64) ([]*ent.ProofRequest, error) {
+	spans, err := db.readClient.ProofRequest.Query().
 		Where(
 			proofrequest.TypeEQ(proofrequest.TypeSPAN),
 			proofrequest.StatusEQ(proofrequest.StatusCOMPLETE),
 			proofrequest.StartBlockGTE(start),
 		).
-		Order(ent.Asc(proofrequest.FieldStartBlock))
-
-	// Execute the query.
-	spans, err := query.All(ctx)
+		Order(ent.Asc(proofrequest.FieldStartBlock)).
+		All(context.Background())
 	if err != nil {
-		return 0, fmt.Errorf("failed to query span proofs: %w", err)
+		return nil, fmt.Errorf("failed to query span proofs: %w", err)
 	}
 
 	// Keep iterating until we can't find another contiguous span proof.
 	currentBlock := start
-	for _, span := range spans {
+	for i, span := range spans {
 		if span.StartBlock != currentBlock {
-			break
+			return spans[:i], nil
 		}
 		currentBlock = span.EndBlock
 	}
+	return spans, nil
+}
 
-	// The current block is at minimum the start block, and at maximum the end block of the last span proof.
-	return currentBlock, nil
+// HasAggProof returns whether there's an AGG proof starting at the given block that hasn't failed.
+func (db *ProofDB) HasAggProof(from uint64) (bool, error) {
+	count, err := db.readClient.ProofRequest.Query().
+		Where(
+			proofrequest.TypeEQ(proofrequest.TypeAGG),
+			proofrequest.StartBlockEQ(from),
+			proofrequest.StatusNEQ(proofrequest.StatusFAILED),
+		).
+		Count(context.Background())
+	if err != nil {
+		return false, fmt.Errorf("failed to query DB for AGG proof with start block %d: %w", from, err)
+	}
+	return count > 0, nil
 }
 
 // GetConsecutiveSpanProofs returns the span proofs that cover the range [start, end].
@@ -428,22 +694,178 @@ func (db *ProofDB) GetConsecutiveSpanProofs(start, end uint64) ([][]byte, error)
 	}
 
 	// Verify that the proofs are consecutive and cover the entire range.
-	var result [][]byte
-	currentBlock := start
+	if err := checkConsecutiveSpans(spans, start, end); err != nil {
+		return nil, err
+	}
+	result := make([][]byte, len(spans))
+	for i, span := range spans {
+		result[i] = span.Proof
+	}
+	return result, nil
+}
+
+// GetLatestSpanProofRequestTime returns the latest proof request time of the completed span proofs in the range
+// [start, end], or 0 if there are none. The server picks the L1 head of a span proof before the proof is requested, so
+// an L1 block from no earlier than this time is not older than the L1 head of any of the span proofs.
+func (db *ProofDB) GetLatestSpanProofRequestTime(start, end uint64) (uint64, error) {
+	latest, err := db.readClient.ProofRequest.Query().
+		Where(
+			proofrequest.TypeEQ(proofrequest.TypeSPAN),
+			proofrequest.StatusEQ(proofrequest.StatusCOMPLETE),
+			proofrequest.StartBlockGTE(start),
+			proofrequest.EndBlockLTE(end),
+		).
+		Order(ent.Desc(proofrequest.FieldProofRequestTime)).
+		Select(proofrequest.FieldProofRequestTime).
+		First(context.Background())
+	if err != nil {
+		if ent.IsNotFound(err) {
+			return 0, nil
+		}
+		return 0, fmt.Errorf("failed to get latest span proof request time: %w", err)
+	}
+	return latest.ProofRequestTime, nil
+}
+
+// StartAggSession moves an unrequested AGG proof to WITNESSGEN and records the IDs of the span proofs it aggregates, in
+// one transaction. The session is requested with exactly these span proofs, including when it is resumed after a
+// restart, so the server recognizes the request and returns the proof it already requested.
+func (db *ProofDB) StartAggSession(id int) (*ent.ProofRequest, error) {
+	ctx := context.Background()
+	tx, err := db.writeClient.Tx(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to start transaction: %w", err)
+	}
+	defer tx.Rollback()
+
+	agg, err := tx.ProofRequest.Get(ctx, id)
+	if err != nil {
+		return nil, fmt.Errorf("failed to find AGG proof: %w", err)
+	}
+	if agg.Type != proofrequest.TypeAGG || agg.Status != proofrequest.StatusUNREQ {
+		return nil, fmt.Errorf("proof request %d is not an unrequested AGG proof", id)
+	}
+
+	spans, err := tx.ProofRequest.Query().
+		Where(
+			proofrequest.TypeEQ(proofrequest.TypeSPAN),
+			proofrequest.StatusEQ(proofrequest.StatusCOMPLETE),
+			proofrequest.StartBlockGTE(agg.StartBlock),
+			proofrequest.EndBlockLTE(agg.EndBlock),
+		).
+		Order(ent.Asc(proofrequest.FieldStartBlock)).
+		All(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to query span proofs: %w", err)
+	}
+	if err := checkConsecutiveSpans(spans, agg.StartBlock, agg.EndBlock); err != nil {
+		return nil, err
+	}
+	ids := make([]int, len(spans))
+	for i, span := range spans {
+		ids[i] = span.ID
+	}
 
+	agg, err = tx.ProofRequest.UpdateOne(agg).
+		SetStatus(proofrequest.StatusWITNESSGEN).
+		SetSubproofs(formatSubproofIDs(ids)).
+		SetLastUpdatedTime(uint64(time.Now().Unix())).
+		Save(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("failed to start AGG session: %w", err)
+	}
+
+	if err := tx.Commit(); err != nil {
+		return nil, fmt.Errorf("failed to commit transaction: %w", err)
+	}
+	return agg, nil
+}
+
+// GetAggSessionSpanProofs returns the proofs of the span proofs recorded for an AGG proof's session, in order.
+func (db *ProofDB) GetAggSessionSpanProofs(agg *ent.ProofRequest) ([][]byte, error) {
+	ids, err := parseSubproofIDs(agg.Subproofs)
+	if err != nil {
+		return nil, err
+	}
+	spans, err := db.readClient.ProofRequest.Query().
+		Where(proofrequest.IDIn(ids...)).
+		All(context.Background())
+	if err != nil {
+		return nil, fmt.Errorf("failed to query span proofs: %w", err)
+	}
+	byID := make(map[int]*ent.ProofRequest, len(spans))
+	for _, span := range spans {
+		byID[span.ID] = span
+	}
+
+	proofs := make([][]byte, len(ids))
+	for i, id := range ids {
+		span, ok := byID[id]
+		if !ok || span.Status != proofrequest.StatusCOMPLETE {
+			return nil, fmt.Errorf("span proof %d of AGG proof %d is no longer available", id, agg.ID)
+		}
+		proofs[i] = span.Proof
+	}
+	return proofs, nil
+}
+
+// GetResumableAggSessions returns the AGG proofs whose session started but whose proof request wasn't recorded, e.g.
+// because the proposer stopped while the server built the request.
+func (db *ProofDB) GetResumableAggSessions() ([]*ent.ProofRequest, error) {
+	sessions, err := db.readClient.ProofRequest.Query().
+		Where(
+			proofrequest.TypeEQ(proofrequest.TypeAGG),
+			proofrequest.StatusEQ(proofrequest.StatusWITNESSGEN),
+			proofrequest.SubproofsNotNil(),
+			proofrequest.SubproofsNEQ(""),
+		).
+		Order(ent.Asc(proofrequest.FieldStartBlock)).
+		All(context.Background())
+	if err != nil {
+		return nil, fmt.Errorf("failed to query AGG sessions: %w", err)
+	}
+	return sessions, nil
+}
+
+// checkConsecutiveSpans checks that the span proofs, ordered by start block, cover [start, end] without gaps.
+func checkConsecutiveSpans(spans []*ent.ProofRequest, start, end uint64) error {
+	currentBlock := start
 	for _, span := range spans {
 		if span.StartBlock != currentBlock {
-			return nil, fmt.Errorf("gap in proof chain: expected start block %d, got %d", currentBlock, span.StartBlock)
+			return fmt.Errorf("gap in proof chain: expected start block %d, got %d", currentBlock, span.StartBlock)
 		}
-		result = append(result, span.Proof)
 		currentBlock = span.EndBlock
 	}
-
 	if currentBlock != end {
-		return nil, fmt.Errorf("incomplete proof chain: ends at block %d, expected %d", currentBlock, end)
+		return fmt.Errorf("incomplete proof chain: ends at block %d, expected %d", currentBlock, end)
 	}
+	return nil
+}
 
-	return result, nil
+// formatSubproofIDs formats the IDs of an AGG proof's span proofs as stored in the subproofs field.
+func formatSubproofIDs(ids []int) string {
+	parts := make([]string, len(ids))
+	for i, id := range ids {
+		parts[i] = strconv.Itoa(id)
+	}
+	return strings.Join(parts, ",")
+}
+
+// parseSubproofIDs parses the subproofs field of an AGG proof.
+func parseSubproofIDs(subproofs string) ([]int, error) {
+	if subproofs == "" {
+		return nil, fmt.Errorf("no subproofs recorded")
+	}
+	parts := strings.Split(subproofs, ",")
+	ids := make([]int, len(parts))
+	for i, part := range parts {
+		id, err := strconv.Atoi(part)
+		if err != nil {
+			return nil, fmt.Errorf("invalid subproof ID %q: %w", part, err)
+		}
+		ids[i] = id
+	}
+	return ids, nil
 }
 
 // Get the proofs with start block and end block of a specific status.
@@ -460,4 +882,167 @@ func (db *ProofDB) GetProofRequestsWithBlockRangeAndStatus(proofType proofreques
 		return nil, fmt.Errorf("failed to query proofs with block range and status: %w", err)
 	}
 	return proofs, nil
-}
\ No newline at end of file
+}
+
+// ProofRequestQuery filters, sorts and paginates proof requests. Zero values don't filter.
+type ProofRequestQuery struct {
+	Type   proofrequest.Type
+	Status proofrequest.Status
+	// ErrorCode selects the FAILED requests that failed with the error code.
+	ErrorCode string
+	// FromBlock and ToBlock select the requests within [FromBlock, ToBlock].
+	FromBlock uint64
+	ToBlock   uint64
+	// SortField is the field to sort by, the ID if empty. Ties are sorted by ID.
+	SortField  string
+	Descending bool
+	// Limit is the maximum number of requests to return, all if 0.
+	Limit  int
+	Offset int
+}
+
+// QueryProofRequests returns the page of proof requests matching the query, and the number of requests matching it
+// across all pages.
+func (db *ProofDB) QueryProofRequests(q ProofRequestQuery) ([]*ent.ProofRequest, int, error) {
+	var predicates []predicate.ProofRequest
+	if q.Type != "" {
+		predicates = append(predicates, proofrequest.TypeEQ(q.Type))
+	}
+	if q.Status != "" {
+		predicates = append(predicates, proofrequest.StatusEQ(q.Status))
+	}
+	if q.ErrorCode != "" {
+		predicates = append(predicates, proofrequest.ErrorCodeEQ(q.ErrorCode))
+	}
+	if q.FromBlock != 0 {
+		predicates = append(predicates, proofrequest.StartBlockGTE(q.FromBlock))
+	}
+	if q.ToBlock != 0 {
+		predicates = append(predicates, proofrequest.EndBlockLTE(q.ToBlock))
+	}
+	query := db.readClient.ProofRequest.Query().Where(predicates...)
+
+	total, err := query.Clone().Count(context.Background())
+	if err != nil {
+		return nil, 0, fmt.Errorf("failed to count proof requests: %w", err)
+	}
+
+	sortField := q.SortField
+	if sortField == "" {
+		sortField = proofrequest.FieldID
+	}
+	if !proofrequest.ValidColumn(sortField) {
+		return nil, 0, fmt.Errorf("invalid sort field %q", sortField)
+	}
+	order := ent.Asc
+	if q.Descending {
+		order = ent.Desc
+	}
+	query = query.Order(order(sortField), order(proofrequest.FieldID)).Offset(q.Offset)
+	if q.Limit != 0 {
+		query = query.Limit(q.Limit)
+	}
+	proofs, err := query.All(context.Background())
+	if err != nil {
+		return nil, 0, fmt.Errorf("failed to query proof requests: %w", err)
+	}
+
+	return proofs, total, nil
+}
+
+// GetCompletedAggProofsEndingAt returns the completed AGG proofs ending at any of the given blocks.
+func (db *ProofDB) GetCompletedAggProofsEndingAt(endBlocks []uint64) ([]*ent.ProofRequest, error) {
+	proofs, err := db.readClient.ProofRequest.Query().
+		Where(
+			proofrequest.TypeEQ(proofrequest.TypeAGG),
+			proofrequest.StatusEQ(proofrequest.StatusCOMPLETE),
+			proofrequest.EndBlockIn(endBlocks...),
+		).
+		Order(ent.Asc(proofrequest.FieldID)).
+		All(context.Background())
+	if err != nil {
+		return nil, fmt.Errorf("failed to query completed agg proofs: %w", err)
+	}
+
+	return proofs, nil
+}
+
+// GetOldestPendingRequest returns the unrequested, witness generating or proving request that was added
+// first, or nil if there is none.
+func (db *ProofDB) GetOldestPendingRequest() (*ent.ProofRequest, error) {
+	proof, err := db.readClient.ProofRequest.Query().
+		Where(
+			proofrequest.StatusIn(proofrequest.StatusUNREQ, proofrequest.StatusWITNESSGEN, proofrequest.StatusPROVING),
+		).
+		Order(ent.Asc(proofrequest.FieldRequestAddedTime)).
+		First(context.Background())
+	if err != nil {
+		if ent.IsNotFound(err) {
+			return nil, nil
+		}
+		return nil, fmt.Errorf("failed to query oldest pending request: %w", err)
+	}
+
+	return proof, nil
+}
+
+// GetRecentFailures returns the last `limit` requests that failed, most recent first.
+func (db *ProofDB) GetRecentFailures(limit int) ([]*ent.ProofRequest, error) {
+	proofs, err := db.readClient.ProofRequest.Query().
+		Where(
+			proofrequest.StatusEQ(proofrequest.StatusFAILED),
+		).
+		Order(ent.Desc(proofrequest.FieldLastUpdatedTime)).
+		Limit(limit).
+		All(context.Background())
+	if err != nil {
+		return nil, fmt.Errorf("failed to query recent failures: %w", err)
+	}
+
+	return proofs, nil
+}
+
+// GetPrunableProofs returns the completed requests last updated before `completedBefore` that end at or before
+// `latestOutput`, which the proposer no longer needs once the L2OO is past them, and the failed requests last
+// updated before `failedBefore`. A zero time keeps all requests of that status.
+func (db *ProofDB) GetPrunableProofs(completedBefore, failedBefore time.Time, latestOutput uint64) ([]*ent.ProofRequest, error) {
+	var predicates []predicate.ProofRequest
+	if !completedBefore.IsZero() {
+		predicates = append(predicates, proofrequest.And(
+			proofrequest.StatusEQ(proofrequest.StatusCOMPLETE),
+			proofrequest.EndBlockLTE(latestOutput),
+			proofrequest.LastUpdatedTimeLT(uint64(completedBefore.Unix())),
+		))
+	}
+	if !failedBefore.IsZero() {
+		predicates = append(predicates, proofrequest.And(
+			proofrequest.StatusEQ(proofrequest.StatusFAILED),
+			proofrequest.LastUpdatedTimeLT(uint64(failedBefore.Unix())),
+		))
+	}
+	if len(predicates) == 0 {
+		return nil, nil
+	}
+
+	proofs, err := db.readClient.ProofRequest.Query().
+		Where(proofrequest.Or(predicates...)).
+		Order(ent.Asc(proofrequest.FieldID)).
+		All(context.Background())
+	if err != nil {
+		return nil, fmt.Errorf("failed to query prunable proofs: %w", err)
+	}
+
+	return proofs, nil
+}
+
+// DeleteProofs deletes the requests with the given IDs, returning the number deleted.
+func (db *ProofDB) DeleteProofs(ids []int) (int, error) {
+	deleted, err := db.writeClient.ProofRequest.Delete().
+		Where(proofrequest.IDIn(ids...)).
+		Exec(context.Background())
+	if err != nil {
+		return 0, fmt.Errorf("failed to delete proofs: %w", err)
+	}
+
+	return deleted, nil
+}
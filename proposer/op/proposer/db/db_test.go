@@ -0,0 +1,274 @@
+package db
+
+import (
+	"context"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/require"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/cycleusage"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
+)
+
+func TestAggSession(t *testing.T) {
+	db, err := InitDB(filepath.Join(t.TempDir(), "proofs.db"), false)
+	require.NoError(t, err)
+	defer db.CloseDB()
+	ctx := context.Background()
+
+	for _, span := range [][2]uint64{{100, 150}, {150, 200}} {
+		_, err := db.writeClient.ProofRequest.Create().
+			SetType(proofrequest.TypeSPAN).
+			SetStartBlock(span[0]).
+			SetEndBlock(span[1]).
+			SetStatus(proofrequest.StatusCOMPLETE).
+			SetRequestAddedTime(0).
+			SetLastUpdatedTime(0).
+			SetProof([]byte{byte(span[0])}).
+			Save(ctx)
+		require.NoError(t, err)
+	}
+	require.NoError(t, db.NewEntry(proofrequest.TypeAGG, 100, 200))
+	agg, err := db.writeClient.ProofRequest.Query().Where(proofrequest.TypeEQ(proofrequest.TypeAGG)).Only(ctx)
+	require.NoError(t, err)
+
+	// Starting the session records the span proofs, and the session can only be started once.
+	agg, err = db.StartAggSession(agg.ID)
+	require.NoError(t, err)
+	require.Equal(t, proofrequest.StatusWITNESSGEN, agg.Status)
+	require.Equal(t, "1,2", agg.Subproofs)
+	_, err = db.StartAggSession(agg.ID)
+	require.Error(t, err)
+
+	proofs, err := db.GetAggSessionSpanProofs(agg)
+	require.NoError(t, err)
+	require.Equal(t, [][]byte{{100}, {150}}, proofs)
+
+	// The session is resumable until its proof request is recorded.
+	sessions, err := db.GetResumableAggSessions()
+	require.NoError(t, err)
+	require.Len(t, sessions, 1)
+	require.NoError(t, db.SetProving(agg.ID, []byte{0xab}, ""))
+	sessions, err = db.GetResumableAggSessions()
+	require.NoError(t, err)
+	require.Empty(t, sessions)
+
+	agg, err = db.readClient.ProofRequest.Get(ctx, agg.ID)
+	require.NoError(t, err)
+	require.Equal(t, proofrequest.StatusPROVING, agg.Status)
+	require.Equal(t, "ab", agg.ProverRequestID)
+}
+
+func TestRequeue(t *testing.T) {
+	db, err := InitDB(filepath.Join(t.TempDir(), "proofs.db"), false)
+	require.NoError(t, err)
+	defer db.CloseDB()
+	ctx := context.Background()
+
+	_, err = db.writeClient.ProofRequest.Create().
+		SetType(proofrequest.TypeSPAN).
+		SetStartBlock(100).
+		SetEndBlock(200).
+		SetStatus(proofrequest.StatusCOMPLETE).
+		SetRequestAddedTime(0).
+		SetLastUpdatedTime(0).
+		SetProof([]byte{1}).
+		Save(ctx)
+	require.NoError(t, err)
+	require.NoError(t, db.NewEntry(proofrequest.TypeAGG, 100, 200))
+	agg, err := db.writeClient.ProofRequest.Query().Where(proofrequest.TypeEQ(proofrequest.TypeAGG)).Only(ctx)
+	require.NoError(t, err)
+	agg, err = db.StartAggSession(agg.ID)
+	require.NoError(t, err)
+
+	// The request is queued again as is, without a failure, and its session can be started again.
+	require.NoError(t, db.Requeue(agg.ID))
+	agg, err = db.readClient.ProofRequest.Get(ctx, agg.ID)
+	require.NoError(t, err)
+	require.Equal(t, proofrequest.StatusUNREQ, agg.Status)
+	require.Empty(t, agg.Subproofs)
+	require.Empty(t, agg.ErrorCode)
+	failed, err := db.GetProofRequestsWithBlockRangeAndStatus(proofrequest.TypeAGG, 100, 200, proofrequest.StatusFAILED)
+	require.NoError(t, err)
+	require.Empty(t, failed)
+	_, err = db.StartAggSession(agg.ID)
+	require.NoError(t, err)
+
+	require.NoError(t, db.SetProving(agg.ID, []byte{0xab}, ""))
+	require.ErrorIs(t, db.Requeue(agg.ID), ErrNotPending)
+}
+
+func TestRetryKeepsWitnessSha256(t *testing.T) {
+	db, err := InitDB(filepath.Join(t.TempDir(), "proofs.db"), false)
+	require.NoError(t, err)
+	defer db.CloseDB()
+	ctx := context.Background()
+
+	require.NoError(t, db.NewEntry(proofrequest.TypeSPAN, 100, 110))
+	span, err := db.readClient.ProofRequest.Query().Only(ctx)
+	require.NoError(t, err)
+	require.NoError(t, db.SetProving(span.ID, []byte{0xab}, "0x01"))
+	span, err = db.readClient.ProofRequest.Get(ctx, span.ID)
+	require.NoError(t, err)
+	require.Equal(t, "0x01", span.WitnessSha256)
+
+	// The retry is a new request over the same range, with the witness hash of the failed one.
+	require.NoError(t, db.NewRetryEntry(span))
+	retry, err := db.readClient.ProofRequest.Query().Where(proofrequest.StatusEQ(proofrequest.StatusUNREQ)).Only(ctx)
+	require.NoError(t, err)
+	require.Equal(t, uint64(100), retry.StartBlock)
+	require.Equal(t, uint64(110), retry.EndBlock)
+	require.Equal(t, "0x01", retry.WitnessSha256)
+}
+
+func TestAggProofRecordsCheckpointOverrides(t *testing.T) {
+	db, err := InitDB(filepath.Join(t.TempDir(), "proofs.db"), false)
+	require.NoError(t, err)
+	defer db.CloseDB()
+	ctx := context.Background()
+
+	_, err = db.writeClient.ProofRequest.Create().
+		SetType(proofrequest.TypeSPAN).
+		SetStartBlock(100).
+		SetEndBlock(110).
+		SetStatus(proofrequest.StatusCOMPLETE).
+		SetRequestAddedTime(0).
+		SetLastUpdatedTime(0).
+		Save(ctx)
+	require.NoError(t, err)
+
+	// The AGG request records the checkpoint overrides it aggregates over, with their justification.
+	overrides := `{"gaps":[{"from_root":"0x11","to_root":"0x22"}],"justification":"force-resolved by governance"}`
+	created, end, err := db.TryCreateAggProofFromSpanProofs(100, 110, overrides)
+	require.NoError(t, err)
+	require.True(t, created)
+	require.Equal(t, uint64(110), end)
+	agg, err := db.readClient.ProofRequest.Query().Where(proofrequest.TypeEQ(proofrequest.TypeAGG)).Only(ctx)
+	require.NoError(t, err)
+	require.Equal(t, overrides, agg.CheckpointOverrides)
+
+	// A retry aggregates over the same overrides.
+	require.NoError(t, db.NewRetryEntry(agg))
+	retries, err := db.readClient.ProofRequest.Query().Where(proofrequest.TypeEQ(proofrequest.TypeAGG)).All(ctx)
+	require.NoError(t, err)
+	require.Len(t, retries, 2)
+	require.Equal(t, overrides, retries[1].CheckpointOverrides)
+}
+
+func TestInvalidateProofs(t *testing.T) {
+	db, err := InitDB(filepath.Join(t.TempDir(), "proofs.db"), false)
+	require.NoError(t, err)
+	defer db.CloseDB()
+	ctx := context.Background()
+
+	create := func(proofType proofrequest.Type, start, end uint64, status proofrequest.Status) int {
+		req, err := db.writeClient.ProofRequest.Create().
+			SetType(proofType).
+			SetStartBlock(start).
+			SetEndBlock(end).
+			SetStatus(status).
+			SetRequestAddedTime(0).
+			SetLastUpdatedTime(0).
+			SetWitnessSha256("0x01").
+			Save(ctx)
+		require.NoError(t, err)
+		return req.ID
+	}
+	submitted := create(proofrequest.TypeSPAN, 50, 100, proofrequest.StatusCOMPLETE)
+	complete := create(proofrequest.TypeSPAN, 100, 150, proofrequest.StatusCOMPLETE)
+	witnessgen := create(proofrequest.TypeSPAN, 150, 200, proofrequest.StatusWITNESSGEN)
+	queued := create(proofrequest.TypeSPAN, 200, 250, proofrequest.StatusUNREQ)
+	agg := create(proofrequest.TypeAGG, 100, 150, proofrequest.StatusUNREQ)
+
+	// Only the AGG proof is stale after a new aggregation vkey.
+	invalidated, err := db.InvalidateProofs([]proofrequest.Type{proofrequest.TypeAGG}, 100, "E_STALE_CONFIG")
+	require.NoError(t, err)
+	require.Len(t, invalidated, 1)
+	require.Equal(t, agg, invalidated[0].ID)
+
+	// The span proofs past the L2OO's latest block that are proven or being proven are failed and queued again.
+	invalidated, err = db.InvalidateProofs([]proofrequest.Type{proofrequest.TypeSPAN, proofrequest.TypeAGG}, 100, "E_STALE_CONFIG")
+	require.NoError(t, err)
+	require.Len(t, invalidated, 2)
+	require.Equal(t, complete, invalidated[0].ID)
+	require.Equal(t, witnessgen, invalidated[1].ID)
+	for _, id := range []int{agg, complete, witnessgen} {
+		req, err := db.readClient.ProofRequest.Get(ctx, id)
+		require.NoError(t, err)
+		require.Equal(t, proofrequest.StatusFAILED, req.Status)
+		require.Equal(t, "E_STALE_CONFIG", req.ErrorCode)
+	}
+	for _, id := range []int{submitted, queued} {
+		req, err := db.readClient.ProofRequest.Get(ctx, id)
+		require.NoError(t, err)
+		require.NotEqual(t, proofrequest.StatusFAILED, req.Status)
+	}
+	retries, err := db.readClient.ProofRequest.Query().
+		Where(proofrequest.StatusEQ(proofrequest.StatusUNREQ), proofrequest.IDNEQ(queued)).
+		Order(ent.Asc(proofrequest.FieldStartBlock)).
+		All(ctx)
+	require.NoError(t, err)
+	require.Len(t, retries, 2)
+	require.Equal(t, uint64(100), retries[0].StartBlock)
+	require.Equal(t, uint64(150), retries[1].StartBlock)
+	// The witness may differ under a new rollup config.
+	require.Empty(t, retries[0].WitnessSha256)
+
+	// A worker that was generating the witness of an invalidated request can't move it to PROVING.
+	require.ErrorIs(t, db.SetProving(witnessgen, []byte{0xab}, "0x02"), ErrNotPending)
+}
+
+func TestCycleUsage(t *testing.T) {
+	db, err := InitDB(filepath.Join(t.TempDir(), "proofs.db"), false)
+	require.NoError(t, err)
+	defer db.CloseDB()
+
+	day := time.Date(2024, 6, 1, 0, 0, 0, 0, time.UTC)
+	fulfill := func(proofType proofrequest.Type, start uint64, usage *FulfilledUsage) {
+		require.NoError(t, db.NewEntry(proofType, start, start+10))
+		proofs, err := db.GetProofRequestsWithBlockRangeAndStatus(proofType, start, start+10, proofrequest.StatusUNREQ)
+		require.NoError(t, err)
+		require.NoError(t, db.SetProving(proofs[0].ID, []byte{0xab}, ""))
+		require.NoError(t, db.AddFulfilledProof(proofs[0].ID, []byte{0x01}, usage))
+	}
+	fulfill(proofrequest.TypeSPAN, 100, &FulfilledUsage{ChainID: 10, Cycles: 1000, FulfilledAt: day.Add(time.Hour)})
+	fulfill(proofrequest.TypeSPAN, 110, &FulfilledUsage{ChainID: 10, Cycles: 500, FulfilledAt: day.Add(23 * time.Hour)})
+	fulfill(proofrequest.TypeAGG, 100, &FulfilledUsage{ChainID: 10, Cycles: 200, FulfilledAt: day.Add(2 * time.Hour)})
+	fulfill(proofrequest.TypeSPAN, 120, &FulfilledUsage{ChainID: 10, Cycles: 700, FulfilledAt: day.Add(25 * time.Hour)})
+	// Mock proofs aren't billed.
+	fulfill(proofrequest.TypeSPAN, 130, nil)
+
+	usage, err := db.GetCycleUsage(day, day)
+	require.NoError(t, err)
+	require.Len(t, usage, 2)
+	require.Equal(t, cycleusage.TypeAGG, usage[0].Type)
+	require.Equal(t, uint64(day.Unix()), usage[0].Day)
+	require.Equal(t, uint64(1), usage[0].Proofs)
+	require.Equal(t, uint64(200), usage[0].Cycles)
+	require.Equal(t, cycleusage.TypeSPAN, usage[1].Type)
+	require.Equal(t, uint64(10), usage[1].ChainID)
+	require.Equal(t, uint64(2), usage[1].Proofs)
+	require.Equal(t, uint64(1500), usage[1].Cycles)
+
+	// Usage is kept when the proofs are pruned.
+	_, err = db.DeleteProofs([]int{1, 2, 3, 4, 5})
+	require.NoError(t, err)
+	usage, err = db.GetCycleUsage(day, day.AddDate(0, 0, 1))
+	require.NoError(t, err)
+	require.Len(t, usage, 3)
+	require.Equal(t, uint64(day.AddDate(0, 0, 1).Unix()), usage[2].Day)
+	require.Equal(t, uint64(700), usage[2].Cycles)
+}
+
+func TestParseSubproofIDs(t *testing.T) {
+	ids, err := parseSubproofIDs(formatSubproofIDs([]int{3, 1, 2}))
+	require.NoError(t, err)
+	require.Equal(t, []int{3, 1, 2}, ids)
+
+	_, err = parseSubproofIDs("")
+	require.Error(t, err)
+	_, err = parseSubproofIDs("1,x")
+	require.Error(t, err)
+}
@@ -14,6 +14,7 @@ import (
 	"entgo.io/ent"
 	"entgo.io/ent/dialect"
 	"entgo.io/ent/dialect/sql"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/cycleusage"
 	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
 )
 
@@ -22,6 +23,8 @@ type Client struct {
 	config
 	// Schema is the client for creating, migrating and dropping schema.
 	Schema *migrate.Schema
+	// CycleUsage is the client for interacting with the CycleUsage builders.
+	CycleUsage *CycleUsageClient
 	// ProofRequest is the client for interacting with the ProofRequest builders.
 	ProofRequest *ProofRequestClient
 }
@@ -35,6 +38,7 @@ func NewClient(opts ...Option) *Client {
 
 func (c *Client) init() {
 	c.Schema = migrate.NewSchema(c.driver)
+	c.CycleUsage = NewCycleUsageClient(c.config)
 	c.ProofRequest = NewProofRequestClient(c.config)
 }
 
@@ -128,6 +132,7 @@ func (c *Client) Tx(ctx context.Context) (*Tx, error) {
 	return &Tx{
 		ctx:          ctx,
 		config:       cfg,
+		CycleUsage:   NewCycleUsageClient(cfg),
 		ProofRequest: NewProofRequestClient(cfg),
 	}, nil
 }
@@ -148,6 +153,7 @@ func (c *Client) BeginTx(ctx context.Context, opts *sql.TxOptions) (*Tx, error)
 	return &Tx{
 		ctx:          ctx,
 		config:       cfg,
+		CycleUsage:   NewCycleUsageClient(cfg),
 		ProofRequest: NewProofRequestClient(cfg),
 	}, nil
 }
@@ -155,7 +161,7 @@ func (c *Client) BeginTx(ctx context.Context, opts *sql.TxOptions) (*Tx, error)
 // Debug returns a new debug-client. It's used to get verbose logging on specific operations.
 //
 //	client.Debug().
-//		ProofRequest.
+//		CycleUsage.
 //		Query().
 //		Count(ctx)
 func (c *Client) Debug() *Client {
@@ -177,18 +183,28 @@ func (c *Client) Close() error {
 // Use adds the mutation hooks to all the entity clients.
 // In order to add hooks to a specific client, call: `client.Node.Use(...)`.
 func (c *Client) Use(hooks ...Hook) {
-	c.ProofRequest.Use(hooks...)
+	for _, n := range []interface{ Use(...Hook) }{
+		c.CycleUsage, c.ProofRequest,
+	} {
+		n.Use(hooks...)
+	}
 }
 
 // Intercept adds the query interceptors to all the entity clients.
 // In order to add interceptors to a specific client, call: `client.Node.Intercept(...)`.
 func (c *Client) Intercept(interceptors ...Interceptor) {
-	c.ProofRequest.Intercept(interceptors...)
+	for _, n := range []interface{ Intercept(...Interceptor) }{
+		c.CycleUsage, c.ProofRequest,
+	} {
+		n.Intercept(interceptors...)
+	}
 }
 
 // Mutate implements the ent.Mutator interface.
 func (c *Client) Mutate(ctx context.Context, m Mutation) (Value, error) {
 	switch m := m.(type) {
+	case *CycleUsageMutation:
+		return c.CycleUsage.mutate(ctx, m)
 	case *ProofRequestMutation:
 		return c.ProofRequest.mutate(ctx, m)
 	default:
@@ -196,6 +212,139 @@ func (c *Client) Mutate(ctx context.Context, m Mutation) (Value, error) {
 	}
 }
 
+// CycleUsageClient is a client for the CycleUsage schema.
+type CycleUsageClient struct {
+	config
+}
+
+// NewCycleUsageClient returns a client for the CycleUsage from the given config.
+func NewCycleUsageClient(c config) *CycleUsageClient {
+	return &CycleUsageClient{config: c}
+}
+
+// Use adds a list of mutation hooks to the hooks stack.
+// A call to `Use(f, g, h)` equals to `cycleusage.Hooks(f(g(h())))`.
+func (c *CycleUsageClient) Use(hooks ...Hook) {
+	c.hooks.CycleUsage = append(c.hooks.CycleUsage, hooks...)
+}
+
+// Intercept adds a list of query interceptors to the interceptors stack.
+// A call to `Intercept(f, g, h)` equals to `cycleusage.Intercept(f(g(h())))`.
+func (c *CycleUsageClient) Intercept(interceptors ...Interceptor) {
+	c.inters.CycleUsage = append(c.inters.CycleUsage, interceptors...)
+}
+
+// Create returns a builder for creating a CycleUsage entity.
+func (c *CycleUsageClient) Create() *CycleUsageCreate {
+	mutation := newCycleUsageMutation(c.config, OpCreate)
+	return &CycleUsageCreate{config: c.config, hooks: c.Hooks(), mutation: mutation}
+}
+
+// CreateBulk returns a builder for creating a bulk of CycleUsage entities.
+func (c *CycleUsageClient) CreateBulk(builders ...*CycleUsageCreate) *CycleUsageCreateBulk {
+	return &CycleUsageCreateBulk{config: c.config, builders: builders}
+}
+
+// MapCreateBulk creates a bulk creation builder from the given slice. For each item in the slice, the function creates
+// a builder and applies setFunc on it.
+func (c *CycleUsageClient) MapCreateBulk(slice any, setFunc func(*CycleUsageCreate, int)) *CycleUsageCreateBulk {
+	rv := reflect.ValueOf(slice)
+	if rv.Kind() != reflect.Slice {
+		return &CycleUsageCreateBulk{err: fmt.Errorf("calling to CycleUsageClient.MapCreateBulk with wrong type %T, need slice", slice)}
+	}
+	builders := make([]*CycleUsageCreate, rv.Len())
+	for i := 0; i < rv.Len(); i++ {
+		builders[i] = c.Create()
+		setFunc(builders[i], i)
+	}
+	return &CycleUsageCreateBulk{config: c.config, builders: builders}
+}
+
+// Update returns an update builder for CycleUsage.
+func (c *CycleUsageClient) Update() *CycleUsageUpdate {
+	mutation := newCycleUsageMutation(c.config, OpUpdate)
+	return &CycleUsageUpdate{config: c.config, hooks: c.Hooks(), mutation: mutation}
+}
+
+// UpdateOne returns an update builder for the given entity.
+func (c *CycleUsageClient) UpdateOne(cu *CycleUsage) *CycleUsageUpdateOne {
+	mutation := newCycleUsageMutation(c.config, OpUpdateOne, withCycleUsage(cu))
+	return &CycleUsageUpdateOne{config: c.config, hooks: c.Hooks(), mutation: mutation}
+}
+
+// UpdateOneID returns an update builder for the given id.
+func (c *CycleUsageClient) UpdateOneID(id int) *CycleUsageUpdateOne {
+	mutation := newCycleUsageMutation(c.config, OpUpdateOne, withCycleUsageID(id))
+	return &CycleUsageUpdateOne{config: c.config, hooks: c.Hooks(), mutation: mutation}
+}
+
+// Delete returns a delete builder for CycleUsage.
+func (c *CycleUsageClient) Delete() *CycleUsageDelete {
+	mutation := newCycleUsageMutation(c.config, OpDelete)
+	return &CycleUsageDelete{config: c.config, hooks: c.Hooks(), mutation: mutation}
+}
+
+// DeleteOne returns a builder for deleting the given entity.
+func (c *CycleUsageClient) DeleteOne(cu *CycleUsage) *CycleUsageDeleteOne {
+	return c.DeleteOneID(cu.ID)
+}
+
+// DeleteOneID returns a builder for deleting the given entity by its id.
+func (c *CycleUsageClient) DeleteOneID(id int) *CycleUsageDeleteOne {
+	builder := c.Delete().Where(cycleusage.ID(id))
+	builder.mutation.id = &id
+	builder.mutation.op = OpDeleteOne
+	return &CycleUsageDeleteOne{builder}
+}
+
+// Query returns a query builder for CycleUsage.
+func (c *CycleUsageClient) Query() *CycleUsageQuery {
+	return &CycleUsageQuery{
+		config: c.config,
+		ctx:    &QueryContext{Type: TypeCycleUsage},
+		inters: c.Interceptors(),
+	}
+}
+
+// Get returns a CycleUsage entity by its id.
+func (c *CycleUsageClient) Get(ctx context.Context, id int) (*CycleUsage, error) {
+	return c.Query().Where(cycleusage.ID(id)).Only(ctx)
+}
+
+// GetX is like Get, but panics if an error occurs.
+func (c *CycleUsageClient) GetX(ctx context.Context, id int) *CycleUsage {
+	obj, err := c.Get(ctx, id)
+	if err != nil {
+		panic(err)
+	}
+	return obj
+}
+
+// Hooks returns the client hooks.
+func (c *CycleUsageClient) Hooks() []Hook {
+	return c.hooks.CycleUsage
+}
+
+// Interceptors returns the client interceptors.
+func (c *CycleUsageClient) Interceptors() []Interceptor {
+	return c.inters.CycleUsage
+}
+
+func (c *CycleUsageClient) mutate(ctx context.Context, m *CycleUsageMutation) (Value, error) {
+	switch m.Op() {
+	case OpCreate:
+		return (&CycleUsageCreate{config: c.config, hooks: c.Hooks(), mutation: m}).Save(ctx)
+	case OpUpdate:
+		return (&CycleUsageUpdate{config: c.config, hooks: c.Hooks(), mutation: m}).Save(ctx)
+	case OpUpdateOne:
+		return (&CycleUsageUpdateOne{config: c.config, hooks: c.Hooks(), mutation: m}).Save(ctx)
+	case OpDelete, OpDeleteOne:
+		return (&CycleUsageDelete{config: c.config, hooks: c.Hooks(), mutation: m}).Exec(ctx)
+	default:
+		return nil, fmt.Errorf("ent: unknown CycleUsage mutation op: %q", m.Op())
+	}
+}
+
 // ProofRequestClient is a client for the ProofRequest schema.
 type ProofRequestClient struct {
 	config
@@ -332,9 +481,9 @@ func (c *ProofRequestClient) mutate(ctx context.Context, m *ProofRequestMutation
 // hooks and interceptors per client, for fast access.
 type (
 	hooks struct {
-		ProofRequest []ent.Hook
+		CycleUsage, ProofRequest []ent.Hook
 	}
 	inters struct {
-		ProofRequest []ent.Interceptor
+		CycleUsage, ProofRequest []ent.Interceptor
 	}
 )
@@ -0,0 +1,147 @@
+// Code generated by ent, DO NOT EDIT.
+
+package ent
+
+import (
+	"fmt"
+	"strings"
+
+	"entgo.io/ent"
+	"entgo.io/ent/dialect/sql"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/cycleusage"
+)
+
+// CycleUsage is the model entity for the CycleUsage schema.
+type CycleUsage struct {
+	config `json:"-"`
+	// ID of the ent.
+	ID int `json:"id,omitempty"`
+	// Type holds the value of the "type" field.
+	Type cycleusage.Type `json:"type,omitempty"`
+	// ChainID holds the value of the "chain_id" field.
+	ChainID uint64 `json:"chain_id,omitempty"`
+	// Day holds the value of the "day" field.
+	Day uint64 `json:"day,omitempty"`
+	// Proofs holds the value of the "proofs" field.
+	Proofs uint64 `json:"proofs,omitempty"`
+	// Cycles holds the value of the "cycles" field.
+	Cycles uint64 `json:"cycles,omitempty"`
+	selectValues sql.SelectValues
+}
+
+// scanValues returns the types for scanning values from sql.Rows.
+func (*CycleUsage) scanValues(columns []string) ([]any, error) {
+	values := make([]any, len(columns))
+	for i := range columns {
+		switch columns[i] {
+		case cycleusage.FieldID, cycleusage.FieldChainID, cycleusage.FieldDay, cycleusage.FieldProofs, cycleusage.FieldCycles:
+			values[i] = new(sql.NullInt64)
+		case cycleusage.FieldType:
+			values[i] = new(sql.NullString)
+		default:
+			values[i] = new(sql.UnknownType)
+		}
+	}
+	return values, nil
+}
+
+// assignValues assigns the values that were returned from sql.Rows (after scanning)
+// to the CycleUsage fields.
+func (cu *CycleUsage) assignValues(columns []string, values []any) error {
+	if m, n := len(values), len(columns); m < n {
+		return fmt.Errorf("mismatch number of scan values: %d != %d", m, n)
+	}
+	for i := range columns {
+		switch columns[i] {
+		case cycleusage.FieldID:
+			value, ok := values[i].(*sql.NullInt64)
+			if !ok {
+				return fmt.Errorf("unexpected type %T for field id", value)
+			}
+			cu.ID = int(value.Int64)
+		case cycleusage.FieldType:
+			if value, ok := values[i].(*sql.NullString); !ok {
+				return fmt.Errorf("unexpected type %T for field type", values[i])
+			} else if value.Valid {
+				cu.Type = cycleusage.Type(value.String)
+			}
+		case cycleusage.FieldChainID:
+			if value, ok := values[i].(*sql.NullInt64); !ok {
+				return fmt.Errorf("unexpected type %T for field chain_id", values[i])
+			} else if value.Valid {
+				cu.ChainID = uint64(value.Int64)
+			}
+		case cycleusage.FieldDay:
+			if value, ok := values[i].(*sql.NullInt64); !ok {
+				return fmt.Errorf("unexpected type %T for field day", values[i])
+			} else if value.Valid {
+				cu.Day = uint64(value.Int64)
+			}
+		case cycleusage.FieldProofs:
+			if value, ok := values[i].(*sql.NullInt64); !ok {
+				return fmt.Errorf("unexpected type %T for field proofs", values[i])
+			} else if value.Valid {
+				cu.Proofs = uint64(value.Int64)
+			}
+		case cycleusage.FieldCycles:
+			if value, ok := values[i].(*sql.NullInt64); !ok {
+				return fmt.Errorf("unexpected type %T for field cycles", values[i])
+			} else if value.Valid {
+				cu.Cycles = uint64(value.Int64)
+			}
+		default:
+			cu.selectValues.Set(columns[i], values[i])
+		}
+	}
+	return nil
+}
+
+// Value returns the ent.Value that was dynamically selected and assigned to the CycleUsage.
+// This includes values selected through modifiers, order, etc.
+func (cu *CycleUsage) Value(name string) (ent.Value, error) {
+	return cu.selectValues.Get(name)
+}
+
+// Update returns a builder for updating this CycleUsage.
+// Note that you need to call CycleUsage.Unwrap() before calling this method if this CycleUsage
+// was returned from a transaction, and the transaction was committed or rolled back.
+func (cu *CycleUsage) Update() *CycleUsageUpdateOne {
+	return NewCycleUsageClient(cu.config).UpdateOne(cu)
+}
+
+// Unwrap unwraps the CycleUsage entity that was returned from a transaction after it was closed,
+// so that all future queries will be executed through the driver which created the transaction.
+func (cu *CycleUsage) Unwrap() *CycleUsage {
+	_tx, ok := cu.config.driver.(*txDriver)
+	if !ok {
+		panic("ent: CycleUsage is not a transactional entity")
+	}
+	cu.config.driver = _tx.drv
+	return cu
+}
+
+// String implements the fmt.Stringer.
+func (cu *CycleUsage) String() string {
+	var builder strings.Builder
+	builder.WriteString("CycleUsage(")
+	builder.WriteString(fmt.Sprintf("id=%v, ", cu.ID))
+	builder.WriteString("type=")
+	builder.WriteString(fmt.Sprintf("%v", cu.Type))
+	builder.WriteString(", ")
+	builder.WriteString("chain_id=")
+	builder.WriteString(fmt.Sprintf("%v", cu.ChainID))
+	builder.WriteString(", ")
+	builder.WriteString("day=")
+	builder.WriteString(fmt.Sprintf("%v", cu.Day))
+	builder.WriteString(", ")
+	builder.WriteString("proofs=")
+	builder.WriteString(fmt.Sprintf("%v", cu.Proofs))
+	builder.WriteString(", ")
+	builder.WriteString("cycles=")
+	builder.WriteString(fmt.Sprintf("%v", cu.Cycles))
+	builder.WriteByte(')')
+	return builder.String()
+}
+
+// CycleUsages is a parsable slice of CycleUsage.
+type CycleUsages []*CycleUsage
@@ -0,0 +1,104 @@
+// Code generated by ent, DO NOT EDIT.
+
+package cycleusage
+
+import (
+	"fmt"
+
+	"entgo.io/ent/dialect/sql"
+)
+
+const (
+	// Label holds the string label denoting the cycleusage type in the database.
+	Label = "cycle_usage"
+	// FieldID holds the string denoting the id field in the database.
+	FieldID = "id"
+	// FieldType holds the string denoting the type field in the database.
+	FieldType = "type"
+	// FieldChainID holds the string denoting the chain_id field in the database.
+	FieldChainID = "chain_id"
+	// FieldDay holds the string denoting the day field in the database.
+	FieldDay = "day"
+	// FieldProofs holds the string denoting the proofs field in the database.
+	FieldProofs = "proofs"
+	// FieldCycles holds the string denoting the cycles field in the database.
+	FieldCycles = "cycles"
+	// Table holds the table name of the cycleusage in the database.
+	Table = "cycle_usages"
+)
+
+// Columns holds all SQL columns for cycleusage fields.
+var Columns = []string{
+	FieldID,
+	FieldType,
+	FieldChainID,
+	FieldDay,
+	FieldProofs,
+	FieldCycles,
+}
+
+// ValidColumn reports if the column name is valid (part of the table columns).
+func ValidColumn(column string) bool {
+	for i := range Columns {
+		if column == Columns[i] {
+			return true
+		}
+	}
+	return false
+}
+
+// Type defines the type for the "type" enum field.
+type Type string
+
+// Type values.
+const (
+	TypeSPAN Type = "SPAN"
+	TypeAGG  Type = "AGG"
+)
+
+func (_type Type) String() string {
+	return string(_type)
+}
+
+// TypeValidator is a validator for the "type" field enum values. It is called by the builders before save.
+func TypeValidator(_type Type) error {
+	switch _type {
+	case TypeSPAN, TypeAGG:
+		return nil
+	default:
+		return fmt.Errorf("cycleusage: invalid enum value for type field: %q", _type)
+	}
+}
+
+// OrderOption defines the ordering options for the CycleUsage queries.
+type OrderOption func(*sql.Selector)
+
+// ByID orders the results by the id field.
+func ByID(opts ...sql.OrderTermOption) OrderOption {
+	return sql.OrderByField(FieldID, opts...).ToFunc()
+}
+
+// ByType orders the results by the type field.
+func ByType(opts ...sql.OrderTermOption) OrderOption {
+	return sql.OrderByField(FieldType, opts...).ToFunc()
+}
+
+// ByChainID orders the results by the chain_id field.
+func ByChainID(opts ...sql.OrderTermOption) OrderOption {
+	return sql.OrderByField(FieldChainID, opts...).ToFunc()
+}
+
+// ByDay orders the results by the day field.
+func ByDay(opts ...sql.OrderTermOption) OrderOption {
+	return sql.OrderByField(FieldDay, opts...).ToFunc()
+}
+
+// ByProofs orders the results by the proofs field.
+func ByProofs(opts ...sql.OrderTermOption) OrderOption {
+	return sql.OrderByField(FieldProofs, opts...).ToFunc()
+}
+
+// ByCycles orders the results by the cycles field.
+func ByCycles(opts ...sql.OrderTermOption) OrderOption {
+	return sql.OrderByField(FieldCycles, opts...).ToFunc()
+}
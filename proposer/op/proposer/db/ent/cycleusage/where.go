@@ -0,0 +1,268 @@
+// Code generated by ent, DO NOT EDIT.
+
+package cycleusage
+
+import (
+	"entgo.io/ent/dialect/sql"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/predicate"
+)
+
+// ID filters vertices based on their ID field.
+func ID(id int) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldEQ(FieldID, id))
+}
+
+// IDEQ applies the EQ predicate on the ID field.
+func IDEQ(id int) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldEQ(FieldID, id))
+}
+
+// IDNEQ applies the NEQ predicate on the ID field.
+func IDNEQ(id int) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNEQ(FieldID, id))
+}
+
+// IDIn applies the In predicate on the ID field.
+func IDIn(ids ...int) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldIn(FieldID, ids...))
+}
+
+// IDNotIn applies the NotIn predicate on the ID field.
+func IDNotIn(ids ...int) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNotIn(FieldID, ids...))
+}
+
+// IDGT applies the GT predicate on the ID field.
+func IDGT(id int) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldGT(FieldID, id))
+}
+
+// IDGTE applies the GTE predicate on the ID field.
+func IDGTE(id int) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldGTE(FieldID, id))
+}
+
+// IDLT applies the LT predicate on the ID field.
+func IDLT(id int) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldLT(FieldID, id))
+}
+
+// IDLTE applies the LTE predicate on the ID field.
+func IDLTE(id int) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldLTE(FieldID, id))
+}
+
+// ChainID applies equality check predicate on the "chain_id" field. It's identical to ChainIDEQ.
+func ChainID(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldEQ(FieldChainID, v))
+}
+
+// Day applies equality check predicate on the "day" field. It's identical to DayEQ.
+func Day(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldEQ(FieldDay, v))
+}
+
+// Proofs applies equality check predicate on the "proofs" field. It's identical to ProofsEQ.
+func Proofs(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldEQ(FieldProofs, v))
+}
+
+// Cycles applies equality check predicate on the "cycles" field. It's identical to CyclesEQ.
+func Cycles(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldEQ(FieldCycles, v))
+}
+
+// TypeEQ applies the EQ predicate on the "type" field.
+func TypeEQ(v Type) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldEQ(FieldType, v))
+}
+
+// TypeNEQ applies the NEQ predicate on the "type" field.
+func TypeNEQ(v Type) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNEQ(FieldType, v))
+}
+
+// TypeIn applies the In predicate on the "type" field.
+func TypeIn(vs ...Type) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldIn(FieldType, vs...))
+}
+
+// TypeNotIn applies the NotIn predicate on the "type" field.
+func TypeNotIn(vs ...Type) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNotIn(FieldType, vs...))
+}
+
+// ChainIDEQ applies the EQ predicate on the "chain_id" field.
+func ChainIDEQ(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldEQ(FieldChainID, v))
+}
+
+// ChainIDNEQ applies the NEQ predicate on the "chain_id" field.
+func ChainIDNEQ(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNEQ(FieldChainID, v))
+}
+
+// ChainIDIn applies the In predicate on the "chain_id" field.
+func ChainIDIn(vs ...uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldIn(FieldChainID, vs...))
+}
+
+// ChainIDNotIn applies the NotIn predicate on the "chain_id" field.
+func ChainIDNotIn(vs ...uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNotIn(FieldChainID, vs...))
+}
+
+// ChainIDGT applies the GT predicate on the "chain_id" field.
+func ChainIDGT(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldGT(FieldChainID, v))
+}
+
+// ChainIDGTE applies the GTE predicate on the "chain_id" field.
+func ChainIDGTE(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldGTE(FieldChainID, v))
+}
+
+// ChainIDLT applies the LT predicate on the "chain_id" field.
+func ChainIDLT(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldLT(FieldChainID, v))
+}
+
+// ChainIDLTE applies the LTE predicate on the "chain_id" field.
+func ChainIDLTE(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldLTE(FieldChainID, v))
+}
+
+// DayEQ applies the EQ predicate on the "day" field.
+func DayEQ(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldEQ(FieldDay, v))
+}
+
+// DayNEQ applies the NEQ predicate on the "day" field.
+func DayNEQ(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNEQ(FieldDay, v))
+}
+
+// DayIn applies the In predicate on the "day" field.
+func DayIn(vs ...uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldIn(FieldDay, vs...))
+}
+
+// DayNotIn applies the NotIn predicate on the "day" field.
+func DayNotIn(vs ...uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNotIn(FieldDay, vs...))
+}
+
+// DayGT applies the GT predicate on the "day" field.
+func DayGT(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldGT(FieldDay, v))
+}
+
+// DayGTE applies the GTE predicate on the "day" field.
+func DayGTE(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldGTE(FieldDay, v))
+}
+
+// DayLT applies the LT predicate on the "day" field.
+func DayLT(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldLT(FieldDay, v))
+}
+
+// DayLTE applies the LTE predicate on the "day" field.
+func DayLTE(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldLTE(FieldDay, v))
+}
+
+// ProofsEQ applies the EQ predicate on the "proofs" field.
+func ProofsEQ(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldEQ(FieldProofs, v))
+}
+
+// ProofsNEQ applies the NEQ predicate on the "proofs" field.
+func ProofsNEQ(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNEQ(FieldProofs, v))
+}
+
+// ProofsIn applies the In predicate on the "proofs" field.
+func ProofsIn(vs ...uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldIn(FieldProofs, vs...))
+}
+
+// ProofsNotIn applies the NotIn predicate on the "proofs" field.
+func ProofsNotIn(vs ...uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNotIn(FieldProofs, vs...))
+}
+
+// ProofsGT applies the GT predicate on the "proofs" field.
+func ProofsGT(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldGT(FieldProofs, v))
+}
+
+// ProofsGTE applies the GTE predicate on the "proofs" field.
+func ProofsGTE(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldGTE(FieldProofs, v))
+}
+
+// ProofsLT applies the LT predicate on the "proofs" field.
+func ProofsLT(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldLT(FieldProofs, v))
+}
+
+// ProofsLTE applies the LTE predicate on the "proofs" field.
+func ProofsLTE(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldLTE(FieldProofs, v))
+}
+
+// CyclesEQ applies the EQ predicate on the "cycles" field.
+func CyclesEQ(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldEQ(FieldCycles, v))
+}
+
+// CyclesNEQ applies the NEQ predicate on the "cycles" field.
+func CyclesNEQ(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNEQ(FieldCycles, v))
+}
+
+// CyclesIn applies the In predicate on the "cycles" field.
+func CyclesIn(vs ...uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldIn(FieldCycles, vs...))
+}
+
+// CyclesNotIn applies the NotIn predicate on the "cycles" field.
+func CyclesNotIn(vs ...uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldNotIn(FieldCycles, vs...))
+}
+
+// CyclesGT applies the GT predicate on the "cycles" field.
+func CyclesGT(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldGT(FieldCycles, v))
+}
+
+// CyclesGTE applies the GTE predicate on the "cycles" field.
+func CyclesGTE(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldGTE(FieldCycles, v))
+}
+
+// CyclesLT applies the LT predicate on the "cycles" field.
+func CyclesLT(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldLT(FieldCycles, v))
+}
+
+// CyclesLTE applies the LTE predicate on the "cycles" field.
+func CyclesLTE(v uint64) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.FieldLTE(FieldCycles, v))
+}
+
+// And groups predicates with the AND operator between them.
+func And(predicates ...predicate.CycleUsage) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.AndPredicates(predicates...))
+}
+
+// Or groups predicates with the OR operator between them.
+func Or(predicates ...predicate.CycleUsage) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.OrPredicates(predicates...))
+}
+
+// Not applies the not operator on the given predicate.
+func Not(p predicate.CycleUsage) predicate.CycleUsage {
+	return predicate.CycleUsage(sql.NotPredicates(p))
+}
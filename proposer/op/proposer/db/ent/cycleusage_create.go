@@ -0,0 +1,240 @@
+// Code generated by ent, DO NOT EDIT.
+
+package ent
+
+import (
+	"context"
+	"errors"
+	"fmt"
+
+	"entgo.io/ent/dialect/sql/sqlgraph"
+	"entgo.io/ent/schema/field"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/cycleusage"
+)
+
+// CycleUsageCreate is the builder for creating a CycleUsage entity.
+type CycleUsageCreate struct {
+	config
+	mutation *CycleUsageMutation
+	hooks    []Hook
+}
+
+// SetType sets the "type" field.
+func (cuc *CycleUsageCreate) SetType(cu cycleusage.Type) *CycleUsageCreate {
+	cuc.mutation.SetType(cu)
+	return cuc
+}
+
+// SetChainID sets the "chain_id" field.
+func (cuc *CycleUsageCreate) SetChainID(u uint64) *CycleUsageCreate {
+	cuc.mutation.SetChainID(u)
+	return cuc
+}
+
+// SetDay sets the "day" field.
+func (cuc *CycleUsageCreate) SetDay(u uint64) *CycleUsageCreate {
+	cuc.mutation.SetDay(u)
+	return cuc
+}
+
+// SetProofs sets the "proofs" field.
+func (cuc *CycleUsageCreate) SetProofs(u uint64) *CycleUsageCreate {
+	cuc.mutation.SetProofs(u)
+	return cuc
+}
+
+// SetCycles sets the "cycles" field.
+func (cuc *CycleUsageCreate) SetCycles(u uint64) *CycleUsageCreate {
+	cuc.mutation.SetCycles(u)
+	return cuc
+}
+
+// Mutation returns the CycleUsageMutation object of the builder.
+func (cuc *CycleUsageCreate) Mutation() *CycleUsageMutation {
+	return cuc.mutation
+}
+
+// Save creates the CycleUsage in the database.
+func (cuc *CycleUsageCreate) Save(ctx context.Context) (*CycleUsage, error) {
+	return withHooks(ctx, cuc.sqlSave, cuc.mutation, cuc.hooks)
+}
+
+// SaveX calls Save and panics if Save returns an error.
+func (cuc *CycleUsageCreate) SaveX(ctx context.Context) *CycleUsage {
+	v, err := cuc.Save(ctx)
+	if err != nil {
+		panic(err)
+	}
+	return v
+}
+
+// Exec executes the query.
+func (cuc *CycleUsageCreate) Exec(ctx context.Context) error {
+	_, err := cuc.Save(ctx)
+	return err
+}
+
+// ExecX is like Exec, but panics if an error occurs.
+func (cuc *CycleUsageCreate) ExecX(ctx context.Context) {
+	if err := cuc.Exec(ctx); err != nil {
+		panic(err)
+	}
+}
+
+// check runs all checks and user-defined validators on the builder.
+func (cuc *CycleUsageCreate) check() error {
+	if _, ok := cuc.mutation.GetType(); !ok {
+		return &ValidationError{Name: "type", err: errors.New(`ent: missing required field "CycleUsage.type"`)}
+	}
+	if v, ok := cuc.mutation.GetType(); ok {
+		if err := cycleusage.TypeValidator(v); err != nil {
+			return &ValidationError{Name: "type", err: fmt.Errorf(`ent: validator failed for field "CycleUsage.type": %w`, err)}
+		}
+	}
+	if _, ok := cuc.mutation.ChainID(); !ok {
+		return &ValidationError{Name: "chain_id", err: errors.New(`ent: missing required field "CycleUsage.chain_id"`)}
+	}
+	if _, ok := cuc.mutation.Day(); !ok {
+		return &ValidationError{Name: "day", err: errors.New(`ent: missing required field "CycleUsage.day"`)}
+	}
+	if _, ok := cuc.mutation.Proofs(); !ok {
+		return &ValidationError{Name: "proofs", err: errors.New(`ent: missing required field "CycleUsage.proofs"`)}
+	}
+	if _, ok := cuc.mutation.Cycles(); !ok {
+		return &ValidationError{Name: "cycles", err: errors.New(`ent: missing required field "CycleUsage.cycles"`)}
+	}
+	return nil
+}
+
+func (cuc *CycleUsageCreate) sqlSave(ctx context.Context) (*CycleUsage, error) {
+	if err := cuc.check(); err != nil {
+		return nil, err
+	}
+	_node, _spec := cuc.createSpec()
+	if err := sqlgraph.CreateNode(ctx, cuc.driver, _spec); err != nil {
+		if sqlgraph.IsConstraintError(err) {
+			err = &ConstraintError{msg: err.Error(), wrap: err}
+		}
+		return nil, err
+	}
+	id := _spec.ID.Value.(int64)
+	_node.ID = int(id)
+	cuc.mutation.id = &_node.ID
+	cuc.mutation.done = true
+	return _node, nil
+}
+
+func (cuc *CycleUsageCreate) createSpec() (*CycleUsage, *sqlgraph.CreateSpec) {
+	var (
+		_node = &CycleUsage{config: cuc.config}
+		_spec = sqlgraph.NewCreateSpec(cycleusage.Table, sqlgraph.NewFieldSpec(cycleusage.FieldID, field.TypeInt))
+	)
+	if value, ok := cuc.mutation.GetType(); ok {
+		_spec.SetField(cycleusage.FieldType, field.TypeEnum, value)
+		_node.Type = value
+	}
+	if value, ok := cuc.mutation.ChainID(); ok {
+		_spec.SetField(cycleusage.FieldChainID, field.TypeUint64, value)
+		_node.ChainID = value
+	}
+	if value, ok := cuc.mutation.Day(); ok {
+		_spec.SetField(cycleusage.FieldDay, field.TypeUint64, value)
+		_node.Day = value
+	}
+	if value, ok := cuc.mutation.Proofs(); ok {
+		_spec.SetField(cycleusage.FieldProofs, field.TypeUint64, value)
+		_node.Proofs = value
+	}
+	if value, ok := cuc.mutation.Cycles(); ok {
+		_spec.SetField(cycleusage.FieldCycles, field.TypeUint64, value)
+		_node.Cycles = value
+	}
+	return _node, _spec
+}
+
+// CycleUsageCreateBulk is the builder for creating many CycleUsage entities in bulk.
+type CycleUsageCreateBulk struct {
+	config
+	err      error
+	builders []*CycleUsageCreate
+}
+
+// Save creates the CycleUsage entities in the database.
+func (cucb *CycleUsageCreateBulk) Save(ctx context.Context) ([]*CycleUsage, error) {
+	if cucb.err != nil {
+		return nil, cucb.err
+	}
+	specs := make([]*sqlgraph.CreateSpec, len(cucb.builders))
+	nodes := make([]*CycleUsage, len(cucb.builders))
+	mutators := make([]Mutator, len(cucb.builders))
+	for i := range cucb.builders {
+		func(i int, root context.Context) {
+			builder := cucb.builders[i]
+			var mut Mutator = MutateFunc(func(ctx context.Context, m Mutation) (Value, error) {
+				mutation, ok := m.(*CycleUsageMutation)
+				if !ok {
+					return nil, fmt.Errorf("unexpected mutation type %T", m)
+				}
+				if err := builder.check(); err != nil {
+					return nil, err
+				}
+				builder.mutation = mutation
+				var err error
+				nodes[i], specs[i] = builder.createSpec()
+				if i < len(mutators)-1 {
+					_, err = mutators[i+1].Mutate(root, cucb.builders[i+1].mutation)
+				} else {
+					spec := &sqlgraph.BatchCreateSpec{Nodes: specs}
+					// Invoke the actual operation on the latest mutation in the chain.
+					if err = sqlgraph.BatchCreate(ctx, cucb.driver, spec); err != nil {
+						if sqlgraph.IsConstraintError(err) {
+							err = &ConstraintError{msg: err.Error(), wrap: err}
+						}
+					}
+				}
+				if err != nil {
+					return nil, err
+				}
+				mutation.id = &nodes[i].ID
+				if specs[i].ID.Value != nil {
+					id := specs[i].ID.Value.(int64)
+					nodes[i].ID = int(id)
+				}
+				mutation.done = true
+				return nodes[i], nil
+			})
+			for i := len(builder.hooks) - 1; i >= 0; i-- {
+				mut = builder.hooks[i](mut)
+			}
+			mutators[i] = mut
+		}(i, ctx)
+	}
+	if len(mutators) > 0 {
+		if _, err := mutators[0].Mutate(ctx, cucb.builders[0].mutation); err != nil {
+			return nil, err
+		}
+	}
+	return nodes, nil
+}
+
+// SaveX is like Save, but panics if an error occurs.
+func (cucb *CycleUsageCreateBulk) SaveX(ctx context.Context) []*CycleUsage {
+	v, err := cucb.Save(ctx)
+	if err != nil {
+		panic(err)
+	}
+	return v
+}
+
+// Exec executes the query.
+func (cucb *CycleUsageCreateBulk) Exec(ctx context.Context) error {
+	_, err := cucb.Save(ctx)
+	return err
+}
+
+// ExecX is like Exec, but panics if an error occurs.
+func (cucb *CycleUsageCreateBulk) ExecX(ctx context.Context) {
+	if err := cucb.Exec(ctx); err != nil {
+		panic(err)
+	}
+}
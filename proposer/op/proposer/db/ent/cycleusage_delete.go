@@ -0,0 +1,88 @@
+// Code generated by ent, DO NOT EDIT.
+
+package ent
+
+import (
+	"context"
+
+	"entgo.io/ent/dialect/sql"
+	"entgo.io/ent/dialect/sql/sqlgraph"
+	"entgo.io/ent/schema/field"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/predicate"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/cycleusage"
+)
+
+// CycleUsageDelete is the builder for deleting a CycleUsage entity.
+type CycleUsageDelete struct {
+	config
+	hooks    []Hook
+	mutation *CycleUsageMutation
+}
+
+// Where appends a list predicates to the CycleUsageDelete builder.
+func (cud *CycleUsageDelete) Where(ps ...predicate.CycleUsage) *CycleUsageDelete {
+	cud.mutation.Where(ps...)
+	return cud
+}
+
+// Exec executes the deletion query and returns how many vertices were deleted.
+func (cud *CycleUsageDelete) Exec(ctx context.Context) (int, error) {
+	return withHooks(ctx, cud.sqlExec, cud.mutation, cud.hooks)
+}
+
+// ExecX is like Exec, but panics if an error occurs.
+func (cud *CycleUsageDelete) ExecX(ctx context.Context) int {
+	n, err := cud.Exec(ctx)
+	if err != nil {
+		panic(err)
+	}
+	return n
+}
+
+func (cud *CycleUsageDelete) sqlExec(ctx context.Context) (int, error) {
+	_spec := sqlgraph.NewDeleteSpec(cycleusage.Table, sqlgraph.NewFieldSpec(cycleusage.FieldID, field.TypeInt))
+	if ps := cud.mutation.predicates; len(ps) > 0 {
+		_spec.Predicate = func(selector *sql.Selector) {
+			for i := range ps {
+				ps[i](selector)
+			}
+		}
+	}
+	affected, err := sqlgraph.DeleteNodes(ctx, cud.driver, _spec)
+	if err != nil && sqlgraph.IsConstraintError(err) {
+		err = &ConstraintError{msg: err.Error(), wrap: err}
+	}
+	cud.mutation.done = true
+	return affected, err
+}
+
+// CycleUsageDeleteOne is the builder for deleting a single CycleUsage entity.
+type CycleUsageDeleteOne struct {
+	cud *CycleUsageDelete
+}
+
+// Where appends a list predicates to the CycleUsageDelete builder.
+func (cudo *CycleUsageDeleteOne) Where(ps ...predicate.CycleUsage) *CycleUsageDeleteOne {
+	cudo.cud.mutation.Where(ps...)
+	return cudo
+}
+
+// Exec executes the deletion query.
+func (cudo *CycleUsageDeleteOne) Exec(ctx context.Context) error {
+	n, err := cudo.cud.Exec(ctx)
+	switch {
+	case err != nil:
+		return err
+	case n == 0:
+		return &NotFoundError{cycleusage.Label}
+	default:
+		return nil
+	}
+}
+
+// ExecX is like Exec, but panics if an error occurs.
+func (cudo *CycleUsageDeleteOne) ExecX(ctx context.Context) {
+	if err := cudo.Exec(ctx); err != nil {
+		panic(err)
+	}
+}
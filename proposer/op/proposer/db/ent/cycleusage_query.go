@@ -0,0 +1,526 @@
+// Code generated by ent, DO NOT EDIT.
+
+package ent
+
+import (
+	"context"
+	"fmt"
+	"math"
+
+	"entgo.io/ent/dialect/sql"
+	"entgo.io/ent/dialect/sql/sqlgraph"
+	"entgo.io/ent/schema/field"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/predicate"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/cycleusage"
+)
+
+// CycleUsageQuery is the builder for querying CycleUsage entities.
+type CycleUsageQuery struct {
+	config
+	ctx        *QueryContext
+	order      []cycleusage.OrderOption
+	inters     []Interceptor
+	predicates []predicate.CycleUsage
+	// intermediate query (i.e. traversal path).
+	sql  *sql.Selector
+	path func(context.Context) (*sql.Selector, error)
+}
+
+// Where adds a new predicate for the CycleUsageQuery builder.
+func (cuq *CycleUsageQuery) Where(ps ...predicate.CycleUsage) *CycleUsageQuery {
+	cuq.predicates = append(cuq.predicates, ps...)
+	return cuq
+}
+
+// Limit the number of records to be returned by this query.
+func (cuq *CycleUsageQuery) Limit(limit int) *CycleUsageQuery {
+	cuq.ctx.Limit = &limit
+	return cuq
+}
+
+// Offset to start from.
+func (cuq *CycleUsageQuery) Offset(offset int) *CycleUsageQuery {
+	cuq.ctx.Offset = &offset
+	return cuq
+}
+
+// Unique configures the query builder to filter duplicate records on query.
+// By default, unique is set to true, and can be disabled using this method.
+func (cuq *CycleUsageQuery) Unique(unique bool) *CycleUsageQuery {
+	cuq.ctx.Unique = &unique
+	return cuq
+}
+
+// Order specifies how the records should be ordered.
+func (cuq *CycleUsageQuery) Order(o ...cycleusage.OrderOption) *CycleUsageQuery {
+	cuq.order = append(cuq.order, o...)
+	return cuq
+}
+
+// First returns the first CycleUsage entity from the query.
+// Returns a *NotFoundError when no CycleUsage was found.
+func (cuq *CycleUsageQuery) First(ctx context.Context) (*CycleUsage, error) {
+	nodes, err := cuq.Limit(1).All(setContextOp(ctx, cuq.ctx, "First"))
+	if err != nil {
+		return nil, err
+	}
+	if len(nodes) == 0 {
+		return nil, &NotFoundError{cycleusage.Label}
+	}
+	return nodes[0], nil
+}
+
+// FirstX is like First, but panics if an error occurs.
+func (cuq *CycleUsageQuery) FirstX(ctx context.Context) *CycleUsage {
+	node, err := cuq.First(ctx)
+	if err != nil && !IsNotFound(err) {
+		panic(err)
+	}
+	return node
+}
+
+// FirstID returns the first CycleUsage ID from the query.
+// Returns a *NotFoundError when no CycleUsage ID was found.
+func (cuq *CycleUsageQuery) FirstID(ctx context.Context) (id int, err error) {
+	var ids []int
+	if ids, err = cuq.Limit(1).IDs(setContextOp(ctx, cuq.ctx, "FirstID")); err != nil {
+		return
+	}
+	if len(ids) == 0 {
+		err = &NotFoundError{cycleusage.Label}
+		return
+	}
+	return ids[0], nil
+}
+
+// FirstIDX is like FirstID, but panics if an error occurs.
+func (cuq *CycleUsageQuery) FirstIDX(ctx context.Context) int {
+	id, err := cuq.FirstID(ctx)
+	if err != nil && !IsNotFound(err) {
+		panic(err)
+	}
+	return id
+}
+
+// Only returns a single CycleUsage entity found by the query, ensuring it only returns one.
+// Returns a *NotSingularError when more than one CycleUsage entity is found.
+// Returns a *NotFoundError when no CycleUsage entities are found.
+func (cuq *CycleUsageQuery) Only(ctx context.Context) (*CycleUsage, error) {
+	nodes, err := cuq.Limit(2).All(setContextOp(ctx, cuq.ctx, "Only"))
+	if err != nil {
+		return nil, err
+	}
+	switch len(nodes) {
+	case 1:
+		return nodes[0], nil
+	case 0:
+		return nil, &NotFoundError{cycleusage.Label}
+	default:
+		return nil, &NotSingularError{cycleusage.Label}
+	}
+}
+
+// OnlyX is like Only, but panics if an error occurs.
+func (cuq *CycleUsageQuery) OnlyX(ctx context.Context) *CycleUsage {
+	node, err := cuq.Only(ctx)
+	if err != nil {
+		panic(err)
+	}
+	return node
+}
+
+// OnlyID is like Only, but returns the only CycleUsage ID in the query.
+// Returns a *NotSingularError when more than one CycleUsage ID is found.
+// Returns a *NotFoundError when no entities are found.
+func (cuq *CycleUsageQuery) OnlyID(ctx context.Context) (id int, err error) {
+	var ids []int
+	if ids, err = cuq.Limit(2).IDs(setContextOp(ctx, cuq.ctx, "OnlyID")); err != nil {
+		return
+	}
+	switch len(ids) {
+	case 1:
+		id = ids[0]
+	case 0:
+		err = &NotFoundError{cycleusage.Label}
+	default:
+		err = &NotSingularError{cycleusage.Label}
+	}
+	return
+}
+
+// OnlyIDX is like OnlyID, but panics if an error occurs.
+func (cuq *CycleUsageQuery) OnlyIDX(ctx context.Context) int {
+	id, err := cuq.OnlyID(ctx)
+	if err != nil {
+		panic(err)
+	}
+	return id
+}
+
+// All executes the query and returns a list of CycleUsages.
+func (cuq *CycleUsageQuery) All(ctx context.Context) ([]*CycleUsage, error) {
+	ctx = setContextOp(ctx, cuq.ctx, "All")
+	if err := cuq.prepareQuery(ctx); err != nil {
+		return nil, err
+	}
+	qr := querierAll[[]*CycleUsage, *CycleUsageQuery]()
+	return withInterceptors[[]*CycleUsage](ctx, cuq, qr, cuq.inters)
+}
+
+// AllX is like All, but panics if an error occurs.
+func (cuq *CycleUsageQuery) AllX(ctx context.Context) []*CycleUsage {
+	nodes, err := cuq.All(ctx)
+	if err != nil {
+		panic(err)
+	}
+	return nodes
+}
+
+// IDs executes the query and returns a list of CycleUsage IDs.
+func (cuq *CycleUsageQuery) IDs(ctx context.Context) (ids []int, err error) {
+	if cuq.ctx.Unique == nil && cuq.path != nil {
+		cuq.Unique(true)
+	}
+	ctx = setContextOp(ctx, cuq.ctx, "IDs")
+	if err = cuq.Select(cycleusage.FieldID).Scan(ctx, &ids); err != nil {
+		return nil, err
+	}
+	return ids, nil
+}
+
+// IDsX is like IDs, but panics if an error occurs.
+func (cuq *CycleUsageQuery) IDsX(ctx context.Context) []int {
+	ids, err := cuq.IDs(ctx)
+	if err != nil {
+		panic(err)
+	}
+	return ids
+}
+
+// Count returns the count of the given query.
+func (cuq *CycleUsageQuery) Count(ctx context.Context) (int, error) {
+	ctx = setContextOp(ctx, cuq.ctx, "Count")
+	if err := cuq.prepareQuery(ctx); err != nil {
+		return 0, err
+	}
+	return withInterceptors[int](ctx, cuq, querierCount[*CycleUsageQuery](), cuq.inters)
+}
+
+// CountX is like Count, but panics if an error occurs.
+func (cuq *CycleUsageQuery) CountX(ctx context.Context) int {
+	count, err := cuq.Count(ctx)
+	if err != nil {
+		panic(err)
+	}
+	return count
+}
+
+// Exist returns true if the query has elements in the graph.
+func (cuq *CycleUsageQuery) Exist(ctx context.Context) (bool, error) {
+	ctx = setContextOp(ctx, cuq.ctx, "Exist")
+	switch _, err := cuq.FirstID(ctx); {
+	case IsNotFound(err):
+		return false, nil
+	case err != nil:
+		return false, fmt.Errorf("ent: check existence: %w", err)
+	default:
+		return true, nil
+	}
+}
+
+// ExistX is like Exist, but panics if an error occurs.
+func (cuq *CycleUsageQuery) ExistX(ctx context.Context) bool {
+	exist, err := cuq.Exist(ctx)
+	if err != nil {
+		panic(err)
+	}
+	return exist
+}
+
+// Clone returns a duplicate of the CycleUsageQuery builder, including all associated steps. It can be
+// used to prepare common query builders and use them differently after the clone is made.
+func (cuq *CycleUsageQuery) Clone() *CycleUsageQuery {
+	if cuq == nil {
+		return nil
+	}
+	return &CycleUsageQuery{
+		config:     cuq.config,
+		ctx:        cuq.ctx.Clone(),
+		order:      append([]cycleusage.OrderOption{}, cuq.order...),
+		inters:     append([]Interceptor{}, cuq.inters...),
+		predicates: append([]predicate.CycleUsage{}, cuq.predicates...),
+		// clone intermediate query.
+		sql:  cuq.sql.Clone(),
+		path: cuq.path,
+	}
+}
+
+// GroupBy is used to group vertices by one or more fields/columns.
+// It is often used with aggregate functions, like: count, max, mean, min, sum.
+//
+// Example:
+//
+//	var v []struct {
+//		Type cycleusage.Type `json:"type,omitempty"`
+//		Count int `json:"count,omitempty"`
+//	}
+//
+//	client.CycleUsage.Query().
+//		GroupBy(cycleusage.FieldType).
+//		Aggregate(ent.Count()).
+//		Scan(ctx, &v)
+func (cuq *CycleUsageQuery) GroupBy(field string, fields ...string) *CycleUsageGroupBy {
+	cuq.ctx.Fields = append([]string{field}, fields...)
+	grbuild := &CycleUsageGroupBy{build: cuq}
+	grbuild.flds = &cuq.ctx.Fields
+	grbuild.label = cycleusage.Label
+	grbuild.scan = grbuild.Scan
+	return grbuild
+}
+
+// Select allows the selection one or more fields/columns for the given query,
+// instead of selecting all fields in the entity.
+//
+// Example:
+//
+//	var v []struct {
+//		Type cycleusage.Type `json:"type,omitempty"`
+//	}
+//
+//	client.CycleUsage.Query().
+//		Select(cycleusage.FieldType).
+//		Scan(ctx, &v)
+func (cuq *CycleUsageQuery) Select(fields ...string) *CycleUsageSelect {
+	cuq.ctx.Fields = append(cuq.ctx.Fields, fields...)
+	sbuild := &CycleUsageSelect{CycleUsageQuery: cuq}
+	sbuild.label = cycleusage.Label
+	sbuild.flds, sbuild.scan = &cuq.ctx.Fields, sbuild.Scan
+	return sbuild
+}
+
+// Aggregate returns a CycleUsageSelect configured with the given aggregations.
+func (cuq *CycleUsageQuery) Aggregate(fns ...AggregateFunc) *CycleUsageSelect {
+	return cuq.Select().Aggregate(fns...)
+}
+
+func (cuq *CycleUsageQuery) prepareQuery(ctx context.Context) error {
+	for _, inter := range cuq.inters {
+		if inter == nil {
+			return fmt.Errorf("ent: uninitialized interceptor (forgotten import ent/runtime?)")
+		}
+		if trv, ok := inter.(Traverser); ok {
+			if err := trv.Traverse(ctx, cuq); err != nil {
+				return err
+			}
+		}
+	}
+	for _, f := range cuq.ctx.Fields {
+		if !cycleusage.ValidColumn(f) {
+			return &ValidationError{Name: f, err: fmt.Errorf("ent: invalid field %q for query", f)}
+		}
+	}
+	if cuq.path != nil {
+		prev, err := cuq.path(ctx)
+		if err != nil {
+			return err
+		}
+		cuq.sql = prev
+	}
+	return nil
+}
+
+func (cuq *CycleUsageQuery) sqlAll(ctx context.Context, hooks ...queryHook) ([]*CycleUsage, error) {
+	var (
+		nodes = []*CycleUsage{}
+		_spec = cuq.querySpec()
+	)
+	_spec.ScanValues = func(columns []string) ([]any, error) {
+		return (*CycleUsage).scanValues(nil, columns)
+	}
+	_spec.Assign = func(columns []string, values []any) error {
+		node := &CycleUsage{config: cuq.config}
+		nodes = append(nodes, node)
+		return node.assignValues(columns, values)
+	}
+	for i := range hooks {
+		hooks[i](ctx, _spec)
+	}
+	if err := sqlgraph.QueryNodes(ctx, cuq.driver, _spec); err != nil {
+		return nil, err
+	}
+	if len(nodes) == 0 {
+		return nodes, nil
+	}
+	return nodes, nil
+}
+
+func (cuq *CycleUsageQuery) sqlCount(ctx context.Context) (int, error) {
+	_spec := cuq.querySpec()
+	_spec.Node.Columns = cuq.ctx.Fields
+	if len(cuq.ctx.Fields) > 0 {
+		_spec.Unique = cuq.ctx.Unique != nil && *cuq.ctx.Unique
+	}
+	return sqlgraph.CountNodes(ctx, cuq.driver, _spec)
+}
+
+func (cuq *CycleUsageQuery) querySpec() *sqlgraph.QuerySpec {
+	_spec := sqlgraph.NewQuerySpec(cycleusage.Table, cycleusage.Columns, sqlgraph.NewFieldSpec(cycleusage.FieldID, field.TypeInt))
+	_spec.From = cuq.sql
+	if unique := cuq.ctx.Unique; unique != nil {
+		_spec.Unique = *unique
+	} else if cuq.path != nil {
+		_spec.Unique = true
+	}
+	if fields := cuq.ctx.Fields; len(fields) > 0 {
+		_spec.Node.Columns = make([]string, 0, len(fields))
+		_spec.Node.Columns = append(_spec.Node.Columns, cycleusage.FieldID)
+		for i := range fields {
+			if fields[i] != cycleusage.FieldID {
+				_spec.Node.Columns = append(_spec.Node.Columns, fields[i])
+			}
+		}
+	}
+	if ps := cuq.predicates; len(ps) > 0 {
+		_spec.Predicate = func(selector *sql.Selector) {
+			for i := range ps {
+				ps[i](selector)
+			}
+		}
+	}
+	if limit := cuq.ctx.Limit; limit != nil {
+		_spec.Limit = *limit
+	}
+	if offset := cuq.ctx.Offset; offset != nil {
+		_spec.Offset = *offset
+	}
+	if ps := cuq.order; len(ps) > 0 {
+		_spec.Order = func(selector *sql.Selector) {
+			for i := range ps {
+				ps[i](selector)
+			}
+		}
+	}
+	return _spec
+}
+
+func (cuq *CycleUsageQuery) sqlQuery(ctx context.Context) *sql.Selector {
+	builder := sql.Dialect(cuq.driver.Dialect())
+	t1 := builder.Table(cycleusage.Table)
+	columns := cuq.ctx.Fields
+	if len(columns) == 0 {
+		columns = cycleusage.Columns
+	}
+	selector := builder.Select(t1.Columns(columns...)...).From(t1)
+	if cuq.sql != nil {
+		selector = cuq.sql
+		selector.Select(selector.Columns(columns...)...)
+	}
+	if cuq.ctx.Unique != nil && *cuq.ctx.Unique {
+		selector.Distinct()
+	}
+	for _, p := range cuq.predicates {
+		p(selector)
+	}
+	for _, p := range cuq.order {
+		p(selector)
+	}
+	if offset := cuq.ctx.Offset; offset != nil {
+		// limit is mandatory for offset clause. We start
+		// with default value, and override it below if needed.
+		selector.Offset(*offset).Limit(math.MaxInt32)
+	}
+	if limit := cuq.ctx.Limit; limit != nil {
+		selector.Limit(*limit)
+	}
+	return selector
+}
+
+// CycleUsageGroupBy is the group-by builder for CycleUsage entities.
+type CycleUsageGroupBy struct {
+	selector
+	build *CycleUsageQuery
+}
+
+// Aggregate adds the given aggregation functions to the group-by query.
+func (cugb *CycleUsageGroupBy) Aggregate(fns ...AggregateFunc) *CycleUsageGroupBy {
+	cugb.fns = append(cugb.fns, fns...)
+	return cugb
+}
+
+// Scan applies the selector query and scans the result into the given value.
+func (cugb *CycleUsageGroupBy) Scan(ctx context.Context, v any) error {
+	ctx = setContextOp(ctx, cugb.build.ctx, "GroupBy")
+	if err := cugb.build.prepareQuery(ctx); err != nil {
+		return err
+	}
+	return scanWithInterceptors[*CycleUsageQuery, *CycleUsageGroupBy](ctx, cugb.build, cugb, cugb.build.inters, v)
+}
+
+func (cugb *CycleUsageGroupBy) sqlScan(ctx context.Context, root *CycleUsageQuery, v any) error {
+	selector := root.sqlQuery(ctx).Select()
+	aggregation := make([]string, 0, len(cugb.fns))
+	for _, fn := range cugb.fns {
+		aggregation = append(aggregation, fn(selector))
+	}
+	if len(selector.SelectedColumns()) == 0 {
+		columns := make([]string, 0, len(*cugb.flds)+len(cugb.fns))
+		for _, f := range *cugb.flds {
+			columns = append(columns, selector.C(f))
+		}
+		columns = append(columns, aggregation...)
+		selector.Select(columns...)
+	}
+	selector.GroupBy(selector.Columns(*cugb.flds...)...)
+	if err := selector.Err(); err != nil {
+		return err
+	}
+	rows := &sql.Rows{}
+	query, args := selector.Query()
+	if err := cugb.build.driver.Query(ctx, query, args, rows); err != nil {
+		return err
+	}
+	defer rows.Close()
+	return sql.ScanSlice(rows, v)
+}
+
+// CycleUsageSelect is the builder for selecting fields of CycleUsage entities.
+type CycleUsageSelect struct {
+	*CycleUsageQuery
+	selector
+}
+
+// Aggregate adds the given aggregation functions to the selector query.
+func (cus *CycleUsageSelect) Aggregate(fns ...AggregateFunc) *CycleUsageSelect {
+	cus.fns = append(cus.fns, fns...)
+	return cus
+}
+
+// Scan applies the selector query and scans the result into the given value.
+func (cus *CycleUsageSelect) Scan(ctx context.Context, v any) error {
+	ctx = setContextOp(ctx, cus.ctx, "Select")
+	if err := cus.prepareQuery(ctx); err != nil {
+		return err
+	}
+	return scanWithInterceptors[*CycleUsageQuery, *CycleUsageSelect](ctx, cus.CycleUsageQuery, cus, cus.inters, v)
+}
+
+func (cus *CycleUsageSelect) sqlScan(ctx context.Context, root *CycleUsageQuery, v any) error {
+	selector := root.sqlQuery(ctx)
+	aggregation := make([]string, 0, len(cus.fns))
+	for _, fn := range cus.fns {
+		aggregation = append(aggregation, fn(selector))
+	}
+	switch n := len(*cus.selector.flds); {
+	case n == 0 && len(aggregation) > 0:
+		selector.Select(aggregation...)
+	case n != 0 && len(aggregation) > 0:
+		selector.AppendSelect(aggregation...)
+	}
+	rows := &sql.Rows{}
+	query, args := selector.Query()
+	if err := cus.driver.Query(ctx, query, args, rows); err != nil {
+		return err
+	}
+	defer rows.Close()
+	return sql.ScanSlice(rows, v)
+}
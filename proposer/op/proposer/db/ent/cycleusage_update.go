@@ -0,0 +1,451 @@
+// Code generated by ent, DO NOT EDIT.
+
+package ent
+
+import (
+	"context"
+	"errors"
+	"fmt"
+
+	"entgo.io/ent/dialect/sql"
+	"entgo.io/ent/dialect/sql/sqlgraph"
+	"entgo.io/ent/schema/field"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/predicate"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/cycleusage"
+)
+
+// CycleUsageUpdate is the builder for updating CycleUsage entities.
+type CycleUsageUpdate struct {
+	config
+	hooks    []Hook
+	mutation *CycleUsageMutation
+}
+
+// Where appends a list predicates to the CycleUsageUpdate builder.
+func (cuu *CycleUsageUpdate) Where(ps ...predicate.CycleUsage) *CycleUsageUpdate {
+	cuu.mutation.Where(ps...)
+	return cuu
+}
+
+// SetType sets the "type" field.
+func (cuu *CycleUsageUpdate) SetType(cu cycleusage.Type) *CycleUsageUpdate {
+	cuu.mutation.SetType(cu)
+	return cuu
+}
+
+// SetNillableType sets the "type" field if the given value is not nil.
+func (cuu *CycleUsageUpdate) SetNillableType(cu *cycleusage.Type) *CycleUsageUpdate {
+	if cu != nil {
+		cuu.SetType(*cu)
+	}
+	return cuu
+}
+
+// SetChainID sets the "chain_id" field.
+func (cuu *CycleUsageUpdate) SetChainID(u uint64) *CycleUsageUpdate {
+	cuu.mutation.ResetChainID()
+	cuu.mutation.SetChainID(u)
+	return cuu
+}
+
+// SetNillableChainID sets the "chain_id" field if the given value is not nil.
+func (cuu *CycleUsageUpdate) SetNillableChainID(u *uint64) *CycleUsageUpdate {
+	if u != nil {
+		cuu.SetChainID(*u)
+	}
+	return cuu
+}
+
+// AddChainID adds u to the "chain_id" field.
+func (cuu *CycleUsageUpdate) AddChainID(u int64) *CycleUsageUpdate {
+	cuu.mutation.AddChainID(u)
+	return cuu
+}
+
+// SetDay sets the "day" field.
+func (cuu *CycleUsageUpdate) SetDay(u uint64) *CycleUsageUpdate {
+	cuu.mutation.ResetDay()
+	cuu.mutation.SetDay(u)
+	return cuu
+}
+
+// SetNillableDay sets the "day" field if the given value is not nil.
+func (cuu *CycleUsageUpdate) SetNillableDay(u *uint64) *CycleUsageUpdate {
+	if u != nil {
+		cuu.SetDay(*u)
+	}
+	return cuu
+}
+
+// AddDay adds u to the "day" field.
+func (cuu *CycleUsageUpdate) AddDay(u int64) *CycleUsageUpdate {
+	cuu.mutation.AddDay(u)
+	return cuu
+}
+
+// SetProofs sets the "proofs" field.
+func (cuu *CycleUsageUpdate) SetProofs(u uint64) *CycleUsageUpdate {
+	cuu.mutation.ResetProofs()
+	cuu.mutation.SetProofs(u)
+	return cuu
+}
+
+// SetNillableProofs sets the "proofs" field if the given value is not nil.
+func (cuu *CycleUsageUpdate) SetNillableProofs(u *uint64) *CycleUsageUpdate {
+	if u != nil {
+		cuu.SetProofs(*u)
+	}
+	return cuu
+}
+
+// AddProofs adds u to the "proofs" field.
+func (cuu *CycleUsageUpdate) AddProofs(u int64) *CycleUsageUpdate {
+	cuu.mutation.AddProofs(u)
+	return cuu
+}
+
+// SetCycles sets the "cycles" field.
+func (cuu *CycleUsageUpdate) SetCycles(u uint64) *CycleUsageUpdate {
+	cuu.mutation.ResetCycles()
+	cuu.mutation.SetCycles(u)
+	return cuu
+}
+
+// SetNillableCycles sets the "cycles" field if the given value is not nil.
+func (cuu *CycleUsageUpdate) SetNillableCycles(u *uint64) *CycleUsageUpdate {
+	if u != nil {
+		cuu.SetCycles(*u)
+	}
+	return cuu
+}
+
+// AddCycles adds u to the "cycles" field.
+func (cuu *CycleUsageUpdate) AddCycles(u int64) *CycleUsageUpdate {
+	cuu.mutation.AddCycles(u)
+	return cuu
+}
+
+// Mutation returns the CycleUsageMutation object of the builder.
+func (cuu *CycleUsageUpdate) Mutation() *CycleUsageMutation {
+	return cuu.mutation
+}
+
+// Save executes the query and returns the number of nodes affected by the update operation.
+func (cuu *CycleUsageUpdate) Save(ctx context.Context) (int, error) {
+	return withHooks(ctx, cuu.sqlSave, cuu.mutation, cuu.hooks)
+}
+
+// SaveX is like Save, but panics if an error occurs.
+func (cuu *CycleUsageUpdate) SaveX(ctx context.Context) int {
+	affected, err := cuu.Save(ctx)
+	if err != nil {
+		panic(err)
+	}
+	return affected
+}
+
+// Exec executes the query.
+func (cuu *CycleUsageUpdate) Exec(ctx context.Context) error {
+	_, err := cuu.Save(ctx)
+	return err
+}
+
+// ExecX is like Exec, but panics if an error occurs.
+func (cuu *CycleUsageUpdate) ExecX(ctx context.Context) {
+	if err := cuu.Exec(ctx); err != nil {
+		panic(err)
+	}
+}
+
+// check runs all checks and user-defined validators on the builder.
+func (cuu *CycleUsageUpdate) check() error {
+	if v, ok := cuu.mutation.GetType(); ok {
+		if err := cycleusage.TypeValidator(v); err != nil {
+			return &ValidationError{Name: "type", err: fmt.Errorf(`ent: validator failed for field "CycleUsage.type": %w`, err)}
+		}
+	}
+	return nil
+}
+
+func (cuu *CycleUsageUpdate) sqlSave(ctx context.Context) (n int, err error) {
+	if err := cuu.check(); err != nil {
+		return n, err
+	}
+	_spec := sqlgraph.NewUpdateSpec(cycleusage.Table, cycleusage.Columns, sqlgraph.NewFieldSpec(cycleusage.FieldID, field.TypeInt))
+	if ps := cuu.mutation.predicates; len(ps) > 0 {
+		_spec.Predicate = func(selector *sql.Selector) {
+			for i := range ps {
+				ps[i](selector)
+			}
+		}
+	}
+	if value, ok := cuu.mutation.GetType(); ok {
+		_spec.SetField(cycleusage.FieldType, field.TypeEnum, value)
+	}
+	if value, ok := cuu.mutation.ChainID(); ok {
+		_spec.SetField(cycleusage.FieldChainID, field.TypeUint64, value)
+	}
+	if value, ok := cuu.mutation.AddedChainID(); ok {
+		_spec.AddField(cycleusage.FieldChainID, field.TypeUint64, value)
+	}
+	if value, ok := cuu.mutation.Day(); ok {
+		_spec.SetField(cycleusage.FieldDay, field.TypeUint64, value)
+	}
+	if value, ok := cuu.mutation.AddedDay(); ok {
+		_spec.AddField(cycleusage.FieldDay, field.TypeUint64, value)
+	}
+	if value, ok := cuu.mutation.Proofs(); ok {
+		_spec.SetField(cycleusage.FieldProofs, field.TypeUint64, value)
+	}
+	if value, ok := cuu.mutation.AddedProofs(); ok {
+		_spec.AddField(cycleusage.FieldProofs, field.TypeUint64, value)
+	}
+	if value, ok := cuu.mutation.Cycles(); ok {
+		_spec.SetField(cycleusage.FieldCycles, field.TypeUint64, value)
+	}
+	if value, ok := cuu.mutation.AddedCycles(); ok {
+		_spec.AddField(cycleusage.FieldCycles, field.TypeUint64, value)
+	}
+	if n, err = sqlgraph.UpdateNodes(ctx, cuu.driver, _spec); err != nil {
+		if _, ok := err.(*sqlgraph.NotFoundError); ok {
+			err = &NotFoundError{cycleusage.Label}
+		} else if sqlgraph.IsConstraintError(err) {
+			err = &ConstraintError{msg: err.Error(), wrap: err}
+		}
+		return 0, err
+	}
+	cuu.mutation.done = true
+	return n, nil
+}
+
+// CycleUsageUpdateOne is the builder for updating a single CycleUsage entity.
+type CycleUsageUpdateOne struct {
+	config
+	fields   []string
+	hooks    []Hook
+	mutation *CycleUsageMutation
+}
+
+// SetType sets the "type" field.
+func (cuuo *CycleUsageUpdateOne) SetType(cu cycleusage.Type) *CycleUsageUpdateOne {
+	cuuo.mutation.SetType(cu)
+	return cuuo
+}
+
+// SetNillableType sets the "type" field if the given value is not nil.
+func (cuuo *CycleUsageUpdateOne) SetNillableType(cu *cycleusage.Type) *CycleUsageUpdateOne {
+	if cu != nil {
+		cuuo.SetType(*cu)
+	}
+	return cuuo
+}
+
+// SetChainID sets the "chain_id" field.
+func (cuuo *CycleUsageUpdateOne) SetChainID(u uint64) *CycleUsageUpdateOne {
+	cuuo.mutation.ResetChainID()
+	cuuo.mutation.SetChainID(u)
+	return cuuo
+}
+
+// SetNillableChainID sets the "chain_id" field if the given value is not nil.
+func (cuuo *CycleUsageUpdateOne) SetNillableChainID(u *uint64) *CycleUsageUpdateOne {
+	if u != nil {
+		cuuo.SetChainID(*u)
+	}
+	return cuuo
+}
+
+// AddChainID adds u to the "chain_id" field.
+func (cuuo *CycleUsageUpdateOne) AddChainID(u int64) *CycleUsageUpdateOne {
+	cuuo.mutation.AddChainID(u)
+	return cuuo
+}
+
+// SetDay sets the "day" field.
+func (cuuo *CycleUsageUpdateOne) SetDay(u uint64) *CycleUsageUpdateOne {
+	cuuo.mutation.ResetDay()
+	cuuo.mutation.SetDay(u)
+	return cuuo
+}
+
+// SetNillableDay sets the "day" field if the given value is not nil.
+func (cuuo *CycleUsageUpdateOne) SetNillableDay(u *uint64) *CycleUsageUpdateOne {
+	if u != nil {
+		cuuo.SetDay(*u)
+	}
+	return cuuo
+}
+
+// AddDay adds u to the "day" field.
+func (cuuo *CycleUsageUpdateOne) AddDay(u int64) *CycleUsageUpdateOne {
+	cuuo.mutation.AddDay(u)
+	return cuuo
+}
+
+// SetProofs sets the "proofs" field.
+func (cuuo *CycleUsageUpdateOne) SetProofs(u uint64) *CycleUsageUpdateOne {
+	cuuo.mutation.ResetProofs()
+	cuuo.mutation.SetProofs(u)
+	return cuuo
+}
+
+// SetNillableProofs sets the "proofs" field if the given value is not nil.
+func (cuuo *CycleUsageUpdateOne) SetNillableProofs(u *uint64) *CycleUsageUpdateOne {
+	if u != nil {
+		cuuo.SetProofs(*u)
+	}
+	return cuuo
+}
+
+// AddProofs adds u to the "proofs" field.
+func (cuuo *CycleUsageUpdateOne) AddProofs(u int64) *CycleUsageUpdateOne {
+	cuuo.mutation.AddProofs(u)
+	return cuuo
+}
+
+// SetCycles sets the "cycles" field.
+func (cuuo *CycleUsageUpdateOne) SetCycles(u uint64) *CycleUsageUpdateOne {
+	cuuo.mutation.ResetCycles()
+	cuuo.mutation.SetCycles(u)
+	return cuuo
+}
+
+// SetNillableCycles sets the "cycles" field if the given value is not nil.
+func (cuuo *CycleUsageUpdateOne) SetNillableCycles(u *uint64) *CycleUsageUpdateOne {
+	if u != nil {
+		cuuo.SetCycles(*u)
+	}
+	return cuuo
+}
+
+// AddCycles adds u to the "cycles" field.
+func (cuuo *CycleUsageUpdateOne) AddCycles(u int64) *CycleUsageUpdateOne {
+	cuuo.mutation.AddCycles(u)
+	return cuuo
+}
+
+// Mutation returns the CycleUsageMutation object of the builder.
+func (cuuo *CycleUsageUpdateOne) Mutation() *CycleUsageMutation {
+	return cuuo.mutation
+}
+
+// Where appends a list predicates to the CycleUsageUpdate builder.
+func (cuuo *CycleUsageUpdateOne) Where(ps ...predicate.CycleUsage) *CycleUsageUpdateOne {
+	cuuo.mutation.Where(ps...)
+	return cuuo
+}
+
+// Select allows selecting one or more fields (columns) of the returned entity.
+// The default is selecting all fields defined in the entity schema.
+func (cuuo *CycleUsageUpdateOne) Select(field string, fields ...string) *CycleUsageUpdateOne {
+	cuuo.fields = append([]string{field}, fields...)
+	return cuuo
+}
+
+// Save executes the query and returns the updated CycleUsage entity.
+func (cuuo *CycleUsageUpdateOne) Save(ctx context.Context) (*CycleUsage, error) {
+	return withHooks(ctx, cuuo.sqlSave, cuuo.mutation, cuuo.hooks)
+}
+
+// SaveX is like Save, but panics if an error occurs.
+func (cuuo *CycleUsageUpdateOne) SaveX(ctx context.Context) *CycleUsage {
+	node, err := cuuo.Save(ctx)
+	if err != nil {
+		panic(err)
+	}
+	return node
+}
+
+// Exec executes the query on the entity.
+func (cuuo *CycleUsageUpdateOne) Exec(ctx context.Context) error {
+	_, err := cuuo.Save(ctx)
+	return err
+}
+
+// ExecX is like Exec, but panics if an error occurs.
+func (cuuo *CycleUsageUpdateOne) ExecX(ctx context.Context) {
+	if err := cuuo.Exec(ctx); err != nil {
+		panic(err)
+	}
+}
+
+// check runs all checks and user-defined validators on the builder.
+func (cuuo *CycleUsageUpdateOne) check() error {
+	if v, ok := cuuo.mutation.GetType(); ok {
+		if err := cycleusage.TypeValidator(v); err != nil {
+			return &ValidationError{Name: "type", err: fmt.Errorf(`ent: validator failed for field "CycleUsage.type": %w`, err)}
+		}
+	}
+	return nil
+}
+
+func (cuuo *CycleUsageUpdateOne) sqlSave(ctx context.Context) (_node *CycleUsage, err error) {
+	if err := cuuo.check(); err != nil {
+		return _node, err
+	}
+	_spec := sqlgraph.NewUpdateSpec(cycleusage.Table, cycleusage.Columns, sqlgraph.NewFieldSpec(cycleusage.FieldID, field.TypeInt))
+	id, ok := cuuo.mutation.ID()
+	if !ok {
+		return nil, &ValidationError{Name: "id", err: errors.New(`ent: missing "CycleUsage.id" for update`)}
+	}
+	_spec.Node.ID.Value = id
+	if fields := cuuo.fields; len(fields) > 0 {
+		_spec.Node.Columns = make([]string, 0, len(fields))
+		_spec.Node.Columns = append(_spec.Node.Columns, cycleusage.FieldID)
+		for _, f := range fields {
+			if !cycleusage.ValidColumn(f) {
+				return nil, &ValidationError{Name: f, err: fmt.Errorf("ent: invalid field %q for query", f)}
+			}
+			if f != cycleusage.FieldID {
+				_spec.Node.Columns = append(_spec.Node.Columns, f)
+			}
+		}
+	}
+	if ps := cuuo.mutation.predicates; len(ps) > 0 {
+		_spec.Predicate = func(selector *sql.Selector) {
+			for i := range ps {
+				ps[i](selector)
+			}
+		}
+	}
+	if value, ok := cuuo.mutation.GetType(); ok {
+		_spec.SetField(cycleusage.FieldType, field.TypeEnum, value)
+	}
+	if value, ok := cuuo.mutation.ChainID(); ok {
+		_spec.SetField(cycleusage.FieldChainID, field.TypeUint64, value)
+	}
+	if value, ok := cuuo.mutation.AddedChainID(); ok {
+		_spec.AddField(cycleusage.FieldChainID, field.TypeUint64, value)
+	}
+	if value, ok := cuuo.mutation.Day(); ok {
+		_spec.SetField(cycleusage.FieldDay, field.TypeUint64, value)
+	}
+	if value, ok := cuuo.mutation.AddedDay(); ok {
+		_spec.AddField(cycleusage.FieldDay, field.TypeUint64, value)
+	}
+	if value, ok := cuuo.mutation.Proofs(); ok {
+		_spec.SetField(cycleusage.FieldProofs, field.TypeUint64, value)
+	}
+	if value, ok := cuuo.mutation.AddedProofs(); ok {
+		_spec.AddField(cycleusage.FieldProofs, field.TypeUint64, value)
+	}
+	if value, ok := cuuo.mutation.Cycles(); ok {
+		_spec.SetField(cycleusage.FieldCycles, field.TypeUint64, value)
+	}
+	if value, ok := cuuo.mutation.AddedCycles(); ok {
+		_spec.AddField(cycleusage.FieldCycles, field.TypeUint64, value)
+	}
+	_node = &CycleUsage{config: cuuo.config}
+	_spec.Assign = _node.assignValues
+	_spec.ScanValues = _node.scanValues
+	if err = sqlgraph.UpdateNode(ctx, cuuo.driver, _spec); err != nil {
+		if _, ok := err.(*sqlgraph.NotFoundError); ok {
+			err = &NotFoundError{cycleusage.Label}
+		} else if sqlgraph.IsConstraintError(err) {
+			err = &ConstraintError{msg: err.Error(), wrap: err}
+		}
+		return nil, err
+	}
+	cuuo.mutation.done = true
+	return _node, nil
+}
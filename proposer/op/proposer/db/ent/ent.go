@@ -12,6 +12,7 @@ import (
 	"entgo.io/ent"
 	"entgo.io/ent/dialect/sql"
 	"entgo.io/ent/dialect/sql/sqlgraph"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/cycleusage"
 	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
 )
 
@@ -73,6 +74,7 @@ var (
 func checkColumn(table, column string) error {
 	initCheck.Do(func() {
 		columnCheck = sql.NewColumnCheck(map[string]func(string) bool{
+			cycleusage.Table:   cycleusage.ValidColumn,
 			proofrequest.Table: proofrequest.ValidColumn,
 		})
 	})
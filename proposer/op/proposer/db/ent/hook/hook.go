@@ -9,6 +9,18 @@ import (
 	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
 )
 
+// The CycleUsageFunc type is an adapter to allow the use of ordinary
+// function as CycleUsage mutator.
+type CycleUsageFunc func(context.Context, *ent.CycleUsageMutation) (ent.Value, error)
+
+// Mutate calls f(ctx, m).
+func (f CycleUsageFunc) Mutate(ctx context.Context, m ent.Mutation) (ent.Value, error) {
+	if mv, ok := m.(*ent.CycleUsageMutation); ok {
+		return f(ctx, mv)
+	}
+	return nil, fmt.Errorf("unexpected mutation type %T. expect *ent.CycleUsageMutation", m)
+}
+
 // The ProofRequestFunc type is an adapter to allow the use of ordinary
 // function as ProofRequest mutator.
 type ProofRequestFunc func(context.Context, *ent.ProofRequestMutation) (ent.Value, error)
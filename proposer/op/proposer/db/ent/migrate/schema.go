@@ -9,6 +9,28 @@ import (
 )
 
 var (
+	// CycleUsagesColumns holds the columns for the "cycle_usages" table.
+	CycleUsagesColumns = []*schema.Column{
+		{Name: "id", Type: field.TypeInt, Increment: true},
+		{Name: "type", Type: field.TypeEnum, Enums: []string{"SPAN", "AGG"}},
+		{Name: "chain_id", Type: field.TypeUint64},
+		{Name: "day", Type: field.TypeUint64},
+		{Name: "proofs", Type: field.TypeUint64},
+		{Name: "cycles", Type: field.TypeUint64},
+	}
+	// CycleUsagesTable holds the schema information for the "cycle_usages" table.
+	CycleUsagesTable = &schema.Table{
+		Name:       "cycle_usages",
+		Columns:    CycleUsagesColumns,
+		PrimaryKey: []*schema.Column{CycleUsagesColumns[0]},
+		Indexes: []*schema.Index{
+			{
+				Name:    "cycleusage_chain_id_day_type",
+				Unique:  true,
+				Columns: []*schema.Column{CycleUsagesColumns[2], CycleUsagesColumns[3], CycleUsagesColumns[1]},
+			},
+		},
+	}
 	// ProofRequestsColumns holds the columns for the "proof_requests" table.
 	ProofRequestsColumns = []*schema.Column{
 		{Name: "id", Type: field.TypeInt, Increment: true},
@@ -22,6 +44,10 @@ var (
 		{Name: "last_updated_time", Type: field.TypeUint64},
 		{Name: "l1_block_number", Type: field.TypeUint64, Nullable: true},
 		{Name: "l1_block_hash", Type: field.TypeString, Nullable: true},
+		{Name: "subproofs", Type: field.TypeString, Nullable: true},
+		{Name: "witness_sha256", Type: field.TypeString, Nullable: true},
+		{Name: "error_code", Type: field.TypeString, Nullable: true},
+		{Name: "checkpoint_overrides", Type: field.TypeString, Nullable: true},
 		{Name: "proof", Type: field.TypeBytes, Nullable: true},
 	}
 	// ProofRequestsTable holds the schema information for the "proof_requests" table.
@@ -32,11 +58,16 @@ var (
 	}
 	// Tables holds all the tables in the schema.
 	Tables = []*schema.Table{
+		CycleUsagesTable,
 		ProofRequestsTable,
 	}
 )
 
 func init() {
+	CycleUsagesTable.Annotation = &entsql.Annotation{
+		Table:   "cycle_usages",
+		Options: "STRICT",
+	}
 	ProofRequestsTable.Annotation = &entsql.Annotation{
 		Table:   "proof_requests",
 		Options: "STRICT",
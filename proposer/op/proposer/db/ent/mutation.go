@@ -10,6 +10,7 @@ import (
 
 	"entgo.io/ent"
 	"entgo.io/ent/dialect/sql"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/cycleusage"
 	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/predicate"
 	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
 )
@@ -23,9 +24,687 @@ const (
 	OpUpdateOne = ent.OpUpdateOne
 
 	// Node types.
+	TypeCycleUsage   = "CycleUsage"
 	TypeProofRequest = "ProofRequest"
 )
 
+// CycleUsageMutation represents an operation that mutates the CycleUsage nodes in the graph.
+type CycleUsageMutation struct {
+	config
+	op            Op
+	typ           string
+	id            *int
+	_type         *cycleusage.Type
+	chain_id      *uint64
+	addchain_id   *int64
+	day           *uint64
+	addday        *int64
+	proofs        *uint64
+	addproofs     *int64
+	cycles        *uint64
+	addcycles     *int64
+	clearedFields map[string]struct{}
+	done          bool
+	oldValue      func(context.Context) (*CycleUsage, error)
+	predicates    []predicate.CycleUsage
+}
+
+var _ ent.Mutation = (*CycleUsageMutation)(nil)
+
+// cycleusageOption allows management of the mutation configuration using functional options.
+type cycleusageOption func(*CycleUsageMutation)
+
+// newCycleUsageMutation creates new mutation for the CycleUsage entity.
+func newCycleUsageMutation(c config, op Op, opts ...cycleusageOption) *CycleUsageMutation {
+	m := &CycleUsageMutation{
+		config:        c,
+		op:            op,
+		typ:           TypeCycleUsage,
+		clearedFields: make(map[string]struct{}),
+	}
+	for _, opt := range opts {
+		opt(m)
+	}
+	return m
+}
+
+// withCycleUsageID sets the ID field of the mutation.
+func withCycleUsageID(id int) cycleusageOption {
+	return func(m *CycleUsageMutation) {
+		var (
+			err   error
+			once  sync.Once
+			value *CycleUsage
+		)
+		m.oldValue = func(ctx context.Context) (*CycleUsage, error) {
+			once.Do(func() {
+				if m.done {
+					err = errors.New("querying old values post mutation is not allowed")
+				} else {
+					value, err = m.Client().CycleUsage.Get(ctx, id)
+				}
+			})
+			return value, err
+		}
+		m.id = &id
+	}
+}
+
+// withCycleUsage sets the old CycleUsage of the mutation.
+func withCycleUsage(node *CycleUsage) cycleusageOption {
+	return func(m *CycleUsageMutation) {
+		m.oldValue = func(context.Context) (*CycleUsage, error) {
+			return node, nil
+		}
+		m.id = &node.ID
+	}
+}
+
+// Client returns a new `ent.Client` from the mutation. If the mutation was
+// executed in a transaction (ent.Tx), a transactional client is returned.
+func (m CycleUsageMutation) Client() *Client {
+	client := &Client{config: m.config}
+	client.init()
+	return client
+}
+
+// Tx returns an `ent.Tx` for mutations that were executed in transactions;
+// it returns an error otherwise.
+func (m CycleUsageMutation) Tx() (*Tx, error) {
+	if _, ok := m.driver.(*txDriver); !ok {
+		return nil, errors.New("ent: mutation is not running in a transaction")
+	}
+	tx := &Tx{config: m.config}
+	tx.init()
+	return tx, nil
+}
+
+// ID returns the ID value in the mutation. Note that the ID is only available
+// if it was provided to the builder or after it was returned from the database.
+func (m *CycleUsageMutation) ID() (id int, exists bool) {
+	if m.id == nil {
+		return
+	}
+	return *m.id, true
+}
+
+// IDs queries the database and returns the entity ids that match the mutation's predicate.
+// That means, if the mutation is applied within a transaction with an isolation level such
+// as sql.LevelSerializable, the returned ids match the ids of the rows that will be updated
+// or updated by the mutation.
+func (m *CycleUsageMutation) IDs(ctx context.Context) ([]int, error) {
+	switch {
+	case m.op.Is(OpUpdateOne | OpDeleteOne):
+		id, exists := m.ID()
+		if exists {
+			return []int{id}, nil
+		}
+		fallthrough
+	case m.op.Is(OpUpdate | OpDelete):
+		return m.Client().CycleUsage.Query().Where(m.predicates...).IDs(ctx)
+	default:
+		return nil, fmt.Errorf("IDs is not allowed on %s operations", m.op)
+	}
+}
+
+// SetType sets the "type" field.
+func (m *CycleUsageMutation) SetType(cu cycleusage.Type) {
+	m._type = &cu
+}
+
+// GetType returns the value of the "type" field in the mutation.
+func (m *CycleUsageMutation) GetType() (r cycleusage.Type, exists bool) {
+	v := m._type
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// OldType returns the old "type" field's value of the CycleUsage entity.
+// If the CycleUsage object wasn't provided to the builder, the object is fetched from the database.
+// An error is returned if the mutation operation is not UpdateOne, or the database query fails.
+func (m *CycleUsageMutation) OldType(ctx context.Context) (v cycleusage.Type, err error) {
+	if !m.op.Is(OpUpdateOne) {
+		return v, errors.New("OldType is only allowed on UpdateOne operations")
+	}
+	if m.id == nil || m.oldValue == nil {
+		return v, errors.New("OldType requires an ID field in the mutation")
+	}
+	oldValue, err := m.oldValue(ctx)
+	if err != nil {
+		return v, fmt.Errorf("querying old value for OldType: %w", err)
+	}
+	return oldValue.Type, nil
+}
+
+// ResetType resets all changes to the "type" field.
+func (m *CycleUsageMutation) ResetType() {
+	m._type = nil
+}
+
+// SetChainID sets the "chain_id" field.
+func (m *CycleUsageMutation) SetChainID(u uint64) {
+	m.chain_id = &u
+	m.addchain_id = nil
+}
+
+// ChainID returns the value of the "chain_id" field in the mutation.
+func (m *CycleUsageMutation) ChainID() (r uint64, exists bool) {
+	v := m.chain_id
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// OldChainID returns the old "chain_id" field's value of the CycleUsage entity.
+// If the CycleUsage object wasn't provided to the builder, the object is fetched from the database.
+// An error is returned if the mutation operation is not UpdateOne, or the database query fails.
+func (m *CycleUsageMutation) OldChainID(ctx context.Context) (v uint64, err error) {
+	if !m.op.Is(OpUpdateOne) {
+		return v, errors.New("OldChainID is only allowed on UpdateOne operations")
+	}
+	if m.id == nil || m.oldValue == nil {
+		return v, errors.New("OldChainID requires an ID field in the mutation")
+	}
+	oldValue, err := m.oldValue(ctx)
+	if err != nil {
+		return v, fmt.Errorf("querying old value for OldChainID: %w", err)
+	}
+	return oldValue.ChainID, nil
+}
+
+// AddChainID adds u to the "chain_id" field.
+func (m *CycleUsageMutation) AddChainID(u int64) {
+	if m.addchain_id != nil {
+		*m.addchain_id += u
+	} else {
+		m.addchain_id = &u
+	}
+}
+
+// AddedChainID returns the value that was added to the "chain_id" field in this mutation.
+func (m *CycleUsageMutation) AddedChainID() (r int64, exists bool) {
+	v := m.addchain_id
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// ResetChainID resets all changes to the "chain_id" field.
+func (m *CycleUsageMutation) ResetChainID() {
+	m.chain_id = nil
+	m.addchain_id = nil
+}
+
+// SetDay sets the "day" field.
+func (m *CycleUsageMutation) SetDay(u uint64) {
+	m.day = &u
+	m.addday = nil
+}
+
+// Day returns the value of the "day" field in the mutation.
+func (m *CycleUsageMutation) Day() (r uint64, exists bool) {
+	v := m.day
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// OldDay returns the old "day" field's value of the CycleUsage entity.
+// If the CycleUsage object wasn't provided to the builder, the object is fetched from the database.
+// An error is returned if the mutation operation is not UpdateOne, or the database query fails.
+func (m *CycleUsageMutation) OldDay(ctx context.Context) (v uint64, err error) {
+	if !m.op.Is(OpUpdateOne) {
+		return v, errors.New("OldDay is only allowed on UpdateOne operations")
+	}
+	if m.id == nil || m.oldValue == nil {
+		return v, errors.New("OldDay requires an ID field in the mutation")
+	}
+	oldValue, err := m.oldValue(ctx)
+	if err != nil {
+		return v, fmt.Errorf("querying old value for OldDay: %w", err)
+	}
+	return oldValue.Day, nil
+}
+
+// AddDay adds u to the "day" field.
+func (m *CycleUsageMutation) AddDay(u int64) {
+	if m.addday != nil {
+		*m.addday += u
+	} else {
+		m.addday = &u
+	}
+}
+
+// AddedDay returns the value that was added to the "day" field in this mutation.
+func (m *CycleUsageMutation) AddedDay() (r int64, exists bool) {
+	v := m.addday
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// ResetDay resets all changes to the "day" field.
+func (m *CycleUsageMutation) ResetDay() {
+	m.day = nil
+	m.addday = nil
+}
+
+// SetProofs sets the "proofs" field.
+func (m *CycleUsageMutation) SetProofs(u uint64) {
+	m.proofs = &u
+	m.addproofs = nil
+}
+
+// Proofs returns the value of the "proofs" field in the mutation.
+func (m *CycleUsageMutation) Proofs() (r uint64, exists bool) {
+	v := m.proofs
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// OldProofs returns the old "proofs" field's value of the CycleUsage entity.
+// If the CycleUsage object wasn't provided to the builder, the object is fetched from the database.
+// An error is returned if the mutation operation is not UpdateOne, or the database query fails.
+func (m *CycleUsageMutation) OldProofs(ctx context.Context) (v uint64, err error) {
+	if !m.op.Is(OpUpdateOne) {
+		return v, errors.New("OldProofs is only allowed on UpdateOne operations")
+	}
+	if m.id == nil || m.oldValue == nil {
+		return v, errors.New("OldProofs requires an ID field in the mutation")
+	}
+	oldValue, err := m.oldValue(ctx)
+	if err != nil {
+		return v, fmt.Errorf("querying old value for OldProofs: %w", err)
+	}
+	return oldValue.Proofs, nil
+}
+
+// AddProofs adds u to the "proofs" field.
+func (m *CycleUsageMutation) AddProofs(u int64) {
+	if m.addproofs != nil {
+		*m.addproofs += u
+	} else {
+		m.addproofs = &u
+	}
+}
+
+// AddedProofs returns the value that was added to the "proofs" field in this mutation.
+func (m *CycleUsageMutation) AddedProofs() (r int64, exists bool) {
+	v := m.addproofs
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// ResetProofs resets all changes to the "proofs" field.
+func (m *CycleUsageMutation) ResetProofs() {
+	m.proofs = nil
+	m.addproofs = nil
+}
+
+// SetCycles sets the "cycles" field.
+func (m *CycleUsageMutation) SetCycles(u uint64) {
+	m.cycles = &u
+	m.addcycles = nil
+}
+
+// Cycles returns the value of the "cycles" field in the mutation.
+func (m *CycleUsageMutation) Cycles() (r uint64, exists bool) {
+	v := m.cycles
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// OldCycles returns the old "cycles" field's value of the CycleUsage entity.
+// If the CycleUsage object wasn't provided to the builder, the object is fetched from the database.
+// An error is returned if the mutation operation is not UpdateOne, or the database query fails.
+func (m *CycleUsageMutation) OldCycles(ctx context.Context) (v uint64, err error) {
+	if !m.op.Is(OpUpdateOne) {
+		return v, errors.New("OldCycles is only allowed on UpdateOne operations")
+	}
+	if m.id == nil || m.oldValue == nil {
+		return v, errors.New("OldCycles requires an ID field in the mutation")
+	}
+	oldValue, err := m.oldValue(ctx)
+	if err != nil {
+		return v, fmt.Errorf("querying old value for OldCycles: %w", err)
+	}
+	return oldValue.Cycles, nil
+}
+
+// AddCycles adds u to the "cycles" field.
+func (m *CycleUsageMutation) AddCycles(u int64) {
+	if m.addcycles != nil {
+		*m.addcycles += u
+	} else {
+		m.addcycles = &u
+	}
+}
+
+// AddedCycles returns the value that was added to the "cycles" field in this mutation.
+func (m *CycleUsageMutation) AddedCycles() (r int64, exists bool) {
+	v := m.addcycles
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// ResetCycles resets all changes to the "cycles" field.
+func (m *CycleUsageMutation) ResetCycles() {
+	m.cycles = nil
+	m.addcycles = nil
+}
+
+// Where appends a list predicates to the CycleUsageMutation builder.
+func (m *CycleUsageMutation) Where(ps ...predicate.CycleUsage) {
+	m.predicates = append(m.predicates, ps...)
+}
+
+// WhereP appends storage-level predicates to the CycleUsageMutation builder. Using this method,
+// users can use type-assertion to append predicates that do not depend on any generated package.
+func (m *CycleUsageMutation) WhereP(ps ...func(*sql.Selector)) {
+	p := make([]predicate.CycleUsage, len(ps))
+	for i := range ps {
+		p[i] = ps[i]
+	}
+	m.Where(p...)
+}
+
+// Op returns the operation name.
+func (m *CycleUsageMutation) Op() Op {
+	return m.op
+}
+
+// SetOp allows setting the mutation operation.
+func (m *CycleUsageMutation) SetOp(op Op) {
+	m.op = op
+}
+
+// Type returns the node type of this mutation (CycleUsage).
+func (m *CycleUsageMutation) Type() string {
+	return m.typ
+}
+
+// Fields returns all fields that were changed during this mutation. Note that in
+// order to get all numeric fields that were incremented/decremented, call
+// AddedFields().
+func (m *CycleUsageMutation) Fields() []string {
+	fields := make([]string, 0, 5)
+	if m._type != nil {
+		fields = append(fields, cycleusage.FieldType)
+	}
+	if m.chain_id != nil {
+		fields = append(fields, cycleusage.FieldChainID)
+	}
+	if m.day != nil {
+		fields = append(fields, cycleusage.FieldDay)
+	}
+	if m.proofs != nil {
+		fields = append(fields, cycleusage.FieldProofs)
+	}
+	if m.cycles != nil {
+		fields = append(fields, cycleusage.FieldCycles)
+	}
+	return fields
+}
+
+// Field returns the value of a field with the given name. The second boolean
+// return value indicates that this field was not set, or was not defined in the
+// schema.
+func (m *CycleUsageMutation) Field(name string) (ent.Value, bool) {
+	switch name {
+	case cycleusage.FieldType:
+		return m.GetType()
+	case cycleusage.FieldChainID:
+		return m.ChainID()
+	case cycleusage.FieldDay:
+		return m.Day()
+	case cycleusage.FieldProofs:
+		return m.Proofs()
+	case cycleusage.FieldCycles:
+		return m.Cycles()
+	}
+	return nil, false
+}
+
+// OldField returns the old value of the field from the database. An error is
+// returned if the mutation operation is not UpdateOne, or the query to the
+// database failed.
+func (m *CycleUsageMutation) OldField(ctx context.Context, name string) (ent.Value, error) {
+	switch name {
+	case cycleusage.FieldType:
+		return m.OldType(ctx)
+	case cycleusage.FieldChainID:
+		return m.OldChainID(ctx)
+	case cycleusage.FieldDay:
+		return m.OldDay(ctx)
+	case cycleusage.FieldProofs:
+		return m.OldProofs(ctx)
+	case cycleusage.FieldCycles:
+		return m.OldCycles(ctx)
+	}
+	return nil, fmt.Errorf("unknown CycleUsage field %s", name)
+}
+
+// SetField sets the value of a field with the given name. It returns an error if
+// the field is not defined in the schema, or if the type mismatched the field
+// type.
+func (m *CycleUsageMutation) SetField(name string, value ent.Value) error {
+	switch name {
+	case cycleusage.FieldType:
+		v, ok := value.(cycleusage.Type)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.SetType(v)
+		return nil
+	case cycleusage.FieldChainID:
+		v, ok := value.(uint64)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.SetChainID(v)
+		return nil
+	case cycleusage.FieldDay:
+		v, ok := value.(uint64)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.SetDay(v)
+		return nil
+	case cycleusage.FieldProofs:
+		v, ok := value.(uint64)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.SetProofs(v)
+		return nil
+	case cycleusage.FieldCycles:
+		v, ok := value.(uint64)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.SetCycles(v)
+		return nil
+	}
+	return fmt.Errorf("unknown CycleUsage field %s", name)
+}
+
+// AddedFields returns all numeric fields that were incremented/decremented during
+// this mutation.
+func (m *CycleUsageMutation) AddedFields() []string {
+	var fields []string
+	if m.addchain_id != nil {
+		fields = append(fields, cycleusage.FieldChainID)
+	}
+	if m.addday != nil {
+		fields = append(fields, cycleusage.FieldDay)
+	}
+	if m.addproofs != nil {
+		fields = append(fields, cycleusage.FieldProofs)
+	}
+	if m.addcycles != nil {
+		fields = append(fields, cycleusage.FieldCycles)
+	}
+	return fields
+}
+
+// AddedField returns the numeric value that was incremented/decremented on a field
+// with the given name. The second boolean return value indicates that this field
+// was not set, or was not defined in the schema.
+func (m *CycleUsageMutation) AddedField(name string) (ent.Value, bool) {
+	switch name {
+	case cycleusage.FieldChainID:
+		return m.AddedChainID()
+	case cycleusage.FieldDay:
+		return m.AddedDay()
+	case cycleusage.FieldProofs:
+		return m.AddedProofs()
+	case cycleusage.FieldCycles:
+		return m.AddedCycles()
+	}
+	return nil, false
+}
+
+// AddField adds the value to the field with the given name. It returns an error if
+// the field is not defined in the schema, or if the type mismatched the field
+// type.
+func (m *CycleUsageMutation) AddField(name string, value ent.Value) error {
+	switch name {
+	case cycleusage.FieldChainID:
+		v, ok := value.(int64)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.AddChainID(v)
+		return nil
+	case cycleusage.FieldDay:
+		v, ok := value.(int64)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.AddDay(v)
+		return nil
+	case cycleusage.FieldProofs:
+		v, ok := value.(int64)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.AddProofs(v)
+		return nil
+	case cycleusage.FieldCycles:
+		v, ok := value.(int64)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.AddCycles(v)
+		return nil
+	}
+	return fmt.Errorf("unknown CycleUsage numeric field %s", name)
+}
+
+// ClearedFields returns all nullable fields that were cleared during this
+// mutation.
+func (m *CycleUsageMutation) ClearedFields() []string {
+	return nil
+}
+
+// FieldCleared returns a boolean indicating if a field with the given name was
+// cleared in this mutation.
+func (m *CycleUsageMutation) FieldCleared(name string) bool {
+	_, ok := m.clearedFields[name]
+	return ok
+}
+
+// ClearField clears the value of the field with the given name. It returns an
+// error if the field is not defined in the schema.
+func (m *CycleUsageMutation) ClearField(name string) error {
+	return fmt.Errorf("unknown CycleUsage nullable field %s", name)
+}
+
+// ResetField resets all changes in the mutation for the field with the given name.
+// It returns an error if the field is not defined in the schema.
+func (m *CycleUsageMutation) ResetField(name string) error {
+	switch name {
+	case cycleusage.FieldType:
+		m.ResetType()
+		return nil
+	case cycleusage.FieldChainID:
+		m.ResetChainID()
+		return nil
+	case cycleusage.FieldDay:
+		m.ResetDay()
+		return nil
+	case cycleusage.FieldProofs:
+		m.ResetProofs()
+		return nil
+	case cycleusage.FieldCycles:
+		m.ResetCycles()
+		return nil
+	}
+	return fmt.Errorf("unknown CycleUsage field %s", name)
+}
+
+// AddedEdges returns all edge names that were set/added in this mutation.
+func (m *CycleUsageMutation) AddedEdges() []string {
+	edges := make([]string, 0, 0)
+	return edges
+}
+
+// AddedIDs returns all IDs (to other nodes) that were added for the given edge
+// name in this mutation.
+func (m *CycleUsageMutation) AddedIDs(name string) []ent.Value {
+	return nil
+}
+
+// RemovedEdges returns all edge names that were removed in this mutation.
+func (m *CycleUsageMutation) RemovedEdges() []string {
+	edges := make([]string, 0, 0)
+	return edges
+}
+
+// RemovedIDs returns all IDs (to other nodes) that were removed for the edge with
+// the given name in this mutation.
+func (m *CycleUsageMutation) RemovedIDs(name string) []ent.Value {
+	return nil
+}
+
+// ClearedEdges returns all edge names that were cleared in this mutation.
+func (m *CycleUsageMutation) ClearedEdges() []string {
+	edges := make([]string, 0, 0)
+	return edges
+}
+
+// EdgeCleared returns a boolean which indicates if the edge with the given name
+// was cleared in this mutation.
+func (m *CycleUsageMutation) EdgeCleared(name string) bool {
+	return false
+}
+
+// ClearEdge clears the value of the edge with the given name. It returns an error
+// if that edge is not defined in the schema.
+func (m *CycleUsageMutation) ClearEdge(name string) error {
+	return fmt.Errorf("unknown CycleUsage unique edge %s", name)
+}
+
+// ResetEdge resets all changes to the edge with the given name in this mutation.
+// It returns an error if the edge is not defined in the schema.
+func (m *CycleUsageMutation) ResetEdge(name string) error {
+	return fmt.Errorf("unknown CycleUsage edge %s", name)
+}
+
 // ProofRequestMutation represents an operation that mutates the ProofRequest nodes in the graph.
 type ProofRequestMutation struct {
 	config
@@ -48,6 +727,10 @@ type ProofRequestMutation struct {
 	l1_block_number       *uint64
 	addl1_block_number    *int64
 	l1_block_hash         *string
+	subproofs             *string
+	witness_sha256        *string
+	error_code            *string
+	checkpoint_overrides  *string
 	proof                 *[]byte
 	clearedFields         map[string]struct{}
 	done                  bool
@@ -687,6 +1370,202 @@ func (m *ProofRequestMutation) ResetL1BlockHash() {
 	delete(m.clearedFields, proofrequest.FieldL1BlockHash)
 }
 
+// SetSubproofs sets the "subproofs" field.
+func (m *ProofRequestMutation) SetSubproofs(s string) {
+	m.subproofs = &s
+}
+
+// Subproofs returns the value of the "subproofs" field in the mutation.
+func (m *ProofRequestMutation) Subproofs() (r string, exists bool) {
+	v := m.subproofs
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// OldSubproofs returns the old "subproofs" field's value of the ProofRequest entity.
+// If the ProofRequest object wasn't provided to the builder, the object is fetched from the database.
+// An error is returned if the mutation operation is not UpdateOne, or the database query fails.
+func (m *ProofRequestMutation) OldSubproofs(ctx context.Context) (v string, err error) {
+	if !m.op.Is(OpUpdateOne) {
+		return v, errors.New("OldSubproofs is only allowed on UpdateOne operations")
+	}
+	if m.id == nil || m.oldValue == nil {
+		return v, errors.New("OldSubproofs requires an ID field in the mutation")
+	}
+	oldValue, err := m.oldValue(ctx)
+	if err != nil {
+		return v, fmt.Errorf("querying old value for OldSubproofs: %w", err)
+	}
+	return oldValue.Subproofs, nil
+}
+
+// ClearSubproofs clears the value of the "subproofs" field.
+func (m *ProofRequestMutation) ClearSubproofs() {
+	m.subproofs = nil
+	m.clearedFields[proofrequest.FieldSubproofs] = struct{}{}
+}
+
+// SubproofsCleared returns if the "subproofs" field was cleared in this mutation.
+func (m *ProofRequestMutation) SubproofsCleared() bool {
+	_, ok := m.clearedFields[proofrequest.FieldSubproofs]
+	return ok
+}
+
+// ResetSubproofs resets all changes to the "subproofs" field.
+func (m *ProofRequestMutation) ResetSubproofs() {
+	m.subproofs = nil
+	delete(m.clearedFields, proofrequest.FieldSubproofs)
+}
+
+// SetWitnessSha256 sets the "witness_sha256" field.
+func (m *ProofRequestMutation) SetWitnessSha256(s string) {
+	m.witness_sha256 = &s
+}
+
+// WitnessSha256 returns the value of the "witness_sha256" field in the mutation.
+func (m *ProofRequestMutation) WitnessSha256() (r string, exists bool) {
+	v := m.witness_sha256
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// OldWitnessSha256 returns the old "witness_sha256" field's value of the ProofRequest entity.
+// If the ProofRequest object wasn't provided to the builder, the object is fetched from the database.
+// An error is returned if the mutation operation is not UpdateOne, or the database query fails.
+func (m *ProofRequestMutation) OldWitnessSha256(ctx context.Context) (v string, err error) {
+	if !m.op.Is(OpUpdateOne) {
+		return v, errors.New("OldWitnessSha256 is only allowed on UpdateOne operations")
+	}
+	if m.id == nil || m.oldValue == nil {
+		return v, errors.New("OldWitnessSha256 requires an ID field in the mutation")
+	}
+	oldValue, err := m.oldValue(ctx)
+	if err != nil {
+		return v, fmt.Errorf("querying old value for OldWitnessSha256: %w", err)
+	}
+	return oldValue.WitnessSha256, nil
+}
+
+// ClearWitnessSha256 clears the value of the "witness_sha256" field.
+func (m *ProofRequestMutation) ClearWitnessSha256() {
+	m.witness_sha256 = nil
+	m.clearedFields[proofrequest.FieldWitnessSha256] = struct{}{}
+}
+
+// WitnessSha256Cleared returns if the "witness_sha256" field was cleared in this mutation.
+func (m *ProofRequestMutation) WitnessSha256Cleared() bool {
+	_, ok := m.clearedFields[proofrequest.FieldWitnessSha256]
+	return ok
+}
+
+// ResetWitnessSha256 resets all changes to the "witness_sha256" field.
+func (m *ProofRequestMutation) ResetWitnessSha256() {
+	m.witness_sha256 = nil
+	delete(m.clearedFields, proofrequest.FieldWitnessSha256)
+}
+
+// SetErrorCode sets the "error_code" field.
+func (m *ProofRequestMutation) SetErrorCode(s string) {
+	m.error_code = &s
+}
+
+// ErrorCode returns the value of the "error_code" field in the mutation.
+func (m *ProofRequestMutation) ErrorCode() (r string, exists bool) {
+	v := m.error_code
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// OldErrorCode returns the old "error_code" field's value of the ProofRequest entity.
+// If the ProofRequest object wasn't provided to the builder, the object is fetched from the database.
+// An error is returned if the mutation operation is not UpdateOne, or the database query fails.
+func (m *ProofRequestMutation) OldErrorCode(ctx context.Context) (v string, err error) {
+	if !m.op.Is(OpUpdateOne) {
+		return v, errors.New("OldErrorCode is only allowed on UpdateOne operations")
+	}
+	if m.id == nil || m.oldValue == nil {
+		return v, errors.New("OldErrorCode requires an ID field in the mutation")
+	}
+	oldValue, err := m.oldValue(ctx)
+	if err != nil {
+		return v, fmt.Errorf("querying old value for OldErrorCode: %w", err)
+	}
+	return oldValue.ErrorCode, nil
+}
+
+// ClearErrorCode clears the value of the "error_code" field.
+func (m *ProofRequestMutation) ClearErrorCode() {
+	m.error_code = nil
+	m.clearedFields[proofrequest.FieldErrorCode] = struct{}{}
+}
+
+// ErrorCodeCleared returns if the "error_code" field was cleared in this mutation.
+func (m *ProofRequestMutation) ErrorCodeCleared() bool {
+	_, ok := m.clearedFields[proofrequest.FieldErrorCode]
+	return ok
+}
+
+// ResetErrorCode resets all changes to the "error_code" field.
+func (m *ProofRequestMutation) ResetErrorCode() {
+	m.error_code = nil
+	delete(m.clearedFields, proofrequest.FieldErrorCode)
+}
+
+// SetCheckpointOverrides sets the "checkpoint_overrides" field.
+func (m *ProofRequestMutation) SetCheckpointOverrides(s string) {
+	m.checkpoint_overrides = &s
+}
+
+// CheckpointOverrides returns the value of the "checkpoint_overrides" field in the mutation.
+func (m *ProofRequestMutation) CheckpointOverrides() (r string, exists bool) {
+	v := m.checkpoint_overrides
+	if v == nil {
+		return
+	}
+	return *v, true
+}
+
+// OldCheckpointOverrides returns the old "checkpoint_overrides" field's value of the ProofRequest entity.
+// If the ProofRequest object wasn't provided to the builder, the object is fetched from the database.
+// An error is returned if the mutation operation is not UpdateOne, or the database query fails.
+func (m *ProofRequestMutation) OldCheckpointOverrides(ctx context.Context) (v string, err error) {
+	if !m.op.Is(OpUpdateOne) {
+		return v, errors.New("OldCheckpointOverrides is only allowed on UpdateOne operations")
+	}
+	if m.id == nil || m.oldValue == nil {
+		return v, errors.New("OldCheckpointOverrides requires an ID field in the mutation")
+	}
+	oldValue, err := m.oldValue(ctx)
+	if err != nil {
+		return v, fmt.Errorf("querying old value for OldCheckpointOverrides: %w", err)
+	}
+	return oldValue.CheckpointOverrides, nil
+}
+
+// ClearCheckpointOverrides clears the value of the "checkpoint_overrides" field.
+func (m *ProofRequestMutation) ClearCheckpointOverrides() {
+	m.checkpoint_overrides = nil
+	m.clearedFields[proofrequest.FieldCheckpointOverrides] = struct{}{}
+}
+
+// CheckpointOverridesCleared returns if the "checkpoint_overrides" field was cleared in this mutation.
+func (m *ProofRequestMutation) CheckpointOverridesCleared() bool {
+	_, ok := m.clearedFields[proofrequest.FieldCheckpointOverrides]
+	return ok
+}
+
+// ResetCheckpointOverrides resets all changes to the "checkpoint_overrides" field.
+func (m *ProofRequestMutation) ResetCheckpointOverrides() {
+	m.checkpoint_overrides = nil
+	delete(m.clearedFields, proofrequest.FieldCheckpointOverrides)
+}
+
 // SetProof sets the "proof" field.
 func (m *ProofRequestMutation) SetProof(b []byte) {
 	m.proof = &b
@@ -770,7 +1649,7 @@ func (m *ProofRequestMutation) Type() string {
 // order to get all numeric fields that were incremented/decremented, call
 // AddedFields().
 func (m *ProofRequestMutation) Fields() []string {
-	fields := make([]string, 0, 11)
+	fields := make([]string, 0, 15)
 	if m._type != nil {
 		fields = append(fields, proofrequest.FieldType)
 	}
@@ -801,6 +1680,18 @@ func (m *ProofRequestMutation) Fields() []string {
 	if m.l1_block_hash != nil {
 		fields = append(fields, proofrequest.FieldL1BlockHash)
 	}
+	if m.subproofs != nil {
+		fields = append(fields, proofrequest.FieldSubproofs)
+	}
+	if m.witness_sha256 != nil {
+		fields = append(fields, proofrequest.FieldWitnessSha256)
+	}
+	if m.error_code != nil {
+		fields = append(fields, proofrequest.FieldErrorCode)
+	}
+	if m.checkpoint_overrides != nil {
+		fields = append(fields, proofrequest.FieldCheckpointOverrides)
+	}
 	if m.proof != nil {
 		fields = append(fields, proofrequest.FieldProof)
 	}
@@ -832,6 +1723,14 @@ func (m *ProofRequestMutation) Field(name string) (ent.Value, bool) {
 		return m.L1BlockNumber()
 	case proofrequest.FieldL1BlockHash:
 		return m.L1BlockHash()
+	case proofrequest.FieldSubproofs:
+		return m.Subproofs()
+	case proofrequest.FieldWitnessSha256:
+		return m.WitnessSha256()
+	case proofrequest.FieldErrorCode:
+		return m.ErrorCode()
+	case proofrequest.FieldCheckpointOverrides:
+		return m.CheckpointOverrides()
 	case proofrequest.FieldProof:
 		return m.Proof()
 	}
@@ -863,6 +1762,14 @@ func (m *ProofRequestMutation) OldField(ctx context.Context, name string) (ent.V
 		return m.OldL1BlockNumber(ctx)
 	case proofrequest.FieldL1BlockHash:
 		return m.OldL1BlockHash(ctx)
+	case proofrequest.FieldSubproofs:
+		return m.OldSubproofs(ctx)
+	case proofrequest.FieldWitnessSha256:
+		return m.OldWitnessSha256(ctx)
+	case proofrequest.FieldErrorCode:
+		return m.OldErrorCode(ctx)
+	case proofrequest.FieldCheckpointOverrides:
+		return m.OldCheckpointOverrides(ctx)
 	case proofrequest.FieldProof:
 		return m.OldProof(ctx)
 	}
@@ -944,6 +1851,34 @@ func (m *ProofRequestMutation) SetField(name string, value ent.Value) error {
 		}
 		m.SetL1BlockHash(v)
 		return nil
+	case proofrequest.FieldSubproofs:
+		v, ok := value.(string)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.SetSubproofs(v)
+		return nil
+	case proofrequest.FieldWitnessSha256:
+		v, ok := value.(string)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.SetWitnessSha256(v)
+		return nil
+	case proofrequest.FieldErrorCode:
+		v, ok := value.(string)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.SetErrorCode(v)
+		return nil
+	case proofrequest.FieldCheckpointOverrides:
+		v, ok := value.(string)
+		if !ok {
+			return fmt.Errorf("unexpected type %T for field %s", value, name)
+		}
+		m.SetCheckpointOverrides(v)
+		return nil
 	case proofrequest.FieldProof:
 		v, ok := value.([]byte)
 		if !ok {
@@ -1068,6 +2003,18 @@ func (m *ProofRequestMutation) ClearedFields() []string {
 	if m.FieldCleared(proofrequest.FieldL1BlockHash) {
 		fields = append(fields, proofrequest.FieldL1BlockHash)
 	}
+	if m.FieldCleared(proofrequest.FieldSubproofs) {
+		fields = append(fields, proofrequest.FieldSubproofs)
+	}
+	if m.FieldCleared(proofrequest.FieldWitnessSha256) {
+		fields = append(fields, proofrequest.FieldWitnessSha256)
+	}
+	if m.FieldCleared(proofrequest.FieldErrorCode) {
+		fields = append(fields, proofrequest.FieldErrorCode)
+	}
+	if m.FieldCleared(proofrequest.FieldCheckpointOverrides) {
+		fields = append(fields, proofrequest.FieldCheckpointOverrides)
+	}
 	if m.FieldCleared(proofrequest.FieldProof) {
 		fields = append(fields, proofrequest.FieldProof)
 	}
@@ -1097,6 +2044,18 @@ func (m *ProofRequestMutation) ClearField(name string) error {
 	case proofrequest.FieldL1BlockHash:
 		m.ClearL1BlockHash()
 		return nil
+	case proofrequest.FieldSubproofs:
+		m.ClearSubproofs()
+		return nil
+	case proofrequest.FieldWitnessSha256:
+		m.ClearWitnessSha256()
+		return nil
+	case proofrequest.FieldErrorCode:
+		m.ClearErrorCode()
+		return nil
+	case proofrequest.FieldCheckpointOverrides:
+		m.ClearCheckpointOverrides()
+		return nil
 	case proofrequest.FieldProof:
 		m.ClearProof()
 		return nil
@@ -1138,6 +2097,18 @@ func (m *ProofRequestMutation) ResetField(name string) error {
 	case proofrequest.FieldL1BlockHash:
 		m.ResetL1BlockHash()
 		return nil
+	case proofrequest.FieldSubproofs:
+		m.ResetSubproofs()
+		return nil
+	case proofrequest.FieldWitnessSha256:
+		m.ResetWitnessSha256()
+		return nil
+	case proofrequest.FieldErrorCode:
+		m.ResetErrorCode()
+		return nil
+	case proofrequest.FieldCheckpointOverrides:
+		m.ResetCheckpointOverrides()
+		return nil
 	case proofrequest.FieldProof:
 		m.ResetProof()
 		return nil
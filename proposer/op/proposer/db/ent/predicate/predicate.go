@@ -6,5 +6,8 @@ import (
 	"entgo.io/ent/dialect/sql"
 )
 
+// CycleUsage is the predicate function for cycleusage builders.
+type CycleUsage func(*sql.Selector)
+
 // ProofRequest is the predicate function for proofrequest builders.
 type ProofRequest func(*sql.Selector)
@@ -36,6 +36,14 @@ type ProofRequest struct {
 	L1BlockNumber uint64 `json:"l1_block_number,omitempty"`
 	// L1BlockHash holds the value of the "l1_block_hash" field.
 	L1BlockHash string `json:"l1_block_hash,omitempty"`
+	// Subproofs holds the value of the "subproofs" field.
+	Subproofs string `json:"subproofs,omitempty"`
+	// WitnessSha256 holds the value of the "witness_sha256" field.
+	WitnessSha256 string `json:"witness_sha256,omitempty"`
+	// ErrorCode holds the value of the "error_code" field.
+	ErrorCode string `json:"error_code,omitempty"`
+	// CheckpointOverrides holds the value of the "checkpoint_overrides" field.
+	CheckpointOverrides string `json:"checkpoint_overrides,omitempty"`
 	// Proof holds the value of the "proof" field.
 	Proof        []byte `json:"proof,omitempty"`
 	selectValues sql.SelectValues
@@ -50,7 +58,7 @@ func (*ProofRequest) scanValues(columns []string) ([]any, error) {
 			values[i] = new([]byte)
 		case proofrequest.FieldID, proofrequest.FieldStartBlock, proofrequest.FieldEndBlock, proofrequest.FieldRequestAddedTime, proofrequest.FieldProofRequestTime, proofrequest.FieldLastUpdatedTime, proofrequest.FieldL1BlockNumber:
 			values[i] = new(sql.NullInt64)
-		case proofrequest.FieldType, proofrequest.FieldStatus, proofrequest.FieldProverRequestID, proofrequest.FieldL1BlockHash:
+		case proofrequest.FieldType, proofrequest.FieldStatus, proofrequest.FieldProverRequestID, proofrequest.FieldL1BlockHash, proofrequest.FieldSubproofs, proofrequest.FieldWitnessSha256, proofrequest.FieldErrorCode, proofrequest.FieldCheckpointOverrides:
 			values[i] = new(sql.NullString)
 		default:
 			values[i] = new(sql.UnknownType)
@@ -133,6 +141,30 @@ func (pr *ProofRequest) assignValues(columns []string, values []any) error {
 			} else if value.Valid {
 				pr.L1BlockHash = value.String
 			}
+		case proofrequest.FieldSubproofs:
+			if value, ok := values[i].(*sql.NullString); !ok {
+				return fmt.Errorf("unexpected type %T for field subproofs", values[i])
+			} else if value.Valid {
+				pr.Subproofs = value.String
+			}
+		case proofrequest.FieldWitnessSha256:
+			if value, ok := values[i].(*sql.NullString); !ok {
+				return fmt.Errorf("unexpected type %T for field witness_sha256", values[i])
+			} else if value.Valid {
+				pr.WitnessSha256 = value.String
+			}
+		case proofrequest.FieldErrorCode:
+			if value, ok := values[i].(*sql.NullString); !ok {
+				return fmt.Errorf("unexpected type %T for field error_code", values[i])
+			} else if value.Valid {
+				pr.ErrorCode = value.String
+			}
+		case proofrequest.FieldCheckpointOverrides:
+			if value, ok := values[i].(*sql.NullString); !ok {
+				return fmt.Errorf("unexpected type %T for field checkpoint_overrides", values[i])
+			} else if value.Valid {
+				pr.CheckpointOverrides = value.String
+			}
 		case proofrequest.FieldProof:
 			if value, ok := values[i].(*[]byte); !ok {
 				return fmt.Errorf("unexpected type %T for field proof", values[i])
@@ -205,6 +237,18 @@ func (pr *ProofRequest) String() string {
 	builder.WriteString("l1_block_hash=")
 	builder.WriteString(pr.L1BlockHash)
 	builder.WriteString(", ")
+	builder.WriteString("subproofs=")
+	builder.WriteString(pr.Subproofs)
+	builder.WriteString(", ")
+	builder.WriteString("witness_sha256=")
+	builder.WriteString(pr.WitnessSha256)
+	builder.WriteString(", ")
+	builder.WriteString("error_code=")
+	builder.WriteString(pr.ErrorCode)
+	builder.WriteString(", ")
+	builder.WriteString("checkpoint_overrides=")
+	builder.WriteString(pr.CheckpointOverrides)
+	builder.WriteString(", ")
 	builder.WriteString("proof=")
 	builder.WriteString(fmt.Sprintf("%v", pr.Proof))
 	builder.WriteByte(')')
@@ -33,6 +33,14 @@ const (
 	FieldL1BlockNumber = "l1_block_number"
 	// FieldL1BlockHash holds the string denoting the l1_block_hash field in the database.
 	FieldL1BlockHash = "l1_block_hash"
+	// FieldSubproofs holds the string denoting the subproofs field in the database.
+	FieldSubproofs = "subproofs"
+	// FieldWitnessSha256 holds the string denoting the witness_sha256 field in the database.
+	FieldWitnessSha256 = "witness_sha256"
+	// FieldErrorCode holds the string denoting the error_code field in the database.
+	FieldErrorCode = "error_code"
+	// FieldCheckpointOverrides holds the string denoting the checkpoint_overrides field in the database.
+	FieldCheckpointOverrides = "checkpoint_overrides"
 	// FieldProof holds the string denoting the proof field in the database.
 	FieldProof = "proof"
 	// Table holds the table name of the proofrequest in the database.
@@ -52,6 +60,10 @@ var Columns = []string{
 	FieldLastUpdatedTime,
 	FieldL1BlockNumber,
 	FieldL1BlockHash,
+	FieldSubproofs,
+	FieldWitnessSha256,
+	FieldErrorCode,
+	FieldCheckpointOverrides,
 	FieldProof,
 }
 
@@ -171,3 +183,23 @@ func ByL1BlockNumber(opts ...sql.OrderTermOption) OrderOption {
 func ByL1BlockHash(opts ...sql.OrderTermOption) OrderOption {
 	return sql.OrderByField(FieldL1BlockHash, opts...).ToFunc()
 }
+
+// BySubproofs orders the results by the subproofs field.
+func BySubproofs(opts ...sql.OrderTermOption) OrderOption {
+	return sql.OrderByField(FieldSubproofs, opts...).ToFunc()
+}
+
+// ByWitnessSha256 orders the results by the witness_sha256 field.
+func ByWitnessSha256(opts ...sql.OrderTermOption) OrderOption {
+	return sql.OrderByField(FieldWitnessSha256, opts...).ToFunc()
+}
+
+// ByErrorCode orders the results by the error_code field.
+func ByErrorCode(opts ...sql.OrderTermOption) OrderOption {
+	return sql.OrderByField(FieldErrorCode, opts...).ToFunc()
+}
+
+// ByCheckpointOverrides orders the results by the checkpoint_overrides field.
+func ByCheckpointOverrides(opts ...sql.OrderTermOption) OrderOption {
+	return sql.OrderByField(FieldCheckpointOverrides, opts...).ToFunc()
+}
@@ -92,6 +92,26 @@ func L1BlockHash(v string) predicate.ProofRequest {
 	return predicate.ProofRequest(sql.FieldEQ(FieldL1BlockHash, v))
 }
 
+// Subproofs applies equality check predicate on the "subproofs" field. It's identical to SubproofsEQ.
+func Subproofs(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEQ(FieldSubproofs, v))
+}
+
+// WitnessSha256 applies equality check predicate on the "witness_sha256" field. It's identical to WitnessSha256EQ.
+func WitnessSha256(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEQ(FieldWitnessSha256, v))
+}
+
+// ErrorCode applies equality check predicate on the "error_code" field. It's identical to ErrorCodeEQ.
+func ErrorCode(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEQ(FieldErrorCode, v))
+}
+
+// CheckpointOverrides applies equality check predicate on the "checkpoint_overrides" field. It's identical to CheckpointOverridesEQ.
+func CheckpointOverrides(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEQ(FieldCheckpointOverrides, v))
+}
+
 // Proof applies equality check predicate on the "proof" field. It's identical to ProofEQ.
 func Proof(v []byte) predicate.ProofRequest {
 	return predicate.ProofRequest(sql.FieldEQ(FieldProof, v))
@@ -547,6 +567,306 @@ func L1BlockHashContainsFold(v string) predicate.ProofRequest {
 	return predicate.ProofRequest(sql.FieldContainsFold(FieldL1BlockHash, v))
 }
 
+// SubproofsEQ applies the EQ predicate on the "subproofs" field.
+func SubproofsEQ(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEQ(FieldSubproofs, v))
+}
+
+// SubproofsNEQ applies the NEQ predicate on the "subproofs" field.
+func SubproofsNEQ(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNEQ(FieldSubproofs, v))
+}
+
+// SubproofsIn applies the In predicate on the "subproofs" field.
+func SubproofsIn(vs ...string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldIn(FieldSubproofs, vs...))
+}
+
+// SubproofsNotIn applies the NotIn predicate on the "subproofs" field.
+func SubproofsNotIn(vs ...string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNotIn(FieldSubproofs, vs...))
+}
+
+// SubproofsGT applies the GT predicate on the "subproofs" field.
+func SubproofsGT(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldGT(FieldSubproofs, v))
+}
+
+// SubproofsGTE applies the GTE predicate on the "subproofs" field.
+func SubproofsGTE(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldGTE(FieldSubproofs, v))
+}
+
+// SubproofsLT applies the LT predicate on the "subproofs" field.
+func SubproofsLT(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldLT(FieldSubproofs, v))
+}
+
+// SubproofsLTE applies the LTE predicate on the "subproofs" field.
+func SubproofsLTE(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldLTE(FieldSubproofs, v))
+}
+
+// SubproofsContains applies the Contains predicate on the "subproofs" field.
+func SubproofsContains(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldContains(FieldSubproofs, v))
+}
+
+// SubproofsHasPrefix applies the HasPrefix predicate on the "subproofs" field.
+func SubproofsHasPrefix(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldHasPrefix(FieldSubproofs, v))
+}
+
+// SubproofsHasSuffix applies the HasSuffix predicate on the "subproofs" field.
+func SubproofsHasSuffix(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldHasSuffix(FieldSubproofs, v))
+}
+
+// SubproofsIsNil applies the IsNil predicate on the "subproofs" field.
+func SubproofsIsNil() predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldIsNull(FieldSubproofs))
+}
+
+// SubproofsNotNil applies the NotNil predicate on the "subproofs" field.
+func SubproofsNotNil() predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNotNull(FieldSubproofs))
+}
+
+// SubproofsEqualFold applies the EqualFold predicate on the "subproofs" field.
+func SubproofsEqualFold(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEqualFold(FieldSubproofs, v))
+}
+
+// SubproofsContainsFold applies the ContainsFold predicate on the "subproofs" field.
+func SubproofsContainsFold(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldContainsFold(FieldSubproofs, v))
+}
+
+// WitnessSha256EQ applies the EQ predicate on the "witness_sha256" field.
+func WitnessSha256EQ(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEQ(FieldWitnessSha256, v))
+}
+
+// WitnessSha256NEQ applies the NEQ predicate on the "witness_sha256" field.
+func WitnessSha256NEQ(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNEQ(FieldWitnessSha256, v))
+}
+
+// WitnessSha256In applies the In predicate on the "witness_sha256" field.
+func WitnessSha256In(vs ...string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldIn(FieldWitnessSha256, vs...))
+}
+
+// WitnessSha256NotIn applies the NotIn predicate on the "witness_sha256" field.
+func WitnessSha256NotIn(vs ...string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNotIn(FieldWitnessSha256, vs...))
+}
+
+// WitnessSha256GT applies the GT predicate on the "witness_sha256" field.
+func WitnessSha256GT(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldGT(FieldWitnessSha256, v))
+}
+
+// WitnessSha256GTE applies the GTE predicate on the "witness_sha256" field.
+func WitnessSha256GTE(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldGTE(FieldWitnessSha256, v))
+}
+
+// WitnessSha256LT applies the LT predicate on the "witness_sha256" field.
+func WitnessSha256LT(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldLT(FieldWitnessSha256, v))
+}
+
+// WitnessSha256LTE applies the LTE predicate on the "witness_sha256" field.
+func WitnessSha256LTE(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldLTE(FieldWitnessSha256, v))
+}
+
+// WitnessSha256Contains applies the Contains predicate on the "witness_sha256" field.
+func WitnessSha256Contains(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldContains(FieldWitnessSha256, v))
+}
+
+// WitnessSha256HasPrefix applies the HasPrefix predicate on the "witness_sha256" field.
+func WitnessSha256HasPrefix(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldHasPrefix(FieldWitnessSha256, v))
+}
+
+// WitnessSha256HasSuffix applies the HasSuffix predicate on the "witness_sha256" field.
+func WitnessSha256HasSuffix(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldHasSuffix(FieldWitnessSha256, v))
+}
+
+// WitnessSha256IsNil applies the IsNil predicate on the "witness_sha256" field.
+func WitnessSha256IsNil() predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldIsNull(FieldWitnessSha256))
+}
+
+// WitnessSha256NotNil applies the NotNil predicate on the "witness_sha256" field.
+func WitnessSha256NotNil() predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNotNull(FieldWitnessSha256))
+}
+
+// WitnessSha256EqualFold applies the EqualFold predicate on the "witness_sha256" field.
+func WitnessSha256EqualFold(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEqualFold(FieldWitnessSha256, v))
+}
+
+// WitnessSha256ContainsFold applies the ContainsFold predicate on the "witness_sha256" field.
+func WitnessSha256ContainsFold(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldContainsFold(FieldWitnessSha256, v))
+}
+
+// ErrorCodeEQ applies the EQ predicate on the "error_code" field.
+func ErrorCodeEQ(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEQ(FieldErrorCode, v))
+}
+
+// ErrorCodeNEQ applies the NEQ predicate on the "error_code" field.
+func ErrorCodeNEQ(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNEQ(FieldErrorCode, v))
+}
+
+// ErrorCodeIn applies the In predicate on the "error_code" field.
+func ErrorCodeIn(vs ...string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldIn(FieldErrorCode, vs...))
+}
+
+// ErrorCodeNotIn applies the NotIn predicate on the "error_code" field.
+func ErrorCodeNotIn(vs ...string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNotIn(FieldErrorCode, vs...))
+}
+
+// ErrorCodeGT applies the GT predicate on the "error_code" field.
+func ErrorCodeGT(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldGT(FieldErrorCode, v))
+}
+
+// ErrorCodeGTE applies the GTE predicate on the "error_code" field.
+func ErrorCodeGTE(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldGTE(FieldErrorCode, v))
+}
+
+// ErrorCodeLT applies the LT predicate on the "error_code" field.
+func ErrorCodeLT(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldLT(FieldErrorCode, v))
+}
+
+// ErrorCodeLTE applies the LTE predicate on the "error_code" field.
+func ErrorCodeLTE(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldLTE(FieldErrorCode, v))
+}
+
+// ErrorCodeContains applies the Contains predicate on the "error_code" field.
+func ErrorCodeContains(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldContains(FieldErrorCode, v))
+}
+
+// ErrorCodeHasPrefix applies the HasPrefix predicate on the "error_code" field.
+func ErrorCodeHasPrefix(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldHasPrefix(FieldErrorCode, v))
+}
+
+// ErrorCodeHasSuffix applies the HasSuffix predicate on the "error_code" field.
+func ErrorCodeHasSuffix(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldHasSuffix(FieldErrorCode, v))
+}
+
+// ErrorCodeIsNil applies the IsNil predicate on the "error_code" field.
+func ErrorCodeIsNil() predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldIsNull(FieldErrorCode))
+}
+
+// ErrorCodeNotNil applies the NotNil predicate on the "error_code" field.
+func ErrorCodeNotNil() predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNotNull(FieldErrorCode))
+}
+
+// ErrorCodeEqualFold applies the EqualFold predicate on the "error_code" field.
+func ErrorCodeEqualFold(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEqualFold(FieldErrorCode, v))
+}
+
+// ErrorCodeContainsFold applies the ContainsFold predicate on the "error_code" field.
+func ErrorCodeContainsFold(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldContainsFold(FieldErrorCode, v))
+}
+
+// CheckpointOverridesEQ applies the EQ predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesEQ(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEQ(FieldCheckpointOverrides, v))
+}
+
+// CheckpointOverridesNEQ applies the NEQ predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesNEQ(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNEQ(FieldCheckpointOverrides, v))
+}
+
+// CheckpointOverridesIn applies the In predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesIn(vs ...string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldIn(FieldCheckpointOverrides, vs...))
+}
+
+// CheckpointOverridesNotIn applies the NotIn predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesNotIn(vs ...string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNotIn(FieldCheckpointOverrides, vs...))
+}
+
+// CheckpointOverridesGT applies the GT predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesGT(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldGT(FieldCheckpointOverrides, v))
+}
+
+// CheckpointOverridesGTE applies the GTE predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesGTE(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldGTE(FieldCheckpointOverrides, v))
+}
+
+// CheckpointOverridesLT applies the LT predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesLT(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldLT(FieldCheckpointOverrides, v))
+}
+
+// CheckpointOverridesLTE applies the LTE predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesLTE(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldLTE(FieldCheckpointOverrides, v))
+}
+
+// CheckpointOverridesContains applies the Contains predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesContains(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldContains(FieldCheckpointOverrides, v))
+}
+
+// CheckpointOverridesHasPrefix applies the HasPrefix predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesHasPrefix(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldHasPrefix(FieldCheckpointOverrides, v))
+}
+
+// CheckpointOverridesHasSuffix applies the HasSuffix predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesHasSuffix(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldHasSuffix(FieldCheckpointOverrides, v))
+}
+
+// CheckpointOverridesIsNil applies the IsNil predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesIsNil() predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldIsNull(FieldCheckpointOverrides))
+}
+
+// CheckpointOverridesNotNil applies the NotNil predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesNotNil() predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldNotNull(FieldCheckpointOverrides))
+}
+
+// CheckpointOverridesEqualFold applies the EqualFold predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesEqualFold(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldEqualFold(FieldCheckpointOverrides, v))
+}
+
+// CheckpointOverridesContainsFold applies the ContainsFold predicate on the "checkpoint_overrides" field.
+func CheckpointOverridesContainsFold(v string) predicate.ProofRequest {
+	return predicate.ProofRequest(sql.FieldContainsFold(FieldCheckpointOverrides, v))
+}
+
 // ProofEQ applies the EQ predicate on the "proof" field.
 func ProofEQ(v []byte) predicate.ProofRequest {
 	return predicate.ProofRequest(sql.FieldEQ(FieldProof, v))
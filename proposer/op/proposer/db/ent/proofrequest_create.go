@@ -111,6 +111,62 @@ func (prc *ProofRequestCreate) SetNillableL1BlockHash(s *string) *ProofRequestCr
 	return prc
 }
 
+// SetSubproofs sets the "subproofs" field.
+func (prc *ProofRequestCreate) SetSubproofs(s string) *ProofRequestCreate {
+	prc.mutation.SetSubproofs(s)
+	return prc
+}
+
+// SetNillableSubproofs sets the "subproofs" field if the given value is not nil.
+func (prc *ProofRequestCreate) SetNillableSubproofs(s *string) *ProofRequestCreate {
+	if s != nil {
+		prc.SetSubproofs(*s)
+	}
+	return prc
+}
+
+// SetWitnessSha256 sets the "witness_sha256" field.
+func (prc *ProofRequestCreate) SetWitnessSha256(s string) *ProofRequestCreate {
+	prc.mutation.SetWitnessSha256(s)
+	return prc
+}
+
+// SetNillableWitnessSha256 sets the "witness_sha256" field if the given value is not nil.
+func (prc *ProofRequestCreate) SetNillableWitnessSha256(s *string) *ProofRequestCreate {
+	if s != nil {
+		prc.SetWitnessSha256(*s)
+	}
+	return prc
+}
+
+// SetErrorCode sets the "error_code" field.
+func (prc *ProofRequestCreate) SetErrorCode(s string) *ProofRequestCreate {
+	prc.mutation.SetErrorCode(s)
+	return prc
+}
+
+// SetNillableErrorCode sets the "error_code" field if the given value is not nil.
+func (prc *ProofRequestCreate) SetNillableErrorCode(s *string) *ProofRequestCreate {
+	if s != nil {
+		prc.SetErrorCode(*s)
+	}
+	return prc
+}
+
+// SetCheckpointOverrides sets the "checkpoint_overrides" field.
+func (prc *ProofRequestCreate) SetCheckpointOverrides(s string) *ProofRequestCreate {
+	prc.mutation.SetCheckpointOverrides(s)
+	return prc
+}
+
+// SetNillableCheckpointOverrides sets the "checkpoint_overrides" field if the given value is not nil.
+func (prc *ProofRequestCreate) SetNillableCheckpointOverrides(s *string) *ProofRequestCreate {
+	if s != nil {
+		prc.SetCheckpointOverrides(*s)
+	}
+	return prc
+}
+
 // SetProof sets the "proof" field.
 func (prc *ProofRequestCreate) SetProof(b []byte) *ProofRequestCreate {
 	prc.mutation.SetProof(b)
@@ -245,6 +301,22 @@ func (prc *ProofRequestCreate) createSpec() (*ProofRequest, *sqlgraph.CreateSpec
 		_spec.SetField(proofrequest.FieldL1BlockHash, field.TypeString, value)
 		_node.L1BlockHash = value
 	}
+	if value, ok := prc.mutation.Subproofs(); ok {
+		_spec.SetField(proofrequest.FieldSubproofs, field.TypeString, value)
+		_node.Subproofs = value
+	}
+	if value, ok := prc.mutation.WitnessSha256(); ok {
+		_spec.SetField(proofrequest.FieldWitnessSha256, field.TypeString, value)
+		_node.WitnessSha256 = value
+	}
+	if value, ok := prc.mutation.ErrorCode(); ok {
+		_spec.SetField(proofrequest.FieldErrorCode, field.TypeString, value)
+		_node.ErrorCode = value
+	}
+	if value, ok := prc.mutation.CheckpointOverrides(); ok {
+		_spec.SetField(proofrequest.FieldCheckpointOverrides, field.TypeString, value)
+		_node.CheckpointOverrides = value
+	}
 	if value, ok := prc.mutation.Proof(); ok {
 		_spec.SetField(proofrequest.FieldProof, field.TypeBytes, value)
 		_node.Proof = value
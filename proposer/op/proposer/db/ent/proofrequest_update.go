@@ -233,6 +233,86 @@ func (pru *ProofRequestUpdate) ClearL1BlockHash() *ProofRequestUpdate {
 	return pru
 }
 
+// SetSubproofs sets the "subproofs" field.
+func (pru *ProofRequestUpdate) SetSubproofs(s string) *ProofRequestUpdate {
+	pru.mutation.SetSubproofs(s)
+	return pru
+}
+
+// SetNillableSubproofs sets the "subproofs" field if the given value is not nil.
+func (pru *ProofRequestUpdate) SetNillableSubproofs(s *string) *ProofRequestUpdate {
+	if s != nil {
+		pru.SetSubproofs(*s)
+	}
+	return pru
+}
+
+// ClearSubproofs clears the value of the "subproofs" field.
+func (pru *ProofRequestUpdate) ClearSubproofs() *ProofRequestUpdate {
+	pru.mutation.ClearSubproofs()
+	return pru
+}
+
+// SetWitnessSha256 sets the "witness_sha256" field.
+func (pru *ProofRequestUpdate) SetWitnessSha256(s string) *ProofRequestUpdate {
+	pru.mutation.SetWitnessSha256(s)
+	return pru
+}
+
+// SetNillableWitnessSha256 sets the "witness_sha256" field if the given value is not nil.
+func (pru *ProofRequestUpdate) SetNillableWitnessSha256(s *string) *ProofRequestUpdate {
+	if s != nil {
+		pru.SetWitnessSha256(*s)
+	}
+	return pru
+}
+
+// ClearWitnessSha256 clears the value of the "witness_sha256" field.
+func (pru *ProofRequestUpdate) ClearWitnessSha256() *ProofRequestUpdate {
+	pru.mutation.ClearWitnessSha256()
+	return pru
+}
+
+// SetErrorCode sets the "error_code" field.
+func (pru *ProofRequestUpdate) SetErrorCode(s string) *ProofRequestUpdate {
+	pru.mutation.SetErrorCode(s)
+	return pru
+}
+
+// SetNillableErrorCode sets the "error_code" field if the given value is not nil.
+func (pru *ProofRequestUpdate) SetNillableErrorCode(s *string) *ProofRequestUpdate {
+	if s != nil {
+		pru.SetErrorCode(*s)
+	}
+	return pru
+}
+
+// ClearErrorCode clears the value of the "error_code" field.
+func (pru *ProofRequestUpdate) ClearErrorCode() *ProofRequestUpdate {
+	pru.mutation.ClearErrorCode()
+	return pru
+}
+
+// SetCheckpointOverrides sets the "checkpoint_overrides" field.
+func (pru *ProofRequestUpdate) SetCheckpointOverrides(s string) *ProofRequestUpdate {
+	pru.mutation.SetCheckpointOverrides(s)
+	return pru
+}
+
+// SetNillableCheckpointOverrides sets the "checkpoint_overrides" field if the given value is not nil.
+func (pru *ProofRequestUpdate) SetNillableCheckpointOverrides(s *string) *ProofRequestUpdate {
+	if s != nil {
+		pru.SetCheckpointOverrides(*s)
+	}
+	return pru
+}
+
+// ClearCheckpointOverrides clears the value of the "checkpoint_overrides" field.
+func (pru *ProofRequestUpdate) ClearCheckpointOverrides() *ProofRequestUpdate {
+	pru.mutation.ClearCheckpointOverrides()
+	return pru
+}
+
 // SetProof sets the "proof" field.
 func (pru *ProofRequestUpdate) SetProof(b []byte) *ProofRequestUpdate {
 	pru.mutation.SetProof(b)
@@ -364,6 +444,30 @@ func (pru *ProofRequestUpdate) sqlSave(ctx context.Context) (n int, err error) {
 	if pru.mutation.L1BlockHashCleared() {
 		_spec.ClearField(proofrequest.FieldL1BlockHash, field.TypeString)
 	}
+	if value, ok := pru.mutation.Subproofs(); ok {
+		_spec.SetField(proofrequest.FieldSubproofs, field.TypeString, value)
+	}
+	if pru.mutation.SubproofsCleared() {
+		_spec.ClearField(proofrequest.FieldSubproofs, field.TypeString)
+	}
+	if value, ok := pru.mutation.WitnessSha256(); ok {
+		_spec.SetField(proofrequest.FieldWitnessSha256, field.TypeString, value)
+	}
+	if pru.mutation.WitnessSha256Cleared() {
+		_spec.ClearField(proofrequest.FieldWitnessSha256, field.TypeString)
+	}
+	if value, ok := pru.mutation.ErrorCode(); ok {
+		_spec.SetField(proofrequest.FieldErrorCode, field.TypeString, value)
+	}
+	if value, ok := pru.mutation.CheckpointOverrides(); ok {
+		_spec.SetField(proofrequest.FieldCheckpointOverrides, field.TypeString, value)
+	}
+	if pru.mutation.ErrorCodeCleared() {
+		_spec.ClearField(proofrequest.FieldErrorCode, field.TypeString)
+	}
+	if pru.mutation.CheckpointOverridesCleared() {
+		_spec.ClearField(proofrequest.FieldCheckpointOverrides, field.TypeString)
+	}
 	if value, ok := pru.mutation.Proof(); ok {
 		_spec.SetField(proofrequest.FieldProof, field.TypeBytes, value)
 	}
@@ -596,6 +700,86 @@ func (pruo *ProofRequestUpdateOne) ClearL1BlockHash() *ProofRequestUpdateOne {
 	return pruo
 }
 
+// SetSubproofs sets the "subproofs" field.
+func (pruo *ProofRequestUpdateOne) SetSubproofs(s string) *ProofRequestUpdateOne {
+	pruo.mutation.SetSubproofs(s)
+	return pruo
+}
+
+// SetNillableSubproofs sets the "subproofs" field if the given value is not nil.
+func (pruo *ProofRequestUpdateOne) SetNillableSubproofs(s *string) *ProofRequestUpdateOne {
+	if s != nil {
+		pruo.SetSubproofs(*s)
+	}
+	return pruo
+}
+
+// ClearSubproofs clears the value of the "subproofs" field.
+func (pruo *ProofRequestUpdateOne) ClearSubproofs() *ProofRequestUpdateOne {
+	pruo.mutation.ClearSubproofs()
+	return pruo
+}
+
+// SetWitnessSha256 sets the "witness_sha256" field.
+func (pruo *ProofRequestUpdateOne) SetWitnessSha256(s string) *ProofRequestUpdateOne {
+	pruo.mutation.SetWitnessSha256(s)
+	return pruo
+}
+
+// SetNillableWitnessSha256 sets the "witness_sha256" field if the given value is not nil.
+func (pruo *ProofRequestUpdateOne) SetNillableWitnessSha256(s *string) *ProofRequestUpdateOne {
+	if s != nil {
+		pruo.SetWitnessSha256(*s)
+	}
+	return pruo
+}
+
+// ClearWitnessSha256 clears the value of the "witness_sha256" field.
+func (pruo *ProofRequestUpdateOne) ClearWitnessSha256() *ProofRequestUpdateOne {
+	pruo.mutation.ClearWitnessSha256()
+	return pruo
+}
+
+// SetErrorCode sets the "error_code" field.
+func (pruo *ProofRequestUpdateOne) SetErrorCode(s string) *ProofRequestUpdateOne {
+	pruo.mutation.SetErrorCode(s)
+	return pruo
+}
+
+// SetNillableErrorCode sets the "error_code" field if the given value is not nil.
+func (pruo *ProofRequestUpdateOne) SetNillableErrorCode(s *string) *ProofRequestUpdateOne {
+	if s != nil {
+		pruo.SetErrorCode(*s)
+	}
+	return pruo
+}
+
+// ClearErrorCode clears the value of the "error_code" field.
+func (pruo *ProofRequestUpdateOne) ClearErrorCode() *ProofRequestUpdateOne {
+	pruo.mutation.ClearErrorCode()
+	return pruo
+}
+
+// SetCheckpointOverrides sets the "checkpoint_overrides" field.
+func (pruo *ProofRequestUpdateOne) SetCheckpointOverrides(s string) *ProofRequestUpdateOne {
+	pruo.mutation.SetCheckpointOverrides(s)
+	return pruo
+}
+
+// SetNillableCheckpointOverrides sets the "checkpoint_overrides" field if the given value is not nil.
+func (pruo *ProofRequestUpdateOne) SetNillableCheckpointOverrides(s *string) *ProofRequestUpdateOne {
+	if s != nil {
+		pruo.SetCheckpointOverrides(*s)
+	}
+	return pruo
+}
+
+// ClearCheckpointOverrides clears the value of the "checkpoint_overrides" field.
+func (pruo *ProofRequestUpdateOne) ClearCheckpointOverrides() *ProofRequestUpdateOne {
+	pruo.mutation.ClearCheckpointOverrides()
+	return pruo
+}
+
 // SetProof sets the "proof" field.
 func (pruo *ProofRequestUpdateOne) SetProof(b []byte) *ProofRequestUpdateOne {
 	pruo.mutation.SetProof(b)
@@ -757,6 +941,30 @@ func (pruo *ProofRequestUpdateOne) sqlSave(ctx context.Context) (_node *ProofReq
 	if pruo.mutation.L1BlockHashCleared() {
 		_spec.ClearField(proofrequest.FieldL1BlockHash, field.TypeString)
 	}
+	if value, ok := pruo.mutation.Subproofs(); ok {
+		_spec.SetField(proofrequest.FieldSubproofs, field.TypeString, value)
+	}
+	if pruo.mutation.SubproofsCleared() {
+		_spec.ClearField(proofrequest.FieldSubproofs, field.TypeString)
+	}
+	if value, ok := pruo.mutation.WitnessSha256(); ok {
+		_spec.SetField(proofrequest.FieldWitnessSha256, field.TypeString, value)
+	}
+	if pruo.mutation.WitnessSha256Cleared() {
+		_spec.ClearField(proofrequest.FieldWitnessSha256, field.TypeString)
+	}
+	if value, ok := pruo.mutation.ErrorCode(); ok {
+		_spec.SetField(proofrequest.FieldErrorCode, field.TypeString, value)
+	}
+	if value, ok := pruo.mutation.CheckpointOverrides(); ok {
+		_spec.SetField(proofrequest.FieldCheckpointOverrides, field.TypeString, value)
+	}
+	if pruo.mutation.ErrorCodeCleared() {
+		_spec.ClearField(proofrequest.FieldErrorCode, field.TypeString)
+	}
+	if pruo.mutation.CheckpointOverridesCleared() {
+		_spec.ClearField(proofrequest.FieldCheckpointOverrides, field.TypeString)
+	}
 	if value, ok := pruo.mutation.Proof(); ok {
 		_spec.SetField(proofrequest.FieldProof, field.TypeBytes, value)
 	}
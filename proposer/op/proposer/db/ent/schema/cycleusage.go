@@ -0,0 +1,41 @@
+package schema
+
+import (
+	"entgo.io/ent"
+	"entgo.io/ent/dialect/entsql"
+	"entgo.io/ent/schema"
+	"entgo.io/ent/schema/field"
+	"entgo.io/ent/schema/index"
+)
+
+// CycleUsage holds the schema definition for the CycleUsage entity, the zkVM cycles of the proofs of one type
+// fulfilled on a chain in a UTC day. Kept apart from the proof requests so the totals survive pruning.
+type CycleUsage struct {
+	ent.Schema
+}
+
+func (CycleUsage) Annotations() []schema.Annotation {
+	// Use STRICT mode to enforce strong typing.
+	return []schema.Annotation{
+		entsql.Annotation{Table: "cycle_usages", Options: "STRICT"},
+	}
+}
+
+// Fields of the CycleUsage.
+func (CycleUsage) Fields() []ent.Field {
+	return []ent.Field{
+		field.Enum("type").Values("SPAN", "AGG"),
+		field.Uint64("chain_id"),
+		// The unix timestamp of the start of the UTC day.
+		field.Uint64("day"),
+		field.Uint64("proofs"),
+		field.Uint64("cycles"),
+	}
+}
+
+// Indexes of the CycleUsage.
+func (CycleUsage) Indexes() []ent.Index {
+	return []ent.Index{
+		index.Fields("chain_id", "day", "type").Unique(),
+	}
+}
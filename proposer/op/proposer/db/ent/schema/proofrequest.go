@@ -32,6 +32,17 @@ func (ProofRequest) Fields() []ent.Field {
 		field.Uint64("last_updated_time"),
 		field.Uint64("l1_block_number").Optional(),
 		field.String("l1_block_hash").Optional(),
+		// The IDs of the span proofs an AGG proof aggregates, recorded when its session starts.
+		field.String("subproofs").Optional(),
+		// The SHA-256 hash of the witness a SPAN proof was proven from, as returned by the server. Sent back when the
+		// span is retried, so the server only reuses its cached witness if it is unchanged.
+		field.String("witness_sha256").Optional(),
+		// Why a FAILED proof request failed, as an error code shared with the op-succinct-server, e.g.
+		// "E_PROVER_TIMEOUT".
+		field.String("error_code").Optional(),
+		// The checkpoint overrides an AGG proof aggregates over, as JSON, with the justification governance signed for
+		// skipping the gaps they bridge. Kept with the request for audit.
+		field.String("checkpoint_overrides").Optional(),
 		field.Bytes("proof").Optional(),
 	}
 }
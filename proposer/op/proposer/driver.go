@@ -6,7 +6,6 @@ import (
 	"fmt"
 	"math/big"
 	_ "net/http/pprof"
-	"sort"
 	"sync"
 	"time"
 
@@ -51,10 +50,16 @@ type L2OOContract interface {
 	Version(*bind.CallOpts) (string, error)
 	LatestBlockNumber(*bind.CallOpts) (*big.Int, error)
 	NextBlockNumber(*bind.CallOpts) (*big.Int, error)
+	SubmissionInterval(*bind.CallOpts) (*big.Int, error)
 	LatestOutputIndex(*bind.CallOpts) (*big.Int, error)
 	NextOutputIndex(*bind.CallOpts) (*big.Int, error)
 	StartingTimestamp(*bind.CallOpts) (*big.Int, error)
 	L2BLOCKTIME(*bind.CallOpts) (*big.Int, error)
+	HistoricBlockHashes(*bind.CallOpts, *big.Int) ([32]byte, error)
+	GetL2Output(*bind.CallOpts, *big.Int) (opsuccinctbindings.TypesOutputProposal, error)
+	AggregationVkey(*bind.CallOpts) ([32]byte, error)
+	RangeVkeyCommitment(*bind.CallOpts) ([32]byte, error)
+	RollupConfigHash(*bind.CallOpts) ([32]byte, error)
 }
 
 type RollupClient interface {
@@ -92,6 +97,8 @@ type L2OutputSubmitter struct {
 	dgfABI *abi.ABI
 
 	db db.ProofDB
+
+	checkpoints *CheckpointTracker
 }
 
 // NewL2OutputSubmitter creates a new L2 Output Submitter
@@ -158,6 +165,8 @@ func newL2OOSubmitter(ctx context.Context, cancel context.CancelFunc, setup Driv
 		dgfABI:       dfgAbiParsed,
 
 		db: *db,
+
+		checkpoints: NewCheckpointTracker(),
 	}, nil
 }
 
@@ -194,6 +203,11 @@ func (l *L2OutputSubmitter) StartL2OutputSubmitting() error {
 	l.wg.Add(1)
 	go l.loop()
 
+	if l.Cfg.FulfilledRetentionDays > 0 || l.Cfg.FailedRetentionDays > 0 {
+		l.wg.Add(1)
+		go l.loopPrune(l.ctx)
+	}
+
 	l.Log.Info("Proposer started")
 	return nil
 }
@@ -281,6 +295,12 @@ func (l *L2OutputSubmitter) GetProposerMetrics(ctx context.Context) (opsuccinctm
 		return opsuccinctmetrics.ProposerMetrics{}, fmt.Errorf("failed to get number of unrequested proofs: %w", err)
 	}
 
+	now := time.Now()
+	todayUsage, err := l.db.GetCycleUsage(now, now)
+	if err != nil {
+		return opsuccinctmetrics.ProposerMetrics{}, fmt.Errorf("failed to get cycle usage: %w", err)
+	}
+
 	metrics := opsuccinctmetrics.ProposerMetrics{
 		L2UnsafeHeadBlock:              l2UnsafeHeadBlock,
 		L2FinalizedBlock:               l2FinalizedBlock,
@@ -296,39 +316,85 @@ func (l *L2OutputSubmitter) GetProposerMetrics(ctx context.Context) (opsuccinctm
 	if m, ok := l.Metr.(*opsuccinctmetrics.OPSuccinctMetrics); ok {
 		m.RecordProposerStatus(metrics)
 	}
+	// Each proof type reports zero cycles until one of its proofs is fulfilled today.
+	for _, proofType := range []proofrequest.Type{proofrequest.TypeSPAN, proofrequest.TypeAGG} {
+		var proofs, cycles uint64
+		for _, usage := range todayUsage {
+			if usage.ChainID == l.Cfg.L2ChainID && usage.Type.String() == proofType.String() {
+				proofs, cycles = usage.Proofs, usage.Cycles
+			}
+		}
+		l.Metr.RecordCycleUsage(l.Cfg.L2ChainID, proofType.String(), proofs, cycles)
+	}
 
 	return metrics, nil
 }
 
 func (l *L2OutputSubmitter) SubmitAggProofs(ctx context.Context) error {
-	// Get the latest output index from the L2OutputOracle contract
-	latestBlockNumber, err := l.l2ooContract.LatestBlockNumber(&bind.CallOpts{Context: ctx})
+	state, err := l.FetchL2OOState(ctx)
 	if err != nil {
-		return fmt.Errorf("failed to get latest output index: %w", err)
+		return err
 	}
 
-	// Check for a completed AGG proof starting at the next index
-	completedAggProofs, err := l.db.GetAllCompletedAggProofs(latestBlockNumber.Uint64())
+	completedAggProofs, err := l.db.GetCompletedAggProofsFrom(state.LatestBlockNumber)
 	if err != nil {
-		return fmt.Errorf("failed to query for completed AGG proof: %w", err)
+		return err
 	}
 
-	if len(completedAggProofs) == 0 {
+	// Propose the completed AGG proofs that follow each other from the latest output, up to the max per transaction.
+	// The contract rejects proposals before its next block number, so proofs that don't reach it are skipped.
+	chain := proposalChain(state, completedAggProofs, l.Cfg.MaxProposalsPerTx)
+	if len(chain) == 0 {
+		if len(completedAggProofs) > 0 {
+			l.Log.Debug("No completed AGG proof continues from the latest output to the contract's next block number",
+				"latest", state.LatestBlockNumber, "nextBlockNumber", state.NextBlockNumber)
+		}
 		return nil
 	}
 
-	// Select the agg proof with the highest L2 block number.
-	sort.Slice(completedAggProofs, func(i, j int) bool {
-		return completedAggProofs[i].EndBlock > completedAggProofs[j].EndBlock
-	})
+	proposals := make([]outputProposal, 0, len(chain))
+proposals:
+	for _, aggProof := range chain {
+		output, err := l.FetchOutput(ctx, aggProof.EndBlock)
+		if err != nil {
+			return fmt.Errorf("failed to fetch output at block %d: %w", aggProof.EndBlock, err)
+		}
+		overrides, err := parseCheckpointOverrides(aggProof.CheckpointOverrides)
+		if err != nil {
+			return fmt.Errorf("invalid checkpoint overrides of AGG proof %d: %w", aggProof.ID, err)
+		}
+		var overridesHash common.Hash
+		if overrides != nil {
+			if overridesHash, err = overrides.Hash(); err != nil {
+				return err
+			}
+			approved, err := l.l2ooContract.ApprovedCheckpointOverrides(&bind.CallOpts{Context: ctx}, overridesHash)
+			if err != nil {
+				return fmt.Errorf("failed to check the approval of checkpoint overrides %s: %w", overridesHash, err)
+			}
+			// The proposal would revert until the L2OO's owner approves the overrides, and so would the ones after it.
+			if !approved {
+				l.Log.Warn("Checkpoint overrides not approved on the L2OO yet, waiting to propose", "end", aggProof.EndBlock, "hash", overridesHash, "justification", overrides.Justification)
+				break proposals
+			}
+			l.Log.Warn("Proposing AGG proof over checkpoint overrides", "end", aggProof.EndBlock, "hash", overridesHash, "justification", overrides.Justification)
+		}
+		proposals = append(proposals, outputProposal{
+			output:                  output,
+			proof:                   aggProof.Proof,
+			l1BlockNum:              aggProof.L1BlockNumber,
+			checkpointOverridesHash: overridesHash,
+		})
+	}
 
-	// Submit the agg proof with the highest L2 block number.
-	aggProof := completedAggProofs[0]
-	output, err := l.FetchOutput(ctx, aggProof.EndBlock)
-	if err != nil {
-		return fmt.Errorf("failed to fetch output at block %d: %w", aggProof.EndBlock, err)
+	if len(proposals) == 0 {
+		return nil
+	}
+	if len(proposals) == 1 {
+		err = l.proposeOutput(ctx, proposals[0])
+	} else {
+		err = l.proposeOutputs(ctx, proposals)
 	}
-	err = l.proposeOutput(ctx, output, aggProof.Proof, aggProof.L1BlockNumber)
 	if err != nil {
 		return fmt.Errorf("failed to propose output: %w", err)
 	}
@@ -430,10 +496,29 @@ func (l *L2OutputSubmitter) ProposeL2OutputTxData(output *eth.OutputResponse, pr
 	return proposeL2OutputTxData(l.l2ooABI, output, proof, l1BlockNum)
 }
 
+// proposeL2OutputSig is the signature of the L2OO's `proposeL2Output` verifying an AGG proof. The optimistic mode's
+// `proposeL2Output` overloads it, and the ABI parser names overloads in the order of their selectors, so the method is
+// looked up by its signature.
+const proposeL2OutputSig = "proposeL2Output(bytes32,uint256,uint256,bytes)"
+
+// methodBySig returns the method of a contract ABI with the given signature.
+func methodBySig(contractAbi *abi.ABI, sig string) (abi.Method, error) {
+	for _, method := range contractAbi.Methods {
+		if method.Sig == sig {
+			return method, nil
+		}
+	}
+	return abi.Method{}, fmt.Errorf("method %s not found in the ABI", sig)
+}
+
 // proposeL2OutputTxData creates the transaction data for the ProposeL2Output function
 func proposeL2OutputTxData(abi *abi.ABI, output *eth.OutputResponse, proof []byte, l1BlockNum uint64) ([]byte, error) {
+	method, err := methodBySig(abi, proposeL2OutputSig)
+	if err != nil {
+		return nil, err
+	}
 	return abi.Pack(
-		"proposeL2Output",
+		method.Name,
 		output.OutputRoot,
 		new(big.Int).SetUint64(output.BlockRef.Number),
 		new(big.Int).SetUint64(l1BlockNum),
@@ -514,7 +599,8 @@ func (l *L2OutputSubmitter) waitForL1Head(ctx context.Context, blockNum uint64)
 }
 
 // sendTransaction creates & sends transactions through the underlying transaction manager.
-func (l *L2OutputSubmitter) sendTransaction(ctx context.Context, output *eth.OutputResponse, proof []byte, l1BlockNum uint64) error {
+func (l *L2OutputSubmitter) sendTransaction(ctx context.Context, p outputProposal) error {
+	output, proof, l1BlockNum := p.output, p.proof, p.l1BlockNum
 	err := l.waitForL1Head(ctx, output.Status.HeadL1.Number+1)
 	if err != nil {
 		return err
@@ -542,7 +628,7 @@ func (l *L2OutputSubmitter) sendTransaction(ctx context.Context, output *eth.Out
 			return err
 		}
 	} else {
-		data, err := l.ProposeL2OutputTxData(output, proof, l1BlockNum)
+		data, err := l.l2ooProposalTxData(ctx, p)
 		if err != nil {
 			return err
 		}
@@ -565,8 +651,7 @@ func (l *L2OutputSubmitter) sendTransaction(ctx context.Context, output *eth.Out
 	return nil
 }
 
-// loop is responsible for creating & submitting the next outputs
-// TODO: Look into adding a transaction cache so the loop isn't waiting for the transaction to confirm. This sometimes takes up to 30s.
+// loop is responsible for creating & submitting the next outputs, with the stages of the proving pipeline.
 func (l *L2OutputSubmitter) loop() {
 	defer l.wg.Done()
 	ctx := l.ctx
@@ -579,7 +664,7 @@ func (l *L2OutputSubmitter) loop() {
 		}
 	}
 
-	l.loopL2OO(ctx)
+	l.runPipeline(ctx)
 }
 
 func (l *L2OutputSubmitter) waitNodeSync() error {
@@ -599,85 +684,8 @@ func (l *L2OutputSubmitter) waitNodeSync() error {
 	return dial.WaitRollupSync(l.ctx, l.Log, rollupClient, l1head, time.Second*12)
 }
 
-// The loopL2OO regularly polls the L2OO for the next block to propose,
-// and if the current finalized (or safe) block is past that next block, it
-// proposes it.
-func (l *L2OutputSubmitter) loopL2OO(ctx context.Context) {
-	ticker := time.NewTicker(l.Cfg.PollInterval)
-	defer ticker.Stop()
-	for {
-		select {
-		case <-ticker.C:
-			// Get the current metrics for the proposer.
-			metrics, err := l.GetProposerMetrics(ctx)
-			if err != nil {
-				l.Log.Error("failed to get metrics", "err", err)
-				continue
-			}
-			l.Log.Info("Proposer status", "metrics", metrics)
-
-			// 1) Queue up the range proofs that are ready to prove. Determine these range proofs based on the latest L2 finalized block,
-			// and the current L2 unsafe head.
-			l.Log.Info("Stage 1: Getting Range Proof Boundaries...")
-			err = l.GetRangeProofBoundaries(ctx)
-			if err != nil {
-				l.Log.Error("failed to get range proof boundaries", "err", err)
-				continue
-			}
-
-			// 2) Check the statuses of PROVING requests.
-			// If it's successfully returned, we validate that we have it on disk and set status = "COMPLETE".
-			// If it fails or times out, we set status = "FAILED" (and, if it's a span proof, split the request in half to try again).
-			l.Log.Info("Stage 2: Processing PROVING requests...")
-			err = l.ProcessProvingRequests()
-			if err != nil {
-				l.Log.Error("failed to update PROVING requests", "err", err)
-				continue
-			}
-
-			// 3) Check the statuses of WITNESSGEN requests.
-			// If the witness generation request has been in the WITNESSGEN state for longer than the timeout, set status to FAILED and retry.
-			l.Log.Info("Stage 3: Processing WITNESSGEN requests...")
-			err = l.ProcessWitnessgenRequests()
-			if err != nil {
-				l.Log.Error("failed to update WITNESSGEN requests", "err", err)
-				continue
-			}
-
-			// 4) Determine if there is a continguous chain of span proofs starting from the latest block on the L2OO contract.
-			// If there is, queue an aggregate proof for all of the span proofs.
-			l.Log.Info("Stage 4: Deriving Agg Proofs...")
-			err = l.DeriveAggProofs(ctx)
-			if err != nil {
-				l.Log.Error("failed to generate pending agg proofs", "err", err)
-				continue
-			}
-
-			// 5) Request all unrequested proofs from the prover network.
-			// Any DB entry with status = "UNREQ" means it's queued up and ready.
-			// We request all of these (both span and agg) from the prover network.
-			// For agg proofs, we also checkpoint the blockhash in advance.
-			l.Log.Info("Stage 5: Requesting Queued Proofs...")
-			err = l.RequestQueuedProofs(ctx)
-			if err != nil {
-				l.Log.Error("failed to request unrequested proofs", "err", err)
-				continue
-			}
-
-			// 6) Submit agg proofs on chain.
-			// If we have a completed agg proof waiting in the DB, we submit them on chain.
-			l.Log.Info("Stage 6: Submitting Agg Proofs...")
-			err = l.SubmitAggProofs(ctx)
-			if err != nil {
-				l.Log.Error("failed to submit agg proofs", "err", err)
-			}
-		case <-l.done:
-			return
-		}
-	}
-}
-
-func (l *L2OutputSubmitter) proposeOutput(ctx context.Context, output *eth.OutputResponse, proof []byte, l1BlockNum uint64) error {
+func (l *L2OutputSubmitter) proposeOutput(ctx context.Context, p outputProposal) error {
+	output, proof, l1BlockNum := p.output, p.proof, p.l1BlockNum
 	cCtx, cancel := context.WithTimeout(ctx, 10*time.Minute)
 	defer cancel()
 
@@ -688,7 +696,7 @@ func (l *L2OutputSubmitter) proposeOutput(ctx context.Context, output *eth.Outpu
 		return err
 	}
 
-	if err := l.sendTransaction(cCtx, output, proof, l1BlockNum); err != nil {
+	if err := l.sendTransaction(cCtx, p); err != nil {
 		l.Log.Error("Failed to send proposal transaction",
 			"err", err,
 			"expected_next_blocknum", nextBlockNumber.Uint64(),
@@ -703,44 +711,40 @@ func (l *L2OutputSubmitter) proposeOutput(ctx context.Context, output *eth.Outpu
 	return nil
 }
 
-// checkpointBlockHash gets the current L1 head, and then sends a transaction to checkpoint the blockhash on
-// the L2OO contract for the aggregation proof.
-func (l *L2OutputSubmitter) checkpointBlockHash(ctx context.Context) (uint64, common.Hash, error) {
+// proposeOutputs proposes sequential outputs to the L2OO in one transaction.
+func (l *L2OutputSubmitter) proposeOutputs(ctx context.Context, proposals []outputProposal) error {
 	cCtx, cancel := context.WithTimeout(ctx, 10*time.Minute)
 	defer cancel()
 
-	currBlockNum, err := l.L1Client.BlockNumber(cCtx)
-	if err != nil {
-		return 0, common.Hash{}, err
-	}
-	header, err := l.L1Client.HeaderByNumber(cCtx, new(big.Int).SetUint64(currBlockNum-1))
-	if err != nil {
-		return 0, common.Hash{}, err
+	first, last := proposals[0].output, proposals[len(proposals)-1].output
+	for _, p := range proposals {
+		if err := l.waitForL1Head(cCtx, p.output.Status.HeadL1.Number+1); err != nil {
+			return err
+		}
 	}
-	blockHash := header.Hash()
-	blockNumber := header.Number
 
-	var receipt *types.Receipt
-	data, err := l.CheckpointBlockHashTxData(blockNumber)
+	data, err := l.l2ooBatchProposalTxData(cCtx, proposals)
 	if err != nil {
-		return 0, common.Hash{}, err
+		return err
 	}
-
+	l.Log.Info("Proposing output roots in one transaction", "count", len(proposals), "first", first.BlockRef, "last", last.BlockRef)
 	// TODO: This currently blocks the loop while it waits for the transaction to be confirmed. Up to 3 minutes.
-	receipt, err = l.Txmgr.Send(ctx, txmgr.TxCandidate{
+	receipt, err := l.Txmgr.Send(cCtx, txmgr.TxCandidate{
 		TxData:   data,
 		To:       l.Cfg.L2OutputOracleAddr,
 		GasLimit: 0,
 	})
 	if err != nil {
-		return 0, common.Hash{}, err
+		l.Log.Error("Failed to send batch proposal transaction", "err", err, "count", len(proposals),
+			"first", first.BlockRef.Number, "last", last.BlockRef.Number)
+		return err
 	}
-
 	if receipt.Status == types.ReceiptStatusFailed {
-		l.Log.Error("checkpoint blockhash tx successfully published but reverted", "tx_hash", receipt.TxHash)
-	} else {
-		l.Log.Info("checkpoint blockhash tx successfully published",
-			"tx_hash", receipt.TxHash)
+		l.Log.Error("Proposer tx successfully published but reverted", "tx_hash", receipt.TxHash)
+		return nil
 	}
-	return blockNumber.Uint64(), blockHash, nil
+
+	l.Log.Info("AGG proofs submitted on-chain", "tx_hash", receipt.TxHash, "count", len(proposals), "end", last.BlockRef.Number)
+	l.Metr.RecordL2BlocksProposed(last.BlockRef)
+	return nil
 }
@@ -4,6 +4,7 @@ import (
 	"testing"
 
 	"github.com/ethereum-optimism/optimism/op-service/eth"
+	"github.com/ethereum/go-ethereum/common"
 	"github.com/stretchr/testify/require"
 	opsuccinctbindings "github.com/succinctlabs/op-succinct-go/bindings"
 )
@@ -23,7 +24,19 @@ func TestProposeL2OutputTxData(t *testing.T) {
 		t.Fatalf("failed to get abi: %v", err)
 	}
 
-	_, err = proposeL2OutputTxData(l2ooAbiParsed, output, proof, l1BlockNum)
+	data, err := proposeL2OutputTxData(l2ooAbiParsed, output, proof, l1BlockNum)
 	require.NoError(t, err)
+	// The selector of proposeL2Output(bytes32,uint256,uint256,bytes), not of the optimistic overload.
+	require.Equal(t, common.FromHex("0x9ad84880"), data[:4])
+}
+
+func TestNextRangeStart(t *testing.T) {
+	state := L2OOState{LatestBlockNumber: 100, NextBlockNumber: 150, SubmissionInterval: 50}
 
+	// Without span proofs, proving starts from the contract's latest block number.
+	require.Equal(t, uint64(100), NextRangeStart(state, 0, false))
+	// Span proofs continue from the latest requested span.
+	require.Equal(t, uint64(120), NextRangeStart(state, 120, true))
+	// Span proofs behind the contract can never be proposed, so proving restarts from the contract.
+	require.Equal(t, uint64(100), NextRangeStart(state, 80, true))
 }
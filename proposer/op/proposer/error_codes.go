@@ -0,0 +1,93 @@
+package proposer
+
+import (
+	"errors"
+	"fmt"
+	"net"
+)
+
+// Error codes of why a proof request failed, shared with the op-succinct-server, which uses them in its error responses,
+// logs and metrics. They are recorded with FAILED proof requests and label the proposer's failure metrics, so failures
+// can be broken down by cause.
+const (
+	ErrorCodeRpcUnavailable      = "E_RPC_UNAVAILABLE"
+	ErrorCodeBlobPruned          = "E_BLOB_PRUNED"
+	ErrorCodeWitnessTooLarge     = "E_WITNESS_TOO_LARGE"
+	ErrorCodeWitnessGenStalled   = "E_WITNESS_GEN_STALLED"
+	ErrorCodeWitnessGenTimeout   = "E_WITNESS_GEN_TIMEOUT"
+	ErrorCodeWitnessHashMismatch = "E_WITNESS_HASH_MISMATCH"
+	ErrorCodeUnexecutableSpan    = "E_UNEXECUTABLE_SPAN"
+	ErrorCodeInvalidAggInput     = "E_INVALID_AGG_INPUT"
+	ErrorCodeQuorumMismatch      = "E_QUORUM_MISMATCH"
+	ErrorCodeProverUnavailable   = "E_PROVER_UNAVAILABLE"
+	ErrorCodeProverTimeout       = "E_PROVER_TIMEOUT"
+	ErrorCodeProverUnfulfillable = "E_PROVER_UNFULFILLABLE"
+	ErrorCodeVkeyMismatch        = "E_VKEY_MISMATCH"
+	ErrorCodeStaleConfig         = "E_STALE_CONFIG"
+	ErrorCodeServerUnavailable   = "E_SERVER_UNAVAILABLE"
+	ErrorCodePaused              = "E_PAUSED"
+	ErrorCodeInternal            = "E_INTERNAL"
+)
+
+// ErrorCodeHeader carries the error code of a failed request to the op-succinct-server.
+const ErrorCodeHeader = "X-Error-Code"
+
+// ServerError is a non-200 response of the op-succinct-server, with the error code of the failure if the server sent
+// one.
+type ServerError struct {
+	StatusCode int
+	Code       string
+	Body       string
+}
+
+func (e *ServerError) Error() string {
+	msg := fmt.Sprintf("received non-200 status code: %d", e.StatusCode)
+	if e.Code != "" {
+		msg += fmt.Sprintf(" (%s)", e.Code)
+	}
+	if e.Body != "" {
+		msg += ": " + e.Body
+	}
+	return msg
+}
+
+// requestErrorCode returns the error code of why requesting a proof from the op-succinct-server failed.
+func requestErrorCode(err error) string {
+	var splitErr *SplitSpanError
+	var serverErr *ServerError
+	var netErr net.Error
+	switch {
+	case err == nil:
+		return ""
+	case errors.As(err, &splitErr) && splitErr.Code != "":
+		return splitErr.Code
+	case errors.As(err, &serverErr) && serverErr.Code != "":
+		return serverErr.Code
+	case errors.Is(err, ErrUnexecutableSpan):
+		return ErrorCodeUnexecutableSpan
+	case errors.As(err, &netErr) && netErr.Timeout():
+		return ErrorCodeWitnessGenTimeout
+	case errors.As(err, &netErr):
+		return ErrorCodeServerUnavailable
+	default:
+		return ErrorCodeInternal
+	}
+}
+
+// isServerPaused reports whether the op-succinct-server turned a request away because it is paused. The request wasn't
+// attempted, so it is queued again without being recorded as failed.
+func isServerPaused(err error) bool {
+	var serverErr *ServerError
+	return errors.As(err, &serverErr) && serverErr.Code == ErrorCodePaused
+}
+
+// statusErrorCode returns the error code of why a proof is unfulfillable, for servers that don't send one.
+func statusErrorCode(status ProofStatusResponse) string {
+	if status.ErrorCode != "" {
+		return status.ErrorCode
+	}
+	if status.FulfillmentStatus == SP1FulfillmentStatusUnfulfillable {
+		return ErrorCodeProverUnfulfillable
+	}
+	return ""
+}
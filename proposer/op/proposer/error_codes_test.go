@@ -0,0 +1,40 @@
+package proposer
+
+import (
+	"errors"
+	"fmt"
+	"net"
+	"testing"
+
+	"github.com/stretchr/testify/require"
+)
+
+func TestRequestErrorCode(t *testing.T) {
+	require.Equal(t, "", requestErrorCode(nil))
+
+	serverErr := &ServerError{StatusCode: 500, Code: ErrorCodeProverUnavailable, Body: "Failed to request proof"}
+	require.Equal(t, ErrorCodeProverUnavailable, requestErrorCode(fmt.Errorf("real proof request failed: %w", serverErr)))
+	require.Equal(t, ErrorCodeBlobPruned, requestErrorCode(&SplitSpanError{SplitAt: []uint64{104}, Code: ErrorCodeBlobPruned}))
+
+	// Servers that don't send error codes.
+	unexecutable := fmt.Errorf("%w: %w", ErrUnexecutableSpan, &ServerError{StatusCode: 422})
+	require.Equal(t, ErrorCodeUnexecutableSpan, requestErrorCode(unexecutable))
+	require.Equal(t, ErrorCodeInternal, requestErrorCode(&ServerError{StatusCode: 500}))
+
+	timeout := &net.DNSError{IsTimeout: true}
+	require.Equal(t, ErrorCodeWitnessGenTimeout, requestErrorCode(fmt.Errorf("request timed out: %w", timeout)))
+	require.Equal(t, ErrorCodeServerUnavailable, requestErrorCode(&net.OpError{Op: "dial", Err: errors.New("connection refused")}))
+}
+
+func TestIsServerPaused(t *testing.T) {
+	paused := &ServerError{StatusCode: 503, Code: ErrorCodePaused, Body: "Server is paused: upgrading"}
+	require.True(t, isServerPaused(fmt.Errorf("real proof request failed: %w", paused)))
+	require.False(t, isServerPaused(&ServerError{StatusCode: 500, Code: ErrorCodeInternal}))
+	require.False(t, isServerPaused(errors.New("connection refused")))
+}
+
+func TestStatusErrorCode(t *testing.T) {
+	require.Equal(t, ErrorCodeProverTimeout, statusErrorCode(ProofStatusResponse{FulfillmentStatus: SP1FulfillmentStatusUnfulfillable, ErrorCode: ErrorCodeProverTimeout}))
+	require.Equal(t, ErrorCodeProverUnfulfillable, statusErrorCode(ProofStatusResponse{FulfillmentStatus: SP1FulfillmentStatusUnfulfillable}))
+	require.Equal(t, "", statusErrorCode(ProofStatusResponse{}))
+}
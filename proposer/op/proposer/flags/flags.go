@@ -130,6 +130,56 @@ var (
 		Value:   false,
 		EnvVars: prefixEnvVars("OP_SUCCINCT_MOCK"),
 	}
+	FulfilledRetentionDaysFlag = &cli.Uint64Flag{
+		Name:    "fulfilled-retention-days",
+		Usage:   "Days to keep completed proof requests in the DB after the L2OO is past them. 0 keeps them forever",
+		Value:   0,
+		EnvVars: prefixEnvVars("FULFILLED_RETENTION_DAYS"),
+	}
+	FailedRetentionDaysFlag = &cli.Uint64Flag{
+		Name:    "failed-retention-days",
+		Usage:   "Days to keep failed proof requests in the DB. 0 keeps them forever",
+		Value:   0,
+		EnvVars: prefixEnvVars("FAILED_RETENTION_DAYS"),
+	}
+	PruneArchiveDirFlag = &cli.StringFlag{
+		Name:    "prune-archive-dir",
+		Usage:   "Directory to archive pruned proof requests to as JSONL. Defaults to an archive directory next to the DB",
+		EnvVars: prefixEnvVars("PRUNE_ARCHIVE_DIR"),
+	}
+	SafeHeadPollIntervalFlag = &cli.DurationFlag{
+		Name:    "safe-head-poll-interval",
+		Usage:   "How frequently to poll the rollup node's sync status, planning spans as soon as its finalized head advances. 0 disables it",
+		Value:   2 * time.Second,
+		EnvVars: prefixEnvVars("SAFE_HEAD_POLL_INTERVAL"),
+	}
+	SubmissionWindowsFlag = &cli.StringFlag{
+		Name:    "submission-windows",
+		Usage:   "Semicolon-separated windows during which no outputs are proposed, each a cron expression in UTC and a duration, e.g. \"0 14 * * 2 2h\"",
+		EnvVars: prefixEnvVars("SUBMISSION_WINDOWS"),
+	}
+	QueryApiAddrFlag = &cli.StringFlag{
+		Name:    "query-api-addr",
+		Usage:   "Address to serve the read-only query API on. Defaults to the RPC server's listen address",
+		EnvVars: prefixEnvVars("QUERY_API_ADDR"),
+	}
+	QueryApiPortFlag = &cli.IntFlag{
+		Name:    "query-api-port",
+		Usage:   "Port to serve the read-only query API over the proposer's DB on. 0 disables it",
+		Value:   0,
+		EnvVars: prefixEnvVars("QUERY_API_PORT"),
+	}
+	MaxProposalsPerTxFlag = &cli.Uint64Flag{
+		Name:    "max-proposals-per-tx",
+		Usage:   "Maximum number of sequential outputs to propose to the L2OutputOracle in one transaction, e.g. for backfills",
+		Value:   1,
+		EnvVars: prefixEnvVars("MAX_PROPOSALS_PER_TX"),
+	}
+	CheckpointOverridesFileFlag = &cli.StringFlag{
+		Name:    "checkpoint-overrides-file",
+		Usage:   "Path to the checkpoint overrides to aggregate over for emergency catch-up, as JSON with the gaps force-resolved by governance and the justification it signed. Used while the L2OutputOracle's latest output is the start of the first gap",
+		EnvVars: prefixEnvVars("CHECKPOINT_OVERRIDES_FILE"),
+	}
 
 	// Legacy Flags
 	L2OutputHDPathFlag = txmgr.L2OutputHDPathFlag
@@ -158,6 +208,15 @@ var optionalFlags = []cli.Flag{
 	MaxConcurrentProofRequestsFlag,
 	MockFlag,
 	WitnessGenTimeoutFlag,
+	FulfilledRetentionDaysFlag,
+	FailedRetentionDaysFlag,
+	PruneArchiveDirFlag,
+	SafeHeadPollIntervalFlag,
+	SubmissionWindowsFlag,
+	QueryApiAddrFlag,
+	QueryApiPortFlag,
+	MaxProposalsPerTxFlag,
+	CheckpointOverridesFileFlag,
 }
 
 func init() {
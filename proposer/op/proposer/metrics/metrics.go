@@ -2,6 +2,8 @@ package metrics
 
 import (
 	"io"
+	"strconv"
+	"time"
 
 	"github.com/ethereum/go-ethereum/log"
 
@@ -26,6 +28,9 @@ type OPSuccinctMetricer interface {
 	RecordError(label string, num uint64)
 	RecordProveFailure(reason string)
 	RecordWitnessGenFailure(reason string)
+	RecordProofRequestFailure(proofType string, errorCode string)
+	RecordStageRun(stage string, duration time.Duration, items int)
+	RecordCycleUsage(chainID uint64, proofType string, proofs, cycles uint64)
 }
 
 type OPSuccinctMetrics struct {
@@ -49,9 +54,16 @@ type OPSuccinctMetrics struct {
 	HighestProvenContiguousL2Block prometheus.Gauge
 	MinBlockToProveToAgg           prometheus.Gauge
 
-	ErrorCount         *prometheus.CounterVec
-	ProveFailures      *prometheus.CounterVec
-	WitnessGenFailures *prometheus.CounterVec
+	ErrorCount           *prometheus.CounterVec
+	ProveFailures        *prometheus.CounterVec
+	WitnessGenFailures   *prometheus.CounterVec
+	ProofRequestFailures *prometheus.CounterVec
+
+	StageDuration *prometheus.HistogramVec
+	StageItems    *prometheus.CounterVec
+
+	CyclesToday *prometheus.GaugeVec
+	ProofsToday *prometheus.GaugeVec
 }
 
 var _ OPSuccinctMetricer = (*OPSuccinctMetrics)(nil)
@@ -136,6 +148,32 @@ func NewMetrics(procName string) *OPSuccinctMetrics {
 			Name:      "witness_gen_failures",
 			Help:      "Number of witness generation failures by type",
 		}, []string{"reason"}),
+		ProofRequestFailures: factory.NewCounterVec(prometheus.CounterOpts{
+			Namespace: ns,
+			Name:      "proof_request_failures",
+			Help:      "Number of failed proof requests by proof type and error code",
+		}, []string{"type", "code"}),
+		StageDuration: factory.NewHistogramVec(prometheus.HistogramOpts{
+			Namespace: ns,
+			Name:      "stage_duration_seconds",
+			Help:      "Duration of each run of a proving pipeline stage",
+			Buckets:   []float64{0.1, 0.5, 1, 5, 15, 60, 300, 900, 1800},
+		}, []string{"stage"}),
+		StageItems: factory.NewCounterVec(prometheus.CounterOpts{
+			Namespace: ns,
+			Name:      "stage_items",
+			Help:      "Number of proof requests processed by each proving pipeline stage",
+		}, []string{"stage"}),
+		CyclesToday: factory.NewGaugeVec(prometheus.GaugeOpts{
+			Namespace: ns,
+			Name:      "cycles_today",
+			Help:      "zkVM cycles of the proofs fulfilled in the current UTC day by chain and proof type",
+		}, []string{"chain_id", "type"}),
+		ProofsToday: factory.NewGaugeVec(prometheus.GaugeOpts{
+			Namespace: ns,
+			Name:      "proofs_today",
+			Help:      "Number of proofs fulfilled in the current UTC day by chain and proof type",
+		}, []string{"chain_id", "type"}),
 	}
 }
 
@@ -187,6 +225,24 @@ func (m *OPSuccinctMetrics) RecordWitnessGenFailure(reason string) {
 	m.WitnessGenFailures.WithLabelValues(reason).Inc()
 }
 
+// RecordProofRequestFailure records a failed proof request by the error code of its failure
+func (m *OPSuccinctMetrics) RecordProofRequestFailure(proofType string, errorCode string) {
+	m.ProofRequestFailures.WithLabelValues(proofType, errorCode).Inc()
+}
+
+// RecordStageRun records a run of a proving pipeline stage, and the number of proof requests it processed.
+func (m *OPSuccinctMetrics) RecordStageRun(stage string, duration time.Duration, items int) {
+	m.StageDuration.WithLabelValues(stage).Observe(duration.Seconds())
+	m.StageItems.WithLabelValues(stage).Add(float64(items))
+}
+
+// RecordCycleUsage sets the cycles and number of the proofs of a type fulfilled on a chain in the current UTC day
+func (m *OPSuccinctMetrics) RecordCycleUsage(chainID uint64, proofType string, proofs, cycles uint64) {
+	chain := strconv.FormatUint(chainID, 10)
+	m.CyclesToday.WithLabelValues(chain, proofType).Set(float64(cycles))
+	m.ProofsToday.WithLabelValues(chain, proofType).Set(float64(proofs))
+}
+
 // RecordProposerStatus sets the proposer Prometheus metrics to the given values.
 func (m *OPSuccinctMetrics) RecordProposerStatus(metrics ProposerMetrics) {
 	m.NumProving.Set(float64(metrics.NumProving))
@@ -2,6 +2,7 @@ package metrics
 
 import (
 	"io"
+	"time"
 
 	"github.com/ethereum/go-ethereum/common"
 	"github.com/ethereum/go-ethereum/ethclient"
@@ -25,6 +26,12 @@ func (*noopMetrics) RecordError(label string, num uint64)         {}
 func (*noopMetrics) RecordProveFailure(reason string)             {}
 func (*noopMetrics) RecordWitnessGenFailure(reason string)        {}
 
+func (*noopMetrics) RecordProofRequestFailure(proofType string, errorCode string) {}
+
+func (*noopMetrics) RecordStageRun(stage string, duration time.Duration, items int) {}
+
+func (*noopMetrics) RecordCycleUsage(chainID uint64, proofType string, proofs, cycles uint64) {}
+
 func (*noopMetrics) RecordInfo(version string) {}
 func (*noopMetrics) RecordUp()                 {}
 
@@ -0,0 +1,279 @@
+package proposer
+
+import (
+	"context"
+	"time"
+
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
+)
+
+// The stages of the proving pipeline. Each stage runs in its own goroutines, so witness generation of a span overlaps
+// with proving of the spans before it and with aggregation and submission of earlier ranges, instead of every stage
+// waiting on the others once per poll interval.
+//
+//	plan ──spans──▶ witnessgen ──▶ proving ──span proved──▶ aggregate ──AGG proved──▶ submit
+//
+// The DB remains the source of truth: a request moves between stages by changing status, and the channels only carry
+// the claimed span requests and wake-ups, so a restarted proposer resumes from the DB.
+const (
+	// stagePlan splits the finalized L2 blocks into span requests, and claims the unrequested ones for witness
+	// generation as the concurrency limits allow.
+	stagePlan = "plan"
+	// stageWitnessgen requests span proofs from the server, which generates their witnesses. It runs
+	// `MaxConcurrentWitnessGen` workers.
+	stageWitnessgen = "witnessgen"
+	// stageProving polls the server for the proofs being proven.
+	stageProving = "proving"
+	// stageAggregate queues an AGG proof once the span proofs from the L2OO's latest block reach its next block, and
+	// requests it. It runs whenever a span proof completes, so aggregation starts as soon as its dependencies are
+	// proven.
+	stageAggregate = "aggregate"
+	// stageSubmit proposes completed AGG proofs on-chain. It runs whenever an AGG proof completes, and defers the
+	// proposal during submission windows.
+	stageSubmit = "submit"
+	// stageConfig watches the L2OO's verification keys and rollup config hash, and invalidates the proofs a change
+	// makes stale.
+	stageConfig = "config"
+)
+
+// pipeline connects the stages of the proposer.
+type pipeline struct {
+	// spans carries the span requests claimed by the plan stage to the witnessgen workers.
+	spans chan *ent.ProofRequest
+	// wakePlan wakes the plan stage when the rollup node's finalized head advances.
+	wakePlan chan struct{}
+	// wakeProving, wakeAggregate and wakeSubmit wake a stage before its next poll when the stage before it made
+	// progress. Sends never block, and wake-ups sent while the stage is busy coalesce into one.
+	wakeProving   chan struct{}
+	wakeAggregate chan struct{}
+	wakeSubmit    chan struct{}
+}
+
+func newPipeline(witnessgenWorkers int) *pipeline {
+	return &pipeline{
+		spans:         make(chan *ent.ProofRequest, witnessgenWorkers),
+		wakePlan:      make(chan struct{}, 1),
+		wakeProving:   make(chan struct{}, 1),
+		wakeAggregate: make(chan struct{}, 1),
+		wakeSubmit:    make(chan struct{}, 1),
+	}
+}
+
+// wake wakes the stage waiting on `ch`, unless it already has a pending wake-up.
+func wake(ch chan<- struct{}) {
+	select {
+	case ch <- struct{}{}:
+	default:
+	}
+}
+
+// runPipeline starts the stages of the proposer and returns once they have all stopped.
+func (l *L2OutputSubmitter) runPipeline(ctx context.Context) {
+	workers := max(int(l.Cfg.MaxConcurrentWitnessGen), 1)
+	p := newPipeline(workers)
+
+	if err := l.ResumeAggSessions(); err != nil {
+		l.Log.Error("failed to resume AGG proof requests", "err", err)
+	}
+
+	// The safe head and proving config watchers run alongside the stages, and stop with them.
+	stages := []func(context.Context, *pipeline){
+		l.planStage, l.provingStage, l.aggregateStage, l.submitStage, l.watchSafeHead, l.watchProvingConfig,
+	}
+	done := make(chan struct{})
+	for _, stage := range stages {
+		go func(stage func(context.Context, *pipeline)) {
+			stage(ctx, p)
+			done <- struct{}{}
+		}(stage)
+	}
+	for i := 0; i < workers; i++ {
+		go func() {
+			l.witnessgenWorker(p)
+			done <- struct{}{}
+		}()
+	}
+
+	// The plan stage closes the span channel when it stops, which stops the workers once they drain it.
+	for i := 0; i < len(stages)+workers; i++ {
+		<-done
+	}
+}
+
+// poll runs `step` every poll interval and whenever `wakeup` fires, until the proposer stops. Each run is recorded in
+// the stage's metrics.
+func (l *L2OutputSubmitter) poll(stage string, wakeup <-chan struct{}, step func() (int, error)) {
+	ticker := time.NewTicker(l.Cfg.PollInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ticker.C:
+		case <-wakeup:
+		case <-l.done:
+			return
+		}
+		start := time.Now()
+		items, err := step()
+		l.Metr.RecordStageRun(stage, time.Since(start), items)
+		if err != nil {
+			l.Log.Error("pipeline stage failed", "stage", stage, "err", err)
+			l.Metr.RecordError(stage, 1)
+		}
+	}
+}
+
+// planStage queues span requests for the newly finalized L2 blocks, retries requests stuck in witness generation,
+// and hands the span requests the concurrency limits allow to the witnessgen workers.
+func (l *L2OutputSubmitter) planStage(ctx context.Context, p *pipeline) {
+	defer close(p.spans)
+	l.poll(stagePlan, p.wakePlan, func() (int, error) {
+		// Get the current metrics for the proposer.
+		metrics, err := l.GetProposerMetrics(ctx)
+		if err != nil {
+			return 0, err
+		}
+		l.Log.Info("Proposer status", "metrics", metrics)
+
+		// Queue up the range proofs that are ready to prove, based on the latest L2 finalized block.
+		if err := l.GetRangeProofBoundaries(ctx); err != nil {
+			return 0, err
+		}
+
+		// If a witness generation request has been in the WITNESSGEN state for longer than the timeout, set its status
+		// to FAILED and retry it.
+		if err := l.ProcessWitnessgenRequests(); err != nil {
+			return 0, err
+		}
+
+		claimed, err := l.ClaimQueuedSpanProofs()
+		if err != nil {
+			return 0, err
+		}
+		for _, req := range claimed {
+			select {
+			case p.spans <- req:
+			case <-l.done:
+				return 0, nil
+			}
+		}
+
+		// The contract may have moved without new span proofs, e.g. when another proposer submitted an output.
+		wake(p.wakeAggregate)
+		return len(claimed), nil
+	})
+}
+
+// headTracker remembers the highest finalized L2 block the rollup node reported.
+type headTracker struct {
+	finalized uint64
+}
+
+// advance records the rollup node's finalized L2 block, and reports whether it advanced. A lower block, e.g. from a
+// lagging sequencer op-conductor failed over to, is ignored.
+func (h *headTracker) advance(finalized uint64) bool {
+	if finalized <= h.finalized {
+		return false
+	}
+	h.finalized = finalized
+	return true
+}
+
+// watchSafeHead polls the rollup node's sync status every SafeHeadPollInterval, and wakes the plan stage as soon as
+// the finalized L2 head advances, so new blocks are planned without waiting for the next poll interval. The rollup
+// provider follows the active sequencer when the proposer is pointed at several op-conductor managed nodes.
+func (l *L2OutputSubmitter) watchSafeHead(ctx context.Context, p *pipeline) {
+	if l.Cfg.SafeHeadPollInterval == 0 {
+		return
+	}
+	ticker := time.NewTicker(l.Cfg.SafeHeadPollInterval)
+	defer ticker.Stop()
+	var heads headTracker
+	for {
+		select {
+		case <-ticker.C:
+		case <-l.done:
+			return
+		}
+		rollupClient, err := l.RollupProvider.RollupClient(ctx)
+		if err != nil {
+			l.Log.Warn("failed to get the rollup client", "err", err)
+			continue
+		}
+		status, err := rollupClient.SyncStatus(ctx)
+		if err != nil {
+			l.Log.Warn("failed to get the sync status", "err", err)
+			continue
+		}
+		if heads.advance(status.FinalizedL2.Number) {
+			l.Log.Debug("finalized L2 head advanced", "block", status.FinalizedL2.Number, "safe", status.SafeL2.Number)
+			wake(p.wakePlan)
+		}
+	}
+}
+
+// witnessgenWorker requests span proofs from the server one at a time, until the plan stage stops.
+func (l *L2OutputSubmitter) witnessgenWorker(p *pipeline) {
+	for req := range p.spans {
+		start := time.Now()
+		l.RequestSpanProof(req)
+		l.Metr.RecordStageRun(stageWitnessgen, time.Since(start), 1)
+
+		// Mock proofs are fulfilled by the request, so aggregation may be unblocked right away.
+		wake(p.wakeProving)
+		if l.Cfg.Mock {
+			wake(p.wakeAggregate)
+		}
+	}
+}
+
+// provingStage polls the server for the proofs being proven, and wakes the stages waiting on the ones fulfilled.
+func (l *L2OutputSubmitter) provingStage(ctx context.Context, p *pipeline) {
+	l.poll(stageProving, p.wakeProving, func() (int, error) {
+		// If a proof is fulfilled, we store it and set status = "COMPLETE". If it is unfulfillable, we set status =
+		// "FAILED" (and, if it's a span proof, split the request in half to try again).
+		fulfilled, err := l.ProcessProvingRequests()
+		for _, req := range fulfilled {
+			if req.Type == proofrequest.TypeAGG {
+				wake(p.wakeSubmit)
+			} else {
+				wake(p.wakeAggregate)
+			}
+		}
+		return len(fulfilled), err
+	})
+}
+
+// aggregateStage queues an AGG proof once a contiguous chain of span proofs from the L2OO's latest block reaches its
+// next block, and requests the queued AGG proofs.
+func (l *L2OutputSubmitter) aggregateStage(ctx context.Context, p *pipeline) {
+	l.poll(stageAggregate, p.wakeAggregate, func() (int, error) {
+		if err := l.DeriveAggProofs(ctx); err != nil {
+			return 0, err
+		}
+
+		// Checkpointing the L1 block hash for an AGG proof waits for a transaction, which only blocks this stage.
+		requested, err := l.RequestQueuedAggProof(ctx)
+		if err != nil || !requested {
+			return 0, err
+		}
+		wake(p.wakeProving)
+		if l.Cfg.Mock {
+			wake(p.wakeSubmit)
+		}
+		return 1, nil
+	})
+}
+
+// submitStage proposes the completed AGG proof reaching the furthest L2 block on-chain.
+func (l *L2OutputSubmitter) submitStage(ctx context.Context, p *pipeline) {
+	l.poll(stageSubmit, p.wakeSubmit, func() (int, error) {
+		// Completed AGG proofs stay queued in the DB during a submission window, and the one reaching the furthest
+		// block is proposed on the first poll after the window ends.
+		if until, deferred := submissionsDeferredUntil(l.Cfg.SubmissionWindows, time.Now()); deferred {
+			l.Log.Info("deferring output submission during a submission window", "until", until)
+			return 0, nil
+		}
+		return 0, l.SubmitAggProofs(ctx)
+	})
+}
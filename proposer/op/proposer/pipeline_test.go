@@ -0,0 +1,40 @@
+package proposer
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/require"
+)
+
+func TestWitnessgenSlots(t *testing.T) {
+	// Limited by the concurrent witness generation processes.
+	require.Equal(t, 3, witnessgenSlots(5, 10, 2, 0))
+	// Limited by the concurrent proof requests, which include the proofs being proven.
+	require.Equal(t, 1, witnessgenSlots(5, 10, 2, 7))
+	// Never negative, e.g. after the limits are lowered on a restart.
+	require.Equal(t, 0, witnessgenSlots(5, 10, 6, 0))
+	require.Equal(t, 0, witnessgenSlots(5, 10, 0, 12))
+}
+
+func TestHeadTrackerAdvance(t *testing.T) {
+	var heads headTracker
+	require.True(t, heads.advance(100))
+	require.False(t, heads.advance(100))
+	// A lower head, e.g. after failing over to a lagging sequencer, doesn't wake the plan stage.
+	require.False(t, heads.advance(90))
+	require.True(t, heads.advance(101))
+}
+
+func TestWakeCoalesces(t *testing.T) {
+	p := newPipeline(1)
+
+	// Wake-ups sent while the stage is busy coalesce, and never block the sender.
+	wake(p.wakeAggregate)
+	wake(p.wakeAggregate)
+	require.Len(t, p.wakeAggregate, 1)
+
+	<-p.wakeAggregate
+	require.Len(t, p.wakeAggregate, 0)
+	wake(p.wakeAggregate)
+	require.Len(t, p.wakeAggregate, 1)
+}
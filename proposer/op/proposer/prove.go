@@ -4,27 +4,33 @@ import (
 	"bytes"
 	"context"
 	"encoding/json"
+	"errors"
 	"fmt"
 	"io"
 	"net"
 	"net/http"
+	"slices"
+	"strconv"
 	"strings"
 	"time"
 
-	"github.com/ethereum/go-ethereum/accounts/abi/bind"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/succinctlabs/op-succinct-go/proposer/db"
 	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
 	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
 )
 
 const PROOF_STATUS_TIMEOUT = 30 * time.Second
+const PROOF_DOWNLOAD_TIMEOUT = 5 * time.Minute
 
-// Process all of requests in PROVING state.
-func (l *L2OutputSubmitter) ProcessProvingRequests() error {
+// Process all of requests in PROVING state. Returns the requests that were fulfilled.
+func (l *L2OutputSubmitter) ProcessProvingRequests() ([]*ent.ProofRequest, error) {
 	// Get all proof requests that are currently in the PROVING state.
 	reqs, err := l.db.GetAllProofsWithStatus(proofrequest.StatusPROVING)
 	if err != nil {
-		return err
+		return nil, err
 	}
+	var fulfilled []*ent.ProofRequest
 	for _, req := range reqs {
 		proofStatus, err := l.GetProofStatus(req.ProverRequestID)
 		if err != nil {
@@ -32,16 +38,17 @@ func (l *L2OutputSubmitter) ProcessProvingRequests() error {
 
 			// Record the error for the get proof status call.
 			l.Metr.RecordError("get_proof_status", 1)
-			return err
+			return fulfilled, err
 		}
 		if proofStatus.FulfillmentStatus == SP1FulfillmentStatusFulfilled {
 			// Update the proof in the DB and update status to COMPLETE.
 			l.Log.Info("Fulfilled Proof", "id", req.ProverRequestID)
-			err = l.db.AddFulfilledProof(req.ID, proofStatus.Proof)
+			err = l.db.AddFulfilledProof(req.ID, proofStatus.Proof, l.fulfilledUsage(req, proofStatus))
 			if err != nil {
 				l.Log.Error("failed to update completed proof status", "err", err)
-				return err
+				return fulfilled, err
 			}
+			fulfilled = append(fulfilled, req)
 			continue
 		}
 
@@ -52,12 +59,12 @@ func (l *L2OutputSubmitter) ProcessProvingRequests() error {
 
 			err = l.RetryRequest(req, proofStatus)
 			if err != nil {
-				return fmt.Errorf("failed to retry request: %w", err)
+				return fulfilled, fmt.Errorf("failed to retry request: %w", err)
 			}
 		}
 	}
 
-	return nil
+	return fulfilled, nil
 }
 
 // Process all of requests in WITNESSGEN state.
@@ -72,7 +79,7 @@ func (l *L2OutputSubmitter) ProcessWitnessgenRequests() error {
 		// This is a catch-all in case the witness generation state update failed.
 		if req.LastUpdatedTime+uint64(l.Cfg.WitnessGenTimeout) < uint64(time.Now().Unix()) {
 			// Retry the request if it timed out.
-			l.RetryRequest(req, ProofStatusResponse{})
+			l.RetryRequest(req, ProofStatusResponse{ErrorCode: ErrorCodeWitnessGenTimeout})
 		}
 	}
 
@@ -83,10 +90,87 @@ func (l *L2OutputSubmitter) ProcessWitnessgenRequests() error {
 // If an error response is received:
 // - Range Proof: Split in two if the block range is > 1 AND the proof is unexecutable OR has failed before. Retry the same request if range is 1 block.
 // - Agg Proof: Retry the same request.
+// ErrUnexecutableSpan is returned when the op-succinct-server fails to generate the witness for a span, or the
+// witness exceeds the server's stdin size limit.
+var ErrUnexecutableSpan = errors.New("witness generation failed for span")
+
+// SplitAtHeader lists the blocks the op-succinct-server asks an unexecutable span to be split at, comma-separated,
+// when it knows which block made the span unexecutable, such as a block exceeding its per-block witness limit.
+const SplitAtHeader = "X-Split-At"
+
+// SplitSpanError is returned when the op-succinct-server fails to generate the witness for a span, and asks for the
+// span to be split at the given blocks.
+type SplitSpanError struct {
+	SplitAt []uint64
+	Body    string
+	// Code is the error code of the failure, if the server sent one.
+	Code string
+}
+
+func (e *SplitSpanError) Error() string {
+	return fmt.Sprintf("%s: %s", ErrUnexecutableSpan, e.Body)
+}
+
+func (e *SplitSpanError) Unwrap() error {
+	return ErrUnexecutableSpan
+}
+
+// parseSplitAt parses the blocks of the SplitAtHeader.
+func parseSplitAt(header string) ([]uint64, error) {
+	var blocks []uint64
+	for _, field := range strings.Split(header, ",") {
+		block, err := strconv.ParseUint(strings.TrimSpace(field), 10, 64)
+		if err != nil {
+			return nil, fmt.Errorf("invalid %s header %q: %w", SplitAtHeader, header, err)
+		}
+		blocks = append(blocks, block)
+	}
+	return blocks, nil
+}
+
+// splitSpan splits a span at the given blocks, ignoring the blocks outside of it.
+func splitSpan(start, end uint64, splitAt []uint64) []Span {
+	points := []uint64{start}
+	for _, block := range splitAt {
+		if block > start && block < end {
+			points = append(points, block)
+		}
+	}
+	slices.Sort(points)
+	points = slices.Compact(append(points, end))
+
+	spans := make([]Span, 0, len(points)-1)
+	for i := 1; i < len(points); i++ {
+		spans = append(spans, Span{Start: points[i-1], End: points[i]})
+	}
+	return spans
+}
+
+// SplitRequest sets the status of a span proof to FAILED with the error code of its failure, and requests its span
+// split at the given blocks instead. If none of the blocks split the span, the span is retried like any other
+// unexecutable span.
+func (l *L2OutputSubmitter) SplitRequest(req *ent.ProofRequest, splitAt []uint64, errorCode string) error {
+	spans := splitSpan(req.StartBlock, req.EndBlock, splitAt)
+	if len(spans) < 2 {
+		return l.RetryRequest(req, ProofStatusResponse{ExecutionStatus: SP1ExecutionStatusUnexecutable, ErrorCode: errorCode})
+	}
+
+	if err := l.setFailed(req, errorCode); err != nil {
+		return err
+	}
+	l.Log.Info("splitting span at the blocks requested by the server", "start", req.StartBlock, "end", req.EndBlock, "splitAt", splitAt)
+	for _, span := range spans {
+		if err := l.db.NewEntry(req.Type, span.Start, span.End); err != nil {
+			l.Log.Error("failed to request split span proof", "start", span.Start, "end", span.End, "err", err)
+			return err
+		}
+	}
+	return nil
+}
+
 func (l *L2OutputSubmitter) RetryRequest(req *ent.ProofRequest, status ProofStatusResponse) error {
-	err := l.db.UpdateProofStatus(req.ID, proofrequest.StatusFAILED)
+	err := l.setFailed(req, statusErrorCode(status))
 	if err != nil {
-		l.Log.Error("failed to update proof status", "err", err)
 		return err
 	}
 
@@ -128,7 +212,7 @@ func (l *L2OutputSubmitter) RetryRequest(req *ent.ProofRequest, status ProofStat
 		}
 	} else {
 		// Retry the same request.
-		err = l.db.NewEntry(req.Type, req.StartBlock, req.EndBlock)
+		err = l.db.NewRetryEntry(req)
 		if err != nil {
 			l.Log.Error("failed to retry proof request", "err", err)
 			return err
@@ -138,95 +222,221 @@ func (l *L2OutputSubmitter) RetryRequest(req *ent.ProofRequest, status ProofStat
 	return nil
 }
 
-func (l *L2OutputSubmitter) RequestQueuedProofs(ctx context.Context) error {
-	nextProofToRequest, err := l.db.GetNextUnrequestedProof()
-	if err != nil {
-		return fmt.Errorf("failed to get unrequested proofs: %w", err)
+// requeue queues a proof request the op-succinct-server turned away because it is paused again as is. No failure is
+// recorded, so the request is neither split nor counted as a failure of its range, however long the server is paused.
+func (l *L2OutputSubmitter) requeue(req *ent.ProofRequest, err error) error {
+	l.Log.Warn("op-succinct-server is paused, requeueing proof request", "type", req.Type, "start", req.StartBlock, "end", req.EndBlock, "id", req.ID, "err", err)
+	if err := l.db.Requeue(req.ID); err != nil && !errors.Is(err, db.ErrNotPending) {
+		return err
 	}
-	if nextProofToRequest == nil {
-		return nil
+	return nil
+}
+
+// fulfilledUsage returns the cycles a fulfilled proof was billed for, to add to the daily cycle usage of the chain. The
+// proof is counted with no cycles if the server hasn't fetched them from the prover network.
+func (l *L2OutputSubmitter) fulfilledUsage(req *ent.ProofRequest, status ProofStatusResponse) *db.FulfilledUsage {
+	usage := &db.FulfilledUsage{ChainID: l.Cfg.L2ChainID, FulfilledAt: time.Now()}
+	if status.Metadata == nil || status.Metadata.Cycles == nil {
+		l.Log.Warn("cycles of fulfilled proof unknown", "type", req.Type, "start", req.StartBlock, "end", req.EndBlock, "id", req.ID)
+		return usage
 	}
+	usage.Cycles = *status.Metadata.Cycles
+	if status.Metadata.FulfilledAt != nil {
+		usage.FulfilledAt = time.Unix(int64(*status.Metadata.FulfilledAt), 0)
+	}
+	return usage
+}
 
-	if nextProofToRequest.Type == proofrequest.TypeAGG {
-		if nextProofToRequest.L1BlockHash == "" {
-			// Check if there's an existing agg proof with the same block range that's already failed.
-			existingProofs, err := l.db.GetProofRequestsWithBlockRangeAndStatus(proofrequest.TypeAGG, nextProofToRequest.StartBlock, nextProofToRequest.EndBlock, proofrequest.StatusFAILED)
-			if err != nil {
-				l.Log.Error("failed to check for existing agg proof", "err", err)
-				return err
-			}
-			// Loop over existing proofs and if any of them have a checkpointed L1BlockHash, add it to the next proof to request.
-			for _, proof := range existingProofs {
-				if proof.L1BlockHash != "" {
-					nextProofToRequest, err = l.db.AddL1BlockInfoToAggRequest(nextProofToRequest.StartBlock, nextProofToRequest.EndBlock, proof.L1BlockNumber, proof.L1BlockHash)
-					if err != nil {
-						l.Log.Error("failed to add L1 block info from existing checkpointed proof to AGG request", "err", err)
-						return err
-					}
-					break
-				}
-			}
+// setFailed sets the status of a proof request to FAILED, recording the error code of its failure if known.
+func (l *L2OutputSubmitter) setFailed(req *ent.ProofRequest, errorCode string) error {
+	if err := l.db.SetFailed(req.ID, errorCode); err != nil {
+		l.Log.Error("failed to update proof status", "err", err)
+		return err
+	}
+	if errorCode != "" {
+		l.Log.Info("proof request failed", "type", req.Type, "start", req.StartBlock, "end", req.EndBlock, "id", req.ID, "code", errorCode)
+		l.Metr.RecordProofRequestFailure(req.Type.String(), errorCode)
+	}
+	return nil
+}
 
-			// If the proof still doesn't have a L1BlockHash, checkpoint the block hash and add it to the request.
-			if nextProofToRequest.L1BlockHash == "" {
-				blockNumber, blockHash, err := l.checkpointBlockHash(ctx)
-				if err != nil {
-					l.Log.Error("failed to checkpoint block hash", "err", err)
-					return err
-				}
-				nextProofToRequest, err = l.db.AddL1BlockInfoToAggRequest(nextProofToRequest.StartBlock, nextProofToRequest.EndBlock, blockNumber, blockHash.Hex())
-				if err != nil {
-					l.Log.Error("failed to add L1 block info to AGG request", "err", err)
-					return err
-				}
-			}
-		} else {
-			l.Log.Info("found agg proof with already checkpointed l1 block info")
-		}
-	} else {
-		witnessGenProofs, err := l.db.GetNumberOfRequestsWithStatuses(proofrequest.StatusWITNESSGEN)
-		if err != nil {
-			return fmt.Errorf("failed to count witnessgen proofs: %w", err)
+// witnessgenSlots returns how many more span proofs can enter witness generation: at most maxWitnessGen span proofs
+// generate witnesses at once, which caps the processes spawned by the witness generation server, and at most
+// maxRequests span proofs are in witness generation or proving at once.
+func witnessgenSlots(maxWitnessGen, maxRequests uint64, witnessGen, proving int) int {
+	slots := min(int(maxWitnessGen)-witnessGen, int(maxRequests)-witnessGen-proving)
+	return max(slots, 0)
+}
+
+// ClaimQueuedSpanProofs moves the unrequested span proofs with the lowest start blocks to WITNESSGEN, as many as the
+// concurrency limits allow, and returns them to be requested from the server.
+func (l *L2OutputSubmitter) ClaimQueuedSpanProofs() ([]*ent.ProofRequest, error) {
+	witnessGenProofs, err := l.db.GetNumberOfRequestsWithStatuses(proofrequest.StatusWITNESSGEN)
+	if err != nil {
+		return nil, fmt.Errorf("failed to count witnessgen proofs: %w", err)
+	}
+	provingProofs, err := l.db.GetNumberOfRequestsWithStatuses(proofrequest.StatusPROVING)
+	if err != nil {
+		return nil, fmt.Errorf("failed to count proving proofs: %w", err)
+	}
+	slots := witnessgenSlots(l.Cfg.MaxConcurrentWitnessGen, l.Cfg.MaxConcurrentProofRequests, witnessGenProofs, provingProofs)
+	if slots == 0 {
+		l.Log.Debug("max concurrent span proof requests reached", "witnessgen", witnessGenProofs, "proving", provingProofs)
+		return nil, nil
+	}
+
+	proofs, err := l.db.GetUnrequestedProofs(proofrequest.TypeSPAN, slots)
+	if err != nil {
+		return nil, err
+	}
+	for _, p := range proofs {
+		if err := l.db.UpdateProofStatus(p.ID, proofrequest.StatusWITNESSGEN); err != nil {
+			return nil, fmt.Errorf("failed to update proof status: %w", err)
 		}
-		provingProofs, err := l.db.GetNumberOfRequestsWithStatuses(proofrequest.StatusPROVING)
-		if err != nil {
-			return fmt.Errorf("failed to count proving proofs: %w", err)
+	}
+	return proofs, nil
+}
+
+// RequestSpanProof requests a claimed span proof from the server, and queues it to be retried if the request fails.
+func (l *L2OutputSubmitter) RequestSpanProof(p *ent.ProofRequest) {
+	l.Log.Info("requesting proof from server", "type", p.Type, "start", p.StartBlock, "end", p.EndBlock, "id", p.ID)
+	err := l.RequestProof(*p, l.Cfg.Mock)
+	if err == nil {
+		return
+	}
+
+	// The request was invalidated while its proof was requested, and is already queued again.
+	if errors.Is(err, db.ErrNotPending) {
+		l.Log.Info("span proof request invalidated while requested", "start", p.StartBlock, "end", p.EndBlock, "id", p.ID)
+		return
+	}
+
+	if isServerPaused(err) {
+		if err := l.requeue(p, err); err != nil {
+			l.Log.Error("failed to requeue request", "err", err)
 		}
+		return
+	}
 
-		// The number of witness generation requests is capped at MAX_CONCURRENT_WITNESS_GEN. This prevents overloading the machine with processes spawned by the witness generation server.
-		// Once https://github.com/anton-rs/kona/issues/553 is fixed, we may be able to remove this check.
-		if witnessGenProofs >= int(l.Cfg.MaxConcurrentWitnessGen) {
-			l.Log.Info("max witness generation reached, waiting for next cycle")
-			return nil
+	// If the server knows which block made the span unexecutable, split the span around it.
+	var splitErr *SplitSpanError
+	if errors.As(err, &splitErr) {
+		l.Log.Error("failed to request span proof", "start", p.StartBlock, "end", p.EndBlock, "err", err)
+		if err := l.SplitRequest(p, splitErr.SplitAt, splitErr.Code); err != nil {
+			l.Log.Error("failed to split request", "err", err)
 		}
+		return
+	}
+
+	// If witness generation failed, split the span right away instead of waiting for it to fail again, so the
+	// block that can't be proven is isolated while the blocks around it are proven.
+	status := ProofStatusResponse{ErrorCode: requestErrorCode(err)}
+	if errors.Is(err, ErrUnexecutableSpan) {
+		status.ExecutionStatus = SP1ExecutionStatusUnexecutable
+	}
+	l.Log.Error("failed to request span proof", "start", p.StartBlock, "end", p.EndBlock, "err", err)
 
-		// The total number of concurrent proofs is capped at MAX_CONCURRENT_PROOF_REQUESTS.
-		if (witnessGenProofs + provingProofs) >= int(l.Cfg.MaxConcurrentProofRequests) {
-			l.Log.Info("max concurrent proof requests reached, waiting for next cycle")
-			return nil
+	// If the proof fails to be requested, we should add it to the queue to be retried.
+	if err := l.RetryRequest(p, status); err != nil {
+		l.Log.Error("failed to retry request", "err", err)
+	}
+}
+
+// RequestQueuedAggProof requests the unrequested AGG proof with the lowest start block from the server, after
+// checkpointing the L1 block hash it commits to. Returns whether a proof was requested.
+func (l *L2OutputSubmitter) RequestQueuedAggProof(ctx context.Context) (bool, error) {
+	proofs, err := l.db.GetUnrequestedProofs(proofrequest.TypeAGG, 1)
+	if err != nil {
+		return false, err
+	}
+	if len(proofs) == 0 {
+		return false, nil
+	}
+	nextProofToRequest := proofs[0]
+
+	// The AGG proof commits to an L1 block hash checkpointed on the L2OO, which must not be older than the L1 heads of the
+	// span proofs it aggregates. Prefer the block the request already commits to, then the ones failed requests of the
+	// same range committed to, then the latest one the proposer checkpointed, before checkpointing a new one.
+	var candidates []Checkpoint
+	if nextProofToRequest.L1BlockHash != "" {
+		candidates = append(candidates, Checkpoint{BlockNumber: nextProofToRequest.L1BlockNumber, BlockHash: common.HexToHash(nextProofToRequest.L1BlockHash)})
+	}
+	failedProofs, err := l.db.GetProofRequestsWithBlockRangeAndStatus(proofrequest.TypeAGG, nextProofToRequest.StartBlock, nextProofToRequest.EndBlock, proofrequest.StatusFAILED)
+	if err != nil {
+		l.Log.Error("failed to check for existing agg proof", "err", err)
+		return false, err
+	}
+	for _, proof := range failedProofs {
+		if proof.L1BlockHash != "" {
+			candidates = append(candidates, Checkpoint{BlockNumber: proof.L1BlockNumber, BlockHash: common.HexToHash(proof.L1BlockHash)})
 		}
 	}
-	go func(p ent.ProofRequest) {
-		l.Log.Info("requesting proof from server", "type", p.Type, "start", p.StartBlock, "end", p.EndBlock, "id", p.ID)
-		// Set the proof status to WITNESSGEN.
-		err = l.db.UpdateProofStatus(p.ID, proofrequest.StatusWITNESSGEN)
+	if latest, ok := l.checkpoints.Latest(); ok {
+		candidates = append(candidates, latest)
+	}
+	minBlockTime, err := l.db.GetLatestSpanProofRequestTime(nextProofToRequest.StartBlock, nextProofToRequest.EndBlock)
+	if err != nil {
+		return false, err
+	}
+	checkpoint, err := l.selectCheckpoint(ctx, candidates, minBlockTime)
+	if err != nil {
+		l.Log.Error("failed to checkpoint block hash", "err", err)
+		return false, err
+	}
+	if checkpoint.BlockNumber != nextProofToRequest.L1BlockNumber || checkpoint.BlockHash.Hex() != nextProofToRequest.L1BlockHash {
+		nextProofToRequest, err = l.db.AddL1BlockInfoToAggRequest(nextProofToRequest.StartBlock, nextProofToRequest.EndBlock, checkpoint.BlockNumber, checkpoint.BlockHash.Hex())
 		if err != nil {
-			l.Log.Error("failed to update proof status", "err", err)
-			return
+			l.Log.Error("failed to add L1 block info to AGG request", "err", err)
+			return false, err
 		}
+	}
 
-		// Request the type of proof depending on the mock configuration.
-		err = l.RequestProof(p, l.Cfg.Mock)
-		if err != nil {
-			// If the proof fails to be requested, we should add it to the queue to be retried.
-			err = l.RetryRequest(nextProofToRequest, ProofStatusResponse{})
-			if err != nil {
-				l.Log.Error("failed to retry request", "err", err)
-			}
-
+	// Record the span proofs the AGG proof aggregates before requesting it, so the request can be resumed.
+	nextProofToRequest, err = l.db.StartAggSession(nextProofToRequest.ID)
+	if err != nil {
+		return false, err
+	}
+	l.Log.Info("requesting proof from server", "type", nextProofToRequest.Type, "start", nextProofToRequest.StartBlock, "end", nextProofToRequest.EndBlock, "id", nextProofToRequest.ID, "subproofs", nextProofToRequest.Subproofs)
+	if err := l.RequestProof(*nextProofToRequest, l.Cfg.Mock); errors.Is(err, db.ErrNotPending) {
+		l.Log.Info("agg proof request invalidated while requested", "start", nextProofToRequest.StartBlock, "end", nextProofToRequest.EndBlock, "id", nextProofToRequest.ID)
+	} else if isServerPaused(err) {
+		if err := l.requeue(nextProofToRequest, err); err != nil {
+			return false, fmt.Errorf("failed to requeue request: %w", err)
+		}
+	} else if err != nil {
+		l.Log.Error("failed to request agg proof", "start", nextProofToRequest.StartBlock, "end", nextProofToRequest.EndBlock, "err", err)
+		// If the proof fails to be requested, we should add it to the queue to be retried.
+		if err := l.RetryRequest(nextProofToRequest, ProofStatusResponse{ErrorCode: requestErrorCode(err)}); err != nil {
+			return false, fmt.Errorf("failed to retry request: %w", err)
 		}
-	}(*nextProofToRequest)
+	}
+	return true, nil
+}
 
+// ResumeAggSessions requests the AGG proofs again whose session started but whose proof ID wasn't recorded, e.g.
+// because the proposer stopped while the server built the request. The request is identical to the first one, so a
+// server that already requested the proof returns its proof ID instead of aggregating the span proofs again. Must run
+// before the pipeline starts, so the plan stage doesn't time out the sessions while they are requested.
+func (l *L2OutputSubmitter) ResumeAggSessions() error {
+	sessions, err := l.db.GetResumableAggSessions()
+	if err != nil {
+		return err
+	}
+	for _, session := range sessions {
+		l.Log.Info("resuming AGG proof request", "start", session.StartBlock, "end", session.EndBlock, "id", session.ID, "subproofs", session.Subproofs)
+		if err := l.RequestProof(*session, l.Cfg.Mock); errors.Is(err, db.ErrNotPending) {
+			l.Log.Info("agg proof request invalidated while requested", "start", session.StartBlock, "end", session.EndBlock, "id", session.ID)
+		} else if isServerPaused(err) {
+			if err := l.requeue(session, err); err != nil {
+				return fmt.Errorf("failed to requeue request: %w", err)
+			}
+		} else if err != nil {
+			l.Log.Error("failed to resume agg proof request", "start", session.StartBlock, "end", session.EndBlock, "err", err)
+			if err := l.RetryRequest(session, ProofStatusResponse{ErrorCode: requestErrorCode(err)}); err != nil {
+				return fmt.Errorf("failed to retry request: %w", err)
+			}
+		}
+	}
 	return nil
 }
 
@@ -234,28 +444,106 @@ func (l *L2OutputSubmitter) RequestQueuedProofs(ctx context.Context) error {
 // Check the DB to see if we have sufficient span proofs to request an agg proof that covers this range.
 // If so, queue up the agg proof in the DB to be requested later.
 func (l *L2OutputSubmitter) DeriveAggProofs(ctx context.Context) error {
-	latest, err := l.l2ooContract.LatestBlockNumber(&bind.CallOpts{Context: ctx})
+	state, err := l.FetchL2OOState(ctx)
 	if err != nil {
-		return fmt.Errorf("failed to get latest L2OO output: %w", err)
+		return err
+	}
+
+	aggregate, err := l.shouldAggregate(state)
+	if err != nil || !aggregate {
+		return err
 	}
 
-	// This fetches the next block number, which is the currentBlock + submissionInterval.
-	minTo, err := l.l2ooContract.NextBlockNumber(&bind.CallOpts{Context: ctx})
+	// After governance force-resolved the latest output, the AGG proof aggregates over the configured checkpoint
+	// overrides, which are recorded with it.
+	checkpointOverrides, err := l.catchUpCheckpointOverrides(ctx)
 	if err != nil {
-		return fmt.Errorf("failed to get next L2OO output: %w", err)
+		return err
 	}
 
-	created, end, err := l.db.TryCreateAggProofFromSpanProofs(latest.Uint64(), minTo.Uint64())
+	created, end, err := l.db.TryCreateAggProofFromSpanProofs(state.LatestBlockNumber, state.NextBlockNumber, checkpointOverrides)
 	if err != nil {
 		return fmt.Errorf("failed to create agg proof from span proofs: %w", err)
 	}
-	if created {
-		l.Log.Info("created new AGG proof", "from", latest.Uint64(), "to", end)
+	if created && checkpointOverrides != "" {
+		l.Log.Warn("created new AGG proof over checkpoint overrides", "from", state.LatestBlockNumber, "to", end, "justification", l.Cfg.CheckpointOverrides.Justification)
+	} else if created {
+		l.Log.Info("created new AGG proof", "from", state.LatestBlockNumber, "to", end)
 	}
 
 	return nil
 }
 
+// shouldAggregate asks the op-succinct-server's aggregation policy whether to aggregate the span proofs contiguous with
+// the L2OO's latest output, once they reach its next block and no AGG proof starts at the latest output yet. If the
+// server can't be reached, the span proofs are aggregated, as without a policy.
+func (l *L2OutputSubmitter) shouldAggregate(state L2OOState) (bool, error) {
+	exists, err := l.db.HasAggProof(state.LatestBlockNumber)
+	if err != nil || exists {
+		return false, err
+	}
+	spans, err := l.db.GetContiguousSpanProofs(state.LatestBlockNumber)
+	if err != nil {
+		return false, fmt.Errorf("failed to get contiguous span proofs: %w", err)
+	}
+	if len(spans) == 0 || spans[len(spans)-1].EndBlock < state.NextBlockNumber {
+		return false, nil
+	}
+
+	pending := pendingSpanProofs(spans, uint64(time.Now().Unix()))
+	decision, err := l.GetAggregationDecision(pending)
+	if err != nil {
+		l.Log.Warn("failed to get aggregation decision, aggregating", "err", err)
+		return true, nil
+	}
+	if !decision.Aggregate {
+		l.Log.Info("aggregation policy is waiting", "from", state.LatestBlockNumber, "spans", pending.Count, "oldestAge", pending.OldestProofAgeSecs, "reason", decision.Reason)
+	}
+	return decision.Aggregate, nil
+}
+
+// pendingSpanProofs summarizes the completed span proofs for the aggregation policy. A span proof's last update is when
+// it completed.
+func pendingSpanProofs(spans []*ent.ProofRequest, now uint64) PendingSpanProofs {
+	pending := PendingSpanProofs{Count: uint64(len(spans))}
+	for _, span := range spans {
+		if now > span.LastUpdatedTime && now-span.LastUpdatedTime > pending.OldestProofAgeSecs {
+			pending.OldestProofAgeSecs = now - span.LastUpdatedTime
+		}
+	}
+	return pending
+}
+
+// GetAggregationDecision evaluates the op-succinct-server's aggregation policy for the pending span proofs.
+func (l *L2OutputSubmitter) GetAggregationDecision(pending PendingSpanProofs) (AggregationDecision, error) {
+	jsonBody, err := json.Marshal(pending)
+	if err != nil {
+		return AggregationDecision{}, fmt.Errorf("failed to marshal request body: %w", err)
+	}
+	client := &http.Client{
+		Timeout: PROOF_STATUS_TIMEOUT,
+	}
+	resp, err := client.Post(l.Cfg.OPSuccinctServerUrl+"/aggregation_decision", "application/json", bytes.NewBuffer(jsonBody))
+	if err != nil {
+		return AggregationDecision{}, fmt.Errorf("failed to send request: %w", err)
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return AggregationDecision{}, fmt.Errorf("error reading the response body: %v", err)
+	}
+	if resp.StatusCode != http.StatusOK {
+		return AggregationDecision{}, fmt.Errorf("received status code %d: %s", resp.StatusCode, body)
+	}
+
+	var decision AggregationDecision
+	if err := json.Unmarshal(body, &decision); err != nil {
+		return AggregationDecision{}, fmt.Errorf("error decoding JSON response: %v", err)
+	}
+	return decision, nil
+}
+
 func (l *L2OutputSubmitter) prepareProofRequest(p ent.ProofRequest) ([]byte, error) {
 	if p.Type == proofrequest.TypeSPAN {
 		if p.StartBlock >= p.EndBlock {
@@ -263,8 +551,9 @@ func (l *L2OutputSubmitter) prepareProofRequest(p ent.ProofRequest) ([]byte, err
 		}
 
 		requestBody := SpanProofRequest{
-			Start: p.StartBlock,
-			End:   p.EndBlock,
+			Start:         p.StartBlock,
+			End:           p.EndBlock,
+			WitnessSha256: p.WitnessSha256,
 		}
 		jsonBody, err := json.Marshal(requestBody)
 		if err != nil {
@@ -272,13 +561,26 @@ func (l *L2OutputSubmitter) prepareProofRequest(p ent.ProofRequest) ([]byte, err
 		}
 		return jsonBody, nil
 	} else {
-		subproofs, err := l.db.GetConsecutiveSpanProofs(p.StartBlock, p.EndBlock)
+		// AGG proofs are requested with the span proofs recorded when their session started. Sessions started by an
+		// older proposer have none recorded.
+		var subproofs [][]byte
+		var err error
+		if p.Subproofs != "" {
+			subproofs, err = l.db.GetAggSessionSpanProofs(&p)
+		} else {
+			subproofs, err = l.db.GetConsecutiveSpanProofs(p.StartBlock, p.EndBlock)
+		}
 		if err != nil {
 			return nil, fmt.Errorf("failed to get subproofs: %w", err)
 		}
+		checkpointOverrides, err := parseCheckpointOverrides(p.CheckpointOverrides)
+		if err != nil {
+			return nil, fmt.Errorf("invalid checkpoint overrides: %w", err)
+		}
 		requestBody := AggProofRequest{
-			Subproofs: subproofs,
-			L1Head:    p.L1BlockHash,
+			Subproofs:           subproofs,
+			L1Head:              p.L1BlockHash,
+			CheckpointOverrides: checkpointOverrides,
 		}
 		jsonBody, err := json.Marshal(requestBody)
 		if err != nil {
@@ -306,25 +608,20 @@ func (l *L2OutputSubmitter) RequestProof(p ent.ProofRequest, isMock bool) error
 		if err != nil {
 			return fmt.Errorf("failed to set proof status to proving: %w", err)
 		}
-		return l.db.AddFulfilledProof(p.ID, proofData)
+		return l.db.AddFulfilledProof(p.ID, proofData, nil)
 	}
 
 	// Request a real proof from the witness generation server. Returns the proof ID from the network.
-	proofID, err := l.requestRealProof(p.Type, jsonBody)
+	response, err := l.requestRealProof(p.Type, jsonBody)
 	if err != nil {
 		return fmt.Errorf("real proof request failed: %w", err)
 	}
 
 	// Set the proof status to PROVING once the prover ID has been retrieved. Only proofs with status PROVING, SUCCESS or FAILED have a prover request ID.
-	err = l.db.UpdateProofStatus(p.ID, proofrequest.StatusPROVING)
-	if err != nil {
-		return fmt.Errorf("failed to set proof status to proving: %w", err)
-	}
-
-	return l.db.SetProverRequestID(p.ID, proofID)
+	return l.db.SetProving(p.ID, response.ProofID, response.WitnessSha256)
 }
 
-func (l *L2OutputSubmitter) requestRealProof(proofType proofrequest.Type, jsonBody []byte) ([]byte, error) {
+func (l *L2OutputSubmitter) requestRealProof(proofType proofrequest.Type, jsonBody []byte) (*WitnessGenerationResponse, error) {
 	resp, err := l.makeProofRequest(proofType, jsonBody)
 	if err != nil {
 		return nil, err
@@ -336,8 +633,8 @@ func (l *L2OutputSubmitter) requestRealProof(proofType proofrequest.Type, jsonBo
 	}
 	// Format the proof ID as a hex string.
 	proofIdHex := fmt.Sprintf("%x", response.ProofID)
-	l.Log.Info("successfully submitted proof", "proofID", proofIdHex)
-	return response.ProofID, nil
+	l.Log.Info("successfully submitted proof", "proofID", proofIdHex, "witnessSha256", response.WitnessSha256)
+	return &response, nil
 }
 
 // Request a mock proof from the witness generation server.
@@ -386,14 +683,31 @@ func (l *L2OutputSubmitter) makeProofRequest(proofType proofrequest.Type, jsonBo
 		if err := json.Unmarshal(body, &errResp); err == nil {
 			l.Log.Error("Witness generation request failed",
 				"status", resp.StatusCode,
+				"code", resp.Header.Get(ErrorCodeHeader),
 				"error", errResp.Error)
 		} else {
 			l.Log.Error("Witness generation request failed",
 				"status", resp.StatusCode,
+				"code", resp.Header.Get(ErrorCodeHeader),
 				"body", string(body))
 		}
-		l.Metr.RecordWitnessGenFailure("Failed")
-		return nil, fmt.Errorf("received non-200 status code: %d", resp.StatusCode)
+		serverErr := &ServerError{StatusCode: resp.StatusCode, Code: resp.Header.Get(ErrorCodeHeader), Body: string(body)}
+		// A paused server didn't attempt witness generation.
+		if serverErr.Code != ErrorCodePaused {
+			l.Metr.RecordWitnessGenFailure("Failed")
+		}
+		if resp.StatusCode == http.StatusUnprocessableEntity && proofType == proofrequest.TypeSPAN {
+			if header := resp.Header.Get(SplitAtHeader); header != "" {
+				splitAt, err := parseSplitAt(header)
+				if err != nil {
+					l.Log.Warn("ignoring the server's split points", "err", err)
+				} else {
+					return nil, &SplitSpanError{SplitAt: splitAt, Body: serverErr.Body, Code: serverErr.Code}
+				}
+			}
+			return nil, fmt.Errorf("%w: %w", ErrUnexecutableSpan, serverErr)
+		}
+		return nil, serverErr
 	}
 
 	return io.ReadAll(resp.Body)
@@ -464,9 +778,71 @@ func (l *L2OutputSubmitter) GetProofStatus(proofId string) (ProofStatusResponse,
 		return ProofStatusResponse{}, fmt.Errorf("error decoding JSON response: %v", err)
 	}
 
+	// Large proofs are downloaded separately rather than returned inline.
+	if len(proofStatus.Proof) == 0 && proofStatus.ProofURL != "" {
+		proof, err := l.DownloadProof(proofStatus.ProofURL)
+		if err != nil {
+			return ProofStatusResponse{}, fmt.Errorf("failed to download proof %s: %w", proofId, err)
+		}
+		proofStatus.Proof = proof
+	}
+
 	return proofStatus, nil
 }
 
+// DownloadProof downloads a proof's raw bytes from the path a status response links to.
+func (l *L2OutputSubmitter) DownloadProof(path string) ([]byte, error) {
+	client := &http.Client{
+		Timeout: PROOF_DOWNLOAD_TIMEOUT,
+	}
+	resp, err := client.Get(strings.TrimSuffix(l.Cfg.OPSuccinctServerUrl, "/") + "/" + strings.TrimPrefix(path, "/"))
+	if err != nil {
+		return nil, fmt.Errorf("failed to send request: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(resp.Body)
+		return nil, fmt.Errorf("received non-200 status code: %d: %s", resp.StatusCode, body)
+	}
+
+	proof, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, fmt.Errorf("error reading the response body: %w", err)
+	}
+	if resp.ContentLength >= 0 && int64(len(proof)) != resp.ContentLength {
+		return nil, fmt.Errorf("proof download truncated: got %d of %d bytes", len(proof), resp.ContentLength)
+	}
+	return proof, nil
+}
+
+// GetProvableHead gets the highest L2 block a range can be proven up to from the op-succinct-server:
+// the safe head at the finalized L1 block.
+func (l *L2OutputSubmitter) GetProvableHead() (ProvableHeadResponse, error) {
+	client := &http.Client{
+		Timeout: PROOF_STATUS_TIMEOUT,
+	}
+	resp, err := client.Get(l.Cfg.OPSuccinctServerUrl + "/provable_head")
+	if err != nil {
+		return ProvableHeadResponse{}, fmt.Errorf("failed to send request: %w", err)
+	}
+	defer resp.Body.Close()
+
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return ProvableHeadResponse{}, fmt.Errorf("error reading the response body: %v", err)
+	}
+	if resp.StatusCode != http.StatusOK {
+		return ProvableHeadResponse{}, fmt.Errorf("received status code %d: %s", resp.StatusCode, body)
+	}
+
+	var head ProvableHeadResponse
+	if err := json.Unmarshal(body, &head); err != nil {
+		return ProvableHeadResponse{}, fmt.Errorf("error decoding JSON response: %v", err)
+	}
+	return head, nil
+}
+
 // Validate the contract's configuration of the aggregation and range verification keys as well
 // as the rollup config hash.
 func (l *L2OutputSubmitter) ValidateConfig(address string) error {
@@ -541,7 +917,8 @@ func (l *L2OutputSubmitter) ValidateConfig(address string) error {
 		invalidConfigs = append(invalidConfigs, "range verification key")
 	}
 	if len(invalidConfigs) > 0 {
-		return fmt.Errorf("config is invalid: %s", strings.Join(invalidConfigs, ", "))
+		l.Metr.RecordError(ErrorCodeVkeyMismatch, 1)
+		return fmt.Errorf("config is invalid (%s): %s", ErrorCodeVkeyMismatch, strings.Join(invalidConfigs, ", "))
 	}
 
 	return nil
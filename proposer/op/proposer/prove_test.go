@@ -0,0 +1,37 @@
+package proposer
+
+import (
+	"errors"
+	"testing"
+
+	"github.com/stretchr/testify/require"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
+)
+
+func TestSplitSpan(t *testing.T) {
+	// The block is isolated in a span of its own.
+	require.Equal(t, []Span{{100, 104}, {104, 105}, {105, 110}}, splitSpan(100, 110, []uint64{104, 105}))
+	// Blocks outside of the span, and duplicates, are ignored.
+	require.Equal(t, []Span{{100, 101}, {101, 110}}, splitSpan(100, 110, []uint64{101, 101, 100, 120}))
+	require.Equal(t, []Span{{100, 110}}, splitSpan(100, 110, nil))
+}
+
+func TestParseSplitAt(t *testing.T) {
+	blocks, err := parseSplitAt("104, 105")
+	require.NoError(t, err)
+	require.Equal(t, []uint64{104, 105}, blocks)
+
+	_, err = parseSplitAt("104,latest")
+	require.Error(t, err)
+}
+
+func TestSplitSpanErrorIsUnexecutable(t *testing.T) {
+	var err error = &SplitSpanError{SplitAt: []uint64{104}, Body: "oversized block"}
+	require.True(t, errors.Is(err, ErrUnexecutableSpan))
+}
+
+func TestPendingSpanProofs(t *testing.T) {
+	spans := []*ent.ProofRequest{{LastUpdatedTime: 1000}, {LastUpdatedTime: 700}, {LastUpdatedTime: 1300}}
+	require.Equal(t, PendingSpanProofs{Count: 3, OldestProofAgeSecs: 500}, pendingSpanProofs(spans, 1200))
+	require.Equal(t, PendingSpanProofs{}, pendingSpanProofs(nil, 1200))
+}
@@ -0,0 +1,110 @@
+package proposer
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/ethereum/go-ethereum/accounts/abi/bind"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
+)
+
+// ProvingConfig is the L2OO's configuration the proposed proofs are verified against.
+type ProvingConfig struct {
+	// AggregationVkey is the verification key of the aggregation program, which verifies AGG proofs.
+	AggregationVkey common.Hash
+	// RangeVkeyCommitment is the commitment to the range program's verification key, which AGG proofs check the
+	// span proofs they aggregate against.
+	RangeVkeyCommitment common.Hash
+	// RollupConfigHash is the hash of the rollup config the span proofs derive the L2 chain with.
+	RollupConfigHash common.Hash
+}
+
+// FetchProvingConfig reads the L2OO's verification keys and rollup config hash.
+func (l *L2OutputSubmitter) FetchProvingConfig(ctx context.Context) (ProvingConfig, error) {
+	callOpts := &bind.CallOpts{Context: ctx}
+	aggVkey, err := l.l2ooContract.AggregationVkey(callOpts)
+	if err != nil {
+		return ProvingConfig{}, fmt.Errorf("failed to get aggregation vkey: %w", err)
+	}
+	rangeVkey, err := l.l2ooContract.RangeVkeyCommitment(callOpts)
+	if err != nil {
+		return ProvingConfig{}, fmt.Errorf("failed to get range vkey commitment: %w", err)
+	}
+	rollupConfigHash, err := l.l2ooContract.RollupConfigHash(callOpts)
+	if err != nil {
+		return ProvingConfig{}, fmt.Errorf("failed to get rollup config hash: %w", err)
+	}
+	return ProvingConfig{
+		AggregationVkey:     aggVkey,
+		RangeVkeyCommitment: rangeVkey,
+		RollupConfigHash:    rollupConfigHash,
+	}, nil
+}
+
+// staleProofTypes returns the types of proofs proven under `prev` that no longer verify on the L2OO under `next`. Span
+// proofs are proven by the range program with the rollup config, and AGG proofs aggregate them, so a change to either
+// makes both stale. A change to the aggregation vkey only makes the AGG proofs stale.
+func staleProofTypes(prev, next ProvingConfig) []proofrequest.Type {
+	if prev.RangeVkeyCommitment != next.RangeVkeyCommitment || prev.RollupConfigHash != next.RollupConfigHash {
+		return []proofrequest.Type{proofrequest.TypeSPAN, proofrequest.TypeAGG}
+	}
+	if prev.AggregationVkey != next.AggregationVkey {
+		return []proofrequest.Type{proofrequest.TypeAGG}
+	}
+	return nil
+}
+
+// watchProvingConfig polls the L2OO's verification keys and rollup config hash every poll interval. Once they change,
+// e.g. after an upgrade of the range program, the proofs proven or being proven under the old config can't be proposed,
+// so it fails the stale ones that aren't submitted yet and queues their span proofs again, checks that the server
+// proves under the new config, and wakes the plan and aggregate stages to prove them again. A change while the
+// proposer is stopped isn't detected.
+func (l *L2OutputSubmitter) watchProvingConfig(ctx context.Context, p *pipeline) {
+	current, err := l.FetchProvingConfig(ctx)
+	known := err == nil
+	if err != nil {
+		l.Log.Warn("failed to get the L2OO proving config", "err", err)
+	}
+
+	l.poll(stageConfig, nil, func() (int, error) {
+		config, err := l.FetchProvingConfig(ctx)
+		if err != nil {
+			return 0, err
+		}
+		if !known {
+			current, known = config, true
+			return 0, nil
+		}
+		stale := staleProofTypes(current, config)
+		if len(stale) == 0 {
+			current = config
+			return 0, nil
+		}
+		l.Log.Warn("L2OO proving config changed", "old", current, "new", config, "stale", stale)
+
+		// The config is only updated once the stale proofs are invalidated, so a failure is retried on the next poll.
+		state, err := l.FetchL2OOState(ctx)
+		if err != nil {
+			return 0, err
+		}
+		invalidated, err := l.db.InvalidateProofs(stale, state.LatestBlockNumber, ErrorCodeStaleConfig)
+		if err != nil {
+			return 0, err
+		}
+		current = config
+		for _, req := range invalidated {
+			l.Log.Info("proof request failed", "type", req.Type, "start", req.StartBlock, "end", req.EndBlock, "id", req.ID, "code", ErrorCodeStaleConfig)
+			l.Metr.RecordProofRequestFailure(req.Type.String(), ErrorCodeStaleConfig)
+		}
+
+		wake(p.wakePlan)
+		wake(p.wakeAggregate)
+
+		// Proofs from a server that still proves under the old config can't be proposed either.
+		if err := l.ValidateConfig(l.Cfg.L2OutputOracleAddr.Hex()); err != nil {
+			return len(invalidated), fmt.Errorf("server doesn't prove under the new L2OO config: %w", err)
+		}
+		return len(invalidated), nil
+	})
+}
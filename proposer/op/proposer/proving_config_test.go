@@ -0,0 +1,33 @@
+package proposer
+
+import (
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/stretchr/testify/require"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
+)
+
+func TestStaleProofTypes(t *testing.T) {
+	config := ProvingConfig{
+		AggregationVkey:     common.HexToHash("0x01"),
+		RangeVkeyCommitment: common.HexToHash("0x02"),
+		RollupConfigHash:    common.HexToHash("0x03"),
+	}
+	require.Empty(t, staleProofTypes(config, config))
+
+	// A new aggregation vkey only verifies AGG proofs differently.
+	next := config
+	next.AggregationVkey = common.HexToHash("0x04")
+	require.Equal(t, []proofrequest.Type{proofrequest.TypeAGG}, staleProofTypes(config, next))
+
+	// A new range program or rollup config makes the span proofs, and the AGG proofs of them, stale.
+	both := []proofrequest.Type{proofrequest.TypeSPAN, proofrequest.TypeAGG}
+	next = config
+	next.RangeVkeyCommitment = common.HexToHash("0x04")
+	require.Equal(t, both, staleProofTypes(config, next))
+	next = config
+	next.RollupConfigHash = common.HexToHash("0x04")
+	next.AggregationVkey = common.HexToHash("0x05")
+	require.Equal(t, both, staleProofTypes(config, next))
+}
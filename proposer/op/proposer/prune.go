@@ -0,0 +1,105 @@
+package proposer
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
+)
+
+// pruneInterval is how often the proposer prunes proof requests past their retention from its DB.
+const pruneInterval = time.Hour
+
+// loopPrune regularly prunes the proof requests past their retention, so the DB doesn't grow with every
+// proof the proposer has ever requested.
+func (l *L2OutputSubmitter) loopPrune(ctx context.Context) {
+	defer l.wg.Done()
+	ticker := time.NewTicker(pruneInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ticker.C:
+			if err := l.PruneProofs(ctx); err != nil {
+				l.Log.Error("failed to prune proofs", "err", err)
+			}
+		case <-l.done:
+			return
+		}
+	}
+}
+
+// PruneProofs archives the completed requests the L2OO is past and the failed requests that are older than their
+// retention, and deletes them from the DB. Completed requests the L2OO isn't past yet are kept, as they may still
+// be aggregated and submitted.
+func (l *L2OutputSubmitter) PruneProofs(ctx context.Context) error {
+	now := time.Now()
+	state, err := l.FetchL2OOState(ctx)
+	if err != nil {
+		return err
+	}
+	proofs, err := l.db.GetPrunableProofs(
+		retentionCutoff(now, l.Cfg.FulfilledRetentionDays),
+		retentionCutoff(now, l.Cfg.FailedRetentionDays),
+		state.LatestBlockNumber,
+	)
+	if err != nil {
+		return err
+	}
+	if len(proofs) == 0 {
+		return nil
+	}
+
+	path, err := archiveProofs(l.Cfg.PruneArchiveDir, now, proofs)
+	if err != nil {
+		return fmt.Errorf("failed to archive proofs: %w", err)
+	}
+	ids := make([]int, len(proofs))
+	for i, proof := range proofs {
+		ids[i] = proof.ID
+	}
+	deleted, err := l.db.DeleteProofs(ids)
+	if err != nil {
+		return err
+	}
+
+	l.Log.Info("Pruned proof requests", "count", deleted, "archive", path)
+	return nil
+}
+
+// retentionCutoff returns the time before which requests kept for `days` are pruned, or the zero time if they are
+// kept forever.
+func retentionCutoff(now time.Time, days uint64) time.Time {
+	if days == 0 {
+		return time.Time{}
+	}
+	return now.Add(-time.Duration(days) * 24 * time.Hour)
+}
+
+// archiveProofs appends the requests to `{dir}/pruned-{date}.jsonl`, one JSON object per line, and syncs the
+// file before returning, so no request is deleted before it is archived. Returns the path of the archive.
+func archiveProofs(dir string, now time.Time, proofs []*ent.ProofRequest) (string, error) {
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return "", err
+	}
+	path := filepath.Join(dir, fmt.Sprintf("pruned-%s.jsonl", now.UTC().Format(time.DateOnly)))
+	file, err := os.OpenFile(path, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0644)
+	if err != nil {
+		return "", err
+	}
+	defer file.Close()
+
+	encoder := json.NewEncoder(file)
+	for _, proof := range proofs {
+		if err := encoder.Encode(proof); err != nil {
+			return "", err
+		}
+	}
+	if err := file.Sync(); err != nil {
+		return "", err
+	}
+	return path, nil
+}
@@ -0,0 +1,51 @@
+package proposer
+
+import (
+	"bufio"
+	"encoding/json"
+	"os"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/require"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
+)
+
+func TestRetentionCutoff(t *testing.T) {
+	now := time.Date(2024, 6, 10, 12, 0, 0, 0, time.UTC)
+
+	require.True(t, retentionCutoff(now, 0).IsZero())
+	require.Equal(t, time.Date(2024, 6, 3, 12, 0, 0, 0, time.UTC), retentionCutoff(now, 7))
+}
+
+func TestArchiveProofs(t *testing.T) {
+	dir := t.TempDir()
+	now := time.Date(2024, 6, 10, 12, 0, 0, 0, time.UTC)
+	proofs := []*ent.ProofRequest{
+		{ID: 1, Type: proofrequest.TypeSPAN, StartBlock: 100, EndBlock: 200, Status: proofrequest.StatusCOMPLETE, Proof: []byte{1, 2}},
+		{ID: 2, Type: proofrequest.TypeAGG, StartBlock: 100, EndBlock: 300, Status: proofrequest.StatusFAILED},
+	}
+
+	// Archiving twice on the same day appends to the same file.
+	path, err := archiveProofs(dir, now, proofs[:1])
+	require.NoError(t, err)
+	_, err = archiveProofs(dir, now, proofs[1:])
+	require.NoError(t, err)
+	require.Equal(t, "pruned-2024-06-10.jsonl", path[len(dir)+1:])
+
+	file, err := os.Open(path)
+	require.NoError(t, err)
+	defer file.Close()
+	var archived []ent.ProofRequest
+	scanner := bufio.NewScanner(file)
+	for scanner.Scan() {
+		var proof ent.ProofRequest
+		require.NoError(t, json.Unmarshal(scanner.Bytes(), &proof))
+		archived = append(archived, proof)
+	}
+	require.NoError(t, scanner.Err())
+	require.Len(t, archived, 2)
+	require.Equal(t, []byte{1, 2}, archived[0].Proof)
+	require.Equal(t, proofrequest.StatusFAILED, archived[1].Status)
+}
@@ -0,0 +1,417 @@
+package proposer
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"math/big"
+	"net/http"
+	"strconv"
+	"time"
+
+	"github.com/ethereum/go-ethereum/accounts/abi/bind"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/log"
+
+	"github.com/succinctlabs/op-succinct-go/proposer/db"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
+)
+
+const (
+	defaultQueryLimit = 100
+	maxQueryLimit     = 1000
+
+	// usageDayFormat is the format of the UTC days cycle usage is queried and returned by.
+	usageDayFormat = "2006-01-02"
+	// defaultUsageDays is the number of days, up to and including today, cycle usage is returned for by default.
+	defaultUsageDays = 30
+)
+
+// The fields proof requests can be sorted by in the query API.
+var querySortFields = map[string]bool{
+	proofrequest.FieldID:               true,
+	proofrequest.FieldStartBlock:       true,
+	proofrequest.FieldEndBlock:         true,
+	proofrequest.FieldRequestAddedTime: true,
+	proofrequest.FieldLastUpdatedTime:  true,
+}
+
+// QueryAPI serves a read-only view of the proposer's DB and the outputs on the L2OO over HTTP, for dashboards and
+// explorers that shouldn't access the DB directly.
+type QueryAPI struct {
+	log  log.Logger
+	db   *db.ProofDB
+	l2oo L2OOContract
+}
+
+func NewQueryAPI(log log.Logger, proofDB *db.ProofDB, l2oo L2OOContract) *QueryAPI {
+	return &QueryAPI{log: log, db: proofDB, l2oo: l2oo}
+}
+
+// Handler returns the handler of the API's endpoints.
+func (a *QueryAPI) Handler() http.Handler {
+	mux := http.NewServeMux()
+	mux.HandleFunc("GET /api/v1/proofs", a.handleProofs)
+	mux.HandleFunc("GET /api/v1/ranges", a.handleRanges)
+	mux.HandleFunc("GET /api/v1/submissions", a.handleSubmissions)
+	mux.HandleFunc("GET /api/v1/usage", a.handleUsage)
+	return mux
+}
+
+// ProofView is a proof request as returned by the query API, without its proof.
+type ProofView struct {
+	ID               int    `json:"id"`
+	Type             string `json:"type"`
+	StartBlock       uint64 `json:"start_block"`
+	EndBlock         uint64 `json:"end_block"`
+	Status           string `json:"status"`
+	RequestAddedTime uint64 `json:"request_added_time"`
+	ProverRequestID  string `json:"prover_request_id,omitempty"`
+	ProofRequestTime uint64 `json:"proof_request_time,omitempty"`
+	LastUpdatedTime  uint64 `json:"last_updated_time"`
+	L1BlockNumber    uint64 `json:"l1_block_number,omitempty"`
+	L1BlockHash      string `json:"l1_block_hash,omitempty"`
+	WitnessSha256    string `json:"witness_sha256,omitempty"`
+	ErrorCode        string `json:"error_code,omitempty"`
+}
+
+func newProofView(proof *ent.ProofRequest) ProofView {
+	return ProofView{
+		ID:               proof.ID,
+		Type:             proof.Type.String(),
+		StartBlock:       proof.StartBlock,
+		EndBlock:         proof.EndBlock,
+		Status:           proof.Status.String(),
+		RequestAddedTime: proof.RequestAddedTime,
+		ProverRequestID:  proof.ProverRequestID,
+		ProofRequestTime: proof.ProofRequestTime,
+		LastUpdatedTime:  proof.LastUpdatedTime,
+		L1BlockNumber:    proof.L1BlockNumber,
+		L1BlockHash:      proof.L1BlockHash,
+		WitnessSha256:    proof.WitnessSha256,
+		ErrorCode:        proof.ErrorCode,
+	}
+}
+
+// RangeView is a span proof request with the state of its range in the pipeline: its status, or once completed, whether
+// it is only fulfilled, aggregated, or submitted on-chain, as printed by the status command.
+type RangeView struct {
+	ProofView
+	State string `json:"state"`
+}
+
+// SubmissionView is an output on the L2OO, with the completed AGG proof reaching its block if the DB has one. Outputs
+// proposed by another proposer, or whose AGG proof was pruned, have none.
+type SubmissionView struct {
+	OutputIndex   uint64      `json:"output_index"`
+	OutputRoot    common.Hash `json:"output_root"`
+	L2BlockNumber uint64      `json:"l2_block_number"`
+	Timestamp     uint64      `json:"timestamp"`
+	Proof         *ProofView  `json:"proof,omitempty"`
+}
+
+// UsageView is the number and zkVM cycles of the proofs of a type fulfilled on a chain in a UTC day.
+type UsageView struct {
+	Day     string `json:"day"`
+	ChainID uint64 `json:"chain_id"`
+	Type    string `json:"type"`
+	Proofs  uint64 `json:"proofs"`
+	Cycles  uint64 `json:"cycles"`
+}
+
+// UsageResponse is the daily cycle usage from one UTC day to another, inclusive, with its totals.
+type UsageResponse struct {
+	From        string      `json:"from"`
+	To          string      `json:"to"`
+	Days        []UsageView `json:"days"`
+	TotalProofs uint64      `json:"total_proofs"`
+	TotalCycles uint64      `json:"total_cycles"`
+}
+
+// Page is a page of query results, with the number of results across all pages.
+type Page[T any] struct {
+	Items  []T `json:"items"`
+	Total  int `json:"total"`
+	Limit  int `json:"limit"`
+	Offset int `json:"offset"`
+}
+
+// parseProofQuery parses the filters, sort and pagination of a proof request query. Proofs can be filtered by `type`,
+// `status`, the `error_code` they failed with, and the blocks `from` and `to` they must lie within, sorted by `sort` in
+// `order` (`asc` or `desc`), and paginated with `limit` and `offset`.
+func parseProofQuery(r *http.Request) (db.ProofRequestQuery, error) {
+	params := r.URL.Query()
+	var q db.ProofRequestQuery
+	if t := params.Get("type"); t != "" {
+		q.Type = proofrequest.Type(t)
+		if err := proofrequest.TypeValidator(q.Type); err != nil {
+			return q, err
+		}
+	}
+	if s := params.Get("status"); s != "" {
+		q.Status = proofrequest.Status(s)
+		if err := proofrequest.StatusValidator(q.Status); err != nil {
+			return q, err
+		}
+	}
+	q.ErrorCode = params.Get("error_code")
+	var err error
+	if q.FromBlock, err = parseUintParam(params.Get("from")); err != nil {
+		return q, fmt.Errorf("invalid from: %w", err)
+	}
+	if q.ToBlock, err = parseUintParam(params.Get("to")); err != nil {
+		return q, fmt.Errorf("invalid to: %w", err)
+	}
+	if q.SortField = params.Get("sort"); q.SortField != "" && !querySortFields[q.SortField] {
+		return q, fmt.Errorf("invalid sort %q", q.SortField)
+	}
+	if q.Descending, err = parseOrder(params.Get("order")); err != nil {
+		return q, err
+	}
+	if q.Limit, q.Offset, err = parsePagination(r); err != nil {
+		return q, err
+	}
+	return q, nil
+}
+
+func parseUintParam(value string) (uint64, error) {
+	if value == "" {
+		return 0, nil
+	}
+	return strconv.ParseUint(value, 10, 64)
+}
+
+// parseOrder returns whether an `order` parameter sorts descending.
+func parseOrder(order string) (bool, error) {
+	switch order {
+	case "", "asc":
+		return false, nil
+	case "desc":
+		return true, nil
+	default:
+		return false, fmt.Errorf("invalid order %q", order)
+	}
+}
+
+func parsePagination(r *http.Request) (int, int, error) {
+	limit, err := parseUintParam(r.URL.Query().Get("limit"))
+	if err != nil {
+		return 0, 0, fmt.Errorf("invalid limit: %w", err)
+	}
+	if limit == 0 {
+		limit = defaultQueryLimit
+	}
+	if limit > maxQueryLimit {
+		return 0, 0, fmt.Errorf("limit must be at most %d", maxQueryLimit)
+	}
+	offset, err := parseUintParam(r.URL.Query().Get("offset"))
+	if err != nil {
+		return 0, 0, fmt.Errorf("invalid offset: %w", err)
+	}
+	return int(limit), int(offset), nil
+}
+
+func (a *QueryAPI) handleProofs(w http.ResponseWriter, r *http.Request) {
+	q, err := parseProofQuery(r)
+	if err != nil {
+		writeQueryError(w, http.StatusBadRequest, err)
+		return
+	}
+	proofs, total, err := a.db.QueryProofRequests(q)
+	if err != nil {
+		a.internalError(w, err)
+		return
+	}
+	page := Page[ProofView]{Items: make([]ProofView, 0, len(proofs)), Total: total, Limit: q.Limit, Offset: q.Offset}
+	for _, proof := range proofs {
+		page.Items = append(page.Items, newProofView(proof))
+	}
+	writeQueryResponse(w, page)
+}
+
+func (a *QueryAPI) handleRanges(w http.ResponseWriter, r *http.Request) {
+	q, err := parseProofQuery(r)
+	if err != nil {
+		writeQueryError(w, http.StatusBadRequest, err)
+		return
+	}
+	if q.Type == proofrequest.TypeAGG {
+		writeQueryError(w, http.StatusBadRequest, fmt.Errorf("ranges are span proofs"))
+		return
+	}
+	q.Type = proofrequest.TypeSPAN
+	if q.SortField == "" {
+		q.SortField = proofrequest.FieldStartBlock
+	}
+	spans, total, err := a.db.QueryProofRequests(q)
+	if err != nil {
+		a.internalError(w, err)
+		return
+	}
+
+	latestOutput, err := a.l2oo.LatestBlockNumber(&bind.CallOpts{Context: r.Context()})
+	if err != nil {
+		a.internalError(w, fmt.Errorf("failed to get latest output block: %w", err))
+		return
+	}
+	completedAggs, _, err := a.db.QueryProofRequests(db.ProofRequestQuery{Type: proofrequest.TypeAGG, Status: proofrequest.StatusCOMPLETE})
+	if err != nil {
+		a.internalError(w, err)
+		return
+	}
+
+	page := Page[RangeView]{Items: make([]RangeView, 0, len(spans)), Total: total, Limit: q.Limit, Offset: q.Offset}
+	for _, span := range spans {
+		state := span.Status.String()
+		if span.Status == proofrequest.StatusCOMPLETE {
+			state = completedSpanState(span, completedAggs, latestOutput.Uint64())
+		}
+		page.Items = append(page.Items, RangeView{ProofView: newProofView(span), State: state})
+	}
+	writeQueryResponse(w, page)
+}
+
+func (a *QueryAPI) handleSubmissions(w http.ResponseWriter, r *http.Request) {
+	limit, offset, err := parsePagination(r)
+	if err != nil {
+		writeQueryError(w, http.StatusBadRequest, err)
+		return
+	}
+	descending := true
+	if order := r.URL.Query().Get("order"); order != "" {
+		if descending, err = parseOrder(order); err != nil {
+			writeQueryError(w, http.StatusBadRequest, err)
+			return
+		}
+	}
+
+	submissions, total, err := a.submissions(r.Context(), limit, offset, descending)
+	if err != nil {
+		a.internalError(w, err)
+		return
+	}
+	writeQueryResponse(w, Page[SubmissionView]{Items: submissions, Total: total, Limit: limit, Offset: offset})
+}
+
+// submissions returns a page of the outputs on the L2OO, newest first if descending, with the completed AGG proofs
+// reaching them, and the number of outputs.
+func (a *QueryAPI) submissions(ctx context.Context, limit, offset int, descending bool) ([]SubmissionView, int, error) {
+	nextIndex, err := a.l2oo.NextOutputIndex(&bind.CallOpts{Context: ctx})
+	if err != nil {
+		return nil, 0, fmt.Errorf("failed to get next output index: %w", err)
+	}
+	total := int(nextIndex.Uint64())
+
+	submissions := make([]SubmissionView, 0, limit)
+	var endBlocks []uint64
+	for i := offset; i < total && i < offset+limit; i++ {
+		index := uint64(i)
+		if descending {
+			index = uint64(total - 1 - i)
+		}
+		output, err := a.l2oo.GetL2Output(&bind.CallOpts{Context: ctx}, new(big.Int).SetUint64(index))
+		if err != nil {
+			return nil, 0, fmt.Errorf("failed to get output %d: %w", index, err)
+		}
+		submissions = append(submissions, SubmissionView{
+			OutputIndex:   index,
+			OutputRoot:    output.OutputRoot,
+			L2BlockNumber: output.L2BlockNumber.Uint64(),
+			Timestamp:     output.Timestamp.Uint64(),
+		})
+		endBlocks = append(endBlocks, output.L2BlockNumber.Uint64())
+	}
+	if len(endBlocks) == 0 {
+		return submissions, total, nil
+	}
+
+	aggs, err := a.db.GetCompletedAggProofsEndingAt(endBlocks)
+	if err != nil {
+		return nil, 0, err
+	}
+	proofs := make(map[uint64]*ProofView, len(aggs))
+	for _, agg := range aggs {
+		view := newProofView(agg)
+		proofs[agg.EndBlock] = &view
+	}
+	for i := range submissions {
+		submissions[i].Proof = proofs[submissions[i].L2BlockNumber]
+	}
+	return submissions, total, nil
+}
+
+// parseUsageDays parses the UTC days `from` and `to` of a cycle usage query, formatted as YYYY-MM-DD. They default to
+// the last 30 days, up to and including today.
+func parseUsageDays(r *http.Request, now time.Time) (time.Time, time.Time, error) {
+	params := r.URL.Query()
+	to := db.UsageDay(now)
+	if value := params.Get("to"); value != "" {
+		day, err := time.Parse(usageDayFormat, value)
+		if err != nil {
+			return time.Time{}, time.Time{}, fmt.Errorf("invalid to: %w", err)
+		}
+		to = day
+	}
+	from := to.AddDate(0, 0, 1-defaultUsageDays)
+	if value := params.Get("from"); value != "" {
+		day, err := time.Parse(usageDayFormat, value)
+		if err != nil {
+			return time.Time{}, time.Time{}, fmt.Errorf("invalid from: %w", err)
+		}
+		from = day
+	}
+	if from.After(to) {
+		return time.Time{}, time.Time{}, fmt.Errorf("from must not be after to")
+	}
+	return from, to, nil
+}
+
+func (a *QueryAPI) handleUsage(w http.ResponseWriter, r *http.Request) {
+	from, to, err := parseUsageDays(r, time.Now())
+	if err != nil {
+		writeQueryError(w, http.StatusBadRequest, err)
+		return
+	}
+	usage, err := a.db.GetCycleUsage(from, to)
+	if err != nil {
+		a.internalError(w, err)
+		return
+	}
+
+	response := UsageResponse{
+		From: from.Format(usageDayFormat),
+		To:   to.Format(usageDayFormat),
+		Days: make([]UsageView, 0, len(usage)),
+	}
+	for _, day := range usage {
+		response.Days = append(response.Days, UsageView{
+			Day:     time.Unix(int64(day.Day), 0).UTC().Format(usageDayFormat),
+			ChainID: day.ChainID,
+			Type:    day.Type.String(),
+			Proofs:  day.Proofs,
+			Cycles:  day.Cycles,
+		})
+		response.TotalProofs += day.Proofs
+		response.TotalCycles += day.Cycles
+	}
+	writeQueryResponse(w, response)
+}
+
+func (a *QueryAPI) internalError(w http.ResponseWriter, err error) {
+	a.log.Error("query API request failed", "err", err)
+	writeQueryError(w, http.StatusInternalServerError, err)
+}
+
+func writeQueryResponse(w http.ResponseWriter, body any) {
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(body); err != nil {
+		log.Error("failed to write query API response", "err", err)
+	}
+}
+
+func writeQueryError(w http.ResponseWriter, status int, err error) {
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(status)
+	json.NewEncoder(w).Encode(map[string]string{"error": err.Error()})
+}
@@ -0,0 +1,116 @@
+package proposer
+
+import (
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/ethereum/go-ethereum/log"
+	"github.com/stretchr/testify/require"
+	"github.com/succinctlabs/op-succinct-go/proposer/db"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
+)
+
+func TestQueryAPIProofs(t *testing.T) {
+	proofDB, err := db.InitDB(filepath.Join(t.TempDir(), "proofs.db"), false)
+	require.NoError(t, err)
+	defer proofDB.CloseDB()
+	for _, span := range [][2]uint64{{100, 150}, {150, 200}, {200, 250}} {
+		require.NoError(t, proofDB.NewEntry(proofrequest.TypeSPAN, span[0], span[1]))
+	}
+	require.NoError(t, proofDB.NewEntry(proofrequest.TypeAGG, 100, 200))
+	handler := NewQueryAPI(log.New(), proofDB, nil).Handler()
+
+	get := func(url string) (int, Page[ProofView]) {
+		rec := httptest.NewRecorder()
+		handler.ServeHTTP(rec, httptest.NewRequest(http.MethodGet, url, nil))
+		var page Page[ProofView]
+		if rec.Code == http.StatusOK {
+			require.NoError(t, json.Unmarshal(rec.Body.Bytes(), &page))
+		}
+		return rec.Code, page
+	}
+
+	code, page := get("/api/v1/proofs?type=SPAN&sort=start_block&order=desc&limit=2")
+	require.Equal(t, http.StatusOK, code)
+	require.Equal(t, 3, page.Total)
+	require.Len(t, page.Items, 2)
+	require.Equal(t, uint64(200), page.Items[0].StartBlock)
+	require.Equal(t, uint64(150), page.Items[1].StartBlock)
+
+	code, page = get("/api/v1/proofs?type=SPAN&sort=start_block&order=desc&limit=2&offset=2")
+	require.Equal(t, http.StatusOK, code)
+	require.Len(t, page.Items, 1)
+	require.Equal(t, uint64(100), page.Items[0].StartBlock)
+
+	// Filters select the requests within the blocks.
+	code, page = get("/api/v1/proofs?from=100&to=200&status=UNREQ")
+	require.Equal(t, http.StatusOK, code)
+	require.Equal(t, 3, page.Total)
+
+	// Failed requests can be selected by their error code.
+	require.NoError(t, proofDB.SetFailed(1, ErrorCodeBlobPruned))
+	code, page = get("/api/v1/proofs?status=FAILED&error_code=E_BLOB_PRUNED")
+	require.Equal(t, http.StatusOK, code)
+	require.Equal(t, 1, page.Total)
+	require.Equal(t, ErrorCodeBlobPruned, page.Items[0].ErrorCode)
+
+	for _, url := range []string{
+		"/api/v1/proofs?type=FOO",
+		"/api/v1/proofs?status=DONE",
+		"/api/v1/proofs?sort=proof",
+		"/api/v1/proofs?order=up",
+		"/api/v1/proofs?limit=5000",
+		"/api/v1/proofs?from=-1",
+	} {
+		code, _ := get(url)
+		require.Equal(t, http.StatusBadRequest, code, url)
+	}
+}
+
+func TestQueryAPIUsage(t *testing.T) {
+	proofDB, err := db.InitDB(filepath.Join(t.TempDir(), "proofs.db"), false)
+	require.NoError(t, err)
+	defer proofDB.CloseDB()
+	require.NoError(t, proofDB.NewEntry(proofrequest.TypeSPAN, 100, 150))
+	require.NoError(t, proofDB.SetProving(1, []byte{0xab}, ""))
+	fulfilledAt := time.Date(2024, 6, 1, 12, 0, 0, 0, time.UTC)
+	require.NoError(t, proofDB.AddFulfilledProof(1, []byte{0x01}, &db.FulfilledUsage{ChainID: 10, Cycles: 1000, FulfilledAt: fulfilledAt}))
+	handler := NewQueryAPI(log.New(), proofDB, nil).Handler()
+
+	get := func(url string) (int, UsageResponse) {
+		rec := httptest.NewRecorder()
+		handler.ServeHTTP(rec, httptest.NewRequest(http.MethodGet, url, nil))
+		var response UsageResponse
+		if rec.Code == http.StatusOK {
+			require.NoError(t, json.Unmarshal(rec.Body.Bytes(), &response))
+		}
+		return rec.Code, response
+	}
+
+	code, response := get("/api/v1/usage?from=2024-05-01&to=2024-06-01")
+	require.Equal(t, http.StatusOK, code)
+	require.Equal(t, []UsageView{{Day: "2024-06-01", ChainID: 10, Type: "SPAN", Proofs: 1, Cycles: 1000}}, response.Days)
+	require.Equal(t, uint64(1), response.TotalProofs)
+	require.Equal(t, uint64(1000), response.TotalCycles)
+
+	// The default range is the last 30 days.
+	code, response = get("/api/v1/usage?to=2024-06-30")
+	require.Equal(t, http.StatusOK, code)
+	require.Equal(t, "2024-06-01", response.From)
+	require.Len(t, response.Days, 1)
+	code, response = get("/api/v1/usage?to=2024-07-01")
+	require.Equal(t, http.StatusOK, code)
+	require.Empty(t, response.Days)
+
+	for _, url := range []string{
+		"/api/v1/usage?from=yesterday",
+		"/api/v1/usage?from=2024-06-02&to=2024-06-01",
+	} {
+		code, _ := get(url)
+		require.Equal(t, http.StatusBadRequest, code, url)
+	}
+}
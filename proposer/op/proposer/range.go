@@ -153,23 +153,70 @@ func (l *L2OutputSubmitter) SplitRangeBasic(start, end uint64) []Span {
 	return spans
 }
 
+// L2OOState is the L2 Output Oracle's record of which L2 blocks have been proposed, which the
+// proposer derives the ranges to prove from rather than from its own configuration.
+type L2OOState struct {
+	// LatestBlockNumber is the L2 block of the latest proposed output.
+	LatestBlockNumber  uint64
+	// NextBlockNumber is the earliest L2 block the next output can be proposed for.
+	NextBlockNumber    uint64
+	// SubmissionInterval is the minimum number of L2 blocks between proposed outputs.
+	SubmissionInterval uint64
+}
+
+// FetchL2OOState reads the L2OO's latest and next block numbers and submission interval.
+func (l *L2OutputSubmitter) FetchL2OOState(ctx context.Context) (L2OOState, error) {
+	callOpts := &bind.CallOpts{Context: ctx}
+	latest, err := l.l2ooContract.LatestBlockNumber(callOpts)
+	if err != nil {
+		return L2OOState{}, fmt.Errorf("failed to get latest block number: %w", err)
+	}
+	next, err := l.l2ooContract.NextBlockNumber(callOpts)
+	if err != nil {
+		return L2OOState{}, fmt.Errorf("failed to get next block number: %w", err)
+	}
+	interval, err := l.l2ooContract.SubmissionInterval(callOpts)
+	if err != nil {
+		return L2OOState{}, fmt.Errorf("failed to get submission interval: %w", err)
+	}
+	return L2OOState{
+		LatestBlockNumber:  latest.Uint64(),
+		NextBlockNumber:    next.Uint64(),
+		SubmissionInterval: interval.Uint64(),
+	}, nil
+}
+
+// NextRangeStart returns the L2 block to start the next span proofs from. Span proofs continue from
+// the end of the latest requested span, unless the contract's latest block number is past it (e.g.
+// after another proposer submitted an output), in which case the requested spans before it can
+// never be proposed and proving restarts from the contract's latest block number.
+func NextRangeStart(state L2OOState, latestRequestedEnd uint64, hasRequests bool) uint64 {
+	if !hasRequests || latestRequestedEnd < state.LatestBlockNumber {
+		return state.LatestBlockNumber
+	}
+	return latestRequestedEnd
+}
+
 func (l *L2OutputSubmitter) GetRangeProofBoundaries(ctx context.Context) error {
+	state, err := l.FetchL2OOState(ctx)
+	if err != nil {
+		return err
+	}
+
 	// nextBlock is equal to the highest value in the `EndBlock` column of the DB, plus 1.
 	latestL2EndBlock, err := l.db.GetLatestEndBlock()
-	if err != nil {
-		if ent.IsNotFound(err) {
-			latestEndBlockU256, err := l.l2ooContract.LatestBlockNumber(&bind.CallOpts{Context: ctx})
-			if err != nil {
-				return fmt.Errorf("failed to get latest output index: %w", err)
-			} else {
-				latestL2EndBlock = latestEndBlockU256.Uint64()
-			}
-		} else {
-			l.Log.Error("failed to get latest end requested", "err", err)
-			return err
-		}
+	hasRequests := err == nil
+	if err != nil && !ent.IsNotFound(err) {
+		l.Log.Error("failed to get latest end requested", "err", err)
+		return err
+	}
+	newL2StartBlock := NextRangeStart(state, latestL2EndBlock, hasRequests)
+	l.Log.Debug("Deriving span proof ranges from the L2OO", "latestBlockNumber", state.LatestBlockNumber,
+		"nextBlockNumber", state.NextBlockNumber, "submissionInterval", state.SubmissionInterval, "start", newL2StartBlock)
+	if hasRequests && newL2StartBlock != latestL2EndBlock {
+		l.Log.Warn("Contract's latest block number is past the latest requested span, restarting from it",
+			"latestRequestedEnd", latestL2EndBlock, "latestBlockNumber", state.LatestBlockNumber)
 	}
-	newL2StartBlock := latestL2EndBlock
 
 	rollupClient, err := dial.DialRollupClientWithTimeout(ctx, dial.DefaultDialTimeout, l.Log, l.Cfg.RollupRpc)
 	if err != nil {
@@ -182,8 +229,15 @@ func (l *L2OutputSubmitter) GetRangeProofBoundaries(ctx context.Context) error {
 		l.Log.Error("proposer unable to get sync status", "err", err)
 		return err
 	}
-	// Note: Originally, this used the L1 finalized block. However, to satisfy the new API, we now use the L2 finalized block.
+	// Request spans up to the highest block whose batches are all in finalized L1 blocks. If the
+	// server can't tell, fall back to the L2 finalized block, which is derived from finalized L1
+	// blocks but can lag behind.
 	newL2EndBlock := status.FinalizedL2.Number
+	if head, err := l.GetProvableHead(); err != nil {
+		l.Log.Warn("failed to get the provable head, using the L2 finalized block", "err", err)
+	} else {
+		newL2EndBlock = head.L2Block
+	}
 
 	spans := l.SplitRangeBasic(newL2StartBlock, newL2EndBlock)
 
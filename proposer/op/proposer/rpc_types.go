@@ -1,13 +1,35 @@
 package proposer
 
+import "github.com/ethereum/go-ethereum/common"
+
 type SpanProofRequest struct {
 	Start uint64 `json:"start"`
 	End   uint64 `json:"end"`
+	// The hash of the witness the span was last proven from, if it is a retry. The server reuses its cached witness of
+	// the span only if it still has this hash.
+	WitnessSha256 string `json:"witness_sha256,omitempty"`
 }
 
 type AggProofRequest struct {
 	Subproofs [][]byte `json:"subproofs"`
 	L1Head    string   `json:"head"`
+	// The gaps in the chain of span proofs force-resolved by governance, for emergency catch-up. Unset for a normal
+	// aggregation.
+	CheckpointOverrides *CheckpointOverrides `json:"checkpoint_overrides,omitempty"`
+}
+
+// CheckpointOverrides are the gaps in the chain of span proofs an AGG proof aggregates over, with the justification
+// governance signed for skipping them. The L2OO only accepts the proof once its owner approved their hash.
+type CheckpointOverrides struct {
+	Gaps          []CheckpointGap `json:"gaps"`
+	Justification string          `json:"justification"`
+}
+
+// CheckpointGap is a gap from the output root the chain of span proofs ends at, or the L2OO's latest output for the
+// first gap, to the output root the next span proof starts from.
+type CheckpointGap struct {
+	FromRoot common.Hash `json:"from_root"`
+	ToRoot   common.Hash `json:"to_root"`
 }
 
 type ValidateConfigRequest struct {
@@ -20,10 +42,34 @@ type ValidateConfigResponse struct {
 	RangeVkeyValid        bool `json:"range_vkey_valid"`
 }
 
+// ProvableHeadResponse is the response type for the `/provable_head` RPC from the op-succinct-server.
+type ProvableHeadResponse struct {
+	L2Block     uint64 `json:"l2_block"`
+	L1Block     uint64 `json:"l1_block"`
+	L1BlockHash string `json:"l1_block_hash"`
+	Source      string `json:"source"` // "safe_db" or "l2_finalized"
+}
+
+// PendingSpanProofs is the request type for the `/aggregation_decision` RPC from the op-succinct-server: the span
+// proofs contiguous with the L2OO's latest output that aren't aggregated yet.
+type PendingSpanProofs struct {
+	Count              uint64 `json:"count"`
+	OldestProofAgeSecs uint64 `json:"oldest_proof_age_secs"`
+}
+
+// AggregationDecision is the response type for the `/aggregation_decision` RPC from the op-succinct-server: whether
+// the server's aggregation policy aggregates the pending span proofs now, and why.
+type AggregationDecision struct {
+	Aggregate bool   `json:"aggregate"`
+	Reason    string `json:"reason"`
+}
+
 // WitnessGenerationResponse is the response type for the `request_span_proof` and `request_agg_proof`
 // RPCs from the op-succinct-server.
 type WitnessGenerationResponse struct {
 	ProofID []byte `json:"proof_id"`
+	// The SHA-256 hash of the witness a span proof is proven from. Empty for AGG proofs.
+	WitnessSha256 string `json:"witness_sha256,omitempty"`
 }
 
 // UnclaimDescription is the description of why a proof was unclaimed.
@@ -79,5 +125,20 @@ type ProofStatusResponse struct {
 	FulfillmentStatus SP1FulfillmentStatus `json:"fulfillment_status"`
 	ExecutionStatus   SP1ExecutionStatus   `json:"execution_status"`
 	Proof             []byte               `json:"proof"`
+	// ProofURL is the path the proof is downloaded from, relative to the server's URL, when it is
+	// too large to return inline. Proof is empty when it is set.
+	ProofURL          string               `json:"proof_url,omitempty"`
+	// ErrorCode is why the proof is unfulfillable, if the server knows.
+	ErrorCode string `json:"error_code,omitempty"`
+	// Metadata is the prover network's record of the proof, once the server has fetched it.
+	Metadata *ProofMetadata `json:"metadata,omitempty"`
+}
+
+// ProofMetadata is the prover network's record of a proof request, as returned by the op-succinct-server.
+type ProofMetadata struct {
+	RequestedAt uint64  `json:"requested_at"`
+	FulfilledAt *uint64 `json:"fulfilled_at,omitempty"`
+	Cycles      *uint64 `json:"cycles,omitempty"`
+	GasUsed     *uint64 `json:"gas_used,omitempty"`
 }
 
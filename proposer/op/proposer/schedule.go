@@ -0,0 +1,155 @@
+package proposer
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// SubmissionWindow is a recurring window during which no outputs are proposed on-chain, e.g. for maintenance or
+// governance votes. It is written as a cron expression for its start, in UTC, followed by its duration:
+//
+//	0 14 * * 2 2h    every Tuesday from 14:00 to 16:00 UTC
+//	30 0 1 * * 45m   the first day of every month from 00:30 to 01:15 UTC
+//
+// The cron fields are minute, hour, day of month, month and day of week (0 is Sunday). Each accepts `*`, a value, a
+// range `a-b`, a step `*/n` or `a-b/n`, and comma-separated lists of those. Unlike cron, a start time matches only
+// when all five fields match.
+type SubmissionWindow struct {
+	spec     string
+	minute   uint64
+	hour     uint64
+	day      uint64
+	month    uint64
+	weekday  uint64
+	duration time.Duration
+}
+
+// ParseSubmissionWindows parses windows separated by semicolons. An empty string has no windows.
+func ParseSubmissionWindows(specs string) ([]SubmissionWindow, error) {
+	var windows []SubmissionWindow
+	for _, spec := range strings.Split(specs, ";") {
+		spec = strings.TrimSpace(spec)
+		if spec == "" {
+			continue
+		}
+		window, err := parseSubmissionWindow(spec)
+		if err != nil {
+			return nil, fmt.Errorf("invalid submission window %q: %w", spec, err)
+		}
+		windows = append(windows, window)
+	}
+	return windows, nil
+}
+
+func parseSubmissionWindow(spec string) (SubmissionWindow, error) {
+	fields := strings.Fields(spec)
+	if len(fields) != 6 {
+		return SubmissionWindow{}, fmt.Errorf("expected 5 cron fields and a duration, got %d fields", len(fields))
+	}
+	window := SubmissionWindow{spec: spec}
+	bounds := []struct {
+		mask     *uint64
+		min, max int
+	}{
+		{&window.minute, 0, 59},
+		{&window.hour, 0, 23},
+		{&window.day, 1, 31},
+		{&window.month, 1, 12},
+		{&window.weekday, 0, 6},
+	}
+	for i, b := range bounds {
+		mask, err := parseCronField(fields[i], b.min, b.max)
+		if err != nil {
+			return SubmissionWindow{}, err
+		}
+		*b.mask = mask
+	}
+	duration, err := time.ParseDuration(fields[5])
+	if err != nil {
+		return SubmissionWindow{}, err
+	}
+	if duration < time.Minute {
+		return SubmissionWindow{}, fmt.Errorf("duration %s is shorter than a minute", duration)
+	}
+	window.duration = duration
+	return window, nil
+}
+
+// parseCronField returns the values matched by a cron field as a bit mask.
+func parseCronField(field string, min, max int) (uint64, error) {
+	var mask uint64
+	for _, part := range strings.Split(field, ",") {
+		step := 1
+		if rangePart, stepPart, ok := strings.Cut(part, "/"); ok {
+			var err error
+			if step, err = strconv.Atoi(stepPart); err != nil || step < 1 {
+				return 0, fmt.Errorf("invalid step in %q", part)
+			}
+			part = rangePart
+		}
+
+		lo, hi := min, max
+		if part != "*" {
+			var err error
+			if from, to, ok := strings.Cut(part, "-"); ok {
+				lo, err = strconv.Atoi(from)
+				if err == nil {
+					hi, err = strconv.Atoi(to)
+				}
+			} else {
+				lo, err = strconv.Atoi(part)
+				hi = lo
+				if step > 1 {
+					hi = max
+				}
+			}
+			if err != nil {
+				return 0, fmt.Errorf("invalid value in %q", part)
+			}
+		}
+		if lo < min || hi > max || lo > hi {
+			return 0, fmt.Errorf("%q is out of the range %d-%d", part, min, max)
+		}
+		for v := lo; v <= hi; v += step {
+			mask |= 1 << v
+		}
+	}
+	return mask, nil
+}
+
+// starts reports whether the window starts at the minute `t`.
+func (w SubmissionWindow) starts(t time.Time) bool {
+	return w.minute&(1<<t.Minute()) != 0 &&
+		w.hour&(1<<t.Hour()) != 0 &&
+		w.day&(1<<t.Day()) != 0 &&
+		w.month&(1<<int(t.Month())) != 0 &&
+		w.weekday&(1<<int(t.Weekday())) != 0
+}
+
+// end returns when the window containing `t` ends, if `t` is in the window.
+func (w SubmissionWindow) end(t time.Time) (time.Time, bool) {
+	t = t.UTC()
+	for start := t.Truncate(time.Minute); start.After(t.Add(-w.duration)); start = start.Add(-time.Minute) {
+		if w.starts(start) {
+			return start.Add(w.duration), true
+		}
+	}
+	return time.Time{}, false
+}
+
+func (w SubmissionWindow) String() string {
+	return w.spec
+}
+
+// submissionsDeferredUntil returns when the last of the windows containing `t` ends, if any does.
+func submissionsDeferredUntil(windows []SubmissionWindow, t time.Time) (time.Time, bool) {
+	var until time.Time
+	for _, window := range windows {
+		if end, ok := window.end(t); ok && end.After(until) {
+			until = end
+		}
+	}
+	return until, !until.IsZero()
+}
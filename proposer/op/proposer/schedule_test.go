@@ -0,0 +1,59 @@
+package proposer
+
+import (
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/require"
+)
+
+func TestParseSubmissionWindows(t *testing.T) {
+	windows, err := ParseSubmissionWindows("0 14 * * 2 2h; */15 0-6/2 1,15 * * 30m;")
+	require.NoError(t, err)
+	require.Len(t, windows, 2)
+
+	empty, err := ParseSubmissionWindows("")
+	require.NoError(t, err)
+	require.Empty(t, empty)
+
+	for _, spec := range []string{
+		"0 14 * * 2",      // No duration.
+		"60 14 * * 2 2h",  // Minute out of range.
+		"0 14 * * 7 2h",   // Weekday out of range.
+		"0 14 * * */0 2h", // Zero step.
+		"0 14 * * 2 10s",  // Shorter than a minute.
+	} {
+		_, err := ParseSubmissionWindows(spec)
+		require.Error(t, err, spec)
+	}
+}
+
+func TestSubmissionsDeferredUntil(t *testing.T) {
+	windows, err := ParseSubmissionWindows("0 14 * * 2 2h; 30 15 * * * 1h")
+	require.NoError(t, err)
+
+	// 2024-01-02 is a Tuesday.
+	tuesday := func(hour, minute int) time.Time {
+		return time.Date(2024, 1, 2, hour, minute, 0, 0, time.UTC)
+	}
+
+	_, deferred := submissionsDeferredUntil(windows, tuesday(13, 59))
+	require.False(t, deferred)
+
+	until, deferred := submissionsDeferredUntil(windows, tuesday(14, 0))
+	require.True(t, deferred)
+	require.Equal(t, tuesday(16, 0), until)
+
+	// Overlapping windows defer until the last one ends.
+	until, deferred = submissionsDeferredUntil(windows, tuesday(15, 45))
+	require.True(t, deferred)
+	require.Equal(t, tuesday(16, 30), until)
+
+	_, deferred = submissionsDeferredUntil(windows, tuesday(16, 30))
+	require.False(t, deferred)
+
+	// Only the daily window applies on Wednesday.
+	until, deferred = submissionsDeferredUntil(windows, tuesday(15, 45).Add(24*time.Hour))
+	require.True(t, deferred)
+	require.Equal(t, tuesday(16, 30).Add(24*time.Hour), until)
+}
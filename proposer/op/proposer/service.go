@@ -5,6 +5,8 @@ import (
 	"errors"
 	"fmt"
 	"io"
+	"net"
+	"strconv"
 	"strings"
 	"sync/atomic"
 	"time"
@@ -64,6 +66,14 @@ type ProposerConfig struct {
 	OPSuccinctServerUrl        string
 	MaxConcurrentProofRequests uint64
 	Mock                       bool
+	FulfilledRetentionDays     uint64
+	FailedRetentionDays        uint64
+	PruneArchiveDir            string
+	SafeHeadPollInterval       time.Duration
+	SubmissionWindows          []SubmissionWindow
+	MaxProposalsPerTx          uint64
+	// The checkpoint overrides to aggregate over for emergency catch-up, if any.
+	CheckpointOverrides *CheckpointOverrides
 }
 
 type ProposerService struct {
@@ -83,6 +93,7 @@ type ProposerService struct {
 	pprofService *oppprof.Service
 	metricsSrv   *httputil.HTTPServer
 	rpcServer    *oprpc.Server
+	queryApiSrv  *httputil.HTTPServer
 
 	balanceMetricer io.Closer
 
@@ -125,6 +136,21 @@ func (ps *ProposerService) initFromCLIConfig(ctx context.Context, version string
 	ps.L2ChainID = cfg.L2ChainID
 	ps.MaxConcurrentProofRequests = cfg.MaxConcurrentProofRequests
 	ps.Mock = cfg.Mock
+	ps.FulfilledRetentionDays = cfg.FulfilledRetentionDays
+	ps.FailedRetentionDays = cfg.FailedRetentionDays
+	ps.PruneArchiveDir = cfg.PruneArchiveDir
+	ps.SafeHeadPollInterval = cfg.SafeHeadPollInterval
+	ps.MaxProposalsPerTx = cfg.MaxProposalsPerTx
+	submissionWindows, err := ParseSubmissionWindows(cfg.SubmissionWindows)
+	if err != nil {
+		return err
+	}
+	ps.SubmissionWindows = submissionWindows
+	if cfg.CheckpointOverridesFile != "" {
+		if ps.CheckpointOverrides, err = LoadCheckpointOverrides(cfg.CheckpointOverridesFile); err != nil {
+			return err
+		}
+	}
 
 	ps.initL2ooAddress(cfg)
 	ps.initDGF(cfg)
@@ -148,6 +174,9 @@ func (ps *ProposerService) initFromCLIConfig(ctx context.Context, version string
 	if err := ps.initRPCServer(cfg); err != nil {
 		return fmt.Errorf("failed to start RPC server: %w", err)
 	}
+	if err := ps.initQueryApiServer(cfg); err != nil {
+		return fmt.Errorf("failed to start query API server: %w", err)
+	}
 
 	ps.Metrics.RecordInfo(ps.Version)
 	ps.Metrics.RecordUp()
@@ -291,6 +320,25 @@ func (ps *ProposerService) initRPCServer(cfg *CLIConfig) error {
 	return nil
 }
 
+// initQueryApiServer serves the read-only query API over the driver's DB, if a port is configured.
+func (ps *ProposerService) initQueryApiServer(cfg *CLIConfig) error {
+	if cfg.QueryApiPort == 0 {
+		return nil
+	}
+	addr := cfg.QueryApiAddr
+	if addr == "" {
+		addr = cfg.RPCConfig.ListenAddr
+	}
+	api := NewQueryAPI(ps.Log, &ps.driver.db, ps.driver.l2ooContract)
+	srv, err := httputil.StartHTTPServer(net.JoinHostPort(addr, strconv.Itoa(cfg.QueryApiPort)), api.Handler())
+	if err != nil {
+		return err
+	}
+	ps.Log.Info("Started query API server", "addr", srv.Addr())
+	ps.queryApiSrv = srv
+	return nil
+}
+
 // Start runs once upon start of the proposer lifecycle,
 // and starts L2Output-submission work if the proposer is configured to start submit data on startup.
 func (ps *ProposerService) Start(_ context.Context) error {
@@ -330,6 +378,11 @@ func (ps *ProposerService) Stop(ctx context.Context) error {
 			result = errors.Join(result, fmt.Errorf("failed to stop RPC server: %w", err))
 		}
 	}
+	if ps.queryApiSrv != nil {
+		if err := ps.queryApiSrv.Stop(ctx); err != nil {
+			result = errors.Join(result, fmt.Errorf("failed to stop query API server: %w", err))
+		}
+	}
 	if ps.pprofService != nil {
 		if err := ps.pprofService.Stop(ctx); err != nil {
 			result = errors.Join(result, fmt.Errorf("failed to stop PProf server: %w", err))
@@ -0,0 +1,181 @@
+package proposer
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"text/tabwriter"
+	"time"
+
+	"github.com/ethereum-optimism/optimism/op-service/dial"
+	"github.com/ethereum/go-ethereum/accounts/abi/bind"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/ethereum/go-ethereum/ethclient"
+	"github.com/urfave/cli/v2"
+
+	opsuccinctbindings "github.com/succinctlabs/op-succinct-go/bindings"
+	"github.com/succinctlabs/op-succinct-go/proposer/db"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent/proofrequest"
+	"github.com/succinctlabs/op-succinct-go/proposer/flags"
+)
+
+var statusFailuresFlag = &cli.IntFlag{
+	Name:  "failures",
+	Usage: "The number of recent failures to print",
+	Value: 5,
+}
+
+// StatusCommand prints the state of the proving pipeline from the proposer's DB and the L2OO, as a single
+// view for on-call engineers. It only reads the DB, so it can be run next to a running proposer.
+var StatusCommand = &cli.Command{
+	Name:   "status",
+	Usage:  "Print the state of the proving pipeline",
+	Flags:  []cli.Flag{flags.L1EthRpcFlag, flags.RollupRpcFlag, flags.L2OOAddressFlag, flags.DbPathFlag, statusFailuresFlag},
+	Action: printStatus,
+}
+
+// The states of a span proof, from requested to proposed on-chain. Completed span proofs are split by
+// whether a completed AGG proof covers them, and whether the L2OO is past them.
+const (
+	spanFulfilled  = "FULFILLED"
+	spanAggregated = "AGGREGATED"
+	spanSubmitted  = "SUBMITTED"
+)
+
+func printStatus(cliCtx *cli.Context) error {
+	ctx := cliCtx.Context
+
+	rollupClient, err := dial.DialRollupClientWithTimeout(ctx, dial.DefaultDialTimeout, nil, cliCtx.String(flags.RollupRpcFlag.Name))
+	if err != nil {
+		return fmt.Errorf("failed to dial rollup client: %w", err)
+	}
+	rollupConfig, err := rollupClient.RollupConfig(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to get rollup config: %w", err)
+	}
+	dbPath := filepath.Join(cliCtx.String(flags.DbPathFlag.Name), fmt.Sprintf("%d", rollupConfig.L2ChainID.Uint64()), "proofs.db")
+	if _, err := os.Stat(dbPath); err != nil {
+		return fmt.Errorf("no proposer DB at %s: %w", dbPath, err)
+	}
+	proofDB, err := db.InitDB(dbPath, true)
+	if err != nil {
+		return err
+	}
+	defer proofDB.CloseDB()
+
+	l1Client, err := ethclient.DialContext(ctx, cliCtx.String(flags.L1EthRpcFlag.Name))
+	if err != nil {
+		return fmt.Errorf("failed to dial L1 client: %w", err)
+	}
+	l2oo, err := opsuccinctbindings.NewOPSuccinctL2OutputOracleCaller(common.HexToAddress(cliCtx.String(flags.L2OOAddressFlag.Name)), l1Client)
+	if err != nil {
+		return fmt.Errorf("failed to bind L2OO: %w", err)
+	}
+	latestOutput, err := l2oo.LatestBlockNumber(&bind.CallOpts{Context: ctx})
+	if err != nil {
+		return fmt.Errorf("failed to get latest output block: %w", err)
+	}
+	latestProven, err := proofDB.GetMaxContiguousSpanProofRange(latestOutput.Uint64())
+	if err != nil {
+		return err
+	}
+
+	spanCounts := make(map[string]int)
+	aggCounts := make(map[string]int)
+	for _, status := range []proofrequest.Status{proofrequest.StatusUNREQ, proofrequest.StatusWITNESSGEN, proofrequest.StatusPROVING, proofrequest.StatusFAILED} {
+		proofs, err := proofDB.GetAllProofsWithStatus(status)
+		if err != nil {
+			return err
+		}
+		for _, proof := range proofs {
+			if proof.Type == proofrequest.TypeSPAN {
+				spanCounts[status.String()]++
+			} else {
+				aggCounts[status.String()]++
+			}
+		}
+	}
+	completed, err := proofDB.GetAllProofsWithStatus(proofrequest.StatusCOMPLETE)
+	if err != nil {
+		return err
+	}
+	var completedAggs []*ent.ProofRequest
+	for _, proof := range completed {
+		if proof.Type == proofrequest.TypeAGG {
+			completedAggs = append(completedAggs, proof)
+			aggCounts[proofrequest.StatusCOMPLETE.String()]++
+		}
+	}
+	for _, proof := range completed {
+		if proof.Type == proofrequest.TypeSPAN {
+			spanCounts[completedSpanState(proof, completedAggs, latestOutput.Uint64())]++
+		}
+	}
+
+	oldestPending, err := proofDB.GetOldestPendingRequest()
+	if err != nil {
+		return err
+	}
+	failures, err := proofDB.GetRecentFailures(cliCtx.Int(statusFailuresFlag.Name))
+	if err != nil {
+		return err
+	}
+
+	now := time.Now()
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	fmt.Fprintf(w, "Latest on-chain output:\tL2 block %d\n", latestOutput.Uint64())
+	fmt.Fprintf(w, "Latest proven block:\tL2 block %d\n", latestProven)
+	fmt.Fprintln(w, "\nSpan proofs:")
+	for _, state := range []string{"UNREQ", "WITNESSGEN", "PROVING", spanFulfilled, spanAggregated, spanSubmitted, "FAILED"} {
+		fmt.Fprintf(w, "  %s\t%d\n", state, spanCounts[state])
+	}
+	fmt.Fprintln(w, "\nAggregation proofs:")
+	for _, state := range []string{"UNREQ", "WITNESSGEN", "PROVING", "COMPLETE", "FAILED"} {
+		fmt.Fprintf(w, "  %s\t%d\n", state, aggCounts[state])
+	}
+	fmt.Fprintln(w)
+	if oldestPending != nil {
+		fmt.Fprintf(w, "Oldest pending request:\t%s, added %s ago\n", describeRequest(oldestPending), age(now, oldestPending.RequestAddedTime))
+	} else {
+		fmt.Fprintln(w, "Oldest pending request:\tnone")
+	}
+	fmt.Fprintln(w, "\nRecent failures:")
+	if len(failures) == 0 {
+		fmt.Fprintln(w, "  none")
+	}
+	for _, failure := range failures {
+		line := fmt.Sprintf("  %s\tfailed %s ago", describeRequest(failure), age(now, failure.LastUpdatedTime))
+		if failure.ErrorCode != "" {
+			line += fmt.Sprintf(" with %s", failure.ErrorCode)
+		}
+		fmt.Fprintln(w, line)
+	}
+	return w.Flush()
+}
+
+// completedSpanState returns whether a completed span proof is only fulfilled, covered by a completed AGG
+// proof, or behind the latest output on the L2OO.
+func completedSpanState(span *ent.ProofRequest, completedAggs []*ent.ProofRequest, latestOutput uint64) string {
+	if span.EndBlock <= latestOutput {
+		return spanSubmitted
+	}
+	for _, agg := range completedAggs {
+		if agg.StartBlock <= span.StartBlock && span.EndBlock <= agg.EndBlock {
+			return spanAggregated
+		}
+	}
+	return spanFulfilled
+}
+
+func describeRequest(proof *ent.ProofRequest) string {
+	description := fmt.Sprintf("%s %d-%d (%s)", proof.Type, proof.StartBlock, proof.EndBlock, proof.Status)
+	if proof.ProverRequestID != "" {
+		description += fmt.Sprintf(", prover request %s", proof.ProverRequestID)
+	}
+	return description
+}
+
+func age(now time.Time, unixTime uint64) time.Duration {
+	return now.Sub(time.Unix(int64(unixTime), 0)).Truncate(time.Second)
+}
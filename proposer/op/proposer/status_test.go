@@ -0,0 +1,16 @@
+package proposer
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/require"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
+)
+
+func TestCompletedSpanState(t *testing.T) {
+	aggs := []*ent.ProofRequest{{StartBlock: 100, EndBlock: 300}}
+
+	require.Equal(t, spanSubmitted, completedSpanState(&ent.ProofRequest{StartBlock: 50, EndBlock: 100}, aggs, 100))
+	require.Equal(t, spanAggregated, completedSpanState(&ent.ProofRequest{StartBlock: 200, EndBlock: 300}, aggs, 100))
+	require.Equal(t, spanFulfilled, completedSpanState(&ent.ProofRequest{StartBlock: 300, EndBlock: 400}, aggs, 100))
+}
@@ -0,0 +1,127 @@
+package proposer
+
+import (
+	"context"
+	"fmt"
+	"math/big"
+
+	"github.com/ethereum-optimism/optimism/op-service/eth"
+	"github.com/ethereum/go-ethereum/accounts/abi/bind"
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
+)
+
+// multicallTxData creates the transaction data calling the `multicall` the L2OO inherits from OpenZeppelin's Multicall,
+// which runs the given calls to the L2OO in one transaction and reverts them all if one reverts.
+func (l *L2OutputSubmitter) multicallTxData(calls [][]byte) ([]byte, error) {
+	return l.l2ooABI.Pack("multicall", calls)
+}
+
+// l2ooProposalTxData creates the transaction data proposing an output to the L2OO. The L1 block hash the proof commits
+// to is checkpointed before the proof is requested, but if the checkpoint isn't on-chain anymore, e.g. because it was
+// reorged out, the checkpoint and the proposal are bundled in one multicall, so the block can't leave the BLOCKHASH
+// window between them.
+func (l *L2OutputSubmitter) l2ooProposalTxData(ctx context.Context, p outputProposal) ([]byte, error) {
+	data, err := l.proposalTxData(p)
+	if err != nil {
+		return nil, err
+	}
+
+	checkpoint, err := l.missingCheckpointTxData(ctx, p.l1BlockNum)
+	if err != nil {
+		return nil, err
+	}
+	if checkpoint == nil {
+		return data, nil
+	}
+	return l.multicallTxData([][]byte{checkpoint, data})
+}
+
+// outputProposal is an output to propose with the AGG proof justifying it.
+type outputProposal struct {
+	output     *eth.OutputResponse
+	proof      []byte
+	l1BlockNum uint64
+	// The hash of the checkpoint overrides the AGG proof aggregates over, or zero if none.
+	checkpointOverridesHash common.Hash
+}
+
+// proposalTxData creates the transaction data of the L2OO call proposing an output, with the checkpoint overrides the
+// AGG proof relies on if any.
+func (l *L2OutputSubmitter) proposalTxData(p outputProposal) ([]byte, error) {
+	if p.checkpointOverridesHash != (common.Hash{}) {
+		return l.proposeL2OutputWithCheckpointOverridesTxData(p)
+	}
+	return l.ProposeL2OutputTxData(p.output, p.proof, p.l1BlockNum)
+}
+
+// l2ooBatchProposalTxData creates the transaction data proposing sequential outputs to the L2OO in one multicall, for
+// backfills where sending each proposal separately pays the transaction overhead every time. The L1 block hashes the
+// proofs commit to that aren't checkpointed are checkpointed first in the same multicall. If any call reverts, e.g.
+// because another proposer submitted an output in between, the whole batch reverts.
+func (l *L2OutputSubmitter) l2ooBatchProposalTxData(ctx context.Context, proposals []outputProposal) ([]byte, error) {
+	var checkpoints, proposes [][]byte
+	seen := make(map[uint64]bool)
+	for _, p := range proposals {
+		data, err := l.proposalTxData(p)
+		if err != nil {
+			return nil, err
+		}
+		proposes = append(proposes, data)
+
+		if seen[p.l1BlockNum] {
+			continue
+		}
+		seen[p.l1BlockNum] = true
+		checkpoint, err := l.missingCheckpointTxData(ctx, p.l1BlockNum)
+		if err != nil {
+			return nil, err
+		}
+		if checkpoint != nil {
+			checkpoints = append(checkpoints, checkpoint)
+		}
+	}
+	return l.multicallTxData(append(checkpoints, proposes...))
+}
+
+// missingCheckpointTxData creates the transaction data checkpointing the hash of an L1 block, or returns nil if it is
+// already checkpointed on the L2OO.
+func (l *L2OutputSubmitter) missingCheckpointTxData(ctx context.Context, l1BlockNum uint64) ([]byte, error) {
+	blockNumber := new(big.Int).SetUint64(l1BlockNum)
+	checkpointed, err := l.l2ooContract.HistoricBlockHashes(&bind.CallOpts{Context: ctx}, blockNumber)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read checkpointed block hash of L1 block %d: %w", l1BlockNum, err)
+	}
+	if checkpointed != ([32]byte{}) {
+		return nil, nil
+	}
+
+	l.Log.Info("L1 block hash not checkpointed, checkpointing it with the proposal", "l1blocknum", l1BlockNum)
+	return l.CheckpointBlockHashTxData(blockNumber)
+}
+
+// proposalChain returns the completed AGG proofs to propose next, in order. Each proof starts where the previous one
+// ends, the first at the L2OO's latest block, and ends at least a submission interval after it, the first at or after
+// the L2OO's next block number. Of the proofs starting at a block, the one reaching the highest block is picked. At
+// most limit proofs are returned.
+func proposalChain(state L2OOState, aggProofs []*ent.ProofRequest, limit uint64) []*ent.ProofRequest {
+	var chain []*ent.ProofRequest
+	start, minEnd := state.LatestBlockNumber, state.NextBlockNumber
+	for uint64(len(chain)) < limit {
+		var next *ent.ProofRequest
+		for _, proof := range aggProofs {
+			if proof.StartBlock != start || proof.EndBlock < minEnd {
+				continue
+			}
+			if next == nil || proof.EndBlock > next.EndBlock {
+				next = proof
+			}
+		}
+		if next == nil {
+			break
+		}
+		chain = append(chain, next)
+		start, minEnd = next.EndBlock, next.EndBlock+state.SubmissionInterval
+	}
+	return chain
+}
@@ -0,0 +1,57 @@
+package proposer
+
+import (
+	"math/big"
+	"testing"
+
+	"github.com/ethereum/go-ethereum/common"
+	"github.com/stretchr/testify/require"
+	opsuccinctbindings "github.com/succinctlabs/op-succinct-go/bindings"
+	"github.com/succinctlabs/op-succinct-go/proposer/db/ent"
+)
+
+func TestMulticallTxData(t *testing.T) {
+	l2ooAbi, err := opsuccinctbindings.OPSuccinctL2OutputOracleMetaData.GetAbi()
+	require.NoError(t, err)
+	checkpoint, err := l2ooAbi.Pack("checkpointBlockHash", big.NewInt(200))
+	require.NoError(t, err)
+	proposeL2Output, err := methodBySig(l2ooAbi, proposeL2OutputSig)
+	require.NoError(t, err)
+	propose, err := l2ooAbi.Pack(proposeL2Output.Name, [32]byte{0x01}, big.NewInt(100), big.NewInt(200), []byte{0x02})
+	require.NoError(t, err)
+
+	data, err := (&L2OutputSubmitter{l2ooABI: l2ooAbi}).multicallTxData([][]byte{checkpoint, propose})
+	require.NoError(t, err)
+	// The selector of OpenZeppelin's multicall(bytes[]).
+	require.Equal(t, common.FromHex("0xac9650d8"), data[:4])
+
+	args, err := l2ooAbi.Methods["multicall"].Inputs.Unpack(data[4:])
+	require.NoError(t, err)
+	require.Equal(t, [][]byte{checkpoint, propose}, args[0])
+}
+
+func TestProposalChain(t *testing.T) {
+	state := L2OOState{LatestBlockNumber: 100, NextBlockNumber: 150, SubmissionInterval: 50}
+	proofs := []*ent.ProofRequest{
+		{ID: 1, StartBlock: 100, EndBlock: 140}, // Ends before the next block number.
+		{ID: 2, StartBlock: 100, EndBlock: 200},
+		{ID: 3, StartBlock: 100, EndBlock: 160},
+		{ID: 4, StartBlock: 200, EndBlock: 230}, // Ends less than the submission interval after 200.
+		{ID: 5, StartBlock: 200, EndBlock: 300},
+		{ID: 6, StartBlock: 300, EndBlock: 400},
+		{ID: 7, StartBlock: 500, EndBlock: 600}, // Doesn't continue from 400.
+	}
+	ids := func(chain []*ent.ProofRequest) []int {
+		var ids []int
+		for _, proof := range chain {
+			ids = append(ids, proof.ID)
+		}
+		return ids
+	}
+
+	require.Equal(t, []int{2}, ids(proposalChain(state, proofs, 1)))
+	require.Equal(t, []int{2, 5}, ids(proposalChain(state, proofs, 2)))
+	require.Equal(t, []int{2, 5, 6}, ids(proposalChain(state, proofs, 10)))
+	require.Empty(t, proposalChain(state, proofs[:1], 10))
+	require.Empty(t, proposalChain(L2OOState{LatestBlockNumber: 200, NextBlockNumber: 500}, proofs, 10))
+}
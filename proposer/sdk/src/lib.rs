@@ -0,0 +1,325 @@
+//! A typed client for the OP Succinct proposer server's HTTP API.
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! use op_succinct_client_sdk::ProposerClient;
+//!
+//! let client = ProposerClient::new("http://localhost:3000".parse()?);
+//! let proof_id = client.request_span_proof(1000, 1300).await?;
+//! let status = client.proof_status(proof_id).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod types;
+
+use std::fmt;
+
+use alloy_primitives::{hex, B256};
+use anyhow::{Context, Result};
+use reqwest::{RequestBuilder, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+
+pub use types::*;
+
+/// A client for the proposer server.
+#[derive(Debug, Clone)]
+pub struct ProposerClient {
+    base_url: Url,
+    client: reqwest::Client,
+    callback_url: Option<Url>,
+}
+
+impl ProposerClient {
+    pub fn new(base_url: Url) -> Self {
+        Self::with_client(base_url, reqwest::Client::new())
+    }
+
+    /// Use a preconfigured HTTP client, e.g. with timeouts or default headers.
+    pub fn with_client(mut base_url: Url, client: reqwest::Client) -> Self {
+        // Paths are joined onto the base URL, which replaces its last segment unless it ends with
+        // a slash, e.g. for a server behind a reverse proxy at `https://example.com/proposer`.
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+        Self {
+            base_url,
+            client,
+            callback_url: None,
+        }
+    }
+
+    /// Have the server POST a [`ProofWebhookPayload`] to `callback_url` once each proof requested
+    /// by this client is fulfilled or unfulfillable, instead of polling its status.
+    pub fn with_callback_url(mut self, callback_url: Url) -> Self {
+        self.callback_url = Some(callback_url);
+        self
+    }
+
+    fn url(&self, path: &str) -> Result<Url> {
+        Ok(self.base_url.join(path)?)
+    }
+
+    /// Send a request, failing with a [`ServerError`] on a non-success status.
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(ServerError::from_response(response).await.into());
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Request a span proof of the L2 blocks after `start` up to and including `end`, returning the
+    /// proof ID.
+    pub async fn request_span_proof(&self, start: u64, end: u64) -> Result<B256> {
+        self.send_span_proof_request(self.span_proof_request(start, end))
+            .await
+    }
+
+    /// Request a span proof like [`Self::request_span_proof`], with its witness generated from the
+    /// given endpoints instead of the server's.
+    pub async fn request_span_proof_with_rpc_overrides(
+        &self,
+        start: u64,
+        end: u64,
+        rpc_overrides: RpcOverrides,
+    ) -> Result<B256> {
+        self.send_span_proof_request(SpanProofRequest {
+            rpc_overrides,
+            ..self.span_proof_request(start, end)
+        })
+        .await
+    }
+
+    /// Request a span proof like [`Self::request_span_proof`], claiming `claimed_output_root` for
+    /// `end`, e.g. to dispute a proposal. See [`ClaimMode`].
+    pub async fn request_disputed_span_proof(
+        &self,
+        start: u64,
+        end: u64,
+        claimed_output_root: B256,
+        claim_mode: ClaimMode,
+    ) -> Result<B256> {
+        self.send_span_proof_request(SpanProofRequest {
+            claimed_output_root: Some(claimed_output_root),
+            claim_mode,
+            ..self.span_proof_request(start, end)
+        })
+        .await
+    }
+
+    fn span_proof_request(&self, start: u64, end: u64) -> SpanProofRequest {
+        SpanProofRequest {
+            start,
+            end,
+            callback_url: self.callback_url.as_ref().map(Url::to_string),
+            witness_sha256: None,
+            rpc_overrides: RpcOverrides::default(),
+            claimed_output_root: None,
+            claim_mode: ClaimMode::Check,
+        }
+    }
+
+    async fn send_span_proof_request(&self, request: SpanProofRequest) -> Result<B256> {
+        let response: ProofResponse = self
+            .send(
+                self.client
+                    .post(self.url("request_span_proof")?)
+                    .json(&request),
+            )
+            .await?;
+        proof_id(response)
+    }
+
+    /// Request an aggregation proof of span proofs, checkpointing the L1 block hash `head`,
+    /// returning the proof ID.
+    pub async fn request_agg_proof(&self, subproofs: Vec<Vec<u8>>, head: B256) -> Result<B256> {
+        self.send_agg_proof_request(subproofs, head, None).await
+    }
+
+    /// Request an aggregation proof of span proofs with gaps force-resolved by governance, for
+    /// emergency catch-up. See [`CheckpointOverrides`].
+    pub async fn request_catch_up_agg_proof(
+        &self,
+        subproofs: Vec<Vec<u8>>,
+        head: B256,
+        checkpoint_overrides: CheckpointOverrides,
+    ) -> Result<B256> {
+        self.send_agg_proof_request(subproofs, head, Some(checkpoint_overrides))
+            .await
+    }
+
+    async fn send_agg_proof_request(
+        &self,
+        subproofs: Vec<Vec<u8>>,
+        head: B256,
+        checkpoint_overrides: Option<CheckpointOverrides>,
+    ) -> Result<B256> {
+        let response: ProofResponse = self
+            .send(
+                self.client
+                    .post(self.url("request_agg_proof")?)
+                    .json(&AggProofRequest {
+                        subproofs,
+                        head: head.to_string(),
+                        checkpoint_overrides,
+                        callback_url: self.callback_url.as_ref().map(Url::to_string),
+                    }),
+            )
+            .await?;
+        proof_id(response)
+    }
+
+    /// Get the status of a proof, which includes the proof once it is fulfilled. Proofs the server
+    /// links to rather than returning inline are downloaded.
+    pub async fn proof_status(&self, proof_id: B256) -> Result<ProofStatus> {
+        let mut status: ProofStatus = self
+            .send(
+                self.client
+                    .get(self.url(&format!("status/{}", hex::encode(proof_id)))?),
+            )
+            .await?;
+        if let Some(proof_url) = status.proof_url.take() {
+            if status.proof.is_empty() {
+                status.proof = self.download_proof(&proof_url).await?;
+            }
+        }
+        Ok(status)
+    }
+
+    /// Download a proof's bytes from the path a status response links to.
+    async fn download_proof(&self, path: &str) -> Result<Vec<u8>> {
+        let response = self.client.get(self.url(path)?).send().await?;
+        if !response.status().is_success() {
+            return Err(ServerError::from_response(response).await.into());
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Get the highest L2 block a range can be proven up to.
+    pub async fn provable_head(&self) -> Result<ProvableHead> {
+        self.send(self.client.get(self.url("provable_head")?)).await
+    }
+}
+
+/// A non-success response of the server, with the [`ErrorCode`] of the failure if the server
+/// classified it.
+#[derive(Debug)]
+pub struct ServerError {
+    pub status: StatusCode,
+    pub code: Option<ErrorCode>,
+    pub message: String,
+}
+
+impl ServerError {
+    async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let code = response
+            .headers()
+            .get(ERROR_CODE_HEADER)
+            .and_then(|code| code.to_str().ok()?.parse().ok());
+        let message = response.text().await.unwrap_or_default();
+        Self {
+            status,
+            code,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Proposer server returned {}", self.status)?;
+        if let Some(code) = self.code {
+            write!(f, " ({code})")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+fn proof_id(response: ProofResponse) -> Result<B256> {
+    B256::try_from(response.proof_id.as_slice()).context("Invalid proof ID in response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        extract::Path,
+        http::HeaderMap,
+        response::IntoResponse,
+        routing::{get, post},
+        Json, Router,
+    };
+
+    /// Serve a mock of the server's API under `/proposer`, returning its base URL without a
+    /// trailing slash.
+    async fn mock_server() -> Url {
+        let api = Router::new()
+            .route(
+                "/request_span_proof",
+                post(|Json(request): Json<SpanProofRequest>| async move {
+                    if request.end <= request.start {
+                        let mut headers = HeaderMap::new();
+                        headers.insert(ERROR_CODE_HEADER, "E_UNEXECUTABLE_SPAN".parse().unwrap());
+                        return (StatusCode::UNPROCESSABLE_ENTITY, headers, "Invalid range")
+                            .into_response();
+                    }
+                    Json(ProofResponse {
+                        proof_id: vec![0x11; 32],
+                        witness_sha256: None,
+                    })
+                    .into_response()
+                }),
+            )
+            .route(
+                "/status/:proof_id",
+                get(|Path(proof_id): Path<String>| async move {
+                    Json(ProofStatus {
+                        fulfillment_status: 3,
+                        execution_status: 2,
+                        proof: vec![],
+                        metadata: None,
+                        proof_url: Some(format!("proofs/{proof_id}/download")),
+                        error_code: None,
+                    })
+                }),
+            )
+            .route(
+                "/proofs/:proof_id/download",
+                get(|| async { vec![0xab_u8; 4] }),
+            );
+        let app = Router::new().nest("/proposer", api);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{address}/proposer").parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let client = ProposerClient::new(mock_server().await);
+
+        let proof_id = client.request_span_proof(1000, 1300).await.unwrap();
+        assert_eq!(proof_id, B256::repeat_byte(0x11));
+
+        // The proof is downloaded from the URL the status links to.
+        let status = client.proof_status(proof_id).await.unwrap();
+        assert_eq!(status.proof, vec![0xab; 4]);
+        assert!(status.proof_url.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_server_error() {
+        let client = ProposerClient::new(mock_server().await);
+
+        let error = client.request_span_proof(1300, 1000).await.unwrap_err();
+        let error = error.downcast_ref::<ServerError>().unwrap();
+        assert_eq!(error.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(error.code, Some(ErrorCode::UnexecutableSpan));
+        assert_eq!(error.message, "Invalid range");
+    }
+}
@@ -0,0 +1,555 @@
+//! The request and response types of the proposer server's HTTP API, shared by the server and
+//! the client.
+
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use alloy_primitives::{Address, B256};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ValidateConfigRequest {
+    pub address: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ValidateConfigResponse {
+    pub rollup_config_hash_valid: bool,
+    pub agg_vkey_valid: bool,
+    pub range_vkey_valid: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SpanProofRequest {
+    pub start: u64,
+    pub end: u64,
+    /// Notified with a [`ProofWebhookPayload`] once the proof is fulfilled or unfulfillable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<String>,
+    /// When retrying a span, the [`ProofResponse::witness_sha256`] of its last proof. The server
+    /// proves the span from its cached witness if it still has this hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_sha256: Option<B256>,
+    /// The endpoints to generate the witness from instead of the server's.
+    #[serde(flatten)]
+    pub rpc_overrides: RpcOverrides,
+    /// The output root to claim for the end block instead of the one the server's L2 RPC reports,
+    /// e.g. the output root of a disputed proposal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claimed_output_root: Option<B256>,
+    /// How the range program treats the claimed output root.
+    #[serde(default, skip_serializing_if = "ClaimMode::is_check")]
+    pub claim_mode: ClaimMode,
+}
+
+/// How the range program treats the claimed output root of a span. The proof commits to the output
+/// root of the end block either way.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimMode {
+    /// Fail unless the claimed output root is the output root of the end block, so a wrong claim
+    /// can't be proven.
+    #[default]
+    Check,
+    /// Prove the output root of the end block whatever the claimed output root, e.g. to prove the
+    /// correct output of a range whose proposal is wrong.
+    Compute,
+}
+
+impl ClaimMode {
+    pub fn is_check(&self) -> bool {
+        *self == Self::Check
+    }
+}
+
+/// Endpoints to generate a span's witness from instead of the server's, e.g. an archive node for a
+/// historical range. Each must be in the server's `RPC_OVERRIDE_ALLOWLIST`.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpcOverrides {
+    /// The L1 execution RPC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_rpc: Option<String>,
+    /// The L2 execution RPC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_rpc: Option<String>,
+    /// The beacon API blobs are read from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beacon_rpc: Option<String>,
+}
+
+/// The maximum number of blocks in a span when previewing a range, matching the default of the
+/// proposer's `MAX_BLOCK_RANGE_PER_SPAN_PROOF`.
+pub const DEFAULT_MAX_SPAN_SIZE: u64 = 300;
+
+/// The maximum number of blocks in a range previewed at once. Planning reads a few L1 and L2
+/// blocks and the batcher's blobs for every span, so larger ranges are previewed in parts.
+pub const MAX_PLAN_RANGE_SIZE: u64 = 100_000;
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PlanRangeRequest {
+    pub start: u64,
+    pub end: u64,
+    /// Default: [`DEFAULT_MAX_SPAN_SIZE`].
+    #[serde(default)]
+    pub max_span_size: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AggProofRequest {
+    #[serde(
+        serialize_with = "serialize_base64_vec",
+        deserialize_with = "deserialize_base64_vec"
+    )]
+    pub subproofs: Vec<Vec<u8>>,
+    pub head: String,
+    /// Gaps in the chain of subproofs force-resolved by governance, for emergency catch-up. Unset
+    /// for a normal aggregation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_overrides: Option<CheckpointOverrides>,
+    /// Notified with a [`ProofWebhookPayload`] once the proof is fulfilled or unfulfillable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<String>,
+}
+
+/// Gaps in the chain of subproofs that an aggregation proof skips. The L2 Output Oracle only
+/// accepts the proof once its owner approved the overrides with `approveCheckpointOverrides`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointOverrides {
+    /// The gaps, in order.
+    pub gaps: Vec<CheckpointGap>,
+    /// The justification governance signed for skipping the blocks in the gaps. The aggregation
+    /// proof commits to its keccak256 hash.
+    pub justification: String,
+}
+
+/// A gap from the output root the chain of subproofs ends at (or the latest output on-chain, for
+/// the first gap) to the output root the next subproof starts from.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckpointGap {
+    pub from_root: B256,
+    pub to_root: B256,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MockProofResponse {
+    pub proof_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProofResponse {
+    pub proof_id: Vec<u8>,
+    /// The SHA-256 hash of the witness a span proof is proven from. Unset for aggregation proofs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_sha256: Option<B256>,
+}
+
+#[derive(Debug, Serialize_repr, Deserialize_repr)]
+#[repr(i32)]
+/// The type of error that occurred when unclaiming a proof. Based off of the `unclaim_description`
+/// field in the `ProofStatus` struct.
+pub enum UnclaimDescription {
+    UnexpectedProverError = 0,
+    ProgramExecutionError = 1,
+    CycleLimitExceeded = 2,
+    Other = 3,
+}
+
+/// Convert a string to an `UnclaimDescription`. These cover the common reasons why a proof might
+/// be unclaimed.
+impl From<String> for UnclaimDescription {
+    fn from(description: String) -> Self {
+        match description.as_str().to_lowercase().as_str() {
+            "unexpected prover error" => UnclaimDescription::UnexpectedProverError,
+            "program execution error" => UnclaimDescription::ProgramExecutionError,
+            "cycle limit exceeded" => UnclaimDescription::CycleLimitExceeded,
+            _ => UnclaimDescription::Other,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+/// The status of a proof request.
+pub struct ProofStatus {
+    // Note: Can't use `FulfillmentStatus`/`ExecutionStatus` directly because `Serialize_repr` and `Deserialize_repr` aren't derived on it.
+    pub fulfillment_status: i32,
+    pub execution_status: i32,
+    /// The proof once it is fulfilled, unless it is too large to return inline.
+    pub proof: Vec<u8>,
+    /// The prover network's record of the proof request, once the proof is fulfilled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ProofMetadata>,
+    /// Where to download a fulfilled proof too large to return inline, relative to the server's
+    /// base URL. `proof` is empty if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof_url: Option<String>,
+    /// Why the proof is unfulfillable, if it is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<ErrorCode>,
+}
+
+/// The header of an error response carrying its [`ErrorCode`].
+pub const ERROR_CODE_HEADER: &str = "X-Error-Code";
+
+/// The cause of a failed request or an unfulfillable proof, shared by the server's error responses,
+/// logs and metrics and the proposer's failed proof requests, so failures can be broken down by
+/// cause.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// An L1, L2 or beacon RPC failed or couldn't be reached.
+    #[serde(rename = "E_RPC_UNAVAILABLE")]
+    RpcUnavailable,
+    /// A blob the span derives from is no longer served by the beacon node.
+    #[serde(rename = "E_BLOB_PRUNED")]
+    BlobPruned,
+    /// A block's witness or the span's stdin exceeds its size limit.
+    #[serde(rename = "E_WITNESS_TOO_LARGE")]
+    WitnessTooLarge,
+    /// Witness generation made no progress for longer than the stall timeout.
+    #[serde(rename = "E_WITNESS_GEN_STALLED")]
+    WitnessGenStalled,
+    /// The proposer gave up waiting for witness generation.
+    #[serde(rename = "E_WITNESS_GEN_TIMEOUT")]
+    WitnessGenTimeout,
+    /// A cached witness doesn't hash to the witness its span was proven from.
+    #[serde(rename = "E_WITNESS_HASH_MISMATCH")]
+    WitnessHashMismatch,
+    /// Witness generation failed for another reason.
+    #[serde(rename = "E_UNEXECUTABLE_SPAN")]
+    UnexecutableSpan,
+    /// The subproofs or L1 headers of an aggregation proof would fail the aggregation program.
+    #[serde(rename = "E_INVALID_AGG_INPUT")]
+    InvalidAggInput,
+    /// The primary and secondary proofs of a span disagree.
+    #[serde(rename = "E_QUORUM_MISMATCH")]
+    QuorumMismatch,
+    /// The prover network rejected or failed the proof request.
+    #[serde(rename = "E_PROVER_UNAVAILABLE")]
+    ProverUnavailable,
+    /// The proof passed its deadline.
+    #[serde(rename = "E_PROVER_TIMEOUT")]
+    ProverTimeout,
+    /// The prover network marked the proof unfulfillable.
+    #[serde(rename = "E_PROVER_UNFULFILLABLE")]
+    ProverUnfulfillable,
+    /// The verification keys or rollup config hash on the L2OO don't match the prover's.
+    #[serde(rename = "E_VKEY_MISMATCH")]
+    VkeyMismatch,
+    /// The proposer couldn't reach the server.
+    #[serde(rename = "E_SERVER_UNAVAILABLE")]
+    ServerUnavailable,
+    /// The server is paused and turned the request away without attempting it.
+    #[serde(rename = "E_PAUSED")]
+    Paused,
+    /// Any other failure.
+    #[serde(rename = "E_INTERNAL")]
+    Internal,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 16] = [
+        ErrorCode::RpcUnavailable,
+        ErrorCode::BlobPruned,
+        ErrorCode::WitnessTooLarge,
+        ErrorCode::WitnessGenStalled,
+        ErrorCode::WitnessGenTimeout,
+        ErrorCode::WitnessHashMismatch,
+        ErrorCode::UnexecutableSpan,
+        ErrorCode::InvalidAggInput,
+        ErrorCode::QuorumMismatch,
+        ErrorCode::ProverUnavailable,
+        ErrorCode::ProverTimeout,
+        ErrorCode::ProverUnfulfillable,
+        ErrorCode::VkeyMismatch,
+        ErrorCode::ServerUnavailable,
+        ErrorCode::Paused,
+        ErrorCode::Internal,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::RpcUnavailable => "E_RPC_UNAVAILABLE",
+            ErrorCode::BlobPruned => "E_BLOB_PRUNED",
+            ErrorCode::WitnessTooLarge => "E_WITNESS_TOO_LARGE",
+            ErrorCode::WitnessGenStalled => "E_WITNESS_GEN_STALLED",
+            ErrorCode::WitnessGenTimeout => "E_WITNESS_GEN_TIMEOUT",
+            ErrorCode::WitnessHashMismatch => "E_WITNESS_HASH_MISMATCH",
+            ErrorCode::UnexecutableSpan => "E_UNEXECUTABLE_SPAN",
+            ErrorCode::InvalidAggInput => "E_INVALID_AGG_INPUT",
+            ErrorCode::QuorumMismatch => "E_QUORUM_MISMATCH",
+            ErrorCode::ProverUnavailable => "E_PROVER_UNAVAILABLE",
+            ErrorCode::ProverTimeout => "E_PROVER_TIMEOUT",
+            ErrorCode::ProverUnfulfillable => "E_PROVER_UNFULFILLABLE",
+            ErrorCode::VkeyMismatch => "E_VKEY_MISMATCH",
+            ErrorCode::ServerUnavailable => "E_SERVER_UNAVAILABLE",
+            ErrorCode::Paused => "E_PAUSED",
+            ErrorCode::Internal => "E_INTERNAL",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|code| code.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown error code {s}"))
+    }
+}
+
+/// Whether a proof is a span proof or an aggregation proof.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProofKind {
+    Span,
+    Agg,
+}
+
+/// The status a proof settled at.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TerminalStatus {
+    Fulfilled,
+    Unfulfillable,
+}
+
+/// The body of the POST request sent to a proof request's `callback_url` once the proof settles.
+/// The request carries the hex-encoded HMAC-SHA256 of the body, keyed with the server's
+/// `WEBHOOK_SECRET`, in the `X-OP-Succinct-Signature` header as `sha256=<hex>`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProofWebhookPayload {
+    pub proof_id: B256,
+    pub kind: ProofKind,
+    pub status: TerminalStatus,
+    /// The keccak256 hash of the proof's public values, if fulfilled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_values_hash: Option<B256>,
+    /// The Unix timestamp the payload was signed at, for receivers to reject replays.
+    pub timestamp: u64,
+}
+
+/// The L2 blocks a proof covers: the blocks after `start` up to and including `end`, starting from
+/// the output root at `start`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvenBlocks {
+    pub kind: ProofKind,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ProvenBlocks {
+    pub fn covers(&self, l2_block: u64) -> bool {
+        self.start < l2_block && l2_block <= self.end
+    }
+}
+
+/// A fulfilled proof covering an L2 block.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CoveringProof {
+    pub proof_id: B256,
+    #[serde(flatten)]
+    pub blocks: ProvenBlocks,
+    pub metadata: Option<ProofMetadata>,
+    /// The path the proof's raw bytes are downloaded from.
+    pub download_url: String,
+}
+
+/// The fulfilled proofs covering an L2 block, aggregation proofs first.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProofsByBlockResponse {
+    pub l2_block: u64,
+    pub proofs: Vec<CoveringProof>,
+}
+
+/// The prover network's record of a proof request, used to audit proving costs.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ProofMetadata {
+    /// Unix timestamp at which the proof was requested.
+    pub requested_at: u64,
+    /// Unix timestamp at which the proof was fulfilled.
+    pub fulfilled_at: Option<u64>,
+    /// The number of cycles billed for the proof.
+    pub cycles: Option<u64>,
+    /// The prover gas used by the proof.
+    pub gas_used: Option<u64>,
+    /// The address of the prover that fulfilled the proof.
+    pub fulfiller: Option<Address>,
+}
+
+/// The health of a dependency checked by the readiness endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ComponentStatus {
+    pub healthy: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ComponentStatus {
+    /// Convert the result of a dependency check into its status.
+    pub fn from_result<T>(result: anyhow::Result<T>) -> Self {
+        match result {
+            Ok(_) => Self {
+                healthy: true,
+                error: None,
+            },
+            Err(e) => Self {
+                healthy: false,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+/// The response of the readiness endpoint. The server is ready only if every component is healthy.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReadinessResponse {
+    pub ready: bool,
+    pub components: BTreeMap<String, ComponentStatus>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PauseRequest {
+    pub reason: String,
+}
+
+/// Why and when the server was paused.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PauseState {
+    pub reason: String,
+    /// Unix timestamp at which the server was paused.
+    pub paused_at: u64,
+}
+
+/// Whether the server is accepting new proof requests.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AdminStatus {
+    pub paused: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pause_state: Option<PauseState>,
+}
+
+/// The programs the server proves with after a reload.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReloadResponse {
+    /// Incremented by every reload, starting at 0 when the server starts.
+    pub generation: u64,
+    pub aggregation_vkey: B256,
+    pub range_vkey_commitment: B256,
+}
+
+/// The highest L2 block a range can be proven up to: every batch of the blocks up to it is in a
+/// finalized L1 block, so the range's witness can be generated now and its L1 head won't be
+/// reorged out.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvableHead {
+    pub l2_block: u64,
+    /// The finalized L1 block the L2 block is derived from.
+    pub l1_block: u64,
+    pub l1_block_hash: B256,
+    pub source: ProvableHeadSource,
+}
+
+/// How the server found the [`ProvableHead`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvableHeadSource {
+    /// The L2 node's safe head at the finalized L1 block, from its safe head database.
+    SafeDb,
+    /// The L2 chain's finalized block, if the L2 node's safe head database is disabled. It may lag
+    /// behind the safe head at the finalized L1 block.
+    L2Finalized,
+}
+
+/// Deserialize a vector of base64 strings into a vector of vectors of bytes. Go serializes
+/// the subproofs as base64 strings.
+fn deserialize_base64_vec<'de, D>(deserializer: D) -> Result<Vec<Vec<u8>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Vec<String> = Deserialize::deserialize(deserializer)?;
+    s.into_iter()
+        .map(|base64_str| {
+            general_purpose::STANDARD
+                .decode(base64_str)
+                .map_err(serde::de::Error::custom)
+        })
+        .collect()
+}
+
+/// Serialize a vector of vectors of bytes as a vector of base64 strings, as Go does.
+fn serialize_base64_vec<S>(bytes: &[Vec<u8>], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_seq(
+        bytes
+            .iter()
+            .map(|bytes| general_purpose::STANDARD.encode(bytes)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agg_proof_request_roundtrip() {
+        let request = AggProofRequest {
+            subproofs: vec![vec![1, 2, 3], vec![]],
+            head: "0x01".to_string(),
+            checkpoint_overrides: None,
+            callback_url: None,
+        };
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["subproofs"], serde_json::json!(["AQID", ""]));
+
+        let decoded: AggProofRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.subproofs, request.subproofs);
+    }
+
+    #[test]
+    fn test_span_proof_request_json() {
+        let request: SpanProofRequest = serde_json::from_value(serde_json::json!({
+            "start": 100,
+            "end": 200,
+            "l2_rpc": "https://archive.example.com",
+            "claimed_output_root": B256::repeat_byte(1),
+            "claim_mode": "compute",
+        }))
+        .unwrap();
+        assert_eq!(
+            request.rpc_overrides.l2_rpc.as_deref(),
+            Some("https://archive.example.com")
+        );
+        assert_eq!(request.claimed_output_root, Some(B256::repeat_byte(1)));
+        assert_eq!(request.claim_mode, ClaimMode::Compute);
+
+        // Requests without overrides are serialized as before.
+        let request: SpanProofRequest =
+            serde_json::from_value(serde_json::json!({"start": 100, "end": 200})).unwrap();
+        assert_eq!(request.claim_mode, ClaimMode::Check);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({"start": 100, "end": 200})
+        );
+    }
+
+    #[test]
+    fn test_error_code_roundtrip() {
+        for code in ErrorCode::ALL {
+            let json = serde_json::to_value(code).unwrap();
+            assert_eq!(json, serde_json::json!(code.as_str()));
+            assert_eq!(serde_json::from_value::<ErrorCode>(json).unwrap(), code);
+            assert_eq!(code.as_str().parse::<ErrorCode>().unwrap(), code);
+        }
+        assert!("E_UNKNOWN".parse::<ErrorCode>().is_err());
+    }
+}
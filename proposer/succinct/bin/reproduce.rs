@@ -0,0 +1,176 @@
+use std::path::PathBuf;
+
+use alloy_primitives::{Bytes, B256};
+use anyhow::{anyhow, bail, Result};
+use clap::Parser;
+use log::{info, warn};
+use op_succinct_client_utils::public_values::decode_boot_info;
+use op_succinct_host_utils::{
+    fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
+    get_proof_stdin, start_server_and_native_client, witness_sha256, ProgramType,
+};
+use op_succinct_proposer::{
+    artifact::ProofArtifacts, config::ProposerConfig, programs::ProgramElfs, AGG_ELF, RANGE_ELF,
+};
+use sp1_sdk::{utils, HashableKey, Prover, ProverClient};
+
+/// Reproduces a fulfilled span proof from its proof ID, for dispute forensics. Reads the range, L1
+/// head and public values of the proof from its artifact (or, without one, fetches the proof from
+/// the prover network), regenerates the witness of the range with the same L1 head, executes the
+/// range program locally and checks that it commits to the same public values as the proof.
+#[derive(Debug, Clone, Parser)]
+struct ReproduceArgs {
+    /// The ID of the span proof on the prover network.
+    #[clap(long)]
+    proof_id: B256,
+    /// The first L2 block of the range. Required if the proof has no artifact recording it.
+    #[clap(long)]
+    start: Option<u64>,
+    /// The environment file to use.
+    #[clap(long, default_value = ".env")]
+    env_file: PathBuf,
+}
+
+/// What the fulfilled proof commits to, and what it was proven with.
+struct FulfilledProof {
+    l2_start_block: Option<u64>,
+    range_vkey: Option<B256>,
+    /// The SHA-256 hash of the witness the proof was proven from, if its artifact records it.
+    witness_sha256: Option<B256>,
+    public_values: Bytes,
+}
+
+/// Read the fulfilled proof from its artifact, or fetch it from the prover network.
+async fn fulfilled_proof(artifacts: &ProofArtifacts, proof_id: B256) -> Result<FulfilledProof> {
+    if let Some(artifact) = artifacts.read(&proof_id)? {
+        info!("Read span proof {} from its artifact", proof_id);
+        return Ok(FulfilledProof {
+            l2_start_block: artifact.l2_start_block,
+            range_vkey: Some(artifact.range_vkey),
+            witness_sha256: artifact.witness_sha256,
+            public_values: artifact.public_values,
+        });
+    }
+
+    info!(
+        "Span proof {} has no artifact, fetching it from the prover network",
+        proof_id
+    );
+    let network_prover = ProverClient::builder().network().build();
+    let (_, proof) = network_prover.get_proof_status(proof_id).await?;
+    let proof = proof.ok_or_else(|| anyhow!("Span proof {proof_id} is not fulfilled"))?;
+    Ok(FulfilledProof {
+        l2_start_block: None,
+        range_vkey: None,
+        witness_sha256: None,
+        public_values: Bytes::copy_from_slice(proof.public_values.as_slice()),
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = ReproduceArgs::parse();
+    dotenv::from_path(&args.env_file).ok();
+    utils::setup_logger();
+
+    let config = ProposerConfig::from_env()?;
+    let fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
+    let l2_chain_id = fetcher.get_l2_chain_id().await?;
+    let artifacts = ProofArtifacts::new(
+        fetcher.workspace()?.artifacts_dir(l2_chain_id),
+        config.host.witness_key.clone(),
+    )?;
+
+    let proof = fulfilled_proof(&artifacts, args.proof_id).await?;
+    let (version, boot_info) = decode_boot_info(&proof.public_values)?;
+    let start = match (args.start, proof.l2_start_block) {
+        (Some(start), _) | (None, Some(start)) => start,
+        (None, None) => bail!(
+            "The artifact of span proof {} doesn't record its start block. Pass --start.",
+            args.proof_id
+        ),
+    };
+    let end = boot_info.l2BlockNumber;
+    info!(
+        "Span proof {} proves L2 blocks {}-{} with L1 head {} ({:?} public values)",
+        args.proof_id, start, end, boot_info.l1Head, version
+    );
+
+    // The proof's pre-root must be the output root of the start block.
+    let start_output_root = fetcher.get_l2_output_at_block(start).await?;
+    if start_output_root != boot_info.l2PreRoot {
+        bail!(
+            "The output root of L2 block {} is {}, but span proof {} starts from {}. Check the \
+             start block.",
+            start,
+            start_output_root,
+            args.proof_id,
+            boot_info.l2PreRoot
+        );
+    }
+
+    let host_args = fetcher
+        .get_host_args(
+            start,
+            end,
+            Some(boot_info.l1Head),
+            ProgramType::Multi,
+            CacheMode::DeleteCache,
+        )
+        .await?;
+    let oracle = start_server_and_native_client(host_args).await?;
+    let stdin = get_proof_stdin(oracle)?;
+    let regenerated_sha256 = witness_sha256(&stdin);
+    info!("Regenerated the witness, SHA-256 {}", regenerated_sha256);
+    match proof.witness_sha256 {
+        Some(proven_sha256) if proven_sha256 == regenerated_sha256 => info!(
+            "The regenerated witness is identical to the witness span proof {} was proven from",
+            args.proof_id
+        ),
+        Some(proven_sha256) => warn!(
+            "Span proof {} was proven from a witness with SHA-256 {}, but the regenerated witness \
+             differs. Executing the regenerated witness still checks the proof's public values.",
+            args.proof_id, proven_sha256
+        ),
+        None => {}
+    }
+
+    let elfs = ProgramElfs::load(&config, RANGE_ELF, AGG_ELF)?;
+    let prover = ProverClient::builder().mock().build();
+    let (_, range_vk) = prover.setup(&elfs.range);
+    let range_vkey: B256 = range_vk.bytes32().parse()?;
+    match proof.range_vkey {
+        Some(proven_vkey) if proven_vkey != range_vkey => warn!(
+            "Span proof {} was proven with range vkey {}, but the local range program has vkey {}. \
+             Set RANGE_ELF_PATH to the range program the proof was generated with.",
+            args.proof_id, proven_vkey, range_vkey
+        ),
+        Some(_) => {}
+        None => warn!(
+            "The range vkey of span proof {} is unknown. Check that the local range program, with \
+             vkey {}, is the one the proof was generated with.",
+            args.proof_id, range_vkey
+        ),
+    }
+
+    let (public_values, report) = prover.execute(&elfs.range, &stdin).run()?;
+    info!(
+        "Executed the range program in {} cycles",
+        report.total_instruction_count()
+    );
+    if public_values.as_slice() != proof.public_values.as_ref() {
+        let (_, reproduced) = decode_boot_info(public_values.as_slice())?;
+        bail!(
+            "The public values of the local execution don't match span proof {}.\nProven: \
+             {:?}\nReproduced: {:?}",
+            args.proof_id,
+            boot_info,
+            reproduced
+        );
+    }
+    println!(
+        "Reproduced span proof {}: L2 blocks {}-{} from {} to {} with L1 head {}",
+        args.proof_id, start, end, boot_info.l2PreRoot, boot_info.l2PostRoot, boot_info.l1Head
+    );
+    Ok(())
+}
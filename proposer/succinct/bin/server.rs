@@ -1,61 +1,127 @@
-use alloy_primitives::{hex, Address, B256};
-use anyhow::Result;
+use alloy_primitives::{hex, keccak256, Address, B256};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::BlockId;
+use anyhow::{bail, Result};
 use axum::{
-    extract::{DefaultBodyLimit, Path, State},
-    http::StatusCode,
-    response::{IntoResponse, Response},
+    extract::{DefaultBodyLimit, Path, Query, RawQuery, State},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderMap, StatusCode,
+    },
+    middleware,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     routing::{get, post},
     Json, Router,
 };
-use log::{error, info};
+use futures::Stream;
+use log::{error, info, warn};
 use op_succinct_client_utils::{
-    boot::{hash_rollup_config, BootInfoStruct},
-    types::u32_to_u8,
+    boot::{self, hash_rollup_config},
+    public_values::{decode_boot_infos, decode_range_intermediate_outputs},
 };
 use op_succinct_host_utils::{
-    fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
-    get_agg_proof_stdin, get_proof_stdin, start_server_and_native_client,
+    agg_stdin::AggStdinError,
+    beacon::{BeaconTiming, BlobAvailability},
+    blob_archive::{BlobArchive, BlobArchiver},
+    block_range::{plan_range, RangePlan},
+    chaos::{self, FailurePoint},
+    config::{redact_url, Url},
+    da::{BeaconNode, DaSourceConfig, DataAvailabilitySource, ProxiedRequest},
+    datadir::LocalNodes,
+    fetcher::{CacheMode, OPSuccinctDataFetcher, RPCMode, RunContext},
+    get_agg_proof_stdin, get_proof_stdin, get_quorum_agg_proof_stdin,
+    l1_head::L1HeadSelection,
+    l2_node::select_witness_strategy,
+    panic_report::PanicReportCollector,
+    progress::InFlightRuns,
+    start_server_and_native_client,
     stats::ExecutionStats,
-    L2OutputOracle, ProgramType,
+    stdin_size, witness_sha256, L2OutputOracle, OPSuccinctHost, ProgramType,
 };
 use op_succinct_proposer::{
-    AggProofRequest, ProofResponse, ProofStatus, SpanProofRequest, SuccinctProposerConfig,
-    ValidateConfigRequest, ValidateConfigResponse,
+    agg_requests::AggRequestStore,
+    artifact::ProofArtifacts,
+    auditor::{AuditStatus, OutputAuditor},
+    checkpoint::{record_checkpoint_overrides, requested_overrides},
+    config::{ProposerConfig, ProvingConfig},
+    contract::OutputContract,
+    custody::WitnessCache,
+    diagnostics::{write_span_failure_report, UnexecutableSpan, SPLIT_AT_HEADER},
+    download::{download_path, externalize_proof, serve_proof},
+    error_code::{error_code, is_unavailable, is_unprocessable, with_code},
+    metrics::{track_metrics, ServerMetrics},
+    pause::PauseStore,
+    policy::{AggregationDecision, AggregationStatus, PendingSpanProofs},
+    programs::{CurrentProvingContext, ProgramElfs, ProvingContext},
+    quorum::QuorumSpanProof,
+    readiness::ReadinessCache,
+    reconciler::{proof_bytes, run_reconciler, AggRequestKey, ProofTracker, SpanRequestKey},
+    rpc_overrides::CheckedRpcOverrides,
+    sync_status::{SyncStatus, SyncStatusTracker},
+    utils::{make_request_span, setup_logging},
+    validation::{check_stdin_size, validate_subproof_chain, BrokenSubproofChain},
+    webhook::Webhooks,
+    witnessgen_events::witnessgen_event_stream,
+    AdminStatus, AggProofRequest, ClaimMode, ComponentStatus, CoveringProof, ErrorCode,
+    PauseRequest, PauseState, PlanRangeRequest, ProofKind, ProofResponse, ProofStatus,
+    ProofsByBlockResponse, ProvableHead, ProvableHeadSource, ProvenBlocks, ReadinessResponse,
+    ReloadResponse, SpanProofRequest, SuccinctProposerConfig, ValidateConfigRequest,
+    ValidateConfigResponse, AGG_ELF, DEFAULT_MAX_SPAN_SIZE, ERROR_CODE_HEADER, MAX_PLAN_RANGE_SIZE,
+    RANGE_ELF,
 };
+use serde::de::DeserializeOwned;
 use sp1_sdk::{
-    network::{
-        proto::network::{ExecutionStatus, FulfillmentStatus},
-        FulfillmentStrategy,
-    },
-    utils, HashableKey, Prover, ProverClient, SP1Proof, SP1ProofMode, SP1ProofWithPublicValues,
+    network::proto::network::{ExecutionStatus, FulfillmentStatus},
+    ProverClient, SP1Proof, SP1ProofMode, SP1ProofWithPublicValues, SP1Stdin, SP1VerifyingKey,
     SP1_CIRCUIT_VERSION,
 };
 use std::{
+    collections::{BTreeMap, HashMap},
+    convert::Infallible,
     env, fs,
+    future::Future,
+    io,
     str::FromStr,
-    sync::Arc,
-    time::{Instant, SystemTime, UNIX_EPOCH},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use subtle::ConstantTimeEq;
+use tokio::signal::unix::{signal, Signal, SignalKind};
+use tower_http::{
+    limit::RequestBodyLimitLayer,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
 };
-use tower_http::limit::RequestBodyLimitLayer;
-
-pub const RANGE_ELF: &[u8] = include_bytes!("../../../elf/range-elf");
-pub const AGG_ELF: &[u8] = include_bytes!("../../../elf/aggregation-elf");
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Enable logging.
     env::set_var("RUST_LOG", "info");
 
-    // Set up the SP1 SDK logger.
-    utils::setup_logger();
     dotenv::dotenv().ok();
+    setup_logging();
+
+    // Serve the L1 and L2 RPCs from database snapshots if `L1_DATADIR` or `L2_DATADIR` is set, for
+    // air-gapped proving. The nodes run until the server exits.
+    let _local_nodes = LocalNodes::start_from_env().await?;
+
+    // Validate every setting up front, rather than failing when a request first reads it.
+    let config = ProposerConfig::from_env()?;
+    info!("Config:");
+    for (name, value) in config.summary() {
+        info!("  {name}: {value}");
+    }
+
+    // Fail fast if the L2 node lacks the debug APIs witness generation needs.
+    select_witness_strategy(config.host.l2_node_kind).await?;
 
     let network_prover = Arc::new(ProverClient::builder().network().build());
-    let (range_pk, range_vk) = network_prover.setup(RANGE_ELF);
-    let (agg_pk, agg_vk) = network_prover.setup(AGG_ELF);
-    let multi_block_vkey_u8 = u32_to_u8(range_vk.vk.hash_u32());
-    let range_vkey_commitment = B256::from(multi_block_vkey_u8);
-    let agg_vkey_hash = B256::from_str(&agg_vk.bytes32()).unwrap();
+    let elfs = ProgramElfs::load(&config.proving, RANGE_ELF, AGG_ELF)?;
+    let proving = ProvingContext::setup(&network_prover, elfs, &config.proving, 0)?;
+    info!("Range vkey commitment: {}", proving.range_vkey_commitment);
 
     let fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Docker).await?;
     // Note: The rollup config hash never changes for a given chain, so we can just hash it once at
@@ -63,50 +129,176 @@ async fn main() -> Result<()> {
     // [`RollupConfig`] is released from `op-alloy`.
     let rollup_config_hash = hash_rollup_config(fetcher.rollup_config.as_ref().unwrap());
 
-    // Set the proof strategies based on environment variables. Default to reserved to keep existing behavior.
-    let range_proof_strategy = match env::var("RANGE_PROOF_STRATEGY") {
-        Ok(strategy) if strategy.to_lowercase() == "hosted" => FulfillmentStrategy::Hosted,
-        _ => FulfillmentStrategy::Reserved,
+    // Poll the status of requested proofs in the background, so status requests are served from
+    // the local proof tracker.
+    let reconciler_config = config.reconciler.clone();
+    let proof_tracker = Arc::new(ProofTracker::default());
+    // Write an auditable artifact of every fulfilled span proof.
+    let proof_artifacts = if config.proof_artifacts {
+        Some(Arc::new(ProofArtifacts::new(
+            fetcher
+                .workspace()?
+                .artifacts_dir(fetcher.get_l2_chain_id().await?),
+            config.host.witness_key.clone(),
+        )?))
+    } else {
+        None
     };
-    let agg_proof_strategy = match env::var("AGG_PROOF_STRATEGY") {
-        Ok(strategy) if strategy.to_lowercase() == "hosted" => FulfillmentStrategy::Hosted,
-        _ => FulfillmentStrategy::Reserved,
+    // Cache the stdin of every span proof, so a retried span is proven from it if it is unchanged.
+    let witness_cache = if config.witness_cache {
+        Some(Arc::new(WitnessCache::new(
+            fetcher.workspace()?,
+            fetcher.get_l2_chain_id().await?,
+            config.host.witness_key.clone(),
+        )))
+    } else {
+        None
     };
+    let webhooks = Arc::new(Webhooks::new(config.webhooks.clone()));
+    tokio::spawn(run_reconciler(
+        proof_tracker.clone(),
+        network_prover.clone(),
+        reconciler_config.clone(),
+        proof_artifacts.clone(),
+        webhooks.clone(),
+    ));
 
-    // Set the aggregation proof type based on environment variable. Default to groth16.
-    let agg_proof_mode = match env::var("AGG_PROOF_MODE") {
-        Ok(proof_type) if proof_type.to_lowercase() == "plonk" => SP1ProofMode::Plonk,
-        _ => SP1ProofMode::Groth16,
+    // Serve the data availability source to witness generation through the beacon API proxy. The
+    // blob archive is filled as the batcher posts blobs, so ranges remain provable after their
+    // blobs expire on the beacon node.
+    let da_source = match &config.da_source {
+        DaSourceConfig::Beacon => None,
+        DaSourceConfig::BlobArchive { dir } => {
+            let beacon = BeaconNode::from_pool(fetcher.rpc_config.l1_beacon_pool.clone());
+            let archive = BlobArchive::new(dir.clone(), beacon)?;
+            tokio::spawn(BlobArchiver::new(archive.clone(), fetcher.clone())?.run());
+            Some(Arc::new(archive) as Arc<dyn DataAvailabilitySource>)
+        }
+        source => Some(source.build(&fetcher).await?),
     };
+    info!(
+        "Witness generation reads batch data from {}",
+        config.da_source
+    );
+
+    let metrics = Arc::new(ServerMetrics::new()?);
+
+    // Track the rollup node's heads, and alert on discrepancies with the L2 execution RPC.
+    let sync_status = match &config.sync_tracker {
+        Some(tracker_config) => {
+            let status = Arc::new(Mutex::new(SyncStatus::default()));
+            tokio::spawn(
+                SyncStatusTracker::new(
+                    tracker_config.clone(),
+                    fetcher.clone(),
+                    status.clone(),
+                    metrics.clone(),
+                )
+                .run(),
+            );
+            Some(status)
+        }
+        None => None,
+    };
+
+    // Periodically re-verify the outputs on the L2OO against the L2 RPC.
+    let audit_status = match &config.auditor {
+        Some(auditor_config) => {
+            let status = Arc::new(Mutex::new(AuditStatus::default()));
+            tokio::spawn(
+                OutputAuditor::new(auditor_config.clone(), fetcher.clone(), status.clone()).run(),
+            );
+            Some(status)
+        }
+        None => None,
+    };
+
+    // A server paused before it was restarted stays paused.
+    let pause_state = Arc::new(PauseStore::load(
+        fetcher
+            .workspace()?
+            .pause_state_path(fetcher.get_l2_chain_id().await?),
+    )?);
+    if let Some(pause_state) = pause_state.get() {
+        warn!("Server is paused: {}", pause_state.reason);
+    }
+
+    // The aggregation proofs requested before a restart are returned to identical requests.
+    let agg_requests = Arc::new(AggRequestStore::load(
+        fetcher
+            .workspace()?
+            .agg_requests_path(fetcher.get_l2_chain_id().await?),
+        reconciler_config.proof_retention_secs,
+    )?);
 
     // Initialize global hashes.
     let global_hashes = SuccinctProposerConfig {
-        agg_vkey_hash,
-        range_vkey_commitment,
+        proving: CurrentProvingContext::new(proving),
+        reload_lock: Arc::new(tokio::sync::Mutex::new(())),
         rollup_config_hash,
-        range_vk: Arc::new(range_vk),
-        range_pk: Arc::new(range_pk),
-        agg_vk: Arc::new(agg_vk),
-        agg_pk: Arc::new(agg_pk),
-        range_proof_strategy,
-        agg_proof_strategy,
-        agg_proof_mode,
         network_prover,
+        aggregation_status: Arc::new(Mutex::new(AggregationStatus::new(
+            config.proving.aggregation_policy.clone(),
+        ))),
+        proof_tracker,
+        agg_requests,
+        proof_artifacts,
+        witness_cache,
+        reconciler_config,
+        pause_state,
+        admin_api_key: config.admin_api_key.clone(),
+        da_source,
+        audit_status,
+        sync_status,
+        metrics: metrics.clone(),
+        witnessgen_runs: InFlightRuns::default(),
+        webhooks,
+        proof_inline_max_bytes: config.proof_inline_max_bytes,
+        rpc_override_allowlist: config.rpc_override_allowlist.clone(),
+        readiness: Arc::new(ReadinessCache::default()),
     };
 
+    // Reload the programs and settings on `SIGHUP`, like `POST /admin/reload`.
+    tokio::spawn(reload_on_sighup(
+        signal(SignalKind::hangup())?,
+        global_hashes.clone(),
+    ));
+
     let app = Router::new()
         .route("/request_span_proof", post(request_span_proof))
         .route("/request_agg_proof", post(request_agg_proof))
         .route("/request_mock_span_proof", post(request_mock_span_proof))
         .route("/request_mock_agg_proof", post(request_mock_agg_proof))
         .route("/status/:proof_id", get(get_proof_status))
+        .route("/proofs/by_block/:l2_block", get(get_proofs_by_block))
+        .route("/proofs/:proof_id/artifact", get(get_proof_artifact))
+        .route("/proofs/:proof_id/download", get(download_proof))
+        .route("/plan_range", get(get_range_plan))
+        .route("/provable_head", get(get_provable_head))
         .route("/validate_config", post(validate_config))
+        .route("/aggregation_decision", post(aggregation_decision))
+        .route("/aggregation_status", get(aggregation_status))
+        .route("/audit_status", get(audit_status))
+        .route("/sync_status", get(sync_status))
+        .route("/ready", get(ready))
+        .route("/admin/pause", post(admin_pause))
+        .route("/admin/resume", post(admin_resume))
+        .route("/admin/status", get(admin_status))
+        .route("/admin/reload", post(admin_reload))
+        .route("/beacon/*path", get(beacon_proxy))
+        .route("/metrics", get(get_metrics))
+        .route("/witnessgen/events", get(witnessgen_events))
+        // Record the metrics of every request, labeled with the route it matched.
+        .route_layer(middleware::from_fn_with_state(metrics, track_metrics))
         .layer(DefaultBodyLimit::disable())
         .layer(RequestBodyLimitLayer::new(102400 * 1024 * 1024))
+        // Tag every request with an `x-request-id` and record its logs in a span with that ID.
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(TraceLayer::new_for_http().make_span_with(make_request_span))
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
         .with_state(global_hashes);
 
-    let port = env::var("PORT").unwrap_or_else(|_| "3000".to_string());
-    let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port))
+    let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", config.port))
         .await
         .unwrap();
 
@@ -115,23 +307,223 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Serve the metrics of the server's endpoints in the Prometheus text format.
+async fn get_metrics(State(state): State<SuccinctProposerConfig>) -> Result<Response, AppError> {
+    let body = state.metrics.render()?;
+    Ok(([(CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response())
+}
+
+/// Stream the progress of the witness generation runs in flight as server-sent events.
+async fn witnessgen_events(
+    State(state): State<SuccinctProposerConfig>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    Sse::new(witnessgen_event_stream(state.witnessgen_runs)).keep_alive(KeepAlive::default())
+}
+
+/// Serve the beacon API to witness generation from the data availability source (see
+/// [`DataAvailabilitySource::get`]). Responds with `404` if witness generation reads from the
+/// beacon node directly, and with `400` to requests witness generation doesn't make.
+async fn beacon_proxy(
+    State(state): State<SuccinctProposerConfig>,
+    Path(path): Path<String>,
+    RawQuery(query): RawQuery,
+) -> Result<Response, AppError> {
+    let Some(da_source) = &state.da_source else {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    };
+
+    let path_and_query = match query {
+        Some(query) => format!("{}?{}", path, query),
+        None => path,
+    };
+    // Only the requests witness generation makes are forwarded, so the proxy can't be used to
+    // reach the rest of the beacon node's API.
+    let request = match ProxiedRequest::parse(&path_and_query) {
+        Ok(request) => request,
+        Err(e) => return Ok((StatusCode::BAD_REQUEST, e.to_string()).into_response()),
+    };
+    let body = da_source.get(request).await?;
+    Ok(([(CONTENT_TYPE, "application/json")], body).into_response())
+}
+
+/// Get the fulfilled span and aggregation proofs requested by this server that cover an L2 block,
+/// for example to find the proof justifying a withdrawal. Responds with `404` if there are none.
+async fn get_proofs_by_block(
+    State(state): State<SuccinctProposerConfig>,
+    Path(l2_block): Path<u64>,
+) -> Result<Response, AppError> {
+    let proofs: Vec<CoveringProof> = state
+        .proof_tracker
+        .covering(l2_block)
+        .into_iter()
+        .map(|(proof_id, blocks, status)| CoveringProof {
+            proof_id,
+            blocks,
+            metadata: status.metadata,
+            download_url: format!("/{}", download_path(&proof_id)),
+        })
+        .collect();
+
+    let status = if proofs.is_empty() {
+        StatusCode::NOT_FOUND
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(ProofsByBlockResponse { l2_block, proofs })).into_response())
+}
+
+/// Get the artifact of a fulfilled span proof, with the boot info it commits to, the vkey it was
+/// proven against and the hash of the proof. Responds with `404` if the proof has no artifact, or
+/// if artifacts are disabled.
+async fn get_proof_artifact(
+    State(state): State<SuccinctProposerConfig>,
+    Path(proof_id): Path<String>,
+) -> Result<Response, AppError> {
+    let Some(artifacts) = &state.proof_artifacts else {
+        return Ok((
+            StatusCode::NOT_FOUND,
+            "Proof artifacts are disabled. Set PROOF_ARTIFACTS=true to enable them.",
+        )
+            .into_response());
+    };
+
+    let proof_id = match parse_proof_id(&proof_id) {
+        Ok(proof_id) => proof_id,
+        Err(response) => return Ok(response),
+    };
+    match artifacts.read(&proof_id)? {
+        Some(artifact) => Ok((StatusCode::OK, Json(artifact)).into_response()),
+        None => Ok((
+            StatusCode::NOT_FOUND,
+            format!("No artifact for proof {proof_id}"),
+        )
+            .into_response()),
+    }
+}
+
+/// Preview how a range would be split into spans and what proving them would take, without
+/// generating witnesses or requesting proofs. Planning reads from the RPCs for every span, so it
+/// requires the admin API key and responds with `400` to ranges of more than
+/// [`MAX_PLAN_RANGE_SIZE`] blocks.
+async fn get_range_plan(
+    State(state): State<SuccinctProposerConfig>,
+    headers: HeaderMap,
+    Query(payload): Query<PlanRangeRequest>,
+) -> Result<Response, AppError> {
+    if let Err(status) = authorize_admin(&state, &headers) {
+        return Ok(status.into_response());
+    }
+
+    info!("Received range plan request: {:?}", payload);
+    let max_span_size = payload.max_span_size.unwrap_or(DEFAULT_MAX_SPAN_SIZE);
+    if max_span_size == 0 {
+        return Err(AppError(anyhow::anyhow!("max_span_size must be positive")));
+    }
+    if payload.end.saturating_sub(payload.start) > MAX_PLAN_RANGE_SIZE {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            format!("Ranges of more than {MAX_PLAN_RANGE_SIZE} blocks can't be planned at once"),
+        )
+            .into_response());
+    }
+
+    let fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Docker).await?;
+    let plan = plan_range(&fetcher, payload.start, payload.end, max_span_size).await?;
+    Ok((StatusCode::OK, Json(plan)).into_response())
+}
+
+/// Get the highest L2 block a range can be proven up to: the L2 node's safe head at the finalized
+/// L1 block. Falls back to the L2 chain's finalized block if the L2 node's safe head database is
+/// disabled.
+///
+/// If witness generation reads blobs from the beacon node, the L1 block is capped by the beacon
+/// node's head, and the head is rejected with `E_BLOB_PRUNED` if the beacon node no longer serves
+/// the blobs of the L1 block.
+async fn get_provable_head(
+    State(state): State<SuccinctProposerConfig>,
+) -> Result<(StatusCode, Json<ProvableHead>), AppError> {
+    let fetcher = OPSuccinctDataFetcher::new(RunContext::Dev)?;
+    let mut l1_block = fetcher.get_l1_header(BlockId::finalized()).await?;
+
+    if state.da_source.is_none() {
+        let beacon_url = &fetcher.rpc_config.l1_beacon_rpc;
+        let blobs = BlobAvailability::load(beacon_url).await?;
+        if l1_block.number > blobs.head_l1_block {
+            warn!(
+                "The beacon node's head is at L1 block {}, behind the finalized L1 block {}",
+                blobs.head_l1_block, l1_block.number
+            );
+            l1_block = fetcher.get_l1_header(blobs.head_l1_block.into()).await?;
+        }
+        let slot = BeaconTiming::load(beacon_url)
+            .await?
+            .slot(l1_block.timestamp)?;
+        if slot < blobs.oldest_slot {
+            return Err(AppError(with_code(
+                ErrorCode::BlobPruned,
+                anyhow::anyhow!(
+                    "The beacon node no longer serves the blobs of L1 block {} (slot {slot}, oldest served slot {})",
+                    l1_block.number,
+                    blobs.oldest_slot
+                ),
+            )));
+        }
+    }
+
+    let (l2_block, source) = match fetcher
+        .get_l2_safe_head_from_l1_block_number(l1_block.number)
+        .await
+    {
+        Ok(l2_block) => (l2_block, ProvableHeadSource::SafeDb),
+        Err(e) => {
+            warn!(
+                "Failed to get the safe head at L1 block {}, using the finalized L2 block: {e}",
+                l1_block.number
+            );
+            let l2_finalized = fetcher.get_l2_header(BlockId::finalized()).await?;
+            (l2_finalized.number, ProvableHeadSource::L2Finalized)
+        }
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(ProvableHead {
+            l2_block,
+            l1_block: l1_block.number,
+            l1_block_hash: l1_block.hash_slow(),
+            source,
+        }),
+    ))
+}
+
 /// Validate the configuration of the L2 Output Oracle.
 async fn validate_config(
     State(state): State<SuccinctProposerConfig>,
     Json(payload): Json<ValidateConfigRequest>,
 ) -> Result<(StatusCode, Json<ValidateConfigResponse>), AppError> {
     info!("Received validate config request: {:?}", payload);
-    let fetcher = OPSuccinctDataFetcher::default();
+    let proving = state.proving.get();
+    let fetcher = OPSuccinctDataFetcher::new(RunContext::Dev)?;
 
     let address = Address::from_str(&payload.address).unwrap();
+    match OutputContract::detect(&fetcher.l1_provider, address).await? {
+        OutputContract::ZkL2OutputOracle(_) => {}
+        contract => {
+            return Err(AppError(anyhow::anyhow!(
+                "The contract at {} is a {}, which has no verification keys to validate. Pass the address of the OPSuccinctL2OutputOracle.",
+                address,
+                contract.name()
+            )))
+        }
+    }
     let l2_output_oracle = L2OutputOracle::new(address, fetcher.l1_provider);
 
     let agg_vkey = l2_output_oracle.aggregationVkey().call().await?;
     let range_vkey = l2_output_oracle.rangeVkeyCommitment().call().await?;
     let rollup_config_hash = l2_output_oracle.rollupConfigHash().call().await?;
 
-    let agg_vkey_valid = agg_vkey.aggregationVkey == state.agg_vkey_hash;
-    let range_vkey_valid = range_vkey.rangeVkeyCommitment == state.range_vkey_commitment;
+    let agg_vkey_valid = agg_vkey.aggregationVkey == proving.agg_vkey_hash;
+    let range_vkey_valid = range_vkey.rangeVkeyCommitment == proving.range_vkey_commitment;
     let rollup_config_hash_valid = rollup_config_hash.rollupConfigHash == state.rollup_config_hash;
 
     Ok((
@@ -144,21 +536,557 @@ async fn validate_config(
     ))
 }
 
+/// The maximum time a single readiness check may take.
+const READINESS_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Run a readiness check, failing it if it takes longer than [`READINESS_CHECK_TIMEOUT`].
+async fn check_with_timeout(check: impl Future<Output = Result<()>>) -> Result<()> {
+    tokio::time::timeout(READINESS_CHECK_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("Check timed out")))
+}
+
+/// Check that the server can serve proof requests: the RPCs used for witness generation are
+/// reachable and the SP1 network accepts the server's credentials. The ELFs and verifying keys are
+/// loaded before the server starts listening, so they are always reported as healthy. The
+/// statuses are cached for a few seconds (see [`ReadinessCache`]), so probes don't each call the
+/// RPCs and the SP1 network.
+async fn ready(
+    State(state): State<SuccinctProposerConfig>,
+) -> Result<(StatusCode, Json<ReadinessResponse>), AppError> {
+    let fetcher = OPSuccinctDataFetcher::new(RunContext::Dev)?;
+    let components = state
+        .readiness
+        .get_or_check(|| check_components(&state, &fetcher))
+        .await;
+    let ready = components.values().all(|component| component.healthy);
+    if !ready {
+        error!("Server is not ready: {:?}", components);
+    }
+
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok((status, Json(ReadinessResponse { ready, components })))
+}
+
+/// Run the readiness checks of every component.
+async fn check_components(
+    state: &SuccinctProposerConfig,
+    fetcher: &OPSuccinctDataFetcher,
+) -> BTreeMap<String, ComponentStatus> {
+    let proving = state.proving.get();
+
+    let (l1_rpc, l2_rpc, l2_node_rpc, sp1_network) = tokio::join!(
+        check_with_timeout(async {
+            fetcher.l1_provider.get_block_number().await?;
+            Ok::<_, anyhow::Error>(())
+        }),
+        check_with_timeout(async {
+            fetcher.l2_provider.get_block_number().await?;
+            Ok::<_, anyhow::Error>(())
+        }),
+        check_with_timeout(async {
+            fetcher
+                .fetch_rpc_data_with_mode::<serde_json::Value>(
+                    RPCMode::L2Node,
+                    "optimism_syncStatus",
+                    vec![],
+                )
+                .await?;
+            Ok::<_, anyhow::Error>(())
+        }),
+        // Registering an already registered program only looks it up, which requires valid
+        // network credentials.
+        check_with_timeout(async {
+            state
+                .network_prover
+                .register_program(&proving.range_vk, &proving.range_elf)
+                .await?;
+            Ok::<_, anyhow::Error>(())
+        }),
+    );
+
+    BTreeMap::from([
+        ("l1_rpc".to_string(), ComponentStatus::from_result(l1_rpc)),
+        ("l2_rpc".to_string(), ComponentStatus::from_result(l2_rpc)),
+        (
+            "l2_node_rpc".to_string(),
+            ComponentStatus::from_result(l2_node_rpc),
+        ),
+        (
+            "sp1_network".to_string(),
+            ComponentStatus::from_result(sp1_network),
+        ),
+        (
+            "elf_and_vkeys".to_string(),
+            ComponentStatus::from_result(Ok(())),
+        ),
+    ])
+}
+
+/// Check the admin bearer token. Admin endpoints are disabled if `ADMIN_API_KEY` is not set.
+fn authorize_admin(state: &SuccinctProposerConfig, headers: &HeaderMap) -> Result<(), StatusCode> {
+    let Some(api_key) = &state.admin_api_key else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match token {
+        // Compare in constant time, so the response time doesn't leak how much of the token matched.
+        Some(token) if bool::from(token.as_bytes().ct_eq(api_key.as_bytes())) => Ok(()),
+        _ => Err(StatusCode::UNAUTHORIZED),
+    }
+}
+
+/// Reject new witness generation and proof requests while the server is paused.
+fn ensure_not_paused(state: &SuccinctProposerConfig) -> Result<(), AppError> {
+    match state.pause_state.get() {
+        Some(pause_state) => {
+            error!(
+                "Rejecting request, server is paused: {}",
+                pause_state.reason
+            );
+            Err(AppError(with_code(
+                ErrorCode::Paused,
+                anyhow::anyhow!("Server is paused: {}", pause_state.reason),
+            )))
+        }
+        None => Ok(()),
+    }
+}
+
+fn admin_status_response(state: &SuccinctProposerConfig) -> Json<AdminStatus> {
+    let pause_state = state.pause_state.get();
+    Json(AdminStatus {
+        paused: pause_state.is_some(),
+        pause_state,
+    })
+}
+
+/// Pause the server. New witness generation and proof requests are rejected until the server is
+/// resumed, while in-flight requests complete and requested proofs continue to be tracked.
+async fn admin_pause(
+    State(state): State<SuccinctProposerConfig>,
+    headers: HeaderMap,
+    Json(payload): Json<PauseRequest>,
+) -> Response {
+    if let Err(status) = authorize_admin(&state, &headers) {
+        return status.into_response();
+    }
+
+    info!("Pausing server: {}", payload.reason);
+    let pause_state = PauseState {
+        reason: payload.reason,
+        paused_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+    };
+    if let Err(e) = state.pause_state.set(Some(pause_state)) {
+        error!("Failed to persist the pause state: {:?}", e);
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    (StatusCode::OK, admin_status_response(&state)).into_response()
+}
+
+/// Resume accepting new witness generation and proof requests.
+async fn admin_resume(State(state): State<SuccinctProposerConfig>, headers: HeaderMap) -> Response {
+    if let Err(status) = authorize_admin(&state, &headers) {
+        return status.into_response();
+    }
+
+    info!("Resuming server");
+    if let Err(e) = state.pause_state.set(None) {
+        error!("Failed to persist the pause state: {:?}", e);
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    (StatusCode::OK, admin_status_response(&state)).into_response()
+}
+
+/// Get whether the server is paused, and why.
+async fn admin_status(State(state): State<SuccinctProposerConfig>, headers: HeaderMap) -> Response {
+    if let Err(status) = authorize_admin(&state, &headers) {
+        return status.into_response();
+    }
+
+    (StatusCode::OK, admin_status_response(&state)).into_response()
+}
+
+/// Reload the programs and settings from the environment file. See [`reload`].
+async fn admin_reload(State(state): State<SuccinctProposerConfig>, headers: HeaderMap) -> Response {
+    if let Err(status) = authorize_admin(&state, &headers) {
+        return status.into_response();
+    }
+
+    match reload(&state).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(e) => {
+            error!("Failed to reload: {:?}", e);
+            AppError(e).into_response()
+        }
+    }
+}
+
+/// Reload the programs and settings on every `SIGHUP`.
+async fn reload_on_sighup(mut hangups: Signal, state: SuccinctProposerConfig) {
+    while hangups.recv().await.is_some() {
+        info!("Received SIGHUP, reloading");
+        if let Err(e) = reload(&state).await {
+            error!("Failed to reload: {:?}", e);
+        }
+    }
+}
+
+/// Re-read the environment file, and serve new requests with the programs and settings it
+/// configures. In-flight requests complete against the programs they started with.
+///
+/// The programs are reloaded from `RANGE_ELF_PATH`, `AGGREGATION_ELF_PATH` and
+/// `SECONDARY_RANGE_ELF_PATH`, along with the proof strategies, the aggregation proof mode, the
+/// stdin size limit and the aggregation policy. The variables are read from the environment file
+/// over the environment, which is left untouched, so every other setting, like the RPCs, the port,
+/// the reconciler, the blob archive, the auditor and the admin API key, only changes on restart.
+/// If the new settings are invalid, the server keeps its programs.
+async fn reload(state: &SuccinctProposerConfig) -> Result<ReloadResponse> {
+    let _reloading = state.reload_lock.lock().await;
+    let vars = read_env_file()?;
+
+    let generation = state.proving.get().generation + 1;
+    let network_prover = state.network_prover.clone();
+    let (config, proving) = tokio::task::spawn_blocking(move || {
+        let config = ProvingConfig::from_vars(&|name| {
+            vars.get(name).cloned().or_else(|| env::var(name).ok())
+        })?;
+        let elfs = ProgramElfs::load(&config, RANGE_ELF, AGG_ELF)?;
+        let proving = ProvingContext::setup(&network_prover, elfs, &config, generation)?;
+        Ok::<_, anyhow::Error>((config, proving))
+    })
+    .await??;
+
+    let response = proving.summary();
+    state.proving.replace(proving);
+    state.aggregation_status.lock().unwrap().policy = config.aggregation_policy;
+    info!(
+        "Reloaded proving context {}: aggregation vkey {}, range vkey commitment {}",
+        response.generation, response.aggregation_vkey, response.range_vkey_commitment
+    );
+    Ok(response)
+}
+
+/// Read the variables in the `.env` file, which reloads read over the environment. Without a
+/// `.env` file, there are none.
+fn read_env_file() -> Result<HashMap<String, String>> {
+    let vars = match dotenv::dotenv_iter() {
+        Ok(vars) => vars,
+        Err(dotenv::Error::Io(e)) if e.kind() == io::ErrorKind::NotFound => {
+            return Ok(HashMap::new())
+        }
+        Err(e) => return Err(e.into()),
+    };
+    Ok(vars.collect::<Result<_, _>>()?)
+}
+
+/// Evaluate the aggregation policy for the span proofs the proposer has not yet aggregated.
+async fn aggregation_decision(
+    State(state): State<SuccinctProposerConfig>,
+    Json(payload): Json<PendingSpanProofs>,
+) -> Result<(StatusCode, Json<AggregationDecision>), AppError> {
+    let policy = state.aggregation_status.lock().unwrap().policy.clone();
+
+    // Only fetch the gas price if the policy depends on it.
+    let l1_gas_price = match policy.max_l1_gas_price {
+        Some(_) => {
+            let fetcher = OPSuccinctDataFetcher::new(RunContext::Dev)?;
+            match fetcher.l1_provider.get_gas_price().await {
+                Ok(gas_price) => Some(gas_price),
+                Err(e) => {
+                    error!("Failed to get L1 gas price: {}", e);
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
+    let decision = policy.evaluate(&payload, l1_gas_price);
+    info!(
+        "Aggregation decision for {:?} at L1 gas price {:?}: {:?}",
+        payload, l1_gas_price, decision
+    );
+
+    let mut status = state.aggregation_status.lock().unwrap();
+    status.pending = Some(payload);
+    status.l1_gas_price = l1_gas_price;
+    status.decision = Some(decision);
+    status.evaluated_at = Some(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+    );
+
+    Ok((StatusCode::OK, Json(decision)))
+}
+
+/// Get the aggregation policy and the last decision made by it.
+async fn aggregation_status(
+    State(state): State<SuccinctProposerConfig>,
+) -> Result<(StatusCode, Json<AggregationStatus>), AppError> {
+    let status = state.aggregation_status.lock().unwrap().clone();
+    Ok((StatusCode::OK, Json(status)))
+}
+
+/// Get the outcome of the audits of the outputs on the L2OO. Responds with `404` if the auditor is
+/// disabled.
+async fn audit_status(State(state): State<SuccinctProposerConfig>) -> Response {
+    match &state.audit_status {
+        Some(status) => {
+            let status = status.lock().unwrap().clone();
+            (StatusCode::OK, Json(status)).into_response()
+        }
+        None => (
+            StatusCode::NOT_FOUND,
+            "The output auditor is disabled. Set AUDIT_INTERVAL_SECS to enable it.",
+        )
+            .into_response(),
+    }
+}
+
+/// Get the rollup node's latest safe and finalized L2 heads, and the discrepancies found between
+/// them and the L2 execution RPC. Responds with `404` if the sync status tracker is disabled.
+async fn sync_status(State(state): State<SuccinctProposerConfig>) -> Response {
+    match &state.sync_status {
+        Some(status) => {
+            let status = status.lock().unwrap().clone();
+            (StatusCode::OK, Json(status)).into_response()
+        }
+        None => (
+            StatusCode::NOT_FOUND,
+            "The sync status tracker is disabled. Set SYNC_STATUS_INTERVAL_SECS above 0 to enable it.",
+        )
+            .into_response(),
+    }
+}
+
 /// Request a proof for a span of blocks.
 async fn request_span_proof(
     State(state): State<SuccinctProposerConfig>,
     Json(payload): Json<SpanProofRequest>,
 ) -> Result<(StatusCode, Json<ProofResponse>), AppError> {
     info!("Received span proof request: {:?}", payload);
-    let fetcher = match OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Docker).await {
+    ensure_not_paused(&state)?;
+    let callback_url = parse_callback_url(&state, payload.callback_url.as_deref()).await?;
+    let rpc_overrides = check_rpc_overrides(&state, &payload)?;
+    let proving = state.proving.get();
+
+    // Identical requests share the proof, so a duplicate waits for the first request to generate
+    // the witness and request the proof instead of doing it again.
+    let key = SpanRequestKey {
+        start: payload.start,
+        end: payload.end,
+        range_vkey_commitment: proving.range_vkey_commitment,
+        rollup_config_hash: state.rollup_config_hash,
+        claimed_output_root: payload.claimed_output_root,
+        claim_mode: payload.claim_mode,
+    };
+    let proof_id = *state
+        .proof_tracker
+        .span_request(key)
+        .get_or_try_init(|| {
+            request_span_proof_from_network(&state, &proving, &payload, &rpc_overrides)
+        })
+        .await?;
+    if let Some(url) = callback_url {
+        state
+            .webhooks
+            .register(&state.proof_tracker, proof_id, ProofKind::Span, url);
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(ProofResponse {
+            proof_id: proof_id.to_vec(),
+            witness_sha256: state.proof_tracker.witness_sha256(&proof_id),
+        }),
+    ))
+}
+
+/// Generate the witness for a span, or read it from the witness cache when retrying the span, and
+/// request its proof from the prover network.
+async fn request_span_proof_from_network(
+    state: &SuccinctProposerConfig,
+    proving: &ProvingContext,
+    payload: &SpanProofRequest,
+    rpc_overrides: &CheckedRpcOverrides,
+) -> Result<B256, AppError> {
+    // The witness cache holds the witnesses of the spans as proposed, so a span proven against
+    // another claim neither reads nor replaces them.
+    let witness_cache = state
+        .witness_cache
+        .as_ref()
+        .filter(|_| !overrides_claim(payload));
+    let cached_stdin = witness_cache.and_then(|cache| cached_span_stdin(cache, payload));
+    let (sp1_stdin, l1_head_selection) = match cached_stdin {
+        Some(stdin) => (stdin, None),
+        None => {
+            let (stdin, l1_head_selection) =
+                generate_span_stdin(state, payload, rpc_overrides).await?;
+            if let Some(cache) = witness_cache {
+                if let Err(e) = cache.store(payload.start, payload.end, &stdin) {
+                    warn!(
+                        "Failed to cache the witness of span {}-{}: {}",
+                        payload.start, payload.end, e
+                    );
+                }
+            }
+            (stdin, l1_head_selection)
+        }
+    };
+    let witness_sha256 = witness_sha256(&sp1_stdin);
+    info!(
+        "Witness of span {}-{} has SHA-256 {}",
+        payload.start, payload.end, witness_sha256
+    );
+
+    // The prover network rejects stdins above its size limit only after they are uploaded, so
+    // check the size up front.
+    let size = stdin_size(&sp1_stdin);
+    info!(
+        "Stdin for span {}-{} is {} bytes",
+        payload.start, payload.end, size
+    );
+    if let Err(e) = check_stdin_size(payload.start, payload.end, size, proving.max_stdin_size) {
+        error!("{}", e);
+        return Err(AppError(e.into()));
+    }
+
+    chaos::inject(FailurePoint::Prover).map_err(AppError)?;
+    let proof_id = state
+        .network_prover
+        .prove(&proving.range_pk, &sp1_stdin)
+        .compressed()
+        .strategy(proving.range_proof_strategy)
+        .skip_simulation(true)
+        .cycle_limit(1_000_000_000_000)
+        .request_async()
+        .await
+        .map_err(|e| {
+            error!("Failed to request proof: {}", e);
+            AppError(with_code(
+                ErrorCode::ProverUnavailable,
+                anyhow::anyhow!("Failed to request proof: {}", e),
+            ))
+        })?;
+    info!("Requested span proof {}", proof_id);
+    if let Some(selection) = l1_head_selection {
+        info!(
+            "Span proof {} uses L1 head {} (block {}): {}",
+            proof_id, selection.l1_head, selection.l1_head_number, selection.rationale
+        );
+    }
+    state
+        .proof_tracker
+        .record_witness_sha256(proof_id, witness_sha256);
+    if let Some(artifacts) = &state.proof_artifacts {
+        artifacts.record_request(
+            proof_id,
+            payload.start,
+            proving.range_vkeys()?,
+            witness_sha256,
+        );
+    }
+    let blocks = ProvenBlocks {
+        kind: ProofKind::Span,
+        start: payload.start,
+        end: payload.end,
+    };
+    state
+        .proof_tracker
+        .track(proof_id, Some(blocks), &state.reconciler_config);
+
+    // In quorum mode, prove the span with the secondary range program as well. The span proof is
+    // fulfilled once both proofs are.
+    if let Some(secondary_range) = &proving.secondary_range {
+        let secondary_proof_id = state
+            .network_prover
+            .prove(&secondary_range.pk, &sp1_stdin)
+            .compressed()
+            .strategy(proving.range_proof_strategy)
+            .skip_simulation(true)
+            .cycle_limit(1_000_000_000_000)
+            .request_async()
+            .await
+            .map_err(|e| {
+                error!("Failed to request secondary proof: {}", e);
+                AppError(with_code(
+                    ErrorCode::ProverUnavailable,
+                    anyhow::anyhow!("Failed to request secondary proof: {}", e),
+                ))
+            })?;
+        info!(
+            "Requested secondary span proof {} for {}",
+            secondary_proof_id, proof_id
+        );
+        state
+            .proof_tracker
+            .track(secondary_proof_id, None, &state.reconciler_config);
+        state
+            .proof_tracker
+            .link_secondary(proof_id, secondary_proof_id);
+    }
+
+    Ok(proof_id)
+}
+
+/// Read the witness of a retried span from the witness cache, if it still hashes to the hash of the
+/// witness the span was last proven from. A cached witness that changed is discarded, so the span
+/// is proven from a freshly generated one.
+fn cached_span_stdin(cache: &WitnessCache, payload: &SpanProofRequest) -> Option<SP1Stdin> {
+    let expected = payload.witness_sha256?;
+    match cache.load(payload.start, payload.end, expected) {
+        Ok(Some(stdin)) => {
+            info!(
+                "Proving span {}-{} from its cached witness {}",
+                payload.start, payload.end, expected
+            );
+            Some(stdin)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            error!(
+                "Discarding the cached witness of span {}-{}: {}",
+                payload.start, payload.end, e
+            );
+            if let Err(e) = cache.remove(payload.start, payload.end) {
+                warn!("Failed to remove the cached witness: {}", e);
+            }
+            None
+        }
+    }
+}
+
+/// Generate the witness for a span, returning its stdin and where its L1 head was chosen.
+async fn generate_span_stdin(
+    state: &SuccinctProposerConfig,
+    payload: &SpanProofRequest,
+    rpc_overrides: &CheckedRpcOverrides,
+) -> Result<(SP1Stdin, Option<L1HeadSelection>), AppError> {
+    let fetcher = match rpc_overrides.fetcher(RunContext::Docker).await {
         Ok(f) => f,
         Err(e) => {
             error!("Failed to create data fetcher: {}", e);
-            return Err(AppError(e));
+            return Err(AppError(with_code(ErrorCode::RpcUnavailable, e)));
         }
     };
 
-    let host_args = match fetcher
+    let mut host_args = match fetcher
         .get_host_args(
             payload.start,
             payload.end,
@@ -177,40 +1105,116 @@ async fn request_span_proof(
             )));
         }
     };
+    rpc_overrides.apply(&mut host_args);
+    apply_claim(&mut host_args, payload);
 
-    let mem_kv_store = start_server_and_native_client(host_args).await?;
+    let l1_head_selection = host_args.l1_head_selection.clone();
+    let witness_generation = (
+        state.metrics.witness_generation(),
+        state.witnessgen_runs.track(host_args.progress.clone()),
+    );
+    let mem_kv_store = match start_server_and_native_client(host_args).await {
+        Ok(mem_kv_store) => mem_kv_store,
+        Err(e) => {
+            return Err(witness_generation_failed(&fetcher, &state.metrics, payload, e).await)
+        }
+    };
+    drop(witness_generation);
 
-    let sp1_stdin = match get_proof_stdin(mem_kv_store) {
-        Ok(stdin) => stdin,
+    match get_proof_stdin(mem_kv_store) {
+        Ok(stdin) => Ok((stdin, l1_head_selection)),
         Err(e) => {
             error!("Failed to get proof stdin: {}", e);
-            return Err(AppError(anyhow::anyhow!(
+            Err(AppError(anyhow::anyhow!(
                 "Failed to get proof stdin: {}",
                 e
-            )));
+            )))
         }
+    }
+}
+
+/// Whether a span proof request claims another output root than the L2 RPC's, or proves whatever
+/// output root it computes.
+fn overrides_claim(payload: &SpanProofRequest) -> bool {
+    payload.claimed_output_root.is_some() || payload.claim_mode != ClaimMode::Check
+}
+
+/// Claim the output root of a span proof request for its end block, and set how the range program
+/// treats it.
+fn apply_claim(host_args: &mut OPSuccinctHost, payload: &SpanProofRequest) {
+    if let Some(claimed_output_root) = payload.claimed_output_root {
+        info!(
+            "Claiming output root {} for block {} in place of {}",
+            claimed_output_root, payload.end, host_args.kona_args.claimed_l2_output_root
+        );
+        host_args.kona_args.claimed_l2_output_root = claimed_output_root;
+    }
+    host_args.claim_mode = match payload.claim_mode {
+        ClaimMode::Check => boot::ClaimMode::Check,
+        ClaimMode::Compute => boot::ClaimMode::Compute,
     };
+}
 
-    let proof_id = state
-        .network_prover
-        .prove(&state.range_pk, &sp1_stdin)
-        .compressed()
-        .strategy(state.range_proof_strategy)
-        .skip_simulation(true)
-        .cycle_limit(1_000_000_000_000)
-        .request_async()
-        .await
-        .map_err(|e| {
-            error!("Failed to request proof: {}", e);
-            AppError(anyhow::anyhow!("Failed to request proof: {}", e))
-        })?;
+/// Check the RPC overrides of a span proof request against the allowlist.
+fn check_rpc_overrides(
+    state: &SuccinctProposerConfig,
+    payload: &SpanProofRequest,
+) -> Result<CheckedRpcOverrides, AppError> {
+    let rpc_overrides = state
+        .rpc_override_allowlist
+        .check(&payload.rpc_overrides)
+        .map_err(AppError)?;
+    if !rpc_overrides.is_empty() {
+        info!(
+            "Generating the witness of span {}-{} from L1 RPC {}, L2 RPC {} and beacon API {}",
+            payload.start,
+            payload.end,
+            display_override(rpc_overrides.l1_rpc.as_ref()),
+            display_override(rpc_overrides.l2_rpc.as_ref()),
+            display_override(rpc_overrides.beacon_rpc.as_ref()),
+        );
+    }
+    Ok(rpc_overrides)
+}
 
-    Ok((
-        StatusCode::OK,
-        Json(ProofResponse {
-            proof_id: proof_id.to_vec(),
-        }),
-    ))
+/// Format an overridden RPC for logging, or the server's RPC if it isn't overridden.
+fn display_override(url: Option<&Url>) -> String {
+    url.map_or_else(|| "<server default>".to_string(), redact_url)
+}
+
+/// Parse the callback URL of a proof request, and check that its host resolves to public
+/// addresses.
+async fn parse_callback_url(
+    state: &SuccinctProposerConfig,
+    callback_url: Option<&str>,
+) -> Result<Option<Url>, AppError> {
+    match callback_url {
+        Some(url) => Ok(Some(state.webhooks.check_callback_url(url).await?)),
+        None => Ok(None),
+    }
+}
+
+/// Record a span that failed witness generation, so the proposer splits it and the offending block
+/// can be investigated once the span has been narrowed down to it.
+async fn witness_generation_failed(
+    fetcher: &OPSuccinctDataFetcher,
+    metrics: &ServerMetrics,
+    payload: &SpanProofRequest,
+    error: anyhow::Error,
+) -> AppError {
+    let failure = UnexecutableSpan {
+        start: payload.start,
+        end: payload.end,
+        error,
+    };
+    error!("{}", failure);
+    if let Some(oversized) = failure.oversized_block() {
+        metrics.record_oversized_block(&oversized.policy.to_string());
+    }
+    if let Err(e) = write_span_failure_report(fetcher, &failure).await {
+        error!("Failed to write span failure report: {}", e);
+    }
+    AppError(failure.into())
 }
 
 /// Request an aggregation proof for a set of subproofs.
@@ -219,52 +1223,191 @@ async fn request_agg_proof(
     Json(payload): Json<AggProofRequest>,
 ) -> Result<(StatusCode, Json<ProofResponse>), AppError> {
     info!("Received agg proof request");
-    let mut proofs_with_pv: Vec<SP1ProofWithPublicValues> = payload
+    ensure_not_paused(&state)?;
+    let callback_url = parse_callback_url(&state, payload.callback_url.as_deref()).await?;
+    let proving = state.proving.get();
+
+    // Identical requests share the proof, so a proposer that restarts before recording the proof
+    // ID of an aggregation gets the proof it requested instead of aggregating the subproofs again.
+    // Requests differing only in their callback are identical.
+    let key = AggRequestKey {
+        request_hash: keccak256(serde_json::to_vec(&AggProofRequest {
+            callback_url: None,
+            ..payload.clone()
+        })?),
+        aggregation_vkey: proving.agg_vkey_hash,
+        range_vkey_commitment: proving.range_vkey_commitment,
+    };
+    let proof_id = *state
+        .proof_tracker
+        .agg_request(key)
+        .get_or_try_init(|| request_agg_proof_once(&state, &proving, &payload, key))
+        .await?;
+    if let Some(url) = callback_url {
+        state
+            .webhooks
+            .register(&state.proof_tracker, proof_id, ProofKind::Agg, url);
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(ProofResponse {
+            proof_id: proof_id.to_vec(),
+            witness_sha256: None,
+        }),
+    ))
+}
+
+/// Return the proof of an identical aggregation request made before the server restarted, or
+/// request the proof from the prover network and persist its ID before returning it, so a proposer
+/// that restarts before recording the ID gets the same proof.
+async fn request_agg_proof_once(
+    state: &SuccinctProposerConfig,
+    proving: &ProvingContext,
+    payload: &AggProofRequest,
+    key: AggRequestKey,
+) -> Result<B256, AppError> {
+    // A proof that turned out unfulfillable is requested again.
+    let requested = state.agg_requests.get(&key).filter(|proof_id| {
+        state.proof_tracker.status(proof_id).is_none_or(|status| {
+            status.fulfillment_status != FulfillmentStatus::Unfulfillable as i32
+        })
+    });
+    if let Some(proof_id) = requested {
+        info!("Returning agg proof {} requested before", proof_id);
+        // Track the proof again if the server restarted since, so it is polled.
+        if state.proof_tracker.status(&proof_id).is_none() {
+            state
+                .proof_tracker
+                .track(proof_id, None, &state.reconciler_config);
+        }
+        return Ok(proof_id);
+    }
+
+    let proof_id = request_agg_proof_from_network(state, proving, payload).await?;
+    state.agg_requests.record(key, proof_id)?;
+    Ok(proof_id)
+}
+
+/// Deserialize the subproofs of an aggregation request.
+fn decode_subproofs<T: DeserializeOwned>(
+    payload: &AggProofRequest,
+) -> Result<Vec<T>, BrokenSubproofChain> {
+    payload
         .subproofs
         .iter()
-        .map(|sp| bincode::deserialize(sp).unwrap())
-        .collect();
+        .enumerate()
+        .map(|(index, subproof)| {
+            bincode::deserialize(subproof).map_err(|e| BrokenSubproofChain::Undecodable {
+                index,
+                error: e.to_string(),
+            })
+        })
+        .collect()
+}
 
-    let boot_infos: Vec<BootInfoStruct> = proofs_with_pv
-        .iter_mut()
-        .map(|proof| proof.public_values.read())
-        .collect();
+/// Parse the `0x`-prefixed L1 head of an aggregation request.
+fn parse_l1_head(head: &str) -> Result<[u8; 32]> {
+    let Some(hex_str) = head.strip_prefix("0x") else {
+        bail!("Invalid L1 head format: missing 0x prefix");
+    };
+    let bytes = hex::decode(hex_str)
+        .map_err(|e| anyhow::anyhow!("Failed to decode L1 head hex string: {}", e))?;
+    let len = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid L1 head length: expected 32 bytes, got {}", len))
+}
 
-    let proofs: Vec<SP1Proof> = proofs_with_pv
-        .iter_mut()
-        .map(|proof| proof.proof.clone())
-        .collect();
+/// Build the stdin of an aggregation proof and request the proof from the prover network.
+async fn request_agg_proof_from_network(
+    state: &SuccinctProposerConfig,
+    proving: &ProvingContext,
+    payload: &AggProofRequest,
+) -> Result<B256, AppError> {
+    let (range_public_values_version, range_intermediate_outputs, boot_infos, span_proofs) =
+        match &proving.secondary_range {
+            None => {
+                let mut proofs_with_pv: Vec<SP1ProofWithPublicValues> = decode_subproofs(payload)?;
+
+                let public_values = || {
+                    proofs_with_pv
+                        .iter()
+                        .map(|proof| proof.public_values.as_slice())
+                };
+                let (version, boot_infos) = decode_boot_infos(public_values())?;
+                let intermediate_outputs = decode_range_intermediate_outputs(public_values())?;
 
-    let l1_head_bytes = match payload.head.strip_prefix("0x") {
-        Some(hex_str) => match hex::decode(hex_str) {
-            Ok(bytes) => bytes,
-            Err(e) => {
-                error!("Failed to decode L1 head hex string: {}", e);
-                return Err(AppError(anyhow::anyhow!(
-                    "Failed to decode L1 head hex string: {}",
-                    e
-                )));
+                let proofs: Vec<SP1Proof> = proofs_with_pv
+                    .iter_mut()
+                    .map(|proof| proof.proof.clone())
+                    .collect();
+                (
+                    version,
+                    intermediate_outputs,
+                    boot_infos,
+                    SpanProofs::Single(proofs),
+                )
             }
-        },
-        None => {
-            error!("Invalid L1 head format: missing 0x prefix");
-            return Err(AppError(anyhow::anyhow!(
-                "Invalid L1 head format: missing 0x prefix"
-            )));
-        }
-    };
+            // In quorum mode, each subproof holds the primary and secondary proofs of its span.
+            Some(secondary_range) => {
+                let quorum_proofs: Vec<QuorumSpanProof> = decode_subproofs(payload)?;
 
-    let l1_head: [u8; 32] = match l1_head_bytes.clone().try_into() {
-        Ok(array) => array,
-        Err(_) => {
-            error!(
-                "Invalid L1 head length: expected 32 bytes, got {}",
-                l1_head_bytes.len()
-            );
-            return Err(AppError(anyhow::anyhow!(
-                "Invalid L1 head length: expected 32 bytes, got {}",
-                l1_head_bytes.len()
-            )));
+                // The secondary proofs commit to the same public values as the primary proofs,
+                // which `boot_info` checks.
+                let public_values = || {
+                    quorum_proofs
+                        .iter()
+                        .map(|proof| proof.primary.public_values.as_slice())
+                };
+                let (version, _) = decode_boot_infos(public_values())?;
+                let intermediate_outputs = decode_range_intermediate_outputs(public_values())?;
+                let boot_infos = match quorum_proofs
+                    .iter()
+                    .enumerate()
+                    .map(|(index, proof)| proof.boot_info(index))
+                    .collect::<Result<Vec<_>, _>>()
+                {
+                    Ok(boot_infos) => boot_infos,
+                    Err(e) => {
+                        error!("Invalid subproofs: {}", e);
+                        return Err(AppError(e.into()));
+                    }
+                };
+
+                let proofs = quorum_proofs
+                    .into_iter()
+                    .map(|proof| (proof.primary.proof, proof.secondary.proof))
+                    .collect();
+                (
+                    version,
+                    intermediate_outputs,
+                    boot_infos,
+                    SpanProofs::Quorum(proofs, secondary_range.vk.clone()),
+                )
+            }
+        };
+
+    // Reject subproofs the aggregation program would fail on before spending time proving.
+    let checkpoint_overrides = payload
+        .checkpoint_overrides
+        .as_ref()
+        .map(requested_overrides)
+        .unwrap_or_default();
+    if let Err(e) =
+        validate_subproof_chain(&boot_infos, state.rollup_config_hash, &checkpoint_overrides)
+    {
+        error!("Invalid subproofs: {}", e);
+        return Err(AppError(e.into()));
+    }
+    let first_subproof_end = boot_infos[0].l2BlockNumber;
+    let last_subproof_end = boot_infos[boot_infos.len() - 1].l2BlockNumber;
+
+    let l1_head = match parse_l1_head(&payload.head) {
+        Ok(l1_head) => l1_head,
+        Err(e) => {
+            error!("{}", e);
+            return Err(AppError(e));
         }
     };
 
@@ -272,57 +1415,109 @@ async fn request_agg_proof(
         Ok(f) => f,
         Err(e) => {
             error!("Failed to create fetcher: {}", e);
-            return Err(AppError(anyhow::anyhow!("Failed to create fetcher: {}", e)));
+            return Err(AppError(with_code(
+                ErrorCode::RpcUnavailable,
+                anyhow::anyhow!("Failed to create fetcher: {}", e),
+            )));
         }
     };
 
+    if let Some(request) = &payload.checkpoint_overrides {
+        record_checkpoint_overrides(&fetcher, request, &boot_infos).await?;
+    }
+
     let headers = match fetcher
         .get_header_preimages(&boot_infos, l1_head.into())
         .await
     {
         Ok(h) => h,
+        // The checkpointed block is unusable for these span proofs, which the proposer fixes by
+        // checkpointing another one.
+        Err(e) if e.is::<AggStdinError>() => {
+            error!("Failed to get header preimages: {}", e);
+            return Err(AppError(e));
+        }
         Err(e) => {
             error!("Failed to get header preimages: {}", e);
-            return Err(AppError(anyhow::anyhow!(
-                "Failed to get header preimages: {}",
-                e
+            return Err(AppError(with_code(
+                ErrorCode::RpcUnavailable,
+                anyhow::anyhow!("Failed to get header preimages: {}", e),
             )));
         }
     };
 
-    let stdin =
-        match get_agg_proof_stdin(proofs, boot_infos, headers, &state.range_vk, l1_head.into()) {
-            Ok(s) => s,
-            Err(e) => {
-                error!("Failed to get agg proof stdin: {}", e);
-                return Err(AppError(anyhow::anyhow!(
-                    "Failed to get agg proof stdin: {}",
-                    e
-                )));
-            }
-        };
+    let stdin = match span_proofs {
+        SpanProofs::Single(proofs) => get_agg_proof_stdin(
+            proofs,
+            boot_infos,
+            range_public_values_version,
+            range_intermediate_outputs,
+            headers,
+            &proving.range_vk,
+            l1_head.into(),
+            checkpoint_overrides,
+        ),
+        SpanProofs::Quorum(proofs, secondary_vk) => get_quorum_agg_proof_stdin(
+            proofs,
+            boot_infos,
+            range_public_values_version,
+            range_intermediate_outputs,
+            headers,
+            &proving.range_vk,
+            &secondary_vk,
+            l1_head.into(),
+            checkpoint_overrides,
+        ),
+    };
+    let stdin = match stdin {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to get agg proof stdin: {}", e);
+            return Err(AppError(e));
+        }
+    };
 
+    chaos::inject(FailurePoint::Prover).map_err(AppError)?;
     let proof_id = match state
         .network_prover
-        .prove(&state.agg_pk, &stdin)
-        .mode(state.agg_proof_mode)
-        .strategy(state.agg_proof_strategy)
+        .prove(&proving.agg_pk, &stdin)
+        .mode(proving.agg_proof_mode)
+        .strategy(proving.agg_proof_strategy)
         .request_async()
         .await
     {
         Ok(id) => id,
         Err(e) => {
             error!("Failed to request proof: {}", e);
-            return Err(AppError(anyhow::anyhow!("Failed to request proof: {}", e)));
+            return Err(AppError(with_code(
+                ErrorCode::ProverUnavailable,
+                anyhow::anyhow!("Failed to request proof: {}", e),
+            )));
         }
     };
+    info!("Requested agg proof {}", proof_id);
+    // The aggregation proof starts where its first subproof starts, which is known if the
+    // subproof was requested from this server.
+    let blocks = state
+        .proof_tracker
+        .span_start(first_subproof_end)
+        .map(|start| ProvenBlocks {
+            kind: ProofKind::Agg,
+            start,
+            end: last_subproof_end,
+        });
+    state
+        .proof_tracker
+        .track(proof_id, blocks, &state.reconciler_config);
 
-    Ok((
-        StatusCode::OK,
-        Json(ProofResponse {
-            proof_id: proof_id.to_vec(),
-        }),
-    ))
+    Ok(proof_id)
+}
+
+/// The range proofs of the subproofs of an aggregation request.
+enum SpanProofs {
+    Single(Vec<SP1Proof>),
+    /// The primary and secondary proofs of each span and the secondary range vkey, in quorum mode.
+    Quorum(Vec<(SP1Proof, SP1Proof)>, Arc<SP1VerifyingKey>),
 }
 
 /// Request a mock proof for a span of blocks.
@@ -331,15 +1526,18 @@ async fn request_mock_span_proof(
     Json(payload): Json<SpanProofRequest>,
 ) -> Result<(StatusCode, Json<ProofStatus>), AppError> {
     info!("Received mock span proof request: {:?}", payload);
-    let fetcher = match OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Docker).await {
+    ensure_not_paused(&state)?;
+    let rpc_overrides = check_rpc_overrides(&state, &payload)?;
+    let proving = state.proving.get();
+    let fetcher = match rpc_overrides.fetcher(RunContext::Docker).await {
         Ok(f) => f,
         Err(e) => {
             error!("Failed to create data fetcher: {}", e);
-            return Err(AppError(e));
+            return Err(AppError(with_code(ErrorCode::RpcUnavailable, e)));
         }
     };
 
-    let host_args = match fetcher
+    let mut host_args = match fetcher
         .get_host_args(
             payload.start,
             payload.end,
@@ -355,9 +1553,21 @@ async fn request_mock_span_proof(
             return Err(AppError(e));
         }
     };
+    rpc_overrides.apply(&mut host_args);
+    apply_claim(&mut host_args, payload);
 
     let start_time = Instant::now();
-    let oracle = start_server_and_native_client(host_args.clone()).await?;
+    let witness_generation = (
+        state.metrics.witness_generation(),
+        state.witnessgen_runs.track(host_args.progress.clone()),
+    );
+    let oracle = match start_server_and_native_client(host_args.clone()).await {
+        Ok(oracle) => oracle,
+        Err(e) => {
+            return Err(witness_generation_failed(&fetcher, &state.metrics, &payload, e).await)
+        }
+    };
+    drop(witness_generation);
     let witness_generation_duration = start_time.elapsed();
 
     let sp1_stdin = match get_proof_stdin(oracle) {
@@ -372,7 +1582,18 @@ async fn request_mock_span_proof(
 
     // Note(ratan): In a future version of the server which only supports mock proofs, Arc<MockProver> should be used to reduce memory usage.
     let prover = ProverClient::builder().mock().build();
-    let (pv, report) = prover.execute(RANGE_ELF, &sp1_stdin).run().unwrap();
+    let panic_report = PanicReportCollector::default();
+    let (pv, report) = match prover
+        .execute(&proving.range_elf, &sp1_stdin)
+        .with_hook(PanicReportCollector::FD, panic_report.hook())
+        .run()
+    {
+        Ok(result) => result,
+        Err(e) => {
+            let e = panic_report.explain(e);
+            return Err(witness_generation_failed(&fetcher, &state.metrics, &payload, e).await);
+        }
+    };
     let execution_duration = start_time.elapsed();
 
     let block_data = fetcher
@@ -392,19 +1613,18 @@ async fn request_mock_span_proof(
 
     let l2_chain_id = fetcher.get_l2_chain_id().await?;
     // Save the report to disk.
-    let report_dir = format!("execution-reports/{}", l2_chain_id);
-    if !std::path::Path::new(&report_dir).exists() {
-        fs::create_dir_all(&report_dir)?;
-    }
-
-    let report_path = format!("{}/{}-{}.json", report_dir, payload.start, payload.end);
+    let report_path =
+        fetcher
+            .workspace()?
+            .execution_report_path(l2_chain_id, payload.start, payload.end);
+    fs::create_dir_all(report_path.parent().unwrap())?;
     // Write to CSV.
     let mut csv_writer = csv::Writer::from_path(report_path)?;
     csv_writer.serialize(&stats)?;
     csv_writer.flush()?;
 
     let proof = SP1ProofWithPublicValues::create_mock_proof(
-        &state.range_pk,
+        &proving.range_pk,
         pv.clone(),
         SP1ProofMode::Compressed,
         SP1_CIRCUIT_VERSION,
@@ -418,6 +1638,9 @@ async fn request_mock_span_proof(
             fulfillment_status: FulfillmentStatus::Fulfilled.into(),
             execution_status: ExecutionStatus::UnspecifiedExecutionStatus.into(),
             proof: proof_bytes,
+            metadata: None,
+            proof_url: None,
+            error_code: None,
         }),
     ))
 }
@@ -428,42 +1651,50 @@ async fn request_mock_agg_proof(
     Json(payload): Json<AggProofRequest>,
 ) -> Result<(StatusCode, Json<ProofStatus>), AppError> {
     info!("Received mock agg proof request!");
+    ensure_not_paused(&state)?;
+    let proving = state.proving.get();
 
-    let mut proofs_with_pv: Vec<SP1ProofWithPublicValues> = payload
-        .subproofs
-        .iter()
-        .map(|sp| bincode::deserialize(sp).unwrap())
-        .collect();
+    let mut proofs_with_pv: Vec<SP1ProofWithPublicValues> = decode_subproofs(&payload)?;
 
-    let boot_infos: Vec<BootInfoStruct> = proofs_with_pv
-        .iter_mut()
-        .map(|proof| proof.public_values.read())
-        .collect();
+    let public_values = || {
+        proofs_with_pv
+            .iter()
+            .map(|proof| proof.public_values.as_slice())
+    };
+    let (range_public_values_version, boot_infos) = decode_boot_infos(public_values())?;
+    let range_intermediate_outputs = decode_range_intermediate_outputs(public_values())?;
+
+    // Reject subproofs the aggregation program would fail on before spending time proving.
+    let checkpoint_overrides = payload
+        .checkpoint_overrides
+        .as_ref()
+        .map(requested_overrides)
+        .unwrap_or_default();
+    if let Err(e) =
+        validate_subproof_chain(&boot_infos, state.rollup_config_hash, &checkpoint_overrides)
+    {
+        error!("Invalid subproofs: {}", e);
+        return Err(AppError(e.into()));
+    }
 
     let proofs: Vec<SP1Proof> = proofs_with_pv
         .iter_mut()
         .map(|proof| proof.proof.clone())
         .collect();
 
-    let l1_head_bytes = match hex::decode(
-        payload
-            .head
-            .strip_prefix("0x")
-            .expect("Invalid L1 head, no 0x prefix."),
-    ) {
-        Ok(bytes) => bytes,
+    let l1_head = match parse_l1_head(&payload.head) {
+        Ok(l1_head) => l1_head,
         Err(e) => {
-            error!("Failed to decode L1 head: {}", e);
-            return Err(AppError(anyhow::anyhow!("Failed to decode L1 head: {}", e)));
+            error!("{}", e);
+            return Err(AppError(e));
         }
     };
-    let l1_head: [u8; 32] = l1_head_bytes.try_into().unwrap();
 
     let fetcher = match OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Docker).await {
         Ok(f) => f,
         Err(e) => {
             error!("Failed to create data fetcher: {}", e);
-            return Err(AppError(e));
+            return Err(AppError(with_code(ErrorCode::RpcUnavailable, e)));
         }
     };
     let headers = match fetcher
@@ -477,20 +1708,28 @@ async fn request_mock_agg_proof(
         }
     };
 
-    let stdin =
-        match get_agg_proof_stdin(proofs, boot_infos, headers, &state.range_vk, l1_head.into()) {
-            Ok(s) => s,
-            Err(e) => {
-                error!("Failed to get aggregation proof stdin: {}", e);
-                return Err(AppError(e));
-            }
-        };
+    let stdin = match get_agg_proof_stdin(
+        proofs,
+        boot_infos,
+        range_public_values_version,
+        range_intermediate_outputs,
+        headers,
+        &proving.range_vk,
+        l1_head.into(),
+        checkpoint_overrides,
+    ) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to get aggregation proof stdin: {}", e);
+            return Err(AppError(e));
+        }
+    };
 
     // Note(ratan): In a future version of the server which only supports mock proofs, Arc<MockProver> should be used to reduce memory usage.
     let prover = ProverClient::builder().mock().build();
     let proof = match prover
-        .prove(&state.agg_pk, &stdin)
-        .mode(state.agg_proof_mode)
+        .prove(&proving.agg_pk, &stdin)
+        .mode(proving.agg_proof_mode)
         .deferred_proof_verification(false)
         .run()
     {
@@ -506,30 +1745,83 @@ async fn request_mock_agg_proof(
         Json(ProofStatus {
             fulfillment_status: FulfillmentStatus::Fulfilled.into(),
             execution_status: ExecutionStatus::UnspecifiedExecutionStatus.into(),
-            proof: proof.bytes(),
+            proof: proof_bytes(&proof)?,
+            metadata: None,
+            proof_url: None,
+            error_code: None,
         }),
     ))
 }
 
-/// Get the status of a proof.
+/// Parse the hex-encoded ID of a proof in a request's path, or respond with `400` if it isn't one.
+fn parse_proof_id(proof_id: &str) -> Result<B256, Response> {
+    hex::decode(proof_id)
+        .ok()
+        .and_then(|bytes| B256::try_from(bytes.as_slice()).ok())
+        .ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                "Invalid proof ID: expected 32 hex-encoded bytes",
+            )
+                .into_response()
+        })
+}
+
+/// Get the status of a proof. Proofs larger than `PROOF_INLINE_MAX_BYTES` are replaced by the
+/// URL they are downloaded from.
 async fn get_proof_status(
     State(state): State<SuccinctProposerConfig>,
     Path(proof_id): Path<String>,
-) -> Result<(StatusCode, Json<ProofStatus>), AppError> {
+) -> Result<Response, AppError> {
     info!("Received proof status request: {:?}", proof_id);
 
-    let proof_id_bytes = hex::decode(proof_id)?;
+    let proof_id = match parse_proof_id(&proof_id) {
+        Ok(proof_id) => proof_id,
+        Err(response) => return Ok(response),
+    };
+    let mut status = proof_status(&state, proof_id).await?;
+    externalize_proof(&mut status, &proof_id, state.proof_inline_max_bytes);
+    Ok((StatusCode::OK, Json(status)).into_response())
+}
+
+/// Download a fulfilled proof as raw bytes, with support for byte ranges.
+async fn download_proof(
+    State(state): State<SuccinctProposerConfig>,
+    Path(proof_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let proof_id = match parse_proof_id(&proof_id) {
+        Ok(proof_id) => proof_id,
+        Err(response) => return Ok(response),
+    };
+    let status = proof_status(&state, proof_id).await?;
+    if status.fulfillment_status != FulfillmentStatus::Fulfilled as i32 || status.proof.is_empty() {
+        return Ok((
+            StatusCode::NOT_FOUND,
+            format!("Proof {proof_id} is not fulfilled"),
+        )
+            .into_response());
+    }
+    Ok(serve_proof(&headers, status.proof))
+}
+
+/// The status of a proof, with the proof once it is fulfilled.
+async fn proof_status(
+    state: &SuccinctProposerConfig,
+    proof_id: B256,
+) -> Result<ProofStatus, AppError> {
+    // Proofs requested by this server are polled by the reconciler, so their status is available
+    // locally.
+    if let Some(status) = state.proof_tracker.status(&proof_id) {
+        return Ok(status);
+    }
 
     // This request will time out if the server is down.
-    let (status, maybe_proof) = match state
-        .network_prover
-        .get_proof_status(B256::from_slice(&proof_id_bytes))
-        .await
-    {
+    let (status, maybe_proof) = match state.network_prover.get_proof_status(proof_id).await {
         Ok(res) => res,
         Err(e) => {
             error!("Failed to get proof status: {}", e);
-            return Err(AppError(e));
+            return Err(AppError(with_code(ErrorCode::ProverUnavailable, e)));
         }
     };
 
@@ -543,87 +1835,66 @@ async fn get_proof_status(
         error!(
             "Proof request timed out on the server. Default timeout is set to 4 hours. Returning status as Unfulfillable."
         );
-        return Ok((
-            StatusCode::OK,
-            Json(ProofStatus {
-                fulfillment_status: FulfillmentStatus::Unfulfillable.into(),
-                execution_status: ExecutionStatus::Executed.into(),
-                proof: vec![],
-            }),
-        ));
+        return Ok(ProofStatus {
+            fulfillment_status: FulfillmentStatus::Unfulfillable.into(),
+            execution_status: ExecutionStatus::Executed.into(),
+            proof: vec![],
+            metadata: None,
+            proof_url: None,
+            error_code: Some(ErrorCode::ProverTimeout),
+        });
     }
 
     let fulfillment_status = status.fulfillment_status;
     let execution_status = status.execution_status;
-    if fulfillment_status == FulfillmentStatus::Fulfilled as i32 {
-        let proof: SP1ProofWithPublicValues = maybe_proof.unwrap();
-
-        match proof.proof {
-            SP1Proof::Compressed(_) => {
-                // If it's a compressed proof, we need to serialize the entire struct with bincode.
-                // Note: We're re-serializing the entire struct with bincode here, but this is fine
-                // because we're on localhost and the size of the struct is small.
-                let proof_bytes = bincode::serialize(&proof).unwrap();
-                return Ok((
-                    StatusCode::OK,
-                    Json(ProofStatus {
-                        fulfillment_status,
-                        execution_status,
-                        proof: proof_bytes,
-                    }),
-                ));
-            }
-            SP1Proof::Groth16(_) => {
-                // If it's a groth16 proof, we need to get the proof bytes that we put on-chain.
-                let proof_bytes = proof.bytes();
-                return Ok((
-                    StatusCode::OK,
-                    Json(ProofStatus {
-                        fulfillment_status,
-                        execution_status,
-                        proof: proof_bytes,
-                    }),
-                ));
-            }
-            SP1Proof::Plonk(_) => {
-                // If it's a plonk proof, we need to get the proof bytes that we put on-chain.
-                let proof_bytes = proof.bytes();
-                return Ok((
-                    StatusCode::OK,
-                    Json(ProofStatus {
-                        fulfillment_status,
-                        execution_status,
-                        proof: proof_bytes,
-                    }),
-                ));
-            }
-            _ => (),
+    let proof = match maybe_proof {
+        Some(proof) if fulfillment_status == FulfillmentStatus::Fulfilled as i32 => {
+            proof_bytes(&proof)?
         }
-    } else if fulfillment_status == FulfillmentStatus::Unfulfillable as i32 {
-        return Ok((
-            StatusCode::OK,
-            Json(ProofStatus {
-                fulfillment_status,
-                execution_status,
-                proof: vec![],
-            }),
-        ));
-    }
-    Ok((
-        StatusCode::OK,
-        Json(ProofStatus {
-            fulfillment_status,
-            execution_status,
-            proof: vec![],
-        }),
-    ))
+        _ => vec![],
+    };
+
+    Ok(ProofStatus {
+        fulfillment_status,
+        execution_status,
+        proof,
+        metadata: None,
+        proof_url: None,
+        error_code: (fulfillment_status == FulfillmentStatus::Unfulfillable as i32)
+            .then_some(ErrorCode::ProverUnfulfillable),
+    })
 }
 
 pub struct AppError(anyhow::Error);
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        (StatusCode::INTERNAL_SERVER_ERROR, format!("{}", self.0)).into_response()
+        let status = if is_unprocessable(&self.0) {
+            StatusCode::UNPROCESSABLE_ENTITY
+        } else if is_unavailable(&self.0) {
+            StatusCode::SERVICE_UNAVAILABLE
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        };
+        let code = error_code(&self.0);
+        error!("Responding with {} ({}): {}", status, code, self.0);
+        let mut headers = HeaderMap::new();
+        headers.insert(ERROR_CODE_HEADER, code.as_str().parse().unwrap());
+        if let Some(failure) = self.0.downcast_ref::<UnexecutableSpan>() {
+            let split_points = failure.split_points();
+            if !split_points.is_empty() {
+                let split_points = split_points
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",");
+                headers.insert(SPLIT_AT_HEADER, split_points.parse().unwrap());
+            }
+        }
+        let mut response = (status, headers, format!("{}", self.0)).into_response();
+        // Read by the metrics middleware, to count failures by code.
+        response.extensions_mut().insert(code);
+        response
     }
 }
 
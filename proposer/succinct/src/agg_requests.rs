@@ -0,0 +1,147 @@
+//! The proof IDs of the aggregation proofs the server requested, persisted to
+//! `{l2_chain_id}/agg-requests.json` in the workspace.
+//!
+//! The proposer records the proof ID of an aggregation in its database once the server returns it.
+//! If the proposer stops before that, it sends the same request again when it restarts, and the
+//! server returns the proof it already requested rather than paying for the aggregation twice, even
+//! if the server was restarted in between.
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::PathBuf,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use alloy_primitives::B256;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::reconciler::AggRequestKey;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct AggRequestRecord {
+    #[serde(flatten)]
+    key: AggRequestKey,
+    proof_id: B256,
+    requested_at: u64,
+}
+
+/// The proof IDs of the aggregation requests, kept in memory and mirrored to a file.
+#[derive(Debug)]
+pub struct AggRequestStore {
+    path: PathBuf,
+    /// How long a request is kept, after which an identical request requests a new proof.
+    retention_secs: u64,
+    requests: Mutex<HashMap<AggRequestKey, AggRequestRecord>>,
+}
+
+impl AggRequestStore {
+    /// Load the aggregation requests from the file at `path`, dropping those older than
+    /// `retention_secs`. There are none if it doesn't exist.
+    pub fn load(path: PathBuf, retention_secs: u64) -> Result<Self> {
+        let records: Vec<AggRequestRecord> = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("Invalid aggregation requests in {}", path.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e).context(format!("Failed to read {}", path.display())),
+        };
+        let now = unix_now();
+        let requests = records
+            .into_iter()
+            .filter(|record| now.saturating_sub(record.requested_at) < retention_secs)
+            .map(|record| (record.key, record))
+            .collect();
+        Ok(Self {
+            path,
+            retention_secs,
+            requests: Mutex::new(requests),
+        })
+    }
+
+    /// The proof ID of an identical aggregation request, if one was made within the retention.
+    pub fn get(&self, key: &AggRequestKey) -> Option<B256> {
+        let now = unix_now();
+        self.requests
+            .lock()
+            .unwrap()
+            .get(key)
+            .filter(|record| now.saturating_sub(record.requested_at) < self.retention_secs)
+            .map(|record| record.proof_id)
+    }
+
+    /// Record the proof ID of an aggregation request, replacing any earlier proof of the same
+    /// request, and evict the requests past their retention. The file is written before the
+    /// request is recorded in memory, so a failure leaves both unchanged.
+    pub fn record(&self, key: AggRequestKey, proof_id: B256) -> Result<()> {
+        let now = unix_now();
+        let mut requests = self.requests.lock().unwrap();
+        let mut updated = requests.clone();
+        updated.retain(|_, record| now.saturating_sub(record.requested_at) < self.retention_secs);
+        updated.insert(
+            key,
+            AggRequestRecord {
+                key,
+                proof_id,
+                requested_at: now,
+            },
+        );
+
+        fs::create_dir_all(self.path.parent().unwrap())?;
+        // Write to a temporary file and rename it, so a crash doesn't leave a torn file.
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(
+            &tmp,
+            serde_json::to_vec_pretty(&updated.values().collect::<Vec<_>>())?,
+        )?;
+        fs::rename(&tmp, &self.path)?;
+
+        *requests = updated;
+        Ok(())
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> AggRequestKey {
+        AggRequestKey {
+            request_hash: B256::repeat_byte(byte),
+            aggregation_vkey: B256::repeat_byte(0xaa),
+            range_vkey_commitment: B256::repeat_byte(0xbb),
+        }
+    }
+
+    #[test]
+    fn test_agg_requests_survive_restart() {
+        let dir = std::env::temp_dir().join(format!("agg-requests-test-{}", std::process::id()));
+        let path = dir.join("10").join("agg-requests.json");
+
+        let store = AggRequestStore::load(path.clone(), 3600).unwrap();
+        assert_eq!(store.get(&key(1)), None);
+        store.record(key(1), B256::repeat_byte(0x11)).unwrap();
+        store.record(key(2), B256::repeat_byte(0x22)).unwrap();
+        // A new proof of the same request replaces the first one.
+        store.record(key(1), B256::repeat_byte(0x33)).unwrap();
+
+        let restarted = AggRequestStore::load(path.clone(), 3600).unwrap();
+        assert_eq!(restarted.get(&key(1)), Some(B256::repeat_byte(0x33)));
+        assert_eq!(restarted.get(&key(2)), Some(B256::repeat_byte(0x22)));
+        assert_eq!(restarted.get(&key(3)), None);
+
+        // Requests past their retention are evicted.
+        let expired = AggRequestStore::load(path, 0).unwrap();
+        assert_eq!(expired.get(&key(1)), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
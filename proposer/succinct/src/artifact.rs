@@ -0,0 +1,182 @@
+//! Auditable artifacts of fulfilled span proofs.
+//!
+//! The span proofs the server returns are bincode-encoded SP1 proofs, which can't be inspected
+//! without the SP1 SDK. When `PROOF_ARTIFACTS` is enabled, the server writes a JSON artifact for
+//! every fulfilled span proof to the chain's `artifacts` directory in the workspace, with the boot
+//! info it commits to, the vkey it was proven against and the SHA-256 hashes of the proof and of
+//! the witness it was proven from, and serves it at `GET /proofs/:proof_id/artifact`. Auditors can
+//! check a proof's claims from the artifact, and replay its verification against the vkey and
+//! public values. If witness encryption is enabled, the artifacts are encrypted on disk and
+//! decrypted when served.
+
+use std::{collections::HashMap, fs, io, path::PathBuf, sync::Mutex};
+
+use alloy_primitives::{Bytes, B256};
+use anyhow::{anyhow, Result};
+use op_succinct_client_utils::{boot::BootInfoStruct, public_values::decode_boot_info};
+use op_succinct_host_utils::encryption::WitnessKey;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_sdk::SP1ProofWithPublicValues;
+
+use crate::quorum::QuorumSpanProof;
+
+/// The claims of a fulfilled span proof.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SpanProofArtifact {
+    pub proof_id: B256,
+    /// The first L2 block of the proven range, whose output root is `l2_pre_root`. Not recorded by
+    /// artifacts written before it was added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_start_block: Option<u64>,
+    pub l1_head: B256,
+    pub l2_pre_root: B256,
+    pub l2_post_root: B256,
+    pub l2_block_number: u64,
+    pub rollup_config_hash: B256,
+    /// The vkey hash of the range program that generated the proof.
+    pub range_vkey: B256,
+    /// In quorum mode, the vkey hash of the secondary range program, which proved the same boot
+    /// info.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_range_vkey: Option<B256>,
+    /// The public values of the range program's proof, starting with the encoded boot info.
+    pub public_values: Bytes,
+    pub sp1_version: String,
+    /// The SHA-256 hash of the proof as returned by the status endpoint.
+    pub proof_sha256: B256,
+    /// The SHA-256 hash of the witness the proof was proven from, see [`crate::custody`]. Not
+    /// recorded by artifacts written before it was added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness_sha256: Option<B256>,
+}
+
+/// What a span proof was requested with, recorded for its artifact.
+#[derive(Debug, Clone, Copy)]
+struct SpanProofRequestRecord {
+    l2_start_block: u64,
+    vkeys: SpanProofVkeys,
+    witness_sha256: B256,
+}
+
+/// The vkey hashes of the range programs a span proof was requested with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpanProofVkeys {
+    pub range_vkey: B256,
+    pub secondary_range_vkey: Option<B256>,
+}
+
+/// The artifacts of fulfilled span proofs, stored as `{dir}/{proof_id}.json`.
+#[derive(Debug)]
+pub struct ProofArtifacts {
+    dir: PathBuf,
+    key: Option<WitnessKey>,
+    /// The start blocks, vkeys and witness hashes of the span proofs whose artifacts are yet to be
+    /// written. They are recorded per proof, as reloading the server may change the range programs
+    /// while proofs are in flight.
+    requests: Mutex<HashMap<B256, SpanProofRequestRecord>>,
+}
+
+impl ProofArtifacts {
+    pub fn new(dir: PathBuf, key: Option<WitnessKey>) -> Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            key,
+            requests: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Record the start block, vkeys and witness hash a span proof was requested with, for its
+    /// artifact.
+    pub fn record_request(
+        &self,
+        proof_id: B256,
+        l2_start_block: u64,
+        vkeys: SpanProofVkeys,
+        witness_sha256: B256,
+    ) {
+        self.requests.lock().unwrap().insert(
+            proof_id,
+            SpanProofRequestRecord {
+                l2_start_block,
+                vkeys,
+                witness_sha256,
+            },
+        );
+    }
+
+    fn path(&self, proof_id: &B256) -> PathBuf {
+        self.dir.join(format!("{proof_id}.json"))
+    }
+
+    /// Decode a fulfilled span proof, as returned by the status endpoint, into its artifact.
+    pub fn artifact(&self, proof_id: B256, proof: &[u8]) -> Result<SpanProofArtifact> {
+        let SpanProofRequestRecord {
+            l2_start_block,
+            vkeys,
+            witness_sha256,
+        } = self
+            .requests
+            .lock()
+            .unwrap()
+            .get(&proof_id)
+            .copied()
+            .ok_or_else(|| anyhow!("No request recorded for span proof {proof_id}"))?;
+        let (boot_info, range_proof): (BootInfoStruct, _) = match vkeys.secondary_range_vkey {
+            Some(_) => {
+                let quorum_proof: QuorumSpanProof = bincode::deserialize(proof)?;
+                (quorum_proof.boot_info(0)?, quorum_proof.primary)
+            }
+            None => {
+                let range_proof: SP1ProofWithPublicValues = bincode::deserialize(proof)?;
+                let (_, boot_info) = decode_boot_info(range_proof.public_values.as_slice())?;
+                (boot_info, range_proof)
+            }
+        };
+        Ok(SpanProofArtifact {
+            proof_id,
+            l2_start_block: Some(l2_start_block),
+            l1_head: boot_info.l1Head,
+            l2_pre_root: boot_info.l2PreRoot,
+            l2_post_root: boot_info.l2PostRoot,
+            l2_block_number: boot_info.l2BlockNumber,
+            rollup_config_hash: boot_info.rollupConfigHash,
+            range_vkey: vkeys.range_vkey,
+            secondary_range_vkey: vkeys.secondary_range_vkey,
+            public_values: Bytes::copy_from_slice(range_proof.public_values.as_slice()),
+            sp1_version: range_proof.sp1_version,
+            proof_sha256: B256::from(<[u8; 32]>::from(Sha256::digest(proof))),
+            witness_sha256: Some(witness_sha256),
+        })
+    }
+
+    /// Write the artifact of a fulfilled span proof.
+    pub fn write(&self, proof_id: B256, proof: &[u8]) -> Result<()> {
+        let artifact = self.artifact(proof_id, proof)?;
+        // Write to a temporary file first, so a partially written artifact is never served.
+        let tmp = self.dir.join(format!("{proof_id}.json.tmp"));
+        let mut contents = serde_json::to_vec_pretty(&artifact)?;
+        if let Some(key) = &self.key {
+            contents = key.encrypt(proof_id.as_slice(), &contents)?;
+        }
+        fs::write(&tmp, contents)?;
+        fs::rename(tmp, self.path(&proof_id))?;
+        self.requests.lock().unwrap().remove(&proof_id);
+        Ok(())
+    }
+
+    /// Read the artifact of a span proof, if it was written.
+    pub fn read(&self, proof_id: &B256) -> Result<Option<SpanProofArtifact>> {
+        match fs::read(self.path(proof_id)) {
+            Ok(mut artifact) => {
+                if let Some(key) = &self.key {
+                    artifact = key.decrypt(proof_id.as_slice(), &artifact)?;
+                }
+                Ok(Some(serde_json::from_slice(&artifact)?))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
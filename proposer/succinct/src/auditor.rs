@@ -0,0 +1,263 @@
+//! Periodic re-verification of the output roots proposed on-chain.
+//!
+//! Every proposed output root is proven, so an output root the L2 chain doesn't agree with means
+//! the proving pipeline, the vkeys or the rollup config on the L2OO were tampered with. When
+//! `AUDIT_INTERVAL_SECS` is set, the [`OutputAuditor`] periodically checks a sample of the outputs
+//! on the L2OO against output roots recomputed from the L2 RPC: the latest output, and outputs from
+//! a cursor walking back through the older ones, so every output is eventually checked again.
+//!
+//! Mismatches are logged, counted in the [`AuditStatus`] served at `GET /audit_status`, and posted
+//! to `AUDIT_WEBHOOK_URL` if set. An output that can't be checked, e.g. because the L2 RPC has
+//! pruned its state, is counted as a failed check and skipped, so it doesn't hold up the audits of
+//! the others. Only when an RPC is unavailable is the sample retried on the next audit.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use alloy_primitives::{Address, B256};
+use alloy_provider::RootProvider;
+use anyhow::{Context, Result};
+use log::{error, info};
+use op_succinct_host_utils::{
+    config::{env_var, url_var, Url},
+    fetcher::OPSuccinctDataFetcher,
+    output_root::OutputRootFields,
+};
+use serde::Serialize;
+
+use crate::{
+    contract::OPSuccinctL2OutputOracle::{self, OPSuccinctL2OutputOracleInstance},
+    error_code::{error_code, is_transient},
+};
+
+/// The default number of outputs checked per audit.
+const DEFAULT_SAMPLE_SIZE: u64 = 4;
+
+/// Configures the output auditor.
+#[derive(Debug, Clone)]
+pub struct AuditorConfig {
+    pub l2oo_address: Address,
+    pub interval: Duration,
+    /// The number of outputs checked per audit, including the latest.
+    pub sample_size: u64,
+    pub webhook_url: Option<Url>,
+}
+
+impl AuditorConfig {
+    /// Read the auditor config from the environment. The auditor is disabled unless
+    /// `AUDIT_INTERVAL_SECS` is set.
+    ///
+    /// - `L2OO_ADDRESS`: Required if enabled.
+    /// - `AUDIT_SAMPLE_SIZE`: Default: 4.
+    /// - `AUDIT_WEBHOOK_URL`: Default: unset.
+    pub fn from_env() -> Result<Option<Self>> {
+        let Some(interval_secs) = env_var::<u64>("AUDIT_INTERVAL_SECS")? else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            l2oo_address: env_var("L2OO_ADDRESS")?
+                .context("L2OO_ADDRESS must be set when AUDIT_INTERVAL_SECS is set")?,
+            interval: Duration::from_secs(interval_secs),
+            sample_size: env_var("AUDIT_SAMPLE_SIZE")?
+                .unwrap_or(DEFAULT_SAMPLE_SIZE)
+                .max(1),
+            webhook_url: url_var("AUDIT_WEBHOOK_URL")?,
+        }))
+    }
+}
+
+/// An output on the L2OO whose output root differs from the one recomputed from the L2 RPC.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutputMismatch {
+    pub l2oo_address: Address,
+    pub output_index: u64,
+    pub l2_block_number: u64,
+    pub proposed_output_root: B256,
+    pub expected_output_root: B256,
+    pub detected_at: u64,
+}
+
+/// An output on the L2OO that couldn't be checked for a reason other than an unavailable RPC.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FailedCheck {
+    pub output_index: u64,
+    pub error: String,
+    pub failed_at: u64,
+}
+
+/// The outcome of the audits so far.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AuditStatus {
+    pub outputs_checked: u64,
+    pub mismatches: u64,
+    pub failed_checks: u64,
+    pub last_audit_at: Option<u64>,
+    pub last_mismatch: Option<OutputMismatch>,
+    pub last_failed_check: Option<FailedCheck>,
+}
+
+/// The indices of the outputs to check when the latest output index is `latest`: the latest, and
+/// `sample_size - 1` older outputs walking back from `cursor`, wrapping around to the latest. Also
+/// returns the cursor for the next audit.
+fn sample_indices(latest: u64, cursor: Option<u64>, sample_size: u64) -> (Vec<u64>, Option<u64>) {
+    let mut indices = vec![latest];
+    if latest == 0 {
+        return (indices, None);
+    }
+    let older = (sample_size - 1).min(latest);
+    let mut next = cursor
+        .filter(|&cursor| cursor < latest)
+        .unwrap_or(latest - 1);
+    for _ in 0..older {
+        indices.push(next);
+        next = next.checked_sub(1).unwrap_or(latest - 1);
+    }
+    (indices, Some(next))
+}
+
+/// Checks the outputs on the L2OO against output roots recomputed from the L2 RPC.
+pub struct OutputAuditor {
+    config: AuditorConfig,
+    l2oo: OPSuccinctL2OutputOracleInstance<(), Arc<RootProvider>>,
+    fetcher: OPSuccinctDataFetcher,
+    status: Arc<Mutex<AuditStatus>>,
+    client: reqwest::Client,
+}
+
+impl OutputAuditor {
+    pub fn new(
+        config: AuditorConfig,
+        fetcher: OPSuccinctDataFetcher,
+        status: Arc<Mutex<AuditStatus>>,
+    ) -> Self {
+        Self {
+            l2oo: OPSuccinctL2OutputOracle::new(config.l2oo_address, fetcher.l1_provider.clone()),
+            config,
+            fetcher,
+            status,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Audit a sample of the outputs every interval. Failures are logged and the audit is retried
+    /// on the next interval.
+    pub async fn run(self) {
+        let mut cursor = None;
+        let mut interval = tokio::time::interval(self.config.interval);
+        loop {
+            interval.tick().await;
+            match self.audit(cursor).await {
+                Ok(next) => cursor = next,
+                Err(e) => error!("Failed to audit the proposed outputs: {:#}", e),
+            }
+        }
+    }
+
+    /// Check a sample of the outputs, returning the cursor for the next audit. Fails, so the
+    /// sample is checked again on the next audit, only if an RPC is unavailable. Outputs that
+    /// can't be checked for any other reason are recorded as failed checks and skipped.
+    async fn audit(&self, cursor: Option<u64>) -> Result<Option<u64>> {
+        let latest = self.l2oo.latestOutputIndex().call().await?._0.to::<u64>();
+        let (indices, next) = sample_indices(latest, cursor, self.config.sample_size);
+        for output_index in indices {
+            let Err(e) = self.check_output(output_index).await else {
+                continue;
+            };
+            if is_transient(error_code(&e)) {
+                return Err(e.context(format!("Failed to check output {output_index}")));
+            }
+            error!("Failed to check output {output_index}, skipping it: {e:#}");
+            let mut status = self.status.lock().unwrap();
+            status.failed_checks += 1;
+            status.last_failed_check = Some(FailedCheck {
+                output_index,
+                error: format!("{e:#}"),
+                failed_at: now(),
+            });
+        }
+        self.status.lock().unwrap().last_audit_at = Some(now());
+        Ok(next)
+    }
+
+    async fn check_output(&self, output_index: u64) -> Result<()> {
+        let output = self
+            .l2oo
+            .getL2Output(output_index.try_into()?)
+            .call()
+            .await?
+            ._0;
+        let l2_block_number = output.l2BlockNumber as u64;
+        let expected = OutputRootFields::local(&self.fetcher, l2_block_number).await?;
+
+        let mismatch = (output.outputRoot != expected.output_root).then(|| OutputMismatch {
+            l2oo_address: self.config.l2oo_address,
+            output_index,
+            l2_block_number,
+            proposed_output_root: output.outputRoot,
+            expected_output_root: expected.output_root,
+            detected_at: now(),
+        });
+        {
+            let mut status = self.status.lock().unwrap();
+            status.outputs_checked += 1;
+            if let Some(mismatch) = &mismatch {
+                status.mismatches += 1;
+                status.last_mismatch = Some(mismatch.clone());
+            }
+        }
+
+        if let Some(mismatch) = mismatch {
+            error!(
+                "Output {} on the L2OO has output root {} for L2 block {}, but the L2 RPC gives {}",
+                output_index,
+                mismatch.proposed_output_root,
+                l2_block_number,
+                mismatch.expected_output_root
+            );
+            if let Some(url) = &self.config.webhook_url {
+                if let Err(e) = self.notify(url, &mismatch).await {
+                    error!("Failed to post the output mismatch to the webhook: {:#}", e);
+                }
+            }
+        } else {
+            info!("Output {output_index} (L2 block {l2_block_number}) matches the L2 RPC");
+        }
+        Ok(())
+    }
+
+    async fn notify(&self, url: &Url, mismatch: &OutputMismatch) -> Result<()> {
+        self.client
+            .post(url.clone())
+            .json(mismatch)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_indices() {
+        assert_eq!(sample_indices(0, None, 4), (vec![0], None));
+        assert_eq!(sample_indices(10, None, 3), (vec![10, 9, 8], Some(7)));
+        // The cursor walks back from where the last audit stopped and wraps around.
+        assert_eq!(sample_indices(10, Some(1), 4), (vec![10, 1, 0, 9], Some(8)));
+        // Fewer older outputs than the sample size are each checked once.
+        assert_eq!(sample_indices(2, None, 5), (vec![2, 1, 0], Some(1)));
+        // A cursor past the latest output, after outputs were deleted, restarts from the latest.
+        assert_eq!(sample_indices(5, Some(7), 2), (vec![5, 4], Some(3)));
+    }
+}
@@ -0,0 +1,113 @@
+//! Aggregation over gaps force-resolved by governance, for emergency catch-up.
+//!
+//! When a stretch of blocks can't be proven and governance force-resolves it, the spans after the
+//! stretch don't start from the latest output on-chain. An aggregation request can carry
+//! [`CheckpointOverrides`]: the gaps to skip and the justification governance signed for skipping
+//! them. The aggregation program accepts a gap only if an override bridges it, and commits to the
+//! hash of the overrides in its public values, so the L2 Output Oracle only accepts the proof once
+//! its owner approved that hash. Every override request is recorded in the workspace before it is
+//! proven.
+
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use alloy_primitives::{keccak256, B256};
+use anyhow::Result;
+use log::warn;
+use op_succinct_client_sdk::types::{CheckpointGap, CheckpointOverrides};
+use op_succinct_client_utils::{
+    boot::BootInfoStruct,
+    types::{checkpoint_overrides_hash, CheckpointOverride},
+};
+use op_succinct_host_utils::fetcher::OPSuccinctDataFetcher;
+use serde::Serialize;
+
+/// The overrides the aggregation program checks, each committing to the justification's hash.
+pub fn requested_overrides(request: &CheckpointOverrides) -> Vec<CheckpointOverride> {
+    let justification_hash = keccak256(request.justification.as_bytes());
+    request
+        .gaps
+        .iter()
+        .map(|gap| CheckpointOverride {
+            fromRoot: gap.from_root,
+            toRoot: gap.to_root,
+            justificationHash: justification_hash,
+        })
+        .collect()
+}
+
+/// The record of an aggregation request with checkpoint overrides.
+#[derive(Debug, Serialize)]
+struct CheckpointOverrideRecord<'a> {
+    /// The hash the L2OO's owner must approve, and the aggregation proof commits to.
+    checkpoint_overrides_hash: B256,
+    justification_hash: B256,
+    justification: &'a str,
+    gaps: &'a [CheckpointGap],
+    /// The L2 blocks the first and last subproofs end at.
+    first_subproof_end: u64,
+    last_subproof_end: u64,
+    requested_at: u64,
+}
+
+/// Record an aggregation request with checkpoint overrides to
+/// `{l2_chain_id}/checkpoint-overrides/{hash}.json` in the workspace, returning the path.
+pub async fn record_checkpoint_overrides(
+    fetcher: &OPSuccinctDataFetcher,
+    request: &CheckpointOverrides,
+    boot_infos: &[BootInfoStruct],
+) -> Result<PathBuf> {
+    let overrides = requested_overrides(request);
+    let hash = checkpoint_overrides_hash(&overrides);
+    let record = CheckpointOverrideRecord {
+        checkpoint_overrides_hash: hash,
+        justification_hash: keccak256(request.justification.as_bytes()),
+        justification: &request.justification,
+        gaps: &request.gaps,
+        first_subproof_end: boot_infos.first().map_or(0, |b| b.l2BlockNumber),
+        last_subproof_end: boot_infos.last().map_or(0, |b| b.l2BlockNumber),
+        requested_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+    };
+
+    let l2_chain_id = fetcher.get_l2_chain_id().await?;
+    let path = fetcher
+        .workspace()?
+        .checkpoint_overrides_path(l2_chain_id, hash);
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, serde_json::to_string_pretty(&record)?)?;
+    warn!(
+        "Aggregating over {} checkpoint override(s) with hash {}, recorded to {}: {}",
+        request.gaps.len(),
+        hash,
+        path.display(),
+        request.justification
+    );
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_overrides() {
+        let request = CheckpointOverrides {
+            gaps: vec![CheckpointGap {
+                from_root: B256::repeat_byte(1),
+                to_root: B256::repeat_byte(2),
+            }],
+            justification: "Blocks 100-200 force-resolved by governance".to_string(),
+        };
+        let overrides = requested_overrides(&request);
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].toRoot, B256::repeat_byte(2));
+        assert_eq!(
+            overrides[0].justificationHash,
+            keccak256("Blocks 100-200 force-resolved by governance")
+        );
+    }
+}
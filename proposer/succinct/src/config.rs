@@ -0,0 +1,261 @@
+use std::{env, path::PathBuf};
+
+use alloy_primitives::B256;
+use anyhow::{bail, Context, Result};
+use op_succinct_host_utils::{
+    config::{display_or_unset, env_var, redact_url, url_var, var_from, HostConfig, Url},
+    da::DaSourceConfig,
+    settlement::SettlementLayer,
+};
+use sp1_sdk::{network::FulfillmentStrategy, SP1ProofMode};
+
+use crate::{
+    auditor::AuditorConfig, download::proof_inline_max_bytes_from_env, policy::AggregationPolicy,
+    reconciler::ReconcilerConfig, rpc_overrides::RpcOverrideAllowlist,
+    sync_status::SyncTrackerConfig, webhook::WebhookConfig,
+};
+
+/// The server's settings, read from the environment and validated once at startup.
+#[derive(Debug, Clone)]
+pub struct ProposerConfig {
+    pub host: HostConfig,
+    pub port: u16,
+    /// The prover network RPC, if not the default.
+    pub network_rpc_url: Option<Url>,
+    /// The programs and settings proofs are requested with, which reloads change.
+    pub proving: ProvingConfig,
+    pub reconciler: ReconcilerConfig,
+    /// The data availability source witness generation reads batch data from.
+    pub da_source: DaSourceConfig,
+    pub admin_api_key: Option<String>,
+    /// Whether to write the artifacts of fulfilled span proofs to the workspace.
+    pub proof_artifacts: bool,
+    /// Whether to cache the stdins of span proofs in the workspace, to prove retried spans from.
+    pub witness_cache: bool,
+    /// Periodically re-verifies the outputs on the L2OO, if enabled.
+    pub auditor: Option<AuditorConfig>,
+    /// Tracks the rollup node's heads against the L2 execution RPC, unless disabled.
+    pub sync_tracker: Option<SyncTrackerConfig>,
+    /// Signs and delivers the callbacks of proof requests.
+    pub webhooks: WebhookConfig,
+    /// The largest proof returned inline in status responses.
+    pub proof_inline_max_bytes: usize,
+    /// The endpoints span proof requests may override the RPCs with.
+    pub rpc_override_allowlist: RpcOverrideAllowlist,
+}
+
+impl ProposerConfig {
+    /// Read and validate the server's settings from the environment.
+    ///
+    /// Witness generation reads its beacon API URLs from the environment, so when they default to
+    /// the server's own beacon API proxy (for settlement layers without a beacon node, and for
+    /// data availability sources other than the beacon node), the defaults are written back to the
+    /// environment.
+    pub fn from_env() -> Result<Self> {
+        let port = env_var("PORT")?.unwrap_or(3000);
+
+        // The prover network key is read by the SP1 SDK, which panics on a malformed key.
+        env_var::<B256>("NETWORK_PRIVATE_KEY")?.context("NETWORK_PRIVATE_KEY must be set")?;
+
+        let beacon_proxy = format!("http://127.0.0.1:{port}/beacon");
+        if SettlementLayer::from_env()? == SettlementLayer::OpStack
+            && url_var("L1_BEACON_RPC")?.is_none()
+        {
+            env::set_var("L1_BEACON_RPC", &beacon_proxy);
+        }
+        let da_source = DaSourceConfig::from_env()?;
+        if da_source.is_proxied() && url_var("BLOB_ARCHIVE_RPC")?.is_none() {
+            env::set_var("BLOB_ARCHIVE_RPC", &beacon_proxy);
+        }
+
+        Ok(Self {
+            host: HostConfig::from_env()?,
+            port,
+            network_rpc_url: url_var("NETWORK_RPC_URL")?,
+            proving: ProvingConfig::from_vars(&|name| env::var(name).ok())?,
+            reconciler: ReconcilerConfig::from_env()?,
+            da_source,
+            admin_api_key: env_var("ADMIN_API_KEY")?,
+            proof_artifacts: env_var("PROOF_ARTIFACTS")?.unwrap_or(false),
+            witness_cache: env_var("WITNESS_CACHE")?.unwrap_or(false),
+            auditor: AuditorConfig::from_env()?,
+            sync_tracker: SyncTrackerConfig::from_env()?,
+            webhooks: WebhookConfig::from_env()?,
+            proof_inline_max_bytes: proof_inline_max_bytes_from_env()?,
+            rpc_override_allowlist: RpcOverrideAllowlist::from_env()?,
+        })
+    }
+
+    /// The settings as `(name, value)` pairs for logging, with secrets redacted.
+    pub fn summary(&self) -> Vec<(&'static str, String)> {
+        let mut summary = self.host.summary();
+        summary.extend([
+            ("PORT", self.port.to_string()),
+            ("NETWORK_PRIVATE_KEY", "<redacted>".to_string()),
+            (
+                "NETWORK_RPC_URL",
+                display_or_unset(self.network_rpc_url.as_ref().map(redact_url)),
+            ),
+        ]);
+        summary.extend(self.proving.summary());
+        summary.extend([
+            ("RECONCILER", format!("{:?}", self.reconciler)),
+            ("DA_SOURCE", self.da_source.to_string()),
+            (
+                "ADMIN_API_KEY",
+                display_or_unset(self.admin_api_key.as_ref().map(|_| "<redacted>")),
+            ),
+            ("PROOF_ARTIFACTS", self.proof_artifacts.to_string()),
+            ("WITNESS_CACHE", self.witness_cache.to_string()),
+            (
+                "AUDIT_INTERVAL_SECS",
+                display_or_unset(self.auditor.as_ref().map(|a| a.interval.as_secs())),
+            ),
+            (
+                "L2OO_ADDRESS",
+                display_or_unset(self.auditor.as_ref().map(|a| a.l2oo_address)),
+            ),
+            (
+                "AUDIT_SAMPLE_SIZE",
+                display_or_unset(self.auditor.as_ref().map(|a| a.sample_size)),
+            ),
+            (
+                "AUDIT_WEBHOOK_URL",
+                display_or_unset(
+                    self.auditor
+                        .as_ref()
+                        .and_then(|a| a.webhook_url.as_ref())
+                        .map(redact_url),
+                ),
+            ),
+            (
+                "SYNC_STATUS_INTERVAL_SECS",
+                display_or_unset(self.sync_tracker.as_ref().map(|t| t.interval.as_secs())),
+            ),
+            (
+                "WEBHOOK_SECRET",
+                display_or_unset(self.webhooks.secret.as_ref().map(|_| "<redacted>")),
+            ),
+            (
+                "WEBHOOK_MAX_ATTEMPTS",
+                self.webhooks.max_attempts.to_string(),
+            ),
+            (
+                "WEBHOOK_HOST_ALLOWLIST",
+                display_or_unset(self.webhooks.allowlist_summary()),
+            ),
+            (
+                "PROOF_INLINE_MAX_BYTES",
+                self.proof_inline_max_bytes.to_string(),
+            ),
+            (
+                "RPC_OVERRIDE_ALLOWLIST",
+                display_or_unset(self.rpc_override_allowlist.summary()),
+            ),
+        ]);
+        summary
+    }
+}
+
+/// The programs and settings span and aggregation proofs are requested with. Unlike the rest of
+/// the server's settings, they are read again by every reload.
+#[derive(Debug, Clone)]
+pub struct ProvingConfig {
+    /// The ELF of the range program, read at startup and on every reload instead of the embedded
+    /// ELF.
+    pub range_elf: Option<PathBuf>,
+    /// The ELF of the aggregation program, read at startup and on every reload instead of the
+    /// embedded ELF.
+    pub aggregation_elf: Option<PathBuf>,
+    /// The ELF of the secondary range program. If set, the server runs in quorum mode.
+    pub secondary_range_elf: Option<PathBuf>,
+    pub range_proof_strategy: FulfillmentStrategy,
+    pub agg_proof_strategy: FulfillmentStrategy,
+    pub agg_proof_mode: SP1ProofMode,
+    pub max_stdin_size: Option<usize>,
+    pub aggregation_policy: AggregationPolicy,
+}
+
+impl ProvingConfig {
+    /// Read and validate the settings from the variables `vars` looks up: the environment at
+    /// startup, and the environment file over the environment on reload, which leaves the
+    /// process's environment untouched.
+    pub fn from_vars(vars: &dyn Fn(&str) -> Option<String>) -> Result<Self> {
+        Ok(Self {
+            range_elf: elf_path_var(vars, "RANGE_ELF_PATH")?,
+            aggregation_elf: elf_path_var(vars, "AGGREGATION_ELF_PATH")?,
+            secondary_range_elf: elf_path_var(vars, "SECONDARY_RANGE_ELF_PATH")?,
+            range_proof_strategy: fulfillment_strategy_var(vars, "RANGE_PROOF_STRATEGY")?,
+            agg_proof_strategy: fulfillment_strategy_var(vars, "AGG_PROOF_STRATEGY")?,
+            agg_proof_mode: match var_from::<String>(vars, "AGG_PROOF_MODE")?
+                .map(|m| m.to_lowercase())
+            {
+                None => SP1ProofMode::Groth16,
+                Some(mode) if mode == "groth16" => SP1ProofMode::Groth16,
+                Some(mode) if mode == "plonk" => SP1ProofMode::Plonk,
+                Some(mode) => bail!("Invalid AGG_PROOF_MODE {mode:?}: expected groth16 or plonk"),
+            },
+            max_stdin_size: var_from(vars, "MAX_STDIN_SIZE_BYTES")?,
+            aggregation_policy: AggregationPolicy::from_vars(vars)?,
+        })
+    }
+
+    /// The settings as `(name, value)` pairs for logging.
+    pub fn summary(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (
+                "RANGE_PROOF_STRATEGY",
+                format!("{:?}", self.range_proof_strategy),
+            ),
+            (
+                "AGG_PROOF_STRATEGY",
+                format!("{:?}", self.agg_proof_strategy),
+            ),
+            ("AGG_PROOF_MODE", format!("{:?}", self.agg_proof_mode)),
+            (
+                "AGGREGATION_POLICY",
+                format!("{:?}", self.aggregation_policy),
+            ),
+            (
+                "MAX_STDIN_SIZE_BYTES",
+                display_or_unset(self.max_stdin_size),
+            ),
+            (
+                "RANGE_ELF_PATH",
+                display_or_unset(self.range_elf.as_ref().map(|path| path.display())),
+            ),
+            (
+                "AGGREGATION_ELF_PATH",
+                display_or_unset(self.aggregation_elf.as_ref().map(|path| path.display())),
+            ),
+            (
+                "SECONDARY_RANGE_ELF_PATH",
+                display_or_unset(self.secondary_range_elf.as_ref().map(|path| path.display())),
+            ),
+        ]
+    }
+}
+
+/// Read a proof fulfillment strategy, defaulting to reserved.
+fn fulfillment_strategy_var(
+    vars: &dyn Fn(&str) -> Option<String>,
+    name: &str,
+) -> Result<FulfillmentStrategy> {
+    match var_from::<String>(vars, name)?.map(|s| s.to_lowercase()) {
+        None => Ok(FulfillmentStrategy::Reserved),
+        Some(strategy) if strategy == "reserved" => Ok(FulfillmentStrategy::Reserved),
+        Some(strategy) if strategy == "hosted" => Ok(FulfillmentStrategy::Hosted),
+        Some(strategy) => bail!("Invalid {name} {strategy:?}: expected reserved or hosted"),
+    }
+}
+
+/// Read the path to an ELF, which must be a file.
+fn elf_path_var(vars: &dyn Fn(&str) -> Option<String>, name: &str) -> Result<Option<PathBuf>> {
+    let path = var_from::<PathBuf>(vars, name)?;
+    if let Some(path) = &path {
+        if !path.is_file() {
+            bail!("{name} {} is not a file", path.display());
+        }
+    }
+    Ok(path)
+}
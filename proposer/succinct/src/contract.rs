@@ -0,0 +1,288 @@
+//! The contracts outputs are proposed to.
+//!
+//! Chains at different upgrade stages propose outputs to different contracts. [`OutputContract`]
+//! detects which one is deployed at an address:
+//!
+//! - The original `L2OutputOracle`, which takes outputs from a permissioned proposer without a
+//!   proof.
+//! - The ZK-enabled `OPSuccinctL2OutputOracle`, which verifies the aggregation proof of each output.
+//! - An `OptimismPortal2`, whose outputs are proposed by creating dispute games of its respected
+//!   game type with its `DisputeGameFactory`. The `OPSuccinctDisputeGame` forwards the proposal to
+//!   an `OPSuccinctL2OutputOracle`.
+
+use alloy_network::Ethereum;
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_provider::Provider;
+use alloy_sol_types::{sol, SolValue};
+use anyhow::{anyhow, bail, Result};
+
+pub const NUM_CONFIRMATIONS: u64 = 3;
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    contract OPSuccinctL2OutputOracle {
+        /// @notice A mapping from L1 block numbers to their checkpointed block hashes.
+        mapping(uint256 => bytes32) public historicBlockHashes;
+
+        /// @notice Returns the block number of the latest submitted L2 output proposal.
+        function latestBlockNumber() public view returns (uint256);
+
+        /// @notice Returns the L2 block number of the next expected output proposal.
+        function nextBlockNumber() public view returns (uint256);
+
+        /// @notice The minimum number of L2 blocks between two output proposals.
+        uint256 public submissionInterval;
+
+        /// @notice Checkpoints a block hash at a given block number.
+        function checkpointBlockHash(uint256 _blockNumber) external;
+
+        /// @notice Accepts an outputRoot and the proof of the aggregation program.
+        function proposeL2Output(bytes32 _outputRoot, uint256 _l2BlockNumber, uint256 _l1BlockNumber, bytes memory _proof) external payable;
+
+        /// @notice Receives and executes a batch of function calls on this contract.
+        function multicall(bytes[] calldata data) external returns (bytes[] memory results);
+
+        /// @notice The verification key of the aggregation program.
+        bytes32 public aggregationVkey;
+
+        /// @notice The version of the public values the aggregation program commits to.
+        uint8 public aggregationOutputsVersion;
+
+        /// @notice The 32 byte commitment to the BabyBear representation of the verification key of the range program.
+        bytes32 public rangeVkeyCommitment;
+
+        /// @notice The address of the SP1 verifier.
+        address public verifier;
+
+        /// @notice The hash of the chain's rollup config.
+        bytes32 public rollupConfigHash;
+
+        struct OutputProposal {
+            bytes32 outputRoot;
+            uint128 timestamp;
+            uint128 l2BlockNumber;
+        }
+
+        /// @notice Returns the index of the latest L2 output proposal.
+        function latestOutputIndex() public view returns (uint256);
+
+        /// @notice Returns an output by index.
+        function getL2Output(uint256 _l2OutputIndex) external view returns (OutputProposal memory);
+    }
+}
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface ILegacyL2OutputOracle {
+        /// @notice Returns the block number of the latest submitted L2 output proposal.
+        function latestBlockNumber() external view returns (uint256);
+
+        /// @notice Accepts an outputRoot, committing to the hash of an L1 block to guard against reorgs.
+        function proposeL2Output(bytes32 _outputRoot, uint256 _l2BlockNumber, bytes32 _l1BlockHash, uint256 _l1BlockNumber) external payable;
+    }
+}
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface IOptimismPortal2 {
+        /// @notice The game type that the portal respects for withdrawals.
+        function respectedGameType() external view returns (uint32);
+
+        /// @notice The dispute game factory the portal reads games from.
+        function disputeGameFactory() external view returns (address);
+    }
+}
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface IDisputeGameFactory {
+        struct GameSearchResult {
+            uint256 index;
+            bytes32 metadata;
+            uint64 timestamp;
+            bytes32 rootClaim;
+            bytes extraData;
+        }
+
+        /// @notice The total number of dispute games created.
+        function gameCount() external view returns (uint256);
+
+        /// @notice The bond required to create a game of a type.
+        function initBonds(uint32 _gameType) external view returns (uint256);
+
+        /// @notice Creates a new dispute game.
+        function create(uint32 _gameType, bytes32 _rootClaim, bytes calldata _extraData) external payable returns (address);
+
+        /// @notice Finds the `_n` most recent games of a type, searching backwards from index `_start`.
+        function findLatestGames(uint32 _gameType, uint256 _start, uint256 _n) external view returns (GameSearchResult[] memory);
+    }
+}
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface ISP1Verifier {
+        /// @notice Verifies a proof with given public values and vkey.
+        function verifyProof(bytes32 programVKey, bytes calldata publicValues, bytes calldata proofBytes) external view;
+
+        /// @notice The gateway has no verifier for the proof's selector.
+        error RouteNotFound(bytes4 selector);
+
+        /// @notice The gateway's verifier for the proof's selector was frozen.
+        error RouteIsFrozen(bytes4 selector);
+
+        /// @notice The proof was generated for a different verifier version.
+        error WrongVerifierSelector(bytes4 receivedSelector, bytes4 expectedSelector);
+
+        /// @notice The proof does not verify against the vkey and public values.
+        error InvalidProof();
+
+        /// @notice The Groth16 proof does not verify against the vkey and public values.
+        error ProofInvalid();
+
+        /// @notice A public input is outside the scalar field.
+        error PublicInputNotInField();
+    }
+}
+
+/// The contract a chain's outputs are proposed to, detected with [`OutputContract::detect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputContract {
+    /// The original `L2OutputOracle`, which takes outputs without a proof.
+    LegacyL2OutputOracle(Address),
+    /// The `OPSuccinctL2OutputOracle`, which verifies the aggregation proof of each output.
+    ZkL2OutputOracle(Address),
+    /// An `OptimismPortal2`, whose outputs are proposed as dispute games of its respected game
+    /// type.
+    OptimismPortal2 {
+        portal: Address,
+        dispute_game_factory: Address,
+        game_type: u32,
+    },
+}
+
+impl OutputContract {
+    /// Detect the contract deployed at `address` from the interfaces it implements.
+    pub async fn detect<P>(provider: &P, address: Address) -> Result<Self>
+    where
+        P: Provider<Ethereum> + Clone,
+    {
+        if provider.get_code_at(address).await?.is_empty() {
+            bail!("No contract is deployed at {}", address);
+        }
+
+        let portal = IOptimismPortal2::new(address, provider.clone());
+        if let Ok(game_type) = portal.respectedGameType().call().await {
+            return Ok(Self::OptimismPortal2 {
+                portal: address,
+                dispute_game_factory: portal.disputeGameFactory().call().await?._0,
+                game_type: game_type._0,
+            });
+        }
+        let l2oo = OPSuccinctL2OutputOracle::new(address, provider.clone());
+        if l2oo.aggregationVkey().call().await.is_ok() {
+            return Ok(Self::ZkL2OutputOracle(address));
+        }
+        if l2oo.latestBlockNumber().call().await.is_ok() {
+            return Ok(Self::LegacyL2OutputOracle(address));
+        }
+        bail!(
+            "The contract at {} is not an L2OutputOracle or an OptimismPortal2",
+            address
+        )
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::LegacyL2OutputOracle(_) => "legacy L2OutputOracle",
+            Self::ZkL2OutputOracle(_) => "OPSuccinctL2OutputOracle",
+            Self::OptimismPortal2 { .. } => "OptimismPortal2",
+        }
+    }
+
+    /// Whether the contract verifies the aggregation proof of each output.
+    pub fn verifies_proofs(&self) -> bool {
+        !matches!(self, Self::LegacyL2OutputOracle(_))
+    }
+
+    /// The L2 block number of the latest output proposed to the contract, or 0 if there is none.
+    pub async fn latest_block_number<P>(&self, provider: &P) -> Result<u64>
+    where
+        P: Provider<Ethereum> + Clone,
+    {
+        match *self {
+            Self::LegacyL2OutputOracle(address) | Self::ZkL2OutputOracle(address) => {
+                Ok(OPSuccinctL2OutputOracle::new(address, provider.clone())
+                    .latestBlockNumber()
+                    .call()
+                    .await?
+                    ._0
+                    .to())
+            }
+            Self::OptimismPortal2 {
+                dispute_game_factory,
+                game_type,
+                ..
+            } => {
+                let factory = IDisputeGameFactory::new(dispute_game_factory, provider.clone());
+                let game_count = factory.gameCount().call().await?._0;
+                if game_count.is_zero() {
+                    return Ok(0);
+                }
+                let games = factory
+                    .findLatestGames(game_type, game_count - U256::from(1), U256::from(1))
+                    .call()
+                    .await?
+                    ._0;
+                match games.first() {
+                    Some(game) => dispute_game_l2_block_number(&game.extraData),
+                    None => Ok(0),
+                }
+            }
+        }
+    }
+}
+
+/// The extra data of an `OPSuccinctDisputeGame`, which it decodes to propose the output to its
+/// `OPSuccinctL2OutputOracle`.
+pub fn dispute_game_extra_data(l2_block_number: u64, l1_block_number: u64, proof: &Bytes) -> Bytes {
+    (
+        U256::from(l2_block_number),
+        U256::from(l1_block_number),
+        proof.clone(),
+    )
+        .abi_encode_params()
+        .into()
+}
+
+/// The L2 block number of a dispute game's output, from its extra data.
+fn dispute_game_l2_block_number(extra_data: &[u8]) -> Result<u64> {
+    let Some(word) = extra_data.get(..32) else {
+        bail!("Dispute game extra data is shorter than a word");
+    };
+    u64::try_from(U256::from_be_slice(word))
+        .map_err(|_| anyhow!("Dispute game L2 block number doesn't fit in a u64"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispute_game_extra_data() {
+        let proof = Bytes::from(vec![0xab; 40]);
+        let extra_data = dispute_game_extra_data(1_000, 20, &proof);
+
+        let (l2_block_number, l1_block_number, decoded_proof) =
+            <(U256, U256, Bytes)>::abi_decode_params(&extra_data, true).unwrap();
+        assert_eq!(l2_block_number, U256::from(1_000));
+        assert_eq!(l1_block_number, U256::from(20));
+        assert_eq!(decoded_proof, proof);
+        assert_eq!(dispute_game_l2_block_number(&extra_data).unwrap(), 1_000);
+        assert!(dispute_game_l2_block_number(&[0; 31]).is_err());
+    }
+}
@@ -0,0 +1,162 @@
+//! Proof of custody of span witnesses.
+//!
+//! The server returns the SHA-256 hash of the witness every span proof is proven from with the
+//! proof ID, and records it in the proof's artifact. The proposer stores the hash with the proof
+//! request, and sends it back when it retries the span. When `WITNESS_CACHE` is enabled, the
+//! server keeps the stdin of every span proof it requests in the span's directory of the
+//! workspace, and proves a retried span from it instead of generating the witness again, but only
+//! if it still hashes to the hash the proposer sent. A cached witness that was corrupted or
+//! tampered with on disk is discarded and generated again. If witness encryption is enabled, the
+//! cached stdins are encrypted on disk.
+
+use std::{fmt, fs, io, path::PathBuf};
+
+use alloy_primitives::B256;
+use anyhow::Result;
+use op_succinct_host_utils::{encryption::WitnessKey, witness_sha256, workspace::Workspace};
+use sp1_sdk::SP1Stdin;
+
+/// A cached witness that doesn't hash to the hash of the witness its span was proven from.
+#[derive(Debug)]
+pub struct WitnessHashMismatch {
+    pub start: u64,
+    pub end: u64,
+    pub expected: B256,
+    pub actual: B256,
+}
+
+impl fmt::Display for WitnessHashMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The cached witness of span {}-{} has SHA-256 {}, but the span was proven from a \
+             witness with SHA-256 {}",
+            self.start, self.end, self.actual, self.expected
+        )
+    }
+}
+
+impl std::error::Error for WitnessHashMismatch {}
+
+/// The stdins of the span proofs the server requested, stored as
+/// [`Workspace::witness_stdin_path`].
+#[derive(Debug)]
+pub struct WitnessCache {
+    workspace: Workspace,
+    l2_chain_id: u64,
+    key: Option<WitnessKey>,
+}
+
+impl WitnessCache {
+    pub fn new(workspace: Workspace, l2_chain_id: u64, key: Option<WitnessKey>) -> Self {
+        Self {
+            workspace,
+            l2_chain_id,
+            key,
+        }
+    }
+
+    fn path(&self, start: u64, end: u64) -> PathBuf {
+        self.workspace
+            .witness_stdin_path(self.l2_chain_id, start, end)
+    }
+
+    /// The associated data the cached stdin of a span is encrypted with, so it can't be swapped
+    /// for the stdin of another span.
+    fn aad(start: u64, end: u64) -> Vec<u8> {
+        format!("{start}-{end}").into_bytes()
+    }
+
+    /// Cache the stdin of a span proof, replacing the span's previous one.
+    pub fn store(&self, start: u64, end: u64, stdin: &SP1Stdin) -> Result<()> {
+        let path = self.path(start, end);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut contents = bincode::serialize(stdin)?;
+        if let Some(key) = &self.key {
+            contents = key.encrypt(&Self::aad(start, end), &contents)?;
+        }
+        // Write to a temporary file first, so a partially written stdin is never read.
+        let tmp = path.with_extension("bin.tmp");
+        fs::write(&tmp, contents)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    /// Read the cached stdin of a span, checking that it hashes to `expected`, the hash of the
+    /// witness the span was proven from. Returns `None` if the span has no cached stdin, and a
+    /// [`WitnessHashMismatch`] if it changed.
+    pub fn load(&self, start: u64, end: u64, expected: B256) -> Result<Option<SP1Stdin>> {
+        let mut contents = match fs::read(self.path(start, end)) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        if let Some(key) = &self.key {
+            contents = key.decrypt(&Self::aad(start, end), &contents)?;
+        }
+        let stdin: SP1Stdin = bincode::deserialize(&contents)?;
+        let actual = witness_sha256(&stdin);
+        if actual != expected {
+            return Err(WitnessHashMismatch {
+                start,
+                end,
+                expected,
+                actual,
+            }
+            .into());
+        }
+        Ok(Some(stdin))
+    }
+
+    /// Discard the cached stdin of a span.
+    pub fn remove(&self, start: u64, end: u64) -> Result<()> {
+        match fs::remove_file(self.path(start, end)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stdin(witness: &[u8]) -> SP1Stdin {
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&0u8);
+        stdin.write_slice(witness);
+        stdin
+    }
+
+    #[test]
+    fn test_load_verifies_witness_hash() {
+        let dir = std::env::temp_dir().join(format!("witness-cache-test-{}", std::process::id()));
+        let cache = WitnessCache::new(
+            Workspace::new(dir.clone()),
+            10,
+            Some(WitnessKey::new(B256::repeat_byte(1))),
+        );
+        let hash = witness_sha256(&stdin(b"witness"));
+
+        assert!(cache.load(100, 110, hash).unwrap().is_none());
+        cache.store(100, 110, &stdin(b"witness")).unwrap();
+        let loaded = cache.load(100, 110, hash).unwrap().unwrap();
+        assert_eq!(loaded.buffer, stdin(b"witness").buffer);
+
+        // A witness that doesn't match the hash the span was proven from is rejected.
+        cache.store(100, 110, &stdin(b"tampered")).unwrap();
+        let err = cache.load(100, 110, hash).unwrap_err();
+        assert!(err.downcast_ref::<WitnessHashMismatch>().is_some());
+
+        // The stdin of another span doesn't decrypt in its place.
+        cache.store(110, 120, &stdin(b"witness")).unwrap();
+        fs::copy(cache.path(110, 120), cache.path(100, 110)).unwrap();
+        assert!(cache.load(100, 110, hash).is_err());
+
+        cache.remove(100, 110).unwrap();
+        assert!(cache.load(100, 110, hash).unwrap().is_none());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
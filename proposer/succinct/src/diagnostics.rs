@@ -0,0 +1,137 @@
+use alloy_primitives::B256;
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::BlockTransactionsKind;
+use anyhow::{Context, Result};
+use log::info;
+use op_succinct_host_utils::{fetcher::OPSuccinctDataFetcher, supervisor::OversizedBlockWitness};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The header of a `422` response listing the blocks to split the span at, comma-separated, when
+/// the server knows which block made the span unexecutable.
+pub const SPLIT_AT_HEADER: &str = "X-Split-At";
+
+/// Witness generation failed for a span. Unless an RPC failed, the server responds with `422
+/// Unprocessable Entity` so the proposer splits the span instead of retrying it as is.
+#[derive(Debug)]
+pub struct UnexecutableSpan {
+    pub start: u64,
+    pub end: u64,
+    pub error: anyhow::Error,
+}
+
+impl UnexecutableSpan {
+    /// The block that exceeded the per-block witness limit, if that's why witness generation
+    /// failed.
+    pub fn oversized_block(&self) -> Option<&OversizedBlockWitness> {
+        self.error.downcast_ref()
+    }
+
+    /// The blocks to split the span at, or none to leave the split to the proposer.
+    pub fn split_points(&self) -> Vec<u64> {
+        self.oversized_block()
+            .map(OversizedBlockWitness::split_points)
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for UnexecutableSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Witness generation failed for span {}-{}: {:#}",
+            self.start, self.end, self.error
+        )
+    }
+}
+
+impl std::error::Error for UnexecutableSpan {}
+
+/// The block a single-block span proves, as seen by the L2 node.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BlockDiagnostics {
+    pub number: u64,
+    pub hash: B256,
+    pub timestamp: u64,
+    pub gas_used: u64,
+    pub l1_origin_number: u64,
+    pub l1_origin_hash: B256,
+    pub transactions: Vec<B256>,
+}
+
+/// A record of a span that failed witness generation. Once the proposer has split a failing span
+/// down to a single block, the report identifies the offending block and its transactions.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SpanFailureReport {
+    pub start: u64,
+    pub end: u64,
+    pub error: String,
+    /// Unix timestamp at which witness generation failed.
+    pub failed_at: u64,
+    /// Only set for single-block spans.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block: Option<BlockDiagnostics>,
+    /// Only set if a block exceeded the per-block witness limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oversized_block: Option<OversizedBlockWitness>,
+}
+
+/// Fetch the details of a block that failed witness generation.
+async fn get_block_diagnostics(
+    fetcher: &OPSuccinctDataFetcher,
+    block_number: u64,
+) -> Result<BlockDiagnostics> {
+    let block = fetcher
+        .l2_provider
+        .get_block_by_number(block_number.into(), BlockTransactionsKind::Hashes)
+        .await?
+        .with_context(|| format!("Block {} not found", block_number))?;
+    let block_info = fetcher.l2_block_info_by_number(block_number).await?;
+
+    Ok(BlockDiagnostics {
+        number: block_number,
+        hash: block.header.hash,
+        timestamp: block.header.timestamp,
+        gas_used: block.header.gas_used,
+        l1_origin_number: block_info.l1_origin.number,
+        l1_origin_hash: block_info.l1_origin.hash,
+        transactions: block.transactions.hashes().collect(),
+    })
+}
+
+/// Write a report for a span that failed witness generation to the span's `failure-report.json` in
+/// the workspace, replacing any earlier report for the span.
+pub async fn write_span_failure_report(
+    fetcher: &OPSuccinctDataFetcher,
+    failure: &UnexecutableSpan,
+) -> Result<PathBuf> {
+    // A span proves the blocks after its start block, so a single-block span proves its end block.
+    let block = if failure.end - failure.start == 1 {
+        Some(get_block_diagnostics(fetcher, failure.end).await?)
+    } else {
+        None
+    };
+
+    let report = SpanFailureReport {
+        start: failure.start,
+        end: failure.end,
+        error: format!("{:#}", failure.error),
+        failed_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        block,
+        oversized_block: failure.oversized_block().cloned(),
+    };
+
+    let l2_chain_id = fetcher.get_l2_chain_id().await?;
+    let path = fetcher
+        .workspace()?
+        .failure_report_path(l2_chain_id, failure.start, failure.end);
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+    info!("Wrote span failure report to {}", path.display());
+
+    Ok(path)
+}
@@ -0,0 +1,283 @@
+//! Serving fulfilled proofs as raw bytes.
+//!
+//! Status responses carry the proof as a JSON array of numbers, which takes up to four bytes per
+//! proof byte and far more memory to decode. Proofs larger than `PROOF_INLINE_MAX_BYTES` are left
+//! out of status responses, which point to `GET /proofs/:proof_id/download` instead. It serves the
+//! proof's bytes with their length, an `ETag` of their hash, and single byte ranges, so a large
+//! proof can be fetched in parts and an interrupted download resumed. The bytes are streamed in
+//! chunks rather than copied into a single response buffer.
+
+use std::convert::Infallible;
+
+use alloy_primitives::{hex, keccak256, B256};
+use anyhow::Result;
+use axum::{
+    body::{Body, Bytes},
+    http::{
+        header::{
+            ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG, IF_NONE_MATCH,
+            IF_RANGE, RANGE,
+        },
+        HeaderMap, HeaderName, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use op_succinct_host_utils::config::env_var;
+
+use crate::ProofStatus;
+
+/// The largest proof returned inline in status responses by default. Compressed span proofs are
+/// larger, so they are downloaded.
+pub const DEFAULT_PROOF_INLINE_MAX_BYTES: usize = 1024 * 1024;
+
+/// Read the largest proof returned inline in status responses from `PROOF_INLINE_MAX_BYTES`.
+/// Default: [`DEFAULT_PROOF_INLINE_MAX_BYTES`].
+pub fn proof_inline_max_bytes_from_env() -> Result<usize> {
+    Ok(env_var("PROOF_INLINE_MAX_BYTES")?.unwrap_or(DEFAULT_PROOF_INLINE_MAX_BYTES))
+}
+
+/// The size of the chunks proofs are streamed in.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The path a proof is downloaded from, relative to the server's base URL.
+pub fn download_path(proof_id: &B256) -> String {
+    format!("proofs/{}/download", hex::encode(proof_id))
+}
+
+/// Replace a proof larger than `max_inline_bytes` with the path it is downloaded from.
+pub fn externalize_proof(status: &mut ProofStatus, proof_id: &B256, max_inline_bytes: usize) {
+    if status.proof.len() > max_inline_bytes {
+        status.proof = vec![];
+        status.proof_url = Some(download_path(proof_id));
+    }
+}
+
+/// The entity tag of a proof's bytes.
+pub fn etag(bytes: &[u8]) -> String {
+    format!("\"{}\"", keccak256(bytes))
+}
+
+/// The part of a proof requested by a `Range` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteRange {
+    Full,
+    /// The bytes from `start` to `end`, inclusive.
+    Partial {
+        start: usize,
+        end: usize,
+    },
+    Unsatisfiable,
+}
+
+/// Parse a `Range` header for a body of `len` bytes. Headers this server doesn't support, such as
+/// other units or multiple ranges, are ignored, which serves the full body as allowed by RFC 9110.
+fn parse_range(header: Option<&str>, len: usize) -> ByteRange {
+    let Some(spec) = header.and_then(|header| header.trim().strip_prefix("bytes=")) else {
+        return ByteRange::Full;
+    };
+    let Some((first, last)) = spec.split_once('-') else {
+        return ByteRange::Full;
+    };
+    if spec.contains(',') {
+        return ByteRange::Full;
+    }
+    let parse = |value: &str| value.trim().parse::<usize>();
+    match (first.trim().is_empty(), last.trim().is_empty()) {
+        // The last `suffix` bytes.
+        (true, false) => match parse(last) {
+            Ok(0) => ByteRange::Unsatisfiable,
+            Ok(_) if len == 0 => ByteRange::Unsatisfiable,
+            Ok(suffix) => ByteRange::Partial {
+                start: len.saturating_sub(suffix),
+                end: len - 1,
+            },
+            Err(_) => ByteRange::Full,
+        },
+        // From `start` to the end.
+        (false, true) => match parse(first) {
+            Ok(start) if start >= len => ByteRange::Unsatisfiable,
+            Ok(start) => ByteRange::Partial {
+                start,
+                end: len - 1,
+            },
+            Err(_) => ByteRange::Full,
+        },
+        (false, false) => match (parse(first), parse(last)) {
+            (Ok(start), Ok(end)) if start > end => ByteRange::Full,
+            (Ok(start), Ok(_)) if start >= len => ByteRange::Unsatisfiable,
+            (Ok(start), Ok(end)) => ByteRange::Partial {
+                start,
+                end: end.min(len - 1),
+            },
+            _ => ByteRange::Full,
+        },
+        (true, true) => ByteRange::Full,
+    }
+}
+
+/// Stream bytes in chunks of [`CHUNK_SIZE`]. The chunks share the bytes' buffer.
+fn stream_body(bytes: Bytes) -> Body {
+    let len = bytes.len();
+    let chunks = (0..len)
+        .step_by(CHUNK_SIZE)
+        .map(move |start| Ok::<_, Infallible>(bytes.slice(start..(start + CHUNK_SIZE).min(len))));
+    Body::from_stream(futures::stream::iter(chunks))
+}
+
+/// Serve a proof's bytes, honouring the request's `If-None-Match`, `Range` and `If-Range` headers.
+pub fn serve_proof(request_headers: &HeaderMap, proof: Vec<u8>) -> Response {
+    let etag = etag(&proof);
+    let header = |name: HeaderName| {
+        request_headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+
+    if header(IF_NONE_MATCH).is_some_and(|tags| {
+        tags.split(',')
+            .any(|tag| matches!(tag.trim(), "*") || tag.trim() == etag)
+    }) {
+        return (StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response();
+    }
+
+    // A range of a proof the client no longer has would be spliced into the wrong bytes.
+    let range = match header(IF_RANGE) {
+        Some(tag) if tag.trim() != etag => ByteRange::Full,
+        _ => parse_range(header(RANGE), proof.len()),
+    };
+
+    let len = proof.len();
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, "application/octet-stream".parse().unwrap());
+    headers.insert(ACCEPT_RANGES, "bytes".parse().unwrap());
+    headers.insert(ETAG, etag.parse().unwrap());
+    match range {
+        ByteRange::Full => {
+            headers.insert(CONTENT_LENGTH, len.into());
+            (StatusCode::OK, headers, stream_body(Bytes::from(proof))).into_response()
+        }
+        ByteRange::Partial { start, end } => {
+            headers.insert(CONTENT_LENGTH, (end - start + 1).into());
+            headers.insert(
+                CONTENT_RANGE,
+                format!("bytes {start}-{end}/{len}").parse().unwrap(),
+            );
+            let part = Bytes::from(proof).slice(start..=end);
+            (StatusCode::PARTIAL_CONTENT, headers, stream_body(part)).into_response()
+        }
+        ByteRange::Unsatisfiable => {
+            headers.insert(CONTENT_RANGE, format!("bytes */{len}").parse().unwrap());
+            (StatusCode::RANGE_NOT_SATISFIABLE, headers).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        let range = |header: &str| parse_range(Some(header), 100);
+        assert_eq!(parse_range(None, 100), ByteRange::Full);
+        assert_eq!(range("bytes=0-9"), ByteRange::Partial { start: 0, end: 9 });
+        assert_eq!(
+            range("bytes=90-"),
+            ByteRange::Partial { start: 90, end: 99 }
+        );
+        assert_eq!(
+            range("bytes=-10"),
+            ByteRange::Partial { start: 90, end: 99 }
+        );
+        // The end is clamped to the body.
+        assert_eq!(
+            range("bytes=50-200"),
+            ByteRange::Partial { start: 50, end: 99 }
+        );
+        assert_eq!(range("bytes=100-"), ByteRange::Unsatisfiable);
+        assert_eq!(range("bytes=-0"), ByteRange::Unsatisfiable);
+        // Unsupported or invalid ranges are ignored.
+        assert_eq!(range("bytes=0-9,20-29"), ByteRange::Full);
+        assert_eq!(range("bytes=9-0"), ByteRange::Full);
+        assert_eq!(range("items=0-9"), ByteRange::Full);
+        assert_eq!(range("bytes=a-b"), ByteRange::Full);
+    }
+
+    async fn body(response: Response) -> Vec<u8> {
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec()
+    }
+
+    #[tokio::test]
+    async fn test_serve_proof() {
+        let proof: Vec<u8> = (0..100).collect();
+
+        let response = serve_proof(&HeaderMap::new(), proof.clone());
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_LENGTH], "100");
+        let tag = response.headers()[ETAG].clone();
+        assert_eq!(body(response).await, proof);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RANGE, "bytes=10-19".parse().unwrap());
+        let response = serve_proof(&headers, proof.clone());
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes 10-19/100");
+        assert_eq!(response.headers()[CONTENT_LENGTH], "10");
+        assert_eq!(body(response).await, proof[10..20]);
+
+        // A range of another version of the proof is ignored.
+        headers.insert(IF_RANGE, "\"other\"".parse().unwrap());
+        let response = serve_proof(&headers, proof.clone());
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, tag);
+        let response = serve_proof(&headers, proof.clone());
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RANGE, "bytes=100-".parse().unwrap());
+        let response = serve_proof(&headers, proof);
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes */100");
+    }
+
+    #[tokio::test]
+    async fn test_serve_proof_streams_chunks() {
+        let proof: Vec<u8> = (0..3 * CHUNK_SIZE + 10).map(|i| i as u8).collect();
+
+        let response = serve_proof(&HeaderMap::new(), proof.clone());
+        assert_eq!(body(response).await, proof);
+
+        let mut headers = HeaderMap::new();
+        let (start, end) = (CHUNK_SIZE - 5, 2 * CHUNK_SIZE + 5);
+        headers.insert(RANGE, format!("bytes={start}-{end}").parse().unwrap());
+        let response = serve_proof(&headers, proof.clone());
+        assert_eq!(body(response).await, proof[start..=end]);
+    }
+
+    #[test]
+    fn test_externalize_proof() {
+        let proof_id = B256::repeat_byte(1);
+        let mut status = ProofStatus {
+            fulfillment_status: 0,
+            execution_status: 0,
+            proof: vec![0; 10],
+            metadata: None,
+            proof_url: None,
+            error_code: None,
+        };
+        externalize_proof(&mut status, &proof_id, 10);
+        assert_eq!(status.proof.len(), 10);
+
+        externalize_proof(&mut status, &proof_id, 9);
+        assert!(status.proof.is_empty());
+        assert_eq!(
+            status.proof_url.unwrap(),
+            format!("proofs/{}/download", hex::encode(proof_id))
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Deployment helpers for testing the proposer end to end against a local chain such as Anvil.
+//!
+//! The helpers deploy `SP1MockVerifier`, which accepts any empty proof, and an
+//! `OPSuccinctL2OutputOracle` behind a proxy that verifies against it, so the complete
+//! propose-and-verify loop runs without generating real proofs. The contracts are deployed from
+//! the Foundry artifacts in `contracts/out`, so run `forge build` in `contracts` first.
+
+use std::{fs, path::PathBuf};
+
+use alloy_network::{Ethereum, TransactionBuilder};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::TransactionRequest;
+use alloy_sol_types::{sol, SolCall, SolValue};
+use anyhow::{anyhow, bail, Context, Result};
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    contract Proxy {
+        /// @notice Sets the implementation and calls it with `_data`. Only callable by the admin.
+        function upgradeToAndCall(address _implementation, bytes calldata _data) public payable returns (bytes memory);
+    }
+
+    #[allow(missing_docs)]
+    interface IOPSuccinctL2OutputOracleInitializer {
+        struct InitParams {
+            address challenger;
+            address proposer;
+            address owner;
+            uint256 finalizationPeriodSeconds;
+            uint256 l2BlockTime;
+            bytes32 aggregationVkey;
+            bytes32 rangeVkeyCommitment;
+            bytes32 rollupConfigHash;
+            bytes32 startingOutputRoot;
+            uint256 startingBlockNumber;
+            uint256 startingTimestamp;
+            uint256 submissionInterval;
+            address verifier;
+        }
+
+        function initialize(InitParams memory _initParams) external;
+    }
+}
+
+/// The proof `SP1MockVerifier` accepts for any vkey and public values.
+pub const MOCK_PROOF: Bytes = Bytes::new();
+
+/// The `contracts` directory of the repository.
+pub fn contracts_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../contracts")
+}
+
+/// The creation bytecode of contract `name` defined in the file `source`, read from its Foundry
+/// artifact.
+pub fn contract_bytecode(source: &str, name: &str) -> Result<Bytes> {
+    let path = contracts_dir()
+        .join("out")
+        .join(source)
+        .join(format!("{name}.json"));
+    let artifact: serde_json::Value =
+        serde_json::from_slice(&fs::read(&path).with_context(|| {
+            format!(
+                "Failed to read {}. Run `forge build` in contracts.",
+                path.display()
+            )
+        })?)?;
+    let bytecode = artifact["bytecode"]["object"]
+        .as_str()
+        .ok_or_else(|| anyhow!("{} has no bytecode", path.display()))?;
+    Ok(bytecode.parse()?)
+}
+
+/// Deploy `bytecode` with the ABI-encoded `constructor_args` from the provider's default account,
+/// and return the address of the contract.
+pub async fn deploy<P>(provider: &P, bytecode: Bytes, constructor_args: &[u8]) -> Result<Address>
+where
+    P: Provider<Ethereum>,
+{
+    let code = [bytecode.as_ref(), constructor_args].concat();
+    let receipt = provider
+        .send_transaction(TransactionRequest::default().with_deploy_code(code))
+        .await?
+        .get_receipt()
+        .await?;
+    if !receipt.status() {
+        bail!("Deployment {} reverted", receipt.transaction_hash);
+    }
+    receipt.contract_address.ok_or_else(|| {
+        anyhow!(
+            "Deployment {} created no contract",
+            receipt.transaction_hash
+        )
+    })
+}
+
+/// Deploy an `SP1MockVerifier`, which accepts [`MOCK_PROOF`] for any vkey and public values.
+pub async fn deploy_mock_verifier<P>(provider: &P) -> Result<Address>
+where
+    P: Provider<Ethereum>,
+{
+    deploy(
+        provider,
+        contract_bytecode("SP1MockVerifier.sol", "SP1MockVerifier")?,
+        &[],
+    )
+    .await
+}
+
+/// The parameters of a test L2OO. The starting output is at block `starting_block_number` and
+/// timestamp `starting_timestamp`, which must not be in the future.
+#[derive(Debug, Clone)]
+pub struct TestL2OOConfig {
+    pub owner: Address,
+    pub proposer: Address,
+    pub challenger: Address,
+    pub verifier: Address,
+    pub aggregation_vkey: B256,
+    pub range_vkey_commitment: B256,
+    pub rollup_config_hash: B256,
+    pub starting_output_root: B256,
+    pub starting_block_number: u64,
+    pub starting_timestamp: u64,
+    pub submission_interval: u64,
+    pub l2_block_time: u64,
+    pub finalization_period: u64,
+}
+
+impl TestL2OOConfig {
+    /// A config verifying against `verifier`, owned and proposed to by `account`, starting at block
+    /// 0 at `starting_timestamp`.
+    pub fn new(account: Address, verifier: Address, starting_timestamp: u64) -> Self {
+        Self {
+            owner: account,
+            proposer: account,
+            challenger: Address::ZERO,
+            verifier,
+            aggregation_vkey: B256::repeat_byte(0xaa),
+            range_vkey_commitment: B256::repeat_byte(0xbb),
+            rollup_config_hash: B256::repeat_byte(0xcc),
+            starting_output_root: B256::repeat_byte(0x01),
+            starting_block_number: 0,
+            starting_timestamp,
+            submission_interval: 10,
+            l2_block_time: 2,
+            finalization_period: 0,
+        }
+    }
+}
+
+/// Deploy an `OPSuccinctL2OutputOracle` behind a proxy administered by `admin`, the provider's
+/// default account, and initialize it with `config`. Returns the address of the proxy.
+pub async fn deploy_test_l2oo<P>(
+    provider: &P,
+    admin: Address,
+    config: &TestL2OOConfig,
+) -> Result<Address>
+where
+    P: Provider<Ethereum> + Clone,
+{
+    let implementation = deploy(
+        provider,
+        contract_bytecode("OPSuccinctL2OutputOracle.sol", "OPSuccinctL2OutputOracle")?,
+        &[],
+    )
+    .await?;
+    let proxy = deploy(
+        provider,
+        contract_bytecode("Proxy.sol", "Proxy")?,
+        &admin.abi_encode(),
+    )
+    .await?;
+
+    let initialize = IOPSuccinctL2OutputOracleInitializer::initializeCall {
+        _initParams: IOPSuccinctL2OutputOracleInitializer::InitParams {
+            challenger: config.challenger,
+            proposer: config.proposer,
+            owner: config.owner,
+            finalizationPeriodSeconds: U256::from(config.finalization_period),
+            l2BlockTime: U256::from(config.l2_block_time),
+            aggregationVkey: config.aggregation_vkey,
+            rangeVkeyCommitment: config.range_vkey_commitment,
+            rollupConfigHash: config.rollup_config_hash,
+            startingOutputRoot: config.starting_output_root,
+            startingBlockNumber: U256::from(config.starting_block_number),
+            startingTimestamp: U256::from(config.starting_timestamp),
+            submissionInterval: U256::from(config.submission_interval),
+            verifier: config.verifier,
+        },
+    };
+    let receipt = Proxy::new(proxy, provider.clone())
+        .upgradeToAndCall(implementation, initialize.abi_encode().into())
+        .from(admin)
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+    if !receipt.status() {
+        bail!("Initializing the L2OO {} reverted", proxy);
+    }
+
+    Ok(proxy)
+}
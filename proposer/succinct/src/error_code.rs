@@ -0,0 +1,240 @@
+//! Classification of the server's errors into [`ErrorCode`]s.
+//!
+//! Every error response carries the code of its failure in the [`ERROR_CODE_HEADER`], and is
+//! counted by route and code in the server's metrics, so dashboards can break down failures by
+//! cause. An error is classified by the first typed error in its chain with a known cause, or the
+//! code it was tagged with by [`with_code`] where the server knows the cause but has no typed error
+//! for it. Failures the host only reports by message, like a pruned blob, are recognized by their
+//! message.
+//!
+//! [`ERROR_CODE_HEADER`]: crate::ERROR_CODE_HEADER
+
+use std::{error::Error, fmt};
+
+use op_succinct_host_utils::{
+    agg_stdin::AggStdinError,
+    supervisor::{OversizedBlockWitness, WitnessGenStalled},
+};
+
+use crate::{
+    custody::WitnessHashMismatch,
+    diagnostics::UnexecutableSpan,
+    validation::{BrokenSubproofChain, OversizedStdin},
+    ErrorCode,
+};
+
+/// An error tagged with the code of its cause. Displays as the error itself.
+#[derive(Debug)]
+pub struct CodedError {
+    pub code: ErrorCode,
+    pub error: anyhow::Error,
+}
+
+impl fmt::Display for CodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl Error for CodedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// Tag an error with the code of its cause.
+pub fn with_code(code: ErrorCode, error: impl Into<anyhow::Error>) -> anyhow::Error {
+    CodedError {
+        code,
+        error: error.into(),
+    }
+    .into()
+}
+
+/// The code of an error's cause, [`ErrorCode::Internal`] if it is unknown.
+pub fn error_code(error: &anyhow::Error) -> ErrorCode {
+    classify(error, ErrorCode::Internal)
+}
+
+fn classify(error: &anyhow::Error, unknown: ErrorCode) -> ErrorCode {
+    error
+        .chain()
+        .find_map(cause_code)
+        .or_else(|| message_code(&format!("{:#}", error)))
+        .unwrap_or(unknown)
+}
+
+fn cause_code(cause: &(dyn Error + 'static)) -> Option<ErrorCode> {
+    if let Some(coded) = cause.downcast_ref::<CodedError>() {
+        return Some(coded.code);
+    }
+    // Witness generation fails for many reasons, the more specific of which are in its error.
+    if let Some(failure) = cause.downcast_ref::<UnexecutableSpan>() {
+        return Some(classify(&failure.error, ErrorCode::UnexecutableSpan));
+    }
+    if cause.is::<OversizedStdin>() || cause.is::<OversizedBlockWitness>() {
+        return Some(ErrorCode::WitnessTooLarge);
+    }
+    if cause.is::<WitnessGenStalled>() {
+        return Some(ErrorCode::WitnessGenStalled);
+    }
+    if cause.is::<WitnessHashMismatch>() {
+        return Some(ErrorCode::WitnessHashMismatch);
+    }
+    if let Some(broken) = cause.downcast_ref::<BrokenSubproofChain>() {
+        return Some(match broken {
+            BrokenSubproofChain::QuorumMismatch { .. } => ErrorCode::QuorumMismatch,
+            _ => ErrorCode::InvalidAggInput,
+        });
+    }
+    if cause.is::<AggStdinError>() {
+        return Some(ErrorCode::InvalidAggInput);
+    }
+    if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+        if e.is_connect() || e.is_timeout() {
+            return Some(ErrorCode::RpcUnavailable);
+        }
+    }
+    None
+}
+
+/// Whether failures with a code are transient, i.e. failures of the RPCs the span's witness is
+/// generated from rather than of the span itself. Retrying the span as is may succeed, while
+/// splitting it doesn't help.
+pub fn is_transient(code: ErrorCode) -> bool {
+    matches!(
+        code,
+        ErrorCode::RpcUnavailable | ErrorCode::WitnessGenStalled
+    )
+}
+
+/// Whether an error is a failure of the request itself, which recurs however often it is retried,
+/// like a span whose execution or oracle lookups fail. The server responds to these with `422
+/// Unprocessable Entity`, and to any other error, including witness generation failing because an
+/// RPC did, with `500 Internal Server Error`.
+pub fn is_unprocessable(error: &anyhow::Error) -> bool {
+    if error.is::<UnexecutableSpan>() {
+        return !is_transient(error_code(error));
+    }
+    error.is::<BrokenSubproofChain>() || error.is::<OversizedStdin>() || error.is::<AggStdinError>()
+}
+
+/// Whether an error turned a request away without attempting it, like a request to a paused
+/// server. The server responds to these with `503 Service Unavailable`, and the request is sent
+/// again as is later rather than recorded as failed.
+pub fn is_unavailable(error: &anyhow::Error) -> bool {
+    error_code(error) == ErrorCode::Paused
+}
+
+/// The code of a failure only known by its message.
+fn message_code(message: &str) -> Option<ErrorCode> {
+    let message = message.to_lowercase();
+    let contains_any = |needles: &[&str]| needles.iter().any(|needle| message.contains(needle));
+    if message.contains("blob") && contains_any(&["not found", "pruned", "missing"]) {
+        Some(ErrorCode::BlobPruned)
+    } else if contains_any(&[
+        "error sending request",
+        "connection refused",
+        "connection reset",
+        "too many requests",
+        "rate limit",
+    ]) {
+        Some(ErrorCode::RpcUnavailable)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anyhow::anyhow;
+
+    fn unexecutable(error: anyhow::Error) -> anyhow::Error {
+        UnexecutableSpan {
+            start: 100,
+            end: 200,
+            error,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_error_code() {
+        assert_eq!(error_code(&anyhow!("Unexpected")), ErrorCode::Internal);
+
+        let coded = with_code(
+            ErrorCode::ProverUnavailable,
+            anyhow!("Failed to request proof"),
+        );
+        assert_eq!(coded.to_string(), "Failed to request proof");
+        assert_eq!(error_code(&coded), ErrorCode::ProverUnavailable);
+
+        let oversized = OversizedStdin {
+            start: 100,
+            end: 200,
+            size: 2,
+            limit: 1,
+        };
+        assert_eq!(error_code(&oversized.into()), ErrorCode::WitnessTooLarge);
+
+        let quorum = BrokenSubproofChain::QuorumMismatch { index: 1 };
+        assert_eq!(error_code(&quorum.into()), ErrorCode::QuorumMismatch);
+        assert_eq!(
+            error_code(&BrokenSubproofChain::Empty.into()),
+            ErrorCode::InvalidAggInput
+        );
+    }
+
+    #[test]
+    fn test_error_code_of_unexecutable_span() {
+        assert_eq!(
+            error_code(&unexecutable(anyhow!("Execution failed"))),
+            ErrorCode::UnexecutableSpan
+        );
+        assert_eq!(
+            error_code(&unexecutable(
+                anyhow!("Blob not found").context("Failed to fetch blobs")
+            )),
+            ErrorCode::BlobPruned
+        );
+        assert_eq!(
+            error_code(&unexecutable(anyhow!(
+                "error sending request for url (http://localhost:8545/)"
+            ))),
+            ErrorCode::RpcUnavailable
+        );
+    }
+
+    #[test]
+    fn test_unprocessable_only_if_deterministic() {
+        assert!(is_unprocessable(&unexecutable(anyhow!(
+            "Failed to validate L2 block #200"
+        ))));
+        assert!(is_unprocessable(&BrokenSubproofChain::Empty.into()));
+        // The span may be proven once the RPC is back, so it is retried rather than split.
+        assert!(!is_unprocessable(&unexecutable(anyhow!(
+            "error sending request for url (http://localhost:8545/)"
+        ))));
+        let stalled = WitnessGenStalled {
+            start: 100,
+            end: 200,
+            block: 150,
+            idle_secs: 600,
+            last_hint: None,
+            source: None,
+        };
+        assert!(!is_unprocessable(&unexecutable(stalled.into())));
+        assert!(!is_unprocessable(&anyhow!("Unexpected")));
+    }
+
+    #[test]
+    fn test_unavailable_only_if_paused() {
+        let paused = with_code(ErrorCode::Paused, anyhow!("Server is paused: upgrading"));
+        assert!(is_unavailable(&paused));
+        assert!(!is_unprocessable(&paused));
+        assert!(!is_unavailable(&anyhow!("Unexpected")));
+        assert!(!is_unavailable(&BrokenSubproofChain::Empty.into()));
+    }
+}
@@ -1,109 +1,106 @@
-use alloy_primitives::B256;
-use base64::{engine::general_purpose, Engine as _};
-use serde::{Deserialize, Deserializer, Serialize};
-use serde_repr::{Deserialize_repr, Serialize_repr};
-use sp1_sdk::{
-    network::FulfillmentStrategy, NetworkProver, SP1ProofMode, SP1ProvingKey, SP1VerifyingKey,
-};
-use std::sync::Arc;
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ValidateConfigRequest {
-    pub address: String,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ValidateConfigResponse {
-    pub rollup_config_hash_valid: bool,
-    pub agg_vkey_valid: bool,
-    pub range_vkey_valid: bool,
-}
-
-#[derive(Deserialize, Serialize, Debug)]
-pub struct SpanProofRequest {
-    pub start: u64,
-    pub end: u64,
-}
-
-#[derive(Deserialize, Serialize, Debug)]
-pub struct AggProofRequest {
-    #[serde(deserialize_with = "deserialize_base64_vec")]
-    pub subproofs: Vec<Vec<u8>>,
-    pub head: String,
-}
+pub mod agg_requests;
+pub mod artifact;
+pub mod auditor;
+pub mod checkpoint;
+pub mod config;
+pub mod contract;
+pub mod custody;
+pub mod diagnostics;
+pub mod download;
+#[cfg(feature = "e2e")]
+pub mod e2e;
+pub mod error_code;
+pub mod metrics;
+pub mod pause;
+pub mod policy;
+pub mod programs;
+pub mod quorum;
+pub mod readiness;
+pub mod reconciler;
+pub mod rpc_overrides;
+#[cfg(feature = "e2e")]
+pub mod submission;
+pub mod sync_status;
+#[cfg(feature = "e2e")]
+pub mod tx_manager;
+pub mod utils;
+pub mod validation;
+pub mod webhook;
+pub mod witnessgen_events;
 
-#[derive(Deserialize, Serialize, Debug)]
-pub struct MockProofResponse {
-    pub proof_id: String,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ProofResponse {
-    pub proof_id: Vec<u8>,
-}
+use alloy_primitives::B256;
+use op_succinct_host_utils::{da::DataAvailabilitySource, progress::InFlightRuns};
+use sp1_sdk::NetworkProver;
+use std::sync::{Arc, Mutex};
 
-#[derive(Debug, Serialize_repr, Deserialize_repr)]
-#[repr(i32)]
-/// The type of error that occurred when unclaiming a proof. Based off of the `unclaim_description`
-/// field in the `ProofStatus` struct.
-pub enum UnclaimDescription {
-    UnexpectedProverError = 0,
-    ProgramExecutionError = 1,
-    CycleLimitExceeded = 2,
-    Other = 3,
-}
+// The API types are defined in the client SDK, so the server and its clients share them.
+pub use op_succinct_client_sdk::types::*;
 
-/// Convert a string to an `UnclaimDescription`. These cover the common reasons why a proof might
-/// be unclaimed.
-impl From<String> for UnclaimDescription {
-    fn from(description: String) -> Self {
-        match description.as_str().to_lowercase().as_str() {
-            "unexpected prover error" => UnclaimDescription::UnexpectedProverError,
-            "program execution error" => UnclaimDescription::ProgramExecutionError,
-            "cycle limit exceeded" => UnclaimDescription::CycleLimitExceeded,
-            _ => UnclaimDescription::Other,
-        }
-    }
-}
+pub const RANGE_ELF: &[u8] = include_bytes!("../../../elf/range-elf");
+pub const AGG_ELF: &[u8] = include_bytes!("../../../elf/aggregation-elf");
 
-#[derive(Serialize, Deserialize)]
-/// The status of a proof request.
-pub struct ProofStatus {
-    // Note: Can't use `FulfillmentStatus`/`ExecutionStatus` directly because `Serialize_repr` and `Deserialize_repr` aren't derived on it.
-    pub fulfillment_status: i32,
-    pub execution_status: i32,
-    pub proof: Vec<u8>,
-}
+use crate::{
+    agg_requests::AggRequestStore,
+    artifact::ProofArtifacts,
+    auditor::AuditStatus,
+    custody::WitnessCache,
+    metrics::ServerMetrics,
+    pause::PauseStore,
+    policy::AggregationStatus,
+    programs::CurrentProvingContext,
+    readiness::ReadinessCache,
+    reconciler::{ProofTracker, ReconcilerConfig},
+    rpc_overrides::RpcOverrideAllowlist,
+    sync_status::SyncStatus,
+    webhook::Webhooks,
+};
 
 /// Configuration of the L2 Output Oracle contract. Created once at server start-up, monitors if there are any changes
 /// to the contract's configuration.
 #[derive(Clone)]
 pub struct SuccinctProposerConfig {
-    pub range_vk: Arc<SP1VerifyingKey>,
-    pub range_pk: Arc<SP1ProvingKey>,
-    pub agg_pk: Arc<SP1ProvingKey>,
-    pub agg_vk: Arc<SP1VerifyingKey>,
-    pub agg_vkey_hash: B256,
-    pub range_vkey_commitment: B256,
+    /// The programs and settings new proofs are requested with, replaced on reload.
+    pub proving: CurrentProvingContext,
+    /// Held while reloading, so concurrent reloads don't race.
+    pub reload_lock: Arc<tokio::sync::Mutex<()>>,
     pub rollup_config_hash: B256,
-    pub range_proof_strategy: FulfillmentStrategy,
-    pub agg_proof_strategy: FulfillmentStrategy,
-    pub agg_proof_mode: SP1ProofMode,
     pub network_prover: Arc<NetworkProver>,
-}
-
-/// Deserialize a vector of base64 strings into a vector of vectors of bytes. Go serializes
-/// the subproofs as base64 strings.
-fn deserialize_base64_vec<'de, D>(deserializer: D) -> Result<Vec<Vec<u8>>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let s: Vec<String> = Deserialize::deserialize(deserializer)?;
-    s.into_iter()
-        .map(|base64_str| {
-            general_purpose::STANDARD
-                .decode(base64_str)
-                .map_err(serde::de::Error::custom)
-        })
-        .collect()
+    /// The aggregation policy and the last decision it made.
+    pub aggregation_status: Arc<Mutex<AggregationStatus>>,
+    /// The local status of every requested proof, kept up to date by the reconciler.
+    pub proof_tracker: Arc<ProofTracker>,
+    /// The proof IDs of the aggregation proofs requested, persisted across restarts.
+    pub agg_requests: Arc<AggRequestStore>,
+    /// The artifacts of fulfilled span proofs, if enabled.
+    pub proof_artifacts: Option<Arc<ProofArtifacts>>,
+    /// The stdins of span proofs, to prove retried spans from, if enabled.
+    pub witness_cache: Option<Arc<WitnessCache>>,
+    pub reconciler_config: ReconcilerConfig,
+    /// Set while the server is paused. New witness generation and proof requests are rejected,
+    /// while in-flight ones complete.
+    pub pause_state: Arc<PauseStore>,
+    /// The bearer token required by the admin endpoints. The admin endpoints are disabled if unset.
+    pub admin_api_key: Option<String>,
+    /// The data availability source served to witness generation by the beacon API proxy, if
+    /// witness generation doesn't read from the beacon node directly.
+    pub da_source: Option<Arc<dyn DataAvailabilitySource>>,
+    /// The outcome of the audits of the outputs on the L2OO, if the auditor is enabled.
+    pub audit_status: Option<Arc<Mutex<AuditStatus>>>,
+    /// The rollup node's latest heads, if the sync status tracker is enabled.
+    pub sync_status: Option<Arc<Mutex<SyncStatus>>>,
+    /// The metrics of the server's endpoints, served at `GET /metrics`.
+    pub metrics: Arc<ServerMetrics>,
+    /// The progress of the witness generation runs in flight, streamed at
+    /// `GET /witnessgen/events`.
+    pub witnessgen_runs: InFlightRuns,
+    /// The callbacks of proof requests, sent once their proofs settle.
+    pub webhooks: Arc<Webhooks>,
+    /// The largest proof returned inline in status responses. Larger proofs are downloaded from
+    /// `GET /proofs/:proof_id/download`.
+    pub proof_inline_max_bytes: usize,
+    /// The endpoints span proof requests may generate their witness from instead of the server's
+    /// RPCs.
+    pub rpc_override_allowlist: RpcOverrideAllowlist,
+    /// The statuses of the last readiness check, served to probes until they expire.
+    pub readiness: Arc<ReadinessCache>,
 }
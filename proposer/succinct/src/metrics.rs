@@ -0,0 +1,338 @@
+//! Prometheus metrics of the server's endpoints, served at `GET /metrics`.
+//!
+//! Every routed request is counted by route, method and status, and its latency recorded in a
+//! histogram by route and method. Failed requests are also counted by route and the
+//! [`ErrorCode`] of their failure. Requests in flight are tracked per route, and witness
+//! generation, which dominates the latency and memory of span proof requests, separately, so the
+//! number of concurrent witness generations a server can sustain can be read off its metrics.
+//!
+//! The sync status tracker also records the rollup node's safe and finalized L2 heads, and counts
+//! the discrepancies between the rollup node and the L2 execution RPC, for alerting.
+//!
+//! The utilization and health of every RPC endpoint in the fetcher's provider pools are read when
+//! the metrics are rendered, by pool, endpoint and request class, as are the witness generation
+//! runs that stalled on each RPC.
+
+use std::{sync::Arc, time::Instant};
+
+use anyhow::Result;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use op_succinct_host_utils::{provider_pool::registered_pools, supervisor::stall_counts};
+use prometheus::{
+    GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+
+use crate::ErrorCode;
+
+/// The latency buckets, in seconds. Span proof requests wait on witness generation, which takes
+/// minutes for large spans.
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1200.0,
+];
+
+/// The metrics of the server's endpoints.
+pub struct ServerMetrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    errors: IntCounterVec,
+    latency: HistogramVec,
+    in_flight: IntGaugeVec,
+    witness_generation_in_flight: IntGauge,
+    rollup_head: IntGaugeVec,
+    sync_discrepancies: IntCounterVec,
+    oversized_blocks: IntCounterVec,
+    witness_generation_stalls: IntGaugeVec,
+    provider_requests: IntGaugeVec,
+    provider_errors: IntGaugeVec,
+    provider_latency: GaugeVec,
+    provider_share: GaugeVec,
+}
+
+impl ServerMetrics {
+    pub fn new() -> Result<Self> {
+        let requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "The number of requests handled."),
+            &["route", "method", "status"],
+        )?;
+        let errors = IntCounterVec::new(
+            Opts::new(
+                "http_request_errors_total",
+                "The number of failed requests, by the code of their failure.",
+            ),
+            &["route", "code"],
+        )?;
+        let latency = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "The time taken to handle a request.",
+            )
+            .buckets(LATENCY_BUCKETS.to_vec()),
+            &["route", "method"],
+        )?;
+        let in_flight = IntGaugeVec::new(
+            Opts::new(
+                "http_requests_in_flight",
+                "The number of requests being handled.",
+            ),
+            &["route"],
+        )?;
+        let witness_generation_in_flight = IntGauge::new(
+            "witness_generation_in_flight",
+            "The number of witness generations running.",
+        )?;
+
+        let rollup_head = IntGaugeVec::new(
+            Opts::new(
+                "rollup_node_head_block",
+                "The L2 block of the rollup node's safe and finalized heads.",
+            ),
+            &["head"],
+        )?;
+        let sync_discrepancies = IntCounterVec::new(
+            Opts::new(
+                "sync_status_discrepancies_total",
+                "The number of times the L2 execution RPC disagreed with the rollup node's heads.",
+            ),
+            &["head", "kind"],
+        )?;
+
+        let oversized_blocks = IntCounterVec::new(
+            Opts::new(
+                "witness_oversized_blocks_total",
+                "The number of witness generations cancelled by a block exceeding the per-block \
+                 witness limit.",
+            ),
+            &["policy"],
+        )?;
+
+        let witness_generation_stalls = IntGaugeVec::new(
+            Opts::new(
+                "witness_generation_stalls",
+                "The number of witness generation runs cancelled for stalling, by the RPC the run \
+                 was waiting on.",
+            ),
+            &["provider"],
+        )?;
+
+        let provider_labels = &["pool", "endpoint", "class"];
+        let provider_requests = IntGaugeVec::new(
+            Opts::new(
+                "rpc_provider_requests",
+                "The number of requests sent to an RPC endpoint of a provider pool.",
+            ),
+            provider_labels,
+        )?;
+        let provider_errors = IntGaugeVec::new(
+            Opts::new(
+                "rpc_provider_errors",
+                "The number of requests to an RPC endpoint of a provider pool that failed.",
+            ),
+            provider_labels,
+        )?;
+        let provider_latency = GaugeVec::new(
+            Opts::new(
+                "rpc_provider_latency_seconds",
+                "The rolling latency of an RPC endpoint of a provider pool.",
+            ),
+            provider_labels,
+        )?;
+        let provider_share = GaugeVec::new(
+            Opts::new(
+                "rpc_provider_share",
+                "The share of a provider pool's requests of a class sent to an RPC endpoint.",
+            ),
+            provider_labels,
+        )?;
+
+        let registry = Registry::new_custom(Some("op_succinct".to_string()), None)?;
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(errors.clone()))?;
+        registry.register(Box::new(latency.clone()))?;
+        registry.register(Box::new(in_flight.clone()))?;
+        registry.register(Box::new(witness_generation_in_flight.clone()))?;
+        registry.register(Box::new(rollup_head.clone()))?;
+        registry.register(Box::new(sync_discrepancies.clone()))?;
+        registry.register(Box::new(oversized_blocks.clone()))?;
+        registry.register(Box::new(witness_generation_stalls.clone()))?;
+        registry.register(Box::new(provider_requests.clone()))?;
+        registry.register(Box::new(provider_errors.clone()))?;
+        registry.register(Box::new(provider_latency.clone()))?;
+        registry.register(Box::new(provider_share.clone()))?;
+
+        Ok(Self {
+            registry,
+            requests,
+            errors,
+            latency,
+            in_flight,
+            witness_generation_in_flight,
+            rollup_head,
+            sync_discrepancies,
+            oversized_blocks,
+            witness_generation_stalls,
+            provider_requests,
+            provider_errors,
+            provider_latency,
+            provider_share,
+        })
+    }
+
+    /// Record a handled request.
+    pub fn observe(&self, route: &str, method: &str, status: u16, seconds: f64) {
+        self.requests
+            .with_label_values(&[route, method, &status.to_string()])
+            .inc();
+        self.latency
+            .with_label_values(&[route, method])
+            .observe(seconds);
+    }
+
+    /// Count a failed request by the code of its failure.
+    pub fn record_error(&self, route: &str, code: ErrorCode) {
+        self.errors.with_label_values(&[route, code.as_str()]).inc();
+    }
+
+    /// Track a running witness generation until the returned guard is dropped.
+    pub fn witness_generation(&self) -> InFlight {
+        InFlight::new(self.witness_generation_in_flight.clone())
+    }
+
+    /// Record the L2 block of one of the rollup node's heads, `safe` or `finalized`.
+    pub fn record_rollup_head(&self, head: &str, number: u64) {
+        self.rollup_head
+            .with_label_values(&[head])
+            .set(number as i64);
+    }
+
+    /// Count a discrepancy between the L2 execution RPC and the rollup node's view of a head.
+    pub fn record_sync_discrepancy(&self, head: &str, kind: &str) {
+        self.sync_discrepancies
+            .with_label_values(&[head, kind])
+            .inc();
+    }
+
+    /// Count a witness generation cancelled by a block exceeding the per-block witness limit.
+    pub fn record_oversized_block(&self, policy: &str) {
+        self.oversized_blocks.with_label_values(&[policy]).inc();
+    }
+
+    /// Record the stalled witness generation runs of the process.
+    fn record_witness_generation_stalls(&self) {
+        for (provider, count) in stall_counts() {
+            self.witness_generation_stalls
+                .with_label_values(&[&provider])
+                .set(count as i64);
+        }
+    }
+
+    /// Record the utilization and health of the endpoints of the provider pools.
+    fn record_provider_pools(&self) {
+        for pool in registered_pools() {
+            for stats in pool.stats() {
+                let class = stats.class.to_string();
+                let labels = [pool.name(), stats.endpoint.as_str(), class.as_str()];
+                self.provider_requests
+                    .with_label_values(&labels)
+                    .set(stats.requests as i64);
+                self.provider_errors
+                    .with_label_values(&labels)
+                    .set(stats.errors as i64);
+                self.provider_latency
+                    .with_label_values(&labels)
+                    .set(stats.latency_secs);
+                self.provider_share
+                    .with_label_values(&labels)
+                    .set(stats.share);
+            }
+        }
+    }
+
+    /// The metrics in the Prometheus text format.
+    pub fn render(&self) -> Result<String> {
+        self.record_provider_pools();
+        self.record_witness_generation_stalls();
+        Ok(TextEncoder::new().encode_to_string(&self.registry.gather())?)
+    }
+}
+
+/// Increments a gauge while alive, and decrements it when dropped, including when the future
+/// holding it is cancelled.
+pub struct InFlight(IntGauge);
+
+impl InFlight {
+    fn new(gauge: IntGauge) -> Self {
+        gauge.inc();
+        Self(gauge)
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+/// Middleware recording the metrics of every routed request. Must be added with
+/// `Router::route_layer`, so requests are labeled with the route they matched rather than their
+/// path, and requests to unknown paths aren't recorded.
+pub async fn track_metrics(
+    State(metrics): State<Arc<ServerMetrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("unmatched", |path| path.as_str())
+        .to_string();
+    let method = request.method().to_string();
+
+    let start = Instant::now();
+    let response = {
+        let _in_flight = InFlight::new(metrics.in_flight.with_label_values(&[&route]));
+        next.run(request).await
+    };
+    metrics.observe(
+        &route,
+        &method,
+        response.status().as_u16(),
+        start.elapsed().as_secs_f64(),
+    );
+    if let Some(code) = response.extensions().get::<ErrorCode>() {
+        metrics.record_error(&route, *code);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let metrics = ServerMetrics::new().unwrap();
+        metrics.observe("/status/:proof_id", "GET", 200, 0.02);
+        metrics.record_error("/request_span_proof", ErrorCode::BlobPruned);
+        {
+            let _first = metrics.witness_generation();
+            let _second = metrics.witness_generation();
+            assert_eq!(metrics.witness_generation_in_flight.get(), 2);
+        }
+        assert_eq!(metrics.witness_generation_in_flight.get(), 0);
+
+        let rendered = metrics.render().unwrap();
+        assert!(rendered.contains(
+            r#"op_succinct_http_requests_total{method="GET",route="/status/:proof_id",status="200"} 1"#
+        ));
+        assert!(rendered.contains(
+            r#"op_succinct_http_request_errors_total{code="E_BLOB_PRUNED",route="/request_span_proof"} 1"#
+        ));
+        assert!(rendered.contains("op_succinct_witness_generation_in_flight 0"));
+    }
+}
@@ -0,0 +1,89 @@
+//! The pause state of the server, persisted to `{l2_chain_id}/pause.json` in the workspace so a
+//! server paused during an incident stays paused when it is restarted.
+
+use std::{fs, io, path::PathBuf, sync::Mutex};
+
+use anyhow::{Context, Result};
+
+use crate::PauseState;
+
+/// The pause state of the server, kept in memory and mirrored to a file.
+#[derive(Debug)]
+pub struct PauseStore {
+    path: PathBuf,
+    state: Mutex<Option<PauseState>>,
+}
+
+impl PauseStore {
+    /// Load the pause state from the file at `path`. The server is not paused if it doesn't exist.
+    pub fn load(path: PathBuf) -> Result<Self> {
+        let state = match fs::read(&path) {
+            Ok(bytes) => Some(
+                serde_json::from_slice(&bytes)
+                    .with_context(|| format!("Invalid pause state in {}", path.display()))?,
+            ),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e).context(format!("Failed to read {}", path.display())),
+        };
+        Ok(Self {
+            path,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Why and when the server was paused, if it is.
+    pub fn get(&self) -> Option<PauseState> {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Pause the server, or resume it with `None`. The file is written before the state changes
+    /// in memory, so a failure leaves both unchanged.
+    pub fn set(&self, state: Option<PauseState>) -> Result<()> {
+        let mut current = self.state.lock().unwrap();
+        match &state {
+            Some(state) => {
+                fs::create_dir_all(self.path.parent().unwrap())?;
+                // Write to a temporary file and rename it, so a crash doesn't leave a torn file.
+                let tmp = self.path.with_extension("json.tmp");
+                fs::write(&tmp, serde_json::to_vec_pretty(state)?)?;
+                fs::rename(&tmp, &self.path)?;
+            }
+            None => match fs::remove_file(&self.path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            },
+        }
+        *current = state;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_state_survives_restart() {
+        let dir = std::env::temp_dir().join(format!("pause-test-{}", std::process::id()));
+        let path = dir.join("10").join("pause.json");
+
+        let store = PauseStore::load(path.clone()).unwrap();
+        assert!(store.get().is_none());
+        store
+            .set(Some(PauseState {
+                reason: "L1 contract upgrade".to_string(),
+                paused_at: 1000,
+            }))
+            .unwrap();
+
+        let restarted = PauseStore::load(path.clone()).unwrap();
+        let state = restarted.get().unwrap();
+        assert_eq!(state.reason, "L1 contract upgrade");
+        assert_eq!(state.paused_at, 1000);
+
+        restarted.set(None).unwrap();
+        assert!(PauseStore::load(path).unwrap().get().is_none());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,222 @@
+use op_succinct_host_utils::config::var_from;
+use serde::{Deserialize, Serialize};
+
+/// Controls when the proposer aggregates the span proofs it has collected into an aggregation
+/// proof. Aggregation is triggered as soon as any of the enabled conditions is met.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregationPolicy {
+    /// Aggregate once at least this many contiguous span proofs are unaggregated.
+    pub max_span_proofs: Option<u64>,
+    /// Aggregate once the oldest unaggregated span proof is at least this old, in seconds.
+    pub max_oldest_proof_age_secs: Option<u64>,
+    /// Aggregate whenever the L1 gas price (in wei) is at or below this value.
+    pub max_l1_gas_price: Option<u128>,
+}
+
+impl AggregationPolicy {
+    /// Read the aggregation policy from the variables `vars` looks up. Unset variables disable the
+    /// corresponding condition.
+    ///
+    /// - `AGG_POLICY_MAX_SPAN_PROOFS`: The number of span proofs after which to aggregate.
+    /// - `AGG_POLICY_MAX_AGE_MINUTES`: The age of the oldest span proof after which to aggregate.
+    /// - `AGG_POLICY_MAX_L1_GAS_PRICE_GWEI`: The L1 gas price below which to aggregate.
+    pub fn from_vars(vars: &dyn Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let max_span_proofs = var_from(vars, "AGG_POLICY_MAX_SPAN_PROOFS")?;
+        let max_oldest_proof_age_secs =
+            var_from::<u64>(vars, "AGG_POLICY_MAX_AGE_MINUTES")?.map(|minutes| minutes * 60);
+        let max_l1_gas_price = var_from::<u128>(vars, "AGG_POLICY_MAX_L1_GAS_PRICE_GWEI")?
+            .map(|gwei| gwei * 1_000_000_000);
+
+        Ok(Self {
+            max_span_proofs,
+            max_oldest_proof_age_secs,
+            max_l1_gas_price,
+        })
+    }
+
+    /// Decide whether to aggregate the pending span proofs.
+    pub fn evaluate(
+        &self,
+        pending: &PendingSpanProofs,
+        l1_gas_price: Option<u128>,
+    ) -> AggregationDecision {
+        if pending.count == 0 {
+            return AggregationDecision::wait(AggregationReason::NoPendingProofs);
+        }
+
+        if let Some(max_span_proofs) = self.max_span_proofs {
+            if pending.count >= max_span_proofs {
+                return AggregationDecision::aggregate(AggregationReason::SpanProofCount);
+            }
+        }
+
+        if let Some(max_age_secs) = self.max_oldest_proof_age_secs {
+            if pending.oldest_proof_age_secs >= max_age_secs {
+                return AggregationDecision::aggregate(AggregationReason::OldestProofAge);
+            }
+        }
+
+        if let (Some(max_gas_price), Some(gas_price)) = (self.max_l1_gas_price, l1_gas_price) {
+            if gas_price <= max_gas_price {
+                return AggregationDecision::aggregate(AggregationReason::L1GasPrice);
+            }
+        }
+
+        // With no conditions configured, aggregate as soon as there are span proofs, matching the
+        // behavior without a policy.
+        if self.max_span_proofs.is_none()
+            && self.max_oldest_proof_age_secs.is_none()
+            && self.max_l1_gas_price.is_none()
+        {
+            return AggregationDecision::aggregate(AggregationReason::NoPolicy);
+        }
+
+        AggregationDecision::wait(AggregationReason::ConditionsNotMet)
+    }
+}
+
+/// A summary of the span proofs which have been proven but not yet aggregated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingSpanProofs {
+    /// The number of contiguous unaggregated span proofs.
+    pub count: u64,
+    /// The time in seconds since the oldest unaggregated span proof was completed.
+    pub oldest_proof_age_secs: u64,
+}
+
+/// Why the policy made its decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationReason {
+    NoPendingProofs,
+    SpanProofCount,
+    OldestProofAge,
+    L1GasPrice,
+    NoPolicy,
+    ConditionsNotMet,
+}
+
+/// The outcome of evaluating the [`AggregationPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregationDecision {
+    pub aggregate: bool,
+    pub reason: AggregationReason,
+}
+
+impl AggregationDecision {
+    fn aggregate(reason: AggregationReason) -> Self {
+        Self {
+            aggregate: true,
+            reason,
+        }
+    }
+
+    fn wait(reason: AggregationReason) -> Self {
+        Self {
+            aggregate: false,
+            reason,
+        }
+    }
+}
+
+/// The policy and the most recent decision made by it, exposed on the status endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregationStatus {
+    pub policy: AggregationPolicy,
+    /// The pending span proofs the last decision was made for.
+    pub pending: Option<PendingSpanProofs>,
+    /// The L1 gas price (in wei) at the time of the last decision.
+    pub l1_gas_price: Option<u128>,
+    pub decision: Option<AggregationDecision>,
+    /// The unix timestamp of the last decision.
+    pub evaluated_at: Option<u64>,
+}
+
+impl AggregationStatus {
+    pub fn new(policy: AggregationPolicy) -> Self {
+        Self {
+            policy,
+            pending: None,
+            l1_gas_price: None,
+            decision: None,
+            evaluated_at: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(count: u64, age_secs: u64) -> PendingSpanProofs {
+        PendingSpanProofs {
+            count,
+            oldest_proof_age_secs: age_secs,
+        }
+    }
+
+    #[test]
+    fn test_no_policy_aggregates_immediately() {
+        let policy = AggregationPolicy {
+            max_span_proofs: None,
+            max_oldest_proof_age_secs: None,
+            max_l1_gas_price: None,
+        };
+        assert_eq!(
+            policy.evaluate(&pending(1, 0), None),
+            AggregationDecision::aggregate(AggregationReason::NoPolicy)
+        );
+        assert_eq!(
+            policy.evaluate(&pending(0, 0), None),
+            AggregationDecision::wait(AggregationReason::NoPendingProofs)
+        );
+    }
+
+    #[test]
+    fn test_policy_conditions() {
+        let policy = AggregationPolicy {
+            max_span_proofs: Some(10),
+            max_oldest_proof_age_secs: Some(60 * 60),
+            max_l1_gas_price: Some(5_000_000_000),
+        };
+
+        assert_eq!(
+            policy.evaluate(&pending(10, 0), Some(u128::MAX)),
+            AggregationDecision::aggregate(AggregationReason::SpanProofCount)
+        );
+        assert_eq!(
+            policy.evaluate(&pending(3, 60 * 60), Some(u128::MAX)),
+            AggregationDecision::aggregate(AggregationReason::OldestProofAge)
+        );
+        assert_eq!(
+            policy.evaluate(&pending(3, 0), Some(1_000_000_000)),
+            AggregationDecision::aggregate(AggregationReason::L1GasPrice)
+        );
+        assert_eq!(
+            policy.evaluate(&pending(3, 0), Some(10_000_000_000)),
+            AggregationDecision::wait(AggregationReason::ConditionsNotMet)
+        );
+        // An unknown gas price never triggers the gas condition.
+        assert_eq!(
+            policy.evaluate(&pending(3, 0), None),
+            AggregationDecision::wait(AggregationReason::ConditionsNotMet)
+        );
+    }
+
+    #[test]
+    fn test_policy_from_vars() {
+        let vars = |name: &str| match name {
+            "AGG_POLICY_MAX_SPAN_PROOFS" => Some("10".to_string()),
+            "AGG_POLICY_MAX_AGE_MINUTES" => Some("2".to_string()),
+            _ => None,
+        };
+        let policy = AggregationPolicy::from_vars(&vars).unwrap();
+        assert_eq!(policy.max_span_proofs, Some(10));
+        assert_eq!(policy.max_oldest_proof_age_secs, Some(120));
+        assert_eq!(policy.max_l1_gas_price, None);
+
+        let invalid =
+            |name: &str| (name == "AGG_POLICY_MAX_SPAN_PROOFS").then(|| "ten".to_string());
+        assert!(AggregationPolicy::from_vars(&invalid).is_err());
+    }
+}
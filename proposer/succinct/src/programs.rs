@@ -0,0 +1,208 @@
+//! The programs and settings the server proves with, and reloading them without a restart.
+//!
+//! The server loads the range and aggregation programs' keys and its proving settings into a
+//! [`ProvingContext`] at startup. `POST /admin/reload` and `SIGHUP` re-read the environment file
+//! into a new [`ProvingConfig`], without changing the process's environment, and build a new
+//! context, which serves every request received from then on. Each request holds the context it
+//! started with until it completes, so in-flight witness generation and proof requests drain
+//! against the old programs while new ones use the new programs.
+//!
+//! The programs are embedded in the server, unless runtime loading is enabled by setting
+//! `RANGE_ELF_PATH` or `AGGREGATION_ELF_PATH`, in which case the program is read from that file on
+//! startup and on every reload, so an upgrade only replaces the file and reloads the server.
+
+use std::{
+    fs,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, RwLock, Weak},
+    time::Duration,
+};
+
+use alloy_primitives::B256;
+use anyhow::{bail, Context, Result};
+use log::info;
+use op_succinct_host_utils::vkeys::{vkey_commitment, VkeyCommitment};
+use sp1_sdk::{
+    network::FulfillmentStrategy, HashableKey, NetworkProver, Prover, SP1ProofMode, SP1ProvingKey,
+    SP1VerifyingKey,
+};
+
+use crate::{
+    artifact::SpanProofVkeys, config::ProvingConfig, quorum::SecondaryRangeProgram, ReloadResponse,
+};
+
+/// How often a reload checks whether the requests against the previous context have completed.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The ELFs of the programs the server proves with.
+#[derive(Clone)]
+pub struct ProgramElfs {
+    pub range: Arc<[u8]>,
+    pub aggregation: Arc<[u8]>,
+    /// In quorum mode, the ELF of the secondary range program.
+    pub secondary_range: Option<Arc<[u8]>>,
+}
+
+impl ProgramElfs {
+    /// The ELFs configured by `config`: read from `RANGE_ELF_PATH` and `AGGREGATION_ELF_PATH` if
+    /// set, and the embedded ELFs otherwise.
+    pub fn load(
+        config: &ProvingConfig,
+        embedded_range: &[u8],
+        embedded_agg: &[u8],
+    ) -> Result<Self> {
+        let read = |path: &Path| -> Result<Arc<[u8]>> {
+            Ok(fs::read(path)
+                .with_context(|| format!("Failed to read ELF {}", path.display()))?
+                .into())
+        };
+        Ok(Self {
+            range: match &config.range_elf {
+                Some(path) => read(path)?,
+                None => embedded_range.into(),
+            },
+            aggregation: match &config.aggregation_elf {
+                Some(path) => read(path)?,
+                None => embedded_agg.into(),
+            },
+            secondary_range: config
+                .secondary_range_elf
+                .as_deref()
+                .map(read)
+                .transpose()?,
+        })
+    }
+}
+
+/// The programs and settings span and aggregation proofs are requested with.
+pub struct ProvingContext {
+    /// Incremented by every reload, starting at 0.
+    pub generation: u64,
+    pub range_elf: Arc<[u8]>,
+    pub range_pk: Arc<SP1ProvingKey>,
+    pub range_vk: Arc<SP1VerifyingKey>,
+    /// In quorum mode, the secondary range program that also proves every span.
+    pub secondary_range: Option<SecondaryRangeProgram>,
+    pub agg_pk: Arc<SP1ProvingKey>,
+    pub agg_vk: Arc<SP1VerifyingKey>,
+    pub agg_vkey_hash: B256,
+    pub range_vkey_commitment: B256,
+    pub range_proof_strategy: FulfillmentStrategy,
+    pub agg_proof_strategy: FulfillmentStrategy,
+    pub agg_proof_mode: SP1ProofMode,
+    /// The size in bytes above which span proof stdins are rejected instead of being submitted to
+    /// the prover network.
+    pub max_stdin_size: Option<usize>,
+}
+
+impl ProvingContext {
+    /// Set up the programs' keys. This takes a while, so callers on the async runtime run it on a
+    /// blocking thread.
+    pub fn setup(
+        prover: &NetworkProver,
+        elfs: ProgramElfs,
+        config: &ProvingConfig,
+        generation: u64,
+    ) -> Result<Self> {
+        let (range_pk, range_vk) = prover.setup(&elfs.range);
+        let (agg_pk, agg_vk) = prover.setup(&elfs.aggregation);
+
+        // In quorum mode, every span is also proven by the secondary range program, and the
+        // aggregation proof commits to both range vkeys.
+        let secondary_range = match &elfs.secondary_range {
+            Some(elf) => {
+                let (pk, vk) = prover.setup(elf);
+                if vk.hash_u32() == range_vk.hash_u32() {
+                    bail!("The secondary range program must differ from the range program");
+                }
+                info!("Quorum mode: secondary range vkey {}", vk.bytes32());
+                Some(SecondaryRangeProgram {
+                    pk: Arc::new(pk),
+                    vk: Arc::new(vk),
+                })
+            }
+            None => None,
+        };
+        let VkeyCommitment {
+            aggregation_vkey: agg_vkey_hash,
+            range_vkey_commitment,
+        } = vkey_commitment(
+            &agg_vk,
+            &range_vk,
+            secondary_range
+                .as_ref()
+                .map(|secondary| secondary.vk.as_ref()),
+        );
+
+        Ok(Self {
+            generation,
+            range_elf: elfs.range,
+            range_pk: Arc::new(range_pk),
+            range_vk: Arc::new(range_vk),
+            secondary_range,
+            agg_pk: Arc::new(agg_pk),
+            agg_vk: Arc::new(agg_vk),
+            agg_vkey_hash,
+            range_vkey_commitment,
+            range_proof_strategy: config.range_proof_strategy,
+            agg_proof_strategy: config.agg_proof_strategy,
+            agg_proof_mode: config.agg_proof_mode,
+            max_stdin_size: config.max_stdin_size,
+        })
+    }
+
+    /// The vkey hashes of the range programs, as recorded in proof artifacts.
+    pub fn range_vkeys(&self) -> Result<SpanProofVkeys> {
+        Ok(SpanProofVkeys {
+            range_vkey: B256::from_str(&self.range_vk.bytes32())?,
+            secondary_range_vkey: self
+                .secondary_range
+                .as_ref()
+                .map(|secondary| B256::from_str(&secondary.vk.bytes32()))
+                .transpose()?,
+        })
+    }
+
+    /// The context as reported by `POST /admin/reload`.
+    pub fn summary(&self) -> ReloadResponse {
+        ReloadResponse {
+            generation: self.generation,
+            aggregation_vkey: self.agg_vkey_hash,
+            range_vkey_commitment: self.range_vkey_commitment,
+        }
+    }
+}
+
+/// The context new requests are served with, replaced by reloads.
+#[derive(Clone)]
+pub struct CurrentProvingContext(Arc<RwLock<Arc<ProvingContext>>>);
+
+impl CurrentProvingContext {
+    pub fn new(context: ProvingContext) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(context))))
+    }
+
+    /// The current context. Requests hold it until they complete, so a reload doesn't change the
+    /// programs in-flight work uses.
+    pub fn get(&self) -> Arc<ProvingContext> {
+        self.0.read().unwrap().clone()
+    }
+
+    /// Serve new requests with `context`, and log once the requests holding the previous context
+    /// have completed.
+    pub fn replace(&self, context: ProvingContext) {
+        let previous = std::mem::replace(&mut *self.0.write().unwrap(), Arc::new(context));
+        let generation = previous.generation;
+        let previous = Arc::downgrade(&previous);
+        tokio::spawn(log_when_drained(previous, generation));
+    }
+}
+
+/// Log once no request holds the context anymore.
+async fn log_when_drained(context: Weak<ProvingContext>, generation: u64) {
+    while context.strong_count() > 0 {
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+    info!("The requests against proving context {generation} have drained");
+}
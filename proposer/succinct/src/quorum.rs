@@ -0,0 +1,142 @@
+//! Quorum proving: every span is proven by both the range program and a secondary range program
+//! built independently from it, and the aggregation program verifies both proofs of each span. A
+//! soundness bug in one of the range programs is then not enough to prove an invalid state
+//! transition, at twice the cost of proving spans.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use op_succinct_client_utils::{boot::BootInfoStruct, public_values::decode_boot_info};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{
+    network::proto::network::FulfillmentStatus, SP1ProofWithPublicValues, SP1ProvingKey,
+    SP1VerifyingKey,
+};
+
+use crate::{validation::BrokenSubproofChain, ProofStatus};
+
+/// The secondary range program, which proves every span alongside the range program.
+#[derive(Clone)]
+pub struct SecondaryRangeProgram {
+    pub pk: Arc<SP1ProvingKey>,
+    pub vk: Arc<SP1VerifyingKey>,
+}
+
+/// The proofs of a span in quorum mode, returned bincode-encoded as the span's proof.
+#[derive(Serialize, Deserialize)]
+pub struct QuorumSpanProof {
+    pub primary: SP1ProofWithPublicValues,
+    pub secondary: SP1ProofWithPublicValues,
+}
+
+impl QuorumSpanProof {
+    /// Read the boot info both proofs commit to. `index` is the position of the subproof in its
+    /// aggregation request. The aggregation program verifies both proofs against the same public
+    /// values, so they must commit to the boot info with the same public values version, and to
+    /// the same intermediate outputs.
+    pub fn boot_info(&self, index: usize) -> Result<BootInfoStruct, BrokenSubproofChain> {
+        let public_values = self.primary.public_values.as_slice();
+        if public_values != self.secondary.public_values.as_slice() {
+            return Err(BrokenSubproofChain::QuorumMismatch { index });
+        }
+        decode_boot_info(public_values)
+            .map(|(_, boot_info)| boot_info)
+            .map_err(|_| BrokenSubproofChain::QuorumMismatch { index })
+    }
+}
+
+/// Combine the statuses of the primary and secondary proofs of a span. The span is fulfilled once
+/// both proofs are, with a [`QuorumSpanProof`] as its proof, and unfulfillable if either proof is.
+/// Otherwise it has the status of the proof that is not yet fulfilled.
+pub fn merge_status(primary: ProofStatus, secondary: ProofStatus) -> Result<ProofStatus> {
+    let is = |status: &ProofStatus, fulfillment_status: FulfillmentStatus| {
+        status.fulfillment_status == fulfillment_status as i32
+    };
+
+    if is(&secondary, FulfillmentStatus::Unfulfillable) {
+        return Ok(ProofStatus {
+            proof: vec![],
+            ..secondary
+        });
+    }
+    if !is(&primary, FulfillmentStatus::Fulfilled) {
+        return Ok(ProofStatus {
+            proof: vec![],
+            ..primary
+        });
+    }
+    if !is(&secondary, FulfillmentStatus::Fulfilled) {
+        return Ok(ProofStatus {
+            proof: vec![],
+            ..secondary
+        });
+    }
+
+    let proof = QuorumSpanProof {
+        primary: bincode::deserialize(&primary.proof)?,
+        secondary: bincode::deserialize(&secondary.proof)?,
+    };
+    Ok(ProofStatus {
+        proof: bincode::serialize(&proof)?,
+        ..primary
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProofMetadata;
+
+    fn status(fulfillment_status: FulfillmentStatus, requested_at: u64) -> ProofStatus {
+        ProofStatus {
+            fulfillment_status: fulfillment_status.into(),
+            execution_status: 0,
+            proof: vec![],
+            metadata: Some(ProofMetadata {
+                requested_at,
+                ..Default::default()
+            }),
+            proof_url: None,
+            error_code: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_status_waits_for_both_proofs() {
+        let merged = |primary, secondary| {
+            let merged = merge_status(status(primary, 1), status(secondary, 2)).unwrap();
+            let requested_at = merged.metadata.unwrap().requested_at;
+            (merged.fulfillment_status, requested_at)
+        };
+        let fulfilled = FulfillmentStatus::Fulfilled as i32;
+        let unfulfillable = FulfillmentStatus::Unfulfillable as i32;
+        let assigned = FulfillmentStatus::Assigned as i32;
+
+        assert_eq!(
+            merged(FulfillmentStatus::Fulfilled, FulfillmentStatus::Assigned),
+            (assigned, 2)
+        );
+        assert_eq!(
+            merged(FulfillmentStatus::Assigned, FulfillmentStatus::Fulfilled),
+            (assigned, 1)
+        );
+        assert_eq!(
+            merged(
+                FulfillmentStatus::Fulfilled,
+                FulfillmentStatus::Unfulfillable
+            ),
+            (unfulfillable, 2)
+        );
+        assert_eq!(
+            merged(
+                FulfillmentStatus::Unfulfillable,
+                FulfillmentStatus::Assigned
+            ),
+            (unfulfillable, 1)
+        );
+        assert_ne!(
+            merged(FulfillmentStatus::Requested, FulfillmentStatus::Requested).0,
+            fulfilled
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! The statuses of the readiness checks, cached so that frequent probes don't each look up the
+//! range program on the SP1 network and call the RPCs.
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Mutex;
+
+use crate::ComponentStatus;
+
+/// How long the statuses of a readiness check are served before the checks are run again.
+pub const READINESS_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// The statuses of the last readiness check, and when it ran.
+#[derive(Debug)]
+pub struct ReadinessCache {
+    ttl: Duration,
+    last: Mutex<Option<(Instant, BTreeMap<String, ComponentStatus>)>>,
+}
+
+impl ReadinessCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// The statuses of the last check if it ran less than the TTL ago, otherwise the statuses of a
+    /// new check, which are cached. Probes that arrive while a check runs wait for its statuses
+    /// rather than running another check.
+    pub async fn get_or_check<F, Fut>(&self, check: F) -> BTreeMap<String, ComponentStatus>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = BTreeMap<String, ComponentStatus>>,
+    {
+        let mut last = self.last.lock().await;
+        if let Some((checked_at, statuses)) = last.as_ref() {
+            if checked_at.elapsed() < self.ttl {
+                return statuses.clone();
+            }
+        }
+
+        let statuses = check().await;
+        *last = Some((Instant::now(), statuses.clone()));
+        statuses
+    }
+}
+
+impl Default for ReadinessCache {
+    fn default() -> Self {
+        Self::new(READINESS_CACHE_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn statuses(healthy: bool) -> BTreeMap<String, ComponentStatus> {
+        BTreeMap::from([(
+            "sp1_network".to_string(),
+            ComponentStatus {
+                healthy,
+                error: None,
+            },
+        )])
+    }
+
+    #[tokio::test]
+    async fn test_serves_cached_statuses_within_ttl() {
+        let cache = ReadinessCache::new(Duration::from_secs(60));
+        let checks = &AtomicUsize::new(0);
+        let check = move || async move {
+            checks.fetch_add(1, Ordering::SeqCst);
+            statuses(true)
+        };
+
+        assert!(cache.get_or_check(check).await["sp1_network"].healthy);
+        assert!(cache.get_or_check(check).await["sp1_network"].healthy);
+        assert_eq!(checks.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_checks_again_after_ttl() {
+        let cache = ReadinessCache::new(Duration::ZERO);
+
+        assert!(cache.get_or_check(|| async { statuses(true) }).await["sp1_network"].healthy);
+        assert!(!cache.get_or_check(|| async { statuses(false) }).await["sp1_network"].healthy);
+    }
+}
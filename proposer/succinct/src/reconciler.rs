@@ -0,0 +1,862 @@
+use alloy_primitives::{keccak256, Address, B256};
+use log::{error, info, warn};
+use op_succinct_host_utils::config::env_var;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{
+    network::proto::network::{ExecutionStatus, FulfillmentStatus},
+    NetworkProver, SP1Proof, SP1ProofWithPublicValues,
+};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::OnceCell;
+use tracing::Span;
+
+use crate::{
+    artifact::ProofArtifacts, quorum::merge_status, webhook::Webhooks, ClaimMode, ErrorCode,
+    ProofKind, ProofMetadata, ProofStatus, ProvenBlocks,
+};
+
+/// How often the reconciler checks for proofs that are due to be polled.
+const RECONCILER_TICK: Duration = Duration::from_secs(1);
+
+/// How often the reconciler evicts the proofs past their retention.
+const EVICTION_INTERVAL_SECS: u64 = 60;
+
+/// Configures how the reconciler polls the prover network.
+#[derive(Debug, Clone)]
+pub struct ReconcilerConfig {
+    /// The delay before the first poll of a proof, and the delay after a status change.
+    pub initial_backoff_secs: u64,
+    /// The maximum delay between two polls of the same proof.
+    pub max_backoff_secs: u64,
+    /// How long a proof may take before it is marked as unfulfillable so it gets re-requested.
+    pub proof_deadline_secs: u64,
+    /// How long a fulfilled or unfulfillable proof is kept in the tracker.
+    pub proof_retention_secs: u64,
+}
+
+impl ReconcilerConfig {
+    /// Read the reconciler config from the environment.
+    ///
+    /// - `PROOF_STATUS_INITIAL_BACKOFF_SECS`: Default: 5.
+    /// - `PROOF_STATUS_MAX_BACKOFF_SECS`: Default: 300.
+    /// - `PROOF_DEADLINE_SECS`: Default: 14400 (4 hours).
+    /// - `PROOF_RETENTION_SECS`: Default: 86400 (24 hours).
+    pub fn from_env() -> anyhow::Result<Self> {
+        let var = |name: &str, default: u64| -> anyhow::Result<u64> {
+            Ok(env_var(name)?.unwrap_or(default))
+        };
+
+        Ok(Self {
+            initial_backoff_secs: var("PROOF_STATUS_INITIAL_BACKOFF_SECS", 5)?,
+            max_backoff_secs: var("PROOF_STATUS_MAX_BACKOFF_SECS", 300)?,
+            proof_deadline_secs: var("PROOF_DEADLINE_SECS", 4 * 60 * 60)?,
+            proof_retention_secs: var("PROOF_RETENTION_SECS", 24 * 60 * 60)?,
+        })
+    }
+}
+
+/// A proof requested from the prover network and its last known status.
+#[derive(Clone)]
+struct TrackedProof {
+    status: ProofStatus,
+    /// Unix timestamp after which the proof is considered timed out.
+    deadline: u64,
+    /// Unix timestamp at which the proof is next polled.
+    next_poll_at: u64,
+    backoff_secs: u64,
+    /// The span of the request that requested the proof, so the reconciler's logs for the proof
+    /// carry the request's ID.
+    span: Span,
+    /// The L2 blocks the proof covers, if known.
+    blocks: Option<ProvenBlocks>,
+    /// The keccak256 hash of the proof's public values, once fulfilled.
+    public_values_hash: Option<B256>,
+    /// Unix timestamp at which the proof was fulfilled or became unfulfillable.
+    settled_at: Option<u64>,
+}
+
+impl TrackedProof {
+    fn is_terminal(&self) -> bool {
+        self.status.fulfillment_status == FulfillmentStatus::Fulfilled as i32
+            || self.status.fulfillment_status == FulfillmentStatus::Unfulfillable as i32
+    }
+}
+
+/// Identifies the span proof a request asks for. The L1 head is chosen by the server from the
+/// range, so two requests with the same key ask for the same proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpanRequestKey {
+    pub start: u64,
+    pub end: u64,
+    pub range_vkey_commitment: B256,
+    pub rollup_config_hash: B256,
+    /// The output root the request claims for the end block, if not the one the L2 RPC reports.
+    pub claimed_output_root: Option<B256>,
+    pub claim_mode: ClaimMode,
+}
+
+/// Identifies the aggregation proof a request asks for. The proposer persists the subproofs and L1
+/// head of an aggregation before requesting it, and sends the same request again if it restarts
+/// before recording the proof ID, which then gets the proof requested the first time. The proof
+/// IDs are persisted in an [`AggRequestStore`](crate::agg_requests::AggRequestStore), so this
+/// holds across server restarts too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AggRequestKey {
+    /// The keccak256 hash of the request body.
+    pub request_hash: B256,
+    pub aggregation_vkey: B256,
+    pub range_vkey_commitment: B256,
+}
+
+/// The local record of every proof requested by the server. The reconciler keeps it up to date
+/// with the prover network, so status requests are answered without a network round trip.
+#[derive(Default)]
+pub struct ProofTracker {
+    proofs: Mutex<HashMap<B256, TrackedProof>>,
+    span_requests: Mutex<HashMap<SpanRequestKey, Arc<OnceCell<B256>>>>,
+    agg_requests: Mutex<HashMap<AggRequestKey, Arc<OnceCell<B256>>>>,
+    /// In quorum mode, the secondary proof of each span proof.
+    secondaries: Mutex<HashMap<B256, B256>>,
+    /// The SHA-256 hash of the witness each span proof was proven from.
+    witness_hashes: Mutex<HashMap<B256, B256>>,
+}
+
+impl ProofTracker {
+    /// Start tracking a newly requested proof. Must be called within the span of the request.
+    pub fn track(&self, proof_id: B256, blocks: Option<ProvenBlocks>, config: &ReconcilerConfig) {
+        let now = unix_now();
+        self.proofs.lock().unwrap().insert(
+            proof_id,
+            TrackedProof {
+                status: ProofStatus {
+                    fulfillment_status: FulfillmentStatus::Requested.into(),
+                    execution_status: ExecutionStatus::Unexecuted.into(),
+                    proof: vec![],
+                    metadata: Some(ProofMetadata {
+                        requested_at: now,
+                        ..Default::default()
+                    }),
+                    proof_url: None,
+                    error_code: None,
+                },
+                deadline: now + config.proof_deadline_secs,
+                next_poll_at: now + config.initial_backoff_secs,
+                backoff_secs: config.initial_backoff_secs,
+                span: Span::current(),
+                blocks,
+                public_values_hash: None,
+                settled_at: None,
+            },
+        );
+    }
+
+    /// Record the secondary proof of a span proof in quorum mode. The secondary proof should be
+    /// tracked without its blocks, so only the span proof is listed as covering them.
+    pub fn link_secondary(&self, proof_id: B256, secondary_proof_id: B256) {
+        self.secondaries
+            .lock()
+            .unwrap()
+            .insert(proof_id, secondary_proof_id);
+    }
+
+    /// Record the SHA-256 hash of the witness a span proof was proven from, see
+    /// [`crate::custody`].
+    pub fn record_witness_sha256(&self, proof_id: B256, witness_sha256: B256) {
+        self.witness_hashes
+            .lock()
+            .unwrap()
+            .insert(proof_id, witness_sha256);
+    }
+
+    /// The SHA-256 hash of the witness a span proof was proven from, if it was recorded.
+    pub fn witness_sha256(&self, proof_id: &B256) -> Option<B256> {
+        self.witness_hashes.lock().unwrap().get(proof_id).copied()
+    }
+
+    /// Get the last known status of a proof, if it is tracked. The status of a span proof with a
+    /// secondary proof combines both, see [`merge_status`].
+    pub fn status(&self, proof_id: &B256) -> Option<ProofStatus> {
+        let secondary_proof_id = self.secondaries.lock().unwrap().get(proof_id).copied();
+        let proofs = self.proofs.lock().unwrap();
+        let status = proofs.get(proof_id)?.status.clone();
+        let Some(secondary) = secondary_proof_id.and_then(|id| proofs.get(&id)) else {
+            return Some(status);
+        };
+        match merge_status(status, secondary.status.clone()) {
+            Ok(status) => Some(status),
+            // Mark the span proof as unfulfillable so it is requested again, rather than falling
+            // back to the network's status of the primary proof alone.
+            Err(e) => {
+                error!("Failed to combine the quorum proofs of {}: {}", proof_id, e);
+                Some(ProofStatus {
+                    fulfillment_status: FulfillmentStatus::Unfulfillable.into(),
+                    proof: vec![],
+                    error_code: Some(ErrorCode::QuorumMismatch),
+                    ..status
+                })
+            }
+        }
+    }
+
+    /// The proof that a proof is part of, which is either the proof itself or the span proof it is
+    /// the secondary proof of.
+    pub fn primary_proof(&self, proof_id: &B256) -> B256 {
+        self.secondaries
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(_, secondary_proof_id)| *secondary_proof_id == proof_id)
+            .map_or(*proof_id, |(span_proof_id, _)| *span_proof_id)
+    }
+
+    /// The keccak256 hash of a fulfilled proof's public values.
+    pub fn public_values_hash(&self, proof_id: &B256) -> Option<B256> {
+        self.proofs
+            .lock()
+            .unwrap()
+            .get(proof_id)?
+            .public_values_hash
+    }
+
+    /// The span proof that a proof is part of (see [`Self::primary_proof`]), and its status, if
+    /// the span proof is fulfilled.
+    pub fn fulfilled_span_proof(&self, proof_id: &B256) -> Option<(B256, ProofStatus)> {
+        let span_proof_id = self.primary_proof(proof_id);
+        let blocks = self.proofs.lock().unwrap().get(&span_proof_id)?.blocks?;
+        if blocks.kind != ProofKind::Span {
+            return None;
+        }
+        let status = self.status(&span_proof_id)?;
+        (status.fulfillment_status == FulfillmentStatus::Fulfilled as i32)
+            .then_some((span_proof_id, status))
+    }
+
+    /// The start of a tracked span proof ending at `end`, which is where an aggregation proof whose
+    /// first subproof ends at `end` starts.
+    pub fn span_start(&self, end: u64) -> Option<u64> {
+        self.proofs
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(|proof| proof.blocks)
+            .find(|blocks| blocks.kind == ProofKind::Span && blocks.end == end)
+            .map(|blocks| blocks.start)
+    }
+
+    /// The fulfilled proofs covering an L2 block, aggregation proofs first and otherwise most
+    /// recently requested first.
+    pub fn covering(&self, l2_block: u64) -> Vec<(B256, ProvenBlocks, ProofStatus)> {
+        let tracked: Vec<_> = self
+            .proofs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|(proof_id, proof)| {
+                let blocks = proof.blocks?;
+                blocks.covers(l2_block).then_some((*proof_id, blocks))
+            })
+            .collect();
+        let mut covering: Vec<_> = tracked
+            .into_iter()
+            .filter_map(|(proof_id, blocks)| {
+                let status = self.status(&proof_id)?;
+                (status.fulfillment_status == FulfillmentStatus::Fulfilled as i32)
+                    .then_some((proof_id, blocks, status))
+            })
+            .collect();
+        covering.sort_by_key(|(_, blocks, status)| {
+            let requested_at = status.metadata.as_ref().map(|m| m.requested_at);
+            (blocks.kind == ProofKind::Span, Reverse(requested_at))
+        });
+        covering
+    }
+
+    /// Get the proof ID of a span request, to be initialized by whichever request for the span
+    /// arrives first. Identical concurrent requests share it, so only one of them generates the
+    /// witness and requests the proof. A proof that ended up unfulfillable is not reused.
+    pub fn span_request(&self, key: SpanRequestKey) -> Arc<OnceCell<B256>> {
+        self.request(&self.span_requests, key)
+    }
+
+    /// Get the proof ID of an aggregation request, shared by identical requests like
+    /// [`Self::span_request`].
+    pub fn agg_request(&self, key: AggRequestKey) -> Arc<OnceCell<B256>> {
+        self.request(&self.agg_requests, key)
+    }
+
+    fn request<K: Eq + Hash>(
+        &self,
+        requests: &Mutex<HashMap<K, Arc<OnceCell<B256>>>>,
+        key: K,
+    ) -> Arc<OnceCell<B256>> {
+        let mut requests = requests.lock().unwrap();
+        if let Some(proof_id) = requests.get(&key) {
+            let reusable = match proof_id.get() {
+                Some(proof_id) => self.status(proof_id).is_some_and(|status| {
+                    status.fulfillment_status != FulfillmentStatus::Unfulfillable as i32
+                }),
+                // Either in flight, or the request that initialized it failed and the next one
+                // retries.
+                None => true,
+            };
+            if reusable {
+                return proof_id.clone();
+            }
+        }
+
+        let proof_id = Arc::new(OnceCell::new());
+        requests.insert(key, proof_id.clone());
+        proof_id
+    }
+
+    /// Stop tracking the proofs that settled more than `retention_secs` ago, along with the
+    /// requests for them and their quorum and witness records. Their status is then read from the
+    /// prover network again, and a new request for the same span or aggregation requests a new
+    /// proof. Requests whose initialization failed and that no request is waiting on are dropped
+    /// too.
+    fn evict(&self, now: u64, retention_secs: u64) -> usize {
+        // A secondary proof is evicted with its span proof, whose status it is part of.
+        let mut secondaries = self.secondaries.lock().unwrap();
+        let mut proofs = self.proofs.lock().unwrap();
+        let mut evicted: Vec<B256> = proofs
+            .iter()
+            .filter(|(proof_id, proof)| {
+                !secondaries.values().any(|secondary| secondary == *proof_id)
+                    && proof
+                        .settled_at
+                        .is_some_and(|settled_at| now.saturating_sub(settled_at) >= retention_secs)
+            })
+            .map(|(proof_id, _)| *proof_id)
+            .collect();
+        let count = evicted.len();
+        let evicted_secondaries: Vec<B256> = evicted
+            .iter()
+            .filter_map(|proof_id| secondaries.remove(proof_id))
+            .collect();
+        evicted.extend(evicted_secondaries);
+        for proof_id in &evicted {
+            proofs.remove(proof_id);
+        }
+        drop(proofs);
+        drop(secondaries);
+
+        self.witness_hashes
+            .lock()
+            .unwrap()
+            .retain(|proof_id, _| !evicted.contains(proof_id));
+        retain_requests(&self.span_requests, &evicted);
+        retain_requests(&self.agg_requests, &evicted);
+        count
+    }
+
+    /// The proofs that are not yet fulfilled or unfulfillable and are due to be polled.
+    fn due(&self, now: u64) -> Vec<B256> {
+        self.proofs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, proof)| !proof.is_terminal() && proof.next_poll_at <= now)
+            .map(|(proof_id, _)| *proof_id)
+            .collect()
+    }
+}
+
+/// Drop the requests for evicted proofs, and the requests that failed before getting a proof ID and
+/// that no request is waiting on.
+fn retain_requests<K>(requests: &Mutex<HashMap<K, Arc<OnceCell<B256>>>>, evicted: &[B256]) {
+    requests
+        .lock()
+        .unwrap()
+        .retain(|_, proof_id| match proof_id.get() {
+            Some(proof_id) => !evicted.contains(proof_id),
+            None => Arc::strong_count(proof_id) > 1,
+        });
+}
+
+/// Poll the prover network for every tracked proof that is due, backing off exponentially while a
+/// proof's status is unchanged and marking proofs past their deadline as unfulfillable. If
+/// `artifacts` is set, the artifact of every fulfilled span proof is written to it. The webhooks of
+/// proofs that settle are sent. Settled proofs are evicted once past their retention.
+pub async fn run_reconciler(
+    tracker: Arc<ProofTracker>,
+    network_prover: Arc<NetworkProver>,
+    config: ReconcilerConfig,
+    artifacts: Option<Arc<ProofArtifacts>>,
+    webhooks: Arc<Webhooks>,
+) {
+    let mut interval = tokio::time::interval(RECONCILER_TICK);
+    let mut evicted_at = unix_now();
+    loop {
+        interval.tick().await;
+
+        if unix_now() >= evicted_at + EVICTION_INTERVAL_SECS {
+            evicted_at = unix_now();
+            let evicted = tracker.evict(evicted_at, config.proof_retention_secs);
+            if evicted > 0 {
+                info!("Evicted {} settled proofs from the tracker", evicted);
+            }
+        }
+
+        for proof_id in tracker.due(unix_now()) {
+            let result = network_prover.get_proof_status(proof_id).await;
+            let now = unix_now();
+
+            let mut proofs = tracker.proofs.lock().unwrap();
+            let Some(proof) = proofs.get_mut(&proof_id) else {
+                continue;
+            };
+            let span = proof.span.clone();
+            let _enter = span.enter();
+
+            let changed = match result {
+                Ok((status, maybe_proof)) => {
+                    // The network's deadline applies in addition to the local one.
+                    proof.deadline = proof.deadline.min(status.deadline);
+
+                    let previous = (
+                        proof.status.fulfillment_status,
+                        proof.status.execution_status,
+                    );
+                    proof.status.fulfillment_status = status.fulfillment_status;
+                    proof.status.execution_status = status.execution_status;
+                    if status.fulfillment_status == FulfillmentStatus::Unfulfillable as i32 {
+                        proof.status.error_code = Some(ErrorCode::ProverUnfulfillable);
+                    }
+                    if let Some(fulfilled_proof) = maybe_proof {
+                        proof.public_values_hash =
+                            Some(keccak256(fulfilled_proof.public_values.as_slice()));
+                        match proof_bytes(&fulfilled_proof) {
+                            Ok(bytes) => proof.status.proof = bytes,
+                            // Submitting the proof would fail on-chain, so treat it as
+                            // unfulfillable for the proposer to request it again.
+                            Err(e) => {
+                                error!("Proof {} can't be used: {}", proof_id, e);
+                                proof.status.fulfillment_status =
+                                    FulfillmentStatus::Unfulfillable.into();
+                                proof.status.error_code = Some(ErrorCode::Internal);
+                            }
+                        }
+                    }
+                    previous
+                        != (
+                            proof.status.fulfillment_status,
+                            proof.status.execution_status,
+                        )
+                }
+                Err(e) => {
+                    warn!("Failed to get status of proof {}: {}", proof_id, e);
+                    false
+                }
+            };
+
+            if proof.is_terminal() {
+                proof.settled_at = Some(now);
+                info!(
+                    "Proof {} reached terminal status {:?}",
+                    proof_id, proof.status.fulfillment_status
+                );
+                drop(_enter);
+                drop(proofs);
+                record_metadata(&tracker, &network_prover, proof_id, span.clone()).await;
+                if let Some(artifacts) = &artifacts {
+                    write_artifact(&tracker, artifacts, proof_id, span);
+                }
+                webhooks.proof_settled(&tracker, tracker.primary_proof(&proof_id));
+                continue;
+            }
+
+            if now >= proof.deadline {
+                error!(
+                    "Proof {} passed its deadline. Marking as unfulfillable so it is re-requested.",
+                    proof_id
+                );
+                proof.status.fulfillment_status = FulfillmentStatus::Unfulfillable.into();
+                proof.status.error_code = Some(ErrorCode::ProverTimeout);
+                proof.settled_at = Some(now);
+                drop(_enter);
+                drop(proofs);
+                webhooks.proof_settled(&tracker, tracker.primary_proof(&proof_id));
+                continue;
+            }
+
+            proof.backoff_secs = if changed {
+                config.initial_backoff_secs
+            } else {
+                next_backoff(proof.backoff_secs, config.max_backoff_secs)
+            };
+            proof.next_poll_at = now + proof.backoff_secs;
+        }
+    }
+}
+
+/// Record the prover network's metadata for a proof that reached a terminal status. Failing to
+/// fetch it is logged, since the metadata is only used for cost audits.
+async fn record_metadata(
+    tracker: &ProofTracker,
+    network_prover: &NetworkProver,
+    proof_id: B256,
+    span: Span,
+) {
+    let result = network_prover.get_proof_request(proof_id).await;
+    let _enter = span.enter();
+
+    let request = match result {
+        Ok(Some(request)) => request,
+        Ok(None) => {
+            warn!("Proof request {} not found on the prover network", proof_id);
+            return;
+        }
+        Err(e) => {
+            warn!("Failed to get details of proof request {}: {}", proof_id, e);
+            return;
+        }
+    };
+
+    let mut proofs = tracker.proofs.lock().unwrap();
+    let Some(proof) = proofs.get_mut(&proof_id) else {
+        return;
+    };
+    let metadata = proof.status.metadata.get_or_insert_with(Default::default);
+    metadata.fulfilled_at = request.fulfilled_at;
+    metadata.cycles = request.cycles;
+    metadata.gas_used = request.gas_used;
+    metadata.fulfiller = request
+        .fulfiller
+        .filter(|fulfiller| fulfiller.len() == 20)
+        .map(|fulfiller| Address::from_slice(&fulfiller));
+    info!(
+        "Proof {} used {:?} cycles and {:?} gas, fulfilled by {:?}",
+        proof_id, metadata.cycles, metadata.gas_used, metadata.fulfiller
+    );
+}
+
+/// Write the artifact of the span proof that a proof is part of, once the span proof is fulfilled.
+/// Failing to write it is logged, since the artifact is only used for audits.
+fn write_artifact(tracker: &ProofTracker, artifacts: &ProofArtifacts, proof_id: B256, span: Span) {
+    let Some((span_proof_id, status)) = tracker.fulfilled_span_proof(&proof_id) else {
+        return;
+    };
+    let _enter = span.enter();
+    match artifacts.write(span_proof_id, &status.proof) {
+        Ok(()) => info!("Wrote the artifact of span proof {}", span_proof_id),
+        Err(e) => warn!(
+            "Failed to write the artifact of span proof {}: {}",
+            span_proof_id, e
+        ),
+    }
+}
+
+/// Double the backoff, capped at `max_backoff_secs`.
+fn next_backoff(backoff_secs: u64, max_backoff_secs: u64) -> u64 {
+    backoff_secs.saturating_mul(2).min(max_backoff_secs).max(1)
+}
+
+/// A fulfilled proof of a kind the server never requests, so it has no bytes the proposer can use.
+#[derive(Debug)]
+pub struct UnexpectedProofVariant {
+    pub variant: &'static str,
+}
+
+impl fmt::Display for UnexpectedProofVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unexpected {} proof: expected a compressed, Groth16 or Plonk proof",
+            self.variant
+        )
+    }
+}
+
+impl std::error::Error for UnexpectedProofVariant {}
+
+/// The bytes of a fulfilled proof in the format expected by the proposer.
+pub fn proof_bytes(proof: &SP1ProofWithPublicValues) -> Result<Vec<u8>, UnexpectedProofVariant> {
+    match proof.proof {
+        // If it's a compressed proof, we need to serialize the entire struct with bincode.
+        // Note: We're re-serializing the entire struct with bincode here, but this is fine
+        // because we're on localhost and the size of the struct is small.
+        SP1Proof::Compressed(_) => Ok(bincode::serialize(proof).unwrap()),
+        // If it's a Groth16 or Plonk proof, we need to get the proof bytes that we put on-chain:
+        // the first 4 bytes of the SP1 verifier's vkey hash, which the SP1VerifierGateway routes
+        // on, followed by the encoded proof.
+        SP1Proof::Groth16(_) | SP1Proof::Plonk(_) => Ok(proof.bytes()),
+        SP1Proof::Core(_) => Err(UnexpectedProofVariant { variant: "core" }),
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ReconcilerConfig {
+        ReconcilerConfig {
+            initial_backoff_secs: 5,
+            max_backoff_secs: 60,
+            proof_deadline_secs: 3600,
+            proof_retention_secs: 600,
+        }
+    }
+
+    #[test]
+    fn test_next_backoff_doubles_up_to_max() {
+        assert_eq!(next_backoff(5, 60), 10);
+        assert_eq!(next_backoff(40, 60), 60);
+        assert_eq!(next_backoff(60, 60), 60);
+        assert_eq!(next_backoff(0, 60), 1);
+    }
+
+    #[test]
+    fn test_span_request_is_reused_until_unfulfillable() {
+        let tracker = ProofTracker::default();
+        let key = SpanRequestKey {
+            start: 1,
+            end: 2,
+            range_vkey_commitment: B256::ZERO,
+            rollup_config_hash: B256::ZERO,
+            claimed_output_root: None,
+            claim_mode: ClaimMode::Check,
+        };
+        let proof_id = B256::repeat_byte(1);
+
+        let span_request = tracker.span_request(key);
+        span_request.set(proof_id).unwrap();
+        tracker.track(proof_id, None, &config());
+        assert!(Arc::ptr_eq(&span_request, &tracker.span_request(key)));
+
+        tracker
+            .proofs
+            .lock()
+            .unwrap()
+            .get_mut(&proof_id)
+            .unwrap()
+            .status
+            .fulfillment_status = FulfillmentStatus::Unfulfillable.into();
+        assert!(tracker.span_request(key).get().is_none());
+    }
+
+    #[test]
+    fn test_agg_request_is_shared_by_identical_requests() {
+        let tracker = ProofTracker::default();
+        let key = |request_hash| AggRequestKey {
+            request_hash,
+            aggregation_vkey: B256::ZERO,
+            range_vkey_commitment: B256::ZERO,
+        };
+        let proof_id = B256::repeat_byte(1);
+
+        // A resumed aggregation session gets the proof of its first request.
+        tracker
+            .agg_request(key(B256::repeat_byte(1)))
+            .set(proof_id)
+            .unwrap();
+        tracker.track(proof_id, None, &config());
+        assert_eq!(
+            tracker.agg_request(key(B256::repeat_byte(1))).get(),
+            Some(&proof_id)
+        );
+        assert!(tracker
+            .agg_request(key(B256::repeat_byte(2)))
+            .get()
+            .is_none());
+    }
+
+    #[test]
+    fn test_span_request_with_secondary_proof() {
+        let tracker = ProofTracker::default();
+        let key = SpanRequestKey {
+            start: 1,
+            end: 2,
+            range_vkey_commitment: B256::ZERO,
+            rollup_config_hash: B256::ZERO,
+            claimed_output_root: None,
+            claim_mode: ClaimMode::Check,
+        };
+        let (proof_id, secondary_proof_id) = (B256::repeat_byte(1), B256::repeat_byte(2));
+
+        tracker.span_request(key).set(proof_id).unwrap();
+        tracker.track(proof_id, None, &config());
+        tracker.track(secondary_proof_id, None, &config());
+        tracker.link_secondary(proof_id, secondary_proof_id);
+
+        let set_status = |proof_id: &B256, fulfillment_status: FulfillmentStatus| {
+            tracker
+                .proofs
+                .lock()
+                .unwrap()
+                .get_mut(proof_id)
+                .unwrap()
+                .status
+                .fulfillment_status = fulfillment_status.into();
+        };
+        let fulfillment_status = || tracker.status(&proof_id).unwrap().fulfillment_status;
+
+        // The span proof is not fulfilled until its secondary proof is.
+        set_status(&proof_id, FulfillmentStatus::Fulfilled);
+        assert_eq!(fulfillment_status(), FulfillmentStatus::Requested as i32);
+        assert!(tracker.span_request(key).get().is_some());
+
+        // Nor is it reused once its secondary proof is unfulfillable.
+        set_status(&secondary_proof_id, FulfillmentStatus::Unfulfillable);
+        assert_eq!(
+            fulfillment_status(),
+            FulfillmentStatus::Unfulfillable as i32
+        );
+        assert!(tracker.span_request(key).get().is_none());
+    }
+
+    #[test]
+    fn test_fulfilled_span_proof() {
+        let tracker = ProofTracker::default();
+        let blocks = |kind| ProvenBlocks {
+            kind,
+            start: 10,
+            end: 20,
+        };
+        let (span_id, agg_id) = (B256::repeat_byte(1), B256::repeat_byte(2));
+        let (quorum_span_id, secondary_id) = (B256::repeat_byte(3), B256::repeat_byte(4));
+        tracker.track(span_id, Some(blocks(ProofKind::Span)), &config());
+        tracker.track(agg_id, Some(blocks(ProofKind::Agg)), &config());
+        tracker.track(quorum_span_id, Some(blocks(ProofKind::Span)), &config());
+        tracker.track(secondary_id, None, &config());
+        tracker.link_secondary(quorum_span_id, secondary_id);
+
+        assert!(tracker.fulfilled_span_proof(&span_id).is_none());
+        for proof_id in [span_id, agg_id, quorum_span_id] {
+            tracker
+                .proofs
+                .lock()
+                .unwrap()
+                .get_mut(&proof_id)
+                .unwrap()
+                .status
+                .fulfillment_status = FulfillmentStatus::Fulfilled.into();
+        }
+
+        assert_eq!(tracker.fulfilled_span_proof(&span_id).unwrap().0, span_id);
+        // Artifacts are only written for span proofs.
+        assert!(tracker.fulfilled_span_proof(&agg_id).is_none());
+        // A span proof in quorum mode is not fulfilled until its secondary proof is.
+        assert!(tracker.fulfilled_span_proof(&quorum_span_id).is_none());
+        assert!(tracker.fulfilled_span_proof(&secondary_id).is_none());
+    }
+
+    #[test]
+    fn test_tracked_proof_is_due_after_initial_backoff() {
+        let tracker = ProofTracker::default();
+        let proof_id = B256::repeat_byte(1);
+        tracker.track(proof_id, None, &config());
+
+        let now = unix_now();
+        assert!(tracker.due(now).is_empty());
+        assert_eq!(tracker.due(now + 5), vec![proof_id]);
+
+        let status = tracker.status(&proof_id).unwrap();
+        assert_eq!(
+            status.fulfillment_status,
+            FulfillmentStatus::Requested as i32
+        );
+        let metadata = status.metadata.unwrap();
+        assert!(metadata.requested_at <= now);
+        assert!(metadata.fulfilled_at.is_none());
+    }
+
+    #[test]
+    fn test_covering_proofs() {
+        let tracker = ProofTracker::default();
+        let span = ProvenBlocks {
+            kind: ProofKind::Span,
+            start: 10,
+            end: 20,
+        };
+        let agg = ProvenBlocks {
+            kind: ProofKind::Agg,
+            start: 10,
+            end: 30,
+        };
+        let (span_id, agg_id) = (B256::repeat_byte(1), B256::repeat_byte(2));
+        tracker.track(span_id, Some(span), &config());
+        tracker.track(agg_id, Some(agg), &config());
+        assert_eq!(tracker.span_start(20), Some(10));
+
+        // Only fulfilled proofs are returned.
+        assert!(tracker.covering(15).is_empty());
+        for proof in tracker.proofs.lock().unwrap().values_mut() {
+            proof.status.fulfillment_status = FulfillmentStatus::Fulfilled.into();
+        }
+
+        let ids = |l2_block| {
+            tracker
+                .covering(l2_block)
+                .into_iter()
+                .map(|(proof_id, _, _)| proof_id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(15), vec![agg_id, span_id]);
+        assert_eq!(ids(25), vec![agg_id]);
+        assert!(ids(10).is_empty());
+    }
+
+    #[test]
+    fn test_evict_settled_proofs() {
+        let tracker = ProofTracker::default();
+        let key = |start| SpanRequestKey {
+            start,
+            end: start + 1,
+            range_vkey_commitment: B256::ZERO,
+            rollup_config_hash: B256::ZERO,
+            claimed_output_root: None,
+            claim_mode: ClaimMode::Check,
+        };
+        let (settled_id, secondary_id, pending_id) = (
+            B256::repeat_byte(1),
+            B256::repeat_byte(2),
+            B256::repeat_byte(3),
+        );
+        tracker.span_request(key(1)).set(settled_id).unwrap();
+        tracker.track(settled_id, None, &config());
+        tracker.track(secondary_id, None, &config());
+        tracker.link_secondary(settled_id, secondary_id);
+        tracker.record_witness_sha256(settled_id, B256::repeat_byte(4));
+        tracker.span_request(key(2)).set(pending_id).unwrap();
+        tracker.track(pending_id, None, &config());
+        // A request that failed before getting a proof ID, with no request waiting on it.
+        tracker.span_request(key(3));
+
+        tracker
+            .proofs
+            .lock()
+            .unwrap()
+            .get_mut(&settled_id)
+            .unwrap()
+            .settled_at = Some(1000);
+
+        // Settled proofs are kept for their retention.
+        assert_eq!(tracker.evict(1599, 600), 0);
+        assert!(tracker.status(&settled_id).is_some());
+
+        assert_eq!(tracker.evict(1600, 600), 1);
+        assert!(tracker.status(&settled_id).is_none());
+        assert!(tracker.status(&secondary_id).is_none());
+        assert!(tracker.witness_sha256(&settled_id).is_none());
+        assert!(tracker.secondaries.lock().unwrap().is_empty());
+        let span_requests = tracker.span_requests.lock().unwrap();
+        assert_eq!(span_requests.len(), 1);
+        assert_eq!(span_requests[&key(2)].get(), Some(&pending_id));
+        drop(span_requests);
+        assert!(tracker.status(&pending_id).is_some());
+    }
+}
@@ -0,0 +1,178 @@
+//! Per-request overrides of the RPCs a span's witness is generated from.
+//!
+//! A span proof request may carry [`RpcOverrides`], so a single server can be pointed at alternate
+//! nodes per request, e.g. an archive node only for historical ranges. The server only reads from
+//! the endpoints listed in `RPC_OVERRIDE_ALLOWLIST`, so requests can't make it send requests to
+//! arbitrary URLs.
+
+use anyhow::{anyhow, bail, Result};
+use op_succinct_host_utils::{
+    config::{env_var, redact_url, Url},
+    fetcher::{OPSuccinctDataFetcher, RPCConfig, RunContext},
+    OPSuccinctHost,
+};
+
+use crate::RpcOverrides;
+
+/// The endpoints requests may override the server's RPCs with.
+#[derive(Debug, Clone, Default)]
+pub struct RpcOverrideAllowlist {
+    urls: Vec<Url>,
+}
+
+impl RpcOverrideAllowlist {
+    /// Read the allowlist from `RPC_OVERRIDE_ALLOWLIST`, a comma-separated list of HTTP(S) URLs.
+    /// Default: unset, which rejects requests overriding an RPC.
+    pub fn from_env() -> Result<Self> {
+        let urls = env_var::<String>("RPC_OVERRIDE_ALLOWLIST")?.unwrap_or_default();
+        Self::parse(&urls)
+    }
+
+    fn parse(urls: &str) -> Result<Self> {
+        let urls = urls
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(|url| {
+                let url: Url = url
+                    .parse()
+                    .map_err(|e| anyhow!("Invalid RPC_OVERRIDE_ALLOWLIST entry: {e}"))?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    bail!("Invalid RPC_OVERRIDE_ALLOWLIST: expected http or https URLs");
+                }
+                Ok(url)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { urls })
+    }
+
+    /// The allowlist for logging, with the URLs redacted.
+    pub fn summary(&self) -> Option<String> {
+        if self.urls.is_empty() {
+            return None;
+        }
+        Some(
+            self.urls
+                .iter()
+                .map(redact_url)
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+    }
+
+    /// Parse the RPC overrides of a request, rejecting endpoints not in the allowlist.
+    pub fn check(&self, overrides: &RpcOverrides) -> Result<CheckedRpcOverrides> {
+        Ok(CheckedRpcOverrides {
+            l1_rpc: self.check_url("l1_rpc", overrides.l1_rpc.as_deref())?,
+            l2_rpc: self.check_url("l2_rpc", overrides.l2_rpc.as_deref())?,
+            beacon_rpc: self.check_url("beacon_rpc", overrides.beacon_rpc.as_deref())?,
+        })
+    }
+
+    fn check_url(&self, name: &str, url: Option<&str>) -> Result<Option<Url>> {
+        let Some(url) = url else {
+            return Ok(None);
+        };
+        let url: Url = url.parse().map_err(|e| anyhow!("Invalid {name}: {e}"))?;
+        if !self.urls.contains(&url) {
+            bail!(
+                "{name} {} is not in the server's RPC_OVERRIDE_ALLOWLIST",
+                redact_url(&url)
+            );
+        }
+        Ok(Some(url))
+    }
+}
+
+/// The RPC overrides of a request, each in the allowlist.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckedRpcOverrides {
+    pub l1_rpc: Option<Url>,
+    pub l2_rpc: Option<Url>,
+    pub beacon_rpc: Option<Url>,
+}
+
+impl CheckedRpcOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.l1_rpc.is_none() && self.l2_rpc.is_none() && self.beacon_rpc.is_none()
+    }
+
+    /// A data fetcher reading from the overridden RPCs in place of the server's.
+    pub async fn fetcher(&self, run_context: RunContext) -> Result<OPSuccinctDataFetcher> {
+        let rpc_config = RPCConfig::from_env()?.with_overrides(
+            self.l1_rpc.as_ref(),
+            self.l2_rpc.as_ref(),
+            self.beacon_rpc.as_ref(),
+        );
+        OPSuccinctDataFetcher::new_with_rpc_config(rpc_config, run_context).await
+    }
+
+    /// Point witness generation at the overridden beacon API. It takes precedence over the blob
+    /// archive, which witness generation otherwise reads blobs through.
+    pub fn apply(&self, host: &mut OPSuccinctHost) {
+        if let Some(url) = &self.beacon_rpc {
+            host.kona_args.l1_beacon_address = Some(url.as_str().trim_end_matches('/').to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let allowlist =
+            RpcOverrideAllowlist::parse("https://archive.example.com/key, http://10.0.0.2:8545")
+                .unwrap();
+
+        let overrides = RpcOverrides {
+            l1_rpc: Some("https://archive.example.com/key".to_string()),
+            l2_rpc: None,
+            beacon_rpc: Some("http://10.0.0.2:8545/".to_string()),
+        };
+        let checked = allowlist.check(&overrides).unwrap();
+        assert_eq!(
+            checked.l1_rpc,
+            Some("https://archive.example.com/key".parse().unwrap())
+        );
+        assert_eq!(checked.l2_rpc, None);
+        assert!(checked.beacon_rpc.is_some());
+
+        assert!(allowlist
+            .check(&RpcOverrides::default())
+            .unwrap()
+            .is_empty());
+
+        let err = allowlist
+            .check(&RpcOverrides {
+                l2_rpc: Some("https://archive.example.com/other-key".to_string()),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "l2_rpc https://archive.example.com/<redacted> is not in the server's \
+             RPC_OVERRIDE_ALLOWLIST"
+        );
+    }
+
+    #[test]
+    fn test_check_without_allowlist() {
+        let allowlist = RpcOverrideAllowlist::default();
+        assert!(allowlist.check(&RpcOverrides::default()).is_ok());
+        assert!(allowlist
+            .check(&RpcOverrides {
+                l1_rpc: Some("http://localhost:8545".to_string()),
+                ..Default::default()
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_urls() {
+        assert!(RpcOverrideAllowlist::parse("ws://localhost:8546").is_err());
+        assert!(RpcOverrideAllowlist::parse("not a url").is_err());
+        assert!(RpcOverrideAllowlist::parse("").unwrap().summary().is_none());
+    }
+}
@@ -0,0 +1,434 @@
+//! Proposing outputs from Rust, for the end-to-end tests against a local chain (`tests/e2e.rs`).
+//!
+//! The production proposer is the Go `op-proposer` in `proposer/op`. These helpers drive the same
+//! contract paths from Rust, so the contracts' checkpoint, multicall and dispute game flows are
+//! tested end to end, and are only built with the `e2e` feature.
+
+use alloy_network::Ethereum;
+use alloy_primitives::{Bytes, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::BlockTransactionsKind;
+use alloy_sol_types::SolInterface;
+use anyhow::{anyhow, bail, Result};
+use op_succinct_client_utils::{
+    public_values::{encode_aggregation_outputs, PublicValuesVersion},
+    types::AggregationOutputs,
+};
+use op_succinct_host_utils::chaos::{self, FailurePoint};
+use std::collections::BTreeSet;
+
+use crate::{
+    contract::{
+        dispute_game_extra_data, IDisputeGameFactory, ILegacyL2OutputOracle,
+        ISP1Verifier::{self, ISP1VerifierErrors},
+        OPSuccinctL2OutputOracle::{self, OPSuccinctL2OutputOracleInstance},
+        OutputContract,
+    },
+    tx_manager::{TxInclusion, TxManager},
+};
+
+/// Simulate the verification `proposeL2Output` performs with an `eth_call` to the SP1 verifier, so
+/// a proof the verifier rejects fails with the reason instead of reverting on-chain.
+///
+/// The public values are rebuilt the same way the L2OO does: the checkpointed L1 block hash (or
+/// the block hash that will be checkpointed in the same transaction), the latest output root, and
+/// the rollup config hash and range vkey commitment stored on the contract, encoded in the
+/// aggregation outputs version stored with the aggregation vkey.
+pub async fn simulate_aggregate_verification<P>(
+    l2oo: &OPSuccinctL2OutputOracleInstance<(), P>,
+    output_root: B256,
+    l2_block_number: u64,
+    l1_block_number: u64,
+    proof: &Bytes,
+) -> Result<()>
+where
+    P: Provider<Ethereum> + Clone,
+{
+    let latest_output_index = l2oo.latestOutputIndex().call().await?._0;
+    let l2_pre_root = l2oo
+        .getL2Output(latest_output_index)
+        .call()
+        .await?
+        ._0
+        .outputRoot;
+    simulate_verification_from(
+        l2oo,
+        l2_pre_root,
+        output_root,
+        l2_block_number,
+        l1_block_number,
+        proof,
+    )
+    .await
+}
+
+/// Like [`simulate_aggregate_verification`], for a proposal following an output root that may not
+/// be proposed yet.
+async fn simulate_verification_from<P>(
+    l2oo: &OPSuccinctL2OutputOracleInstance<(), P>,
+    l2_pre_root: B256,
+    output_root: B256,
+    l2_block_number: u64,
+    l1_block_number: u64,
+    proof: &Bytes,
+) -> Result<()>
+where
+    P: Provider<Ethereum> + Clone,
+{
+    let l1_head = match l2oo
+        .historicBlockHashes(U256::from(l1_block_number))
+        .call()
+        .await?
+        ._0
+    {
+        B256::ZERO => {
+            l2oo.provider()
+                .get_block_by_number(l1_block_number.into(), BlockTransactionsKind::Hashes)
+                .await?
+                .ok_or_else(|| anyhow!("L1 block {} not found", l1_block_number))?
+                .header
+                .hash
+        }
+        checkpointed_hash => checkpointed_hash,
+    };
+
+    let aggregation_vkey = l2oo.aggregationVkey().call().await?._0;
+    let version = PublicValuesVersion::try_from(l2oo.aggregationOutputsVersion().call().await?._0)?;
+    let public_values = AggregationOutputs {
+        l1Head: l1_head,
+        l2PreRoot: l2_pre_root,
+        l2PostRoot: output_root,
+        l2BlockNumber: l2_block_number,
+        rollupConfigHash: l2oo.rollupConfigHash().call().await?._0,
+        multiBlockVKey: l2oo.rangeVkeyCommitment().call().await?._0,
+        checkpointOverridesHash: B256::ZERO,
+    };
+
+    let verifier = ISP1Verifier::new(l2oo.verifier().call().await?._0, l2oo.provider());
+    let result = verifier
+        .verifyProof(
+            aggregation_vkey,
+            encode_aggregation_outputs(version, &public_values).into(),
+            proof.clone(),
+        )
+        .call()
+        .await;
+    let Err(e) = result else {
+        return Ok(());
+    };
+    let Some(revert_data) = e.as_revert_data() else {
+        bail!("Failed to simulate aggregation proof verification: {}", e);
+    };
+    match ISP1VerifierErrors::abi_decode(&revert_data, true) {
+        Ok(ISP1VerifierErrors::RouteNotFound(err)) => bail!(
+            "The SP1 verifier has no route for proof selector {}. The proof was generated with an SP1 version the verifier does not support.",
+            err.selector
+        ),
+        Ok(ISP1VerifierErrors::RouteIsFrozen(err)) => bail!(
+            "The SP1 verifier route for proof selector {} is frozen. Regenerate the proof with a supported SP1 version.",
+            err.selector
+        ),
+        Ok(ISP1VerifierErrors::WrongVerifierSelector(err)) => bail!(
+            "The proof was generated for verifier {} but the verifier is {}. Point the L2OO at a verifier for the SP1 version used by the prover.",
+            err.receivedSelector,
+            err.expectedSelector
+        ),
+        Ok(ISP1VerifierErrors::InvalidProof(_)) | Ok(ISP1VerifierErrors::ProofInvalid(_)) => bail!(
+            "The aggregation proof does not verify against the on-chain aggregation vkey {} and the expected {:?} public values {:?}. Check that the aggregation vkey, range vkey commitment and rollup config hash on the L2OO match the prover, and that the proof starts from the latest proposed output root.",
+            aggregation_vkey,
+            version,
+            public_values
+        ),
+        Ok(ISP1VerifierErrors::PublicInputNotInField(_)) => bail!(
+            "The aggregation proof's public inputs are not in the scalar field. The proof is malformed."
+        ),
+        Err(_) => bail!(
+            "The SP1 verifier rejected the aggregation proof with revert data {}",
+            revert_data
+        ),
+    }
+}
+
+/// Propose an L2 output, checkpointing the L1 block hash the aggregation proof commits to in the
+/// same transaction.
+///
+/// Sending the checkpoint and the proposal as separate transactions leaves a window in which the
+/// L1 block can fall out of the 256 block `blockhash` window between the two, causing the
+/// proposal to revert with `L1BlockHashNotCheckpointed`. Bundling both calls into a single
+/// `multicall` makes the submission atomic. If the block hash was already checkpointed (e.g. by a
+/// previous submission attempt), only the proposal is sent.
+///
+/// The proof is checked against the SP1 verifier with [`simulate_aggregate_verification`] first,
+/// so a proof the verifier rejects is not submitted. The transaction is sent with `tx_manager`,
+/// which resubmits it with bumped fees while it isn't included.
+pub async fn propose_l2_output_with_checkpoint<P>(
+    l2oo: &OPSuccinctL2OutputOracleInstance<(), P>,
+    tx_manager: &TxManager<P>,
+    output_root: B256,
+    l2_block_number: u64,
+    l1_block_number: u64,
+    proof: Bytes,
+) -> Result<TxInclusion>
+where
+    P: Provider<Ethereum> + Clone,
+{
+    simulate_aggregate_verification(l2oo, output_root, l2_block_number, l1_block_number, &proof)
+        .await?;
+    chaos::inject(FailurePoint::Propose)?;
+
+    let mut calls = Vec::with_capacity(2);
+
+    let checkpointed_hash = l2oo
+        .historicBlockHashes(U256::from(l1_block_number))
+        .call()
+        .await?
+        ._0;
+    if checkpointed_hash == B256::ZERO {
+        calls.push(
+            l2oo.checkpointBlockHash(U256::from(l1_block_number))
+                .calldata()
+                .clone(),
+        );
+    }
+
+    calls.push(
+        l2oo.proposeL2Output(
+            output_root,
+            U256::from(l2_block_number),
+            U256::from(l1_block_number),
+            proof,
+        )
+        .calldata()
+        .clone(),
+    );
+
+    let inclusion = tx_manager
+        .send(l2oo.multicall(calls).into_transaction_request())
+        .await?;
+
+    if !inclusion.receipt.status() {
+        bail!(
+            "Propose transaction {:?} for L2 block {} reverted",
+            inclusion.receipt.transaction_hash,
+            l2_block_number
+        );
+    }
+
+    Ok(inclusion)
+}
+
+/// An output proposal and the aggregation proof justifying it.
+#[derive(Debug, Clone)]
+pub struct OutputSubmission {
+    pub output_root: B256,
+    pub l2_block_number: u64,
+    pub l1_block_number: u64,
+    pub proof: Bytes,
+}
+
+/// Propose several sequential L2 outputs in a single `multicall` transaction, checkpointing the L1
+/// block hashes their proofs commit to in the same transaction. Used for backfills, where sending
+/// each proposal separately pays the transaction overhead for every checkpoint.
+///
+/// Before sending, the proposals are checked to follow each other by at least the submission
+/// interval starting from the next expected block, and every proof is simulated against the output
+/// root proposed before it. The transaction is sent with `tx_manager` from the proposer's latest
+/// nonce, and is not sent if the proposer has pending transactions it could be reordered with.
+pub async fn propose_l2_outputs_batch<P>(
+    l2oo: &OPSuccinctL2OutputOracleInstance<(), P>,
+    tx_manager: &TxManager<P>,
+    submissions: &[OutputSubmission],
+) -> Result<TxInclusion>
+where
+    P: Provider<Ethereum> + Clone,
+{
+    let (Some(first), Some(last)) = (submissions.first(), submissions.last()) else {
+        bail!("No outputs to propose");
+    };
+
+    let next_block_number = l2oo.nextBlockNumber().call().await?._0;
+    if U256::from(first.l2_block_number) < next_block_number {
+        bail!(
+            "The first output is for L2 block {}, but the next expected block is {}",
+            first.l2_block_number,
+            next_block_number
+        );
+    }
+    let submission_interval = l2oo.submissionInterval().call().await?._0;
+    for pair in submissions.windows(2) {
+        if U256::from(pair[1].l2_block_number)
+            < U256::from(pair[0].l2_block_number) + submission_interval
+        {
+            bail!(
+                "The output for L2 block {} does not follow the output for L2 block {} by the submission interval of {}",
+                pair[1].l2_block_number,
+                pair[0].l2_block_number,
+                submission_interval
+            );
+        }
+    }
+
+    // Each proof starts from the output root proposed before it in the batch.
+    let latest_output_index = l2oo.latestOutputIndex().call().await?._0;
+    let mut l2_pre_root = l2oo
+        .getL2Output(latest_output_index)
+        .call()
+        .await?
+        ._0
+        .outputRoot;
+    for submission in submissions {
+        simulate_verification_from(
+            l2oo,
+            l2_pre_root,
+            submission.output_root,
+            submission.l2_block_number,
+            submission.l1_block_number,
+            &submission.proof,
+        )
+        .await
+        .map_err(|e| {
+            e.context(format!(
+                "Output for L2 block {}",
+                submission.l2_block_number
+            ))
+        })?;
+        l2_pre_root = submission.output_root;
+    }
+    chaos::inject(FailurePoint::Propose)?;
+
+    let provider = l2oo.provider();
+    let proposer = tx_manager.from();
+    let nonce = provider.get_transaction_count(proposer).await?;
+    let pending_nonce = provider.get_transaction_count(proposer).pending().await?;
+    if pending_nonce != nonce {
+        bail!(
+            "Proposer {} has {} pending transactions. Wait for them before proposing a batch.",
+            proposer,
+            pending_nonce - nonce
+        );
+    }
+
+    let l1_block_numbers: BTreeSet<u64> = submissions.iter().map(|s| s.l1_block_number).collect();
+    let mut calls = Vec::with_capacity(l1_block_numbers.len() + submissions.len());
+    for l1_block_number in l1_block_numbers {
+        let checkpointed_hash = l2oo
+            .historicBlockHashes(U256::from(l1_block_number))
+            .call()
+            .await?
+            ._0;
+        if checkpointed_hash == B256::ZERO {
+            calls.push(
+                l2oo.checkpointBlockHash(U256::from(l1_block_number))
+                    .calldata()
+                    .clone(),
+            );
+        }
+    }
+    for submission in submissions {
+        calls.push(
+            l2oo.proposeL2Output(
+                submission.output_root,
+                U256::from(submission.l2_block_number),
+                U256::from(submission.l1_block_number),
+                submission.proof.clone(),
+            )
+            .calldata()
+            .clone(),
+        );
+    }
+
+    let inclusion = tx_manager
+        .send(l2oo.multicall(calls).into_transaction_request())
+        .await?;
+
+    if !inclusion.receipt.status() {
+        bail!(
+            "Batch propose transaction {:?} for L2 blocks {}-{} reverted",
+            inclusion.receipt.transaction_hash,
+            first.l2_block_number,
+            last.l2_block_number
+        );
+    }
+
+    Ok(inclusion)
+}
+
+impl OutputContract {
+    /// Propose an output. The proof and the L1 block it commits to are verified by the
+    /// `OPSuccinctL2OutputOracle`, directly or through a dispute game, see
+    /// [`propose_l2_output_with_checkpoint`]. The legacy `L2OutputOracle` ignores the proof and
+    /// commits to the L1 block's hash instead.
+    pub async fn propose<P>(
+        &self,
+        provider: &P,
+        tx_manager: &TxManager<P>,
+        submission: &OutputSubmission,
+    ) -> Result<TxInclusion>
+    where
+        P: Provider<Ethereum> + Clone,
+    {
+        let tx = match *self {
+            Self::ZkL2OutputOracle(address) => {
+                let l2oo = OPSuccinctL2OutputOracle::new(address, provider.clone());
+                return propose_l2_output_with_checkpoint(
+                    &l2oo,
+                    tx_manager,
+                    submission.output_root,
+                    submission.l2_block_number,
+                    submission.l1_block_number,
+                    submission.proof.clone(),
+                )
+                .await;
+            }
+            Self::LegacyL2OutputOracle(address) => {
+                let l1_block_hash = provider
+                    .get_block_by_number(
+                        submission.l1_block_number.into(),
+                        BlockTransactionsKind::Hashes,
+                    )
+                    .await?
+                    .ok_or_else(|| anyhow!("L1 block {} not found", submission.l1_block_number))?
+                    .header
+                    .hash;
+                ILegacyL2OutputOracle::new(address, provider.clone())
+                    .proposeL2Output(
+                        submission.output_root,
+                        U256::from(submission.l2_block_number),
+                        l1_block_hash,
+                        U256::from(submission.l1_block_number),
+                    )
+                    .into_transaction_request()
+            }
+            Self::OptimismPortal2 {
+                dispute_game_factory,
+                game_type,
+                ..
+            } => {
+                let factory = IDisputeGameFactory::new(dispute_game_factory, provider.clone());
+                let bond = factory.initBonds(game_type).call().await?._0;
+                factory
+                    .create(
+                        game_type,
+                        submission.output_root,
+                        dispute_game_extra_data(
+                            submission.l2_block_number,
+                            submission.l1_block_number,
+                            &submission.proof,
+                        ),
+                    )
+                    .value(bond)
+                    .into_transaction_request()
+            }
+        };
+
+        let inclusion = tx_manager.send(tx).await?;
+        if !inclusion.receipt.status() {
+            bail!(
+                "Propose transaction {:?} for L2 block {} reverted",
+                inclusion.receipt.transaction_hash,
+                submission.l2_block_number
+            );
+        }
+        Ok(inclusion)
+    }
+}
@@ -0,0 +1,258 @@
+//! Tracking of the rollup node's safe and finalized L2 heads.
+//!
+//! Range planning is driven off the rollup node (`L2_NODE_RPC`, which may be an op-conductor
+//! fronted sequencer), while witness generation reads blocks from the L2 execution RPC
+//! (`L2_RPC`). If the two disagree, e.g. an execution node stuck on a stale fork or behind the
+//! rollup node, spans planned up to the rollup node's safe head can't be proven. The
+//! [`SyncStatusTracker`] polls `optimism_syncStatus` every `SYNC_STATUS_INTERVAL_SECS`, and checks
+//! the rollup node's safe and finalized L2 heads against the blocks of the execution RPC.
+//!
+//! The latest heads are served at `GET /sync_status`, for proposers to plan ranges off. Each
+//! discrepancy is logged as an error and counted in the `sync_status_discrepancies_total` metric
+//! for alerting.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use alloy_primitives::B256;
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::BlockTransactionsKind;
+use anyhow::Result;
+use log::{error, warn};
+use op_succinct_host_utils::{
+    config::env_var,
+    fetcher::{OPSuccinctDataFetcher, RPCMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::metrics::ServerMetrics;
+
+/// The default interval between polls of the rollup node's sync status, about one L1 block.
+const DEFAULT_INTERVAL_SECS: u64 = 12;
+
+/// Configures the sync status tracker.
+#[derive(Debug, Clone)]
+pub struct SyncTrackerConfig {
+    pub interval: Duration,
+}
+
+impl SyncTrackerConfig {
+    /// Read the tracker config from the environment.
+    ///
+    /// - `SYNC_STATUS_INTERVAL_SECS`: Default: 12. Set to 0 to disable the tracker.
+    pub fn from_env() -> Result<Option<Self>> {
+        let interval_secs =
+            env_var::<u64>("SYNC_STATUS_INTERVAL_SECS")?.unwrap_or(DEFAULT_INTERVAL_SECS);
+        Ok((interval_secs > 0).then(|| Self {
+            interval: Duration::from_secs(interval_secs),
+        }))
+    }
+}
+
+/// An L2 block as reported by the rollup node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L2BlockRef {
+    pub hash: B256,
+    pub number: u64,
+}
+
+/// The heads read from the rollup node's `optimism_syncStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollupHeads {
+    pub safe_l2: L2BlockRef,
+    pub finalized_l2: L2BlockRef,
+}
+
+impl RollupHeads {
+    fn named(&self) -> [(&'static str, L2BlockRef); 2] {
+        [("safe", self.safe_l2), ("finalized", self.finalized_l2)]
+    }
+}
+
+/// How the execution RPC disagrees with the rollup node about a head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscrepancyKind {
+    /// The execution RPC doesn't have the block yet.
+    ExecutionBehind,
+    /// The execution RPC has a different block at the head's number.
+    HashMismatch,
+}
+
+impl DiscrepancyKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::ExecutionBehind => "execution_behind",
+            Self::HashMismatch => "hash_mismatch",
+        }
+    }
+}
+
+/// A head of the rollup node the execution RPC disagrees with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncDiscrepancy {
+    /// `safe` or `finalized`.
+    pub head: &'static str,
+    pub kind: DiscrepancyKind,
+    pub l2_block_number: u64,
+    pub rollup_hash: B256,
+    /// The hash of the execution RPC's block, if it has one.
+    pub execution_hash: Option<B256>,
+    pub detected_at: u64,
+}
+
+/// The rollup node's latest heads and the discrepancies found so far.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncStatus {
+    pub heads: Option<RollupHeads>,
+    pub last_polled_at: Option<u64>,
+    pub discrepancies: u64,
+    pub last_discrepancy: Option<SyncDiscrepancy>,
+}
+
+/// Compare a head of the rollup node with the execution RPC's hash of the block at its number.
+fn check_head(
+    head: &'static str,
+    rollup: L2BlockRef,
+    execution_hash: Option<B256>,
+) -> Option<SyncDiscrepancy> {
+    let kind = match execution_hash {
+        None => DiscrepancyKind::ExecutionBehind,
+        Some(hash) if hash != rollup.hash => DiscrepancyKind::HashMismatch,
+        Some(_) => return None,
+    };
+    Some(SyncDiscrepancy {
+        head,
+        kind,
+        l2_block_number: rollup.number,
+        rollup_hash: rollup.hash,
+        execution_hash,
+        detected_at: now(),
+    })
+}
+
+/// Polls the rollup node's heads and reconciles them with the execution RPC.
+pub struct SyncStatusTracker {
+    config: SyncTrackerConfig,
+    fetcher: OPSuccinctDataFetcher,
+    status: Arc<Mutex<SyncStatus>>,
+    metrics: Arc<ServerMetrics>,
+}
+
+impl SyncStatusTracker {
+    pub fn new(
+        config: SyncTrackerConfig,
+        fetcher: OPSuccinctDataFetcher,
+        status: Arc<Mutex<SyncStatus>>,
+        metrics: Arc<ServerMetrics>,
+    ) -> Self {
+        Self {
+            config,
+            fetcher,
+            status,
+            metrics,
+        }
+    }
+
+    /// Poll the sync status every interval. Failures are logged and the poll is retried on the
+    /// next interval.
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(self.config.interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.poll().await {
+                warn!("Failed to poll the rollup node's sync status: {:#}", e);
+            }
+        }
+    }
+
+    async fn poll(&self) -> Result<()> {
+        let heads: RollupHeads = self
+            .fetcher
+            .fetch_rpc_data_with_mode(RPCMode::L2Node, "optimism_syncStatus", vec![])
+            .await?;
+
+        let mut discrepancies = Vec::new();
+        for (head, rollup) in heads.named() {
+            self.metrics.record_rollup_head(head, rollup.number);
+            let execution_hash = self
+                .fetcher
+                .l2_provider
+                .get_block_by_number(rollup.number.into(), BlockTransactionsKind::Hashes)
+                .await?
+                .map(|block| block.header.hash);
+            discrepancies.extend(check_head(head, rollup, execution_hash));
+        }
+
+        for discrepancy in &discrepancies {
+            error!(
+                "The L2 execution RPC disagrees with the rollup node's {} head at L2 block {} \
+                 ({:?}): rollup node has {}, execution RPC has {:?}",
+                discrepancy.head,
+                discrepancy.l2_block_number,
+                discrepancy.kind,
+                discrepancy.rollup_hash,
+                discrepancy.execution_hash
+            );
+            self.metrics
+                .record_sync_discrepancy(discrepancy.head, discrepancy.kind.as_str());
+        }
+
+        let mut status = self.status.lock().unwrap();
+        status.heads = Some(heads);
+        status.last_polled_at = Some(now());
+        status.discrepancies += discrepancies.len() as u64;
+        if let Some(discrepancy) = discrepancies.pop() {
+            status.last_discrepancy = Some(discrepancy);
+        }
+        Ok(())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sync_status() {
+        // The fields of `optimism_syncStatus` other than the L2 heads are ignored.
+        let heads: RollupHeads = serde_json::from_value(serde_json::json!({
+            "head_l1": { "hash": B256::repeat_byte(1), "number": 200 },
+            "safe_l2": {
+                "hash": B256::repeat_byte(2),
+                "number": 1000,
+                "parentHash": B256::repeat_byte(3),
+                "timestamp": 1700000000,
+            },
+            "finalized_l2": { "hash": B256::repeat_byte(4), "number": 900 },
+        }))
+        .unwrap();
+        assert_eq!(heads.safe_l2.number, 1000);
+        assert_eq!(heads.finalized_l2.hash, B256::repeat_byte(4));
+    }
+
+    #[test]
+    fn test_check_head() {
+        let rollup = L2BlockRef {
+            hash: B256::repeat_byte(1),
+            number: 100,
+        };
+        assert_eq!(check_head("safe", rollup, Some(rollup.hash)), None);
+
+        let behind = check_head("safe", rollup, None).unwrap();
+        assert_eq!(behind.kind, DiscrepancyKind::ExecutionBehind);
+
+        let mismatch = check_head("finalized", rollup, Some(B256::repeat_byte(2))).unwrap();
+        assert_eq!(mismatch.kind, DiscrepancyKind::HashMismatch);
+        assert_eq!(mismatch.execution_hash, Some(B256::repeat_byte(2)));
+    }
+}
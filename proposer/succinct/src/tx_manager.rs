@@ -0,0 +1,390 @@
+//! Sends the proposer's transactions with EIP-1559 fees and resubmits them while they're stuck.
+//!
+//! A transaction sent with the provider's default fees can sit in the mempool indefinitely if the
+//! base fee rises after it is sent. The [`TxManager`] prices each transaction from the latest base
+//! fee and the configured priority fee strategy, and if it isn't included within
+//! `TX_RESUBMIT_AFTER_BLOCKS` blocks, replaces it with a transaction with the same nonce and fees
+//! bumped by at least `TX_FEE_BUMP_PERCENT`. Whichever of the submitted transactions is included
+//! counts. If the nonce is used by a transaction the manager didn't send, the submission fails.
+
+use std::{
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use alloy_network::{Ethereum, TransactionBuilder};
+use alloy_primitives::{Address, TxHash};
+use alloy_provider::Provider;
+use alloy_rpc_types_eth::{
+    BlockNumberOrTag, BlockTransactionsKind, TransactionReceipt, TransactionRequest,
+};
+use anyhow::{anyhow, bail, Result};
+use log::{info, warn};
+use op_succinct_host_utils::config::env_var;
+
+use crate::contract::NUM_CONFIRMATIONS;
+
+/// How often a submitted transaction is checked for inclusion.
+const POLL_INTERVAL: Duration = Duration::from_secs(4);
+
+/// Nodes reject a replacement transaction unless it raises both fees by at least 10%.
+const MIN_FEE_BUMP_PERCENT: u64 = 10;
+
+/// The number of blocks whose priority fees the percentile strategy samples.
+const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// How the priority fee of a transaction is chosen, set with `TX_PRIORITY_FEE_STRATEGY`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum PriorityFeeStrategy {
+    /// The node's suggestion, from `eth_maxPriorityFeePerGas`.
+    #[default]
+    Estimate,
+    /// The median over the last blocks of a percentile of the priority fees paid in each block,
+    /// from `eth_feeHistory`.
+    Percentile(f64),
+    /// A fixed priority fee in wei.
+    Fixed(u128),
+}
+
+impl FromStr for PriorityFeeStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.to_lowercase();
+        match s.split_once(':') {
+            None if s == "estimate" => Ok(Self::Estimate),
+            Some(("percentile", percentile)) => match percentile.parse::<f64>() {
+                Ok(percentile) if (0.0..=100.0).contains(&percentile) => {
+                    Ok(Self::Percentile(percentile))
+                }
+                _ => bail!("Invalid percentile {percentile}, expected a number from 0 to 100"),
+            },
+            Some(("fixed", fee)) => Ok(Self::Fixed(fee.parse()?)),
+            _ => bail!(
+                "Unknown priority fee strategy {s}, expected estimate, percentile:<p> or fixed:<wei>"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for PriorityFeeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Estimate => write!(f, "estimate"),
+            Self::Percentile(percentile) => write!(f, "percentile:{percentile}"),
+            Self::Fixed(fee) => write!(f, "fixed:{fee}"),
+        }
+    }
+}
+
+/// The fees of an EIP-1559 transaction, in wei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fees {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+/// Settings for pricing and resubmitting transactions.
+#[derive(Debug, Clone)]
+pub struct TxManagerConfig {
+    pub priority_fee: PriorityFeeStrategy,
+    /// The max fee per gas is the latest base fee times this multiplier plus the priority fee, so
+    /// the transaction stays includable while the base fee rises.
+    pub base_fee_multiplier: u64,
+    /// The highest max fee per gas the manager pays, including after fee bumps.
+    pub max_fee_per_gas: Option<u128>,
+    pub resubmit_after_blocks: u64,
+    pub fee_bump_percent: u64,
+    pub max_resubmissions: u32,
+}
+
+impl TxManagerConfig {
+    /// Read the settings from the environment.
+    pub fn from_env() -> Result<Self> {
+        let fee_bump_percent = env_var("TX_FEE_BUMP_PERCENT")?.unwrap_or(MIN_FEE_BUMP_PERCENT);
+        if fee_bump_percent < MIN_FEE_BUMP_PERCENT {
+            bail!(
+                "TX_FEE_BUMP_PERCENT must be at least {MIN_FEE_BUMP_PERCENT}, or nodes reject the replacement transactions"
+            );
+        }
+
+        Ok(Self {
+            priority_fee: env_var("TX_PRIORITY_FEE_STRATEGY")?.unwrap_or_default(),
+            base_fee_multiplier: env_var("TX_BASE_FEE_MULTIPLIER")?.unwrap_or(2),
+            max_fee_per_gas: env_var("TX_MAX_FEE_PER_GAS")?,
+            resubmit_after_blocks: env_var("TX_RESUBMIT_AFTER_BLOCKS")?.unwrap_or(3),
+            fee_bump_percent,
+            max_resubmissions: env_var("TX_MAX_RESUBMISSIONS")?.unwrap_or(10),
+        })
+    }
+
+    /// The fees for a transaction sent now, capped at the max fee per gas.
+    fn fees(&self, base_fee: u128, priority_fee: u128) -> Fees {
+        let mut max_fee_per_gas = base_fee * self.base_fee_multiplier as u128 + priority_fee;
+        if let Some(cap) = self.max_fee_per_gas {
+            max_fee_per_gas = max_fee_per_gas.min(cap);
+        }
+        Fees {
+            max_fee_per_gas,
+            max_priority_fee_per_gas: priority_fee.min(max_fee_per_gas),
+        }
+    }
+
+    /// The fees for a replacement of a transaction sent with `previous`: bumped by the fee bump
+    /// percentage, or the fees for a transaction sent now if they're higher. Returns `None` if the
+    /// bump would exceed the max fee per gas.
+    fn bump(&self, previous: Fees, current: Fees) -> Option<Fees> {
+        let bump = |fee: u128| (fee * (100 + self.fee_bump_percent as u128)).div_ceil(100);
+        let fees = Fees {
+            max_fee_per_gas: bump(previous.max_fee_per_gas).max(current.max_fee_per_gas),
+            max_priority_fee_per_gas: bump(previous.max_priority_fee_per_gas)
+                .max(current.max_priority_fee_per_gas),
+        };
+        match self.max_fee_per_gas {
+            Some(cap) if fees.max_fee_per_gas > cap => None,
+            _ => Some(fees),
+        }
+    }
+}
+
+/// A confirmed transaction and how it got there.
+#[derive(Debug, Clone)]
+pub struct TxInclusion {
+    pub receipt: TransactionReceipt,
+    /// The number of transactions sent with the nonce, including the original.
+    pub attempts: u32,
+    /// The number of blocks between the first submission and the block that included it.
+    pub blocks_to_inclusion: u64,
+    pub elapsed: Duration,
+    /// The fees of the included transaction.
+    pub fees: Fees,
+}
+
+/// Sends transactions from one account, one at a time.
+pub struct TxManager<P> {
+    provider: P,
+    from: Address,
+    config: TxManagerConfig,
+}
+
+impl<P> TxManager<P>
+where
+    P: Provider<Ethereum>,
+{
+    pub fn new(provider: P, from: Address, config: TxManagerConfig) -> Self {
+        Self {
+            provider,
+            from,
+            config,
+        }
+    }
+
+    /// The account the transactions are sent from.
+    pub fn from(&self) -> Address {
+        self.from
+    }
+
+    /// Send a transaction with the account's pending nonce and wait for it to be confirmed,
+    /// resubmitting it with bumped fees while it isn't included.
+    pub async fn send(&self, tx: TransactionRequest) -> Result<TxInclusion> {
+        let started_at = Instant::now();
+        let nonce = self
+            .provider
+            .get_transaction_count(self.from)
+            .pending()
+            .await?;
+        let tx = tx.with_from(self.from).with_nonce(nonce);
+        let gas_limit = self.provider.estimate_gas(&tx).await?;
+        let tx = tx.with_gas_limit(gas_limit);
+
+        let first_block = self.provider.get_block_number().await?;
+        let mut fees = self.current_fees().await?;
+        let mut sent = vec![(self.submit(&tx, fees).await?, fees)];
+        let mut last_submitted_at = first_block;
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let block = self.provider.get_block_number().await?;
+            // Read the nonce before the receipts, so a transaction included in between is not
+            // mistaken for a replacement.
+            let mined_nonce = self.provider.get_transaction_count(self.from).await?;
+
+            if let Some((receipt, included_fees)) = self.find_receipt(&sent).await? {
+                let included_at = receipt.block_number.unwrap_or(block);
+                if block + 1 < included_at + NUM_CONFIRMATIONS {
+                    continue;
+                }
+                let inclusion = TxInclusion {
+                    receipt,
+                    attempts: sent.len() as u32,
+                    blocks_to_inclusion: included_at.saturating_sub(first_block),
+                    elapsed: started_at.elapsed(),
+                    fees: included_fees,
+                };
+                info!(
+                    "Transaction {} with nonce {} included in block {} after {} blocks and {:?} ({} attempts, effective gas price {} wei)",
+                    inclusion.receipt.transaction_hash,
+                    nonce,
+                    included_at,
+                    inclusion.blocks_to_inclusion,
+                    inclusion.elapsed,
+                    inclusion.attempts,
+                    inclusion.receipt.effective_gas_price
+                );
+                return Ok(inclusion);
+            }
+
+            if mined_nonce > nonce {
+                bail!(
+                    "Nonce {} of {} was used by a transaction other than the {} sent by the transaction manager",
+                    nonce,
+                    self.from,
+                    sent.len()
+                );
+            }
+
+            if block < last_submitted_at + self.config.resubmit_after_blocks {
+                continue;
+            }
+            if sent.len() as u32 > self.config.max_resubmissions {
+                bail!(
+                    "Transaction with nonce {} was not included after {} resubmissions",
+                    nonce,
+                    self.config.max_resubmissions
+                );
+            }
+            last_submitted_at = block;
+
+            let Some(bumped) = self.config.bump(fees, self.current_fees().await?) else {
+                warn!(
+                    "Transaction with nonce {} is not included, but its fees {:?} can't be bumped without exceeding TX_MAX_FEE_PER_GAS",
+                    nonce, fees
+                );
+                continue;
+            };
+            match self.submit(&tx, bumped).await {
+                Ok(tx_hash) => {
+                    warn!(
+                        "Transaction with nonce {} not included after {} blocks, resubmitted as {} with fees {:?}",
+                        nonce,
+                        block - first_block,
+                        tx_hash,
+                        bumped
+                    );
+                    sent.push((tx_hash, bumped));
+                    fees = bumped;
+                }
+                // The nonce may have been used by a previous submission since the last poll, in
+                // which case its receipt is found on the next one.
+                Err(e) => warn!("Failed to resubmit transaction with nonce {}: {}", nonce, e),
+            }
+        }
+    }
+
+    /// The fees for a transaction sent now.
+    async fn current_fees(&self) -> Result<Fees> {
+        let base_fee = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Hashes)
+            .await?
+            .ok_or_else(|| anyhow!("The latest block was not found"))?
+            .header
+            .base_fee_per_gas
+            .ok_or_else(|| anyhow!("The latest block has no base fee"))?;
+
+        let priority_fee = match self.config.priority_fee {
+            PriorityFeeStrategy::Estimate => self.provider.get_max_priority_fee_per_gas().await?,
+            PriorityFeeStrategy::Percentile(percentile) => {
+                let history = self
+                    .provider
+                    .get_fee_history(FEE_HISTORY_BLOCKS, BlockNumberOrTag::Latest, &[percentile])
+                    .await?;
+                let mut rewards = history
+                    .reward
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|rewards| rewards.first().copied())
+                    .collect::<Vec<_>>();
+                rewards.sort_unstable();
+                rewards
+                    .get(rewards.len() / 2)
+                    .copied()
+                    .ok_or_else(|| anyhow!("The node returned no fee history rewards"))?
+            }
+            PriorityFeeStrategy::Fixed(fee) => fee,
+        };
+
+        Ok(self.config.fees(base_fee as u128, priority_fee))
+    }
+
+    async fn submit(&self, tx: &TransactionRequest, fees: Fees) -> Result<TxHash> {
+        let tx = tx
+            .clone()
+            .with_max_fee_per_gas(fees.max_fee_per_gas)
+            .with_max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+        Ok(*self.provider.send_transaction(tx).await?.tx_hash())
+    }
+
+    /// The receipt of whichever submitted transaction was included, and its fees.
+    async fn find_receipt(
+        &self,
+        sent: &[(TxHash, Fees)],
+    ) -> Result<Option<(TransactionReceipt, Fees)>> {
+        for (tx_hash, fees) in sent {
+            if let Some(receipt) = self.provider.get_transaction_receipt(*tx_hash).await? {
+                return Ok(Some((receipt, *fees)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(max_fee_per_gas: Option<u128>) -> TxManagerConfig {
+        TxManagerConfig {
+            priority_fee: PriorityFeeStrategy::Estimate,
+            base_fee_multiplier: 2,
+            max_fee_per_gas,
+            resubmit_after_blocks: 3,
+            fee_bump_percent: 10,
+            max_resubmissions: 10,
+        }
+    }
+
+    fn fees(max_fee_per_gas: u128, max_priority_fee_per_gas: u128) -> Fees {
+        Fees {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        }
+    }
+
+    #[test]
+    fn test_bump_fees() {
+        let config = config(Some(150));
+        assert_eq!(config.fees(40, 5), fees(85, 5));
+        assert_eq!(config.fees(100, 5), fees(150, 5));
+
+        // Both fees are bumped, rounding up, unless the current fees are higher.
+        assert_eq!(config.bump(fees(85, 5), fees(85, 5)), Some(fees(94, 6)));
+        assert_eq!(config.bump(fees(85, 5), fees(120, 5)), Some(fees(120, 6)));
+        // A bump beyond the cap is not sent, as the node would reject a smaller one.
+        assert_eq!(config.bump(fees(140, 5), fees(140, 5)), None);
+    }
+
+    #[test]
+    fn test_parse_priority_fee_strategy() {
+        let parse = |s: &str| s.parse::<PriorityFeeStrategy>().ok();
+        assert_eq!(parse("estimate"), Some(PriorityFeeStrategy::Estimate));
+        assert_eq!(
+            parse("percentile:60"),
+            Some(PriorityFeeStrategy::Percentile(60.0))
+        );
+        assert_eq!(parse("fixed:1000"), Some(PriorityFeeStrategy::Fixed(1000)));
+        assert_eq!(parse("percentile:101"), None);
+        assert_eq!(parse("fastest"), None);
+    }
+}
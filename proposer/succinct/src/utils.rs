@@ -0,0 +1,38 @@
+use std::env;
+
+use axum::{body::Body, http::Request};
+use tracing::Span;
+use tracing_subscriber::EnvFilter;
+
+/// Set up logging for the server. Logs are written as JSON if `LOG_FORMAT` is set to `json`, and as
+/// text otherwise. Records from the `log` crate are forwarded to the same subscriber, so they carry
+/// the fields of the span they are emitted in.
+pub fn setup_logging() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false);
+
+    match env::var("LOG_FORMAT") {
+        Ok(format) if format.to_lowercase() == "json" => builder.json().init(),
+        _ => builder.init(),
+    }
+}
+
+/// Create the span for an HTTP request, tagged with the request ID set by the `SetRequestIdLayer`.
+/// Every log emitted while handling the request, including during witness generation and proof
+/// submission, is recorded within this span.
+pub fn make_request_span(request: &Request<Body>) -> Span {
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|id| id.to_str().ok())
+        .unwrap_or_default();
+
+    tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        uri = %request.uri(),
+    )
+}
@@ -0,0 +1,235 @@
+use alloy_primitives::B256;
+use op_succinct_client_utils::{
+    boot::BootInfoStruct,
+    types::{link_spans, CheckpointOverride, SpanLinkError},
+};
+use std::fmt;
+
+/// The subproofs of an aggregation request don't form a single chain of spans, so the aggregation
+/// program would fail on them. The server responds with `422 Unprocessable Entity`.
+#[derive(Debug)]
+pub enum BrokenSubproofChain {
+    Empty,
+    /// The subproof could not be deserialized.
+    Undecodable {
+        index: usize,
+        error: String,
+    },
+    /// The subproof was generated for a different rollup config.
+    RollupConfigMismatch {
+        index: usize,
+        rollup_config_hash: B256,
+    },
+    /// The subproof does not start from the output root the previous subproof ended at.
+    BrokenLink {
+        index: usize,
+        prev_l2_block_number: u64,
+        prev_l2_post_root: B256,
+        l2_block_number: u64,
+        l2_pre_root: B256,
+    },
+    /// In quorum mode, the primary and secondary proofs of the subproof commit to different public
+    /// values.
+    QuorumMismatch {
+        index: usize,
+    },
+    /// The checkpoint override bridges no gap between the subproofs.
+    UnusedCheckpointOverride {
+        index: usize,
+    },
+}
+
+impl fmt::Display for BrokenSubproofChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "No subproofs to aggregate"),
+            Self::Undecodable { index, error } => {
+                write!(f, "Subproof {} could not be decoded: {}", index, error)
+            }
+            Self::RollupConfigMismatch {
+                index,
+                rollup_config_hash,
+            } => write!(
+                f,
+                "Subproof {} was generated for rollup config hash {}",
+                index, rollup_config_hash
+            ),
+            Self::BrokenLink {
+                index,
+                prev_l2_block_number,
+                prev_l2_post_root,
+                l2_block_number,
+                l2_pre_root,
+            } => write!(
+                f,
+                "Subproof {} (ending at L2 block {}) starts from output root {}, but subproof {} \
+                 ends at L2 block {} with output root {}. The subproofs have a gap, overlap or are \
+                 out of order.",
+                index,
+                l2_block_number,
+                l2_pre_root,
+                index - 1,
+                prev_l2_block_number,
+                prev_l2_post_root
+            ),
+            Self::QuorumMismatch { index } => write!(
+                f,
+                "The primary and secondary proofs of subproof {} commit to different public values",
+                index
+            ),
+            Self::UnusedCheckpointOverride { index } => write!(
+                f,
+                "Checkpoint override {} bridges no gap between the subproofs",
+                index
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BrokenSubproofChain {}
+
+/// The stdin of a span proof exceeds the configured size limit, so the prover network would reject
+/// it after upload. The server responds with `422 Unprocessable Entity` so the proposer splits the
+/// span into smaller ones.
+#[derive(Debug)]
+pub struct OversizedStdin {
+    pub start: u64,
+    pub end: u64,
+    pub size: usize,
+    pub limit: usize,
+}
+
+impl fmt::Display for OversizedStdin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Stdin for span {}-{} is {} bytes, which exceeds the limit of {} bytes",
+            self.start, self.end, self.size, self.limit
+        )
+    }
+}
+
+impl std::error::Error for OversizedStdin {}
+
+/// Check the size of a span proof's stdin against the limit, if there is one.
+pub fn check_stdin_size(
+    start: u64,
+    end: u64,
+    size: usize,
+    limit: Option<usize>,
+) -> Result<(), OversizedStdin> {
+    match limit {
+        Some(limit) if size > limit => Err(OversizedStdin {
+            start,
+            end,
+            size,
+            limit,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Check that the subproofs of an aggregation request chain together: each starts from the output
+/// root the previous one ended at, or a checkpoint override bridges them, and all of them were
+/// generated for the chain's rollup config. These are the checks the aggregation program asserts.
+pub fn validate_subproof_chain(
+    boot_infos: &[BootInfoStruct],
+    rollup_config_hash: B256,
+    checkpoint_overrides: &[CheckpointOverride],
+) -> Result<(), BrokenSubproofChain> {
+    if boot_infos.is_empty() {
+        return Err(BrokenSubproofChain::Empty);
+    }
+
+    for (index, boot_info) in boot_infos.iter().enumerate() {
+        if boot_info.rollupConfigHash != rollup_config_hash {
+            return Err(BrokenSubproofChain::RollupConfigMismatch {
+                index,
+                rollup_config_hash: boot_info.rollupConfigHash,
+            });
+        }
+    }
+
+    let broken_link = |index: usize| {
+        let (prev, boot_info) = (&boot_infos[index - 1], &boot_infos[index]);
+        BrokenSubproofChain::BrokenLink {
+            index,
+            prev_l2_block_number: prev.l2BlockNumber,
+            prev_l2_post_root: prev.l2PostRoot,
+            l2_block_number: boot_info.l2BlockNumber,
+            l2_pre_root: boot_info.l2PreRoot,
+        }
+    };
+
+    for (index, pair) in boot_infos.windows(2).enumerate() {
+        if pair[0].l2BlockNumber >= pair[1].l2BlockNumber {
+            return Err(broken_link(index + 1));
+        }
+    }
+
+    match link_spans(boot_infos, checkpoint_overrides) {
+        Ok(_) => Ok(()),
+        Err(SpanLinkError::Gap { index }) => Err(broken_link(index)),
+        Err(SpanLinkError::UnusedOverride { index }) => {
+            Err(BrokenSubproofChain::UnusedCheckpointOverride { index })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boot_info(pre: u8, post: u8, l2_block_number: u64) -> BootInfoStruct {
+        BootInfoStruct {
+            l1Head: B256::ZERO,
+            l2PreRoot: B256::repeat_byte(pre),
+            l2PostRoot: B256::repeat_byte(post),
+            l2BlockNumber: l2_block_number,
+            rollupConfigHash: B256::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_validate_subproof_chain() {
+        let chain = [boot_info(1, 2, 10), boot_info(2, 3, 20)];
+        assert!(validate_subproof_chain(&chain, B256::ZERO, &[]).is_ok());
+
+        let gap = [boot_info(1, 2, 10), boot_info(4, 5, 30)];
+        assert!(matches!(
+            validate_subproof_chain(&gap, B256::ZERO, &[]),
+            Err(BrokenSubproofChain::BrokenLink { index: 1, .. })
+        ));
+
+        // A governance-approved checkpoint override bridges the gap.
+        let bridge = CheckpointOverride {
+            fromRoot: B256::repeat_byte(2),
+            toRoot: B256::repeat_byte(4),
+            justificationHash: B256::ZERO,
+        };
+        assert!(validate_subproof_chain(&gap, B256::ZERO, &[bridge.clone()]).is_ok());
+        assert!(matches!(
+            validate_subproof_chain(&chain, B256::ZERO, &[bridge]),
+            Err(BrokenSubproofChain::UnusedCheckpointOverride { index: 0 })
+        ));
+
+        assert!(matches!(
+            validate_subproof_chain(&chain, B256::repeat_byte(1), &[]),
+            Err(BrokenSubproofChain::RollupConfigMismatch { index: 0, .. })
+        ));
+        assert!(matches!(
+            validate_subproof_chain(&[], B256::ZERO, &[]),
+            Err(BrokenSubproofChain::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_check_stdin_size() {
+        assert!(check_stdin_size(0, 10, 100, None).is_ok());
+        assert!(check_stdin_size(0, 10, 100, Some(100)).is_ok());
+        assert!(matches!(
+            check_stdin_size(0, 10, 101, Some(100)),
+            Err(OversizedStdin { size: 101, .. })
+        ));
+    }
+}
@@ -0,0 +1,445 @@
+//! Notifies external systems, such as bridge relayers, when the proofs they requested settle.
+//!
+//! A span or aggregation proof request may carry a `callback_url`. Once the proof is fulfilled or
+//! unfulfillable, the server POSTs a [`ProofWebhookPayload`] to it, signed with an HMAC-SHA256 of
+//! the body keyed with `WEBHOOK_SECRET`. Deliveries that fail are retried with exponential backoff.
+//! Callbacks are kept in memory, so callbacks of proofs that settle after a restart are not sent.
+//!
+//! Callback URLs must be on a host in `WEBHOOK_HOST_ALLOWLIST`, and the host must resolve to public
+//! IP addresses, so requests can't make the server send requests into its own network. The host is
+//! resolved again before each delivery, and the delivery connects to the address that was checked.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use alloy_primitives::{hex, B256};
+use anyhow::{anyhow, bail, Result};
+use hmac::{Hmac, Mac};
+use log::{info, warn};
+use op_succinct_host_utils::config::{env_var, redact_url, Url};
+use sha2::Sha256;
+use sp1_sdk::network::proto::network::FulfillmentStatus;
+
+use crate::{
+    reconciler::ProofTracker, ProofKind, ProofStatus, ProofWebhookPayload, TerminalStatus,
+};
+
+/// The header carrying the signature of a webhook's body.
+pub const SIGNATURE_HEADER: &str = "X-OP-Succinct-Signature";
+
+/// How long to wait for the receiver to respond to a delivery.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The delay before the first retry of a failed delivery, doubled for every further retry.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Configures the signing and delivery of proof webhooks.
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// The key the payloads are signed with. Requests with a callback URL are rejected if unset.
+    pub secret: Option<String>,
+    /// The number of attempts to deliver a payload before giving up.
+    pub max_attempts: u32,
+    /// The hosts callback URLs may point to. Requests with a callback URL are rejected if empty.
+    pub allowed_hosts: Vec<String>,
+}
+
+impl WebhookConfig {
+    /// Read the webhook config from the environment.
+    ///
+    /// - `WEBHOOK_SECRET`: Default: unset, which disables callbacks.
+    /// - `WEBHOOK_MAX_ATTEMPTS`: Default: 5.
+    /// - `WEBHOOK_HOST_ALLOWLIST`: Default: unset, which disables callbacks. A comma-separated list
+    ///   of host names.
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            secret: env_var("WEBHOOK_SECRET")?,
+            max_attempts: env_var("WEBHOOK_MAX_ATTEMPTS")?.unwrap_or(5).max(1),
+            allowed_hosts: parse_hosts(
+                &env_var::<String>("WEBHOOK_HOST_ALLOWLIST")?.unwrap_or_default(),
+            ),
+        })
+    }
+
+    /// The host allowlist for logging.
+    pub fn allowlist_summary(&self) -> Option<String> {
+        (!self.allowed_hosts.is_empty()).then(|| self.allowed_hosts.join(","))
+    }
+}
+
+fn parse_hosts(hosts: &str) -> Vec<String> {
+    hosts
+        .split(',')
+        .map(|host| host.trim().to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect()
+}
+
+/// Whether an address is publicly routable: not loopback, private, link-local, shared (CGNAT),
+/// unspecified, multicast or reserved for documentation.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                // 100.64.0.0/10, shared address space.
+                || (a == 100 && (b & 0xc0) == 64)
+                // 0.0.0.0/8, "this network".
+                || a == 0)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(ip));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // fc00::/7, unique local.
+                || (first & 0xfe00) == 0xfc00
+                // fe80::/10, link-local.
+                || (first & 0xffc0) == 0xfe80
+                // 2001:db8::/32, documentation.
+                || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+        }
+    }
+}
+
+/// Resolve the host of a callback URL, rejecting it unless every address it resolves to is public.
+/// Returns the address to connect to.
+async fn resolve_callback_host(url: &Url) -> Result<SocketAddr> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("Invalid callback_url: missing host"))?;
+    // IPv6 literals are bracketed in URLs.
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("Invalid callback_url: missing port"))?;
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| anyhow!("Failed to resolve the callback_url host {host}: {e}"))?
+        .collect();
+    if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        bail!(
+            "callback_url host {host} resolves to {}, which is not a public address",
+            addr.ip()
+        );
+    }
+    addrs
+        .first()
+        .copied()
+        .ok_or_else(|| anyhow!("callback_url host {host} doesn't resolve to any address"))
+}
+
+/// The hex-encoded HMAC-SHA256 of a body, as sent in the [`SIGNATURE_HEADER`] after `sha256=`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Check the [`SIGNATURE_HEADER`] of a webhook, for receivers written in Rust.
+pub fn verify(secret: &str, body: &[u8], signature_header: &str) -> bool {
+    let Some(signature) = signature_header
+        .strip_prefix("sha256=")
+        .and_then(|signature| hex::decode(signature).ok())
+    else {
+        return false;
+    };
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// A callback registered by a proof request.
+#[derive(Debug, Clone)]
+struct Callback {
+    url: Url,
+    kind: ProofKind,
+}
+
+/// The callbacks of the proofs that haven't settled yet.
+pub struct Webhooks {
+    config: WebhookConfig,
+    callbacks: Mutex<HashMap<B256, Vec<Callback>>>,
+}
+
+impl Webhooks {
+    pub fn new(config: WebhookConfig) -> Self {
+        Self {
+            config,
+            callbacks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Parse the callback URL of a proof request, rejecting it if callbacks are disabled or its
+    /// host is not in the allowlist.
+    pub fn parse_callback_url(&self, callback_url: &str) -> Result<Url> {
+        if self.config.secret.is_none() {
+            bail!("callback_url requires the server to set WEBHOOK_SECRET");
+        }
+        let url: Url = callback_url
+            .parse()
+            .map_err(|e| anyhow!("Invalid callback_url: {e}"))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!("Invalid callback_url: expected an http or https URL");
+        }
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+        if !self.config.allowed_hosts.contains(&host) {
+            bail!("callback_url host {host} is not in the server's WEBHOOK_HOST_ALLOWLIST");
+        }
+        Ok(url)
+    }
+
+    /// Parse the callback URL of a proof request like [`Self::parse_callback_url`], and check that
+    /// its host resolves to public addresses.
+    pub async fn check_callback_url(&self, callback_url: &str) -> Result<Url> {
+        let url = self.parse_callback_url(callback_url)?;
+        resolve_callback_host(&url).await?;
+        Ok(url)
+    }
+
+    /// Notify `url` once a proof settles, right away if it already has. Requests sharing a proof
+    /// may each register a callback.
+    pub fn register(
+        self: &Arc<Self>,
+        tracker: &ProofTracker,
+        proof_id: B256,
+        kind: ProofKind,
+        url: Url,
+    ) {
+        let callback = Callback { url, kind };
+        // Held while checking the status, so a proof settling concurrently can't miss the callback.
+        let mut callbacks = self.callbacks.lock().unwrap();
+        match settled_payload(tracker, proof_id, kind) {
+            Some(payload) => {
+                drop(callbacks);
+                self.deliver(callback.url, payload);
+            }
+            None => callbacks.entry(proof_id).or_default().push(callback),
+        }
+    }
+
+    /// Send the callbacks of a proof if it has settled. Called by the reconciler whenever a proof
+    /// reaches a terminal status.
+    pub fn proof_settled(self: &Arc<Self>, tracker: &ProofTracker, proof_id: B256) {
+        let mut callbacks = self.callbacks.lock().unwrap();
+        let settled = tracker
+            .status(&proof_id)
+            .is_some_and(|status| terminal_status(&status).is_some());
+        if !settled {
+            return;
+        }
+        let Some(registered) = callbacks.remove(&proof_id) else {
+            return;
+        };
+        drop(callbacks);
+
+        for callback in registered {
+            if let Some(payload) = settled_payload(tracker, proof_id, callback.kind) {
+                self.deliver(callback.url, payload);
+            }
+        }
+    }
+
+    /// Deliver a payload in the background, retrying with exponential backoff.
+    fn deliver(self: &Arc<Self>, url: Url, payload: ProofWebhookPayload) {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let Some(secret) = &this.config.secret else {
+                return;
+            };
+            let body = match serde_json::to_vec(&payload) {
+                Ok(body) => body,
+                Err(e) => {
+                    warn!(
+                        "Failed to encode the webhook of proof {}: {}",
+                        payload.proof_id, e
+                    );
+                    return;
+                }
+            };
+            let signature = format!("sha256={}", sign(secret, &body));
+
+            let mut delay = INITIAL_RETRY_DELAY;
+            for attempt in 1..=this.config.max_attempts {
+                let result = match client_for(&url).await {
+                    Ok(client) => client
+                        .post(url.clone())
+                        .header(reqwest::header::CONTENT_TYPE, "application/json")
+                        .header(SIGNATURE_HEADER, &signature)
+                        .body(body.clone())
+                        .send()
+                        .await
+                        .and_then(|response| response.error_for_status())
+                        .map_err(Into::into),
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(_) => {
+                        info!(
+                            "Delivered the webhook of proof {} to {}",
+                            payload.proof_id,
+                            redact_url(&url)
+                        );
+                        return;
+                    }
+                    Err(e) => warn!(
+                        "Failed to deliver the webhook of proof {} to {} (attempt {}/{}): {}",
+                        payload.proof_id,
+                        redact_url(&url),
+                        attempt,
+                        this.config.max_attempts,
+                        e
+                    ),
+                }
+                if attempt < this.config.max_attempts {
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+            }
+        });
+    }
+}
+
+/// A client delivering to a callback URL, connecting to the address its host resolved to once
+/// checked to be public, so the host can't be rebound to an internal address in between.
+async fn client_for(url: &Url) -> Result<reqwest::Client> {
+    let addr = resolve_callback_host(url).await?;
+    let mut builder = reqwest::Client::builder().timeout(DELIVERY_TIMEOUT);
+    if let Some(host) = url.host_str() {
+        builder = builder.resolve(host, addr);
+    }
+    Ok(builder.build()?)
+}
+
+/// The payload of a proof's webhook, if the proof has settled.
+fn settled_payload(
+    tracker: &ProofTracker,
+    proof_id: B256,
+    kind: ProofKind,
+) -> Option<ProofWebhookPayload> {
+    let status = terminal_status(&tracker.status(&proof_id)?)?;
+    Some(ProofWebhookPayload {
+        proof_id,
+        kind,
+        status,
+        public_values_hash: match status {
+            TerminalStatus::Fulfilled => tracker.public_values_hash(&proof_id),
+            TerminalStatus::Unfulfillable => None,
+        },
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs(),
+    })
+}
+
+fn terminal_status(status: &ProofStatus) -> Option<TerminalStatus> {
+    if status.fulfillment_status == FulfillmentStatus::Fulfilled as i32 {
+        Some(TerminalStatus::Fulfilled)
+    } else if status.fulfillment_status == FulfillmentStatus::Unfulfillable as i32 {
+        Some(TerminalStatus::Unfulfillable)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let body = br#"{"proof_id":"0x01"}"#;
+        let signature = format!("sha256={}", sign("secret", body));
+        assert!(verify("secret", body, &signature));
+        assert!(!verify("other secret", body, &signature));
+        assert!(!verify("secret", br#"{"proof_id":"0x02"}"#, &signature));
+        assert!(!verify("secret", body, &signature[7..]));
+    }
+
+    #[test]
+    fn test_parse_callback_url() {
+        let disabled = Webhooks::new(WebhookConfig {
+            secret: None,
+            max_attempts: 1,
+            allowed_hosts: parse_hosts("relayer.example"),
+        });
+        assert!(disabled
+            .parse_callback_url("https://relayer.example/proofs")
+            .is_err());
+
+        let webhooks = Webhooks::new(WebhookConfig {
+            secret: Some("secret".to_string()),
+            max_attempts: 1,
+            allowed_hosts: parse_hosts(" Relayer.example, 127.0.0.1"),
+        });
+        assert!(webhooks
+            .parse_callback_url("https://relayer.example/proofs")
+            .is_ok());
+        assert!(webhooks
+            .parse_callback_url("https://RELAYER.example:8443/proofs")
+            .is_ok());
+        assert!(webhooks
+            .parse_callback_url("https://other.example/proofs")
+            .is_err());
+        assert!(webhooks
+            .parse_callback_url("ftp://relayer.example")
+            .is_err());
+        assert!(webhooks.parse_callback_url("not a url").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_callback_url_rejects_internal_addresses() {
+        let webhooks = Webhooks::new(WebhookConfig {
+            secret: Some("secret".to_string()),
+            max_attempts: 1,
+            allowed_hosts: parse_hosts("127.0.0.1,localhost,[::1]"),
+        });
+        // Allowlisted, but resolving to loopback addresses.
+        for url in [
+            "http://127.0.0.1:3000/proofs",
+            "http://localhost/proofs",
+            "http://[::1]/proofs",
+        ] {
+            let err = webhooks.check_callback_url(url).await.unwrap_err();
+            assert!(err.to_string().contains("not a public address"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in ["8.8.8.8", "100.128.0.1", "2606:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+}
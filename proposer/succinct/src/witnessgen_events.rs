@@ -0,0 +1,73 @@
+//! The stream of witness generation progress served at `GET /witnessgen/events`.
+//!
+//! Every [`EVENT_INTERVAL`], the server sends one `witnessgen_progress` server-sent event per
+//! witness generation run in flight, with the run's current block, the preimages fetched so far and
+//! the estimated time remaining.
+
+use std::{convert::Infallible, time::Duration};
+
+use axum::response::sse::Event;
+use futures::{stream, Stream, StreamExt};
+use op_succinct_host_utils::progress::{InFlightRuns, ProgressSnapshot};
+use serde::Serialize;
+
+/// How often the progress of the runs in flight is sent.
+pub const EVENT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The data of a `witnessgen_progress` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct WitnessGenProgressEvent {
+    /// Identifies the run across events, as several runs can cover the same blocks.
+    pub run_id: u64,
+    #[serde(flatten)]
+    pub progress: ProgressSnapshot,
+}
+
+fn progress_events(runs: &InFlightRuns) -> Vec<Event> {
+    runs.snapshots()
+        .into_iter()
+        .filter_map(|(run_id, progress)| {
+            Event::default()
+                .event("witnessgen_progress")
+                .json_data(WitnessGenProgressEvent { run_id, progress })
+                .ok()
+        })
+        .collect()
+}
+
+/// The progress events of the runs in flight, every [`EVENT_INTERVAL`].
+pub fn witnessgen_event_stream(
+    runs: InFlightRuns,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    let interval = tokio::time::interval(EVENT_INTERVAL);
+    stream::unfold((interval, runs), |(mut interval, runs)| async move {
+        interval.tick().await;
+        let events = progress_events(&runs);
+        Some((events, (interval, runs)))
+    })
+    .flat_map(|events| stream::iter(events.into_iter().map(Ok)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use op_succinct_host_utils::progress::WitnessGenProgress;
+
+    #[test]
+    fn test_progress_event() {
+        let runs = InFlightRuns::default();
+        assert!(progress_events(&runs).is_empty());
+
+        let run = runs.track(WitnessGenProgress::new(100, 110));
+        assert_eq!(progress_events(&runs).len(), 1);
+
+        let event = WitnessGenProgressEvent {
+            run_id: run.id(),
+            progress: runs.snapshots()[0].1.clone(),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["run_id"], run.id());
+        assert_eq!(json["current_block"], 100);
+        assert_eq!(json["total_blocks"], 10);
+    }
+}
@@ -0,0 +1,137 @@
+//! The propose-and-verify loop against contracts deployed on Anvil, with mock proofs. Requires
+//! `anvil` on the `PATH` and the contracts built with `forge build`. Run with
+//! `cargo test -p op-succinct-proposer --features e2e`.
+#![cfg(feature = "e2e")]
+
+use alloy_network::EthereumWallet;
+use alloy_node_bindings::Anvil;
+use alloy_primitives::{Bytes, B256, U256};
+use alloy_provider::{Provider, ProviderBuilder};
+use alloy_rpc_types_eth::{BlockNumberOrTag, BlockTransactionsKind};
+use alloy_signer_local::PrivateKeySigner;
+use anyhow::Result;
+use op_succinct_proposer::{
+    contract::OPSuccinctL2OutputOracle,
+    e2e::{deploy_mock_verifier, deploy_test_l2oo, TestL2OOConfig, MOCK_PROOF},
+    submission::propose_l2_output_with_checkpoint,
+    tx_manager::{PriorityFeeStrategy, TxManager, TxManagerConfig},
+};
+
+fn tx_manager_config() -> TxManagerConfig {
+    TxManagerConfig {
+        priority_fee: PriorityFeeStrategy::Estimate,
+        base_fee_multiplier: 2,
+        max_fee_per_gas: None,
+        resubmit_after_blocks: 3,
+        fee_bump_percent: 10,
+        max_resubmissions: 10,
+    }
+}
+
+#[tokio::test]
+async fn test_e2e_propose_with_mock_verifier() -> Result<()> {
+    // Blocks are mined every second, so proposals get the confirmations the tx manager waits for.
+    let anvil = Anvil::new().block_time(1).spawn();
+    let account = anvil.addresses()[0];
+    let signer = PrivateKeySigner::from(anvil.keys()[0].clone());
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer))
+        .on_http(anvil.endpoint_url());
+
+    // Start the L2 chain an hour in the past, so the proposed L2 blocks aren't in the future.
+    let latest = provider
+        .get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Hashes)
+        .await?
+        .expect("latest block");
+    let verifier = deploy_mock_verifier(&provider).await?;
+    let config = TestL2OOConfig::new(account, verifier, latest.header.timestamp - 3600);
+    let l2oo = OPSuccinctL2OutputOracle::new(
+        deploy_test_l2oo(&provider, account, &config).await?,
+        provider.clone(),
+    );
+    let tx_manager = TxManager::new(provider.clone(), account, tx_manager_config());
+
+    for i in 1..=3u64 {
+        let output_root = B256::repeat_byte(i as u8 + 0x10);
+        let l2_block_number = l2oo.nextBlockNumber().call().await?._0.to::<u64>();
+        let l1_block_number = provider.get_block_number().await?;
+
+        let inclusion = propose_l2_output_with_checkpoint(
+            &l2oo,
+            &tx_manager,
+            output_root,
+            l2_block_number,
+            l1_block_number,
+            MOCK_PROOF,
+        )
+        .await?;
+        assert!(inclusion.receipt.status());
+
+        assert_eq!(
+            l2oo.latestBlockNumber().call().await?._0,
+            U256::from(l2_block_number)
+        );
+        let output = l2oo.getL2Output(U256::from(i)).call().await?._0;
+        assert_eq!(output.outputRoot, output_root);
+        assert_eq!(
+            l2oo.historicBlockHashes(U256::from(l1_block_number))
+                .call()
+                .await?
+                ._0,
+            provider
+                .get_block_by_number(l1_block_number.into(), BlockTransactionsKind::Hashes)
+                .await?
+                .expect("checkpointed block")
+                .header
+                .hash
+        );
+    }
+    assert_eq!(
+        l2oo.latestBlockNumber().call().await?._0,
+        U256::from(3 * config.submission_interval)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_e2e_rejected_proof_is_not_submitted() -> Result<()> {
+    let anvil = Anvil::new().block_time(1).spawn();
+    let account = anvil.addresses()[0];
+    let signer = PrivateKeySigner::from(anvil.keys()[0].clone());
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer))
+        .on_http(anvil.endpoint_url());
+
+    let latest = provider
+        .get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Hashes)
+        .await?
+        .expect("latest block");
+    let verifier = deploy_mock_verifier(&provider).await?;
+    let config = TestL2OOConfig::new(account, verifier, latest.header.timestamp - 3600);
+    let l2oo = OPSuccinctL2OutputOracle::new(
+        deploy_test_l2oo(&provider, account, &config).await?,
+        provider.clone(),
+    );
+    let tx_manager = TxManager::new(provider.clone(), account, tx_manager_config());
+
+    // The mock verifier only accepts empty proofs, so the simulation fails before sending.
+    let nonce = provider.get_transaction_count(account).await?;
+    let result = propose_l2_output_with_checkpoint(
+        &l2oo,
+        &tx_manager,
+        B256::repeat_byte(0x10),
+        config.submission_interval,
+        provider.get_block_number().await?,
+        Bytes::from_static(&[1, 2, 3]),
+    )
+    .await;
+    assert!(result.is_err());
+    assert_eq!(provider.get_transaction_count(account).await?, nonce);
+    assert_eq!(
+        l2oo.latestBlockNumber().call().await?._0,
+        U256::from(config.starting_block_number)
+    );
+
+    Ok(())
+}
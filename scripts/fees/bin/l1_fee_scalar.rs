@@ -4,7 +4,7 @@ use alloy_primitives::U256;
 use anyhow::Result;
 use clap::Parser;
 use op_succinct_fees::aggregate_fee_data;
-use op_succinct_host_utils::fetcher::OPSuccinctDataFetcher;
+use op_succinct_host_utils::fetcher::{OPSuccinctDataFetcher, RunContext};
 
 #[derive(Parser)]
 struct Args {
@@ -22,7 +22,7 @@ struct Args {
 async fn main() -> Result<()> {
     let args = Args::parse();
     dotenv::from_filename(args.env_file).ok();
-    let fetcher = OPSuccinctDataFetcher::default();
+    let fetcher = OPSuccinctDataFetcher::new(RunContext::Dev)?;
 
     let start_time = Instant::now();
     let (fee_data, modified_fee_data) = tokio::join!(
@@ -2,7 +2,11 @@ use alloy_primitives::B256;
 use anyhow::Result;
 use cargo_metadata::MetadataCommand;
 use clap::Parser;
-use op_succinct_client_utils::{boot::BootInfoStruct, types::u32_to_u8};
+use op_succinct_client_utils::{
+    boot::BootInfoStruct,
+    public_values::{decode_boot_infos, decode_range_intermediate_outputs, PublicValuesVersion},
+    types::{u32_to_u8, IntermediateOutput},
+};
 use op_succinct_host_utils::{
     fetcher::{OPSuccinctDataFetcher, RunContext},
     get_agg_proof_stdin,
@@ -29,17 +33,25 @@ struct Args {
     env_file: String,
 }
 
+/// The range proofs to aggregate, with the public values they commit to.
+struct AggregationProofData {
+    proofs: Vec<SP1Proof>,
+    boot_infos: Vec<BootInfoStruct>,
+    range_public_values_version: PublicValuesVersion,
+    range_intermediate_outputs: Option<Vec<Vec<IntermediateOutput>>>,
+}
+
 /// Load the aggregation proof data.
 fn load_aggregation_proof_data(
     proof_names: Vec<String>,
     range_vkey: &SP1VerifyingKey,
-) -> (Vec<SP1Proof>, Vec<BootInfoStruct>) {
+) -> AggregationProofData {
     let metadata = MetadataCommand::new().exec().unwrap();
     let workspace_root = metadata.workspace_root;
     let proof_directory = format!("{}/data/fetched_proofs", workspace_root);
 
     let mut proofs = Vec::with_capacity(proof_names.len());
-    let mut boot_infos = Vec::with_capacity(proof_names.len());
+    let mut public_values = Vec::with_capacity(proof_names.len());
 
     let prover = ProverClient::builder().cpu().build();
 
@@ -48,19 +60,26 @@ fn load_aggregation_proof_data(
         if fs::metadata(&proof_path).is_err() {
             panic!("Proof file not found: {}", proof_path);
         }
-        let mut deserialized_proof =
+        let deserialized_proof =
             SP1ProofWithPublicValues::load(proof_path).expect("loading proof failed");
         prover
             .verify(&deserialized_proof, range_vkey)
             .expect("proof verification failed");
         proofs.push(deserialized_proof.proof);
-
-        // The public values are the BootInfoStruct.
-        let boot_info = deserialized_proof.public_values.read();
-        boot_infos.push(boot_info);
+        public_values.push(deserialized_proof.public_values);
     }
 
-    (proofs, boot_infos)
+    // The public values start with the encoded BootInfoStruct.
+    let (range_public_values_version, boot_infos) =
+        decode_boot_infos(public_values.iter().map(|pv| pv.as_slice())).unwrap();
+    let range_intermediate_outputs =
+        decode_range_intermediate_outputs(public_values.iter().map(|pv| pv.as_slice())).unwrap();
+    AggregationProofData {
+        proofs,
+        boot_infos,
+        range_public_values_version,
+        range_intermediate_outputs,
+    }
 }
 
 // Execute the OP Succinct program for a single block.
@@ -77,7 +96,12 @@ async fn main() -> Result<()> {
 
     let (_, vkey) = prover.setup(RANGE_ELF);
 
-    let (proofs, boot_infos) = load_aggregation_proof_data(args.proofs, &vkey);
+    let AggregationProofData {
+        proofs,
+        boot_infos,
+        range_public_values_version,
+        range_intermediate_outputs,
+    } = load_aggregation_proof_data(args.proofs, &vkey);
 
     let header = fetcher.get_latest_l1_head_in_batch(&boot_infos).await?;
     let headers = fetcher
@@ -89,8 +113,17 @@ async fn main() -> Result<()> {
         "Range ELF Verification Key Commitment: {}",
         multi_block_vkey_b256
     );
-    let stdin =
-        get_agg_proof_stdin(proofs, boot_infos, headers, &vkey, header.hash_slow()).unwrap();
+    let stdin = get_agg_proof_stdin(
+        proofs,
+        boot_infos,
+        range_public_values_version,
+        range_intermediate_outputs,
+        headers,
+        &vkey,
+        header.hash_slow(),
+        Vec::new(),
+    )
+    .unwrap();
 
     let (agg_pk, agg_vk) = prover.setup(AGG_ELF);
     println!("Aggregate ELF Verification Key: {:?}", agg_vk.vk.bytes32());
@@ -2,8 +2,11 @@ use anyhow::Result;
 use clap::Parser;
 use op_succinct_host_utils::{
     block_range::get_validated_block_range,
+    datadir::LocalNodes,
     fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
-    get_proof_stdin, start_server_and_native_client,
+    get_proof_stdin,
+    l2_node::select_witness_strategy,
+    start_server_and_native_client,
     stats::ExecutionStats,
     ProgramType,
 };
@@ -20,6 +23,9 @@ async fn main() -> Result<()> {
     dotenv::from_path(&args.env_file)?;
     utils::setup_logger();
 
+    // Serve the L1 and L2 RPCs from database snapshots if `L1_DATADIR` or `L2_DATADIR` is set.
+    let _local_nodes = LocalNodes::start_from_env().await?;
+    select_witness_strategy(args.l2_node_kind).await?;
     let data_fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
 
     let cache_mode = if args.use_cache {
@@ -58,21 +64,14 @@ async fn main() -> Result<()> {
         // Generate proofs in compressed mode for aggregation verification.
         let proof = prover.prove(&pk, &sp1_stdin).compressed().run().unwrap();
 
-        // Create a proof directory for the chain ID if it doesn't exist.
-        let proof_dir = format!(
-            "data/{}/proofs",
-            data_fetcher.get_l2_chain_id().await.unwrap()
+        // Save the proof to the range's directory in the workspace.
+        let proof_path = data_fetcher.workspace()?.proof_path(
+            data_fetcher.get_l2_chain_id().await?,
+            l2_start_block,
+            l2_end_block,
         );
-        if !std::path::Path::new(&proof_dir).exists() {
-            fs::create_dir_all(&proof_dir).unwrap();
-        }
-        // Save the proof to the proof directory corresponding to the chain ID.
-        proof
-            .save(format!(
-                "{}/{}-{}.bin",
-                proof_dir, l2_start_block, l2_end_block
-            ))
-            .expect("saving proof failed");
+        fs::create_dir_all(proof_path.parent().unwrap())?;
+        proof.save(proof_path).expect("saving proof failed");
     } else {
         let l2_chain_id = data_fetcher.get_l2_chain_id().await?;
 
@@ -94,16 +93,12 @@ async fn main() -> Result<()> {
 
         println!("Execution Stats: \n{:?}", stats);
 
-        // Create the report directory if it doesn't exist.
-        let report_dir = format!("execution-reports/multi/{}", l2_chain_id);
-        if !std::path::Path::new(&report_dir).exists() {
-            fs::create_dir_all(&report_dir)?;
-        }
-
-        let report_path = format!(
-            "execution-reports/multi/{}/{}-{}.csv",
-            l2_chain_id, l2_start_block, l2_end_block
+        let report_path = data_fetcher.workspace()?.execution_report_path(
+            l2_chain_id,
+            l2_start_block,
+            l2_end_block,
         );
+        fs::create_dir_all(report_path.parent().unwrap())?;
 
         // Write to CSV.
         let mut csv_writer = csv::Writer::from_path(report_path)?;
@@ -1,7 +1,10 @@
 use std::time::{Duration, Instant};
 
 use anyhow::{Ok, Result};
-use op_succinct_host_utils::fetcher::{BlockInfo, OPSuccinctDataFetcher};
+use op_succinct_host_utils::{
+    fetcher::{BlockInfo, OPSuccinctDataFetcher},
+    panic_report::PanicReportCollector,
+};
 use sp1_sdk::{ExecutionReport, ProverClient, SP1Stdin};
 
 pub const DEFAULT_RANGE: u64 = 5;
@@ -19,7 +22,12 @@ pub async fn execute_multi(
 ) -> Result<(Vec<BlockInfo>, ExecutionReport, Duration)> {
     let start_time = Instant::now();
     let prover = ProverClient::builder().mock().build();
-    let (_, report) = prover.execute(RANGE_ELF, &sp1_stdin).run().unwrap();
+    let panic_report = PanicReportCollector::default();
+    let (_, report) = prover
+        .execute(RANGE_ELF, &sp1_stdin)
+        .with_hook(PanicReportCollector::FD, panic_report.hook())
+        .run()
+        .map_err(|e| panic_report.explain(e))?;
     let execution_duration = start_time.elapsed();
 
     let block_data = data_fetcher
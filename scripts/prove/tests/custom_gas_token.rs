@@ -0,0 +1,46 @@
+use anyhow::Result;
+use op_succinct_host_utils::{
+    block_range::get_rolling_block_range,
+    fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
+    get_proof_stdin, start_server_and_native_client, ProgramType,
+};
+use op_succinct_prove::{execute_multi, DEFAULT_RANGE, ONE_HOUR};
+
+/// Executes a range on a custom gas token chain. The RPCs for the chain are read from
+/// `.env.custom-gas-token`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "requires a custom gas token chain configured in .env.custom-gas-token"]
+async fn execute_custom_gas_token_batch() -> Result<()> {
+    dotenv::from_filename(".env.custom-gas-token")?;
+
+    let data_fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
+
+    let (l2_start_block, l2_end_block) =
+        get_rolling_block_range(&data_fetcher, ONE_HOUR, DEFAULT_RANGE).await?;
+
+    let token = data_fetcher.get_gas_paying_token(l2_start_block).await?;
+    assert!(token.is_some(), "L2 chain does not use a custom gas token");
+
+    let host_args = data_fetcher
+        .get_host_args(
+            l2_start_block,
+            l2_end_block,
+            None,
+            ProgramType::Multi,
+            CacheMode::DeleteCache,
+        )
+        .await?;
+
+    let oracle = start_server_and_native_client(host_args).await?;
+    let sp1_stdin = get_proof_stdin(oracle)?;
+
+    let (block_data, report, _) =
+        execute_multi(&data_fetcher, sp1_stdin, l2_start_block, l2_end_block).await?;
+    assert_eq!(block_data.len() as u64, l2_end_block - l2_start_block);
+    println!(
+        "Executed custom gas token range in {} cycles",
+        report.total_instruction_count()
+    );
+
+    Ok(())
+}
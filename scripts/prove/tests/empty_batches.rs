@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use kona_preimage::{
+    errors::PreimageOracleResult, HintWriterClient, PreimageKey, PreimageOracleClient,
+};
+use kona_proof::FlushableCache;
+use op_succinct_client_utils::{
+    boot::{DerivationMode, DERIVATION_MODE_KEY},
+    client::run_opsuccinct_client_with_intermediate_outputs,
+    precompiles::zkvm_handle_register,
+    InMemoryOracle,
+};
+use op_succinct_host_utils::{
+    fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
+    start_server_and_native_client, OPSuccinctHost, ProgramType,
+};
+
+/// A witness that tells the client to run the full derivation pipeline, whichever mode it was
+/// generated with.
+#[derive(Debug)]
+struct FullDerivation(InMemoryOracle);
+
+impl FullDerivation {
+    fn is_mode_key(key: PreimageKey) -> bool {
+        key == PreimageKey::new_local(DERIVATION_MODE_KEY)
+    }
+}
+
+#[async_trait]
+impl PreimageOracleClient for FullDerivation {
+    async fn get(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        if Self::is_mode_key(key) {
+            return Ok(vec![DerivationMode::Full as u8]);
+        }
+        self.0.get(key).await
+    }
+
+    async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<()> {
+        if Self::is_mode_key(key) {
+            buf.copy_from_slice(&[DerivationMode::Full as u8]);
+            return Ok(());
+        }
+        self.0.get_exact(key, buf).await
+    }
+}
+
+#[async_trait]
+impl HintWriterClient for FullDerivation {
+    async fn write(&self, _hint: &str) -> PreimageOracleResult<()> {
+        Ok(())
+    }
+}
+
+impl FlushableCache for FullDerivation {
+    fn flush(&self) {}
+}
+
+/// Replay the client natively against a witness, returning the number and output root of every
+/// block it derives.
+async fn replay<O>(oracle: O) -> Result<Vec<(u64, B256)>>
+where
+    O: PreimageOracleClient + HintWriterClient + FlushableCache + Send + Sync + std::fmt::Debug,
+{
+    let (_, outputs) = run_opsuccinct_client_with_intermediate_outputs(
+        Arc::new(oracle),
+        Some(zkvm_handle_register),
+    )
+    .await?;
+    Ok(outputs
+        .into_iter()
+        .map(|output| (output.l2BlockNumber, output.outputRoot))
+        .collect())
+}
+
+fn env_block(name: &str) -> Result<u64> {
+    std::env::var(name)
+        .map_err(|_| anyhow!("{name} is not set"))?
+        .parse()
+        .map_err(|e| anyhow!("Invalid {name}: {e}"))
+}
+
+/// Derives a span whose blocks all come from forced empty batches both with the empty batch fast
+/// path and with the full derivation pipeline, and checks that every block has the same output
+/// root. Witness generation checks the span's claimed output root against the L2 node in both
+/// modes.
+///
+/// The span is read from `EMPTY_BATCHES_START_BLOCK` and `EMPTY_BATCHES_END_BLOCK`. No batches may
+/// be posted for it, and the sequencing window of its last block's parent must have expired by the
+/// L1 head, e.g. on a devnet whose batcher was stopped for longer than the sequencing window. The
+/// client is run natively, so this doesn't depend on the range ELF being rebuilt.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "requires a span derived from forced empty batches, configured in .env"]
+async fn empty_batches_match_full_derivation() -> Result<()> {
+    dotenv::dotenv()?;
+    let l2_start_block = env_block("EMPTY_BATCHES_START_BLOCK")?;
+    let l2_end_block = env_block("EMPTY_BATCHES_END_BLOCK")?;
+
+    let data_fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
+    let host_args = data_fetcher
+        .get_host_args(
+            l2_start_block,
+            l2_end_block,
+            None,
+            ProgramType::Multi,
+            CacheMode::DeleteCache,
+        )
+        .await?;
+    assert_eq!(
+        host_args.derivation_mode,
+        DerivationMode::EmptyBatches,
+        "Batches were posted for the span"
+    );
+
+    // The outputs are replayed with their fields, which the witnesses must hold.
+    let host_args = OPSuccinctHost {
+        intermediate_outputs: true,
+        ..host_args
+    };
+    let fast = start_server_and_native_client(host_args.clone()).await?;
+    let full = start_server_and_native_client(OPSuccinctHost {
+        derivation_mode: DerivationMode::Full,
+        ..host_args
+    })
+    .await?;
+
+    // The fast path doesn't read the receipts of the L1 blocks the pipeline traverses after the
+    // span's last epoch, so full derivation fails on its witness. Had the client fallen back to
+    // the full pipeline, the witness would hold everything it reads.
+    assert!(
+        replay(FullDerivation(fast.clone())).await.is_err(),
+        "The client fell back to full derivation"
+    );
+
+    let fast_outputs = replay(fast).await?;
+    let full_outputs = replay(full).await?;
+    assert_eq!(fast_outputs.len() as u64, l2_end_block - l2_start_block);
+    assert_eq!(fast_outputs, full_outputs);
+    println!(
+        "Derived {} blocks from forced empty batches, matching the full pipeline",
+        fast_outputs.len()
+    );
+
+    Ok(())
+}
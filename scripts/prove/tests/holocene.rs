@@ -0,0 +1,58 @@
+use anyhow::{anyhow, Result};
+use op_succinct_host_utils::{
+    fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
+    get_proof_stdin, start_server_and_native_client, ProgramType,
+};
+use op_succinct_prove::{execute_multi, DEFAULT_RANGE};
+
+/// Generates the witness for and executes the blocks around the chain's Holocene activation, where
+/// derivation switches to Holocene's batch ordering and validity rules. The RPCs are read from
+/// `.env`, and must serve the state at the activation block.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "requires an archive node of a chain that activated Holocene, configured in .env"]
+async fn execute_holocene_activation_batch() -> Result<()> {
+    dotenv::dotenv()?;
+
+    let data_fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
+    let rollup_config = data_fetcher.rollup_config.as_ref().unwrap();
+    let holocene_time = rollup_config
+        .holocene_time
+        .ok_or_else(|| anyhow!("The chain doesn't schedule Holocene"))?;
+
+    // The range starts before the first Holocene block and ends after it.
+    let (_, activation_block) = data_fetcher
+        .find_l2_block_by_timestamp(holocene_time)
+        .await?;
+    let l2_start_block = activation_block - DEFAULT_RANGE;
+    let l2_end_block = activation_block + DEFAULT_RANGE;
+    let start_timestamp = data_fetcher
+        .get_l2_header_by_number(l2_start_block)
+        .await?
+        .timestamp;
+    assert!(!rollup_config.is_holocene_active(start_timestamp));
+
+    let host_args = data_fetcher
+        .get_host_args(
+            l2_start_block,
+            l2_end_block,
+            None,
+            ProgramType::Multi,
+            CacheMode::DeleteCache,
+        )
+        .await?;
+
+    let oracle = start_server_and_native_client(host_args).await?;
+    let sp1_stdin = get_proof_stdin(oracle)?;
+
+    let (block_data, report, _) =
+        execute_multi(&data_fetcher, sp1_stdin, l2_start_block, l2_end_block).await?;
+    assert_eq!(block_data.len() as u64, l2_end_block - l2_start_block);
+    println!(
+        "Executed the Holocene activation range {}-{} in {} cycles",
+        l2_start_block,
+        l2_end_block,
+        report.total_instruction_count()
+    );
+
+    Ok(())
+}
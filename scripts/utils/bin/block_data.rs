@@ -1,7 +1,7 @@
 use anyhow::Result;
 use clap::Parser;
 use log::info;
-use op_succinct_host_utils::fetcher::{BlockInfo, OPSuccinctDataFetcher};
+use op_succinct_host_utils::fetcher::{BlockInfo, OPSuccinctDataFetcher, RunContext};
 use sp1_sdk::utils;
 use std::{
     fs::{self},
@@ -138,7 +138,7 @@ async fn main() -> Result<()> {
     dotenv::from_path(&args.env_file).ok();
     utils::setup_logger();
 
-    let fetcher = OPSuccinctDataFetcher::default();
+    let fetcher = OPSuccinctDataFetcher::new(RunContext::Dev)?;
     let l2_chain_id = fetcher.get_l2_chain_id().await?;
 
     // Confirm that the start and end blocks are valid.
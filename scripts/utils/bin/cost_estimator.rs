@@ -4,11 +4,14 @@ use futures::StreamExt;
 use log::info;
 use op_succinct_host_utils::{
     block_range::{
-        get_rolling_block_range, get_validated_block_range, split_range_based_on_safe_heads,
-        split_range_basic, SpanBatchRange,
+        get_rolling_block_range, get_validated_block_range, snap_ranges_to_epoch_boundaries,
+        split_range_based_on_safe_heads, split_range_basic, SpanBatchRange,
     },
     fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
-    get_proof_stdin, start_server_and_native_client,
+    get_proof_stdin,
+    l2_node::select_witness_strategy,
+    panic_report::PanicReportCollector,
+    start_server_and_native_client,
     stats::ExecutionStats,
     OPSuccinctHost, ProgramType,
 };
@@ -90,19 +93,24 @@ async fn execute_blocks_and_write_stats_csv(
 
     // Execute the program for each block range in parallel.
     execution_inputs.par_iter().for_each(|(sp1_stdin, (range, block_data))| {
-        let result = prover.execute(RANGE_ELF, sp1_stdin).run();
-
-        if let Some(err) = result.as_ref().err() {
-            log::warn!(
-                "Failed to execute blocks {:?} - {:?} because of {:?}. Reduce your `batch-size` if you're running into OOM issues on SP1.",
-                range.start,
-                range.end,
-                err
-            );
-            return;
-        }
-
-        let (_, report) = result.unwrap();
+        let panic_report = PanicReportCollector::default();
+        let result = prover
+            .execute(RANGE_ELF, sp1_stdin)
+            .with_hook(PanicReportCollector::FD, panic_report.hook())
+            .run();
+
+        let report = match result {
+            Ok((_, report)) => report,
+            Err(err) => {
+                log::warn!(
+                    "Failed to execute blocks {:?} - {:?} because of {:?}. Reduce your `batch-size` if you're running into OOM issues on SP1.",
+                    range.start,
+                    range.end,
+                    panic_report.explain(err)
+                );
+                return;
+            }
+        };
 
         let execution_stats = ExecutionStats::new(0, block_data, &report, 0, 0);
 
@@ -190,6 +198,7 @@ async fn main() -> Result<()> {
     dotenv::from_path(&args.env_file).ok();
     utils::setup_logger();
 
+    select_witness_strategy(args.l2_node_kind).await?;
     let data_fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
     let l2_chain_id = data_fetcher.get_l2_chain_id().await?;
 
@@ -209,6 +218,14 @@ async fn main() -> Result<()> {
         split_range_basic(l2_start_block, l2_end_block, args.batch_size)
     };
 
+    // Optionally snap the span boundaries to L1-origin epoch boundaries, so that no epoch is split
+    // across two spans.
+    let split_ranges = if args.epoch_aligned {
+        snap_ranges_to_epoch_boundaries(&data_fetcher, split_ranges).await?
+    } else {
+        split_ranges
+    };
+
     info!(
         "The span batch ranges which will be executed: {:?}",
         split_ranges
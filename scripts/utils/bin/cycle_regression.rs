@@ -0,0 +1,185 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use op_succinct_host_utils::{
+    fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
+    get_proof_stdin,
+    panic_report::PanicReportCollector,
+    start_server_and_native_client, ProgramType,
+};
+use op_succinct_scripts::cycle_baseline::{
+    BlockCycles, CycleBaseline, LabeledBlock, DEFAULT_MAX_REGRESSION_PERCENT,
+};
+use sp1_sdk::{utils, CpuProver, ProverClient};
+
+pub const RANGE_ELF: &[u8] = include_bytes!("../../../elf/range-elf");
+
+/// Executes a curated set of L2 blocks, one at a time, in the SP1 executor and compares the cycles
+/// of each block against a JSON baseline. Fails if any block's cycles increased by more than the
+/// baseline's threshold.
+#[derive(Debug, Clone, Parser)]
+struct CycleRegressionArgs {
+    /// The baseline to compare against.
+    #[clap(long)]
+    baseline: PathBuf,
+    /// The blocks to execute, as `<label>=<block number>`, e.g. `dex-heavy=123456`. Defaults to
+    /// the blocks in the baseline.
+    #[clap(long = "block")]
+    blocks: Vec<LabeledBlock>,
+    /// Write the measured cycles to the baseline instead of comparing against it.
+    #[clap(long)]
+    update: bool,
+    /// The increase in a block's cycles, in percent, above which it is a regression. Defaults to
+    /// the baseline's threshold.
+    #[clap(long)]
+    max_regression_percent: Option<f64>,
+    /// The environment file to use.
+    #[clap(long, default_value = ".env")]
+    env_file: PathBuf,
+}
+
+/// Execute a single L2 block in the range program.
+async fn execute_block(
+    fetcher: &OPSuccinctDataFetcher,
+    prover: &CpuProver,
+    block: &LabeledBlock,
+) -> Result<BlockCycles> {
+    let host_args = fetcher
+        .get_host_args(
+            block.block - 1,
+            block.block,
+            None,
+            ProgramType::Multi,
+            CacheMode::DeleteCache,
+        )
+        .await?;
+    let oracle = start_server_and_native_client(host_args).await?;
+    let stdin = get_proof_stdin(oracle)?;
+
+    let panic_report = PanicReportCollector::default();
+    let (_, report) = prover
+        .execute(RANGE_ELF, &stdin)
+        .with_hook(PanicReportCollector::FD, panic_report.hook())
+        .run()
+        .map_err(|e| panic_report.explain(e))?;
+
+    let block_data = fetcher
+        .get_l2_block_data_range(block.block - 1, block.block)
+        .await?;
+    Ok(BlockCycles {
+        label: block.label.clone(),
+        block: block.block,
+        transactions: block_data.iter().map(|b| b.transaction_count).sum(),
+        gas_used: block_data.iter().map(|b| b.gas_used).sum(),
+        cycles: report.total_instruction_count(),
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = CycleRegressionArgs::parse();
+
+    dotenv::from_path(&args.env_file).ok();
+    utils::setup_logger();
+
+    let fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
+    let l2_chain_id = fetcher.rollup_config.as_ref().unwrap().l2_chain_id;
+    let baseline = if args.baseline.exists() {
+        Some(CycleBaseline::load(&args.baseline)?)
+    } else if args.update {
+        None
+    } else {
+        bail!(
+            "Baseline {} not found. Create it with --update",
+            args.baseline.display()
+        );
+    };
+    if let Some(baseline) = &baseline {
+        if baseline.l2_chain_id != l2_chain_id {
+            bail!(
+                "The baseline is for chain {}, but the RPCs are for chain {}",
+                baseline.l2_chain_id,
+                l2_chain_id
+            );
+        }
+    }
+
+    let blocks = match (&baseline, args.blocks.is_empty()) {
+        (Some(baseline), true) => baseline
+            .blocks
+            .iter()
+            .map(|b| LabeledBlock {
+                label: b.label.clone(),
+                block: b.block,
+            })
+            .collect(),
+        (None, true) => bail!("Pass the blocks to execute with --block"),
+        (_, false) => args.blocks,
+    };
+
+    let prover = ProverClient::builder().cpu().build();
+    let mut measured = Vec::with_capacity(blocks.len());
+    for block in &blocks {
+        let cycles = execute_block(&fetcher, &prover, block).await?;
+        println!(
+            "{} (block {}): {} transactions, {} gas, {} cycles, {:.2} cycles/gas",
+            cycles.label,
+            cycles.block,
+            cycles.transactions,
+            cycles.gas_used,
+            cycles.cycles,
+            cycles.cycles_per_gas()
+        );
+        measured.push(cycles);
+    }
+
+    if args.update {
+        let updated = CycleBaseline {
+            l2_chain_id,
+            max_regression_percent: args
+                .max_regression_percent
+                .or(baseline.map(|b| b.max_regression_percent))
+                .unwrap_or(DEFAULT_MAX_REGRESSION_PERCENT),
+            blocks: measured,
+        };
+        updated.save(&args.baseline)?;
+        println!("Updated the baseline {}", args.baseline.display());
+        return Ok(());
+    }
+
+    let baseline = baseline.unwrap();
+    let max_regression_percent = args
+        .max_regression_percent
+        .unwrap_or(baseline.max_regression_percent);
+    let comparisons = baseline.compare(&measured);
+    let mut regressions = 0;
+    for comparison in &comparisons {
+        let regressed = comparison.is_regression(max_regression_percent);
+        regressions += regressed as usize;
+        println!(
+            "{} (block {}): {} -> {} cycles ({:+.2}%){}",
+            comparison.label,
+            comparison.block,
+            comparison.baseline_cycles,
+            comparison.cycles,
+            comparison.change_percent(),
+            if regressed { " REGRESSION" } else { "" }
+        );
+    }
+    if comparisons.len() < measured.len() {
+        println!(
+            "{} blocks aren't in the baseline and were not compared",
+            measured.len() - comparisons.len()
+        );
+    }
+
+    if regressions > 0 {
+        bail!(
+            "The cycles of {} blocks regressed by more than {}%",
+            regressions,
+            max_regression_percent
+        );
+    }
+    Ok(())
+}
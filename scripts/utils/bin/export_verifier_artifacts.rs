@@ -0,0 +1,123 @@
+use std::{fs, path::PathBuf};
+
+use alloy_primitives::{Address, Bytes, B256};
+use alloy_sol_types::{sol, SolCall, SolValue};
+use anyhow::{anyhow, Result};
+use clap::{Parser, ValueEnum};
+use op_succinct_host_utils::vkeys::{vkey_commitment, VkeyCommitment};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sp1_sdk::{install::try_install_circuit_artifacts, Prover, ProverClient};
+
+pub const AGG_ELF: &[u8] = include_bytes!("../../../elf/aggregation-elf");
+pub const RANGE_ELF: &[u8] = include_bytes!("../../../elf/range-elf");
+
+sol! {
+    function addRoute(address verifier);
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ProofMode {
+    Groth16,
+    Plonk,
+}
+
+impl ProofMode {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Groth16 => "groth16",
+            Self::Plonk => "plonk",
+        }
+    }
+
+    /// The circuit artifacts needed to verify proofs of this mode on another settlement layer.
+    fn artifact_files(self) -> [&'static str; 3] {
+        match self {
+            Self::Groth16 => [
+                "groth16_vk.bin",
+                "SP1VerifierGroth16.sol",
+                "Groth16Verifier.sol",
+            ],
+            Self::Plonk => ["plonk_vk.bin", "SP1VerifierPlonk.sol", "PlonkVerifier.sol"],
+        }
+    }
+}
+
+/// Exports what is needed to deploy an SP1 verifier gateway and verifier on a new settlement
+/// layer: the circuit's verifier key and verifier contracts, the verifier hash the gateway routes
+/// on, the program vkeys, and the ABI-encoded gateway deployment data.
+#[derive(Debug, Clone, Parser)]
+struct ExportVerifierArtifactsArgs {
+    /// The proof mode of the aggregation proofs to verify.
+    #[clap(long, value_enum, default_value = "groth16")]
+    mode: ProofMode,
+    /// The initial owner of the SP1 verifier gateway.
+    #[clap(long)]
+    owner: Address,
+    /// The address of the deployed verifier, to encode the gateway's `addRoute` call for it.
+    #[clap(long)]
+    verifier: Option<Address>,
+    /// The directory to write the artifacts to. Defaults to `verifier-artifacts/{mode}`.
+    #[clap(long)]
+    out_dir: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VerifierArtifacts {
+    proof_mode: &'static str,
+    circuit_version: String,
+    /// The sha256 hash of the verifier key. Proofs are prefixed with its first 4 bytes, which the
+    /// gateway uses to route them to the verifier.
+    verifier_hash: B256,
+    #[serde(flatten)]
+    vkeys: VkeyCommitment,
+    /// The ABI-encoded constructor arguments of `SP1VerifierGateway`.
+    gateway_constructor_args: Bytes,
+    /// The calldata of `SP1VerifierGateway.addRoute` for the deployed verifier.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    add_route_calldata: Option<Bytes>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = ExportVerifierArtifactsArgs::parse();
+    let out_dir = args
+        .out_dir
+        .unwrap_or_else(|| PathBuf::from("verifier-artifacts").join(args.mode.name()));
+    fs::create_dir_all(&out_dir)?;
+
+    // Downloads the circuit artifacts for the SDK's circuit version if they aren't cached.
+    let circuit_dir = try_install_circuit_artifacts(args.mode.name());
+    let circuit_version = circuit_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("Unexpected circuit artifacts path {:?}", circuit_dir))?
+        .to_string();
+    for file in args.mode.artifact_files() {
+        fs::copy(circuit_dir.join(file), out_dir.join(file))?;
+    }
+    let verifier_key = fs::read(circuit_dir.join(args.mode.artifact_files()[0]))?;
+
+    let prover = ProverClient::builder().cpu().build();
+    let (_, range_vk) = prover.setup(RANGE_ELF);
+    let (_, agg_vk) = prover.setup(AGG_ELF);
+
+    let artifacts = VerifierArtifacts {
+        proof_mode: args.mode.name(),
+        circuit_version,
+        verifier_hash: B256::from_slice(&Sha256::digest(&verifier_key)),
+        vkeys: vkey_commitment(&agg_vk, &range_vk, None),
+        gateway_constructor_args: args.owner.abi_encode().into(),
+        add_route_calldata: args
+            .verifier
+            .map(|verifier| addRouteCall { verifier }.abi_encode().into()),
+    };
+
+    let artifacts_path = out_dir.join("artifacts.json");
+    fs::write(&artifacts_path, serde_json::to_string_pretty(&artifacts)?)?;
+    println!("{}", serde_json::to_string_pretty(&artifacts)?);
+    println!("Wrote verifier artifacts to {}", out_dir.display());
+
+    Ok(())
+}
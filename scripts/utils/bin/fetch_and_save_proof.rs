@@ -1,8 +1,7 @@
 use alloy_primitives::{hex, B256};
-use alloy_sol_types::SolValue;
 use anyhow::Result;
 use clap::Parser;
-use op_succinct_client_utils::{boot::BootInfoStruct, AGGREGATION_OUTPUTS_SIZE};
+use op_succinct_client_utils::public_values::{decode_aggregation_outputs, decode_boot_info};
 use sp1_sdk::{
     network::proto::network::{ExecutionStatus, FulfillmentStatus, GetProofRequestStatusResponse},
     ProverClient, SP1ProofWithPublicValues,
@@ -47,7 +46,7 @@ async fn main() -> Result<()> {
     let fulfillment_status = FulfillmentStatus::try_from(status.fulfillment_status).unwrap();
     let _ = ExecutionStatus::try_from(status.execution_status).unwrap();
 
-    let mut proof = match fulfillment_status {
+    let proof = match fulfillment_status {
         FulfillmentStatus::Fulfilled => proof.unwrap(),
         _ => {
             println!("Proof is still pending");
@@ -56,16 +55,14 @@ async fn main() -> Result<()> {
     };
 
     if args.agg_proof {
-        let mut raw_boot_info = [0u8; AGGREGATION_OUTPUTS_SIZE];
-        proof.public_values.read_slice(&mut raw_boot_info);
-        let boot_info = BootInfoStruct::abi_decode(&raw_boot_info, false).unwrap();
+        let (version, outputs) = decode_aggregation_outputs(proof.public_values.as_slice())?;
 
         let proof_bytes = proof.bytes();
         println!("Proof bytes: {:?}", hex::encode(proof_bytes));
-        println!("Boot info: {:?}", boot_info);
+        println!("Aggregation outputs ({:?}): {:?}", version, outputs);
     } else {
-        // Read the BootInfoStruct from the proof
-        let _boot_info: BootInfoStruct = proof.public_values.read();
+        // Check that the proof commits to a BootInfoStruct.
+        decode_boot_info(proof.public_values.as_slice())?;
 
         // Create the proofs directory if it doesn't exist
         let proof_path = "data/fetched_proofs".to_string();
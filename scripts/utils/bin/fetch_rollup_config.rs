@@ -1,12 +1,15 @@
 use alloy_eips::BlockId;
-use alloy_primitives::{hex, Address};
+use alloy_primitives::Address;
 use alloy_signer_local::PrivateKeySigner;
 use anyhow::Result;
-use op_succinct_client_utils::{boot::hash_rollup_config, types::u32_to_u8};
-use op_succinct_host_utils::fetcher::{OPSuccinctDataFetcher, RPCMode, RunContext};
+use op_succinct_client_utils::boot::hash_rollup_config;
+use op_succinct_host_utils::{
+    fetcher::{OPSuccinctDataFetcher, RPCMode, RunContext},
+    vkeys::vkey_commitment,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use sp1_sdk::{HashableKey, Prover, ProverClient};
+use sp1_sdk::{Prover, ProverClient};
 use std::{
     env, fs,
     path::{Path, PathBuf},
@@ -137,10 +140,15 @@ async fn update_l2oo_config() -> Result<()> {
 
     let prover = ProverClient::builder().cpu().build();
     let (_, agg_vkey) = prover.setup(AGG_ELF);
-    let aggregation_vkey = agg_vkey.vk.bytes32();
-
     let (_, range_vkey) = prover.setup(RANGE_ELF);
-    let range_vkey_commitment = format!("0x{}", hex::encode(u32_to_u8(range_vkey.vk.hash_u32())));
+    // In quorum mode, the L2OO is deployed with the commitment to both range programs.
+    let secondary_range_vkey = match env::var("SECONDARY_RANGE_ELF_PATH") {
+        Ok(path) => Some(prover.setup(&fs::read(path)?).1),
+        Err(_) => None,
+    };
+    let commitment = vkey_commitment(&agg_vkey, &range_vkey, secondary_range_vkey.as_ref());
+    let aggregation_vkey = commitment.aggregation_vkey.to_string();
+    let range_vkey_commitment = commitment.range_vkey_commitment.to_string();
 
     let l2oo_config = L2OOConfig {
         challenger,
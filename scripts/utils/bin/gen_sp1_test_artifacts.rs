@@ -5,7 +5,9 @@ use log::info;
 use op_succinct_host_utils::{
     block_range::{get_validated_block_range, split_range_basic},
     fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
-    get_proof_stdin, start_server_and_native_client, ProgramType,
+    get_proof_stdin,
+    l2_node::select_witness_strategy,
+    start_server_and_native_client, ProgramType,
 };
 use op_succinct_scripts::HostExecutorArgs;
 use sp1_sdk::utils;
@@ -23,6 +25,7 @@ async fn main() -> Result<()> {
     dotenv::from_path(&args.env_file).ok();
     utils::setup_logger();
 
+    select_witness_strategy(args.l2_node_kind).await?;
     let data_fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
     let l2_chain_id = data_fetcher.get_l2_chain_id().await?;
 
@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use alloy_primitives::Address;
+use anyhow::{bail, Result};
+use clap::Parser;
+use op_succinct_host_utils::{
+    chain_config::{diff_rollup_config, fetch_on_chain_rollup_config},
+    fetcher::{OPSuccinctDataFetcher, RunContext},
+    rollup_config::{get_rollup_config_path, read_rollup_config_file},
+};
+use sp1_sdk::utils;
+
+/// Compares a rollup config file against the fields recorded on chain: the L1 and L2 chain IDs, the
+/// batch inbox and deposit contract of the `SystemConfig`, and the L1 and L2 genesis blocks. Exits
+/// with an error if any differ.
+#[derive(Debug, Clone, Parser)]
+struct RollupConfigDiffArgs {
+    /// The rollup config file. Defaults to the one saved for the L2 chain under `configs/`.
+    #[clap(long)]
+    rollup_config: Option<PathBuf>,
+    /// The `SystemConfig` contract. Defaults to the one in the rollup config.
+    #[clap(long)]
+    system_config: Option<Address>,
+    /// The environment file to use.
+    #[clap(long, default_value = ".env")]
+    env_file: PathBuf,
+    /// Print the on-chain config and the differences as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = RollupConfigDiffArgs::parse();
+    dotenv::from_path(&args.env_file).ok();
+    utils::setup_logger();
+
+    let fetcher = OPSuccinctDataFetcher::new(RunContext::Dev)?;
+    let rollup_config_path = match args.rollup_config {
+        Some(path) => path,
+        None => get_rollup_config_path(fetcher.get_l2_chain_id().await?, RunContext::Dev)?,
+    };
+    let rollup_config = read_rollup_config_file(&rollup_config_path)?;
+
+    let on_chain = fetch_on_chain_rollup_config(
+        &fetcher.l1_provider,
+        &fetcher.l2_provider,
+        args.system_config
+            .unwrap_or(rollup_config.l1_system_config_address),
+        rollup_config.genesis.l2.number,
+    )
+    .await?;
+    let drift = diff_rollup_config(&rollup_config, &on_chain);
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "on_chain": on_chain,
+                "drift": drift,
+            }))?
+        );
+    } else {
+        println!("On-chain config: {on_chain:#?}");
+        println!(
+            "{} differs from the chain in {} fields",
+            rollup_config_path.display(),
+            drift.len()
+        );
+        for drift in &drift {
+            println!("{drift}");
+        }
+    }
+    if !drift.is_empty() {
+        bail!("The rollup config differs from the chain");
+    }
+    Ok(())
+}
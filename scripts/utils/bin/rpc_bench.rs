@@ -0,0 +1,88 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use alloy_provider::Provider;
+use anyhow::Result;
+use clap::Parser;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use op_succinct_host_utils::fetcher::{OPSuccinctDataFetcher, RunContext};
+use sp1_sdk::utils;
+
+/// Compares the time spent fetching the L1 headers and L2 receipts needed for witness generation
+/// over a range when issuing one request per block versus batched JSON-RPC requests.
+#[derive(Debug, Clone, Parser)]
+struct RpcBenchArgs {
+    /// The start L2 block of the range.
+    #[clap(long)]
+    start: u64,
+    /// The end L2 block of the range.
+    #[clap(long)]
+    end: u64,
+    /// The environment file to use.
+    #[clap(long, default_value = ".env")]
+    env_file: PathBuf,
+}
+
+fn report(name: &str, unbatched: Duration, batched: Duration) {
+    println!(
+        "{name}: unbatched {:.2}s, batched {:.2}s ({:.1}x)",
+        unbatched.as_secs_f64(),
+        batched.as_secs_f64(),
+        unbatched.as_secs_f64() / batched.as_secs_f64()
+    );
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = RpcBenchArgs::parse();
+
+    dotenv::from_path(&args.env_file).ok();
+    utils::setup_logger();
+
+    let fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
+
+    let l1_start = fetcher
+        .l2_block_info_by_number(args.start)
+        .await?
+        .l1_origin
+        .number;
+    let l1_end = fetcher
+        .l2_block_info_by_number(args.end)
+        .await?
+        .l1_origin
+        .number;
+    println!(
+        "L2 blocks {}-{}, L1 blocks {}-{}, batch size {}",
+        args.start, args.end, l1_start, l1_end, fetcher.rpc_batch_size
+    );
+
+    // L1 headers, one request per block.
+    let now = Instant::now();
+    for block_number in l1_start..=l1_end {
+        fetcher.get_l1_header(block_number.into()).await?;
+    }
+    let unbatched = now.elapsed();
+
+    let now = Instant::now();
+    fetcher.fetch_headers_in_range(l1_start, l1_end).await?;
+    report("L1 headers", unbatched, now.elapsed());
+
+    // L2 receipts, one request per block.
+    let now = Instant::now();
+    stream::iter(args.start..=args.end)
+        .map(|block_number| fetcher.l2_provider.get_block_receipts(block_number.into()))
+        .buffered(100)
+        .try_collect::<Vec<_>>()
+        .await?;
+    let unbatched = now.elapsed();
+
+    let now = Instant::now();
+    fetcher
+        .get_l2_receipts_in_range(args.start, args.end)
+        .await?;
+    report("L2 receipts", unbatched, now.elapsed());
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use op_succinct_host_utils::{
+    fetcher::{OPSuccinctDataFetcher, RunContext},
+    system_config::get_system_config_updates,
+};
+use sp1_sdk::utils;
+
+/// Lists the SystemConfig updates (batcher, fee scalars, gas limit, EIP-1559 parameters) emitted in
+/// the L1 blocks a range derives from. Derivation applies them from the L1 block they're emitted
+/// in, so a range listing updates straddles a config change.
+#[derive(Debug, Clone, Parser)]
+struct SystemConfigUpdatesArgs {
+    /// The start L2 block of the range.
+    #[clap(long)]
+    start: u64,
+    /// The end L2 block of the range.
+    #[clap(long)]
+    end: u64,
+    /// The last L1 block to scan. Defaults to the L1 head witness generation would choose for the
+    /// range.
+    #[clap(long)]
+    l1_head: Option<u64>,
+    /// The environment file to use.
+    #[clap(long, default_value = ".env")]
+    env_file: PathBuf,
+    /// Print the updates as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = SystemConfigUpdatesArgs::parse();
+    dotenv::from_path(&args.env_file).ok();
+    utils::setup_logger();
+    if args.start >= args.end {
+        bail!(
+            "Start block {} is not before end block {}",
+            args.start,
+            args.end
+        );
+    }
+
+    let fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
+    let l1_start = fetcher
+        .l2_block_info_by_number(args.start)
+        .await?
+        .l1_origin
+        .number;
+    let l1_end = match args.l1_head {
+        Some(l1_head) => l1_head,
+        None => fetcher.select_l1_head(args.end).await?.l1_head_number,
+    };
+    let updates = get_system_config_updates(&fetcher, l1_start, l1_end).await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&updates)?);
+        return Ok(());
+    }
+    println!(
+        "L2 blocks {}-{} derive from L1 blocks {}-{}: {} system config updates",
+        args.start,
+        args.end,
+        l1_start,
+        l1_end,
+        updates.len()
+    );
+    for update in &updates {
+        println!(
+            "L1 block {} (tx {}): {:?}",
+            update.l1_block_number,
+            update
+                .tx_hash
+                .map_or("unknown".to_string(), |hash| hash.to_string()),
+            update.change
+        );
+    }
+    Ok(())
+}
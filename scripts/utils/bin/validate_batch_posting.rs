@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use op_succinct_host_utils::{
+    batch_posting::validate_batch_posting,
+    config::url_var,
+    fetcher::{OPSuccinctDataFetcher, RunContext},
+};
+use sp1_sdk::utils;
+
+/// Checks that the batch data of a range can be retrieved before generating its witness. Scans the
+/// L1 blocks the range derives from for the batcher's transactions, and reports the blobs the
+/// beacon node doesn't serve, with their slots.
+#[derive(Debug, Clone, Parser)]
+struct ValidateBatchPostingArgs {
+    /// The start L2 block of the range.
+    #[clap(long)]
+    start: u64,
+    /// The end L2 block of the range.
+    #[clap(long)]
+    end: u64,
+    /// The last L1 block to scan. Defaults to the L1 head witness generation would choose for the
+    /// range.
+    #[clap(long)]
+    l1_head: Option<u64>,
+    /// The environment file to use.
+    #[clap(long, default_value = ".env")]
+    env_file: PathBuf,
+    /// Print the report as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = ValidateBatchPostingArgs::parse();
+    dotenv::from_path(&args.env_file).ok();
+    utils::setup_logger();
+    if args.start >= args.end {
+        bail!(
+            "Start block {} is not before end block {}",
+            args.start,
+            args.end
+        );
+    }
+
+    let fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
+    // Check the beacon API witness generation reads blobs from.
+    let beacon_url = match url_var("BLOB_ARCHIVE_RPC")? {
+        Some(url) => url,
+        None => fetcher.rpc_config.l1_beacon_rpc.clone(),
+    };
+    let report =
+        validate_batch_posting(&fetcher, &beacon_url, args.start, args.end, args.l1_head).await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!(
+            "L2 blocks {}-{} derive from L1 blocks {}-{}: {} batcher transactions, {} as calldata, {} blobs",
+            report.l2_start_block,
+            report.l2_end_block,
+            report.l1_start_block,
+            report.l1_end_block,
+            report.batcher_transactions,
+            report.calldata_transactions,
+            report.blobs
+        );
+        if report.batcher_transactions == 0 {
+            println!("No batcher transactions found. Check BATCHER_ADDRESS and the rollup config.");
+        }
+        for blob in &report.missing_blobs {
+            println!(
+                "Missing blob {} of tx {} in L1 block {} (slot {}): {}",
+                blob.versioned_hash, blob.tx_hash, blob.l1_block_number, blob.slot, blob.reason
+            );
+        }
+        for tx in &report.invalid_calldata {
+            println!(
+                "Batcher tx {} in L1 block {} has calldata of an unknown derivation version",
+                tx.tx_hash, tx.l1_block_number
+            );
+        }
+    }
+
+    if !report.is_available() {
+        bail!(
+            "{} blobs and {} calldata transactions of L2 blocks {}-{} can't be retrieved",
+            report.missing_blobs.len(),
+            report.invalid_calldata.len(),
+            args.start,
+            args.end
+        );
+    }
+    Ok(())
+}
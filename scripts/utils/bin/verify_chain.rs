@@ -0,0 +1,125 @@
+use std::{path::PathBuf, sync::Arc};
+
+use alloy_primitives::B256;
+use anyhow::{bail, Result};
+use clap::Parser;
+use futures::StreamExt;
+use op_succinct_client_utils::{
+    boot::ClaimMode, client::run_opsuccinct_client_with_intermediate_outputs,
+};
+use op_succinct_host_utils::{
+    fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
+    output_root::{FieldDivergence, OutputRootFields},
+    start_server_and_native_client, ProgramType,
+};
+use serde::Serialize;
+
+/// Executes a range of blocks with the client, as the range program would, and compares the output
+/// root of every executed block against the L2 node's. Prints the first block at which they
+/// diverge. The fields of the L2 node's output that differ from the L2 execution client's headers
+/// and Merkle proofs are printed alongside, to trace the divergence to a field.
+#[derive(Debug, Clone, Parser)]
+struct VerifyChainArgs {
+    /// The first block to check.
+    #[clap(long)]
+    start: u64,
+    /// The last block to check.
+    #[clap(long)]
+    end: u64,
+    /// The number of blocks whose L2 node outputs are fetched concurrently.
+    #[clap(long, default_value_t = 16)]
+    concurrency: usize,
+    /// The environment file to use.
+    #[clap(long, default_value = ".env")]
+    env_file: PathBuf,
+    /// Print the divergence as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+struct Divergence {
+    block_number: u64,
+    executed_output_root: B256,
+    node_output_root: B256,
+    /// The fields of the L2 node's output that differ from the L2 execution client's.
+    fields: Vec<FieldDivergence>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = VerifyChainArgs::parse();
+    dotenv::from_path(&args.env_file).ok();
+    if args.start == 0 || args.start > args.end {
+        bail!(
+            "Invalid range {}-{}: the start block must be positive and not after the end block",
+            args.start,
+            args.end
+        );
+    }
+
+    let fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
+    let fetcher = &fetcher;
+
+    // Execute the blocks from the output before the start block, committing the output root the
+    // client computes for the end block rather than checking it against the L2 node's.
+    let mut host_args = fetcher
+        .get_host_args(
+            args.start - 1,
+            args.end,
+            None,
+            ProgramType::Multi,
+            CacheMode::DeleteCache,
+        )
+        .await?;
+    host_args.claim_mode = ClaimMode::Compute;
+    host_args.intermediate_outputs = true;
+    let oracle = start_server_and_native_client(host_args).await?;
+    oracle.verify()?;
+    let (_, executed) =
+        run_opsuccinct_client_with_intermediate_outputs(Arc::new(oracle), None).await?;
+
+    // Outputs are fetched concurrently but compared in order, so the first divergence is
+    // reported.
+    let mut outputs = futures::stream::iter(executed)
+        .map(|executed| async move {
+            let node = OutputRootFields::node(fetcher, executed.l2BlockNumber).await?;
+            anyhow::Ok((executed, node))
+        })
+        .buffered(args.concurrency.max(1));
+
+    while let Some(output) = outputs.next().await {
+        let (executed, node) = output?;
+        if node.output_root == executed.outputRoot {
+            continue;
+        }
+
+        let block_number = executed.l2BlockNumber;
+        let local = OutputRootFields::local(fetcher, block_number).await?;
+        let divergence = Divergence {
+            block_number,
+            executed_output_root: executed.outputRoot,
+            node_output_root: node.output_root,
+            fields: node.diff(&local),
+        };
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&divergence)?);
+        } else {
+            println!("Output roots diverge at block {}:", block_number);
+            println!("  Executed: {}", divergence.executed_output_root);
+            println!("  L2 node:  {}", divergence.node_output_root);
+            for field in &divergence.fields {
+                println!("  {}", field.field);
+                println!("    L2 node: {}", field.node);
+                println!("    Local:   {}", field.local);
+            }
+        }
+        bail!("Output roots diverge at block {}", block_number);
+    }
+
+    println!(
+        "Executed output roots of blocks {}-{} match the L2 node",
+        args.start, args.end
+    );
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use alloy_primitives::B256;
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use op_succinct_build_utils::SP1_DOCKER_TAG;
+use op_succinct_client_utils::types::u32_to_u8;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sp1_sdk::{HashableKey, Prover, ProverClient};
+
+pub const AGG_ELF: &[u8] = include_bytes!("../../../elf/aggregation-elf");
+pub const RANGE_ELF: &[u8] = include_bytes!("../../../elf/range-elf");
+
+/// Rebuilds the range and aggregation programs in the pinned SP1 docker image, and checks that the
+/// rebuilt ELFs are identical to the ELFs in `elf/`, which the vkeys in the contracts are derived
+/// from. Exits with an error if any ELF differs.
+#[derive(Debug, Clone, Parser)]
+struct VerifyElfArgs {
+    /// The tag of the SP1 docker image to build with.
+    #[clap(long, default_value = SP1_DOCKER_TAG)]
+    tag: String,
+    /// The directory to write the rebuilt ELFs to.
+    #[clap(long, default_value = "target/reproducible-elf")]
+    output_dir: PathBuf,
+    /// Print the report as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ElfReport {
+    program: &'static str,
+    embedded_sha256: B256,
+    rebuilt_sha256: B256,
+    /// The vkey of the rebuilt ELF, as stored in the contracts: the range vkey commitment or the
+    /// aggregation vkey.
+    rebuilt_vkey: String,
+    reproducible: bool,
+}
+
+fn sha256(bytes: &[u8]) -> B256 {
+    B256::from(<[u8; 32]>::from(Sha256::digest(bytes)))
+}
+
+/// Build a program with `cargo prove build` in docker, returning the ELF.
+fn build_program(
+    workspace_root: &Path,
+    program: &str,
+    tag: &str,
+    output_dir: &Path,
+) -> Result<Vec<u8>> {
+    let elf_name = format!("{}-elf", program);
+    let status = Command::new("cargo")
+        .args([
+            "prove",
+            "build",
+            "--docker",
+            "--tag",
+            tag,
+            "--elf-name",
+            &elf_name,
+        ])
+        .arg("--output-directory")
+        .arg(output_dir)
+        .arg("--workspace-directory")
+        .arg(workspace_root)
+        .current_dir(workspace_root.join("programs").join(program))
+        .status()
+        .context("Failed to run `cargo prove build`. Is the SP1 toolchain installed?")?;
+    if !status.success() {
+        bail!("Failed to build the {} program: {}", program, status);
+    }
+    Ok(fs::read(output_dir.join(elf_name))?)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = VerifyElfArgs::parse();
+
+    let metadata = cargo_metadata::MetadataCommand::new().exec()?;
+    let workspace_root = PathBuf::from(metadata.workspace_root);
+    let output_dir = workspace_root.join(&args.output_dir);
+    fs::create_dir_all(&output_dir)?;
+
+    let prover = ProverClient::builder().cpu().build();
+
+    let mut reports = Vec::new();
+    for (program, embedded) in [("range", RANGE_ELF), ("aggregation", AGG_ELF)] {
+        let rebuilt = build_program(&workspace_root, program, &args.tag, &output_dir)?;
+        let (_, vk) = prover.setup(&rebuilt);
+        let rebuilt_vkey = match program {
+            // The L2OO stores the range vkey as the 32 byte commitment from `hash_u32`.
+            "range" => B256::from(u32_to_u8(vk.vk.hash_u32())).to_string(),
+            _ => vk.bytes32(),
+        };
+        reports.push(ElfReport {
+            program,
+            embedded_sha256: sha256(embedded),
+            rebuilt_sha256: sha256(&rebuilt),
+            rebuilt_vkey,
+            reproducible: embedded == rebuilt.as_slice(),
+        });
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        for report in &reports {
+            println!(
+                "{}: {}",
+                report.program,
+                if report.reproducible {
+                    "reproducible"
+                } else {
+                    "MISMATCH"
+                }
+            );
+            println!("  Embedded SHA-256: {}", report.embedded_sha256);
+            println!("  Rebuilt SHA-256:  {}", report.rebuilt_sha256);
+            println!("  Rebuilt vkey:     {}", report.rebuilt_vkey);
+        }
+    }
+
+    let mismatches = reports
+        .iter()
+        .filter(|report| !report.reproducible)
+        .map(|report| report.program)
+        .collect::<Vec<_>>();
+    if !mismatches.is_empty() {
+        bail!(
+            "The rebuilt ELFs of {} differ from the ELFs in elf/",
+            mismatches.join(", ")
+        );
+    }
+
+    Ok(())
+}
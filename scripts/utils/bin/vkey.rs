@@ -1,7 +1,6 @@
-use alloy_primitives::B256;
 use anyhow::Result;
-use op_succinct_client_utils::types::u32_to_u8;
-use sp1_sdk::{utils, HashableKey, Prover, ProverClient};
+use op_succinct_host_utils::vkeys::vkey_commitment;
+use sp1_sdk::{utils, Prover, ProverClient};
 
 pub const AGG_ELF: &[u8] = include_bytes!("../../../elf/aggregation-elf");
 pub const RANGE_ELF: &[u8] = include_bytes!("../../../elf/range-elf");
@@ -15,17 +14,17 @@ async fn main() -> Result<()> {
     let prover = ProverClient::builder().cpu().build();
 
     let (_, range_vk) = prover.setup(RANGE_ELF);
+    let (_, agg_vk) = prover.setup(AGG_ELF);
+    let commitment = vkey_commitment(&agg_vk, &range_vk, None);
 
-    // Get the 32 byte commitment to the vkey from vkey.vk.hash_u32()
-    let multi_block_vkey_u8 = u32_to_u8(range_vk.vk.hash_u32());
-    let multi_block_vkey_b256 = B256::from(multi_block_vkey_u8);
     println!(
         "Range ELF Verification Key Commitment: {}",
-        multi_block_vkey_b256
+        commitment.range_vkey_commitment
+    );
+    println!(
+        "Aggregation ELF Verification Key: {}",
+        commitment.aggregation_vkey
     );
-
-    let (_, agg_vk) = prover.setup(AGG_ELF);
-    println!("Aggregation ELF Verification Key: {}", agg_vk.bytes32());
 
     Ok(())
 }
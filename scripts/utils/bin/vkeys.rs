@@ -0,0 +1,112 @@
+use std::{fs, path::PathBuf};
+
+use alloy_primitives::B256;
+use anyhow::Result;
+use clap::Parser;
+use op_succinct_client_utils::{boot::hash_rollup_config, types::u32_to_u8};
+use op_succinct_host_utils::{
+    fetcher::{OPSuccinctDataFetcher, RunContext},
+    vkeys::{vkey_commitment, VkeyCommitment},
+};
+use serde::Serialize;
+use sp1_sdk::{HashableKey, Prover, ProverClient};
+
+pub const AGG_ELF: &[u8] = include_bytes!("../../../elf/aggregation-elf");
+pub const RANGE_ELF: &[u8] = include_bytes!("../../../elf/range-elf");
+
+/// Prints the values the contracts are deployed or upgraded with: the aggregation vkey, the range
+/// vkey commitment and, if an environment file is given, the chain's rollup config hash.
+#[derive(Debug, Clone, Parser)]
+struct VkeysArgs {
+    /// The range ELF to use instead of the one in `elf/`.
+    #[clap(long)]
+    range_elf: Option<PathBuf>,
+    /// The secondary range ELF of quorum mode, which the range vkey commitment then also commits
+    /// to.
+    #[clap(long)]
+    secondary_range_elf: Option<PathBuf>,
+    /// The aggregation ELF to use instead of the one in `elf/`.
+    #[clap(long)]
+    agg_elf: Option<PathBuf>,
+    /// The environment file with the RPCs of the chain to compute the rollup config hash for.
+    #[clap(long)]
+    env_file: Option<PathBuf>,
+    /// Print the values as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Vkeys {
+    #[serde(flatten)]
+    commitment: VkeyCommitment,
+    /// The `hash_u32` digests of the range programs the commitment is computed from, which the
+    /// deploy scripts take as `RANGE_VKEY` and `SECONDARY_RANGE_VKEY`.
+    range_vkey: B256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secondary_range_vkey: Option<B256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rollup_config_hash: Option<B256>,
+}
+
+fn read_elf(path: &Option<PathBuf>, default: &[u8]) -> Result<Vec<u8>> {
+    match path {
+        Some(path) => Ok(fs::read(path)?),
+        None => Ok(default.to_vec()),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = VkeysArgs::parse();
+
+    let prover = ProverClient::builder().cpu().build();
+
+    let (_, range_vk) = prover.setup(&read_elf(&args.range_elf, RANGE_ELF)?);
+    let (_, agg_vk) = prover.setup(&read_elf(&args.agg_elf, AGG_ELF)?);
+    let secondary_range_vk = match &args.secondary_range_elf {
+        Some(path) => Some(prover.setup(&fs::read(path)?).1),
+        None => None,
+    };
+
+    let rollup_config_hash = match &args.env_file {
+        Some(env_file) => {
+            dotenv::from_path(env_file)?;
+            let fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
+            Some(hash_rollup_config(fetcher.rollup_config.as_ref().unwrap()))
+        }
+        None => None,
+    };
+
+    let vkeys = Vkeys {
+        commitment: vkey_commitment(&agg_vk, &range_vk, secondary_range_vk.as_ref()),
+        range_vkey: B256::from(u32_to_u8(range_vk.hash_u32())),
+        secondary_range_vkey: secondary_range_vk
+            .as_ref()
+            .map(|vk| B256::from(u32_to_u8(vk.hash_u32()))),
+        rollup_config_hash,
+    };
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&vkeys)?);
+    } else {
+        println!(
+            "Aggregation Verification Key: {}",
+            vkeys.commitment.aggregation_vkey
+        );
+        println!(
+            "Range Verification Key Commitment: {}",
+            vkeys.commitment.range_vkey_commitment
+        );
+        println!("Range Verification Key: {}", vkeys.range_vkey);
+        if let Some(secondary_range_vkey) = vkeys.secondary_range_vkey {
+            println!("Secondary Range Verification Key: {}", secondary_range_vkey);
+        }
+        if let Some(rollup_config_hash) = vkeys.rollup_config_hash {
+            println!("Rollup Config Hash: {}", rollup_config_hash);
+        }
+    }
+
+    Ok(())
+}
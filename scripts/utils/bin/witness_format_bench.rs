@@ -0,0 +1,93 @@
+use std::{path::PathBuf, time::Instant};
+
+use anyhow::Result;
+use clap::Parser;
+use op_succinct_client_utils::witness::WitnessFormat;
+use op_succinct_host_utils::{
+    fetcher::{CacheMode, OPSuccinctDataFetcher, RunContext},
+    get_proof_stdin_with_format,
+    panic_report::PanicReportCollector,
+    start_server_and_native_client, stdin_size, ProgramType,
+};
+use sp1_sdk::{utils, ProverClient};
+
+pub const RANGE_ELF: &[u8] = include_bytes!("../../../elf/range-elf");
+
+/// Compares the witness serialization formats on a range: the time the host spends serializing the
+/// witness, its size, and the cycles the range program spends deserializing it.
+#[derive(Debug, Clone, Parser)]
+struct WitnessFormatBenchArgs {
+    /// The start L2 block of the range.
+    #[clap(long)]
+    start: u64,
+    /// The end L2 block of the range.
+    #[clap(long)]
+    end: u64,
+    /// The formats to compare. Defaults to all of them.
+    #[clap(long, value_delimiter = ',')]
+    formats: Vec<WitnessFormat>,
+    /// The environment file to use.
+    #[clap(long, default_value = ".env")]
+    env_file: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = WitnessFormatBenchArgs::parse();
+
+    dotenv::from_path(&args.env_file).ok();
+    utils::setup_logger();
+
+    let fetcher = OPSuccinctDataFetcher::new_with_rollup_config(RunContext::Dev).await?;
+    let host_args = fetcher
+        .get_host_args(
+            args.start,
+            args.end,
+            None,
+            ProgramType::Multi,
+            CacheMode::DeleteCache,
+        )
+        .await?;
+    let oracle = start_server_and_native_client(host_args).await?;
+    println!(
+        "L2 blocks {}-{}, {} preimages",
+        args.start,
+        args.end,
+        oracle.cache.len()
+    );
+
+    let formats = if args.formats.is_empty() {
+        WitnessFormat::ALL.to_vec()
+    } else {
+        args.formats
+    };
+
+    let prover = ProverClient::builder().cpu().build();
+    for format in formats {
+        let now = Instant::now();
+        let stdin = get_proof_stdin_with_format(oracle.clone(), format)?;
+        let serialize_time = now.elapsed();
+
+        let panic_report = PanicReportCollector::default();
+        let (_, report) = prover
+            .execute(RANGE_ELF, &stdin)
+            .with_hook(PanicReportCollector::FD, panic_report.hook())
+            .run()
+            .map_err(|e| panic_report.explain(e))?;
+        let deserialize_cycles = report
+            .cycle_tracker
+            .get("in-memory-oracle-from-raw-bytes-deserialize")
+            .copied()
+            .unwrap_or_default();
+
+        println!(
+            "{format}: serialize {:.3}s, {} bytes, deserialize {} cycles, total {} cycles",
+            serialize_time.as_secs_f64(),
+            stdin_size(&stdin),
+            deserialize_cycles,
+            report.total_instruction_count()
+        );
+    }
+
+    Ok(())
+}
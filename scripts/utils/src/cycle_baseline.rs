@@ -0,0 +1,155 @@
+//! The baseline of the `cycle-regression` script: the cycles the range program spends executing a
+//! curated set of L2 blocks, compared against on every run to catch regressions.
+
+use std::{fs, path::Path, str::FromStr};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// The default increase in a block's cycles, in percent, above which it is a regression.
+pub const DEFAULT_MAX_REGRESSION_PERCENT: f64 = 5.0;
+
+/// A block to execute, labeled with what it is representative of, e.g. `dex-heavy`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabeledBlock {
+    pub label: String,
+    pub block: u64,
+}
+
+impl FromStr for LabeledBlock {
+    type Err = anyhow::Error;
+
+    /// Parses `<label>=<block number>`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (label, block) = s
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Expected <label>=<block number>, got {s}"))?;
+        Ok(Self {
+            label: label.to_string(),
+            block: block
+                .parse()
+                .with_context(|| format!("Invalid block number {block}"))?,
+        })
+    }
+}
+
+/// The execution of one L2 block by the range program.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockCycles {
+    pub label: String,
+    pub block: u64,
+    pub transactions: u64,
+    pub gas_used: u64,
+    pub cycles: u64,
+}
+
+impl BlockCycles {
+    pub fn cycles_per_gas(&self) -> f64 {
+        self.cycles as f64 / self.gas_used.max(1) as f64
+    }
+}
+
+/// The cycles of the curated blocks of a chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CycleBaseline {
+    pub l2_chain_id: u64,
+    /// The increase in a block's cycles, in percent, above which it is a regression.
+    pub max_regression_percent: f64,
+    pub blocks: Vec<BlockCycles>,
+}
+
+impl CycleBaseline {
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Compare the measured cycles of the blocks against the baseline. Blocks that aren't in the
+    /// baseline are skipped.
+    pub fn compare(&self, measured: &[BlockCycles]) -> Vec<CycleComparison> {
+        measured
+            .iter()
+            .filter_map(|block| {
+                let baseline = self.blocks.iter().find(|b| b.block == block.block)?;
+                Some(CycleComparison {
+                    label: block.label.clone(),
+                    block: block.block,
+                    baseline_cycles: baseline.cycles,
+                    cycles: block.cycles,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The cycles of a block compared against the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleComparison {
+    pub label: String,
+    pub block: u64,
+    pub baseline_cycles: u64,
+    pub cycles: u64,
+}
+
+impl CycleComparison {
+    /// The change in cycles relative to the baseline, in percent.
+    pub fn change_percent(&self) -> f64 {
+        (self.cycles as f64 - self.baseline_cycles as f64) / self.baseline_cycles.max(1) as f64
+            * 100.0
+    }
+
+    pub fn is_regression(&self, max_regression_percent: f64) -> bool {
+        self.change_percent() > max_regression_percent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(block: u64, cycles: u64) -> BlockCycles {
+        BlockCycles {
+            label: "dex-heavy".to_string(),
+            block,
+            transactions: 10,
+            gas_used: 1_000_000,
+            cycles,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = CycleBaseline {
+            l2_chain_id: 10,
+            max_regression_percent: DEFAULT_MAX_REGRESSION_PERCENT,
+            blocks: vec![block(100, 1_000_000), block(200, 2_000_000)],
+        };
+        let comparisons =
+            baseline.compare(&[block(100, 1_040_000), block(200, 2_200_000), block(300, 1)]);
+
+        // Blocks missing from the baseline are skipped.
+        assert_eq!(comparisons.len(), 2);
+        assert!(!comparisons[0].is_regression(baseline.max_regression_percent));
+        assert!(comparisons[1].is_regression(baseline.max_regression_percent));
+        assert_eq!(comparisons[1].change_percent(), 10.0);
+    }
+
+    #[test]
+    fn test_parse_labeled_block() {
+        assert_eq!(
+            "deposit-heavy=123".parse::<LabeledBlock>().unwrap(),
+            LabeledBlock {
+                label: "deposit-heavy".to_string(),
+                block: 123
+            }
+        );
+        assert!("123".parse::<LabeledBlock>().is_err());
+        assert!("blob-heavy=latest".parse::<LabeledBlock>().is_err());
+    }
+}
@@ -1,4 +1,7 @@
+pub mod cycle_baseline;
+
 use clap::Parser;
+use op_succinct_host_utils::l2_node::L2NodeKind;
 use std::path::PathBuf;
 
 /// The arguments for the host executable.
@@ -28,4 +31,10 @@ pub struct HostExecutorArgs {
     /// Whether to generate proofs.
     #[clap(long)]
     pub prove: bool,
+    /// Snap the span batch boundaries to L1-origin epoch boundaries.
+    #[clap(long)]
+    pub epoch_aligned: bool,
+    /// The kind of L2 node (op-geth, op-reth or erigon), if not detected from its client version.
+    #[clap(long)]
+    pub l2_node_kind: Option<L2NodeKind>,
 }
@@ -1,5 +1,9 @@
 use sp1_build::{build_program_with_args, BuildArgs};
 
+/// The tag of the SP1 docker image the programs are built with, so that the ELFs in `elf/` can be
+/// reproduced.
+pub const SP1_DOCKER_TAG: &str = "v4.0.0-rc.10";
+
 #[derive(Clone, Copy)]
 pub enum ProgramBuildArgs {
     Default,
@@ -18,7 +22,7 @@ fn build_zkvm_program(program: &str) {
             elf_name: Some(format!("{}-elf", program)),
             output_directory: Some("../../elf".to_string()),
             docker: true,
-            tag: "v4.0.0-rc.10".to_string(),
+            tag: SP1_DOCKER_TAG.to_string(),
             workspace_directory: Some("../../".to_string()),
             ..Default::default()
         },
@@ -0,0 +1,43 @@
+//! Fails the build if the rollup config at `OP_SUCCINCT_ROLLUP_CONFIG` schedules a hardfork the
+//! enabled fork features don't support.
+
+use std::{env, fs};
+
+#[allow(dead_code)]
+mod table {
+    include!("src/forks/table.rs");
+}
+use table::{supported_forks, unsupported_forks};
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=OP_SUCCINCT_ROLLUP_CONFIG");
+    let Ok(path) = env::var("OP_SUCCINCT_ROLLUP_CONFIG") else {
+        return;
+    };
+    println!("cargo:rerun-if-changed={path}");
+
+    let rollup_config: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {path}: {e}")),
+    )
+    .unwrap_or_else(|e| panic!("Failed to parse {path}: {e}"));
+    let supported = supported_forks(|fork| {
+        env::var_os(format!("CARGO_FEATURE_{}", fork.to_uppercase())).is_some()
+    });
+
+    // Every `*_time` field other than the block time schedules a hardfork, including ones newer
+    // than this file knows.
+    let activations = rollup_config
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(field, _)| *field != "block_time")
+        .filter_map(|(field, value)| Some((field.strip_suffix("_time")?, value.as_u64())));
+    let unsupported = unsupported_forks(activations, supported, u64::MAX);
+    if !unsupported.is_empty() {
+        panic!(
+            "{path} schedules hardforks this build doesn't support: {unsupported:?}. Enable the \
+             fork feature of the newest one, e.g. `--features {}`.",
+            unsupported.last().unwrap().0
+        );
+    }
+}
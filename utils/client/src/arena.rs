@@ -0,0 +1,343 @@
+//! A block-scoped bump arena for the zkVM heap.
+//!
+//! Executing a block makes many small, short-lived allocations: trie nodes and the RLP they're
+//! decoded from, receipts, and the transactions of the payload. Served by the zkVM's allocator,
+//! they're interleaved with the long-lived state of the range (the span's trie cache, the
+//! derivation pipeline), which fragments the heap: freed memory is scattered between live
+//! allocations, so each block touches fresh memory pages, and every page the program touches is
+//! paid for in cycles.
+//!
+//! [`BlockArena`] wraps the zkVM's allocator and serves small allocations by bumping a cursor
+//! through fixed-size chunks of one region. Each chunk counts its live allocations, and a chunk
+//! whose allocations were all freed is reused from its start, so a block's witness data is
+//! allocated from the same few pages as the previous block's. [`end_block`] is called by the
+//! client after each block and rewinds the current chunk if nothing in it is still alive.
+//! Allocations that outlive the block, such as nodes kept in the span's trie cache, only keep their
+//! own chunk from being reused.
+//!
+//! The arena is installed by the range program's `block-arena` feature.
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use spin::{Mutex, Once};
+
+/// The size of a chunk of the arena.
+pub const CHUNK_SIZE: usize = 1 << 20;
+
+/// The number of chunks in the arena's region.
+pub const CHUNK_COUNT: usize = 64;
+
+/// The largest allocation served from the arena. Larger allocations are passed through to the
+/// underlying allocator.
+pub const MAX_ARENA_ALLOCATION: usize = 4096;
+
+/// The largest alignment served from the arena.
+const MAX_ARENA_ALIGN: usize = 16;
+
+const REGION_SIZE: usize = CHUNK_SIZE * CHUNK_COUNT;
+
+/// Counters of the arena's allocations, printed by the range program to measure its impact.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ArenaStats {
+    /// Allocations served from the arena.
+    pub arena_allocations: u64,
+    /// Allocations passed through to the underlying allocator, because they were too large or
+    /// every chunk of the arena held live allocations.
+    pub fallback_allocations: u64,
+    /// Blocks after which the current chunk was rewound.
+    pub rewinds: u64,
+    /// The most chunks holding live allocations at once. The arena's peak memory is this many
+    /// chunks.
+    pub peak_chunks: usize,
+}
+
+struct State {
+    /// Whether the region couldn't be allocated, in which case everything is passed through.
+    region_failed: bool,
+    current: usize,
+    offset: usize,
+    live: [u32; CHUNK_COUNT],
+    chunks_in_use: usize,
+    stats: ArenaStats,
+}
+
+impl State {
+    /// The next chunk without live allocations, searching from the one after the current chunk.
+    fn free_chunk(&self) -> Option<usize> {
+        (1..=CHUNK_COUNT)
+            .map(|i| (self.current + i) % CHUNK_COUNT)
+            .find(|&chunk| self.live[chunk] == 0)
+    }
+}
+
+/// A [`GlobalAlloc`] that serves small allocations from block-scoped chunks and passes the rest
+/// through to `A`.
+pub struct BlockArena<A> {
+    inner: A,
+    /// The start of the region, or 0 until the first allocation from the arena.
+    base: AtomicUsize,
+    state: Mutex<State>,
+}
+
+impl<A: GlobalAlloc> BlockArena<A> {
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            base: AtomicUsize::new(0),
+            state: Mutex::new(State {
+                region_failed: false,
+                current: 0,
+                offset: 0,
+                live: [0; CHUNK_COUNT],
+                chunks_in_use: 0,
+                stats: ArenaStats {
+                    arena_allocations: 0,
+                    fallback_allocations: 0,
+                    rewinds: 0,
+                    peak_chunks: 0,
+                },
+            }),
+        }
+    }
+
+    pub fn stats(&self) -> ArenaStats {
+        self.state.lock().stats
+    }
+
+    /// Rewind the current chunk if all of its allocations were freed, so the next block allocates
+    /// from its start.
+    pub fn end_block(&self) {
+        let mut state = self.state.lock();
+        if state.live[state.current] == 0 && state.offset > 0 {
+            state.offset = 0;
+            state.stats.rewinds += 1;
+        }
+    }
+
+    /// The chunk of `ptr`, if it was allocated from the arena.
+    fn chunk_of(&self, ptr: *mut u8) -> Option<usize> {
+        let base = self.base.load(Ordering::Acquire);
+        let offset = (ptr as usize).checked_sub(base)?;
+        (base != 0 && offset < REGION_SIZE).then_some(offset / CHUNK_SIZE)
+    }
+
+    /// Allocate from the arena, or return null if the allocation should be passed through.
+    unsafe fn alloc_in_arena(&self, layout: Layout) -> *mut u8 {
+        let mut state = self.state.lock();
+        if layout.size() > MAX_ARENA_ALLOCATION
+            || layout.align() > MAX_ARENA_ALIGN
+            || state.region_failed
+        {
+            state.stats.fallback_allocations += 1;
+            return ptr::null_mut();
+        }
+
+        let mut base = self.base.load(Ordering::Acquire);
+        if base == 0 {
+            let region = self.inner.alloc(Layout::from_size_align_unchecked(
+                REGION_SIZE,
+                MAX_ARENA_ALIGN,
+            ));
+            if region.is_null() {
+                state.region_failed = true;
+                state.stats.fallback_allocations += 1;
+                return ptr::null_mut();
+            }
+            base = region as usize;
+            self.base.store(base, Ordering::Release);
+        }
+
+        let mut start = state.offset.next_multiple_of(layout.align());
+        if start + layout.size() > CHUNK_SIZE {
+            match state.free_chunk() {
+                Some(chunk) => {
+                    state.current = chunk;
+                    start = 0;
+                }
+                None => {
+                    state.stats.fallback_allocations += 1;
+                    return ptr::null_mut();
+                }
+            }
+        }
+
+        let current = state.current;
+        state.offset = start + layout.size();
+        if state.live[current] == 0 {
+            state.chunks_in_use += 1;
+            state.stats.peak_chunks = state.stats.peak_chunks.max(state.chunks_in_use);
+        }
+        state.live[current] += 1;
+        state.stats.arena_allocations += 1;
+        (base + current * CHUNK_SIZE + start) as *mut u8
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for BlockArena<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.alloc_in_arena(layout);
+        if ptr.is_null() {
+            self.inner.alloc(layout)
+        } else {
+            ptr
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let Some(chunk) = self.chunk_of(ptr) else {
+            return self.inner.dealloc(ptr, layout);
+        };
+        let mut state = self.state.lock();
+        state.live[chunk] -= 1;
+        if state.live[chunk] == 0 {
+            state.chunks_in_use -= 1;
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        match self.chunk_of(ptr) {
+            // Grow or shrink the last allocation of the current chunk in place, which is how a
+            // buffer being written to usually grows.
+            Some(chunk) if new_size <= MAX_ARENA_ALLOCATION => {
+                let mut state = self.state.lock();
+                let chunk_start = self.base.load(Ordering::Acquire) + chunk * CHUNK_SIZE;
+                let start = ptr as usize - chunk_start;
+                if chunk == state.current
+                    && start + layout.size() == state.offset
+                    && start + new_size <= CHUNK_SIZE
+                {
+                    state.offset = start + new_size;
+                    return ptr;
+                }
+            }
+            None if new_size > MAX_ARENA_ALLOCATION => {
+                return self.inner.realloc(ptr, layout, new_size);
+            }
+            _ => {}
+        }
+
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+            self.dealloc(ptr, layout);
+        }
+        new_ptr
+    }
+}
+
+static END_BLOCK_HOOK: Once<fn()> = Once::new();
+
+/// Set the function [`end_block`] calls, usually [`BlockArena::end_block`] of the global allocator.
+pub fn set_end_block_hook(hook: fn()) {
+    END_BLOCK_HOOK.call_once(|| hook);
+}
+
+/// Called by the client after each block is executed. Does nothing unless an arena is installed.
+pub fn end_block() {
+    if let Some(hook) = END_BLOCK_HOOK.get() {
+        hook();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::System;
+
+    fn small() -> Layout {
+        Layout::from_size_align(64, 8).unwrap()
+    }
+
+    #[test]
+    fn test_small_allocations_are_bumped() {
+        let arena = BlockArena::new(System);
+        unsafe {
+            let a = arena.alloc(small());
+            let b = arena.alloc(small());
+            assert_eq!(b as usize, a as usize + 64);
+
+            let large = Layout::from_size_align(MAX_ARENA_ALLOCATION + 1, 8).unwrap();
+            let c = arena.alloc(large);
+            assert!(arena.chunk_of(c).is_none());
+
+            assert_eq!(arena.stats().arena_allocations, 2);
+            assert_eq!(arena.stats().fallback_allocations, 1);
+            for (ptr, layout) in [(a, small()), (b, small()), (c, large)] {
+                arena.dealloc(ptr, layout);
+            }
+        }
+    }
+
+    #[test]
+    fn test_end_block_rewinds_freed_chunk() {
+        let arena = BlockArena::new(System);
+        unsafe {
+            let a = arena.alloc(small());
+            let b = arena.alloc(small());
+
+            // A live allocation keeps the chunk from being rewound.
+            arena.dealloc(a, small());
+            arena.end_block();
+            assert_eq!(arena.stats().rewinds, 0);
+
+            arena.dealloc(b, small());
+            arena.end_block();
+            assert_eq!(arena.stats().rewinds, 1);
+            let c = arena.alloc(small());
+            assert_eq!(c, a);
+            arena.dealloc(c, small());
+        }
+    }
+
+    #[test]
+    fn test_live_chunk_is_not_reused() {
+        let arena = BlockArena::new(System);
+        let layout = Layout::from_size_align(MAX_ARENA_ALLOCATION, 8).unwrap();
+        let per_chunk = CHUNK_SIZE / MAX_ARENA_ALLOCATION;
+        unsafe {
+            // Fill the first chunk, keeping one allocation alive, then move to the next chunk.
+            let first: Vec<_> = (0..per_chunk).map(|_| arena.alloc(layout)).collect();
+            for ptr in &first[1..] {
+                arena.dealloc(*ptr, layout);
+            }
+            let next = arena.alloc(layout);
+            assert_eq!(arena.chunk_of(next), Some(1));
+            assert_eq!(arena.stats().peak_chunks, 2);
+
+            // Once the second chunk is full, the third is used rather than the pinned first.
+            let second: Vec<_> = (1..per_chunk).map(|_| arena.alloc(layout)).collect();
+            let third = arena.alloc(layout);
+            assert_eq!(arena.chunk_of(third), Some(2));
+
+            for ptr in [first[0], next, third].into_iter().chain(second) {
+                arena.dealloc(ptr, layout);
+            }
+        }
+    }
+
+    #[test]
+    fn test_realloc_grows_last_allocation_in_place() {
+        let arena = BlockArena::new(System);
+        unsafe {
+            let a = arena.alloc(small());
+            a.write_bytes(7, 64);
+            let grown = arena.realloc(a, small(), 256);
+            assert_eq!(grown, a);
+
+            // An allocation that isn't the last one is moved.
+            let b = arena.alloc(small());
+            let moved = arena.realloc(grown, Layout::from_size_align(256, 8).unwrap(), 512);
+            assert_ne!(moved, grown);
+            assert_eq!(*moved.add(63), 7);
+
+            arena.dealloc(b, small());
+            arena.dealloc(moved, Layout::from_size_align(512, 8).unwrap());
+            assert_eq!(arena.state.lock().chunks_in_use, 0);
+        }
+    }
+}
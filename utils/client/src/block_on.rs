@@ -0,0 +1,97 @@
+//! Running the client's futures to completion from synchronous code.
+//!
+//! The executor reads state through synchronous trie provider methods, which have to wait on the
+//! oracle's futures. In the zkVM, the client is the only task and the in-memory oracle's futures are
+//! always ready, so polling them in a loop is enough. On a host running the client inside a Tokio
+//! runtime, the oracle's futures can wait on the runtime's I/O, which a busy loop on a worker
+//! thread starves, and calling `Handle::block_on` from a worker thread panics. [block_on] handles
+//! both without a separate implementation for each.
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+/// Run a future to completion on the current thread.
+///
+/// Inside a multi-threaded Tokio runtime, the future is run on the runtime with
+/// [tokio::task::block_in_place], which moves the worker's other tasks to another thread while it
+/// blocks. Otherwise it is polled on the current thread, which parks until the future is woken, so
+/// on a current-thread runtime the future must not wait on the runtime's I/O.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    #[cfg(not(target_os = "zkvm"))]
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread {
+            return tokio::task::block_in_place(|| handle.block_on(future));
+        }
+    }
+
+    poll_to_completion(future)
+}
+
+/// Wakes the thread polling a future.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn poll_to_completion<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        // There is nothing else to run in the zkVM, and nothing to wake the future but polling it.
+        #[cfg(target_os = "zkvm")]
+        core::hint::spin_loop();
+        #[cfg(not(target_os = "zkvm"))]
+        thread::park();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn yield_once() -> u64 {
+        let mut yielded = false;
+        core::future::poll_fn(|cx| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await;
+        7
+    }
+
+    #[test]
+    fn test_block_on_outside_runtime() {
+        assert_eq!(block_on(yield_once()), 7);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_block_on_inside_runtime() {
+        let value = block_on(async {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            yield_once().await
+        });
+        assert_eq!(value, 7);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_block_on_inside_current_thread_runtime() {
+        assert_eq!(block_on(yield_once()), 7);
+    }
+}
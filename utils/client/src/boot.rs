@@ -3,14 +3,13 @@
 
 use alloy_primitives::B256;
 use alloy_sol_types::sol;
+use anyhow::{anyhow, Result};
+use kona_preimage::{PreimageKey, PreimageOracleClient};
 use kona_proof::BootInfo;
 use maili_genesis::RollupConfig;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
-// ABI encoding of AggregationOutputs is 6 * 32 bytes.
-pub const AGGREGATION_OUTPUTS_SIZE: usize = 6 * 32;
-
 /// Hash the serialized rollup config using SHA256. Note: The rollup config is never unrolled
 /// on-chain, so switching to a different hash function is not a concern, as long as the config hash
 /// is consistent with the one on the contract.
@@ -50,3 +49,75 @@ impl From<BootInfo> for BootInfoStruct {
         }
     }
 }
+
+/// The local key of the [`ClaimMode`], after kona's boot info keys.
+pub const CLAIM_MODE_KEY: u64 = 0x100;
+
+/// How the range program treats the claimed output root of its boot info. Both modes commit the
+/// output root of the claimed block, so the mode is a local input rather than a public value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum ClaimMode {
+    /// Fail unless the claimed output root is the output root of the claimed block.
+    #[default]
+    Check = 0,
+    /// Commit the output root of the claimed block in place of the claimed output root, e.g. to
+    /// prove the correct output of a range whose proposal is disputed.
+    Compute = 1,
+}
+
+impl ClaimMode {
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Check),
+            1 => Some(Self::Compute),
+            _ => None,
+        }
+    }
+
+    /// Read the claim mode of the run from the oracle.
+    pub async fn load<O: PreimageOracleClient>(oracle: &O) -> Result<Self> {
+        let mut byte = [0u8; 1];
+        oracle
+            .get_exact(PreimageKey::new_local(CLAIM_MODE_KEY), &mut byte)
+            .await
+            .map_err(|e| anyhow!("Failed to load the claim mode: {e}"))?;
+        Self::from_byte(byte[0]).ok_or_else(|| anyhow!("Unknown claim mode {}", byte[0]))
+    }
+}
+
+/// The local key of the [`DerivationMode`].
+pub const DERIVATION_MODE_KEY: u64 = 0x101;
+
+/// How the range program derives the blocks of its span. The fast path is only a hint of the
+/// host: the client checks the L1 data it relies on, and derives the span in full otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum DerivationMode {
+    /// Run the derivation pipeline for every block.
+    #[default]
+    Full = 0,
+    /// No batches were posted for the span, so its blocks are the deposits-only blocks of forced
+    /// empty batches, built without running the channel bank and batch stages.
+    EmptyBatches = 1,
+}
+
+impl DerivationMode {
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::Full),
+            1 => Some(Self::EmptyBatches),
+            _ => None,
+        }
+    }
+
+    /// Read the derivation mode of the run from the oracle.
+    pub async fn load<O: PreimageOracleClient>(oracle: &O) -> Result<Self> {
+        let mut byte = [0u8; 1];
+        oracle
+            .get_exact(PreimageKey::new_local(DERIVATION_MODE_KEY), &mut byte)
+            .await
+            .map_err(|e| anyhow!("Failed to load the derivation mode: {e}"))?;
+        Self::from_byte(byte[0]).ok_or_else(|| anyhow!("Unknown derivation mode {}", byte[0]))
+    }
+}
@@ -1,10 +1,14 @@
-use alloy_consensus::BlockBody;
-use alloy_primitives::{Sealed, B256};
+use alloy_consensus::{BlockBody, Header};
+use alloy_eips::{eip4895::Withdrawals, eip7685::EMPTY_REQUESTS_HASH};
+use alloy_primitives::{Bytes, Sealed, B256};
 use alloy_rlp::Decodable;
 use anyhow::anyhow;
 use anyhow::Result;
+use kona_derive::attributes::StatefulAttributesBuilder;
 use kona_derive::errors::PipelineError;
 use kona_derive::errors::PipelineErrorKind;
+use kona_derive::traits::AttributesBuilder;
+use kona_derive::traits::ChainProvider;
 use kona_derive::traits::Pipeline;
 use kona_derive::traits::SignalReceiver;
 use kona_derive::types::Signal;
@@ -15,6 +19,7 @@ use kona_driver::DriverResult;
 use kona_driver::Executor;
 use kona_driver::TipCursor;
 use kona_executor::{KonaHandleRegister, TrieDBProvider};
+use kona_mpt::TrieHinter;
 use kona_preimage::{CommsClient, PreimageKey};
 use kona_proof::errors::OracleProviderError;
 use kona_proof::executor::KonaExecutor;
@@ -24,10 +29,10 @@ use kona_proof::sync::new_pipeline_cursor;
 use kona_proof::{BootInfo, FlushableCache, HintType};
 use maili_genesis::RollupConfig;
 use maili_protocol::L2BlockInfo;
-use maili_rpc::OpAttributesWithParent;
 use op_alloy_consensus::OpBlock;
 use op_alloy_consensus::OpTxEnvelope;
 use op_alloy_consensus::OpTxType;
+use std::fmt;
 use std::fmt::Debug;
 use std::mem::forget;
 use std::sync::Arc;
@@ -35,12 +40,51 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
-use crate::oracle::OPSuccinctOracleBlobProvider;
+use crate::boot::{ClaimMode, DerivationMode};
+use crate::empty_batches::{plan_empty_batches, EmptyBatchDeriver};
+use crate::forks::check_build_forks;
+use crate::gas_token::read_gas_paying_token;
+use crate::l1_attributes::{check_l1_attributes, decode_l1_attributes, L1AttributesError};
+use crate::message_passer::read_message_passer_storage_root;
+use crate::oracle::{L1ReceiptsOracle, OPSuccinctOracleBlobProvider, OracleTrieDBProvider};
+use crate::trie_cache::CachingTrieDBProvider;
+use crate::types::{compute_output_root, IntermediateOutput, OUTPUT_ROOT_VERSION_V0};
+
+/// The L2 chain provider of the client, reading from the oracle the client is run with.
+pub type ClientL2ChainProvider<O> = OracleL2ChainProvider<L1ReceiptsOracle<O>>;
+
+/// The trie provider of the executor, which caches the state read by the blocks of the span.
+pub type SpanTrieProvider<O> = CachingTrieDBProvider<OracleTrieDBProvider<L1ReceiptsOracle<O>>>;
 
-// Sourced from https://github.com/op-rs/kona/tree/main/bin/client/src/single.rs
 pub async fn run_opsuccinct_client<O>(
     oracle: Arc<O>,
-    handle_register: Option<KonaHandleRegister<OracleL2ChainProvider<O>, OracleL2ChainProvider<O>>>,
+    handle_register: Option<KonaHandleRegister<SpanTrieProvider<O>, ClientL2ChainProvider<O>>>,
+) -> Result<BootInfo>
+where
+    O: CommsClient + FlushableCache + Send + Sync + Debug,
+{
+    run_client(oracle, handle_register, None).await
+}
+
+/// Runs the client like [run_opsuccinct_client], additionally returning the output root of every
+/// block executed in the range with the fields it commits to.
+pub async fn run_opsuccinct_client_with_intermediate_outputs<O>(
+    oracle: Arc<O>,
+    handle_register: Option<KonaHandleRegister<SpanTrieProvider<O>, ClientL2ChainProvider<O>>>,
+) -> Result<(BootInfo, Vec<IntermediateOutput>)>
+where
+    O: CommsClient + FlushableCache + Send + Sync + Debug,
+{
+    let mut intermediate_outputs = Vec::new();
+    let boot = run_client(oracle, handle_register, Some(&mut intermediate_outputs)).await?;
+    Ok((boot, intermediate_outputs))
+}
+
+// Sourced from https://github.com/op-rs/kona/tree/main/bin/client/src/single.rs
+async fn run_client<O>(
+    oracle: Arc<O>,
+    handle_register: Option<KonaHandleRegister<SpanTrieProvider<O>, ClientL2ChainProvider<O>>>,
+    intermediate_outputs: Option<&mut Vec<IntermediateOutput>>,
 ) -> Result<BootInfo>
 where
     O: CommsClient + FlushableCache + Send + Sync + Debug,
@@ -56,12 +100,22 @@ where
         }
     };
 
-    let boot_clone = boot.clone();
+    let claim_mode = ClaimMode::load(oracle.as_ref()).await?;
+    let derivation_mode = DerivationMode::load(oracle.as_ref()).await?;
+
+    let mut boot_clone = boot.clone();
+
+    // Serve the L1 receipts tries the derivation pipeline walks from one preimage per block.
+    let oracle = Arc::new(L1ReceiptsOracle::new(oracle));
 
     let boot_arc = Arc::new(boot.clone());
     let rollup_config = Arc::new(boot.rollup_config);
     let safe_head_hash = fetch_safe_head_hash(oracle.as_ref(), boot.agreed_l2_output_root).await?;
 
+    // Looks L1 headers up by number by walking parent hashes back from the L1 head, so the cost of
+    // a span grows with how far its L1 head is past its L1 origin. `OraclePipeline` only accepts
+    // this provider, and the L1 head is the only header the client can trust, so a faster lookup
+    // would still walk the chain once per span to verify the headers it serves.
     let mut l1_provider = OracleL1ChainProvider::new(boot.l1_head, oracle.clone());
     let mut l2_provider =
         OracleL2ChainProvider::new(safe_head_hash, rollup_config.clone(), oracle.clone());
@@ -82,9 +136,33 @@ where
         ));
     }
 
+    // Blocks after a hardfork this build doesn't support would be derived and executed with the
+    // wrong rules.
+    let claimed_timestamp = rollup_config.genesis.l2_time
+        + boot
+            .claimed_l2_block_number
+            .saturating_sub(rollup_config.genesis.l2.number)
+            * rollup_config.block_time;
+    check_build_forks(rollup_config.as_ref(), claimed_timestamp)?;
+
+    // Share the state fetched by the executor across the blocks of the span.
+    let trie_provider = CachingTrieDBProvider::new(OracleTrieDBProvider::new(oracle.clone()));
+
+    // Fees are charged in the custom gas token, if the chain has one, with arithmetic that assumes
+    // 18 decimals.
+    if let Some(token) = read_gas_paying_token(
+        safe_head.clone(),
+        trie_provider.clone(),
+        l2_provider.clone(),
+    )? {
+        token.check()?;
+        info!(target: "client", "L2 chain uses custom gas token {}", token.token);
+    }
+
     // In the case where the agreed upon L2 output root is the same as the claimed L2 output root,
-    // trace extension is detected and we can skip the derivation and execution steps.
-    if boot.agreed_l2_output_root == boot.claimed_l2_output_root {
+    // trace extension is detected and we can skip the derivation and execution steps. A computed
+    // claim is always derived.
+    if claim_mode == ClaimMode::Check && boot.agreed_l2_output_root == boot.claimed_l2_output_root {
         info!(
             target: "client",
             "Trace extension detected. State transition is already agreed upon.",
@@ -105,6 +183,49 @@ where
     .await?;
     l2_provider.set_cursor(cursor.clone());
 
+    // Build the blocks of a span without batches straight from their epochs if the host found no
+    // batches, once the L1 data confirms it.
+    let mut empty_batches = match derivation_mode {
+        DerivationMode::Full => None,
+        DerivationMode::EmptyBatches => {
+            let (cursor_origin, l2_safe_head) = {
+                let cursor = cursor.read();
+                (cursor.origin(), *cursor.l2_safe_head())
+            };
+            match plan_empty_batches(
+                rollup_config.as_ref(),
+                &mut l1_provider,
+                boot.l1_head,
+                cursor_origin,
+                &l2_safe_head,
+                boot.claimed_l2_block_number,
+            )
+            .await?
+            {
+                Some(epochs) => {
+                    info!(
+                        target: "client",
+                        "No batches in the span, deriving {} blocks from forced empty batches",
+                        epochs.len()
+                    );
+                    let builder = StatefulAttributesBuilder::new(
+                        rollup_config.clone(),
+                        l2_provider.clone(),
+                        l1_provider.clone(),
+                    );
+                    Some(EmptyBatchDeriver::new(builder, epochs))
+                }
+                None => {
+                    warn!(
+                        target: "client",
+                        "The span may contain batches, deriving it with the full pipeline"
+                    );
+                    None
+                }
+            }
+        }
+    };
+
     let pipeline = OraclePipeline::new(
         rollup_config.clone(),
         cursor.clone(),
@@ -115,8 +236,8 @@ where
     );
     let executor = KonaExecutor::new(
         &rollup_config,
+        trie_provider.clone(),
         l2_provider.clone(),
-        l2_provider,
         handle_register,
         None,
     );
@@ -127,26 +248,67 @@ where
     // Use custom advance to target with cycle tracking.
     #[cfg(target_os = "zkvm")]
     println!("cycle-tracker-report-start: block-execution-and-derivation");
+    let mut executed_blocks = intermediate_outputs.is_some().then(Vec::new);
     let (safe_head, output_root) = advance_to_target(
         &mut driver,
         rollup_config.as_ref(),
+        &mut l1_provider,
         Some(boot.claimed_l2_block_number),
+        empty_batches.as_mut(),
+        executed_blocks.as_mut(),
     )
     .await?;
     #[cfg(target_os = "zkvm")]
     println!("cycle-tracker-report-end: block-execution-and-derivation");
 
+    // The executor only returns the output root of each block, so the storage root of the message
+    // passer it commits to is read from the block's state.
+    if let (Some(outputs), Some(executed_blocks)) = (intermediate_outputs, executed_blocks) {
+        for tip in executed_blocks {
+            outputs.push(intermediate_output(
+                tip,
+                trie_provider.clone(),
+                l2_provider.clone(),
+            )?);
+        }
+    }
+
     ////////////////////////////////////////////////////////////////
     //                          EPILOGUE                          //
     ////////////////////////////////////////////////////////////////
 
-    if output_root != boot.claimed_l2_output_root {
-        return Err(anyhow!(
-            "Failed to validate L2 block #{number} with claimed output root {claimed_output_root}. Got {output_root} instead",
-            number = safe_head.block_info.number,
-            output_root = output_root,
-            claimed_output_root = boot.claimed_l2_output_root,
-        ));
+    match claim_mode {
+        ClaimMode::Check => {
+            if output_root != boot.claimed_l2_output_root {
+                return Err(anyhow!(
+                    "Failed to validate L2 block #{number} with claimed output root {claimed_output_root}. Got {output_root} instead",
+                    number = safe_head.block_info.number,
+                    output_root = output_root,
+                    claimed_output_root = boot.claimed_l2_output_root,
+                ));
+            }
+        }
+        ClaimMode::Compute => {
+            // Derivation stops early if the L1 data runs out, and the output root of an earlier
+            // block must not be committed as the claimed block's.
+            if safe_head.block_info.number != boot.claimed_l2_block_number {
+                return Err(anyhow!(
+                    "Failed to compute the output root of L2 block #{claimed}. Derivation stopped at #{number}",
+                    claimed = boot.claimed_l2_block_number,
+                    number = safe_head.block_info.number,
+                ));
+            }
+            if output_root != boot.claimed_l2_output_root {
+                info!(
+                    target: "client",
+                    "Committing output root {output_root} of L2 block #{number} in place of the claimed output root {claimed_output_root}",
+                    number = safe_head.block_info.number,
+                    output_root = output_root,
+                    claimed_output_root = boot.claimed_l2_output_root,
+                );
+            }
+            boot_clone.claimed_l2_output_root = output_root;
+        }
     }
 
     info!(
@@ -165,6 +327,40 @@ where
     Ok(boot_clone)
 }
 
+/// Read the fields of the output root of an executed block, and check that they hash to the output
+/// root the executor computed.
+fn intermediate_output<F, H>(tip: TipCursor, provider: F, hinter: H) -> Result<IntermediateOutput>
+where
+    F: TrieDBProvider,
+    H: TrieHinter,
+{
+    let header = tip.l2_safe_head_header;
+    let number = header.number;
+    let state_root = header.state_root;
+    let block_hash = header.hash();
+    let message_passer_storage_root = read_message_passer_storage_root(header, provider, hinter)?;
+    let output_root = compute_output_root(
+        OUTPUT_ROOT_VERSION_V0,
+        state_root,
+        message_passer_storage_root,
+        block_hash,
+    );
+    if output_root != tip.l2_safe_head_output_root {
+        return Err(anyhow!(
+            "The fields of the output root of L2 block #{number} hash to {output_root}, but the \
+             executor computed {executed}",
+            executed = tip.l2_safe_head_output_root
+        ));
+    }
+    Ok(IntermediateOutput {
+        l2BlockNumber: number,
+        stateRoot: state_root,
+        messagePasserStorageRoot: message_passer_storage_root,
+        blockHash: block_hash,
+        outputRoot: output_root,
+    })
+}
+
 /// Fetches the safe head hash of the L2 chain based on the agreed upon L2 output root in the
 /// [BootInfo].
 async fn fetch_safe_head_hash<O>(
@@ -199,21 +395,31 @@ where
 ///
 /// ## Takes
 /// - `cfg`: The rollup configuration.
+/// - `l1_provider`: The provider of the L1 origin headers the L1 attributes are checked against.
 /// - `target`: The target block number.
+/// - `empty_batches`: If set, produces the attributes of every block in place of the pipeline.
+/// - `executed_blocks`: If set, the tip cursor of each executed block is appended to it.
 ///
 /// ## Returns
 /// - `Ok((number, output_root))` - A tuple containing the number of the produced block and the
 ///   output root.
-/// - `Err(e)` - An error if the block could not be produced.
-pub async fn advance_to_target<E, DP, P>(
+/// - `Err(e)` - An error if the block could not be produced. A derived block whose L1 attributes
+///   deposit is invalid fails with an [L1AttributesError].
+pub async fn advance_to_target<E, DP, P, L1, AB>(
     driver: &mut Driver<E, DP, P>,
     cfg: &RollupConfig,
+    l1_provider: &mut L1,
     mut target: Option<u64>,
-) -> DriverResult<(L2BlockInfo, B256), E::Error>
+    mut empty_batches: Option<&mut EmptyBatchDeriver<AB>>,
+    mut executed_blocks: Option<&mut Vec<TipCursor>>,
+) -> Result<(L2BlockInfo, B256)>
 where
     E: Executor + Send + Sync + Debug,
+    E::Error: Send + Sync + 'static,
     DP: DriverPipeline<P> + Send + Sync + Debug,
     P: Pipeline + SignalReceiver + Send + Sync + Debug,
+    L1: ChainProvider + Send,
+    AB: AttributesBuilder + Send,
 {
     loop {
         // Check if we have reached the target block number.
@@ -228,11 +434,15 @@ where
 
         #[cfg(target_os = "zkvm")]
         println!("cycle-tracker-report-start: payload-derivation");
-        let OpAttributesWithParent { mut attributes, .. } = match driver
-            .pipeline
-            .produce_payload(tip_cursor.l2_safe_head)
-            .await
-        {
+        let produced = match empty_batches.as_deref_mut() {
+            Some(deriver) => deriver.next_attributes(tip_cursor.l2_safe_head).await,
+            None => driver
+                .pipeline
+                .produce_payload(tip_cursor.l2_safe_head)
+                .await
+                .map(|attrs| attrs.attributes),
+        };
+        let mut attributes = match produced {
             Ok(attrs) => attrs,
             Err(PipelineErrorKind::Critical(PipelineError::EndOfSource)) => {
                 warn!(target: "client", "Exhausted data source; Halting derivation and using current safe head.");
@@ -253,12 +463,35 @@ where
             }
             Err(e) => {
                 error!(target: "client", "Failed to produce payload: {:?}", e);
-                return Err(DriverError::Pipeline(e));
+                return Err(DriverError::<E::Error>::Pipeline(e).into());
             }
         };
         #[cfg(target_os = "zkvm")]
         println!("cycle-tracker-report-end: payload-derivation");
 
+        // Check the L1 attributes deposit before executing the block, so a bad batch or witness
+        // is reported as such rather than as a mismatched output root.
+        let parent = tip_cursor.l2_safe_head;
+        let l2_block_number = parent.block_info.number + 1;
+        let l1_attributes = decode_l1_attributes(
+            l2_block_number,
+            attributes.transactions.as_deref().unwrap_or_default(),
+        )?;
+        let l1_origin = l1_provider
+            .header_by_hash(l1_attributes.id().hash)
+            .await
+            .map_err(|e| L1AttributesError::BadWitness {
+                l2_block_number,
+                reason: format!("L1 origin {} is missing: {e}", l1_attributes.id().hash),
+            })?;
+        check_l1_attributes(
+            cfg,
+            &parent,
+            attributes.payload_attributes.timestamp,
+            &l1_attributes,
+            &l1_origin,
+        )?;
+
         driver
             .executor
             .update_safe_head(tip_cursor.l2_safe_head_header.clone());
@@ -281,11 +514,7 @@ where
                     driver.pipeline.signal(Signal::FlushChannel).await?;
 
                     // Strip out all transactions that are not deposits.
-                    attributes.transactions = attributes.transactions.map(|txs| {
-                        txs.into_iter()
-                            .filter(|tx| (!tx.is_empty() && tx[0] == OpTxType::Deposit as u8))
-                            .collect::<Vec<_>>()
-                    });
+                    attributes.transactions = attributes.transactions.map(deposits_only);
 
                     // Retry the execution.
                     driver
@@ -298,7 +527,7 @@ where
                                 target: "client",
                                 "Critical - Failed to execute deposit-only block: {e}",
                             );
-                            return Err(DriverError::Executor(e));
+                            return Err(DriverError::Executor(e).into());
                         }
                     }
                 } else {
@@ -310,36 +539,40 @@ where
         #[cfg(target_os = "zkvm")]
         println!("cycle-tracker-report-end: block-execution");
 
+        // The executor builds the header, so a fork it doesn't support shows up as a header
+        // missing that fork's fields, and a block hash that differs from the L2 chain's.
+        let header = execution_result.block_header.inner();
+        check_fork_header_fields(cfg, header)?;
+
         // Construct the block.
+        let transactions = attributes
+            .transactions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|tx| OpTxEnvelope::decode(&mut tx.as_ref()).map_err(DriverError::Rlp))
+            .collect::<DriverResult<Vec<OpTxEnvelope>, E::Error>>()?;
         let block = OpBlock {
-            header: execution_result.block_header.inner().clone(),
-            body: BlockBody {
-                transactions: attributes
-                    .transactions
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|tx| OpTxEnvelope::decode(&mut tx.as_ref()).map_err(DriverError::Rlp))
-                    .collect::<DriverResult<Vec<OpTxEnvelope>, E::Error>>()?,
-                ommers: Vec::new(),
-                withdrawals: None,
-            },
+            header: header.clone(),
+            body: block_body(cfg, header.timestamp, transactions),
         };
 
-        // Get the pipeline origin and update the tip cursor.
-        let origin = driver
-            .pipeline
-            .origin()
-            .ok_or(PipelineError::MissingOrigin.crit())?;
+        // Get the pipeline origin, or the block's epoch on the fast path, and update the tip
+        // cursor.
+        let origin = match empty_batches.as_deref() {
+            Some(deriver) => deriver.origin(),
+            None => driver.pipeline.origin(),
+        }
+        .ok_or(PipelineError::MissingOrigin.crit())?;
         let l2_info =
             L2BlockInfo::from_block_and_genesis(&block, &driver.pipeline.rollup_config().genesis)?;
-        let tip_cursor = TipCursor::new(
-            l2_info,
-            execution_result.block_header,
-            driver
-                .executor
-                .compute_output_root()
-                .map_err(DriverError::Executor)?,
-        );
+        let output_root = driver
+            .executor
+            .compute_output_root()
+            .map_err(DriverError::Executor)?;
+        let tip_cursor = TipCursor::new(l2_info, execution_result.block_header, output_root);
+        if let Some(blocks) = executed_blocks.as_deref_mut() {
+            blocks.push(tip_cursor.clone());
+        }
 
         // Advance the derivation pipeline cursor
         drop(pipeline_cursor);
@@ -347,5 +580,202 @@ where
 
         // Add forget calls to save cycles
         forget(block);
+
+        // The block's witness data is no longer needed.
+        crate::arena::end_block();
+    }
+}
+
+/// Build the body of a block with the fields its hardforks require. Blocks from Canyon (Shanghai)
+/// on have an empty withdrawals list, since withdrawals are initiated through the
+/// L2ToL1MessagePasser instead. Execution layer requests (Prague) are only committed to in the
+/// header, and OP Stack blocks have none.
+pub fn block_body(
+    cfg: &RollupConfig,
+    timestamp: u64,
+    transactions: Vec<OpTxEnvelope>,
+) -> BlockBody<OpTxEnvelope> {
+    BlockBody {
+        transactions,
+        ommers: Vec::new(),
+        withdrawals: cfg.is_canyon_active(timestamp).then(Withdrawals::default),
+    }
+}
+
+/// A field of an executed block's header doesn't match the hardforks active at its timestamp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForkHeaderError {
+    /// The field of an active hardfork is missing.
+    Missing { number: u64, field: &'static str },
+    /// The field of a hardfork that isn't active yet is set.
+    Unexpected { number: u64, field: &'static str },
+    /// The header commits to execution layer requests, which OP Stack blocks don't have.
+    NonEmptyRequests { number: u64, requests_hash: B256 },
+}
+
+impl fmt::Display for ForkHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { number, field } => write!(
+                f,
+                "Executed L2 block {number} has an invalid header: {field} is missing, but its hardfork is active"
+            ),
+            Self::Unexpected { number, field } => write!(
+                f,
+                "Executed L2 block {number} has an invalid header: {field} is set, but its hardfork is not active"
+            ),
+            Self::NonEmptyRequests {
+                number,
+                requests_hash,
+            } => write!(
+                f,
+                "Executed L2 block {number} has an invalid header: requests_hash is {requests_hash}, but OP Stack blocks have no requests"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ForkHeaderError {}
+
+/// Check that a block header has exactly the fields of the hardforks active at its timestamp:
+/// the withdrawals root from Canyon (Shanghai), the blob gas fields and parent beacon block root
+/// from Ecotone (Cancun), and the hash of the empty list of execution layer requests from Isthmus
+/// (Prague). Every field is part of the block hash, so a header built for the wrong forks has a
+/// different hash than the L2 chain's block.
+pub fn check_fork_header_fields(
+    cfg: &RollupConfig,
+    header: &Header,
+) -> Result<(), ForkHeaderError> {
+    let number = header.number;
+    let check = |field: &'static str, active: bool, present: bool| match (active, present) {
+        (true, false) => Err(ForkHeaderError::Missing { number, field }),
+        (false, true) => Err(ForkHeaderError::Unexpected { number, field }),
+        _ => Ok(()),
+    };
+
+    let canyon = cfg.is_canyon_active(header.timestamp);
+    check(
+        "withdrawals_root",
+        canyon,
+        header.withdrawals_root.is_some(),
+    )?;
+
+    let ecotone = cfg.is_ecotone_active(header.timestamp);
+    check("blob_gas_used", ecotone, header.blob_gas_used.is_some())?;
+    check("excess_blob_gas", ecotone, header.excess_blob_gas.is_some())?;
+    check(
+        "parent_beacon_block_root",
+        ecotone,
+        header.parent_beacon_block_root.is_some(),
+    )?;
+
+    let isthmus = cfg.is_isthmus_active(header.timestamp);
+    check("requests_hash", isthmus, header.requests_hash.is_some())?;
+    match header.requests_hash {
+        Some(requests_hash) if requests_hash != EMPTY_REQUESTS_HASH => {
+            Err(ForkHeaderError::NonEmptyRequests {
+                number,
+                requests_hash,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Strip all transactions that are not deposits from an encoded transaction list.
+///
+/// Used to build the deposits-only replacement block after an execution failure. A deposits-only
+/// block is also what the sequencer produces once the max sequencer drift is exceeded, so the
+/// replacement must keep every deposit (including the L1 info transaction) in its original order.
+pub fn deposits_only(txs: Vec<Bytes>) -> Vec<Bytes> {
+    txs.into_iter()
+        .filter(|tx| !tx.is_empty() && tx[0] == OpTxType::Deposit as u8)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deposits_only_keeps_deposits_in_order() {
+        let l1_info = Bytes::from(vec![OpTxType::Deposit as u8, 0x01]);
+        let user_deposit = Bytes::from(vec![OpTxType::Deposit as u8, 0x02]);
+        let eip1559 = Bytes::from(vec![OpTxType::Eip1559 as u8, 0x03]);
+
+        let txs = vec![l1_info.clone(), eip1559, user_deposit.clone()];
+        assert_eq!(deposits_only(txs), vec![l1_info, user_deposit]);
+    }
+
+    #[test]
+    fn test_deposits_only_drops_empty_transactions() {
+        let l1_info = Bytes::from(vec![OpTxType::Deposit as u8]);
+        assert_eq!(
+            deposits_only(vec![Bytes::new(), l1_info.clone()]),
+            vec![l1_info]
+        );
+    }
+
+    #[test]
+    fn test_deposits_only_block_is_unchanged() {
+        // A block produced at the max sequencer drift only contains deposits and must be kept
+        // as-is.
+        let txs = vec![
+            Bytes::from(vec![OpTxType::Deposit as u8, 0x01]),
+            Bytes::from(vec![OpTxType::Deposit as u8, 0x02]),
+        ];
+        assert_eq!(deposits_only(txs.clone()), txs);
+    }
+
+    #[test]
+    fn test_check_fork_header_fields() {
+        let mut cfg = RollupConfig::default();
+        cfg.hardforks.canyon_time = Some(100);
+        cfg.hardforks.ecotone_time = Some(200);
+        cfg.hardforks.isthmus_time = Some(300);
+
+        let mut header = Header {
+            timestamp: 50,
+            ..Default::default()
+        };
+        assert!(check_fork_header_fields(&cfg, &header).is_ok());
+        assert!(block_body(&cfg, header.timestamp, Vec::new())
+            .withdrawals
+            .is_none());
+
+        header.timestamp = 300;
+        header.withdrawals_root = Some(B256::ZERO);
+        header.blob_gas_used = Some(0);
+        header.excess_blob_gas = Some(0);
+        header.parent_beacon_block_root = Some(B256::ZERO);
+        assert_eq!(
+            check_fork_header_fields(&cfg, &header),
+            Err(ForkHeaderError::Missing {
+                number: 0,
+                field: "requests_hash"
+            })
+        );
+
+        header.requests_hash = Some(B256::ZERO);
+        assert_eq!(
+            check_fork_header_fields(&cfg, &header),
+            Err(ForkHeaderError::NonEmptyRequests {
+                number: 0,
+                requests_hash: B256::ZERO
+            })
+        );
+        header.requests_hash = Some(EMPTY_REQUESTS_HASH);
+        assert!(check_fork_header_fields(&cfg, &header).is_ok());
+        assert_eq!(
+            block_body(&cfg, header.timestamp, Vec::new()).withdrawals,
+            Some(Withdrawals::default())
+        );
+    }
+
+    #[test]
+    fn test_deposits_only_no_deposits() {
+        // Legacy transactions start with an RLP list prefix rather than a type byte.
+        let txs = vec![Bytes::from(vec![0xf8, 0x01])];
+        assert!(deposits_only(txs).is_empty());
     }
 }
@@ -0,0 +1,265 @@
+//! A derivation fast path for spans without batches.
+//!
+//! If nothing reaches the batch inbox, the derivation pipeline produces every block of a span from
+//! a forced empty batch once the sequencing window of the block's epoch has expired: a
+//! deposits-only block, whose epoch advances to the next L1 block once the L2 block's timestamp
+//! reaches it. The pipeline still walks every L1 block up to the end of the sequencing window
+//! through its frame, channel and batch stages, reading the receipts of each along the way.
+//!
+//! The fast path checks that the L1 blocks the pipeline would read have no transaction sent to
+//! the batch inbox, and then builds the attributes of the forced empty batches directly from
+//! their epochs. Any transaction to the batch inbox, valid batch or not, makes the span fall back
+//! to full derivation, so the check needs neither the batcher address nor any decoding.
+
+use alloc::collections::VecDeque;
+use alloy_consensus::Transaction;
+use alloy_primitives::B256;
+use anyhow::{anyhow, Result};
+use kona_derive::errors::{PipelineError, PipelineErrorKind};
+use kona_derive::traits::{AttributesBuilder, ChainProvider};
+use maili_genesis::RollupConfig;
+use maili_protocol::{BlockInfo, L2BlockInfo};
+use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+/// The epoch of every block after `safe_head` up to `target`, assuming each is derived from a
+/// forced empty batch, and the last L1 block the pipeline reads to derive them.
+///
+/// `l1_blocks` is the L1 chain by number, up to the L1 head. Returns `None` if a block can't be
+/// derived that way: its empty batch is only forced once the pipeline has read past the end of the
+/// sequencing window of its parent's epoch, so the window must end at or before the L1 head.
+pub fn plan_epochs(
+    cfg: &RollupConfig,
+    safe_head: &L2BlockInfo,
+    target: u64,
+    l1_blocks: &[BlockInfo],
+) -> Option<(Vec<BlockInfo>, u64)> {
+    let start = l1_blocks.first()?.number;
+    let head = l1_blocks.last()?.number;
+    let block = |number: u64| {
+        number
+            .checked_sub(start)
+            .and_then(|index| l1_blocks.get(index as usize))
+    };
+
+    let mut origin = *block(safe_head.l1_origin.number)?;
+    if origin.hash != safe_head.l1_origin.hash {
+        return None;
+    }
+
+    let mut timestamp = safe_head.block_info.timestamp;
+    let mut last_read = origin.number;
+    let mut epochs = Vec::new();
+    for _ in safe_head.block_info.number..target {
+        let expiry = origin.number + cfg.seq_window_size;
+        if expiry > head {
+            return None;
+        }
+        // Unless the window ends at the L1 head, the pipeline only forces the batch after reading
+        // the next L1 block, which may still hold a batch for the next epoch.
+        last_read = (expiry + 1).min(head);
+
+        timestamp += cfg.block_time;
+        if let Some(next) = block(origin.number + 1) {
+            if timestamp >= next.timestamp {
+                origin = *next;
+            }
+        }
+        epochs.push(origin);
+    }
+
+    Some((epochs, last_read))
+}
+
+/// Plan the span after `safe_head` up to `target` as forced empty batches, see [plan_epochs].
+/// Returns `None` if the span must be derived in full, because a block of the span isn't forced
+/// by the L1 head or a transaction was sent to the batch inbox in an L1 block the pipeline would
+/// read, from the `cursor_origin` it starts at.
+pub async fn plan_empty_batches<L1>(
+    cfg: &RollupConfig,
+    l1_provider: &mut L1,
+    l1_head: B256,
+    cursor_origin: BlockInfo,
+    safe_head: &L2BlockInfo,
+    target: u64,
+) -> Result<Option<VecDeque<BlockInfo>>>
+where
+    L1: ChainProvider + Send,
+{
+    // Walk the L1 chain back from the L1 head, so each block is checked against its child's
+    // parent hash.
+    let mut l1_blocks = Vec::new();
+    let mut hash = l1_head;
+    loop {
+        let header = l1_provider
+            .header_by_hash(hash)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch L1 block {hash}: {e}"))?;
+        l1_blocks.push(BlockInfo {
+            hash,
+            number: header.number,
+            parent_hash: header.parent_hash,
+            timestamp: header.timestamp,
+        });
+        if header.number <= cursor_origin.number {
+            break;
+        }
+        hash = header.parent_hash;
+    }
+    l1_blocks.reverse();
+
+    if l1_blocks[0] != cursor_origin {
+        return Err(anyhow!(
+            "L1 block {} is not the pipeline cursor origin {}",
+            l1_blocks[0].hash,
+            cursor_origin.hash
+        ));
+    }
+
+    let Some((epochs, last_read)) = plan_epochs(cfg, safe_head, target, &l1_blocks) else {
+        return Ok(None);
+    };
+
+    let read = (last_read - cursor_origin.number) as usize;
+    for block in &l1_blocks[..=read] {
+        let (_, transactions) = l1_provider
+            .block_info_and_transactions_by_hash(block.hash)
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to fetch the transactions of L1 block {}: {e}",
+                    block.hash
+                )
+            })?;
+        if transactions
+            .iter()
+            .any(|tx| tx.to() == Some(cfg.batch_inbox_address))
+        {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(epochs.into()))
+}
+
+/// Produces the attributes of the blocks planned by [plan_empty_batches], in place of the
+/// derivation pipeline.
+#[derive(Debug)]
+pub struct EmptyBatchDeriver<B> {
+    builder: B,
+    epochs: VecDeque<BlockInfo>,
+    origin: Option<BlockInfo>,
+}
+
+impl<B: AttributesBuilder> EmptyBatchDeriver<B> {
+    pub fn new(builder: B, epochs: VecDeque<BlockInfo>) -> Self {
+        Self {
+            builder,
+            epochs,
+            origin: None,
+        }
+    }
+
+    /// The epoch of the last block produced, which stands in for the pipeline's L1 origin.
+    pub fn origin(&self) -> Option<BlockInfo> {
+        self.origin
+    }
+
+    /// Build the attributes of the block after `parent`: the deposits of its epoch and nothing
+    /// else, as the attributes queue does for an empty batch. Fails with
+    /// [PipelineError::EndOfSource] once every planned block is produced.
+    pub async fn next_attributes(
+        &mut self,
+        parent: L2BlockInfo,
+    ) -> Result<OpPayloadAttributes, PipelineErrorKind> {
+        let epoch = self
+            .epochs
+            .pop_front()
+            .ok_or_else(|| PipelineError::EndOfSource.crit())?;
+        let mut attributes = self
+            .builder
+            .prepare_payload_attributes(parent, epoch.id())
+            .await?;
+        attributes.no_tx_pool = Some(true);
+        self.origin = Some(epoch);
+        Ok(attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumHash;
+
+    fn l1_chain(start: u64, len: u64) -> Vec<BlockInfo> {
+        (start..start + len)
+            .map(|number| BlockInfo {
+                hash: B256::with_last_byte(number as u8),
+                number,
+                timestamp: number * 12,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn safe_head(origin: u64, timestamp: u64) -> L2BlockInfo {
+        L2BlockInfo {
+            block_info: BlockInfo {
+                number: 100,
+                timestamp,
+                ..Default::default()
+            },
+            l1_origin: BlockNumHash {
+                number: origin,
+                hash: B256::with_last_byte(origin as u8),
+            },
+            seq_num: 0,
+        }
+    }
+
+    #[test]
+    fn test_plan_epochs_advances_with_timestamps() {
+        let cfg = RollupConfig {
+            block_time: 6,
+            seq_window_size: 4,
+            ..Default::default()
+        };
+        let l1_blocks = l1_chain(10, 10);
+        // The safe head is at the start of epoch 12, at timestamp 144.
+        let safe_head = safe_head(12, 144);
+
+        let (epochs, last_read) = plan_epochs(&cfg, &safe_head, 104, &l1_blocks).unwrap();
+        let numbers: Vec<u64> = epochs.iter().map(|epoch| epoch.number).collect();
+        // Timestamps 150, 156, 162 and 168: epoch 13 starts at 156 and epoch 14 at 168.
+        assert_eq!(numbers, vec![12, 13, 13, 14]);
+        // The parent of the last block is in epoch 13, whose window ends at 17.
+        assert_eq!(last_read, 18);
+    }
+
+    #[test]
+    fn test_plan_epochs_requires_expired_window() {
+        let cfg = RollupConfig {
+            block_time: 6,
+            seq_window_size: 4,
+            ..Default::default()
+        };
+        let l1_blocks = l1_chain(10, 7);
+        let safe_head = safe_head(12, 144);
+
+        // The window of epoch 12 ends at the L1 head, so both blocks with a parent in it are
+        // forced without reading past the L1 head.
+        let (epochs, last_read) = plan_epochs(&cfg, &safe_head, 102, &l1_blocks).unwrap();
+        assert_eq!(epochs.len(), 2);
+        assert_eq!(last_read, 16);
+        // The third block's parent is in epoch 13, whose window ends after the L1 head.
+        assert!(plan_epochs(&cfg, &safe_head, 103, &l1_blocks).is_none());
+    }
+
+    #[test]
+    fn test_plan_epochs_rejects_unknown_origin() {
+        let cfg = RollupConfig::default();
+        let l1_blocks = l1_chain(10, 7);
+        let mut safe_head = safe_head(12, 144);
+        safe_head.l1_origin.hash = B256::ZERO;
+        assert!(plan_epochs(&cfg, &safe_head, 101, &l1_blocks).is_none());
+    }
+}
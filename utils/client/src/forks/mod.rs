@@ -0,0 +1,108 @@
+//! The hardforks a build of the client can prove.
+//!
+//! The `ecotone`, `fjord`, `granite` and `holocene` features select the newest hardfork the build
+//! supports, along with the ones before it. Without any, the build supports the hardforks before
+//! Ecotone. Cargo unifies features, so a build with `--features fjord` that also enabled a default
+//! `holocene` would silently prove Holocene blocks. The features are mutually exclusive instead, and
+//! enabling more than one fails the build. Deriving or executing blocks after a hardfork with the rules before it produces
+//! a different chain, so blocks after a hardfork outside the build are rejected rather than proven
+//! wrong.
+//!
+//! Setting `OP_SUCCINCT_ROLLUP_CONFIG` to the path of a rollup config when building fails the build
+//! if the config schedules a hardfork outside it.
+
+mod table;
+pub use table::{supported_forks, unsupported_forks, FORKS, KONA_NEWEST_FORK};
+
+use anyhow::{bail, Result};
+use maili_genesis::RollupConfig;
+
+#[cfg(feature = "isthmus")]
+compile_error!(
+    "The pinned kona version doesn't support Isthmus. Update the kona dependencies to a version \
+     that does before enabling the `isthmus` feature."
+);
+
+#[cfg(any(
+    all(
+        feature = "ecotone",
+        any(feature = "fjord", feature = "granite", feature = "holocene")
+    ),
+    all(feature = "fjord", any(feature = "granite", feature = "holocene")),
+    all(feature = "granite", feature = "holocene"),
+))]
+compile_error!(
+    "The hardfork features are mutually exclusive, but more than one is enabled. Build with \
+     `--no-default-features` when selecting a hardfork other than the default, and check that no \
+     other crate in the build enables a different one."
+);
+
+/// The hardforks this build supports.
+pub fn build_forks() -> &'static [&'static str] {
+    supported_forks(|fork| match fork {
+        "ecotone" => cfg!(feature = "ecotone"),
+        "fjord" => cfg!(feature = "fjord"),
+        "granite" => cfg!(feature = "granite"),
+        "holocene" => cfg!(feature = "holocene"),
+        _ => false,
+    })
+}
+
+/// The activation times of the hardforks a rollup config schedules.
+pub fn fork_activations(rollup_config: &RollupConfig) -> [(&'static str, Option<u64>); 9] {
+    let hardforks = &rollup_config.hardforks;
+    [
+        ("regolith", hardforks.regolith_time),
+        ("canyon", hardforks.canyon_time),
+        ("delta", hardforks.delta_time),
+        ("ecotone", hardforks.ecotone_time),
+        ("fjord", hardforks.fjord_time),
+        ("granite", hardforks.granite_time),
+        ("holocene", hardforks.holocene_time),
+        ("isthmus", hardforks.isthmus_time),
+        ("interop", hardforks.interop_time),
+    ]
+}
+
+/// Check that this build supports every hardfork the rollup config activates at or before
+/// `timestamp`.
+pub fn check_build_forks(rollup_config: &RollupConfig, timestamp: u64) -> Result<()> {
+    if let Some((fork, activation)) =
+        unsupported_forks(fork_activations(rollup_config), build_forks(), timestamp).first()
+    {
+        bail!(
+            "The rollup config activates the {fork} hardfork at {activation}, which this build \
+             doesn't support. Supported hardforks: {}.",
+            build_forks().join(", ")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_forks() {
+        assert_eq!(supported_forks(|_| false), ["regolith", "canyon", "delta"]);
+        assert_eq!(
+            supported_forks(|fork| fork == "granite"),
+            ["regolith", "canyon", "delta", "ecotone", "fjord", "granite"]
+        );
+        assert_eq!(
+            supported_forks(|fork| fork == KONA_NEWEST_FORK).last(),
+            Some(&KONA_NEWEST_FORK)
+        );
+    }
+
+    #[test]
+    fn test_check_build_forks() {
+        let mut rollup_config = RollupConfig::default();
+        rollup_config.hardforks.regolith_time = Some(0);
+        rollup_config.hardforks.isthmus_time = Some(200);
+        check_build_forks(&rollup_config, 199).unwrap();
+        // Isthmus is newer than the pinned kona version.
+        assert!(check_build_forks(&rollup_config, 200).is_err());
+    }
+}
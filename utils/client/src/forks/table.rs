@@ -0,0 +1,36 @@
+// This file is also included by the crate's build script, so it only uses `std`.
+
+/// The OP Stack hardforks scheduled by activation time in the rollup config, in activation order.
+pub const FORKS: [&str; 9] = [
+    "regolith", "canyon", "delta", "ecotone", "fjord", "granite", "holocene", "isthmus", "interop",
+];
+
+/// The number of hardforks every build supports: the ones before Ecotone.
+const PRE_ECOTONE_FORKS: usize = 3;
+
+/// The newest hardfork the pinned kona version derives and executes blocks of.
+pub const KONA_NEWEST_FORK: &str = "holocene";
+
+/// The hardforks supported by a build with the fork features `enabled` reports, up to and including
+/// the newest one enabled.
+pub fn supported_forks(enabled: impl Fn(&str) -> bool) -> &'static [&'static str] {
+    let newest = FORKS
+        .iter()
+        .rposition(|fork| enabled(fork))
+        .map_or(PRE_ECOTONE_FORKS, |index| index + 1);
+    &FORKS[..newest.max(PRE_ECOTONE_FORKS)]
+}
+
+/// The hardforks among `activations` that are scheduled at or before `until` but aren't in
+/// `supported`, with their activation times. Forks this file doesn't know are unsupported.
+pub fn unsupported_forks<'a>(
+    activations: impl IntoIterator<Item = (&'a str, Option<u64>)>,
+    supported: &[&str],
+    until: u64,
+) -> Vec<(&'a str, u64)> {
+    activations
+        .into_iter()
+        .filter_map(|(fork, activation)| Some((fork, activation?)))
+        .filter(|(fork, activation)| *activation <= until && !supported.contains(fork))
+        .collect()
+}
@@ -0,0 +1,143 @@
+//! The custom gas token config of the L2 chain.
+//!
+//! Chains with a custom gas token pay fees in an ERC20 on L1 instead of ETH. The token is set by
+//! the `SystemConfig` through a deposit to the `L1Block` predeploy, which stores it with its
+//! decimals, name and symbol. Fees are charged with the same arithmetic as on ETH chains, which
+//! only holds for a token with 18 decimals, so the client reads the config at the agreed L2 head
+//! and rejects any other.
+
+use alloy_consensus::Header;
+use alloy_primitives::{address, keccak256, Address, Sealed, B256, U256};
+use anyhow::{anyhow, bail, Result};
+use kona_executor::{TrieDB, TrieDBProvider};
+use kona_mpt::TrieHinter;
+use revm::Database;
+
+/// The L1Block predeploy, which holds the gas paying token config on custom gas token chains.
+pub const L1_BLOCK_ADDRESS: Address = address!("4200000000000000000000000000000000000015");
+
+/// The gas paying token stored by chains that use ETH as their gas token.
+pub const ETHER_TOKEN_ADDRESS: Address = address!("EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE");
+
+/// The decimals of the native asset the fee arithmetic is written for.
+pub const GAS_PAYING_TOKEN_DECIMALS: u8 = 18;
+
+/// The storage slots of the gas paying token config in the L1Block predeploy: the token (packed with
+/// its decimals), its name and its symbol. Each slot is `keccak256(key) - 1`, as in the
+/// `GasPayingToken` library.
+pub fn gas_paying_token_slots() -> [B256; 3] {
+    [
+        "opstack.gaspayingtoken",
+        "opstack.gaspayingtokenname",
+        "opstack.gaspayingtokensymbol",
+    ]
+    .map(|key| B256::from(U256::from_be_bytes(keccak256(key).0) - U256::from(1)))
+}
+
+/// A custom gas token, as stored in the L1Block predeploy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasPayingToken {
+    pub token: Address,
+    pub decimals: u8,
+}
+
+impl GasPayingToken {
+    /// Decode the token slot of the L1Block predeploy, or `None` if the chain uses ETH. The lower
+    /// 20 bytes of the slot hold the token address, the byte above them the decimals.
+    pub fn decode(slot: U256) -> Option<Self> {
+        let word = B256::from(slot);
+        let token = Address::from_word(word);
+        if token == Address::ZERO || token == ETHER_TOKEN_ADDRESS {
+            return None;
+        }
+        Some(Self {
+            token,
+            decimals: word[11],
+        })
+    }
+
+    /// Check that fees in the token are charged correctly by the client's execution.
+    pub fn check(&self) -> Result<()> {
+        if self.decimals != GAS_PAYING_TOKEN_DECIMALS {
+            bail!(
+                "Custom gas token {} has {} decimals, only {GAS_PAYING_TOKEN_DECIMALS} are supported",
+                self.token,
+                self.decimals
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Read the custom gas token of the L2 chain from the state of the given block, or `None` if the
+/// chain uses ETH. The account and storage proofs are hinted, so the host adds them to the witness.
+pub fn read_gas_paying_token<F, H>(
+    header: Sealed<Header>,
+    provider: F,
+    hinter: H,
+) -> Result<Option<GasPayingToken>>
+where
+    F: TrieDBProvider,
+    H: TrieHinter,
+{
+    let mut db = TrieDB::new(header, provider, hinter);
+    // The storage root of an account is only known to the trie DB once the account is loaded.
+    if db
+        .basic(L1_BLOCK_ADDRESS)
+        .map_err(|e| anyhow!("Failed to load the L1Block predeploy: {e}"))?
+        .is_none()
+    {
+        return Ok(None);
+    }
+    let slot = db
+        .storage(
+            L1_BLOCK_ADDRESS,
+            U256::from_be_bytes(gas_paying_token_slots()[0].0),
+        )
+        .map_err(|e| anyhow!("Failed to read the gas paying token: {e}"))?;
+    Ok(GasPayingToken::decode(slot))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::b256;
+
+    use super::*;
+
+    #[test]
+    fn test_gas_paying_token_slots() {
+        assert_eq!(
+            gas_paying_token_slots(),
+            [
+                b256!("04adb1412b2ddc16fcc0d4538d5c8f07cf9c83abecc6b41f6f69037b708fbceb"),
+                b256!("657c3582c29b3176614e3a33ddd1ec48352696a04e92b3c0566d72010fa8863c"),
+                b256!("a48b38a4b44951360fbdcbfaaeae5ed6ae92585412e9841b70ec72ed8cd05763"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_gas_paying_token() {
+        let token = address!("1111111111111111111111111111111111111111");
+        let slot = (U256::from(18) << 160) | U256::from_be_bytes(token.into_word().0);
+        let decoded = GasPayingToken::decode(slot).unwrap();
+        assert_eq!(
+            decoded,
+            GasPayingToken {
+                token,
+                decimals: 18
+            }
+        );
+        assert!(decoded.check().is_ok());
+
+        let slot = (U256::from(6) << 160) | U256::from_be_bytes(token.into_word().0);
+        assert!(GasPayingToken::decode(slot).unwrap().check().is_err());
+    }
+
+    #[test]
+    fn test_decode_ether() {
+        assert_eq!(GasPayingToken::decode(U256::ZERO), None);
+        let slot = (U256::from(18) << 160) | U256::from_be_bytes(ETHER_TOKEN_ADDRESS.into_word().0);
+        assert_eq!(GasPayingToken::decode(slot), None);
+    }
+}
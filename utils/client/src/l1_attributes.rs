@@ -0,0 +1,266 @@
+//! Checks of the L1 attributes deposit of each derived block.
+//!
+//! The first transaction of every L2 block is the L1 attributes deposit, which sets the block's L1
+//! origin (its epoch) and the L1 fee parameters it executes with. Derivation builds it from the
+//! batch's epoch and the L1 origin's header, so a deposit that doesn't follow from the parent block
+//! and the L1 origin either comes from a batch that should have been dropped, or from a witness
+//! whose L1 data doesn't match the L1 chain. The client checks the deposit before executing each
+//! block and reports which of the two it is, instead of failing later with a mismatched output
+//! root.
+
+use alloc::{format, string::String, vec::Vec};
+use alloy_consensus::Header;
+use alloy_primitives::{Bytes, U256};
+use alloy_rlp::Decodable;
+use core::fmt;
+use maili_genesis::RollupConfig;
+use maili_protocol::{L1BlockInfoTx, L2BlockInfo};
+use op_alloy_consensus::OpTxEnvelope;
+
+/// The L1 attributes deposit of a derived block doesn't follow from its parent and L1 origin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum L1AttributesError {
+    /// The batch the block was derived from is invalid: the block's epoch, sequence number or
+    /// timestamp doesn't follow from its parent.
+    BadBatch {
+        l2_block_number: u64,
+        reason: String,
+    },
+    /// The L1 origin header in the witness doesn't match the L1 attributes derived from it.
+    BadWitness {
+        l2_block_number: u64,
+        reason: String,
+    },
+}
+
+impl fmt::Display for L1AttributesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadBatch {
+                l2_block_number,
+                reason,
+            } => write!(
+                f,
+                "Bad batch: the L1 attributes of L2 block {l2_block_number} are invalid: {reason}"
+            ),
+            Self::BadWitness {
+                l2_block_number,
+                reason,
+            } => write!(
+                f,
+                "Bad witness: the L1 origin of L2 block {l2_block_number} doesn't match its L1 attributes: {reason}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for L1AttributesError {}
+
+/// Decode the L1 attributes deposit, the first transaction of a derived block.
+pub fn decode_l1_attributes(
+    l2_block_number: u64,
+    transactions: &[Bytes],
+) -> Result<L1BlockInfoTx, L1AttributesError> {
+    let bad_batch = |reason: String| L1AttributesError::BadBatch {
+        l2_block_number,
+        reason,
+    };
+
+    let first = transactions
+        .first()
+        .ok_or_else(|| bad_batch("the block has no transactions".into()))?;
+    let input = match OpTxEnvelope::decode(&mut first.as_ref()) {
+        Ok(OpTxEnvelope::Deposit(tx)) => tx.inner().input.clone(),
+        Ok(_) => return Err(bad_batch("the first transaction isn't a deposit".into())),
+        Err(e) => {
+            return Err(bad_batch(format!(
+                "the first transaction can't be decoded: {e}"
+            )))
+        }
+    };
+    L1BlockInfoTx::decode_calldata(&input).map_err(|e| {
+        bad_batch(format!(
+            "the first deposit isn't an L1 attributes deposit: {e}"
+        ))
+    })
+}
+
+/// Check the L1 attributes deposit of the block after `parent` against the parent and the header
+/// of the L1 origin the deposit names.
+///
+/// The L1 origin must be the parent's, with the next sequence number, or the child of the parent's
+/// L1 origin, with sequence number 0, and the block must not be older than its L1 origin. The header must
+/// hash to the L1 origin's hash, and have the number and base fee of the deposit.
+pub fn check_l1_attributes(
+    cfg: &RollupConfig,
+    parent: &L2BlockInfo,
+    timestamp: u64,
+    l1_attributes: &L1BlockInfoTx,
+    l1_origin: &Header,
+) -> Result<(), L1AttributesError> {
+    let l2_block_number = parent.block_info.number + 1;
+    let mut witness_errors = Vec::new();
+    let mut batch_errors = Vec::new();
+    let origin = l1_attributes.id();
+
+    // The L1 origin's header is looked up by the hash in the deposit, so any difference between
+    // the two is the witness's.
+    let hash = l1_origin.hash_slow();
+    if hash != origin.hash {
+        witness_errors.push(format!(
+            "the header of L1 origin {} hashes to {hash}",
+            origin.hash
+        ));
+    }
+    if l1_origin.number != origin.number {
+        witness_errors.push(format!(
+            "the header has number {}, not {}",
+            l1_origin.number, origin.number
+        ));
+    }
+    let base_fee = U256::from(l1_origin.base_fee_per_gas.unwrap_or_default());
+    if base_fee != l1_attributes.l1_base_fee() {
+        witness_errors.push(format!(
+            "the header has base fee {base_fee}, not {}",
+            l1_attributes.l1_base_fee()
+        ));
+    }
+    if !witness_errors.is_empty() {
+        return Err(L1AttributesError::BadWitness {
+            l2_block_number,
+            reason: witness_errors.join(", "),
+        });
+    }
+
+    let expected_timestamp = parent.block_info.timestamp + cfg.block_time;
+    if timestamp != expected_timestamp {
+        batch_errors.push(format!(
+            "the timestamp is {timestamp}, not {expected_timestamp}"
+        ));
+    }
+    let sequence_number = l1_attributes.sequence_number();
+    if origin.number == parent.l1_origin.number {
+        if origin.hash != parent.l1_origin.hash {
+            batch_errors.push(format!(
+                "the L1 origin {} has the parent block's L1 origin number, but not its hash {}",
+                origin.hash, parent.l1_origin.hash
+            ));
+        }
+        if sequence_number != parent.seq_num + 1 {
+            batch_errors.push(format!(
+                "the sequence number is {sequence_number}, not {}",
+                parent.seq_num + 1
+            ));
+        }
+    } else if origin.number == parent.l1_origin.number + 1 {
+        if sequence_number != 0 {
+            batch_errors.push(format!(
+                "the sequence number is {sequence_number} in a new epoch"
+            ));
+        }
+        if l1_origin.parent_hash != parent.l1_origin.hash {
+            batch_errors.push(format!(
+                "the L1 origin's parent is {}, not the parent block's L1 origin {}",
+                l1_origin.parent_hash, parent.l1_origin.hash
+            ));
+        }
+    } else {
+        batch_errors.push(format!(
+            "the L1 origin {} doesn't follow the parent block's L1 origin {}",
+            origin.number, parent.l1_origin.number
+        ));
+    }
+    if timestamp < l1_origin.timestamp {
+        batch_errors.push(format!(
+            "the block is older than its L1 origin, at {}",
+            l1_origin.timestamp
+        ));
+    }
+    if !batch_errors.is_empty() {
+        return Err(L1AttributesError::BadBatch {
+            l2_block_number,
+            reason: batch_errors.join(", "),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::B256;
+    use maili_protocol::{BlockInfo, L1BlockInfoBedrock};
+
+    #[test]
+    fn test_check_l1_attributes() {
+        let cfg = RollupConfig {
+            block_time: 2,
+            ..Default::default()
+        };
+        let epoch = Header {
+            number: 10,
+            timestamp: 1000,
+            base_fee_per_gas: Some(7),
+            ..Default::default()
+        };
+        let parent = L2BlockInfo {
+            block_info: BlockInfo {
+                number: 100,
+                timestamp: 1002,
+                ..Default::default()
+            },
+            l1_origin: BlockNumHash {
+                number: 9,
+                hash: epoch.parent_hash,
+            },
+            seq_num: 3,
+        };
+        let l1_attributes = |sequence_number| {
+            L1BlockInfoTx::Bedrock(L1BlockInfoBedrock {
+                number: 10,
+                time: 1000,
+                base_fee: 7,
+                block_hash: epoch.hash_slow(),
+                sequence_number,
+                ..Default::default()
+            })
+        };
+
+        // The first block of the next epoch.
+        assert_eq!(
+            check_l1_attributes(&cfg, &parent, 1004, &l1_attributes(0), &epoch),
+            Ok(())
+        );
+        // A skipped timestamp and a sequence number carried over into the new epoch.
+        let err = check_l1_attributes(&cfg, &parent, 1006, &l1_attributes(4), &epoch).unwrap_err();
+        assert!(matches!(
+            err,
+            L1AttributesError::BadBatch {
+                l2_block_number: 101,
+                ..
+            }
+        ));
+
+        // A witness serving a different header for the L1 origin.
+        let other = Header {
+            base_fee_per_gas: Some(8),
+            ..epoch.clone()
+        };
+        let err = check_l1_attributes(&cfg, &parent, 1004, &l1_attributes(0), &other).unwrap_err();
+        assert!(matches!(err, L1AttributesError::BadWitness { .. }));
+        assert!(err.to_string().starts_with("Bad witness"));
+
+        // The same epoch as the parent must have the parent's L1 origin hash.
+        let parent = L2BlockInfo {
+            l1_origin: BlockNumHash {
+                number: 10,
+                hash: B256::repeat_byte(1),
+            },
+            ..parent
+        };
+        let err = check_l1_attributes(&cfg, &parent, 1004, &l1_attributes(4), &epoch).unwrap_err();
+        assert!(matches!(err, L1AttributesError::BadBatch { .. }));
+    }
+}
@@ -1,16 +1,41 @@
 mod hasher;
 pub use hasher::BytesHasherBuilder;
 
+pub mod arena;
+
+pub mod block_on;
+
 pub mod boot;
-pub use boot::AGGREGATION_OUTPUTS_SIZE;
+
+pub mod empty_batches;
+
+pub mod forks;
+
+pub mod gas_token;
 
 mod oracle;
-pub use oracle::{InMemoryOracle, StoreOracle};
+pub use oracle::{
+    l1_receipts_key, serialize_pages, InMemoryOracle, L1ReceiptsOracle, PagedOracle, StoreOracle,
+    PAGE_SIZE,
+};
+
+pub mod l1_attributes;
+
+pub mod message_passer;
+
+pub mod panic_report;
 
 pub mod precompiles;
 
+pub mod public_values;
+pub use public_values::AGGREGATION_OUTPUTS_SIZE;
+
+pub mod trie_cache;
+
 pub mod types;
 
+pub mod witness;
+
 extern crate alloc;
 
 pub mod client;
@@ -0,0 +1,39 @@
+//! The storage root of the L2ToL1MessagePasser, which the output root of a block commits to.
+//!
+//! Withdrawals are initiated by storing their hash in the message passer, and proven on L1 against
+//! an output root with a storage proof under the message passer's storage root. The executor only
+//! returns the output root of a block, so the client reads the storage root from the block's state
+//! to commit to it alongside the output root.
+
+use alloy_consensus::Header;
+use alloy_primitives::{address, Address, Sealed, B256};
+use anyhow::{anyhow, Result};
+use kona_executor::{TrieDB, TrieDBProvider};
+use kona_mpt::TrieHinter;
+
+/// The L2ToL1MessagePasser predeploy.
+pub const L2_TO_L1_MESSAGE_PASSER_ADDRESS: Address =
+    address!("4200000000000000000000000000000000000016");
+
+/// Read the storage root of the L2ToL1MessagePasser from the state of the given block. The account
+/// proof is hinted, so the host adds it to the witness.
+pub fn read_message_passer_storage_root<F, H>(
+    header: Sealed<Header>,
+    provider: F,
+    hinter: H,
+) -> Result<B256>
+where
+    F: TrieDBProvider,
+    H: TrieHinter,
+{
+    let number = header.number;
+    hinter
+        .hint_account_proof(L2_TO_L1_MESSAGE_PASSER_ADDRESS, number)
+        .map_err(|e| anyhow!("Failed to hint the L2ToL1MessagePasser proof: {e}"))?;
+    let mut db = TrieDB::new(header, provider, hinter);
+    let account = db
+        .get_trie_account(&L2_TO_L1_MESSAGE_PASSER_ADDRESS, number)
+        .map_err(|e| anyhow!("Failed to load the L2ToL1MessagePasser at block {number}: {e}"))?
+        .ok_or_else(|| anyhow!("The L2ToL1MessagePasser doesn't exist at block {number}"))?;
+    Ok(account.storage_root)
+}
@@ -10,36 +10,75 @@ use kona_preimage::{
 };
 use kona_proof::FlushableCache;
 use kzg_rs::{get_kzg_settings, Blob as KzgRsBlob, Bytes48};
-use rkyv::{from_bytes, Archive};
+use rkyv::Archive;
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use spin::Mutex;
+use std::collections::{HashMap, HashSet};
 
 use super::StoreOracle;
+use crate::witness::{Rkyv, WitnessSerializer};
 
 /// An in-memory HashMap that will serve as the oracle for the zkVM.
-/// Rather than relying on a trusted host for data, the data in this oracle
-/// is verified with the `verify()` function, and then is trusted for
-/// the remainder of execution.
+/// Rather than relying on a trusted host for data, the data in this oracle is verified before it
+/// is used: blobs are verified up front by `verify()`, and hash-keyed preimages are verified the
+/// first time they are read.
 ///
 /// Note: [u8; 32] is the key type, as serializing and deserializing PreimageKey is expensive.
 #[derive(
-    Debug, Clone, serde::Serialize, serde::Deserialize, Archive, rkyv::Serialize, rkyv::Deserialize,
+    Debug, serde::Serialize, serde::Deserialize, Archive, rkyv::Serialize, rkyv::Deserialize,
 )]
 pub struct InMemoryOracle {
     pub cache: HashMap<[u8; 32], Vec<u8>, BytesHasherBuilder>,
+    /// The keys whose values have already been verified.
+    #[serde(skip)]
+    #[rkyv(with = rkyv::with::Skip)]
+    verified: Mutex<HashSet<[u8; 32], BytesHasherBuilder>>,
+    /// The keys in the order the client first read them during witness generation, if known. Only
+    /// used to lay out paged witnesses, so it isn't serialized.
+    #[serde(skip)]
+    #[rkyv(with = rkyv::with::Skip)]
+    read_order: Vec<[u8; 32]>,
+}
+
+impl Clone for InMemoryOracle {
+    fn clone(&self) -> Self {
+        Self {
+            cache: self.cache.clone(),
+            verified: Mutex::new(self.verified.lock().clone()),
+            read_order: self.read_order.clone(),
+        }
+    }
 }
 
 impl InMemoryOracle {
-    /// Creates a new [InMemoryOracle] from the raw bytes passed into the zkVM.
-    /// These values are deserialized using rkyv for zero copy deserialization.
-    pub fn from_raw_bytes(input: Vec<u8>) -> Self {
-        println!("cycle-tracker-start: in-memory-oracle-from-raw-bytes-deserialize");
-        let deserialized: InMemoryOracle =
-            from_bytes::<InMemoryOracle, rkyv::rancor::Error>(&input)
-                .expect("failed to deserialize");
-        println!("cycle-tracker-end: in-memory-oracle-from-raw-bytes-deserialize");
+    /// Creates a new [InMemoryOracle] from the preimages, none of which have been verified yet.
+    pub fn new(cache: HashMap<[u8; 32], Vec<u8>, BytesHasherBuilder>) -> Self {
+        Self::with_read_order(cache, Vec::new())
+    }
+
+    /// Creates a new [InMemoryOracle] from the preimages and the order the client first read
+    /// them in.
+    pub fn with_read_order(
+        cache: HashMap<[u8; 32], Vec<u8>, BytesHasherBuilder>,
+        read_order: Vec<[u8; 32]>,
+    ) -> Self {
+        Self {
+            cache,
+            verified: Mutex::default(),
+            read_order,
+        }
+    }
 
-        deserialized
+    /// The keys in the order the client first read them during witness generation, or none if the
+    /// order isn't known.
+    pub fn read_order(&self) -> &[[u8; 32]] {
+        &self.read_order
+    }
+
+    /// Creates a new [InMemoryOracle] from the raw bytes passed into the zkVM, serialized with
+    /// rkyv (see [Rkyv]).
+    pub fn from_raw_bytes(input: Vec<u8>) -> Self {
+        Rkyv::deserialize(&input).expect("failed to deserialize")
     }
 
     /// Populates the InMemoryOracle with data from a StoreOracle.
@@ -58,22 +97,47 @@ impl InMemoryOracle {
             let key_bytes: [u8; 32] = (*key).into();
             cache.insert(key_bytes, value.clone());
         }
-        Ok(Self { cache })
+        let read_order = store_oracle
+            .read_order
+            .lock()
+            .iter()
+            .map(|key| (*key).into())
+            .collect();
+        Ok(Self::with_read_order(cache, read_order))
+    }
+
+    /// Get the value for a key, verifying it against the key the first time it is read.
+    ///
+    /// Verifying on first read is sound because every value the client uses is read through this
+    /// function: a value that is never read cannot affect execution, so it does not need to be
+    /// verified. Values that are read are verified before they are returned, exactly as if they
+    /// had been verified up front. Skipping values that are never read (e.g. the host over-fetching
+    /// state) and verifying each value at most once saves cycles for large witnesses.
+    fn get_verified(&self, key: PreimageKey) -> Result<&Vec<u8>, PreimageOracleError> {
+        let key_bytes: [u8; 32] = key.into();
+        let value = self
+            .cache
+            .get(&key_bytes)
+            .ok_or(PreimageOracleError::KeyNotFound)?;
+
+        let mut verified = self.verified.lock();
+        if !verified.contains(&key_bytes) {
+            verify_preimage(&key, value)?;
+            verified.insert(key_bytes);
+        }
+
+        Ok(value)
     }
 }
 
 #[async_trait]
 impl PreimageOracleClient for InMemoryOracle {
     async fn get(&self, key: PreimageKey) -> Result<Vec<u8>, PreimageOracleError> {
-        let key_bytes: [u8; 32] = key.into();
-        self.cache
-            .get(&key_bytes)
-            .cloned()
-            .ok_or_else(|| PreimageOracleError::KeyNotFound)
+        self.get_verified(key).cloned()
     }
 
     async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> Result<(), PreimageOracleError> {
-        let value = self.get(key).await?;
+        let value = self.get_verified(key)?;
         buf.copy_from_slice(value.as_slice());
         Ok(())
     }
@@ -101,12 +165,27 @@ struct Blob {
     kzg_proof: FixedBytes<48>,
 }
 
+/// Verify that a hash-keyed value matches its key. In the zkVM, the hashing is done with SP1's
+/// keccak and sha256 precompiles through the patched `tiny-keccak` and `sha2` crates.
+///
+/// Blob keys aren't hashes of their values, so blobs are only verified up front by
+/// [`InMemoryOracle::verify`], and reading one that wasn't is an error. Precompile results are
+/// computed by the client rather than read from the witness, so they can't be verified at all.
 pub fn verify_preimage(key: &PreimageKey, value: &[u8]) -> PreimageOracleResult<()> {
     let key_type = key.key_type();
     let preimage = match key_type {
         PreimageKeyType::Keccak256 => Some(keccak256(value).0),
         PreimageKeyType::Sha256 => Some(Sha256::digest(value).into()),
-        PreimageKeyType::Precompile | PreimageKeyType::Blob => unimplemented!(),
+        PreimageKeyType::Blob => {
+            return Err(PreimageOracleError::Other(
+                "blob preimage was read before the blobs were verified".into(),
+            ))
+        }
+        PreimageKeyType::Precompile => {
+            return Err(PreimageOracleError::Other(
+                "precompile preimages can't be verified".into(),
+            ))
+        }
         PreimageKeyType::Local | PreimageKeyType::GlobalGeneric => None,
     };
 
@@ -120,80 +199,165 @@ pub fn verify_preimage(key: &PreimageKey, value: &[u8]) -> PreimageOracleResult<
 }
 
 impl InMemoryOracle {
-    /// Verifies all data in the oracle. Once the function has been called, all data in the
-    /// oracle can be trusted for the remainder of execution.
+    /// Verifies the blobs in the oracle. Blob keys are not hashes of their values, so blobs are
+    /// verified up front in a single batched KZG proof check, rather than on first read. All other
+    /// hash-keyed values are verified the first time they are read (see [`Self::get_verified`]).
+    /// Once the function has been called, all data read from the oracle can be trusted.
     ///
     /// TODO(r): Switch to using the BlobProvider to save the witness and verify this.
     pub fn verify(&self) -> AnyhowResult<()> {
-        let mut blobs: HashMap<FixedBytes<48>, Blob, BytesHasherBuilder> =
-            HashMap::with_hasher(BytesHasherBuilder);
+        verify_blobs(
+            self.cache
+                .iter()
+                .map(|(key, value)| (key, value.as_slice())),
+            |key| self.cache.get(key).map(Vec::as_slice),
+            &mut self.verified.lock(),
+        )
+    }
+}
+
+/// Verifies the blobs among the preimages of an oracle in a single batched KZG proof check, marking
+/// them and their blob data keys as verified. `get` looks up a preimage of the oracle.
+pub(crate) fn verify_blobs<'a>(
+    entries: impl Iterator<Item = (&'a [u8; 32], &'a [u8])>,
+    get: impl Fn(&[u8; 32]) -> Option<&'a [u8]>,
+    verified: &mut HashSet<[u8; 32], BytesHasherBuilder>,
+) -> AnyhowResult<()> {
+    let mut blobs: HashMap<FixedBytes<48>, Blob, BytesHasherBuilder> =
+        HashMap::with_hasher(BytesHasherBuilder);
+
+    for (key, value) in entries {
+        let preimage_key = PreimageKey::try_from(*key).unwrap();
+        if preimage_key.key_type() == PreimageKeyType::Blob {
+            // We should verify the keys using the Blob provider.
+            let blob_data_key: [u8; 32] = PreimageKey::new(*key, PreimageKeyType::Keccak256).into();
+            if let Some(blob_data) = get(&blob_data_key) {
+                // The commitment and element index are used to place the blob element, so
+                // they must be verified before use.
+                if verified.insert(blob_data_key) {
+                    verify_preimage(&PreimageKey::try_from(blob_data_key).unwrap(), blob_data)?;
+                }
 
-        for (key, value) in self.cache.iter() {
-            let preimage_key = PreimageKey::try_from(*key).unwrap();
-            if preimage_key.key_type() == PreimageKeyType::Blob {
-                // We should verify the keys using the Blob provider.
-                let blob_data_key: [u8; 32] =
-                    PreimageKey::new(*key, PreimageKeyType::Keccak256).into();
-                if let Some(blob_data) = self.cache.get(&blob_data_key) {
-                    let commitment: FixedBytes<48> = blob_data[..48].try_into().unwrap();
-                    let element_idx_bytes: [u8; 8] = blob_data[72..].try_into().unwrap();
-                    let element_idx: u64 = u64::from_be_bytes(element_idx_bytes);
-
-                    // Blob is stored as one 48 byte element.
-                    if element_idx == 4096 {
-                        blobs
-                            .entry(commitment)
-                            .or_default()
-                            .kzg_proof
-                            .copy_from_slice(value);
-                        continue;
-                    }
-
-                    // Add the 32 bytes of blob data into the correct spot in the blob.
+                let commitment: FixedBytes<48> = blob_data[..48].try_into().unwrap();
+                let element_idx_bytes: [u8; 8] = blob_data[72..].try_into().unwrap();
+                let element_idx: u64 = u64::from_be_bytes(element_idx_bytes);
+
+                // The blob element is verified by the batched KZG proof check below.
+                verified.insert(*key);
+
+                // Blob is stored as one 48 byte element.
+                if element_idx == 4096 {
                     blobs
                         .entry(commitment)
                         .or_default()
-                        .data
-                        .get_mut((element_idx as usize) << 5..(element_idx as usize + 1) << 5)
-                        .map(|slice| {
-                            if slice.iter().all(|&byte| byte == 0) {
-                                slice.copy_from_slice(value);
-                                Ok(())
-                            } else {
-                                Err(anyhow!("trying to overwrite existing blob data"))
-                            }
-                        });
+                        .kzg_proof
+                        .copy_from_slice(value);
+                    continue;
                 }
-            } else {
-                verify_preimage(&preimage_key, value)?;
+
+                // Add the 32 bytes of blob data into the correct spot in the blob.
+                blobs
+                    .entry(commitment)
+                    .or_default()
+                    .data
+                    .get_mut((element_idx as usize) << 5..(element_idx as usize + 1) << 5)
+                    .map(|slice| {
+                        if slice.iter().all(|&byte| byte == 0) {
+                            slice.copy_from_slice(value);
+                            Ok(())
+                        } else {
+                            Err(anyhow!("trying to overwrite existing blob data"))
+                        }
+                    });
             }
         }
+    }
 
-        println!("cycle-tracker-report-start: blob-verification");
-        let commitments: Vec<Bytes48> = blobs
-            .keys()
-            .cloned()
-            .map(|blob| Bytes48::from_slice(&blob.0).unwrap())
-            .collect_vec();
-        let kzg_proofs: Vec<Bytes48> = blobs
-            .values()
-            .map(|blob| Bytes48::from_slice(&blob.kzg_proof.0).unwrap())
-            .collect_vec();
-        let blob_datas: Vec<KzgRsBlob> = blobs
-            .values()
-            .map(|blob| KzgRsBlob::from_slice(&blob.data.0).unwrap())
-            .collect_vec();
-        println!("Verifying {} blobs", blob_datas.len());
-        // Verify reconstructed blobs.
-        kzg_rs::KzgProof::verify_blob_kzg_proof_batch(
-            blob_datas,
-            commitments,
-            kzg_proofs,
-            &get_kzg_settings(),
-        )
-        .map_err(|e| anyhow!("blob verification failed for batch: {:?}", e))?;
-        println!("cycle-tracker-report-end: blob-verification");
+    println!("cycle-tracker-report-start: blob-verification");
+    let commitments: Vec<Bytes48> = blobs
+        .keys()
+        .cloned()
+        .map(|blob| Bytes48::from_slice(&blob.0).unwrap())
+        .collect_vec();
+    let kzg_proofs: Vec<Bytes48> = blobs
+        .values()
+        .map(|blob| Bytes48::from_slice(&blob.kzg_proof.0).unwrap())
+        .collect_vec();
+    let blob_datas: Vec<KzgRsBlob> = blobs
+        .values()
+        .map(|blob| KzgRsBlob::from_slice(&blob.data.0).unwrap())
+        .collect_vec();
+    println!("Verifying {} blobs", blob_datas.len());
+    // Verify reconstructed blobs.
+    let valid = kzg_rs::KzgProof::verify_blob_kzg_proof_batch(
+        blob_datas,
+        commitments,
+        kzg_proofs,
+        &get_kzg_settings(),
+    )
+    .map_err(|e| anyhow!("blob verification failed for batch: {:?}", e))?;
+    if !valid {
+        return Err(anyhow!(
+            "blob verification failed for batch: invalid KZG proof"
+        ));
+    }
+    println!("cycle-tracker-report-end: blob-verification");
 
-        Ok(())
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oracle_with(entries: &[(PreimageKey, &[u8])]) -> InMemoryOracle {
+        let mut cache = HashMap::with_hasher(BytesHasherBuilder);
+        for (key, value) in entries {
+            cache.insert((*key).into(), value.to_vec());
+        }
+        InMemoryOracle::new(cache)
+    }
+
+    #[test]
+    fn test_get_verifies_on_first_read() {
+        let value = b"preimage";
+        let key = PreimageKey::new(keccak256(value).0, PreimageKeyType::Keccak256);
+        let oracle = oracle_with(&[(key, value)]);
+
+        assert_eq!(oracle.get_verified(key).unwrap(), value);
+        assert!(oracle.verified.lock().contains(&<[u8; 32]>::from(key)));
+    }
+
+    #[test]
+    fn test_get_rejects_invalid_preimage() {
+        let key = PreimageKey::new(keccak256(b"preimage").0, PreimageKeyType::Keccak256);
+        let oracle = oracle_with(&[(key, b"tampered")]);
+
+        assert!(matches!(
+            oracle.get_verified(key),
+            Err(PreimageOracleError::InvalidPreimageKey)
+        ));
+    }
+
+    #[test]
+    fn test_unread_invalid_preimage_is_not_verified() {
+        let valid = b"preimage";
+        let valid_key = PreimageKey::new(keccak256(valid).0, PreimageKeyType::Keccak256);
+        let invalid_key = PreimageKey::new(keccak256(b"other").0, PreimageKeyType::Keccak256);
+        let oracle = oracle_with(&[(valid_key, valid), (invalid_key, b"tampered")]);
+
+        // An invalid value that is never read does not affect reads of other values.
+        assert_eq!(oracle.get_verified(valid_key).unwrap(), valid);
+    }
+
+    #[test]
+    fn test_get_rejects_unverified_blob() {
+        let key = PreimageKey::new(keccak256(b"blob").0, PreimageKeyType::Blob);
+        let oracle = oracle_with(&[(key, &[0; 32])]);
+
+        assert!(matches!(
+            oracle.get_verified(key),
+            Err(PreimageOracleError::Other(_))
+        ));
     }
 }
@@ -3,8 +3,17 @@
 mod in_memory_oracle;
 pub use in_memory_oracle::InMemoryOracle;
 
+mod paged_oracle;
+pub use paged_oracle::{serialize_pages, PagedOracle, PAGE_SIZE};
+
 mod store_oracle;
 pub use store_oracle::StoreOracle;
 
+mod receipts_oracle;
+pub use receipts_oracle::{l1_receipts_key, L1ReceiptsOracle};
+
+mod trie_provider;
+pub use trie_provider::OracleTrieDBProvider;
+
 mod blob_provider;
 pub use blob_provider::OPSuccinctOracleBlobProvider;
@@ -0,0 +1,578 @@
+//! An oracle over a witness split into pages, for witnesses too large to deserialize into an
+//! [InMemoryOracle] alongside the buffer they are read from.
+//!
+//! The host splits the preimages into pages of about [PAGE_SIZE] bytes, in the order the client
+//! first read them during witness generation, and writes each page to the stdin as its own buffer
+//! after an index of the page every key is in. The blobs come first, as the range program verifies
+//! them before executing anything. Preimages without a known read order come last, in order of
+//! their keys.
+//!
+//! The oracle reads the index up front, and each page only when a lookup first needs it, so the
+//! witness is copied into memory as execution reaches it rather than before it starts. The stdin
+//! is read in order, so the pages before it are read too, but those were read earlier in witness
+//! generation and are needed already. A value is found by binary search over the index and then
+//! over its page, without copying it. The layout of each page is checked as it is read, and values
+//! are verified against their keys the first time they are read, as in the [InMemoryOracle].
+//!
+//! The index is the number of pages and the number of preimages `n` as little-endian `u32`s,
+//! followed by `n` entries in strictly increasing order of their keys, each the 32 byte key and
+//! its page as a little-endian `u32`. A page is its number of preimages `n` as a little-endian
+//! `u32`, the offset of each preimage from the end of the offsets as `n` little-endian `u32`s, and
+//! the preimages in order of their keys, each as its 32 byte key, its length as a little-endian
+//! `u32` and its value.
+
+use anyhow::{anyhow, ensure, Result};
+use async_trait::async_trait;
+use kona_preimage::{
+    errors::PreimageOracleError, HintWriterClient, PreimageKey, PreimageKeyType,
+    PreimageOracleClient,
+};
+use kona_proof::FlushableCache;
+use spin::{Mutex, Once};
+use std::collections::HashSet;
+
+use super::in_memory_oracle::{verify_blobs, verify_preimage};
+use crate::{BytesHasherBuilder, InMemoryOracle};
+
+/// The size in bytes pages are filled up to. A preimage larger than a page is given a page of its
+/// own.
+pub const PAGE_SIZE: usize = 1 << 20;
+
+const ENTRY_HEADER_SIZE: usize = 32 + 4;
+
+const INDEX_HEADER_SIZE: usize = 4 + 4;
+
+const INDEX_ENTRY_SIZE: usize = 32 + 4;
+
+fn is_blob(key: &[u8; 32]) -> bool {
+    PreimageKey::try_from(*key).is_ok_and(|key| key.key_type() == PreimageKeyType::Blob)
+}
+
+/// Split the preimages of an oracle into the index and pages of a paged witness, in the order
+/// they are written to the stdin.
+pub fn serialize_pages(oracle: &InMemoryOracle, page_size: usize) -> Result<Vec<Vec<u8>>> {
+    let cache = &oracle.cache;
+
+    // The blob elements, with the commitment and index of each, which `verify` reads up front.
+    let mut blob_keys: Vec<&[u8; 32]> = cache.keys().filter(|key| is_blob(key)).collect();
+    let blob_data_keys: Vec<[u8; 32]> = blob_keys
+        .iter()
+        .map(|key| PreimageKey::new(**key, PreimageKeyType::Keccak256).into())
+        .collect();
+    blob_keys.extend(blob_data_keys.iter().filter(|key| cache.contains_key(*key)));
+    blob_keys.sort_unstable();
+    let mut rest: Vec<&[u8; 32]> = cache.keys().collect();
+    rest.sort_unstable();
+
+    let mut seen = HashSet::with_capacity_and_hasher(cache.len(), BytesHasherBuilder);
+    let ordered: Vec<(&[u8; 32], &Vec<u8>)> = blob_keys
+        .into_iter()
+        .chain(oracle.read_order())
+        .chain(rest)
+        .filter_map(|key| Some((key, cache.get(key)?)))
+        .filter(|(key, _)| seen.insert(**key))
+        .collect();
+
+    let mut pages: Vec<Vec<(&[u8; 32], &Vec<u8>)>> = Vec::new();
+    let (mut start, mut size) = (0, 0);
+    for (i, (_, value)) in ordered.iter().enumerate() {
+        let entry_size = 4 + ENTRY_HEADER_SIZE + value.len();
+        if i > start && size + entry_size > page_size {
+            pages.push(ordered[start..i].to_vec());
+            (start, size) = (i, 0);
+        }
+        size += entry_size;
+    }
+    if start < ordered.len() {
+        pages.push(ordered[start..].to_vec());
+    }
+
+    let mut entries: Vec<(&[u8; 32], u32)> = Vec::with_capacity(ordered.len());
+    let mut buffers = Vec::with_capacity(1 + pages.len());
+    for (page, page_entries) in pages.iter_mut().enumerate() {
+        page_entries.sort_unstable_by_key(|(key, _)| *key);
+        let page = u32::try_from(page)?;
+        entries.extend(page_entries.iter().map(|(key, _)| (*key, page)));
+        buffers.push(serialize_page(page_entries)?);
+    }
+    entries.sort_unstable();
+
+    let mut index = Vec::with_capacity(INDEX_HEADER_SIZE + INDEX_ENTRY_SIZE * entries.len());
+    index.extend_from_slice(&u32::try_from(pages.len())?.to_le_bytes());
+    index.extend_from_slice(&u32::try_from(entries.len())?.to_le_bytes());
+    for (key, page) in entries {
+        index.extend_from_slice(key);
+        index.extend_from_slice(&page.to_le_bytes());
+    }
+    buffers.insert(0, index);
+    Ok(buffers)
+}
+
+fn serialize_page(entries: &[(&[u8; 32], &Vec<u8>)]) -> Result<Vec<u8>> {
+    let mut offsets = Vec::with_capacity(4 * (1 + entries.len()));
+    offsets.extend_from_slice(&u32::try_from(entries.len())?.to_le_bytes());
+    let mut body = Vec::new();
+    for (key, value) in entries {
+        offsets.extend_from_slice(&u32::try_from(body.len())?.to_le_bytes());
+        body.extend_from_slice(*key);
+        body.extend_from_slice(&u32::try_from(value.len())?.to_le_bytes());
+        body.extend_from_slice(value);
+    }
+    offsets.extend_from_slice(&body);
+    Ok(offsets)
+}
+
+/// The index of a paged witness, whose layout was checked when it was read.
+#[derive(Debug)]
+struct Index {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl Index {
+    /// Check the layout of the index: its keys are in strictly increasing order and every page
+    /// has at least one of them. Returns the index and the number of keys in each page.
+    fn new(bytes: Vec<u8>) -> Result<(Self, Vec<usize>)> {
+        ensure!(bytes.len() >= INDEX_HEADER_SIZE, "Witness index truncated");
+        let pages = read_u32(&bytes, 0) as usize;
+        let len = read_u32(&bytes, 4) as usize;
+        ensure!(
+            (bytes.len() - INDEX_HEADER_SIZE) % INDEX_ENTRY_SIZE == 0
+                && (bytes.len() - INDEX_HEADER_SIZE) / INDEX_ENTRY_SIZE == len,
+            "Witness index has {} bytes, expected {INDEX_ENTRY_SIZE} per key for {len} keys",
+            bytes.len() - INDEX_HEADER_SIZE
+        );
+        ensure!(pages <= len, "Witness index has more pages than keys");
+        let index = Self { bytes, len };
+
+        let mut page_lens = vec![0; pages];
+        for i in 0..len {
+            let page = index.page(i);
+            ensure!(
+                page < pages,
+                "Witness index points to page {page} of {pages}"
+            );
+            page_lens[page] += 1;
+            if i > 0 {
+                ensure!(
+                    index.key(i - 1) < index.key(i),
+                    "Witness index keys are not sorted"
+                );
+            }
+        }
+        ensure!(
+            page_lens.iter().all(|len| *len > 0),
+            "Witness index has an empty page"
+        );
+        Ok((index, page_lens))
+    }
+
+    fn key(&self, i: usize) -> &[u8; 32] {
+        let start = INDEX_HEADER_SIZE + INDEX_ENTRY_SIZE * i;
+        self.bytes[start..start + 32].try_into().unwrap()
+    }
+
+    fn page(&self, i: usize) -> usize {
+        read_u32(&self.bytes, INDEX_HEADER_SIZE + INDEX_ENTRY_SIZE * i + 32) as usize
+    }
+
+    /// The page a key is in.
+    fn page_of(&self, key: &[u8; 32]) -> Option<usize> {
+        let (mut lo, mut hi) = (0, self.len);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.key(mid).cmp(key) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => return Some(self.page(mid)),
+            }
+        }
+        None
+    }
+
+    fn keys(&self) -> impl Iterator<Item = &[u8; 32]> {
+        (0..self.len).map(|i| self.key(i))
+    }
+}
+
+/// A page of a paged witness, whose layout was checked when it was read.
+#[derive(Debug)]
+struct Page {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl Page {
+    /// Check the layout of a page: its preimages are contiguous, in strictly increasing order of
+    /// their keys, and fill the page.
+    fn new(bytes: Vec<u8>) -> Result<Self> {
+        ensure!(bytes.len() >= 4, "Witness page truncated");
+        let len = read_u32(&bytes, 0) as usize;
+        ensure!(len > 0, "Empty witness page");
+        ensure!(len <= (bytes.len() - 4) / 4, "Witness page truncated");
+        let body_start = 4 + 4 * len;
+        let page = Self { bytes, len };
+
+        let mut expected_offset = 0;
+        for i in 0..len {
+            ensure!(
+                page.offset(i) == expected_offset,
+                "Witness page preimages are not contiguous"
+            );
+            let start = body_start + expected_offset;
+            ensure!(
+                page.bytes.len() >= start + ENTRY_HEADER_SIZE,
+                "Witness page truncated"
+            );
+            let value_len = read_u32(&page.bytes, start + 32) as usize;
+            ensure!(
+                page.bytes.len() - start - ENTRY_HEADER_SIZE >= value_len,
+                "Witness page truncated"
+            );
+            expected_offset += ENTRY_HEADER_SIZE + value_len;
+            if i > 0 {
+                ensure!(
+                    page.key(i - 1) < page.key(i),
+                    "Witness page keys are not sorted"
+                );
+            }
+        }
+        ensure!(
+            page.bytes.len() == body_start + expected_offset,
+            "{} trailing bytes after the witness page",
+            page.bytes.len() - body_start - expected_offset
+        );
+        Ok(page)
+    }
+
+    fn offset(&self, i: usize) -> usize {
+        read_u32(&self.bytes, 4 + 4 * i) as usize
+    }
+
+    fn entry_start(&self, i: usize) -> usize {
+        4 + 4 * self.len + self.offset(i)
+    }
+
+    fn key(&self, i: usize) -> &[u8; 32] {
+        let start = self.entry_start(i);
+        self.bytes[start..start + 32].try_into().unwrap()
+    }
+
+    fn value(&self, i: usize) -> &[u8] {
+        let start = self.entry_start(i) + 32;
+        let len = read_u32(&self.bytes, start) as usize;
+        &self.bytes[start + 4..start + 4 + len]
+    }
+
+    fn get(&self, key: &[u8; 32]) -> Option<&[u8]> {
+        let (mut lo, mut hi) = (0, self.len);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.key(mid).cmp(key) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => return Some(self.value(mid)),
+            }
+        }
+        None
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+/// Reads the pages of a paged witness in order, as lookups need them.
+struct PageReader {
+    read_buffer: Box<dyn FnMut() -> Vec<u8> + Send>,
+    /// The page the next buffer is.
+    next: usize,
+    /// Why a page couldn't be read, after which no more pages are read.
+    error: Option<String>,
+}
+
+impl std::fmt::Debug for PageReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PageReader")
+            .field("next", &self.next)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The oracle over a paged witness. See the [module documentation](self).
+#[derive(Debug)]
+pub struct PagedOracle {
+    index: Index,
+    /// The number of keys in each page, according to the index.
+    page_lens: Vec<usize>,
+    /// The pages read so far.
+    pages: Vec<Once<Page>>,
+    reader: Mutex<PageReader>,
+    /// The keys whose values have already been verified.
+    verified: Mutex<HashSet<[u8; 32], BytesHasherBuilder>>,
+}
+
+impl PagedOracle {
+    /// Read the index of a paged witness with `read_buffer`, which is called again for each page
+    /// as it is needed.
+    pub fn read(mut read_buffer: impl FnMut() -> Vec<u8> + Send + 'static) -> Result<Self> {
+        let (index, page_lens) = Index::new(read_buffer())?;
+        Ok(Self {
+            index,
+            pages: page_lens.iter().map(|_| Once::new()).collect(),
+            page_lens,
+            reader: Mutex::new(PageReader {
+                read_buffer: Box::new(read_buffer),
+                next: 0,
+                error: None,
+            }),
+            verified: Mutex::default(),
+        })
+    }
+
+    /// Get a page, reading it and the pages before it if they haven't been read yet.
+    fn page(&self, page: usize) -> Result<&Page, PreimageOracleError> {
+        if let Some(read) = self.pages[page].get() {
+            return Ok(read);
+        }
+
+        let mut reader = self.reader.lock();
+        while reader.next <= page {
+            if let Some(error) = &reader.error {
+                return Err(PreimageOracleError::Other(error.clone()));
+            }
+            let next = reader.next;
+            let read = Page::new((reader.read_buffer)()).and_then(|read| {
+                ensure!(
+                    read.len == self.page_lens[next],
+                    "Witness page {next} has {} preimages, but the index has {}",
+                    read.len,
+                    self.page_lens[next]
+                );
+                ensure!(
+                    self.index.page_of(read.key(0)) == Some(next),
+                    "Witness page {next} doesn't start with a key the index has in it"
+                );
+                Ok(read)
+            });
+            match read {
+                Ok(read) => {
+                    self.pages[next].call_once(|| read);
+                    reader.next += 1;
+                }
+                Err(e) => reader.error = Some(format!("Failed to read witness page {next}: {e}")),
+            }
+        }
+        Ok(self.pages[page].get().unwrap())
+    }
+
+    /// Look up the value of a key, without verifying it.
+    pub(crate) fn get_unverified(
+        &self,
+        key: &[u8; 32],
+    ) -> Result<Option<&[u8]>, PreimageOracleError> {
+        let Some(page) = self.index.page_of(key) else {
+            return Ok(None);
+        };
+        Ok(self.page(page)?.get(key))
+    }
+
+    /// Get the value for a key, verifying it against the key the first time it is read. See
+    /// [`InMemoryOracle`] for why verifying on first read is sound.
+    fn get_verified(&self, key: PreimageKey) -> Result<&[u8], PreimageOracleError> {
+        let key_bytes: [u8; 32] = key.into();
+        let value = self
+            .get_unverified(&key_bytes)?
+            .ok_or(PreimageOracleError::KeyNotFound)?;
+
+        let mut verified = self.verified.lock();
+        if !verified.contains(&key_bytes) {
+            verify_preimage(&key, value)?;
+            verified.insert(key_bytes);
+        }
+
+        Ok(value)
+    }
+
+    /// The number of preimages in the witness.
+    pub fn len(&self) -> usize {
+        self.index.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.len == 0
+    }
+
+    /// Verifies the blobs in the oracle up front, as [`InMemoryOracle::verify`] does. Only the
+    /// pages holding the blobs are read, which the host writes first.
+    pub fn verify(&self) -> Result<()> {
+        let mut blobs = Vec::new();
+        for key in self.index.keys().filter(|key| is_blob(key)) {
+            let value = self
+                .get_unverified(key)
+                .map_err(|e| anyhow!("{e}"))?
+                .ok_or_else(|| anyhow!("Witness page is missing a key in the index"))?;
+            blobs.push((key, value));
+        }
+        verify_blobs(
+            blobs.into_iter(),
+            |key| self.get_unverified(key).ok().flatten(),
+            &mut self.verified.lock(),
+        )
+    }
+}
+
+#[async_trait]
+impl PreimageOracleClient for PagedOracle {
+    async fn get(&self, key: PreimageKey) -> Result<Vec<u8>, PreimageOracleError> {
+        self.get_verified(key).map(<[u8]>::to_vec)
+    }
+
+    async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> Result<(), PreimageOracleError> {
+        let value = self.get_verified(key)?;
+        buf.copy_from_slice(value);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HintWriterClient for PagedOracle {
+    async fn write(&self, _hint: &str) -> Result<(), PreimageOracleError> {
+        Ok(())
+    }
+}
+
+impl FlushableCache for PagedOracle {
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, VecDeque},
+        sync::Arc,
+    };
+
+    use alloy_primitives::keccak256;
+
+    use super::*;
+
+    fn keccak_entry(value: &[u8]) -> (PreimageKey, Vec<u8>) {
+        (
+            PreimageKey::new(keccak256(value).0, PreimageKeyType::Keccak256),
+            value.to_vec(),
+        )
+    }
+
+    /// Read a paged witness, returning it with the buffers it hasn't read yet.
+    fn read(
+        buffers: Vec<Vec<u8>>,
+    ) -> Result<(PagedOracle, Arc<std::sync::Mutex<VecDeque<Vec<u8>>>>)> {
+        let buffers = Arc::new(std::sync::Mutex::new(VecDeque::from(buffers)));
+        let unread = buffers.clone();
+        let oracle =
+            PagedOracle::read(move || buffers.lock().unwrap().pop_front().unwrap_or_default())?;
+        Ok((oracle, unread))
+    }
+
+    #[test]
+    fn test_pages_round_trip() {
+        let entries: Vec<_> = (0..100u32)
+            .map(|i| keccak_entry(&i.to_le_bytes().repeat(i as usize)))
+            .collect();
+        let mut cache = HashMap::with_hasher(BytesHasherBuilder);
+        for (key, value) in &entries {
+            cache.insert((*key).into(), value.clone());
+        }
+        let buffers = serialize_pages(&InMemoryOracle::new(cache), 1024).unwrap();
+        assert!(buffers.len() > 2);
+
+        let (oracle, unread) = read(buffers).unwrap();
+        assert_eq!(oracle.len(), entries.len());
+        for (key, value) in &entries {
+            assert_eq!(oracle.get_verified(*key).unwrap(), value.as_slice());
+        }
+        assert!(unread.lock().unwrap().is_empty());
+        let (missing, _) = keccak_entry(b"missing");
+        assert!(matches!(
+            oracle.get_verified(missing),
+            Err(PreimageOracleError::KeyNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_pages_read_lazily_in_read_order() {
+        let entries: Vec<_> = (0..20u8).map(|i| keccak_entry(&[i; 100])).collect();
+        let mut cache = HashMap::with_hasher(BytesHasherBuilder);
+        for (key, value) in &entries {
+            cache.insert((*key).into(), value.clone());
+        }
+        let read_order: Vec<[u8; 32]> = entries.iter().map(|(key, _)| (*key).into()).collect();
+        let oracle = InMemoryOracle::with_read_order(cache, read_order);
+        let buffers = serialize_pages(&oracle, 256).unwrap();
+        let pages = buffers.len() - 1;
+        assert!(pages > 2);
+
+        // Only the index is read up front.
+        let (oracle, unread) = read(buffers).unwrap();
+        assert_eq!(unread.lock().unwrap().len(), pages);
+
+        // The preimages read first are in the first page.
+        oracle.get_verified(entries[0].0).unwrap();
+        assert_eq!(unread.lock().unwrap().len(), pages - 1);
+
+        // Reading a later preimage reads the pages up to its own.
+        let (last, value) = entries.last().unwrap();
+        assert_eq!(oracle.get_verified(*last).unwrap(), value.as_slice());
+        assert!(unread.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_rejects_invalid_preimage() {
+        let (key, _) = keccak_entry(b"preimage");
+        let mut cache = HashMap::with_hasher(BytesHasherBuilder);
+        cache.insert(key.into(), b"tampered".to_vec());
+        let (oracle, _) =
+            read(serialize_pages(&InMemoryOracle::new(cache), PAGE_SIZE).unwrap()).unwrap();
+
+        assert!(matches!(
+            oracle.get_verified(key),
+            Err(PreimageOracleError::InvalidPreimageKey)
+        ));
+    }
+
+    #[test]
+    fn test_rejects_malformed_pages() {
+        let mut cache = HashMap::with_hasher(BytesHasherBuilder);
+        for i in 0..10u8 {
+            cache.insert([i; 32], vec![i; 100]);
+        }
+        let buffers = serialize_pages(&InMemoryOracle::new(cache), 256).unwrap();
+        let last = [9; 32];
+        let read_last = |buffers: Vec<Vec<u8>>| -> Result<bool> {
+            let (oracle, _) = read(buffers)?;
+            let value = oracle.get_unverified(&last).map_err(|e| anyhow!("{e}"))?;
+            Ok(value.is_some())
+        };
+        assert!(read_last(buffers.clone()).unwrap());
+
+        // A truncated index.
+        let mut truncated = buffers.clone();
+        truncated[0].pop();
+        assert!(read(truncated).is_err());
+
+        // Pages out of order.
+        let mut swapped = buffers.clone();
+        swapped.swap(1, 2);
+        assert!(read_last(swapped).is_err());
+
+        // A truncated page.
+        let mut truncated = buffers.clone();
+        truncated[1].pop();
+        assert!(read_last(truncated).is_err());
+
+        // A missing page.
+        assert!(read_last(buffers[..buffers.len() - 1].to_vec()).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+//! Batched hydration of L1 receipts tries.
+//!
+//! Deriving an epoch reads the receipts of its L1 origin, for deposits and system config updates.
+//! The L1 chain provider walks the block's receipts trie node by node, so every node is a separate
+//! preimage in the witness, verified and decoded on its own. On deposit-heavy chains, these nodes
+//! make up a large part of the witness.
+//!
+//! Instead, the host serves the block's raw receipts as a single preimage, and the
+//! [`L1ReceiptsOracle`] rebuilds the receipts trie from it when the client hints that it is about
+//! to read the receipts. The trie nodes are then served from memory. Nodes are keyed by their
+//! hash, which is computed here, so serving them is as sound as reading them from the witness: a
+//! wrong receipt list yields a trie whose root doesn't match the block's receipts root, and the
+//! walk fails.
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloy_primitives::{keccak256, Bytes, B256};
+use alloy_rlp::{Decodable, Encodable};
+use alloy_trie::{proof::ProofRetainer, HashBuilder, Nibbles};
+use async_trait::async_trait;
+use core::str::FromStr;
+use kona_preimage::{
+    errors::{PreimageOracleError, PreimageOracleResult},
+    HintWriterClient, PreimageKey, PreimageKeyType, PreimageOracleClient,
+};
+use kona_proof::{FlushableCache, HintType};
+use spin::Mutex;
+use std::collections::HashMap;
+
+/// The key of the preimage holding the RLP list of the raw receipts of an L1 block.
+pub fn l1_receipts_key(block_hash: B256) -> PreimageKey {
+    let hash = keccak256([b"l1-receipts".as_slice(), block_hash.as_slice()].concat());
+    PreimageKey::new(*hash, PreimageKeyType::GlobalGeneric)
+}
+
+/// The root and the RLP encoded nodes of the ordered trie of a list of raw receipts.
+pub fn receipts_trie(receipts: &[Bytes]) -> (B256, Vec<Bytes>) {
+    let mut leaves = receipts
+        .iter()
+        .enumerate()
+        .map(|(index, receipt)| {
+            let mut key = Vec::new();
+            index.encode(&mut key);
+            (Nibbles::unpack(key), receipt)
+        })
+        .collect::<Vec<_>>();
+    // The trie is built from the leaves in key order, which for RLP encoded indices is not the
+    // order of the indices.
+    leaves.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+    let retainer = ProofRetainer::new(leaves.iter().map(|(key, _)| key.clone()).collect());
+    let mut builder = HashBuilder::default().with_proof_retainer(retainer);
+    for (key, receipt) in leaves {
+        builder.add_leaf(key, receipt);
+    }
+    let root = builder.root();
+    let nodes = builder.take_proof_nodes().values().cloned().collect();
+    (root, nodes)
+}
+
+/// An oracle that hydrates the receipts trie of an L1 block from a single preimage when the
+/// client hints that it will read the block's receipts.
+#[derive(Debug)]
+pub struct L1ReceiptsOracle<O> {
+    inner: Arc<O>,
+    /// The hydrated trie nodes, by their keccak256 preimage key.
+    nodes: Mutex<HashMap<PreimageKey, Bytes>>,
+}
+
+impl<O> L1ReceiptsOracle<O> {
+    pub fn new(inner: Arc<O>) -> Self {
+        Self {
+            inner,
+            nodes: Mutex::default(),
+        }
+    }
+}
+
+impl<O> L1ReceiptsOracle<O>
+where
+    O: PreimageOracleClient,
+{
+    async fn hydrate(&self, block_hash: B256) -> PreimageOracleResult<()> {
+        let list = self.inner.get(l1_receipts_key(block_hash)).await?;
+        let receipts = Vec::<Bytes>::decode(&mut list.as_slice())
+            .map_err(|e| PreimageOracleError::Other(e.to_string()))?;
+
+        let (_, nodes) = receipts_trie(&receipts);
+        let mut hydrated = self.nodes.lock();
+        for node in nodes {
+            hydrated.insert(PreimageKey::new_keccak256(*keccak256(&node)), node);
+        }
+        Ok(())
+    }
+}
+
+/// The block hash of an L1 receipts hint.
+fn l1_receipts_hint(hint: &str) -> Option<B256> {
+    let (hint_type, data) = hint.split_once(' ')?;
+    if hint_type != HintType::L1Receipts.to_string() {
+        return None;
+    }
+    B256::from_str(data).ok()
+}
+
+#[async_trait]
+impl<O> PreimageOracleClient for L1ReceiptsOracle<O>
+where
+    O: PreimageOracleClient + Send + Sync,
+{
+    async fn get(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        if let Some(node) = self.nodes.lock().get(&key) {
+            return Ok(node.to_vec());
+        }
+        self.inner.get(key).await
+    }
+
+    async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<()> {
+        if let Some(node) = self.nodes.lock().get(&key) {
+            buf.copy_from_slice(node);
+            return Ok(());
+        }
+        self.inner.get_exact(key, buf).await
+    }
+}
+
+#[async_trait]
+impl<O> HintWriterClient for L1ReceiptsOracle<O>
+where
+    O: PreimageOracleClient + HintWriterClient + Send + Sync,
+{
+    async fn write(&self, hint: &str) -> PreimageOracleResult<()> {
+        self.inner.write(hint).await?;
+        if let Some(block_hash) = l1_receipts_hint(hint) {
+            self.hydrate(block_hash).await?;
+        }
+        Ok(())
+    }
+}
+
+impl<O: FlushableCache> FlushableCache for L1ReceiptsOracle<O> {
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+    use alloy_trie::root::ordered_trie_root_with_encoder;
+
+    #[test]
+    fn test_receipts_trie_matches_ordered_trie_root() {
+        let receipts = (0..200u8)
+            .map(|i| Bytes::from(vec![i; 1 + i as usize]))
+            .collect::<Vec<_>>();
+        let (root, nodes) = receipts_trie(&receipts);
+
+        let expected_root = ordered_trie_root_with_encoder(&receipts, |receipt, buf| {
+            buf.extend_from_slice(receipt)
+        });
+        assert_eq!(root, expected_root);
+        assert!(nodes.iter().any(|node| keccak256(node) == root));
+    }
+
+    #[test]
+    fn test_parse_l1_receipts_hint() {
+        let block_hash = B256::repeat_byte(7);
+        let hint = |hint_type: HintType| format!("{hint_type} {}", hex::encode(block_hash));
+        assert_eq!(
+            l1_receipts_hint(&hint(HintType::L1Receipts)),
+            Some(block_hash)
+        );
+        assert_eq!(l1_receipts_hint(&hint(HintType::L1BlockHeader)), None);
+    }
+}
@@ -27,6 +27,8 @@ where
 {
     /// The spin-locked cache that stores the responses from the oracle.
     pub cache: Arc<Mutex<HashMap<PreimageKey, Vec<u8>>>>,
+    /// The keys of the cache in the order they were first read.
+    pub read_order: Arc<Mutex<Vec<PreimageKey>>>,
     /// Oracle reader type.
     oracle_reader: OR,
     /// Hint writer type.
@@ -45,6 +47,7 @@ where
     pub fn new(oracle_reader: OR, hint_writer: HW) -> Self {
         Self {
             cache: Arc::new(Mutex::new(HashMap::new())),
+            read_order: Arc::new(Mutex::new(Vec::new())),
             oracle_reader,
             hint_writer,
         }
@@ -59,6 +62,7 @@ where
     /// Flushes the cache, removing all entries.
     fn flush(&self) {
         self.cache.lock().clear();
+        self.read_order.lock().clear();
     }
 }
 
@@ -75,6 +79,7 @@ where
         } else {
             let value = self.oracle_reader.get(key).await?;
             cache_lock.insert(key, value.clone());
+            self.read_order.lock().push(key);
             Ok(value)
         }
     }
@@ -89,6 +94,7 @@ where
         } else {
             self.oracle_reader.get_exact(key, buf).await?;
             cache_lock.insert(key, buf.to_vec());
+            self.read_order.lock().push(key);
             Ok(())
         }
     }
@@ -0,0 +1,77 @@
+//! The trie provider of the executor, reading trie nodes, bytecode and headers from the oracle.
+
+use alloc::sync::Arc;
+use alloy_consensus::Header;
+use alloy_primitives::{Bytes, B256};
+use alloy_rlp::Decodable;
+use kona_executor::TrieDBProvider;
+use kona_mpt::{TrieNode, TrieProvider};
+use kona_preimage::{CommsClient, PreimageKey};
+use kona_proof::{errors::OracleProviderError, HintType};
+
+use crate::block_on::block_on;
+
+/// A [TrieDBProvider] over the oracle. Unlike kona's `OracleL2ChainProvider`, it waits on the
+/// oracle with [block_on], so the client can be run both in the zkVM and inside a host's Tokio
+/// runtime.
+#[derive(Debug)]
+pub struct OracleTrieDBProvider<O> {
+    oracle: Arc<O>,
+}
+
+impl<O> OracleTrieDBProvider<O> {
+    pub fn new(oracle: Arc<O>) -> Self {
+        Self { oracle }
+    }
+}
+
+impl<O> Clone for OracleTrieDBProvider<O> {
+    fn clone(&self) -> Self {
+        Self {
+            oracle: self.oracle.clone(),
+        }
+    }
+}
+
+impl<O: CommsClient> OracleTrieDBProvider<O> {
+    async fn hinted_preimage(
+        &self,
+        hint: HintType,
+        hash: B256,
+    ) -> Result<Vec<u8>, OracleProviderError> {
+        hint.with_data(&[hash.as_ref()])
+            .send(self.oracle.as_ref())
+            .await?;
+        self.oracle
+            .get(PreimageKey::new_keccak256(*hash))
+            .await
+            .map_err(OracleProviderError::Preimage)
+    }
+}
+
+impl<O: CommsClient> TrieProvider for OracleTrieDBProvider<O> {
+    type Error = OracleProviderError;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        // Trie nodes are hinted by the executor's trie hinter before they are fetched.
+        block_on(async {
+            let node = self
+                .oracle
+                .get(PreimageKey::new_keccak256(*key))
+                .await
+                .map_err(OracleProviderError::Preimage)?;
+            TrieNode::decode(&mut node.as_slice()).map_err(OracleProviderError::Rlp)
+        })
+    }
+}
+
+impl<O: CommsClient> TrieDBProvider for OracleTrieDBProvider<O> {
+    fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+        block_on(self.hinted_preimage(HintType::L2Code, code_hash)).map(Into::into)
+    }
+
+    fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+        let header = block_on(self.hinted_preimage(HintType::L2BlockHeader, hash))?;
+        Header::decode(&mut header.as_slice()).map_err(OracleProviderError::Rlp)
+    }
+}
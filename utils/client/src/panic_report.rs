@@ -0,0 +1,86 @@
+//! When a program panics in the zkVM, the host only sees that execution halted with a non-zero
+//! exit code. To tell it why, the program's panic hook writes a [`ClientPanicReport`] to
+//! [`FD_PANIC_REPORT`] before aborting, which the host reads back with an executor hook.
+
+use alloc::string::{String, ToString};
+use core::{any::Any, fmt, panic::Location};
+use serde::{Deserialize, Serialize};
+
+/// The file descriptor the panic report is written to. Chosen well above the file descriptors
+/// reserved by SP1 for its own I/O and hooks.
+pub const FD_PANIC_REPORT: u32 = 1000;
+
+/// The panic message and location of a program that panicked in the zkVM.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ClientPanicReport {
+    pub message: String,
+    /// The source location of the panic, as `file:line:column`.
+    pub location: Option<String>,
+}
+
+impl ClientPanicReport {
+    /// Build the report from the payload and location of a panic hook.
+    pub fn new(payload: &(dyn Any + Send), location: Option<&Location<'_>>) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<dyn Any>".to_string()
+        };
+
+        Self {
+            message,
+            location: location.map(|location| location.to_string()),
+        }
+    }
+
+    pub fn to_bytes(&self) -> alloc::vec::Vec<u8> {
+        serde_json::to_vec(self).expect("failed to serialize panic report")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice(bytes).ok()
+    }
+}
+
+/// Install a panic hook that writes a [`ClientPanicReport`] to [`FD_PANIC_REPORT`] with `write`
+/// (`sp1_zkvm::io::write` in the zkVM), then runs the default hook.
+pub fn install_panic_hook(write: fn(u32, &[u8])) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(alloc::boxed::Box::new(move |info| {
+        let report = ClientPanicReport::new(info.payload(), info.location());
+        write(FD_PANIC_REPORT, &report.to_bytes());
+        default_hook(info);
+    }));
+}
+
+impl fmt::Display for ClientPanicReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "program panicked at {}: {}", location, self.message),
+            None => write!(f, "program panicked: {}", self.message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panic_report_roundtrip() {
+        let payload: &(dyn Any + Send) = &"failed to run client";
+        let report = ClientPanicReport::new(payload, Some(Location::caller()));
+        assert_eq!(report.message, "failed to run client");
+        assert!(report
+            .location
+            .as_ref()
+            .unwrap()
+            .contains("panic_report.rs"));
+        assert_eq!(
+            ClientPanicReport::from_bytes(&report.to_bytes()),
+            Some(report)
+        );
+    }
+}
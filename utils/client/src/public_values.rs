@@ -0,0 +1,371 @@
+//! The versioned encoding of the public values the range and aggregation programs commit to.
+//!
+//! The public values are committed as an envelope: a version byte followed by the ABI encoding of
+//! the [`BootInfoStruct`] (range program) or the [`AggregationOutputs`] (aggregation program). A
+//! range program built with `intermediate-outputs` follows the boot info with the ABI encoding of
+//! its [`IntermediateOutput`]s.
+//! Programs built before the envelope committed unversioned public values: the bincode encoding of
+//! the boot info, and the bare ABI encoding of the aggregation outputs without the checkpoint
+//! overrides hash. Decoding accepts both, so proofs of the previous programs can still be aggregated
+//! and proposed while the programs and the contracts are upgraded. The contracts verify each
+//! aggregation vkey's proofs against the version configured with it.
+
+use alloy_sol_types::SolValue;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    boot::BootInfoStruct,
+    types::{AggregationOutputs, IntermediateOutput},
+};
+
+/// The size of the ABI encoding of a [`BootInfoStruct`].
+const BOOT_INFO_SIZE: usize = 5 * 32;
+
+/// The size of the ABI encoding of [`AggregationOutputs`].
+pub const AGGREGATION_OUTPUTS_SIZE: usize = 7 * 32;
+
+/// The size of the unversioned aggregation outputs, which end before the checkpoint overrides hash.
+const UNVERSIONED_AGGREGATION_OUTPUTS_SIZE: usize = 6 * 32;
+
+/// The public values encoding of a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum PublicValuesVersion {
+    /// The encoding of the programs before the envelope, without a version byte.
+    Unversioned = 0,
+    /// A version byte followed by the ABI-encoded public values.
+    V1 = 1,
+}
+
+/// The version the programs of this build commit to.
+pub const CURRENT_PUBLIC_VALUES_VERSION: PublicValuesVersion = PublicValuesVersion::V1;
+
+impl PublicValuesVersion {
+    /// The versions written in an envelope's version byte.
+    const ENVELOPED: [PublicValuesVersion; 1] = [Self::V1];
+
+    fn from_envelope(bytes: &[u8]) -> Option<(Self, &[u8])> {
+        let (tag, payload) = bytes.split_first()?;
+        let version = Self::ENVELOPED
+            .into_iter()
+            .find(|version| *version as u8 == *tag)?;
+        Some((version, payload))
+    }
+
+    fn envelope(self, payload: Vec<u8>) -> Vec<u8> {
+        [vec![self as u8], payload].concat()
+    }
+}
+
+impl TryFrom<u8> for PublicValuesVersion {
+    type Error = anyhow::Error;
+
+    /// The version of the L2OO's `aggregationOutputsVersion`.
+    fn try_from(version: u8) -> Result<Self> {
+        [Self::Unversioned, Self::V1]
+            .into_iter()
+            .find(|v| *v as u8 == version)
+            .ok_or_else(|| anyhow!("Unknown public values version {version}"))
+    }
+}
+
+/// Encode the boot info the range program commits to.
+pub fn encode_boot_info(version: PublicValuesVersion, boot_info: &BootInfoStruct) -> Vec<u8> {
+    match version {
+        PublicValuesVersion::Unversioned => {
+            bincode::serialize(boot_info).expect("boot info serializes")
+        }
+        PublicValuesVersion::V1 => version.envelope(boot_info.abi_encode()),
+    }
+}
+
+/// Encode the public values the range program commits to: the boot info, followed by the
+/// intermediate outputs if the program commits to them.
+///
+/// Panics if unversioned public values have intermediate outputs, which the programs before the
+/// envelope couldn't commit to.
+pub fn encode_range_public_values(
+    version: PublicValuesVersion,
+    boot_info: &BootInfoStruct,
+    intermediate_outputs: Option<&[IntermediateOutput]>,
+) -> Vec<u8> {
+    match (version, intermediate_outputs) {
+        (_, None) => encode_boot_info(version, boot_info),
+        (PublicValuesVersion::Unversioned, Some(_)) => {
+            panic!("Unversioned range public values can't have intermediate outputs")
+        }
+        (PublicValuesVersion::V1, Some(intermediate_outputs)) => version.envelope(
+            [
+                boot_info.abi_encode(),
+                intermediate_outputs.to_vec().abi_encode(),
+            ]
+            .concat(),
+        ),
+    }
+}
+
+/// Decode the boot info at the start of a range proof's public values, which the range program
+/// may follow with other values.
+///
+/// The unversioned encoding starts with bincode's length prefix of the L1 head, 32, which is not a
+/// version byte.
+pub fn decode_boot_info(public_values: &[u8]) -> Result<(PublicValuesVersion, BootInfoStruct)> {
+    match PublicValuesVersion::from_envelope(public_values) {
+        Some((version, payload)) => {
+            let payload = payload.get(..BOOT_INFO_SIZE).ok_or_else(|| {
+                anyhow!("The {version:?} public values are too short for a boot info")
+            })?;
+            Ok((version, BootInfoStruct::abi_decode(payload, true)?))
+        }
+        None => Ok((
+            PublicValuesVersion::Unversioned,
+            bincode::deserialize(public_values)?,
+        )),
+    }
+}
+
+/// Decode the boot infos of the range proofs of an aggregation. They must share a version, as the
+/// aggregation program verifies them all against the same range program.
+pub fn decode_boot_infos<'a>(
+    public_values: impl IntoIterator<Item = &'a [u8]>,
+) -> Result<(PublicValuesVersion, Vec<BootInfoStruct>)> {
+    let mut versions = Vec::new();
+    let mut boot_infos = Vec::new();
+    for public_values in public_values {
+        let (version, boot_info) = decode_boot_info(public_values)?;
+        versions.push(version);
+        boot_infos.push(boot_info);
+    }
+    let version = versions
+        .first()
+        .copied()
+        .unwrap_or(CURRENT_PUBLIC_VALUES_VERSION);
+    if let Some(index) = versions.iter().position(|v| *v != version) {
+        bail!(
+            "Range proof {} commits to {:?} public values, but range proof 0 commits to {:?}. \
+             Aggregate proofs of the same range program.",
+            index,
+            versions[index],
+            version
+        );
+    }
+    Ok((version, boot_infos))
+}
+
+/// Decode the intermediate outputs a range proof commits to after its boot info, or `None` if the
+/// range program commits to the boot info only.
+pub fn decode_intermediate_outputs(
+    public_values: &[u8],
+) -> Result<Option<Vec<IntermediateOutput>>> {
+    match PublicValuesVersion::from_envelope(public_values) {
+        Some((_, payload)) if payload.len() > BOOT_INFO_SIZE => {
+            Ok(Some(Vec::abi_decode(&payload[BOOT_INFO_SIZE..], true)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Decode the intermediate outputs of the range proofs of an aggregation. Either all or none of
+/// them commit to intermediate outputs, as the aggregation program verifies them all against the
+/// same range program.
+pub fn decode_range_intermediate_outputs<'a>(
+    public_values: impl IntoIterator<Item = &'a [u8]>,
+) -> Result<Option<Vec<Vec<IntermediateOutput>>>> {
+    let decoded = public_values
+        .into_iter()
+        .map(decode_intermediate_outputs)
+        .collect::<Result<Vec<_>>>()?;
+    if decoded.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+    match decoded.iter().position(Option::is_none) {
+        Some(index) => bail!(
+            "Range proof {index} doesn't commit to intermediate outputs, but other range proofs \
+             do. Aggregate proofs of the same range program."
+        ),
+        None => Ok(Some(decoded.into_iter().flatten().collect())),
+    }
+}
+
+/// Encode the outputs the aggregation program commits to, which the contracts verify the proof
+/// against.
+///
+/// Panics if unversioned outputs rely on checkpoint overrides, which the programs before the
+/// envelope couldn't commit to.
+pub fn encode_aggregation_outputs(
+    version: PublicValuesVersion,
+    outputs: &AggregationOutputs,
+) -> Vec<u8> {
+    match version {
+        PublicValuesVersion::Unversioned => {
+            assert!(
+                outputs.checkpointOverridesHash.is_zero(),
+                "Unversioned aggregation outputs can't rely on checkpoint overrides"
+            );
+            outputs.abi_encode()[..UNVERSIONED_AGGREGATION_OUTPUTS_SIZE].to_vec()
+        }
+        PublicValuesVersion::V1 => version.envelope(outputs.abi_encode()),
+    }
+}
+
+/// Decode an aggregation proof's public values. The unversioned encoding is told apart by its
+/// length.
+pub fn decode_aggregation_outputs(
+    public_values: &[u8],
+) -> Result<(PublicValuesVersion, AggregationOutputs)> {
+    if public_values.len() == UNVERSIONED_AGGREGATION_OUTPUTS_SIZE {
+        // Without checkpoint overrides, the hash is zero.
+        let padded = [public_values, &[0; 32]].concat();
+        return Ok((
+            PublicValuesVersion::Unversioned,
+            AggregationOutputs::abi_decode(&padded, true)?,
+        ));
+    }
+    let (version, payload) = PublicValuesVersion::from_envelope(public_values)
+        .ok_or_else(|| anyhow!("Unknown aggregation public values version"))?;
+    Ok((version, AggregationOutputs::abi_decode(payload, true)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    fn boot_info(l2_block_number: u64) -> BootInfoStruct {
+        BootInfoStruct {
+            l1Head: B256::repeat_byte(1),
+            l2PreRoot: B256::repeat_byte(2),
+            l2PostRoot: B256::repeat_byte(3),
+            l2BlockNumber: l2_block_number,
+            rollupConfigHash: B256::repeat_byte(4),
+        }
+    }
+
+    #[test]
+    fn test_boot_info_round_trip() {
+        for version in [PublicValuesVersion::Unversioned, PublicValuesVersion::V1] {
+            let mut encoded = encode_boot_info(version, &boot_info(10));
+            // The intermediate outputs may follow the boot info.
+            encoded.extend_from_slice(&[0xff; 40]);
+            let (decoded_version, decoded) = decode_boot_info(&encoded).unwrap();
+            assert_eq!(decoded_version, version);
+            assert_eq!(decoded.abi_encode(), boot_info(10).abi_encode());
+        }
+        assert!(decode_boot_info(&[1; 100]).is_err());
+    }
+
+    fn intermediate_output(l2_block_number: u64) -> IntermediateOutput {
+        IntermediateOutput {
+            l2BlockNumber: l2_block_number,
+            stateRoot: B256::repeat_byte(5),
+            messagePasserStorageRoot: B256::repeat_byte(6),
+            blockHash: B256::repeat_byte(7),
+            outputRoot: B256::repeat_byte(8),
+        }
+    }
+
+    #[test]
+    fn test_range_public_values_round_trip() {
+        let outputs = [intermediate_output(9), intermediate_output(10)];
+        let encoded =
+            encode_range_public_values(PublicValuesVersion::V1, &boot_info(10), Some(&outputs));
+        let (version, decoded) = decode_boot_info(&encoded).unwrap();
+        assert_eq!(version, PublicValuesVersion::V1);
+        assert_eq!(decoded.abi_encode(), boot_info(10).abi_encode());
+        let decoded_outputs = decode_intermediate_outputs(&encoded).unwrap().unwrap();
+        assert_eq!(decoded_outputs.abi_encode(), outputs.to_vec().abi_encode());
+
+        // Without intermediate outputs, the public values are the boot info's.
+        for version in [PublicValuesVersion::Unversioned, PublicValuesVersion::V1] {
+            let encoded = encode_range_public_values(version, &boot_info(10), None);
+            assert_eq!(encoded, encode_boot_info(version, &boot_info(10)));
+            assert!(decode_intermediate_outputs(&encoded).unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn test_range_intermediate_outputs_are_all_or_none() {
+        let outputs = [intermediate_output(10)];
+        let with =
+            encode_range_public_values(PublicValuesVersion::V1, &boot_info(10), Some(&outputs));
+        let without = encode_boot_info(PublicValuesVersion::V1, &boot_info(10));
+
+        let decoded = decode_range_intermediate_outputs([with.as_slice(), with.as_slice()])
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert!(
+            decode_range_intermediate_outputs([without.as_slice(), without.as_slice()])
+                .unwrap()
+                .is_none()
+        );
+        assert!(decode_range_intermediate_outputs([with.as_slice(), without.as_slice()]).is_err());
+    }
+
+    #[test]
+    fn test_boot_infos_share_a_version() {
+        let unversioned = encode_boot_info(PublicValuesVersion::Unversioned, &boot_info(10));
+        let v1 = encode_boot_info(PublicValuesVersion::V1, &boot_info(20));
+
+        let (version, boot_infos) = decode_boot_infos([v1.as_slice(), v1.as_slice()]).unwrap();
+        assert_eq!(version, PublicValuesVersion::V1);
+        assert_eq!(boot_infos.len(), 2);
+        assert!(decode_boot_infos([unversioned.as_slice(), v1.as_slice()]).is_err());
+    }
+
+    #[test]
+    fn test_aggregation_outputs_round_trip() {
+        let outputs = AggregationOutputs {
+            l1Head: B256::repeat_byte(1),
+            l2PreRoot: B256::repeat_byte(2),
+            l2PostRoot: B256::repeat_byte(3),
+            l2BlockNumber: 30,
+            rollupConfigHash: B256::repeat_byte(4),
+            multiBlockVKey: B256::repeat_byte(5),
+            checkpointOverridesHash: B256::ZERO,
+        };
+        for version in [PublicValuesVersion::Unversioned, PublicValuesVersion::V1] {
+            let encoded = encode_aggregation_outputs(version, &outputs);
+            let (decoded_version, decoded) = decode_aggregation_outputs(&encoded).unwrap();
+            assert_eq!(decoded_version, version);
+            assert_eq!(decoded.abi_encode(), outputs.abi_encode());
+        }
+        assert_eq!(
+            encode_aggregation_outputs(PublicValuesVersion::V1, &outputs)[0],
+            1
+        );
+        assert_eq!(
+            encode_aggregation_outputs(PublicValuesVersion::Unversioned, &outputs).len(),
+            6 * 32
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "can't rely on checkpoint overrides")]
+    fn test_unversioned_aggregation_outputs_reject_checkpoint_overrides() {
+        let outputs = AggregationOutputs {
+            l1Head: B256::repeat_byte(1),
+            l2PreRoot: B256::repeat_byte(2),
+            l2PostRoot: B256::repeat_byte(3),
+            l2BlockNumber: 30,
+            rollupConfigHash: B256::repeat_byte(4),
+            multiBlockVKey: B256::repeat_byte(5),
+            checkpointOverridesHash: B256::repeat_byte(6),
+        };
+        encode_aggregation_outputs(PublicValuesVersion::Unversioned, &outputs);
+    }
+
+    #[test]
+    fn test_version_from_u8() {
+        assert_eq!(
+            PublicValuesVersion::try_from(0).unwrap(),
+            PublicValuesVersion::Unversioned
+        );
+        assert_eq!(
+            PublicValuesVersion::try_from(1).unwrap(),
+            PublicValuesVersion::V1
+        );
+        assert!(PublicValuesVersion::try_from(2).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! A span-scoped cache of the state the executor reads through its trie provider.
+//!
+//! The executor opens a fresh trie for every block, so the trie nodes on the path to an account
+//! or storage slot are fetched from the oracle and RLP decoded again for every block that touches
+//! it. On workloads where the same contracts are hit in every block, most of these lookups repeat
+//! across the span. The cache keeps the decoded nodes, bytecode and headers for the whole span.
+//!
+//! Values are keyed by their hash and only ever inserted after the underlying provider returned
+//! them, which verifies them against their hash, so serving them from the cache is as sound as
+//! fetching them again.
+
+use alloc::sync::Arc;
+use alloy_consensus::Header;
+use alloy_primitives::{map::B256HashMap, Bytes, B256};
+use kona_executor::TrieDBProvider;
+use kona_mpt::{TrieNode, TrieProvider};
+use spin::Mutex;
+
+#[derive(Debug, Default)]
+struct Cache {
+    trie_nodes: B256HashMap<TrieNode>,
+    bytecode: B256HashMap<Bytes>,
+    headers: B256HashMap<Header>,
+}
+
+/// A [TrieDBProvider] that caches the values returned by the wrapped provider. Clones share the
+/// cache, so one instance should be created per span.
+#[derive(Debug, Clone)]
+pub struct CachingTrieDBProvider<P> {
+    inner: P,
+    cache: Arc<Mutex<Cache>>,
+}
+
+impl<P> CachingTrieDBProvider<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            cache: Arc::default(),
+        }
+    }
+}
+
+/// Get a value from a cache, or fetch and insert it. The lock is not held while fetching.
+fn get_or_fetch<V: Clone, E>(
+    cache: &Mutex<Cache>,
+    map: impl Fn(&mut Cache) -> &mut B256HashMap<V>,
+    key: B256,
+    fetch: impl FnOnce() -> Result<V, E>,
+) -> Result<V, E> {
+    if let Some(value) = map(&mut cache.lock()).get(&key) {
+        return Ok(value.clone());
+    }
+    let value = fetch()?;
+    map(&mut cache.lock()).insert(key, value.clone());
+    Ok(value)
+}
+
+impl<P: TrieProvider> TrieProvider for CachingTrieDBProvider<P> {
+    type Error = P::Error;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        get_or_fetch(
+            &self.cache,
+            |cache| &mut cache.trie_nodes,
+            key,
+            || self.inner.trie_node_by_hash(key),
+        )
+    }
+}
+
+impl<P: TrieDBProvider> TrieDBProvider for CachingTrieDBProvider<P> {
+    fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+        get_or_fetch(
+            &self.cache,
+            |cache| &mut cache.bytecode,
+            code_hash,
+            || self.inner.bytecode_by_hash(code_hash),
+        )
+    }
+
+    fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+        get_or_fetch(
+            &self.cache,
+            |cache| &mut cache.headers,
+            hash,
+            || self.inner.header_by_hash(hash),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    #[derive(Debug, Default, Clone)]
+    struct CountingProvider {
+        fetches: Arc<Mutex<usize>>,
+    }
+
+    impl TrieProvider for CountingProvider {
+        type Error = Infallible;
+
+        fn trie_node_by_hash(&self, _key: B256) -> Result<TrieNode, Self::Error> {
+            *self.fetches.lock() += 1;
+            Ok(TrieNode::Empty)
+        }
+    }
+
+    #[test]
+    fn test_trie_nodes_are_fetched_once_per_span() {
+        let inner = CountingProvider::default();
+        let provider = CachingTrieDBProvider::new(inner.clone());
+        let clone = provider.clone();
+
+        provider.trie_node_by_hash(B256::ZERO).unwrap();
+        clone.trie_node_by_hash(B256::ZERO).unwrap();
+        provider.trie_node_by_hash(B256::repeat_byte(1)).unwrap();
+        assert_eq!(*inner.fetches.lock(), 2);
+    }
+}
@@ -1,14 +1,28 @@
-use alloy_primitives::B256;
-use alloy_sol_types::sol;
+use alloy_primitives::{keccak256, B256};
+use alloy_sol_types::{sol, SolValue};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
-use crate::boot::BootInfoStruct;
+use crate::{boot::BootInfoStruct, public_values::PublicValuesVersion};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AggregationInputs {
     pub boot_infos: Vec<BootInfoStruct>,
     pub latest_l1_checkpoint_head: B256,
     pub multi_block_vkey: [u32; 8],
+    /// The vkey of a second, independently built range program. If set, every span must also be
+    /// proven by it, and the aggregation proof commits to both vkeys.
+    pub secondary_multi_block_vkey: Option<[u32; 8]>,
+    /// The governance-approved gaps in the chain of spans, in order. Empty for a normal
+    /// aggregation.
+    #[serde(default)]
+    pub checkpoint_overrides: Vec<CheckpointOverride>,
+    /// The public values version the range proofs commit to their boot infos with.
+    pub range_public_values_version: PublicValuesVersion,
+    /// The intermediate outputs each range proof commits to after its boot info, in the order of
+    /// the boot infos, if the range program was built with `intermediate-outputs`.
+    #[serde(default)]
+    pub range_intermediate_outputs: Option<Vec<Vec<IntermediateOutput>>>,
 }
 
 sol! {
@@ -20,9 +34,135 @@ sol! {
         uint64 l2BlockNumber;
         bytes32 rollupConfigHash;
         bytes32 multiBlockVKey;
+        /// The hash of the checkpoint overrides the aggregation relies on, or zero if none.
+        bytes32 checkpointOverridesHash;
     }
 }
 
+sol! {
+    /// A gap in the chain of aggregated spans, force-resolved by governance: the aggregation
+    /// continues from `toRoot` although the chain so far ends at `fromRoot`. `justificationHash` is
+    /// the keccak256 hash of the governance-signed justification for skipping the blocks between
+    /// them.
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct CheckpointOverride {
+        bytes32 fromRoot;
+        bytes32 toRoot;
+        bytes32 justificationHash;
+    }
+}
+
+/// The hash of the checkpoint overrides an aggregation commits to: the keccak256 hash of their ABI
+/// encoding as a `CheckpointOverride[]`, or zero if there are none, so a normal aggregation's
+/// public values don't depend on the encoding.
+pub fn checkpoint_overrides_hash(overrides: &[CheckpointOverride]) -> B256 {
+    if overrides.is_empty() {
+        return B256::ZERO;
+    }
+    keccak256(overrides.to_vec().abi_encode())
+}
+
+/// Why a chain of spans doesn't link up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanLinkError {
+    /// The span at the index doesn't start from the output root the previous span ends at, and no
+    /// checkpoint override bridges them.
+    Gap { index: usize },
+    /// The checkpoint override at the index bridges no gap in the chain.
+    UnusedOverride { index: usize },
+}
+
+impl fmt::Display for SpanLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Gap { index } => write!(
+                f,
+                "Span {} does not start from the output root span {} ends at, and no checkpoint \
+                 override bridges them",
+                index,
+                index - 1
+            ),
+            Self::UnusedOverride { index } => {
+                write!(
+                    f,
+                    "Checkpoint override {} bridges no gap in the spans",
+                    index
+                )
+            }
+        }
+    }
+}
+
+/// Check that the spans chain together: each starts from the output root the previous one ends
+/// at, or the next checkpoint override bridges them. An override whose `toRoot` is the first
+/// span's start makes the aggregation start from its `fromRoot`, the latest output on-chain.
+/// Every override must be used, in order. Returns the output root the aggregation starts from.
+pub fn link_spans(
+    boot_infos: &[BootInfoStruct],
+    overrides: &[CheckpointOverride],
+) -> Result<B256, SpanLinkError> {
+    let Some(first) = boot_infos.first() else {
+        return Ok(B256::ZERO);
+    };
+
+    let mut overrides = overrides.iter().enumerate().peekable();
+    let l2_pre_root = match overrides.next_if(|(_, o)| o.toRoot == first.l2PreRoot) {
+        Some((_, o)) => o.fromRoot,
+        None => first.l2PreRoot,
+    };
+    let mut bridge = |from: B256, to: B256| {
+        overrides
+            .next_if(|(_, o)| o.fromRoot == from && o.toRoot == to)
+            .is_some()
+    };
+
+    for (index, pair) in boot_infos.windows(2).enumerate() {
+        let (prev, boot_info) = (&pair[0], &pair[1]);
+        if prev.l2PostRoot != boot_info.l2PreRoot && !bridge(prev.l2PostRoot, boot_info.l2PreRoot) {
+            return Err(SpanLinkError::Gap { index: index + 1 });
+        }
+    }
+
+    match overrides.next() {
+        Some((index, _)) => Err(SpanLinkError::UnusedOverride { index }),
+        None => Ok(l2_pre_root),
+    }
+}
+
+sol! {
+    /// The output root of a block executed by the range program, with the fields it commits to.
+    /// The state root, the storage root of the `L2ToL1MessagePasser` and the block hash are the
+    /// `OutputRootProof` withdrawals are proven against the output root with.
+    #[derive(Debug, Serialize, Deserialize)]
+    struct IntermediateOutput {
+        uint64 l2BlockNumber;
+        bytes32 stateRoot;
+        bytes32 messagePasserStorageRoot;
+        bytes32 blockHash;
+        bytes32 outputRoot;
+    }
+}
+
+/// The version of the output root format used by OP Stack chains.
+pub const OUTPUT_ROOT_VERSION_V0: B256 = B256::ZERO;
+
+/// Compute the output root of an L2 block, matching the formula used on-chain in
+/// `Hashing.hashOutputRootProof`: `keccak256(version ++ stateRoot ++ messagePasserStorageRoot ++
+/// latestBlockhash)`.
+pub fn compute_output_root(
+    version: B256,
+    state_root: B256,
+    message_passer_storage_root: B256,
+    block_hash: B256,
+) -> B256 {
+    let mut preimage = [0u8; 128];
+    preimage[..32].copy_from_slice(version.as_slice());
+    preimage[32..64].copy_from_slice(state_root.as_slice());
+    preimage[64..96].copy_from_slice(message_passer_storage_root.as_slice());
+    preimage[96..].copy_from_slice(block_hash.as_slice());
+    keccak256(preimage)
+}
+
 /// Convert a u32 array to a u8 array. Useful for converting the range vkey to a B256.
 pub fn u32_to_u8(input: [u32; 8]) -> [u8; 32] {
     let mut output = [0u8; 32];
@@ -32,3 +172,132 @@ pub fn u32_to_u8(input: [u32; 8]) -> [u8; 32] {
     }
     output
 }
+
+/// The range vkey commitment the aggregation program commits to, which the L2 Output Oracle's
+/// `rangeVkeyCommitment` must match. With a secondary range program, it is the hash of both vkeys,
+/// so the commitment can't be satisfied by proofs of either program alone.
+pub fn range_vkey_commitment(
+    multi_block_vkey: [u32; 8],
+    secondary_multi_block_vkey: Option<[u32; 8]>,
+) -> B256 {
+    match secondary_multi_block_vkey {
+        None => B256::from(u32_to_u8(multi_block_vkey)),
+        Some(secondary) => {
+            let mut preimage = [0u8; 64];
+            preimage[..32].copy_from_slice(&u32_to_u8(multi_block_vkey));
+            preimage[32..].copy_from_slice(&u32_to_u8(secondary));
+            keccak256(preimage)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolValue;
+
+    sol! {
+        struct OutputRootProof {
+            bytes32 version;
+            bytes32 stateRoot;
+            bytes32 messagePasserStorageRoot;
+            bytes32 latestBlockhash;
+        }
+    }
+
+    #[test]
+    fn test_compute_output_root_matches_abi_encoding() {
+        let proof = OutputRootProof {
+            version: OUTPUT_ROOT_VERSION_V0,
+            stateRoot: B256::repeat_byte(1),
+            messagePasserStorageRoot: B256::repeat_byte(2),
+            latestBlockhash: B256::repeat_byte(3),
+        };
+
+        assert_eq!(
+            compute_output_root(
+                proof.version,
+                proof.stateRoot,
+                proof.messagePasserStorageRoot,
+                proof.latestBlockhash,
+            ),
+            keccak256(proof.abi_encode())
+        );
+    }
+
+    #[test]
+    fn test_link_spans() {
+        let root = B256::repeat_byte;
+        let span = |pre: u8, post: u8| BootInfoStruct {
+            l1Head: B256::ZERO,
+            l2PreRoot: root(pre),
+            l2PostRoot: root(post),
+            l2BlockNumber: 0,
+            rollupConfigHash: B256::ZERO,
+        };
+        let bridge = |from: u8, to: u8| CheckpointOverride {
+            fromRoot: root(from),
+            toRoot: root(to),
+            justificationHash: root(0xff),
+        };
+
+        let spans = [span(1, 2), span(2, 3)];
+        assert_eq!(link_spans(&spans, &[]), Ok(root(1)));
+
+        let gap = [span(1, 2), span(4, 5)];
+        assert_eq!(link_spans(&gap, &[]), Err(SpanLinkError::Gap { index: 1 }));
+        assert_eq!(link_spans(&gap, &[bridge(2, 4)]), Ok(root(1)));
+        assert_eq!(
+            link_spans(&gap, &[bridge(3, 4)]),
+            Err(SpanLinkError::Gap { index: 1 })
+        );
+
+        // An override ending at the first span's start moves the start of the aggregation.
+        assert_eq!(link_spans(&gap, &[bridge(9, 1), bridge(2, 4)]), Ok(root(9)));
+        assert_eq!(
+            link_spans(&spans, &[bridge(5, 6)]),
+            Err(SpanLinkError::UnusedOverride { index: 0 })
+        );
+
+        assert_eq!(checkpoint_overrides_hash(&[]), B256::ZERO);
+        assert_ne!(checkpoint_overrides_hash(&[bridge(2, 4)]), B256::ZERO);
+    }
+
+    #[test]
+    fn test_range_vkey_commitment() {
+        let primary = [1; 8];
+        let secondary = [2; 8];
+        assert_eq!(
+            range_vkey_commitment(primary, None),
+            B256::from(u32_to_u8(primary))
+        );
+        assert_ne!(
+            range_vkey_commitment(primary, Some(secondary)),
+            range_vkey_commitment(secondary, Some(primary))
+        );
+    }
+
+    /// The commitments in the fixtures the contracts' `VkeyCommitment` library is tested against.
+    #[test]
+    fn test_range_vkey_commitment_fixtures() {
+        let fixtures: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../contracts/test/fixtures/vkey_commitments.json"
+        ))
+        .unwrap();
+        let words = |value: &serde_json::Value| {
+            let bytes: B256 = value.as_str().unwrap().parse().unwrap();
+            core::array::from_fn(|i| {
+                u32::from_be_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap())
+            })
+        };
+
+        for case in fixtures["cases"].as_array().unwrap() {
+            let secondary = words(&case["secondaryRangeVkey"]);
+            let commitment = range_vkey_commitment(
+                words(&case["rangeVkey"]),
+                (secondary != [0; 8]).then_some(secondary),
+            );
+            assert_eq!(commitment.to_string(), case["commitment"].as_str().unwrap());
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! The serialization of the witness, the [InMemoryOracle] the host passes to the range program.
+//!
+//! Deserializing the witness is one of the larger fixed costs of a span proof, and which format is
+//! cheapest depends on the shape of the witness, so the host picks the format at runtime. It
+//! writes the format's tag to the stdin before the witness, and the program deserializes the
+//! witness with whichever format the tag names. Use the `witness-format-bench` script to compare
+//! the formats on a range.
+//!
+//! The paged format isn't deserialized at all: the program reads its index into a [PagedOracle],
+//! which reads each page as execution needs it and serves the preimages from the pages.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use kona_preimage::{
+    errors::PreimageOracleError, HintWriterClient, PreimageKey, PreimageOracleClient,
+};
+use kona_proof::FlushableCache;
+use rkyv::{from_bytes, to_bytes};
+
+use crate::{serialize_pages, BytesHasherBuilder, InMemoryOracle, PagedOracle, PAGE_SIZE};
+
+/// A serialization format for the witness.
+pub trait WitnessSerializer {
+    fn serialize(oracle: &InMemoryOracle) -> Result<Vec<u8>>;
+
+    fn deserialize(bytes: &[u8]) -> Result<InMemoryOracle>;
+}
+
+/// The archived representation of the oracle, serialized with rkyv. Deserializing validates the
+/// archive and copies every preimage out of it into an owned oracle, so it isn't zero copy. The
+/// buffer must be aligned for rkyv's archived types, which the `Vec` read from the stdin is.
+pub struct Rkyv;
+
+impl WitnessSerializer for Rkyv {
+    fn serialize(oracle: &InMemoryOracle) -> Result<Vec<u8>> {
+        Ok(to_bytes::<rkyv::rancor::Error>(oracle)?.into_vec())
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<InMemoryOracle> {
+        Ok(from_bytes::<InMemoryOracle, rkyv::rancor::Error>(bytes)?)
+    }
+}
+
+/// The serde representation of the oracle, serialized with bincode.
+pub struct Bincode;
+
+impl WitnessSerializer for Bincode {
+    fn serialize(oracle: &InMemoryOracle) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(oracle)?)
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<InMemoryOracle> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+}
+
+/// A minimal custom format: the number of preimages as a little-endian `u32`, followed by each
+/// preimage as its 32 byte key, its length as a little-endian `u32` and its value. Decoding is a
+/// single pass copying each value out of the buffer, with no validation beyond bounds checks.
+pub struct LengthPrefixed;
+
+impl WitnessSerializer for LengthPrefixed {
+    fn serialize(oracle: &InMemoryOracle) -> Result<Vec<u8>> {
+        let size = 4 + oracle
+            .cache
+            .values()
+            .map(|value| 32 + 4 + value.len())
+            .sum::<usize>();
+        let mut bytes = Vec::with_capacity(size);
+        bytes.extend_from_slice(&u32::try_from(oracle.cache.len())?.to_le_bytes());
+        for (key, value) in &oracle.cache {
+            bytes.extend_from_slice(key);
+            bytes.extend_from_slice(&u32::try_from(value.len())?.to_le_bytes());
+            bytes.extend_from_slice(value);
+        }
+        Ok(bytes)
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<InMemoryOracle> {
+        let mut reader = Reader(bytes);
+        let len = reader.read_u32()? as usize;
+        let mut cache = HashMap::with_capacity_and_hasher(len, BytesHasherBuilder);
+        for _ in 0..len {
+            let key: [u8; 32] = reader.read(32)?.try_into().unwrap();
+            let value_len = reader.read_u32()? as usize;
+            cache.insert(key, reader.read(value_len)?.to_vec());
+        }
+        if !reader.0.is_empty() {
+            bail!("{} trailing bytes after the witness", reader.0.len());
+        }
+        Ok(InMemoryOracle::new(cache))
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn read(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            bail!("Witness truncated");
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.read(4)?.try_into().unwrap()))
+    }
+}
+
+/// The witness serialization formats, selected on the host with `WITNESS_FORMAT`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum WitnessFormat {
+    #[default]
+    Rkyv = 0,
+    Bincode = 1,
+    LengthPrefixed = 2,
+    /// The preimages sorted and split into pages, see [PagedOracle].
+    Paged = 3,
+}
+
+impl WitnessFormat {
+    pub const ALL: [WitnessFormat; 4] =
+        [Self::Rkyv, Self::Bincode, Self::LengthPrefixed, Self::Paged];
+
+    /// Read the format from `WITNESS_FORMAT`, defaulting to rkyv.
+    pub fn from_env() -> Result<Self> {
+        match std::env::var("WITNESS_FORMAT") {
+            Ok(format) => format.parse(),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// The tag the format is identified by in the stdin.
+    pub fn tag(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_tag(tag: u8) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.tag() == tag)
+            .ok_or_else(|| anyhow!("Unknown witness format tag {tag}"))
+    }
+
+    /// Serialize the witness into the buffers it is written to the stdin as, after the format's
+    /// tag. The paged format is written as its index and pages, the others as a single buffer.
+    pub fn serialize(self, oracle: &InMemoryOracle) -> Result<Vec<Vec<u8>>> {
+        Ok(match self {
+            Self::Rkyv => vec![Rkyv::serialize(oracle)?],
+            Self::Bincode => vec![Bincode::serialize(oracle)?],
+            Self::LengthPrefixed => vec![LengthPrefixed::serialize(oracle)?],
+            Self::Paged => serialize_pages(oracle, PAGE_SIZE)?,
+        })
+    }
+
+    /// Deserialize the witness, calling `read_buffer` for each buffer it was written as. The paged
+    /// format keeps `read_buffer` to read its pages later.
+    pub fn deserialize(
+        self,
+        mut read_buffer: impl FnMut() -> Vec<u8> + Send + 'static,
+    ) -> Result<WitnessOracle> {
+        println!("cycle-tracker-start: in-memory-oracle-from-raw-bytes-deserialize");
+        let oracle = match self {
+            Self::Rkyv => Rkyv::deserialize(&read_buffer()).map(WitnessOracle::InMemory),
+            Self::Bincode => Bincode::deserialize(&read_buffer()).map(WitnessOracle::InMemory),
+            Self::LengthPrefixed => {
+                LengthPrefixed::deserialize(&read_buffer()).map(WitnessOracle::InMemory)
+            }
+            Self::Paged => PagedOracle::read(read_buffer).map(WitnessOracle::Paged),
+        };
+        println!("cycle-tracker-end: in-memory-oracle-from-raw-bytes-deserialize");
+        oracle
+    }
+}
+
+impl std::str::FromStr for WitnessFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "rkyv" => Ok(Self::Rkyv),
+            "bincode" => Ok(Self::Bincode),
+            "length-prefixed" => Ok(Self::LengthPrefixed),
+            "paged" => Ok(Self::Paged),
+            _ => bail!(
+                "Unknown witness format {s}, expected rkyv, bincode, length-prefixed or paged"
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for WitnessFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rkyv => write!(f, "rkyv"),
+            Self::Bincode => write!(f, "bincode"),
+            Self::LengthPrefixed => write!(f, "length-prefixed"),
+            Self::Paged => write!(f, "paged"),
+        }
+    }
+}
+
+/// The oracle a witness deserializes to.
+#[derive(Debug)]
+pub enum WitnessOracle {
+    InMemory(InMemoryOracle),
+    Paged(PagedOracle),
+}
+
+impl WitnessOracle {
+    /// Verifies the blobs in the oracle. See [`InMemoryOracle::verify`].
+    pub fn verify(&self) -> Result<()> {
+        match self {
+            Self::InMemory(oracle) => oracle.verify(),
+            Self::Paged(oracle) => oracle.verify(),
+        }
+    }
+}
+
+#[async_trait]
+impl PreimageOracleClient for WitnessOracle {
+    async fn get(&self, key: PreimageKey) -> Result<Vec<u8>, PreimageOracleError> {
+        match self {
+            Self::InMemory(oracle) => oracle.get(key).await,
+            Self::Paged(oracle) => oracle.get(key).await,
+        }
+    }
+
+    async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> Result<(), PreimageOracleError> {
+        match self {
+            Self::InMemory(oracle) => oracle.get_exact(key, buf).await,
+            Self::Paged(oracle) => oracle.get_exact(key, buf).await,
+        }
+    }
+}
+
+#[async_trait]
+impl HintWriterClient for WitnessOracle {
+    async fn write(&self, _hint: &str) -> Result<(), PreimageOracleError> {
+        Ok(())
+    }
+}
+
+impl FlushableCache for WitnessOracle {
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn test_formats_round_trip() {
+        let mut cache = HashMap::with_hasher(BytesHasherBuilder);
+        cache.insert([1; 32], vec![1, 2, 3]);
+        cache.insert([2; 32], vec![]);
+        let oracle = InMemoryOracle::new(cache);
+
+        for format in WitnessFormat::ALL {
+            let buffers = Arc::new(Mutex::new(format.serialize(&oracle).unwrap().into_iter()));
+            let reader = buffers.clone();
+            let decoded = match format
+                .deserialize(move || reader.lock().unwrap().next().unwrap_or_default())
+                .unwrap()
+            {
+                WitnessOracle::InMemory(decoded) => decoded.cache,
+                WitnessOracle::Paged(decoded) => {
+                    assert_eq!(decoded.len(), oracle.cache.len(), "{format}");
+                    oracle
+                        .cache
+                        .keys()
+                        .map(|key| (*key, decoded.get_unverified(key).unwrap().unwrap().to_vec()))
+                        .collect()
+                }
+            };
+            assert_eq!(decoded, oracle.cache, "{format}");
+            assert!(buffers.lock().unwrap().next().is_none(), "{format}");
+            assert_eq!(WitnessFormat::from_tag(format.tag()).unwrap(), format);
+            assert_eq!(format.to_string().parse::<WitnessFormat>().unwrap(), format);
+        }
+
+        let bytes = LengthPrefixed::serialize(&oracle).unwrap();
+        assert!(LengthPrefixed::deserialize(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Validation of the inputs of an aggregation proof's stdin.
+//!
+//! The aggregation program fails on span proofs it can't verify and on L1 headers that don't link
+//! up to the checkpointed block, after the proof has been requested and paid for. The inputs are
+//! checked while the stdin is assembled instead, and rejected with an [`AggStdinError`] the server
+//! responds to with `422 Unprocessable Entity`.
+
+use std::fmt;
+
+use alloy_consensus::Header;
+use alloy_primitives::B256;
+use sp1_sdk::SP1Proof;
+
+/// The inputs of an aggregation proof the aggregation program would fail on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AggStdinError {
+    /// No span proofs were given.
+    NoProofs,
+    /// The number of span proofs doesn't match the number of boot infos.
+    BootInfoCountMismatch { proofs: usize, boot_infos: usize },
+    /// A span proof is not compressed, so the aggregation program can't verify it.
+    WrongProofMode { index: usize, mode: &'static str },
+    /// No L1 headers were given.
+    NoHeaders,
+    /// An L1 header is not the parent of the next one.
+    HeaderChainDiscontinuity { number: u64, next_number: u64 },
+    /// The last L1 header is not the checkpointed block.
+    CheckpointHeadMismatch { number: u64, checkpoint_head: B256 },
+    /// The checkpointed block is older than the L1 head of a span proof, so the header chain can't
+    /// link the span proof's L1 head to it.
+    CheckpointBeforeSpanL1Head {
+        checkpoint_number: u64,
+        span_l1_head_number: u64,
+    },
+}
+
+impl fmt::Display for AggStdinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoProofs => write!(f, "No span proofs to aggregate"),
+            Self::BootInfoCountMismatch { proofs, boot_infos } => write!(
+                f,
+                "Got {} span proofs but {} boot infos",
+                proofs, boot_infos
+            ),
+            Self::WrongProofMode { index, mode } => write!(
+                f,
+                "Span proof {} is a {} proof, but the aggregation program verifies compressed \
+                 proofs",
+                index, mode
+            ),
+            Self::NoHeaders => write!(f, "No headers in the header chain"),
+            Self::HeaderChainDiscontinuity {
+                number,
+                next_number,
+            } => write!(
+                f,
+                "L1 header {} is not the parent of L1 header {}",
+                number, next_number
+            ),
+            Self::CheckpointHeadMismatch {
+                number,
+                checkpoint_head,
+            } => write!(
+                f,
+                "L1 header {} does not match the checkpointed block hash {}",
+                number, checkpoint_head
+            ),
+            Self::CheckpointBeforeSpanL1Head {
+                checkpoint_number,
+                span_l1_head_number,
+            } => write!(
+                f,
+                "The checkpointed L1 block {} is older than the L1 head {} of a span proof. \
+                 Checkpoint a newer L1 block hash.",
+                checkpoint_number, span_l1_head_number
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AggStdinError {}
+
+/// The name of a proof's mode.
+pub fn proof_mode(proof: &SP1Proof) -> &'static str {
+    match proof {
+        SP1Proof::Core(_) => "core",
+        SP1Proof::Compressed(_) => "compressed",
+        SP1Proof::Plonk(_) => "plonk",
+        SP1Proof::Groth16(_) => "groth16",
+    }
+}
+
+/// Check that there is a span proof for each boot info.
+pub fn check_proof_count(proofs: usize, boot_infos: usize) -> Result<(), AggStdinError> {
+    if proofs == 0 {
+        return Err(AggStdinError::NoProofs);
+    }
+    if proofs != boot_infos {
+        return Err(AggStdinError::BootInfoCountMismatch { proofs, boot_infos });
+    }
+    Ok(())
+}
+
+/// Check that the checkpointed block is not older than the newest L1 head of the span proofs.
+pub fn check_checkpoint_after_span_l1_heads(
+    checkpoint_number: u64,
+    span_l1_head_number: u64,
+) -> Result<(), AggStdinError> {
+    if checkpoint_number < span_l1_head_number {
+        return Err(AggStdinError::CheckpointBeforeSpanL1Head {
+            checkpoint_number,
+            span_l1_head_number,
+        });
+    }
+    Ok(())
+}
+
+/// Check that the headers form a single chain ending at the block with hash `end_hash`.
+pub fn verify_header_chain(headers: &[Header], end_hash: B256) -> Result<(), AggStdinError> {
+    let Some(last) = headers.last() else {
+        return Err(AggStdinError::NoHeaders);
+    };
+    for pair in headers.windows(2) {
+        if pair[1].parent_hash != pair[0].hash_slow() {
+            return Err(AggStdinError::HeaderChainDiscontinuity {
+                number: pair[0].number,
+                next_number: pair[1].number,
+            });
+        }
+    }
+    if last.hash_slow() != end_hash {
+        return Err(AggStdinError::CheckpointHeadMismatch {
+            number: last.number,
+            checkpoint_head: end_hash,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_header_chain() {
+        let first = Header {
+            number: 1,
+            ..Default::default()
+        };
+        let second = Header {
+            number: 2,
+            parent_hash: first.hash_slow(),
+            ..Default::default()
+        };
+        let headers = vec![first.clone(), second.clone()];
+        assert!(verify_header_chain(&headers, second.hash_slow()).is_ok());
+        assert_eq!(
+            verify_header_chain(&headers, first.hash_slow()),
+            Err(AggStdinError::CheckpointHeadMismatch {
+                number: 2,
+                checkpoint_head: first.hash_slow()
+            })
+        );
+
+        let unlinked = vec![second.clone(), first];
+        assert_eq!(
+            verify_header_chain(&unlinked, unlinked[1].hash_slow()),
+            Err(AggStdinError::HeaderChainDiscontinuity {
+                number: 2,
+                next_number: 1
+            })
+        );
+        assert_eq!(
+            verify_header_chain(&[], B256::ZERO),
+            Err(AggStdinError::NoHeaders)
+        );
+    }
+
+    #[test]
+    fn test_check_checkpoint_after_span_l1_heads() {
+        assert!(check_checkpoint_after_span_l1_heads(100, 100).is_ok());
+        assert!(check_checkpoint_after_span_l1_heads(101, 100).is_ok());
+        assert_eq!(
+            check_checkpoint_after_span_l1_heads(99, 100),
+            Err(AggStdinError::CheckpointBeforeSpanL1Head {
+                checkpoint_number: 99,
+                span_l1_head_number: 100
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_proof_count() {
+        assert_eq!(check_proof_count(0, 0), Err(AggStdinError::NoProofs));
+        assert_eq!(
+            check_proof_count(2, 1),
+            Err(AggStdinError::BootInfoCountMismatch {
+                proofs: 2,
+                boot_infos: 1
+            })
+        );
+        assert!(check_proof_count(2, 2).is_ok());
+    }
+}
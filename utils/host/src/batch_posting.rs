@@ -0,0 +1,239 @@
+//! Checks that the batch data a range derives from can be retrieved, before generating its witness.
+//!
+//! Witness generation derives a range's L2 blocks from the batcher's frames, posted to the batch
+//! inbox either as calldata or as blobs. Calldata is part of the L1 block, but blobs are served by
+//! the beacon node, which prunes them after ~18 days, and may be missing on a beacon node that was
+//! resynced or is behind. A missing blob only surfaces once witness generation reaches it, after
+//! minutes of work. [`validate_batch_posting`] scans the L1 blocks a range derives from for the
+//! batcher's transactions and checks that every blob is served for its slot, so operators can fix
+//! data availability (e.g. with a blob archive, see [`blob_archive`](crate::blob_archive)) first.
+
+use std::collections::HashSet;
+
+use alloy_consensus::Transaction;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Bytes, B256};
+use alloy_provider::Provider;
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt, TryStreamExt};
+use op_alloy_network::primitives::{BlockTransactionsKind, TransactionResponse};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{beacon::BeaconTiming, blob_archive::BatcherInbox, fetcher::OPSuccinctDataFetcher};
+
+/// The version byte of the frames of derivation version 0, the first byte of every batcher
+/// transaction's calldata.
+const DERIVATION_VERSION_0: u8 = 0;
+
+/// The version byte of the versioned hashes of KZG commitments.
+const VERSIONED_HASH_VERSION_KZG: u8 = 1;
+
+/// The number of L1 blocks fetched concurrently.
+const L1_BLOCK_CONCURRENCY: usize = 8;
+
+/// A blob posted by the batcher that the beacon node doesn't serve.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingBlob {
+    pub l1_block_number: u64,
+    pub slot: u64,
+    pub tx_hash: B256,
+    pub versioned_hash: B256,
+    /// Why the blob couldn't be retrieved.
+    pub reason: String,
+}
+
+/// A batcher transaction whose calldata isn't a frame of a known derivation version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InvalidCalldata {
+    pub l1_block_number: u64,
+    pub tx_hash: B256,
+}
+
+/// The batch data found in the L1 blocks a range derives from.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchPostingReport {
+    pub l2_start_block: u64,
+    pub l2_end_block: u64,
+    pub l1_start_block: u64,
+    pub l1_end_block: u64,
+    /// The number of transactions the batcher sent to the batch inbox.
+    pub batcher_transactions: usize,
+    /// The number of those that posted their frames as calldata.
+    pub calldata_transactions: usize,
+    /// The number of blobs the batcher posted.
+    pub blobs: usize,
+    pub missing_blobs: Vec<MissingBlob>,
+    pub invalid_calldata: Vec<InvalidCalldata>,
+}
+
+impl BatchPostingReport {
+    /// Whether all the batch data found can be retrieved.
+    pub fn is_available(&self) -> bool {
+        self.missing_blobs.is_empty() && self.invalid_calldata.is_empty()
+    }
+}
+
+/// The blob sidecars of a slot, as returned by the beacon API.
+#[derive(Debug, Deserialize)]
+struct BlobSidecars {
+    data: Vec<BlobSidecar>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlobSidecar {
+    kzg_commitment: Bytes,
+}
+
+/// The versioned hash of a KZG commitment, as committed to by blob transactions.
+fn kzg_to_versioned_hash(commitment: &[u8]) -> B256 {
+    let mut hash = <[u8; 32]>::from(Sha256::digest(commitment));
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    hash.into()
+}
+
+/// The versioned hashes of the blobs in a blob sidecars response.
+fn served_blob_hashes(sidecars: &[u8]) -> Result<HashSet<B256>> {
+    let sidecars: BlobSidecars = serde_json::from_slice(sidecars)?;
+    Ok(sidecars
+        .data
+        .iter()
+        .map(|sidecar| kzg_to_versioned_hash(&sidecar.kzg_commitment))
+        .collect())
+}
+
+/// Fetch the versioned hashes of the blobs the beacon node serves for a slot.
+async fn get_served_blob_hashes(beacon_url: &Url, slot: u64) -> Result<HashSet<B256>> {
+    // Paths are joined onto the beacon URL, which replaces its last segment unless it ends with a
+    // slash.
+    let mut url = beacon_url.clone();
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    let response = reqwest::get(url.join(&format!("eth/v1/beacon/blob_sidecars/{slot}"))?).await?;
+    if response.status() != StatusCode::OK {
+        return Err(anyhow!("Beacon node returned {}", response.status()));
+    }
+    served_blob_hashes(&response.bytes().await?)
+}
+
+/// Check the batch data posted in the L1 blocks a range derives from: from the L1 origin of
+/// `l2_start_block` up to `l1_head`, or the L1 head witness generation would choose for
+/// `l2_end_block` (see [`OPSuccinctDataFetcher::select_l1_head`]). Blobs are looked up through
+/// `beacon_url`, which should be the beacon API witness generation reads blobs from.
+pub async fn validate_batch_posting(
+    fetcher: &OPSuccinctDataFetcher,
+    beacon_url: &Url,
+    l2_start_block: u64,
+    l2_end_block: u64,
+    l1_head: Option<u64>,
+) -> Result<BatchPostingReport> {
+    let inbox = BatcherInbox::from_fetcher(fetcher)?;
+    let l1_start_block = fetcher
+        .l2_block_info_by_number(l2_start_block)
+        .await?
+        .l1_origin
+        .number;
+    let l1_end_block = match l1_head {
+        Some(l1_head) => l1_head,
+        None => fetcher.select_l1_head(l2_end_block).await?.l1_head_number,
+    };
+    let timing = if fetcher.settlement_layer.has_blobs() {
+        Some(BeaconTiming::load(beacon_url).await?)
+    } else {
+        None
+    };
+
+    let mut blocks = Box::pin(
+        stream::iter(l1_start_block..=l1_end_block)
+            .map(|block_number| async move {
+                fetcher
+                    .l1_provider
+                    .get_block_by_number(
+                        BlockNumberOrTag::Number(block_number),
+                        BlockTransactionsKind::Full,
+                    )
+                    .await?
+                    .ok_or_else(|| anyhow!("L1 block {block_number} not found"))
+            })
+            .buffered(L1_BLOCK_CONCURRENCY),
+    );
+
+    let mut report = BatchPostingReport {
+        l2_start_block,
+        l2_end_block,
+        l1_start_block,
+        l1_end_block,
+        ..Default::default()
+    };
+    while let Some(block) = blocks.try_next().await? {
+        let l1_block_number = block.header.number;
+        let mut blobs = Vec::new();
+        for tx in block.transactions.txns().filter(|tx| inbox.is_batch(*tx)) {
+            report.batcher_transactions += 1;
+            match tx.blob_versioned_hashes() {
+                Some(hashes) => blobs.extend(hashes.iter().map(|hash| (tx.tx_hash(), *hash))),
+                None => {
+                    report.calldata_transactions += 1;
+                    if tx.input().first() != Some(&DERIVATION_VERSION_0) {
+                        report.invalid_calldata.push(InvalidCalldata {
+                            l1_block_number,
+                            tx_hash: tx.tx_hash(),
+                        });
+                    }
+                }
+            }
+        }
+        if blobs.is_empty() {
+            continue;
+        }
+        report.blobs += blobs.len();
+
+        let timing = timing.ok_or_else(|| {
+            anyhow!("L1 block {l1_block_number} has blobs, but the settlement layer has none")
+        })?;
+        let slot = timing.slot(block.header.timestamp)?;
+        let served = get_served_blob_hashes(beacon_url, slot).await;
+        for (tx_hash, versioned_hash) in blobs {
+            let reason = match &served {
+                Ok(served) if served.contains(&versioned_hash) => continue,
+                Ok(_) => "Not in the slot's blob sidecars".to_string(),
+                Err(e) => format!("Failed to fetch the slot's blob sidecars: {e}"),
+            };
+            report.missing_blobs.push(MissingBlob {
+                l1_block_number,
+                slot,
+                tx_hash,
+                versioned_hash,
+                reason,
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_served_blob_hashes() {
+        let commitment = Bytes::from(vec![0xc0; 48]);
+        let sidecars = serde_json::json!({
+            "data": [{
+                "index": "0",
+                "blob": "0x00",
+                "kzg_commitment": commitment,
+                "kzg_proof": "0x00",
+            }]
+        });
+        let served = served_blob_hashes(&serde_json::to_vec(&sidecars).unwrap()).unwrap();
+
+        let versioned_hash = kzg_to_versioned_hash(&commitment);
+        assert_eq!(versioned_hash[0], VERSIONED_HASH_VERSION_KZG);
+        assert_eq!(served, HashSet::from([versioned_hash]));
+        assert!(served_blob_hashes(b"{}").is_err());
+    }
+}
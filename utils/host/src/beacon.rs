@@ -0,0 +1,191 @@
+//! The slot timing of the L1 beacon chain, which blob sidecars are looked up by.
+//!
+//! Blobs are requested by slot, which is derived from the L1 block's timestamp, the beacon chain's
+//! genesis time and its slot duration. Mainnet-style defaults resolve the wrong sidecars on L1s
+//! with another slot duration (5 seconds on Gnosis) and on devnets, so the timing is read from the
+//! beacon node's genesis and config endpoints, or set with `L1_BEACON_GENESIS_TIME` and
+//! `L1_SECONDS_PER_SLOT` for beacon nodes that don't serve them.
+
+use alloy_provider::RootProvider;
+use anyhow::{anyhow, bail, Context, Result};
+use kona_host::single::{SingleChainHost, SingleChainProviders};
+use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
+use reqwest::Url;
+use serde_json::Value;
+
+use crate::config::env_var;
+
+/// The genesis time and slot duration of the L1 beacon chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeaconTiming {
+    pub genesis_time: u64,
+    pub seconds_per_slot: u64,
+}
+
+impl BeaconTiming {
+    /// Read the timing from `L1_BEACON_GENESIS_TIME` and `L1_SECONDS_PER_SLOT`, fetching the unset
+    /// ones from the beacon node.
+    pub async fn load(beacon_url: &Url) -> Result<Self> {
+        let genesis_time = match env_var("L1_BEACON_GENESIS_TIME")? {
+            Some(genesis_time) => genesis_time,
+            None => get_beacon_u64(beacon_url, "eth/v1/beacon/genesis", "genesis_time").await?,
+        };
+        let seconds_per_slot = match env_var("L1_SECONDS_PER_SLOT")? {
+            Some(seconds_per_slot) => seconds_per_slot,
+            None => get_beacon_u64(beacon_url, "eth/v1/config/spec", "SECONDS_PER_SLOT").await?,
+        };
+        if seconds_per_slot == 0 {
+            bail!("The beacon chain's seconds per slot must be positive");
+        }
+        Ok(Self {
+            genesis_time,
+            seconds_per_slot,
+        })
+    }
+
+    /// The slot of an L1 block with the given timestamp.
+    pub fn slot(&self, timestamp: u64) -> Result<u64> {
+        let since_genesis = timestamp.checked_sub(self.genesis_time).ok_or_else(|| {
+            anyhow!(
+                "L1 timestamp {timestamp} is before the beacon genesis time {}",
+                self.genesis_time
+            )
+        })?;
+        Ok(since_genesis / self.seconds_per_slot)
+    }
+}
+
+/// The L1 blocks whose blobs a beacon node serves: from the start of its blob retention window up to
+/// its head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobAvailability {
+    /// The oldest slot whose blob sidecars the beacon node still serves.
+    pub oldest_slot: u64,
+    /// The L1 block of the beacon node's head, the newest block it serves blobs for.
+    pub head_l1_block: u64,
+}
+
+impl BlobAvailability {
+    /// Read the beacon node's head and blob retention window.
+    pub async fn load(beacon_url: &Url) -> Result<Self> {
+        let head = get_beacon(beacon_url, "eth/v2/beacon/blocks/head").await?;
+        let message = &head["data"]["message"];
+        let head_slot = parse_u64(&message["slot"], "slot")?;
+        let head_l1_block = parse_u64(
+            &message["body"]["execution_payload"]["block_number"],
+            "execution_payload.block_number",
+        )?;
+        let slots_per_epoch =
+            get_beacon_u64(beacon_url, "eth/v1/config/spec", "SLOTS_PER_EPOCH").await?;
+        let retention_epochs = get_beacon_u64(
+            beacon_url,
+            "eth/v1/config/spec",
+            "MIN_EPOCHS_FOR_BLOB_SIDECARS_REQUESTS",
+        )
+        .await?;
+        Ok(Self {
+            oldest_slot: oldest_blob_slot(head_slot, slots_per_epoch, retention_epochs),
+            head_l1_block,
+        })
+    }
+}
+
+/// The first slot of the oldest epoch whose blob sidecars a beacon node at `head_slot` has to serve,
+/// `MIN_EPOCHS_FOR_BLOB_SIDECARS_REQUESTS` epochs before the head's.
+fn oldest_blob_slot(head_slot: u64, slots_per_epoch: u64, retention_epochs: u64) -> u64 {
+    let head_epoch = head_slot / slots_per_epoch.max(1);
+    head_epoch.saturating_sub(retention_epochs) * slots_per_epoch
+}
+
+/// Read a string-encoded number from the `data` of a beacon API response.
+async fn get_beacon_u64(beacon_url: &Url, path: &str, field: &str) -> Result<u64> {
+    let response = get_beacon(beacon_url, path).await?;
+    parse_u64(&response["data"][field], field)
+        .with_context(|| format!("Invalid beacon response for {path}"))
+}
+
+/// Parse a string-encoded number of a beacon API response.
+fn parse_u64(value: &Value, field: &str) -> Result<u64> {
+    value
+        .as_str()
+        .ok_or_else(|| anyhow!("Missing {field} in beacon response"))?
+        .parse()
+        .map_err(Into::into)
+}
+
+/// Get a beacon API response.
+async fn get_beacon(beacon_url: &Url, path: &str) -> Result<Value> {
+    // Paths are joined onto the beacon URL, which replaces its last segment unless it ends with a
+    // slash.
+    let mut url = beacon_url.clone();
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    Ok(reqwest::get(url.join(path)?)
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+/// Create the providers of a witness generation run like [`SingleChainHost::create_providers`],
+/// with the blob provider using the beacon chain's [`BeaconTiming`].
+pub async fn create_providers(cfg: &SingleChainHost) -> Result<SingleChainProviders> {
+    let l1_node_address = cfg
+        .l1_node_address
+        .as_ref()
+        .context("L1 node address must be set")?;
+    let l2_node_address = cfg
+        .l2_node_address
+        .as_ref()
+        .context("L2 node address must be set")?;
+    let l1_beacon_address = cfg
+        .l1_beacon_address
+        .as_ref()
+        .context("L1 beacon address must be set")?;
+
+    let timing = BeaconTiming::load(&l1_beacon_address.parse()?).await?;
+    let blobs = OnlineBlobProvider::new(
+        OnlineBeaconClient::new_http(l1_beacon_address.clone()),
+        timing.genesis_time,
+        timing.seconds_per_slot,
+    );
+
+    Ok(SingleChainProviders {
+        l1: RootProvider::new_http(l1_node_address.parse()?),
+        blobs,
+        l2: RootProvider::new_http(l2_node_address.parse()?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slot() {
+        // Gnosis: 5 second slots.
+        let timing = BeaconTiming {
+            genesis_time: 1638993340,
+            seconds_per_slot: 5,
+        };
+        assert_eq!(timing.slot(1638993340).unwrap(), 0);
+        assert_eq!(timing.slot(1638993340 + 5 * 1000 + 4).unwrap(), 1000);
+        assert!(timing.slot(1638993339).is_err());
+    }
+
+    #[test]
+    fn test_oldest_blob_slot() {
+        // Mainnet: 32 slots per epoch, blobs kept for 4096 epochs.
+        assert_eq!(
+            oldest_blob_slot(10_000_000, 32, 4096),
+            (312_500 - 4096) * 32
+        );
+        assert_eq!(
+            oldest_blob_slot(10_000_031, 32, 4096),
+            (312_500 - 4096) * 32
+        );
+        // Before the retention window fills, every blob is still served.
+        assert_eq!(oldest_blob_slot(1000, 32, 4096), 0);
+    }
+}
@@ -0,0 +1,189 @@
+//! An archive of the blob sidecars posted by the batcher, so ranges remain provable after the
+//! blobs have expired on the beacon node (after ~18 days on mainnet, much sooner on some testnets).
+//!
+//! The [`BlobArchiver`] stores the beacon node's blob sidecars response for every L1 slot in which
+//! the batcher posted blobs. Witness generation reads blobs through a beacon API proxy backed by
+//! the archive (see [`DataAvailabilitySource`]), which serves archived slots and forwards
+//! everything else to the beacon node.
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use alloy_consensus::Transaction;
+use alloy_primitives::Address;
+use alloy_provider::Provider;
+use anyhow::{anyhow, bail, Result};
+use log::{error, info};
+use op_alloy_network::primitives::{BlockTransactionsKind, TransactionResponse};
+
+use crate::{
+    beacon::BeaconTiming,
+    config::env_var,
+    da::{BeaconNode, BeaconRequest, DataAvailabilitySource},
+    fetcher::OPSuccinctDataFetcher,
+};
+
+/// How often the archiver checks for new L1 blocks.
+const ARCHIVER_POLL_INTERVAL: Duration = Duration::from_secs(12);
+
+/// Blob sidecars responses from the beacon node, stored as `{dir}/{slot}.json`.
+#[derive(Debug, Clone)]
+pub struct BlobArchive {
+    dir: PathBuf,
+    beacon: BeaconNode,
+}
+
+impl BlobArchive {
+    pub fn new(dir: PathBuf, beacon: BeaconNode) -> Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir, beacon })
+    }
+
+    fn path(&self, slot: u64) -> PathBuf {
+        self.dir.join(format!("{slot}.json"))
+    }
+
+    /// Get the blob sidecars for a slot, from the archive if present and otherwise from the
+    /// beacon node. Sidecars fetched from the beacon node are archived.
+    pub async fn get_blob_sidecars(&self, slot: u64) -> Result<Vec<u8>> {
+        if let Ok(sidecars) = fs::read(self.path(slot)) {
+            return Ok(sidecars);
+        }
+
+        let sidecars = self.beacon.blob_sidecars(slot).await?;
+        self.store(slot, &sidecars)?;
+        Ok(sidecars)
+    }
+
+    /// Forward a beacon API request to the beacon node.
+    pub async fn get_beacon(&self, request: BeaconRequest) -> Result<Vec<u8>> {
+        self.beacon.beacon_request(request).await
+    }
+
+    fn store(&self, slot: u64, sidecars: &[u8]) -> Result<()> {
+        // Write to a temporary file first, so a partially written file is never served.
+        let tmp = self.dir.join(format!("{slot}.json.tmp"));
+        fs::write(&tmp, sidecars)?;
+        fs::rename(tmp, self.path(slot))?;
+        Ok(())
+    }
+}
+
+/// The batcher of a chain and the batch inbox it posts batches to.
+#[derive(Debug, Clone, Copy)]
+pub struct BatcherInbox {
+    pub batcher_address: Address,
+    pub batch_inbox_address: Address,
+}
+
+impl BatcherInbox {
+    /// The batcher inbox of the chain of the fetcher. The batcher is read from `BATCHER_ADDRESS` if
+    /// set, and otherwise from the genesis system config of the rollup config.
+    pub fn from_fetcher(fetcher: &OPSuccinctDataFetcher) -> Result<Self> {
+        let rollup_config = fetcher
+            .rollup_config
+            .as_ref()
+            .ok_or_else(|| anyhow!("The batcher inbox requires the rollup config"))?;
+        let batcher_address = match env_var::<Address>("BATCHER_ADDRESS")? {
+            Some(address) => address,
+            None => rollup_config
+                .genesis
+                .system_config
+                .as_ref()
+                .map(|system_config| system_config.batcher_address)
+                .ok_or_else(|| anyhow!("BATCHER_ADDRESS must be set"))?,
+        };
+
+        Ok(Self {
+            batcher_address,
+            batch_inbox_address: rollup_config.batch_inbox_address,
+        })
+    }
+
+    /// Whether a transaction was sent by the batcher to the batch inbox.
+    pub fn is_batch<T>(&self, tx: &T) -> bool
+    where
+        T: TransactionResponse + Transaction,
+    {
+        tx.from() == self.batcher_address && tx.to() == Some(self.batch_inbox_address)
+    }
+
+    /// The number of blobs the batcher posted to the batch inbox in the transactions of a block.
+    pub fn blob_count<'a, T>(&self, transactions: impl IntoIterator<Item = &'a T>) -> usize
+    where
+        T: TransactionResponse + Transaction + 'a,
+    {
+        transactions
+            .into_iter()
+            .filter(|tx| self.is_batch(*tx))
+            .filter_map(|tx| tx.blob_versioned_hashes())
+            .map(|hashes| hashes.len())
+            .sum()
+    }
+}
+
+/// Archives the blob sidecars of every L1 slot in which the batcher posts blobs to the batch
+/// inbox, as the blocks appear.
+pub struct BlobArchiver {
+    archive: BlobArchive,
+    fetcher: OPSuccinctDataFetcher,
+    inbox: BatcherInbox,
+}
+
+impl BlobArchiver {
+    /// Create an archiver for the chain of the fetcher, see [`BatcherInbox::from_fetcher`].
+    pub fn new(archive: BlobArchive, fetcher: OPSuccinctDataFetcher) -> Result<Self> {
+        if !fetcher.settlement_layer.has_blobs() {
+            bail!(
+                "{:?} settlement layers have no blobs to archive",
+                fetcher.settlement_layer
+            );
+        }
+
+        Ok(Self {
+            inbox: BatcherInbox::from_fetcher(&fetcher)?,
+            archive,
+            fetcher,
+        })
+    }
+
+    /// Archive blob sidecars from the latest L1 block onwards. Failures are logged and the block
+    /// is retried on the next poll.
+    pub async fn run(self) {
+        let mut next_block = None;
+        let mut interval = tokio::time::interval(ARCHIVER_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match self.archive_new_blocks(next_block).await {
+                Ok(block) => next_block = Some(block),
+                Err(e) => error!("Failed to archive blob sidecars: {}", e),
+            }
+        }
+    }
+
+    /// Archive the blocks from `next_block` up to the latest block, returning the next block to
+    /// archive.
+    async fn archive_new_blocks(&self, next_block: Option<u64>) -> Result<u64> {
+        let timing = BeaconTiming::load(self.archive.beacon.url()).await?;
+
+        let latest_block = self.fetcher.l1_provider.get_block_number().await?;
+        let mut block_number = next_block.unwrap_or(latest_block);
+        while block_number <= latest_block {
+            let block = self
+                .fetcher
+                .l1_provider
+                .get_block_by_number(block_number.into(), BlockTransactionsKind::Full)
+                .await?
+                .ok_or_else(|| anyhow!("L1 block {block_number} not found"))?;
+
+            if self.inbox.blob_count(block.transactions.txns()) > 0 {
+                let slot = timing.slot(block.header.timestamp)?;
+                self.archive.get_blob_sidecars(slot).await?;
+                info!("Archived blob sidecars of L1 block {block_number} (slot {slot})");
+            }
+
+            block_number += 1;
+        }
+
+        Ok(block_number)
+    }
+}
@@ -1,16 +1,28 @@
 use std::{
     cmp::{max, min},
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     time::Duration,
 };
 
-use crate::fetcher::{OPSuccinctDataFetcher, RPCMode};
+use crate::{
+    blob_archive::BatcherInbox,
+    fetcher::{BlockInfo, OPSuccinctDataFetcher, RPCMode, RunContext},
+    system_config::{get_system_config_updates, SystemConfigUpdate},
+};
 use alloy_eips::BlockId;
-use anyhow::{bail, Result};
+use alloy_provider::Provider;
+use anyhow::{anyhow, bail, Result};
 use futures::StreamExt;
 use maili_rpc::{OutputResponse, SafeHeadResponse};
+use op_alloy_network::primitives::BlockTransactionsKind;
 use serde::{Deserialize, Serialize};
 
+/// Rough cycle costs of the range program, used to preview the cost of a range. The actual cycles
+/// depend on the transactions in each block, so use the cost estimator for exact numbers.
+const ESTIMATED_CYCLES_PER_BLOCK: u64 = 10_000_000;
+const ESTIMATED_CYCLES_PER_GAS: u64 = 25;
+const ESTIMATED_CYCLES_PER_BLOB: u64 = 50_000_000;
+
 /// Get the start and end block numbers for a range, with validation.
 pub async fn get_validated_block_range(
     data_fetcher: &OPSuccinctDataFetcher,
@@ -76,7 +88,7 @@ pub async fn get_rolling_block_range(
     Ok((l2_start_block, l2_start_block + range))
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SpanBatchRange {
     pub start: u64,
     pub end: u64,
@@ -113,7 +125,7 @@ pub async fn split_range_based_on_safe_heads(
     l2_end: u64,
     max_range_size: u64,
 ) -> Result<Vec<SpanBatchRange>> {
-    let data_fetcher = OPSuccinctDataFetcher::default();
+    let data_fetcher = OPSuccinctDataFetcher::new(RunContext::Dev)?;
 
     // Get the L1 origin of l2_start
     let l2_start_hex = format!("0x{:x}", l2_start);
@@ -132,10 +144,10 @@ pub async fn split_range_based_on_safe_heads(
     // Get all the unique safeHeads between l1_start and l1_head
     let mut ranges = Vec::new();
     let mut current_l2_start = l2_start;
+    let data_fetcher = &data_fetcher;
     let safe_heads = futures::stream::iter(l1_start..=l1_head_number)
         .map(|block| async move {
             let l1_block_hex = format!("0x{:x}", block);
-            let data_fetcher = OPSuccinctDataFetcher::default();
             let result: SafeHeadResponse = data_fetcher
                 .fetch_rpc_data_with_mode(
                     RPCMode::L2Node,
@@ -175,3 +187,285 @@ pub async fn split_range_based_on_safe_heads(
 
     Ok(ranges)
 }
+
+/// Snap the interior boundaries of a list of contiguous span batch ranges to L1-origin epoch
+/// boundaries.
+///
+/// A boundary which lands in the middle of an epoch splits the deposits-only first block of the
+/// epoch (and any blocks forced in by the sequencer drift or sequencing window) from the rest of
+/// the epoch, which requires both spans to derive the same L1 origin. Moving the boundary back to
+/// the last L2 block of the previous epoch keeps each epoch in a single span.
+///
+/// The start of the first range and the end of the last range are never modified.
+pub async fn snap_ranges_to_epoch_boundaries(
+    data_fetcher: &OPSuccinctDataFetcher,
+    ranges: Vec<SpanBatchRange>,
+) -> Result<Vec<SpanBatchRange>> {
+    // Fetch the start of the epoch containing the first block after each interior boundary.
+    let mut epoch_starts = HashMap::new();
+    for range in ranges.iter().take(ranges.len().saturating_sub(1)) {
+        let next_block = range.end + 1;
+        // The sequence number is the number of L2 blocks since the first block of the epoch.
+        let block_info = data_fetcher.l2_block_info_by_number(next_block).await?;
+        epoch_starts.insert(next_block, next_block - block_info.seq_num);
+    }
+
+    Ok(align_ranges_to_epochs(&ranges, |block| {
+        epoch_starts.get(&block).copied().unwrap_or(block)
+    }))
+}
+
+/// Align the interior boundaries of a list of contiguous ranges to epoch boundaries.
+///
+/// `epoch_start` returns the first L2 block of the epoch containing the given L2 block. Each
+/// interior boundary is moved back to the last block of the previous epoch (`epoch_start - 1`).
+/// If the boundary is already at the end of an epoch, or if snapping would produce an empty range
+/// (the whole range lies in a single epoch), the boundary is left unchanged.
+pub fn align_ranges_to_epochs(
+    ranges: &[SpanBatchRange],
+    epoch_start: impl Fn(u64) -> u64,
+) -> Vec<SpanBatchRange> {
+    let mut aligned = Vec::with_capacity(ranges.len());
+    let mut current_start = match ranges.first() {
+        Some(range) => range.start,
+        None => return aligned,
+    };
+
+    for (i, range) in ranges.iter().enumerate() {
+        let is_last = i == ranges.len() - 1;
+        let mut end = range.end;
+
+        if !is_last {
+            // Only snap when the boundary is not the first block of an epoch, i.e. the next range
+            // would otherwise start in the middle of an epoch.
+            let start_of_epoch = epoch_start(end + 1);
+            if start_of_epoch <= end && start_of_epoch > current_start + 1 {
+                end = start_of_epoch - 1;
+            }
+        }
+
+        // Skip ranges which became empty after a previous boundary moved past them.
+        if end > current_start {
+            aligned.push(SpanBatchRange {
+                start: current_start,
+                end,
+            });
+            current_start = end;
+        }
+    }
+
+    aligned
+}
+
+/// The plan for proving a span of a [`RangePlan`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpanPlan {
+    pub start: u64,
+    pub end: u64,
+    /// The L1 origin of the first block of the span.
+    pub l1_origin_start: u64,
+    /// The L1 origin of the last block of the span.
+    pub l1_origin_end: u64,
+    /// The L1 block the span is derived up to.
+    pub l1_head: u64,
+    /// The blobs the batcher posted from the span's first L1 origin up to its L1 head, which
+    /// witness generation reads.
+    pub blobs: u64,
+    pub estimated_cycles: u64,
+    /// The system config updates in the L1 blocks the span is derived from, which derivation
+    /// applies mid-span.
+    #[serde(default)]
+    pub system_config_updates: Vec<SystemConfigUpdate>,
+}
+
+/// How a range of blocks would be split into spans and what proving them would take.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangePlan {
+    pub start: u64,
+    pub end: u64,
+    /// Whether the spans are split at the batches the blocks are derived from, which requires the
+    /// safe DB on the L2 node. Otherwise they are split every `max_span_size` blocks.
+    pub safe_db_activated: bool,
+    pub spans: Vec<SpanPlan>,
+    /// The blobs read by all spans. Spans derived from the same L1 blocks each read their blobs.
+    pub blobs: u64,
+    pub estimated_cycles: u64,
+}
+
+/// Plan how the range after `start` up to `end` would be proven, without proving or executing
+/// anything: the spans the proposer splits it into, the L1 blocks and blobs each span is derived
+/// from, and a rough estimate of each span's cycles.
+pub async fn plan_range(
+    data_fetcher: &OPSuccinctDataFetcher,
+    start: u64,
+    end: u64,
+    max_span_size: u64,
+) -> Result<RangePlan> {
+    let (start, end) = get_validated_block_range(data_fetcher, Some(start), Some(end), 0).await?;
+
+    // Split the range the same way as the proposer.
+    let safe_db_activated = data_fetcher.is_safe_db_activated().await?;
+    let ranges = if safe_db_activated {
+        split_range_based_on_safe_heads(start, end, max_span_size).await?
+    } else {
+        split_range_basic(start, end, max_span_size)
+    };
+
+    // Batches are posted as calldata on settlement layers without blobs.
+    let inbox = if data_fetcher.settlement_layer.has_blobs() {
+        Some(BatcherInbox::from_fetcher(data_fetcher)?)
+    } else {
+        None
+    };
+    // The L1 blocks of neighbouring spans overlap, so only fetch each block once.
+    let mut blob_counts = HashMap::new();
+
+    let mut spans = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        let l1_origin_start = data_fetcher
+            .l2_block_info_by_number(range.start + 1)
+            .await?
+            .l1_origin
+            .number;
+        let l1_origin_end = data_fetcher
+            .l2_block_info_by_number(range.end)
+            .await?
+            .l1_origin
+            .number;
+        let (_, l1_head) = data_fetcher.get_l1_head(range.end).await?;
+
+        let mut blobs = 0;
+        if let Some(inbox) = &inbox {
+            for l1_block in l1_origin_start..=l1_head {
+                let count = match blob_counts.get(&l1_block) {
+                    Some(count) => *count,
+                    None => {
+                        let block = data_fetcher
+                            .l1_provider
+                            .get_block_by_number(l1_block.into(), BlockTransactionsKind::Full)
+                            .await?
+                            .ok_or_else(|| anyhow!("L1 block {l1_block} not found"))?;
+                        let count = inbox.blob_count(block.transactions.txns()) as u64;
+                        blob_counts.insert(l1_block, count);
+                        count
+                    }
+                };
+                blobs += count;
+            }
+        }
+
+        let block_data = data_fetcher
+            .get_l2_block_data_range(range.start, range.end)
+            .await?;
+        let system_config_updates =
+            get_system_config_updates(data_fetcher, l1_origin_start, l1_head).await?;
+        spans.push(SpanPlan {
+            start: range.start,
+            end: range.end,
+            l1_origin_start,
+            l1_origin_end,
+            l1_head,
+            blobs,
+            estimated_cycles: estimate_cycles(&block_data, blobs),
+            system_config_updates,
+        });
+    }
+
+    Ok(RangePlan {
+        start,
+        end,
+        safe_db_activated,
+        blobs: spans.iter().map(|span| span.blobs).sum(),
+        estimated_cycles: spans.iter().map(|span| span.estimated_cycles).sum(),
+        spans,
+    })
+}
+
+/// A rough estimate of the cycles of a span, from its blocks and the blobs it reads.
+pub fn estimate_cycles(block_data: &[BlockInfo], blobs: u64) -> u64 {
+    let gas_used: u64 = block_data.iter().map(|block| block.gas_used).sum();
+    block_data.len() as u64 * ESTIMATED_CYCLES_PER_BLOCK
+        + gas_used * ESTIMATED_CYCLES_PER_GAS
+        + blobs * ESTIMATED_CYCLES_PER_BLOB
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(boundaries: &[u64]) -> Vec<SpanBatchRange> {
+        boundaries
+            .windows(2)
+            .map(|w| SpanBatchRange {
+                start: w[0],
+                end: w[1],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_align_ranges_mid_epoch_boundary() {
+        // Epochs of 6 blocks starting at multiples of 6. Block 10 is in the epoch [6, 11], so the
+        // boundary moves back to block 5, the last block of the previous epoch.
+        let aligned = align_ranges_to_epochs(&ranges(&[0, 10, 20]), |b| b - b % 6);
+        assert_eq!(aligned, ranges(&[0, 5, 20]));
+    }
+
+    #[test]
+    fn test_align_ranges_boundary_at_epoch_end() {
+        // Block 11 is the last block of the epoch [6, 11], so the boundary is already aligned.
+        let aligned = align_ranges_to_epochs(&ranges(&[0, 11, 20]), |b| b - b % 6);
+        assert_eq!(aligned, ranges(&[0, 11, 20]));
+    }
+
+    #[test]
+    fn test_align_ranges_single_epoch_span() {
+        // A long run of blocks with the same L1 origin (e.g. the sequencer hitting the max drift
+        // while the L1 origin is stalled) cannot be split on an epoch boundary, so the boundaries
+        // are kept.
+        let aligned = align_ranges_to_epochs(&ranges(&[100, 110, 120, 130]), |_| 50);
+        assert_eq!(aligned, ranges(&[100, 110, 120, 130]));
+    }
+
+    #[test]
+    fn test_align_ranges_deposits_only_epoch_start() {
+        // The boundary lands on the deposits-only first block of the epoch starting at 12. The
+        // block belongs with the rest of its epoch, so the boundary moves back to 11.
+        let aligned = align_ranges_to_epochs(&ranges(&[0, 12, 24]), |b| b - b % 6);
+        assert_eq!(aligned, ranges(&[0, 11, 24]));
+    }
+
+    #[test]
+    fn test_align_ranges_keeps_outer_bounds() {
+        let aligned = align_ranges_to_epochs(&ranges(&[3, 9, 16]), |b| b - b % 6);
+        assert_eq!(aligned.first().unwrap().start, 3);
+        assert_eq!(aligned.last().unwrap().end, 16);
+        // Ranges stay contiguous.
+        aligned
+            .windows(2)
+            .for_each(|w| assert_eq!(w[0].end, w[1].start));
+    }
+
+    #[test]
+    fn test_align_ranges_empty() {
+        assert!(align_ranges_to_epochs(&[], |b| b).is_empty());
+    }
+
+    #[test]
+    fn test_estimate_cycles() {
+        let block = |gas_used| BlockInfo {
+            block_number: 0,
+            transaction_count: 0,
+            gas_used,
+            total_l1_fees: 0,
+            total_tx_fees: 0,
+        };
+        assert_eq!(estimate_cycles(&[], 0), 0);
+        assert_eq!(
+            estimate_cycles(&[block(1_000), block(3_000)], 2),
+            2 * ESTIMATED_CYCLES_PER_BLOCK
+                + 4_000 * ESTIMATED_CYCLES_PER_GAS
+                + 2 * ESTIMATED_CYCLES_PER_BLOB
+        );
+    }
+}
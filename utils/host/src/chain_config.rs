@@ -0,0 +1,389 @@
+//! The rollup config as recorded on chain.
+//!
+//! The rollup config is normally fetched from the L2 node's `optimism_rollupConfig`, which reports
+//! whatever the node was started with. The fields anchored on L1 can instead be read from the
+//! chain's `SystemConfig` contract, the source of truth derivation follows: the batch inbox, the
+//! deposit contract (the `OptimismPortal`), the L1 genesis block, and the L1 and L2 chain IDs. The
+//! L2 genesis is read from the L2 chain at the config's genesis block. Diffing these against a local
+//! or RPC config catches drift, e.g. a rollup config file left over from before a redeployment.
+//!
+//! The block time, the sequencing windows and the hardfork activation times aren't recorded on L1,
+//! so they still come from the L2 node.
+
+use std::{env, fmt};
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::{BlockNumHash, BlockNumberOrTag};
+use alloy_primitives::{Address, U256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_sol_types::sol;
+use anyhow::{anyhow, bail, Result};
+use maili_genesis::RollupConfig;
+use op_alloy_network::{
+    primitives::{BlockTransactionsKind, HeaderResponse},
+    BlockResponse, Network,
+};
+use serde::Serialize;
+
+use crate::config::var_from;
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface ISystemConfig {
+        function batchInbox() external view returns (address);
+        function optimismPortal() external view returns (address);
+        function startBlock() external view returns (uint256);
+        function batcherHash() external view returns (bytes32);
+        function gasLimit() external view returns (uint64);
+        function scalar() external view returns (uint256);
+        function unsafeBlockSigner() external view returns (address);
+        function superchainConfig() external view returns (address);
+    }
+
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    interface ISuperchainConfig {
+        function paused() external view returns (bool);
+    }
+}
+
+/// Where the rollup config is read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RollupConfigSource {
+    /// The L2 node's `optimism_rollupConfig` and the L2 execution client's `debug_chainConfig`.
+    #[default]
+    Rpc,
+    /// The RPC config, with the fields anchored on L1 read from the `SystemConfig` contract.
+    Chain {
+        /// The `SystemConfig` contract, if not the one the L2 node reports.
+        system_config: Option<Address>,
+    },
+}
+
+impl RollupConfigSource {
+    /// Read the source from the environment.
+    ///
+    /// - `ROLLUP_CONFIG_SOURCE`: `rpc` or `chain`. Default: `rpc`.
+    /// - `L1_SYSTEM_CONFIG_ADDRESS`: The `SystemConfig` contract read by `chain`. Default: the one
+    ///   the L2 node reports.
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars(&|name| env::var(name).ok())
+    }
+
+    /// Read the source from `vars` rather than the environment, like [`Self::from_env`].
+    pub fn from_vars(vars: &dyn Fn(&str) -> Option<String>) -> Result<Self> {
+        let system_config = var_from(vars, "L1_SYSTEM_CONFIG_ADDRESS")?;
+        match var_from::<String>(vars, "ROLLUP_CONFIG_SOURCE")?
+            .map(|s| s.to_lowercase())
+            .as_deref()
+        {
+            None | Some("rpc") => {
+                if system_config.is_some() {
+                    bail!("L1_SYSTEM_CONFIG_ADDRESS requires ROLLUP_CONFIG_SOURCE=chain");
+                }
+                Ok(Self::Rpc)
+            }
+            Some("chain") => Ok(Self::Chain { system_config }),
+            Some(source) => bail!("Unknown ROLLUP_CONFIG_SOURCE {source}, expected rpc or chain"),
+        }
+    }
+}
+
+impl fmt::Display for RollupConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rpc => write!(f, "rpc"),
+            Self::Chain {
+                system_config: Some(address),
+            } => write!(f, "chain ({address})"),
+            Self::Chain {
+                system_config: None,
+            } => write!(f, "chain"),
+        }
+    }
+}
+
+/// The rollup config fields read from chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OnChainRollupConfig {
+    pub l1_chain_id: u64,
+    pub l2_chain_id: u64,
+    pub l1_system_config_address: Address,
+    pub batch_inbox_address: Address,
+    pub deposit_contract_address: Address,
+    /// The L1 block the `SystemConfig` was deployed at, from which derivation starts.
+    pub genesis_l1: BlockNumHash,
+    pub genesis_l2: BlockNumHash,
+    pub genesis_l2_time: u64,
+    /// The current system config. Unlike the fields above, it may legitimately differ from the
+    /// genesis system config of the rollup config, as it is updated over the chain's lifetime.
+    pub system_config: CurrentSystemConfig,
+}
+
+/// The current values of the `SystemConfig` contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CurrentSystemConfig {
+    pub batcher_address: Address,
+    pub gas_limit: u64,
+    pub scalar: U256,
+    pub unsafe_block_signer: Address,
+    /// The `SuperchainConfig` the chain is governed by, if its `SystemConfig` has one.
+    pub superchain_config: Option<Address>,
+    /// Whether the superchain is paused, which halts withdrawals but not derivation.
+    pub superchain_paused: Option<bool>,
+}
+
+/// Read the rollup config fields recorded on chain. `l2_genesis_number` is the L2 genesis block,
+/// which L1 doesn't record.
+pub async fn fetch_on_chain_rollup_config<N: Network>(
+    l1_provider: &RootProvider,
+    l2_provider: &RootProvider<N>,
+    system_config_address: Address,
+    l2_genesis_number: u64,
+) -> Result<OnChainRollupConfig> {
+    let system_config = ISystemConfig::new(system_config_address, l1_provider);
+    if l1_provider
+        .get_code_at(system_config_address)
+        .await?
+        .is_empty()
+    {
+        bail!("No SystemConfig contract is deployed at {system_config_address}");
+    }
+
+    let start_block: u64 = system_config.startBlock().call().await?._0.try_into()?;
+    let genesis_l1 = l1_provider
+        .get_block_by_number(start_block.into(), BlockTransactionsKind::Hashes)
+        .await?
+        .ok_or_else(|| anyhow!("L1 block {start_block} not found"))?;
+    let genesis_l2 = l2_provider
+        .get_block_by_number(
+            BlockNumberOrTag::Number(l2_genesis_number),
+            BlockTransactionsKind::Hashes,
+        )
+        .await?
+        .ok_or_else(|| anyhow!("L2 genesis block {l2_genesis_number} not found"))?;
+
+    // Older `SystemConfig` versions predate the `SuperchainConfig`.
+    let superchain_config = system_config
+        .superchainConfig()
+        .call()
+        .await
+        .ok()
+        .map(|r| r._0);
+    let superchain_paused = match superchain_config {
+        Some(address) => Some(
+            ISuperchainConfig::new(address, l1_provider)
+                .paused()
+                .call()
+                .await?
+                ._0,
+        ),
+        None => None,
+    };
+
+    Ok(OnChainRollupConfig {
+        l1_chain_id: l1_provider.get_chain_id().await?,
+        l2_chain_id: l2_provider.get_chain_id().await?,
+        l1_system_config_address: system_config_address,
+        batch_inbox_address: system_config.batchInbox().call().await?._0,
+        deposit_contract_address: system_config.optimismPortal().call().await?._0,
+        genesis_l1: BlockNumHash {
+            number: start_block,
+            hash: genesis_l1.header.hash,
+        },
+        genesis_l2: BlockNumHash {
+            number: l2_genesis_number,
+            hash: genesis_l2.header().hash(),
+        },
+        genesis_l2_time: genesis_l2.header().timestamp(),
+        system_config: CurrentSystemConfig {
+            batcher_address: Address::from_word(system_config.batcherHash().call().await?._0),
+            gas_limit: system_config.gasLimit().call().await?._0,
+            scalar: system_config.scalar().call().await?._0,
+            unsafe_block_signer: system_config.unsafeBlockSigner().call().await?._0,
+            superchain_config,
+            superchain_paused,
+        },
+    })
+}
+
+/// A field whose value in a rollup config differs from the one recorded on chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigDrift {
+    pub field: &'static str,
+    pub local: String,
+    pub on_chain: String,
+}
+
+impl fmt::Display for ConfigDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: local {}, on chain {}",
+            self.field, self.local, self.on_chain
+        )
+    }
+}
+
+/// The fields of `rollup_config` that differ from the ones recorded on chain.
+pub fn diff_rollup_config(
+    rollup_config: &RollupConfig,
+    on_chain: &OnChainRollupConfig,
+) -> Vec<ConfigDrift> {
+    let mut drift = Vec::new();
+    let mut compare = |field: &'static str, local: String, on_chain: String| {
+        if local != on_chain {
+            drift.push(ConfigDrift {
+                field,
+                local,
+                on_chain,
+            });
+        }
+    };
+    compare(
+        "l1_chain_id",
+        rollup_config.l1_chain_id.to_string(),
+        on_chain.l1_chain_id.to_string(),
+    );
+    compare(
+        "l2_chain_id",
+        rollup_config.l2_chain_id.to_string(),
+        on_chain.l2_chain_id.to_string(),
+    );
+    compare(
+        "l1_system_config_address",
+        rollup_config.l1_system_config_address.to_string(),
+        on_chain.l1_system_config_address.to_string(),
+    );
+    compare(
+        "batch_inbox_address",
+        rollup_config.batch_inbox_address.to_string(),
+        on_chain.batch_inbox_address.to_string(),
+    );
+    compare(
+        "deposit_contract_address",
+        rollup_config.deposit_contract_address.to_string(),
+        on_chain.deposit_contract_address.to_string(),
+    );
+    compare(
+        "genesis.l1.number",
+        rollup_config.genesis.l1.number.to_string(),
+        on_chain.genesis_l1.number.to_string(),
+    );
+    compare(
+        "genesis.l1.hash",
+        rollup_config.genesis.l1.hash.to_string(),
+        on_chain.genesis_l1.hash.to_string(),
+    );
+    compare(
+        "genesis.l2.hash",
+        rollup_config.genesis.l2.hash.to_string(),
+        on_chain.genesis_l2.hash.to_string(),
+    );
+    compare(
+        "genesis.l2_time",
+        rollup_config.genesis.l2_time.to_string(),
+        on_chain.genesis_l2_time.to_string(),
+    );
+    drift
+}
+
+/// Overwrite the fields of `rollup_config` recorded on chain with their on-chain values.
+pub fn apply_on_chain_rollup_config(
+    rollup_config: &mut RollupConfig,
+    on_chain: &OnChainRollupConfig,
+) {
+    rollup_config.l1_chain_id = on_chain.l1_chain_id;
+    rollup_config.l2_chain_id = on_chain.l2_chain_id;
+    rollup_config.l1_system_config_address = on_chain.l1_system_config_address;
+    rollup_config.batch_inbox_address = on_chain.batch_inbox_address;
+    rollup_config.deposit_contract_address = on_chain.deposit_contract_address;
+    rollup_config.genesis.l1 = on_chain.genesis_l1;
+    rollup_config.genesis.l2 = on_chain.genesis_l2;
+    rollup_config.genesis.l2_time = on_chain.genesis_l2_time;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, B256};
+    use std::collections::HashMap;
+
+    fn on_chain(rollup_config: &RollupConfig) -> OnChainRollupConfig {
+        OnChainRollupConfig {
+            l1_chain_id: rollup_config.l1_chain_id,
+            l2_chain_id: rollup_config.l2_chain_id,
+            l1_system_config_address: rollup_config.l1_system_config_address,
+            batch_inbox_address: rollup_config.batch_inbox_address,
+            deposit_contract_address: rollup_config.deposit_contract_address,
+            genesis_l1: rollup_config.genesis.l1,
+            genesis_l2: rollup_config.genesis.l2,
+            genesis_l2_time: rollup_config.genesis.l2_time,
+            system_config: CurrentSystemConfig {
+                batcher_address: Address::ZERO,
+                gas_limit: 30_000_000,
+                scalar: U256::ZERO,
+                unsafe_block_signer: Address::ZERO,
+                superchain_config: None,
+                superchain_paused: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_diff_rollup_config() {
+        let mut rollup_config = RollupConfig {
+            l1_chain_id: 11155111,
+            l2_chain_id: 11155420,
+            batch_inbox_address: address!("ff00000000000000000000000000000011155420"),
+            ..Default::default()
+        };
+        rollup_config.genesis.l1 = BlockNumHash {
+            number: 4071408,
+            hash: B256::repeat_byte(1),
+        };
+        let mut on_chain = on_chain(&rollup_config);
+        assert!(diff_rollup_config(&rollup_config, &on_chain).is_empty());
+
+        // The current system config is expected to move on from the genesis one.
+        on_chain.system_config.gas_limit = 60_000_000;
+        assert!(diff_rollup_config(&rollup_config, &on_chain).is_empty());
+
+        on_chain.batch_inbox_address = address!("ff00000000000000000000000000000000000001");
+        on_chain.genesis_l1.hash = B256::repeat_byte(2);
+        let drift = diff_rollup_config(&rollup_config, &on_chain);
+        assert_eq!(
+            drift.iter().map(|d| d.field).collect::<Vec<_>>(),
+            ["batch_inbox_address", "genesis.l1.hash"]
+        );
+
+        apply_on_chain_rollup_config(&mut rollup_config, &on_chain);
+        assert!(diff_rollup_config(&rollup_config, &on_chain).is_empty());
+    }
+
+    #[test]
+    fn test_rollup_config_source_from_vars() {
+        let source = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+            RollupConfigSource::from_vars(&|name| vars.get(name).cloned())
+        };
+        let system_config = (
+            "L1_SYSTEM_CONFIG_ADDRESS",
+            "0x034edD2A225f7f429A63E0f1D2084B9E0A93b538",
+        );
+
+        assert_eq!(source(&[]).unwrap(), RollupConfigSource::Rpc);
+        assert!(source(&[system_config]).is_err());
+        assert_eq!(
+            source(&[("ROLLUP_CONFIG_SOURCE", "chain"), system_config]).unwrap(),
+            RollupConfigSource::Chain {
+                system_config: Some(address!("034edD2A225f7f429A63E0f1D2084B9E0A93b538"))
+            }
+        );
+        assert!(source(&[("ROLLUP_CONFIG_SOURCE", "file")]).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Failure injection for exercising the retry and recovery logic of the proving pipeline in
+//! integration tests. Only compiled with the `chaos` feature. Without it, [`inject`] is a no-op.
+//!
+//! Failures are configured with `CHAOS_FAILURES`, a comma separated list of `stage:count`, where
+//! `count` is the number of times the stage fails before it succeeds again, or `*` to always fail.
+//! For example, `CHAOS_FAILURES=witnessgen:2,propose:1` fails the next two witness generations and
+//! the next proposal. Tests can also configure failures directly with [`set_failures`].
+
+use anyhow::Result;
+
+/// A stage of the pipeline at which a failure can be injected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FailurePoint {
+    /// An RPC request made before witness generation.
+    Rpc,
+    /// Witness generation, failing as if it timed out.
+    WitnessGen,
+    /// A proof request to the prover network.
+    Prover,
+    /// The proposal transaction, failing as if it reverted.
+    Propose,
+}
+
+#[cfg(feature = "chaos")]
+mod imp {
+    use std::{
+        collections::HashMap,
+        env,
+        sync::{LazyLock, Mutex},
+    };
+
+    use anyhow::{anyhow, bail, Result};
+    use log::warn;
+
+    use super::FailurePoint;
+
+    /// How many more times a stage fails. `None` fails forever.
+    type Failures = HashMap<FailurePoint, Option<u64>>;
+
+    static FAILURES: LazyLock<Mutex<Failures>> = LazyLock::new(|| {
+        let failures = env::var("CHAOS_FAILURES")
+            .map(|spec| parse(&spec).expect("CHAOS_FAILURES must be a list of stage:count"))
+            .unwrap_or_default();
+        Mutex::new(failures)
+    });
+
+    impl FailurePoint {
+        fn from_name(name: &str) -> Result<Self> {
+            match name {
+                "rpc" => Ok(Self::Rpc),
+                "witnessgen" => Ok(Self::WitnessGen),
+                "prover" => Ok(Self::Prover),
+                "propose" => Ok(Self::Propose),
+                _ => bail!("Unknown failure point {name}"),
+            }
+        }
+
+        fn error(self) -> anyhow::Error {
+            match self {
+                Self::Rpc => anyhow!("Injected failure: RPC request failed"),
+                Self::WitnessGen => anyhow!("Injected failure: witness generation timed out"),
+                Self::Prover => anyhow!("Injected failure: prover network unavailable"),
+                Self::Propose => anyhow!("Injected failure: propose transaction reverted"),
+            }
+        }
+    }
+
+    pub(super) fn parse(spec: &str) -> Result<Failures> {
+        spec.split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| {
+                let (name, count) = entry
+                    .trim()
+                    .split_once(':')
+                    .ok_or_else(|| anyhow!("Expected stage:count, got {entry}"))?;
+                let count = match count {
+                    "*" => None,
+                    count => Some(count.parse()?),
+                };
+                Ok((FailurePoint::from_name(name)?, count))
+            })
+            .collect()
+    }
+
+    pub fn set_failures(failures: impl IntoIterator<Item = (FailurePoint, Option<u64>)>) {
+        *FAILURES.lock().unwrap() = failures.into_iter().collect();
+    }
+
+    pub fn inject(point: FailurePoint) -> Result<()> {
+        let mut failures = FAILURES.lock().unwrap();
+        let fail = match failures.get_mut(&point) {
+            Some(None) => true,
+            Some(Some(remaining)) if *remaining > 0 => {
+                *remaining -= 1;
+                true
+            }
+            _ => false,
+        };
+        if fail {
+            warn!("Injecting failure at {:?}", point);
+            return Err(point.error());
+        }
+        Ok(())
+    }
+}
+
+/// Configure the injected failures, replacing the current configuration. Each stage fails the
+/// given number of times, or forever if `None`.
+#[cfg(feature = "chaos")]
+pub use imp::set_failures;
+
+/// Fail if a failure is configured for the stage.
+#[inline]
+pub fn inject(point: FailurePoint) -> Result<()> {
+    #[cfg(feature = "chaos")]
+    return imp::inject(point);
+    #[cfg(not(feature = "chaos"))]
+    {
+        let _ = point;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "chaos"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_injected_failures() {
+        assert_eq!(
+            imp::parse("witnessgen:2, prover:*").unwrap(),
+            [
+                (FailurePoint::WitnessGen, Some(2)),
+                (FailurePoint::Prover, None)
+            ]
+            .into_iter()
+            .collect()
+        );
+        assert!(imp::parse("unknown:1").is_err());
+
+        set_failures([(FailurePoint::Propose, Some(1))]);
+        assert!(inject(FailurePoint::Propose).is_err());
+        assert!(inject(FailurePoint::Propose).is_ok());
+        assert!(inject(FailurePoint::Rpc).is_ok());
+    }
+}
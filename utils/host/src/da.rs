@@ -0,0 +1,417 @@
+//! The sources witness generation reads batch data from.
+//!
+//! Kona's host reads calldata batches from the L1 RPC and blobs through the beacon API. The server
+//! serves the beacon API to witness generation through a proxy backed by a
+//! [`DataAvailabilitySource`], so the batch data of a range can come from somewhere other than the
+//! beacon node (a blob archive, or an external DA layer such as Avail or NearDA) by implementing
+//! the trait, without changes to the fetcher or the host.
+//!
+//! The built-in sources, selected with `DA_SOURCE`:
+//! - `beacon`: blobs from the beacon node at `L1_BEACON_RPC`, read by witness generation directly.
+//! - `calldata`: batches posted as calldata only, for settlement layers without a beacon node (see
+//!   [`NoBlobsBeacon`]).
+//! - `blob-archive`: blobs archived to `BLOB_ARCHIVE_DIR` (see [`BlobArchive`]).
+//! - `external`: blob sidecars served by an external DA bridge at `EXTERNAL_DA_URL`.
+
+use std::{fmt, path::PathBuf, sync::Arc};
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use reqwest::{StatusCode, Url};
+
+use crate::{
+    blob_archive::BlobArchive,
+    config::{env_var, redact_url},
+    fetcher::OPSuccinctDataFetcher,
+    provider_pool::{pool_for, ProviderPool, RequestClass},
+    settlement::{NoBlobsBeacon, SettlementLayer},
+};
+
+/// The path of the beacon API's blob sidecars endpoint, followed by the slot.
+pub const BLOB_SIDECARS_PATH: &str = "eth/v1/beacon/blob_sidecars/";
+
+/// A beacon API request witness generation makes other than for blob sidecars. Only these are
+/// served, so the proxy can't be used to reach any other endpoint of the beacon node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BeaconRequest {
+    /// `eth/v1/beacon/genesis`: the genesis time.
+    Genesis,
+    /// `eth/v1/config/spec`: the slot duration.
+    Spec,
+}
+
+impl BeaconRequest {
+    /// The path of the request, relative to the API's root.
+    pub fn path(&self) -> &'static str {
+        match self {
+            Self::Genesis => "eth/v1/beacon/genesis",
+            Self::Spec => "eth/v1/config/spec",
+        }
+    }
+}
+
+/// A beacon API request witness generation makes, parsed from its path and query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxiedRequest {
+    BlobSidecars(u64),
+    Beacon(BeaconRequest),
+}
+
+impl ProxiedRequest {
+    /// Parse a request, rejecting any not on the allowlist. The query of a blob sidecars request
+    /// is dropped, as all sidecars of the slot are served.
+    pub fn parse(path_and_query: &str) -> Result<Self> {
+        let (path, query) = match path_and_query.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (path_and_query, None),
+        };
+        if let Some(slot) = path.strip_prefix(BLOB_SIDECARS_PATH) {
+            if let Ok(slot) = slot.parse::<u64>() {
+                return Ok(Self::BlobSidecars(slot));
+            }
+        } else if query.is_none() {
+            for request in [BeaconRequest::Genesis, BeaconRequest::Spec] {
+                if path == request.path() {
+                    return Ok(Self::Beacon(request));
+                }
+            }
+        }
+        bail!("Unsupported beacon API request {path_and_query}")
+    }
+}
+
+/// A source of the batch data witness generation reads through the beacon API.
+#[async_trait]
+pub trait DataAvailabilitySource: Send + Sync {
+    /// The response to `GET eth/v1/beacon/blob_sidecars/{slot}`: the blob sidecars of the batches
+    /// posted in an L1 slot.
+    async fn blob_sidecars(&self, slot: u64) -> Result<Vec<u8>>;
+
+    /// The response to any other beacon API request witness generation makes, such as the genesis
+    /// time and slot duration.
+    async fn beacon_request(&self, request: BeaconRequest) -> Result<Vec<u8>>;
+
+    /// Answer a beacon API request, routing blob sidecar requests to
+    /// [`DataAvailabilitySource::blob_sidecars`].
+    async fn get(&self, request: ProxiedRequest) -> Result<Vec<u8>> {
+        match request {
+            ProxiedRequest::BlobSidecars(slot) => self.blob_sidecars(slot).await,
+            ProxiedRequest::Beacon(request) => self.beacon_request(request).await,
+        }
+    }
+}
+
+/// A beacon API, such as a beacon node's. Requests are routed between the endpoints of its pool
+/// (see [`provider_pool`](crate::provider_pool)).
+#[derive(Debug, Clone)]
+pub struct BeaconNode {
+    pool: Arc<ProviderPool>,
+    client: reqwest::Client,
+}
+
+impl BeaconNode {
+    /// The beacon API at a URL, with the endpoints pooled with it if any.
+    pub fn new(url: Url) -> Self {
+        Self::from_pool(pool_for(&url))
+    }
+
+    pub fn from_pool(pool: Arc<ProviderPool>) -> Self {
+        Self {
+            pool,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn url(&self) -> &Url {
+        self.pool.primary()
+    }
+
+    /// Send a GET request for `path` to the API. The path is appended to each endpoint's URL
+    /// segment by segment, so it can't replace the endpoint's host or path.
+    async fn get(&self, path: &str) -> Result<Vec<u8>> {
+        let class = RequestClass::of_beacon_path(path);
+        self.pool
+            .route(class, |index| {
+                let url = endpoint_url(&self.pool.endpoints()[index], path);
+                async move { get(&self.client, url?, path).await }
+            })
+            .await
+    }
+}
+
+/// The URL of `path` on the API at `base`.
+fn endpoint_url(base: &Url, path: &str) -> Result<Url> {
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .map_err(|_| anyhow!("Invalid beacon API URL {}", redact_url(base)))?
+        .pop_if_empty()
+        .extend(path.split('/'));
+    Ok(url)
+}
+
+async fn get(client: &reqwest::Client, url: Url, name: &str) -> Result<Vec<u8>> {
+    let response = client.get(url).send().await?;
+    if response.status() != StatusCode::OK {
+        bail!("Data source returned {} for {}", response.status(), name);
+    }
+    Ok(response.bytes().await?.to_vec())
+}
+
+#[async_trait]
+impl DataAvailabilitySource for BeaconNode {
+    async fn blob_sidecars(&self, slot: u64) -> Result<Vec<u8>> {
+        self.get(&format!("{BLOB_SIDECARS_PATH}{slot}")).await
+    }
+
+    async fn beacon_request(&self, request: BeaconRequest) -> Result<Vec<u8>> {
+        self.get(request.path()).await
+    }
+}
+
+#[async_trait]
+impl DataAvailabilitySource for BlobArchive {
+    async fn blob_sidecars(&self, slot: u64) -> Result<Vec<u8>> {
+        self.get_blob_sidecars(slot).await
+    }
+
+    async fn beacon_request(&self, request: BeaconRequest) -> Result<Vec<u8>> {
+        self.get_beacon(request).await
+    }
+}
+
+#[async_trait]
+impl DataAvailabilitySource for NoBlobsBeacon {
+    async fn blob_sidecars(&self, slot: u64) -> Result<Vec<u8>> {
+        NoBlobsBeacon::get(self, &format!("{BLOB_SIDECARS_PATH}{slot}"))
+    }
+
+    async fn beacon_request(&self, request: BeaconRequest) -> Result<Vec<u8>> {
+        NoBlobsBeacon::get(self, request.path())
+    }
+}
+
+/// Blob sidecars served by a bridge to an external DA layer, in the beacon API's response format.
+/// The other beacon API requests are forwarded to the beacon node.
+#[derive(Debug, Clone)]
+pub struct ExternalDa {
+    /// The URL of a slot's blob sidecars, with `{slot}` in place of the slot.
+    sidecars_url: String,
+    beacon: BeaconNode,
+    client: reqwest::Client,
+}
+
+impl ExternalDa {
+    pub fn new(sidecars_url: String, beacon: BeaconNode) -> Result<Self> {
+        if !sidecars_url.contains("{slot}") {
+            bail!("The external DA URL {sidecars_url} must contain {{slot}}");
+        }
+        Ok(Self {
+            sidecars_url,
+            beacon,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    fn url(&self, slot: u64) -> Result<Url> {
+        Ok(self
+            .sidecars_url
+            .replace("{slot}", &slot.to_string())
+            .parse()?)
+    }
+}
+
+#[async_trait]
+impl DataAvailabilitySource for ExternalDa {
+    async fn blob_sidecars(&self, slot: u64) -> Result<Vec<u8>> {
+        let name = format!("the blob sidecars of slot {slot}");
+        get(&self.client, self.url(slot)?, &name).await
+    }
+
+    async fn beacon_request(&self, request: BeaconRequest) -> Result<Vec<u8>> {
+        self.beacon.beacon_request(request).await
+    }
+}
+
+/// The data availability source witness generation reads from, set with `DA_SOURCE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaSourceConfig {
+    Beacon,
+    Calldata,
+    BlobArchive { dir: PathBuf },
+    External { sidecars_url: String },
+}
+
+impl DaSourceConfig {
+    /// Read the source from the environment.
+    ///
+    /// - `DA_SOURCE`: `beacon`, `calldata`, `blob-archive` or `external`. Defaults to `calldata` on
+    ///   settlement layers without blobs, to `blob-archive` if `BLOB_ARCHIVE_DIR` is set, and to
+    ///   `beacon` otherwise.
+    /// - `BLOB_ARCHIVE_DIR`: The archive directory, required by `blob-archive`.
+    /// - `EXTERNAL_DA_URL`: The URL of a slot's blob sidecars with `{slot}` in place of the slot,
+    ///   required by `external`.
+    pub fn from_env() -> Result<Self> {
+        let settlement_layer = SettlementLayer::from_env()?;
+        let blob_archive_dir = env_var::<PathBuf>("BLOB_ARCHIVE_DIR")?;
+        let source = match env_var::<String>("DA_SOURCE")?.map(|s| s.to_lowercase()) {
+            Some(source) => source,
+            None if !settlement_layer.has_blobs() => "calldata".to_string(),
+            None if blob_archive_dir.is_some() => "blob-archive".to_string(),
+            None => "beacon".to_string(),
+        };
+
+        let config = match source.as_str() {
+            "beacon" => Self::Beacon,
+            "calldata" => Self::Calldata,
+            "blob-archive" => Self::BlobArchive {
+                dir: blob_archive_dir
+                    .ok_or_else(|| anyhow!("DA_SOURCE=blob-archive requires BLOB_ARCHIVE_DIR"))?,
+            },
+            "external" => Self::External {
+                sidecars_url: env_var("EXTERNAL_DA_URL")?
+                    .ok_or_else(|| anyhow!("DA_SOURCE=external requires EXTERNAL_DA_URL"))?,
+            },
+            _ => bail!(
+                "Unknown DA_SOURCE {source}, expected beacon, calldata, blob-archive or external"
+            ),
+        };
+        if config != Self::Calldata && !settlement_layer.has_blobs() {
+            bail!("{settlement_layer:?} settlement layers only support DA_SOURCE=calldata");
+        }
+        Ok(config)
+    }
+
+    /// Whether witness generation reads the source through the server's beacon API proxy, rather
+    /// than from the beacon node directly.
+    pub fn is_proxied(&self) -> bool {
+        !matches!(self, Self::Beacon)
+    }
+
+    /// Build the source for the chain of the fetcher.
+    pub async fn build(
+        &self,
+        fetcher: &OPSuccinctDataFetcher,
+    ) -> Result<Arc<dyn DataAvailabilitySource>> {
+        let beacon = || BeaconNode::from_pool(fetcher.rpc_config.l1_beacon_pool.clone());
+        Ok(match self {
+            Self::Beacon => Arc::new(beacon()),
+            Self::Calldata => Arc::new(NoBlobsBeacon::new(fetcher).await?),
+            Self::BlobArchive { dir } => Arc::new(BlobArchive::new(dir.clone(), beacon())?),
+            Self::External { sidecars_url } => {
+                Arc::new(ExternalDa::new(sidecars_url.clone(), beacon())?)
+            }
+        })
+    }
+}
+
+impl fmt::Display for DaSourceConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Beacon => write!(f, "beacon"),
+            Self::Calldata => write!(f, "calldata"),
+            Self::BlobArchive { dir } => write!(f, "blob-archive ({})", dir.display()),
+            Self::External { sidecars_url } => match sidecars_url.parse::<Url>() {
+                Ok(url) => write!(f, "external ({})", redact_url(&url)),
+                Err(_) => write!(f, "external"),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Records the requests it receives.
+    #[derive(Default)]
+    struct RecordingSource(Mutex<Vec<String>>);
+
+    #[async_trait]
+    impl DataAvailabilitySource for RecordingSource {
+        async fn blob_sidecars(&self, slot: u64) -> Result<Vec<u8>> {
+            self.0.lock().unwrap().push(format!("sidecars {slot}"));
+            Ok(vec![])
+        }
+
+        async fn beacon_request(&self, request: BeaconRequest) -> Result<Vec<u8>> {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("beacon {}", request.path()));
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_routes_blob_sidecars() {
+        let source = RecordingSource::default();
+        for path in [
+            "eth/v1/beacon/blob_sidecars/42",
+            "eth/v1/beacon/blob_sidecars/43?indices=0,1",
+            "eth/v1/beacon/genesis",
+            "eth/v1/config/spec",
+        ] {
+            let request = ProxiedRequest::parse(path).unwrap();
+            source.get(request).await.unwrap();
+        }
+        assert_eq!(
+            *source.0.lock().unwrap(),
+            [
+                "sidecars 42",
+                "sidecars 43",
+                "beacon eth/v1/beacon/genesis",
+                "beacon eth/v1/config/spec"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_other_requests() {
+        for path in [
+            "eth/v1/beacon/blob_sidecars/head",
+            "eth/v1/beacon/blob_sidecars/1/../../../node/identity",
+            "eth/v1/config/spec?x=1",
+            "eth/v1/node/identity",
+            "//attacker.example/eth/v1/config/spec",
+            "http://attacker.example/eth/v1/config/spec",
+        ] {
+            assert!(ProxiedRequest::parse(path).is_err(), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_endpoint_url() {
+        let path = "eth/v1/beacon/blob_sidecars/7";
+        for (base, expected) in [
+            (
+                "http://beacon:5052",
+                "http://beacon:5052/eth/v1/beacon/blob_sidecars/7",
+            ),
+            (
+                "http://beacon:5052/",
+                "http://beacon:5052/eth/v1/beacon/blob_sidecars/7",
+            ),
+            (
+                "https://provider.example/key/",
+                "https://provider.example/key/eth/v1/beacon/blob_sidecars/7",
+            ),
+            (
+                "https://provider.example/key?auth=1",
+                "https://provider.example/key/eth/v1/beacon/blob_sidecars/7?auth=1",
+            ),
+        ] {
+            let url = endpoint_url(&base.parse().unwrap(), path).unwrap();
+            assert_eq!(url.as_str(), expected);
+        }
+    }
+
+    #[test]
+    fn test_external_da_url() {
+        let beacon = BeaconNode::new("http://localhost:5052".parse().unwrap());
+        assert!(ExternalDa::new("http://da.example/blobs".to_string(), beacon.clone()).is_err());
+
+        let da = ExternalDa::new("http://da.example/blobs/{slot}".to_string(), beacon).unwrap();
+        assert_eq!(da.url(7).unwrap().as_str(), "http://da.example/blobs/7");
+    }
+}
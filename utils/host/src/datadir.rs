@@ -0,0 +1,297 @@
+//! Witness generation from local execution client databases, for air-gapped proving.
+//!
+//! Witness generation reads headers, receipts, state and execution witnesses from the L1 and L2
+//! execution clients. Deployments that can't expose RPC endpoints can instead point
+//! `L1_DATADIR` and `L2_DATADIR` at snapshots of their databases. At startup, the host runs the
+//! execution client on each snapshot with networking disabled and its RPC bound to a random port
+//! on the loopback interface, and uses it in place of `L1_RPC` and `L2_RPC`. Nothing is reachable
+//! from outside the host, and the snapshots are never synced.
+//!
+//! Blobs aren't stored by execution clients, so they are still read from `L1_BEACON_RPC` (or
+//! `BLOB_ARCHIVE_RPC`), and output roots and sync status from `L2_NODE_RPC`, which can be an
+//! op-node run against the local nodes.
+
+use std::{
+    fmt,
+    net::TcpListener,
+    path::PathBuf,
+    process::Stdio,
+    str::FromStr,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use alloy_provider::{Provider, RootProvider};
+use anyhow::{anyhow, bail, Context, Result};
+use log::info;
+use reqwest::Url;
+use tokio::process::{Child, Command};
+
+use crate::{config::env_var, fetcher::http_provider};
+
+/// How long to wait for a local node to serve RPC requests after it is started.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// The RPC URLs of the local nodes started by [`LocalNodes::start_from_env`], read by
+/// [`RPCConfig::from_env`](crate::fetcher::RPCConfig::from_env) in place of `L1_RPC` and `L2_RPC`.
+static LOCAL_RPCS: OnceLock<LocalRpcs> = OnceLock::new();
+
+#[derive(Debug, Default)]
+struct LocalRpcs {
+    l1: Option<Url>,
+    l2: Option<Url>,
+}
+
+/// The RPC URL of the local L1 node, if the host runs one.
+pub fn local_l1_rpc() -> Option<Url> {
+    LOCAL_RPCS.get().and_then(|rpcs| rpcs.l1.clone())
+}
+
+/// The RPC URL of the local L2 node, if the host runs one.
+pub fn local_l2_rpc() -> Option<Url> {
+    LOCAL_RPCS.get().and_then(|rpcs| rpcs.l2.clone())
+}
+
+/// The execution client a database snapshot was written by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionClient {
+    Geth,
+    Reth,
+}
+
+impl FromStr for ExecutionClient {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "geth" | "op-geth" => Ok(Self::Geth),
+            "reth" | "op-reth" => Ok(Self::Reth),
+            _ => Err(format!("expected geth or reth, got {s}")),
+        }
+    }
+}
+
+impl fmt::Display for ExecutionClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Geth => write!(f, "geth"),
+            Self::Reth => write!(f, "reth"),
+        }
+    }
+}
+
+/// A database snapshot to serve an execution client's RPC from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Datadir {
+    pub path: PathBuf,
+    pub client: ExecutionClient,
+    /// The client's binary.
+    pub binary: PathBuf,
+    /// The chain passed to reth with `--chain`, which it doesn't read from the database.
+    pub chain: Option<String>,
+}
+
+impl Datadir {
+    /// Read the snapshot of one chain from `{prefix}_DATADIR`, `{prefix}_DATADIR_CLIENT`,
+    /// `{prefix}_DATADIR_BIN` and `{prefix}_DATADIR_CHAIN`. Returns `None` if `{prefix}_DATADIR` is
+    /// unset.
+    fn from_env(
+        prefix: &str,
+        default_client: ExecutionClient,
+        op_stack: bool,
+    ) -> Result<Option<Self>> {
+        let Some(path) = env_var::<PathBuf>(&format!("{prefix}_DATADIR"))? else {
+            return Ok(None);
+        };
+        let client = env_var(&format!("{prefix}_DATADIR_CLIENT"))?.unwrap_or(default_client);
+        let binary = env_var(&format!("{prefix}_DATADIR_BIN"))?.unwrap_or_else(|| {
+            let name = match (client, op_stack) {
+                (ExecutionClient::Geth, false) => "geth",
+                (ExecutionClient::Geth, true) => "op-geth",
+                (ExecutionClient::Reth, false) => "reth",
+                (ExecutionClient::Reth, true) => "op-reth",
+            };
+            PathBuf::from(name)
+        });
+        Ok(Some(Self {
+            path,
+            client,
+            binary,
+            chain: env_var(&format!("{prefix}_DATADIR_CHAIN"))?,
+        }))
+    }
+
+    /// The arguments running the client on the snapshot without peers, with its HTTP RPC on
+    /// `127.0.0.1:{port}`.
+    fn args(&self, port: u16) -> Vec<String> {
+        let path = self.path.display().to_string();
+        let port = port.to_string();
+        let mut args = match self.client {
+            ExecutionClient::Geth => vec![
+                "--datadir",
+                path.as_str(),
+                "--nodiscover",
+                "--maxpeers",
+                "0",
+                "--port",
+                "0",
+                "--authrpc.port",
+                "0",
+                "--ipcdisable",
+            ],
+            ExecutionClient::Reth => vec![
+                "node",
+                "--datadir",
+                path.as_str(),
+                "--disable-discovery",
+                "--max-outbound-peers",
+                "0",
+                "--max-inbound-peers",
+                "0",
+                "--port",
+                "0",
+                "--authrpc.port",
+                "0",
+                "--ipcdisable",
+            ],
+        };
+        args.extend([
+            "--http",
+            "--http.addr",
+            "127.0.0.1",
+            "--http.port",
+            port.as_str(),
+            "--http.api",
+            "eth,debug",
+        ]);
+        let mut args: Vec<String> = args.into_iter().map(String::from).collect();
+        if let (ExecutionClient::Reth, Some(chain)) = (self.client, &self.chain) {
+            args.extend(["--chain".to_string(), chain.clone()]);
+        }
+        args
+    }
+}
+
+/// The database snapshots to serve the L1 and L2 RPCs from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Datadirs {
+    pub l1: Option<Datadir>,
+    pub l2: Option<Datadir>,
+}
+
+impl Datadirs {
+    /// Read the snapshots from the environment. `L1_DATADIR_CLIENT` defaults to geth, and
+    /// `L2_DATADIR_CLIENT` to op-geth.
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            l1: Datadir::from_env("L1", ExecutionClient::Geth, false)?,
+            l2: Datadir::from_env("L2", ExecutionClient::Geth, true)?,
+        })
+    }
+}
+
+/// An execution client serving RPC from a database snapshot. Killed when dropped.
+struct LocalNode {
+    url: Url,
+    _child: Child,
+}
+
+impl LocalNode {
+    async fn start(name: &str, datadir: &Datadir) -> Result<Self> {
+        // Let the OS pick a free port. It is released right before the client binds it.
+        let port = TcpListener::bind("127.0.0.1:0")?.local_addr()?.port();
+        let url: Url = format!("http://127.0.0.1:{port}").parse()?;
+
+        info!(
+            "Starting the {name} {} node on {} at {url}",
+            datadir.client,
+            datadir.path.display()
+        );
+        let mut child = Command::new(&datadir.binary)
+            .args(datadir.args(port))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to run {}", datadir.binary.display()))?;
+
+        let provider: RootProvider = http_provider(&url);
+        let started = Instant::now();
+        loop {
+            if let Some(status) = child.try_wait()? {
+                bail!("The {name} node exited with {status} before serving RPC requests");
+            }
+            if let Ok(block) = provider.get_block_number().await {
+                info!("The {name} node serves blocks up to {block}");
+                break;
+            }
+            if started.elapsed() > STARTUP_TIMEOUT {
+                return Err(anyhow!(
+                    "The {name} node didn't serve RPC requests within {}s",
+                    STARTUP_TIMEOUT.as_secs()
+                ));
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+
+        Ok(Self { url, _child: child })
+    }
+}
+
+/// The local nodes serving the L1 and L2 RPCs from database snapshots. They run for as long as
+/// this is held.
+pub struct LocalNodes {
+    _nodes: Vec<LocalNode>,
+}
+
+impl LocalNodes {
+    /// Start a local node for each snapshot in `L1_DATADIR` and `L2_DATADIR`, and use them in place
+    /// of `L1_RPC` and `L2_RPC` for the rest of the process. Must be called before the RPC config
+    /// is first read, and at most once.
+    pub async fn start_from_env() -> Result<Self> {
+        let datadirs = Datadirs::from_env()?;
+        let mut rpcs = LocalRpcs::default();
+        let mut nodes = Vec::new();
+        if let Some(datadir) = &datadirs.l1 {
+            let node = LocalNode::start("L1", datadir).await?;
+            rpcs.l1 = Some(node.url.clone());
+            nodes.push(node);
+        }
+        if let Some(datadir) = &datadirs.l2 {
+            let node = LocalNode::start("L2", datadir).await?;
+            rpcs.l2 = Some(node.url.clone());
+            nodes.push(node);
+        }
+        LOCAL_RPCS
+            .set(rpcs)
+            .map_err(|_| anyhow!("The local nodes were already started"))?;
+        Ok(Self { _nodes: nodes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_args() {
+        let datadir = Datadir {
+            path: PathBuf::from("/snapshots/l2"),
+            client: ExecutionClient::Reth,
+            binary: PathBuf::from("op-reth"),
+            chain: Some("base".to_string()),
+        };
+        let args = datadir.args(8545).join(" ");
+        assert!(args.starts_with("node --datadir /snapshots/l2 --disable-discovery"));
+        assert!(args.contains("--http.addr 127.0.0.1 --http.port 8545"));
+        assert!(args.ends_with("--chain base"));
+
+        let datadir = Datadir {
+            client: ExecutionClient::Geth,
+            ..datadir
+        };
+        let args = datadir.args(8545).join(" ");
+        assert!(args.contains("--maxpeers 0"));
+        assert!(!args.contains("--chain"));
+    }
+}
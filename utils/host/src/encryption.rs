@@ -0,0 +1,200 @@
+//! Encryption at rest of witness data and proof artifacts.
+//!
+//! Witness data holds the preimages of every state, receipt and transaction trie node a range
+//! reads, which some operators consider sensitive. With `WITNESS_ENCRYPTION_KEY` (or
+//! `WITNESS_ENCRYPTION_KEY_COMMAND`) set, the host encrypts every preimage it writes to a run's
+//! on-disk witness directory and to the shared preimage cache with AES-256-GCM, and the proposer
+//! encrypts the proof artifacts it writes. Readers in the same process decrypt transparently.
+//!
+//! Each value is stored as a version byte, a random nonce and the ciphertext. The value's key (the
+//! preimage key, or the proof ID of an artifact) is authenticated as associated data, so a value
+//! can't be swapped for another one under a different key.
+
+use std::{process::Command, str::FromStr, sync::OnceLock};
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use alloy_primitives::B256;
+use anyhow::{anyhow, bail, Context, Result};
+use kona_host::kv::KeyValueStore;
+use log::warn;
+
+use crate::config::env_var;
+
+/// The version of the encrypted format, stored as the first byte of every value.
+const VERSION: u8 = 1;
+
+const NONCE_SIZE: usize = 12;
+
+/// An AES-256-GCM key for data at rest.
+#[derive(Clone)]
+pub struct WitnessKey(Aes256Gcm);
+
+impl std::fmt::Debug for WitnessKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("WitnessKey(<redacted>)")
+    }
+}
+
+impl WitnessKey {
+    pub fn new(key: B256) -> Self {
+        Self(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_slice())))
+    }
+
+    /// Read the key from `WITNESS_ENCRYPTION_KEY`, a hex-encoded 32 byte key, or from the output of
+    /// `WITNESS_ENCRYPTION_KEY_COMMAND`, run with `sh -c`, e.g. a KMS CLI that decrypts the key.
+    /// Returns `None` if neither is set. The command is only run once per process.
+    pub fn from_env() -> Result<Option<Self>> {
+        static KEY: OnceLock<WitnessKey> = OnceLock::new();
+        if let Some(key) = KEY.get() {
+            return Ok(Some(key.clone()));
+        }
+
+        let key = match (
+            env_var::<String>("WITNESS_ENCRYPTION_KEY")?,
+            env_var::<String>("WITNESS_ENCRYPTION_KEY_COMMAND")?,
+        ) {
+            (None, None) => return Ok(None),
+            (Some(_), Some(_)) => {
+                bail!("Set only one of WITNESS_ENCRYPTION_KEY and WITNESS_ENCRYPTION_KEY_COMMAND")
+            }
+            (Some(key), None) => parse_key(&key).context("Invalid WITNESS_ENCRYPTION_KEY")?,
+            (None, Some(command)) => {
+                let output = Command::new("sh").arg("-c").arg(&command).output()?;
+                if !output.status.success() {
+                    bail!(
+                        "WITNESS_ENCRYPTION_KEY_COMMAND failed with {}: {}",
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                parse_key(&String::from_utf8(output.stdout)?)
+                    .context("Invalid output of WITNESS_ENCRYPTION_KEY_COMMAND")?
+            }
+        };
+        Ok(Some(KEY.get_or_init(|| Self::new(key)).clone()))
+    }
+
+    /// Encrypt `plaintext`, authenticating `aad` with it.
+    pub fn encrypt(&self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .0
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            )
+            .map_err(|e| anyhow!("Failed to encrypt: {e}"))?;
+
+        let mut value = Vec::with_capacity(1 + NONCE_SIZE + ciphertext.len());
+        value.push(VERSION);
+        value.extend_from_slice(&nonce);
+        value.extend_from_slice(&ciphertext);
+        Ok(value)
+    }
+
+    /// Decrypt a value written by [`WitnessKey::encrypt`] with the same `aad`.
+    pub fn decrypt(&self, aad: &[u8], value: &[u8]) -> Result<Vec<u8>> {
+        match value.split_first() {
+            Some((&VERSION, rest)) if rest.len() >= NONCE_SIZE => {
+                let (nonce, ciphertext) = rest.split_at(NONCE_SIZE);
+                self.0
+                    .decrypt(
+                        Nonce::from_slice(nonce),
+                        Payload {
+                            msg: ciphertext,
+                            aad,
+                        },
+                    )
+                    .map_err(|_| anyhow!("Failed to decrypt: wrong key or corrupted value"))
+            }
+            _ => bail!("Not an encrypted value"),
+        }
+    }
+}
+
+fn parse_key(key: &str) -> Result<B256> {
+    B256::from_str(key.trim()).map_err(|_| anyhow!("expected a hex-encoded 32 byte key"))
+}
+
+/// A key-value store that encrypts the values it writes to the inner store.
+///
+/// A value that fails to decrypt, e.g. one written to the shared cache before encryption was
+/// enabled, is logged and treated as missing, so the preimage is fetched again.
+pub struct EncryptedKeyValueStore {
+    inner: Box<dyn KeyValueStore + Send + Sync>,
+    key: WitnessKey,
+}
+
+impl EncryptedKeyValueStore {
+    pub fn new(inner: Box<dyn KeyValueStore + Send + Sync>, key: WitnessKey) -> Self {
+        Self { inner, key }
+    }
+}
+
+impl KeyValueStore for EncryptedKeyValueStore {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        let value = self.inner.get(key)?;
+        match self.key.decrypt(key.as_slice(), &value) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                warn!("Ignoring preimage {key}: {e}");
+                None
+            }
+        }
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        let value = self.key.encrypt(key.as_slice(), &value)?;
+        self.inner.set(key, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_host::kv::MemoryKeyValueStore;
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let key = WitnessKey::new(B256::repeat_byte(1));
+        let value = key.encrypt(b"aad", b"preimage").unwrap();
+        assert_eq!(key.decrypt(b"aad", &value).unwrap(), b"preimage");
+
+        // The associated data and the key are both authenticated.
+        assert!(key.decrypt(b"other", &value).is_err());
+        let other = WitnessKey::new(B256::repeat_byte(2));
+        assert!(other.decrypt(b"aad", &value).is_err());
+        assert!(key.decrypt(b"aad", b"preimage").is_err());
+    }
+
+    #[test]
+    fn test_encrypted_store() {
+        let mut store = EncryptedKeyValueStore::new(
+            Box::new(MemoryKeyValueStore::new()),
+            WitnessKey::new(B256::repeat_byte(1)),
+        );
+        let key = B256::repeat_byte(3);
+        store.set(key, vec![1, 2, 3]).unwrap();
+        assert_eq!(store.get(key), Some(vec![1, 2, 3]));
+        assert_ne!(store.inner.get(key), Some(vec![1, 2, 3]));
+
+        // Plaintext values aren't served.
+        store.inner.set(key, vec![1, 2, 3]).unwrap();
+        assert_eq!(store.get(key), None);
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(
+            parse_key(&format!("{}\n", B256::repeat_byte(0xab))).unwrap(),
+            B256::repeat_byte(0xab)
+        );
+        assert!(parse_key("0x1234").is_err());
+    }
+}
@@ -1,60 +1,120 @@
-use alloy_consensus::{BlockHeader, Header};
+use alloy_consensus::{BlockHeader, Header, Transaction as _};
 use alloy_eips::{BlockId, BlockNumberOrTag};
-use alloy_primitives::{Address, B256};
+use alloy_primitives::{address, Address, B256};
 use alloy_provider::{Provider, ProviderBuilder, RootProvider};
 use alloy_rlp::Decodable;
-use alloy_sol_types::SolValue;
+use alloy_rpc_client::{BatchRequest, RpcClient};
+use alloy_transport_http::Http;
 use anyhow::Result;
 use anyhow::{anyhow, bail};
-use cargo_metadata::MetadataCommand;
 use kona_host::single::SingleChainHost;
+use log::{info, warn};
 use maili_genesis::RollupConfig;
 use maili_protocol::calculate_tx_l1_cost_fjord;
 use maili_protocol::L2BlockInfo;
 use maili_rpc::{OutputResponse, SafeHeadResponse};
 use op_alloy_consensus::OpBlock;
 use op_alloy_network::{
-    primitives::{BlockTransactions, BlockTransactionsKind, HeaderResponse},
-    BlockResponse, Network, Optimism,
+    primitives::{BlockTransactionsKind, HeaderResponse},
+    BlockResponse, Network, Optimism, ReceiptResponse,
+};
+use op_alloy_rpc_types::{L1BlockInfo, OpTransactionReceipt};
+use op_succinct_client_utils::{
+    boot::{BootInfoStruct, ClaimMode, DerivationMode},
+    gas_token::{gas_paying_token_slots, GasPayingToken, L1_BLOCK_ADDRESS},
+    types::{compute_output_root, OUTPUT_ROOT_VERSION_V0},
 };
-use op_alloy_rpc_types::OpTransactionReceipt;
-use op_succinct_client_utils::boot::BootInfoStruct;
 use reqwest::Url;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::{
     cmp::{min, Ordering},
-    env, fs,
-    path::Path,
-    str::FromStr,
-    sync::Arc,
+    env,
+    fmt::Debug,
+    fs,
+    sync::{Arc, LazyLock, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use alloy_primitives::{keccak256, map::HashMap, Bytes, U256, U64};
+use alloy_primitives::{map::HashMap, Bytes, U256, U64};
 
 use crate::{
-    rollup_config::{get_rollup_config_path, merge_rollup_config},
-    ProgramType,
+    agg_stdin::{check_checkpoint_after_span_l1_heads, verify_header_chain},
+    chain_config::{
+        apply_on_chain_rollup_config, diff_rollup_config, fetch_on_chain_rollup_config,
+        RollupConfigSource,
+    },
+    chaos::{self, FailurePoint},
+    config::{env_var, required_url_var},
+    datadir::{local_l1_rpc, local_l2_rpc},
+    encryption::WitnessKey,
+    intermediate_outputs_from_env,
+    kv::SharedKvStoreConfig,
+    l1_cache::{l1_cache, L1Cache},
+    l1_head::{
+        choose_l1_head_number, margin_blocks_from_env, BatchInclusion, L1HeadPolicy,
+        L1HeadSelection,
+    },
+    progress::WitnessGenProgress,
+    provider_pool::{pool_for, register_pool_from_env, PooledTransport, ProviderPool},
+    rollup_config::{check_supported_forks, get_rollup_config_path, merge_rollup_config},
+    settlement::SettlementLayer,
+    workspace::Workspace,
+    OPSuccinctHost, ProgramType,
 };
-use crate::{L2Output, OPSuccinctHost};
+
+/// Add an L1 header to the L1 cache if it is finalized. Failing to write it is logged rather than
+/// returned, as the header was fetched anyway.
+fn cache_l1_header(cache: &L1Cache, header: &Header) {
+    if let Err(e) = cache.put_header(header) {
+        warn!(
+            "Failed to write L1 header {} to the L1 cache: {e}",
+            header.number
+        );
+    }
+}
+
+/// The RPC flavor of an L2 the fetcher reads from. The host and the range program work on OP Stack
+/// types, so a network with its own transaction envelopes or header fields only has to map its
+/// RPC responses to them to reuse the fetcher. Raw blocks and transactions, fetched with the
+/// `debug_` methods, are decoded as OP Stack ones.
+pub trait L2Network: Network {
+    /// The consensus header of a block.
+    fn consensus_header(header: &Self::HeaderResponse) -> Header;
+
+    /// The L1 fee data of a transaction receipt.
+    fn l1_block_info(receipt: &Self::ReceiptResponse) -> L1BlockInfo;
+}
+
+impl L2Network for Optimism {
+    fn consensus_header(header: &Self::HeaderResponse) -> Header {
+        header.inner.clone()
+    }
+
+    fn l1_block_info(receipt: &OpTransactionReceipt) -> L1BlockInfo {
+        receipt.l1_block_info.clone()
+    }
+}
 
 #[derive(Clone)]
-/// The OPSuccinctDataFetcher struct is used to fetch the L2 output data and L2 claim data for a
-/// given block number. It is used to generate the boot info for the native host program.
+/// The DataFetcher struct is used to fetch the L2 output data and L2 claim data for a given block
+/// number. It is used to generate the boot info for the native host program. Generic over the
+/// [`L2Network`] of the L2 RPC, see [`OPSuccinctDataFetcher`] for OP Stack chains.
 /// FIXME: Add retries for all requests (3 retries).
-pub struct OPSuccinctDataFetcher {
+pub struct DataFetcher<N: L2Network = Optimism> {
     pub rpc_config: RPCConfig,
     pub l1_provider: Arc<RootProvider>,
-    pub l2_provider: Arc<RootProvider<Optimism>>,
+    pub l2_provider: Arc<RootProvider<N>>,
     pub rollup_config: Option<RollupConfig>,
     pub run_context: RunContext,
+    /// The maximum number of calls to send in a single JSON-RPC batch request.
+    pub rpc_batch_size: usize,
+    /// The chain the rollup settles on, which the L1 RPCs point to.
+    pub settlement_layer: SettlementLayer,
 }
 
-impl Default for OPSuccinctDataFetcher {
-    fn default() -> Self {
-        OPSuccinctDataFetcher::new(RunContext::Dev)
-    }
-}
+/// The fetcher of an L2 whose RPC serves the [`Optimism`] network types.
+pub type OPSuccinctDataFetcher = DataFetcher<Optimism>;
 
 #[derive(Debug, Clone)]
 pub struct RPCConfig {
@@ -62,6 +122,13 @@ pub struct RPCConfig {
     pub l1_beacon_rpc: Url,
     pub l2_rpc: Url,
     pub l2_node_rpc: Url,
+    /// The endpoints the fetcher's L1 requests are routed between, see
+    /// [`provider_pool`](crate::provider_pool).
+    pub l1_pool: Arc<ProviderPool>,
+    /// The endpoints the beacon API proxy's requests are routed between.
+    pub l1_beacon_pool: Arc<ProviderPool>,
+    /// The endpoints the fetcher's L2 requests are routed between.
+    pub l2_pool: Arc<ProviderPool>,
 }
 
 /// The mode corresponding to the chain we are fetching data for.
@@ -87,17 +154,137 @@ pub enum RunContext {
     Docker,
 }
 
-fn get_rpcs() -> RPCConfig {
-    let l1_rpc = env::var("L1_RPC").expect("L1_RPC must be set");
-    let l1_beacon_rpc = env::var("L1_BEACON_RPC").expect("L1_BEACON_RPC must be set");
-    let l2_rpc = env::var("L2_RPC").expect("L2_RPC must be set");
-    let l2_node_rpc = env::var("L2_NODE_RPC").expect("L2_NODE_RPC must be set");
+/// The default maximum number of calls in a single JSON-RPC batch request.
+const DEFAULT_RPC_BATCH_SIZE: usize = 100;
+
+/// The number of JSON-RPC batch requests to keep in flight at once.
+const RPC_BATCH_CONCURRENCY: usize = 4;
+
+/// The L2ToL1MessagePasser predeploy, whose storage root is committed to by the output root.
+pub(crate) const L2_TO_L1_MESSAGE_PASSER_ADDRESS: Address =
+    address!("4200000000000000000000000000000000000016");
+
+/// HTTP client shared by every provider and raw RPC call made by the fetcher, so that connections
+/// (including multiplexed HTTP/2 connections) are pooled and reused instead of re-established for
+/// every request.
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .pool_idle_timeout(Duration::from_secs(90))
+        .tcp_keepalive(Duration::from_secs(30))
+        .http2_keep_alive_interval(Duration::from_secs(30))
+        .http2_keep_alive_while_idle(true)
+        .http2_adaptive_window(true)
+        .build()
+        .expect("Failed to build HTTP client")
+});
+
+/// Build an HTTP provider for the given URL on top of the shared HTTP client.
+pub(crate) fn http_provider<N: Network>(url: &Url) -> RootProvider<N> {
+    let transport = Http::with_client(HTTP_CLIENT.clone(), url.clone());
+    ProviderBuilder::default().on_client(RpcClient::new(transport, false))
+}
+
+/// Build a provider routing its requests between the endpoints of a pool, on top of the shared
+/// HTTP client.
+pub(crate) fn pooled_provider<N: Network>(pool: &Arc<ProviderPool>) -> RootProvider<N> {
+    let transport = PooledTransport::new(pool.clone(), HTTP_CLIENT.clone());
+    ProviderBuilder::default().on_client(RpcClient::new(transport, false))
+}
+
+/// Get the JSON-RPC batch size from the `RPC_BATCH_SIZE` env var.
+pub fn rpc_batch_size_from_env() -> Result<usize> {
+    Ok(env_var("RPC_BATCH_SIZE")?
+        .unwrap_or(DEFAULT_RPC_BATCH_SIZE)
+        .max(1))
+}
+
+/// Send one call of `method` per entry in `params`, grouped into JSON-RPC batch requests of at
+/// most `batch_size` calls with up to `concurrency` batches in flight. Results are returned in the
+/// order of `params`.
+async fn batch_request<N, P, R>(
+    provider: &RootProvider<N>,
+    method: &'static str,
+    params: Vec<P>,
+    batch_size: usize,
+    concurrency: usize,
+) -> Result<Vec<R>>
+where
+    N: Network,
+    P: Serialize + Clone + Debug + Send + Sync + Unpin,
+    R: DeserializeOwned + Debug + Send + Sync + Unpin + 'static,
+{
+    use futures::stream::{self, StreamExt, TryStreamExt};
+
+    let batches = stream::iter(params.chunks(batch_size))
+        .map(|chunk| {
+            let mut batch = BatchRequest::new(provider.client());
+            let waiters = chunk
+                .iter()
+                .map(|params| batch.add_call::<P, R>(method, params))
+                .collect::<Result<Vec<_>, _>>();
+            async move {
+                let waiters = waiters?;
+                batch.send().await?;
+                let mut results = Vec::with_capacity(waiters.len());
+                for waiter in waiters {
+                    results.push(waiter.await?);
+                }
+                Ok::<_, anyhow::Error>(results)
+            }
+        })
+        .buffered(concurrency)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    Ok(batches.into_iter().flatten().collect())
+}
+
+/// Whether each L2 RPC endpoint supports `eth_getBlockReceipts`, probed once per endpoint.
+static BLOCK_RECEIPTS_SUPPORT: LazyLock<Mutex<std::collections::HashMap<Url, bool>>> =
+    LazyLock::new(Default::default);
+
+impl RPCConfig {
+    /// Read the RPC URLs from `L1_RPC`, `L1_BEACON_RPC`, `L2_RPC` and `L2_NODE_RPC`, and the
+    /// endpoints pooled with them from `L1_RPC_POOL`, `L1_BEACON_RPC_POOL` and `L2_RPC_POOL`. The
+    /// RPCs of local nodes started from database snapshots (see [`datadir`](crate::datadir)) are
+    /// used instead of `L1_RPC` and `L2_RPC`.
+    pub fn from_env() -> Result<Self> {
+        let l1_rpc = local_l1_rpc().map_or_else(|| required_url_var("L1_RPC"), Ok)?;
+        let l1_beacon_rpc = required_url_var("L1_BEACON_RPC")?;
+        let l2_rpc = local_l2_rpc().map_or_else(|| required_url_var("L2_RPC"), Ok)?;
+        Ok(Self {
+            l1_pool: register_pool_from_env("L1_RPC", &l1_rpc)?,
+            l1_beacon_pool: register_pool_from_env("L1_BEACON_RPC", &l1_beacon_rpc)?,
+            l2_pool: register_pool_from_env("L2_RPC", &l2_rpc)?,
+            l1_rpc,
+            l1_beacon_rpc,
+            l2_rpc,
+            l2_node_rpc: required_url_var("L2_NODE_RPC")?,
+        })
+    }
 
-    RPCConfig {
-        l1_rpc: Url::parse(&l1_rpc).expect("L1_RPC must be a valid URL"),
-        l1_beacon_rpc: Url::parse(&l1_beacon_rpc).expect("L1_BEACON_RPC must be a valid URL"),
-        l2_rpc: Url::parse(&l2_rpc).expect("L2_RPC must be a valid URL"),
-        l2_node_rpc: Url::parse(&l2_node_rpc).expect("L2_NODE_RPC must be a valid URL"),
+    /// The config with the given endpoints in place of the configured ones. An overridden endpoint
+    /// is routed through its registered pool if it has one, or on its own otherwise.
+    pub fn with_overrides(
+        &self,
+        l1_rpc: Option<&Url>,
+        l2_rpc: Option<&Url>,
+        l1_beacon_rpc: Option<&Url>,
+    ) -> Self {
+        let mut config = self.clone();
+        if let Some(url) = l1_rpc {
+            config.l1_rpc = url.clone();
+            config.l1_pool = pool_for(url);
+        }
+        if let Some(url) = l2_rpc {
+            config.l2_rpc = url.clone();
+            config.l2_pool = pool_for(url);
+        }
+        if let Some(url) = l1_beacon_rpc {
+            config.l1_beacon_rpc = url.clone();
+            config.l1_beacon_pool = pool_for(url);
+        }
+        config
     }
 }
 
@@ -120,38 +307,55 @@ pub struct FeeData {
     pub tx_fee: u128,
 }
 
-impl OPSuccinctDataFetcher {
-    /// Gets the RPC URL's and saves the rollup config for the chain to the rollup config file.
-    pub fn new(run_context: RunContext) -> Self {
-        let rpc_config = get_rpcs();
+impl<N: L2Network> DataFetcher<N> {
+    /// Initialize the fetcher without a rollup config, reading the RPCs from the environment.
+    pub fn new(run_context: RunContext) -> Result<Self> {
+        let rpc_config = RPCConfig::from_env()?;
 
-        let l1_provider = Arc::new(ProviderBuilder::default().on_http(rpc_config.l1_rpc.clone()));
-        let l2_provider = Arc::new(ProviderBuilder::default().on_http(rpc_config.l2_rpc.clone()));
+        let l1_provider = Arc::new(pooled_provider(&rpc_config.l1_pool));
+        let l2_provider = Arc::new(pooled_provider(&rpc_config.l2_pool));
 
-        OPSuccinctDataFetcher {
+        Ok(DataFetcher {
             rpc_config,
             l1_provider,
             l2_provider,
             rollup_config: None,
             run_context,
-        }
+            rpc_batch_size: rpc_batch_size_from_env()?,
+            settlement_layer: SettlementLayer::from_env()?,
+        })
     }
 
     /// Initialize the fetcher with a rollup config.
     pub async fn new_with_rollup_config(run_context: RunContext) -> Result<Self> {
-        let rpc_config = get_rpcs();
-
-        let l1_provider = Arc::new(ProviderBuilder::default().on_http(rpc_config.l1_rpc.clone()));
-        let l2_provider = Arc::new(ProviderBuilder::default().on_http(rpc_config.l2_rpc.clone()));
+        Self::new_with_rpc_config(RPCConfig::from_env()?, run_context).await
+    }
 
-        let rollup_config = Self::fetch_and_save_rollup_config(&rpc_config, run_context).await?;
+    /// Initialize the fetcher with a rollup config, reading from the given RPCs.
+    pub async fn new_with_rpc_config(
+        rpc_config: RPCConfig,
+        run_context: RunContext,
+    ) -> Result<Self> {
+        let l1_provider = Arc::new(pooled_provider(&rpc_config.l1_pool));
+        let l2_provider = Arc::new(pooled_provider(&rpc_config.l2_pool));
+
+        let rollup_config = Self::fetch_and_save_rollup_config(
+            &rpc_config,
+            &l1_provider,
+            &l2_provider,
+            RollupConfigSource::from_env()?,
+            run_context,
+        )
+        .await?;
 
-        Ok(OPSuccinctDataFetcher {
+        Ok(DataFetcher {
             rpc_config,
             l1_provider,
             l2_provider,
             rollup_config: Some(rollup_config),
             run_context,
+            rpc_batch_size: rpc_batch_size_from_env()?,
+            settlement_layer: SettlementLayer::from_env()?,
         })
     }
 
@@ -165,7 +369,7 @@ impl OPSuccinctDataFetcher {
             .get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Hashes)
             .await?;
         if let Some(block) = block {
-            Ok(block.header.inner)
+            Ok(N::consensus_header(block.header()))
         } else {
             bail!("Failed to get L2 head");
         }
@@ -177,7 +381,7 @@ impl OPSuccinctDataFetcher {
             .get_block_by_number(block_number.into(), BlockTransactionsKind::Hashes)
             .await?;
         if let Some(block) = block {
-            Ok(block.header.inner)
+            Ok(N::consensus_header(block.header()))
         } else {
             bail!("Failed to get L2 header for block {block_number}");
         }
@@ -201,10 +405,7 @@ impl OPSuccinctDataFetcher {
                     .get_block(block_number.into(), BlockTransactionsKind::Hashes)
                     .await?;
                 if let Some(block) = block {
-                    match block.transactions {
-                        BlockTransactions::Hashes(txs) => Ok((block_number, txs)),
-                        _ => Err(anyhow::anyhow!("Unsupported transaction type")),
-                    }
+                    Ok((block_number, block.transactions().hashes().collect()))
                 } else {
                     bail!("Failed to get L2 block for block {block_number}");
                 }
@@ -221,7 +422,7 @@ impl OPSuccinctDataFetcher {
             transactions.into_iter().collect();
 
         // Fetch all of the L1 block receipts in parallel.
-        let block_receipts: Vec<(u64, Vec<OpTransactionReceipt>)> = stream::iter(start..=end)
+        let block_receipts: Vec<(u64, Vec<N::ReceiptResponse>)> = stream::iter(start..=end)
             .map(|block_number| async move {
                 let receipts = self
                     .l2_provider
@@ -234,7 +435,7 @@ impl OPSuccinctDataFetcher {
                 }
             })
             .buffered(100)
-            .collect::<Vec<Result<(u64, Vec<OpTransactionReceipt>), anyhow::Error>>>()
+            .collect::<Vec<Result<(u64, Vec<N::ReceiptResponse>), anyhow::Error>>>()
             .await
             .into_iter()
             .filter_map(Result::ok)
@@ -264,7 +465,7 @@ impl OPSuccinctDataFetcher {
         // Zip the block number to encoded transactions with the block number to receipts.
         let block_number_to_receipts_and_transactions: HashMap<
             u64,
-            (Vec<OpTransactionReceipt>, Vec<Bytes>),
+            (Vec<N::ReceiptResponse>, Vec<Bytes>),
         > = block_receipts
             .into_iter()
             .filter_map(|(block_number, receipts)| {
@@ -277,26 +478,27 @@ impl OPSuccinctDataFetcher {
         let mut fee_data = Vec::new();
         for (block_number, (receipts, transactions)) in block_number_to_receipts_and_transactions {
             for (transaction, receipt) in transactions.iter().zip(receipts) {
+                let l1_block_info = N::l1_block_info(&receipt);
                 let l1_fee_scalar = if let Some(custom_l1_fee_scalar) = custom_l1_fee_scalar {
                     custom_l1_fee_scalar
                 } else {
-                    U256::from(receipt.l1_block_info.l1_base_fee_scalar.unwrap_or(0))
+                    U256::from(l1_block_info.l1_base_fee_scalar.unwrap_or(0))
                 };
                 // Get the Fjord L1 cost of the transaction.
                 let l1_gas_cost = calculate_tx_l1_cost_fjord(
                     transaction.as_ref(),
-                    U256::from(receipt.l1_block_info.l1_gas_price.unwrap_or(0)),
+                    U256::from(l1_block_info.l1_gas_price.unwrap_or(0)),
                     l1_fee_scalar,
-                    U256::from(receipt.l1_block_info.l1_blob_base_fee.unwrap_or(0)),
-                    U256::from(receipt.l1_block_info.l1_blob_base_fee_scalar.unwrap_or(0)),
+                    U256::from(l1_block_info.l1_blob_base_fee.unwrap_or(0)),
+                    U256::from(l1_block_info.l1_blob_base_fee_scalar.unwrap_or(0)),
                 );
 
                 fee_data.push(FeeData {
                     block_number,
-                    tx_index: receipt.inner.transaction_index.unwrap(),
-                    tx_hash: receipt.inner.transaction_hash,
+                    tx_index: receipt.transaction_index().unwrap(),
+                    tx_hash: receipt.transaction_hash(),
                     l1_gas_cost,
-                    tx_fee: receipt.inner.effective_gas_price * receipt.inner.gas_used as u128,
+                    tx_fee: receipt.effective_gas_price() * receipt.gas_used() as u128,
                 });
             }
         }
@@ -305,41 +507,143 @@ impl OPSuccinctDataFetcher {
 
     /// Get the fee data for a range of blocks. Extracts the l1 fee data from the receipts.
     pub async fn get_l2_fee_data_range(&self, start: u64, end: u64) -> Result<Vec<FeeData>> {
-        let l2_provider = self.l2_provider.clone();
+        let receipts = self.get_l2_receipts_in_range(start, end).await?;
+
+        let fee_data = (start..=end)
+            .zip(receipts)
+            .flat_map(|(block_number, transactions)| {
+                transactions
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(tx_index, tx)| FeeData {
+                        block_number,
+                        tx_index: tx_index as u64,
+                        tx_hash: tx.transaction_hash(),
+                        l1_gas_cost: U256::from(N::l1_block_info(&tx).l1_fee.unwrap_or(0)),
+                        tx_fee: tx.effective_gas_price() * tx.gas_used() as u128,
+                    })
+            })
+            .collect();
+        Ok(fee_data)
+    }
 
-        use futures::stream::{self, StreamExt};
+    /// Get the receipts for a range of L2 blocks inclusive. The `eth_getBlockReceipts` calls are
+    /// sent in JSON-RPC batches of `rpc_batch_size`.
+    pub async fn get_l2_receipts_in_range(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<Vec<N::ReceiptResponse>>> {
+        if !self.supports_block_receipts().await? {
+            return self.get_l2_receipts_in_range_per_tx(start, end).await;
+        }
 
-        // Only fetch 100 receipts at a time to better use system resources. Increases stability.
-        let fee_data = stream::iter(start..=end)
-            .map(|block_number| {
-                let l2_provider = l2_provider.clone();
-                async move {
-                    let receipt = l2_provider
-                        .get_block_receipts(block_number.into())
-                        .await
-                        .unwrap();
-                    let transactions = receipt.unwrap();
-                    let block_fee_data: Vec<FeeData> = transactions
-                        .iter()
-                        .enumerate()
-                        .map(|(tx_index, tx)| FeeData {
-                            block_number,
-                            tx_index: tx_index as u64,
-                            tx_hash: tx.inner.transaction_hash,
-                            l1_gas_cost: U256::from(tx.l1_block_info.l1_fee.unwrap_or(0)),
-                            tx_fee: tx.inner.effective_gas_price * tx.inner.gas_used as u128,
-                        })
-                        .collect();
-                    block_fee_data
-                }
+        let receipts: Vec<Option<Vec<N::ReceiptResponse>>> = batch_request(
+            &self.l2_provider,
+            "eth_getBlockReceipts",
+            (start..=end)
+                .map(|block_number| (BlockNumberOrTag::Number(block_number),))
+                .collect(),
+            self.rpc_batch_size,
+            RPC_BATCH_CONCURRENCY,
+        )
+        .await?;
+
+        (start..=end)
+            .zip(receipts)
+            .map(|(block_number, receipts)| {
+                receipts
+                    .ok_or_else(|| anyhow!("Failed to get receipts for L2 block {block_number}"))
             })
-            .buffered(100)
-            .collect::<Vec<_>>()
-            .await
+            .collect()
+    }
+
+    /// Get the receipts for a range of L2 blocks inclusive by fetching each transaction's receipt
+    /// with `eth_getTransactionReceipt`. Used when the L2 RPC does not support
+    /// `eth_getBlockReceipts`.
+    async fn get_l2_receipts_in_range_per_tx(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<Vec<N::ReceiptResponse>>> {
+        let blocks = self.get_l2_blocks_in_range(start, end).await?;
+        let tx_hashes = blocks
+            .iter()
+            .flat_map(|block| block.transactions().hashes())
+            .map(|tx_hash| (tx_hash,))
+            .collect::<Vec<_>>();
+
+        let receipts: Vec<Option<N::ReceiptResponse>> = batch_request(
+            &self.l2_provider,
+            "eth_getTransactionReceipt",
+            tx_hashes.clone(),
+            self.rpc_batch_size,
+            RPC_BATCH_CONCURRENCY,
+        )
+        .await?;
+        let mut receipts = tx_hashes
             .into_iter()
-            .flatten()
-            .collect();
-        Ok(fee_data)
+            .zip(receipts)
+            .map(|((tx_hash,), receipt)| {
+                receipt.ok_or_else(|| anyhow!("Failed to get receipt for L2 transaction {tx_hash}"))
+            });
+
+        // Group the receipts back into their blocks.
+        blocks
+            .iter()
+            .map(|block| {
+                receipts
+                    .by_ref()
+                    .take(block.transactions().len())
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect()
+    }
+
+    /// Whether the L2 RPC supports `eth_getBlockReceipts`. The result is probed once per endpoint
+    /// and cached for the lifetime of the process.
+    async fn supports_block_receipts(&self) -> Result<bool> {
+        let url = &self.rpc_config.l2_rpc;
+        if let Some(supported) = BLOCK_RECEIPTS_SUPPORT.lock().unwrap().get(url) {
+            return Ok(*supported);
+        }
+
+        let supported = match self.l2_provider.get_block_receipts(BlockId::latest()).await {
+            Ok(_) => true,
+            // An error response means the RPC rejected the method, rather than being unreachable.
+            Err(e) if e.as_error_resp().is_some() => {
+                warn!("L2 RPC does not support eth_getBlockReceipts, falling back to per-transaction receipts: {e}");
+                false
+            }
+            Err(e) => return Err(e.into()),
+        };
+        BLOCK_RECEIPTS_SUPPORT
+            .lock()
+            .unwrap()
+            .insert(url.clone(), supported);
+
+        Ok(supported)
+    }
+
+    /// Get a range of L2 blocks inclusive, with transaction hashes only.
+    async fn get_l2_blocks_in_range(&self, start: u64, end: u64) -> Result<Vec<N::BlockResponse>> {
+        let blocks: Vec<Option<N::BlockResponse>> = batch_request(
+            &self.l2_provider,
+            "eth_getBlockByNumber",
+            (start..=end)
+                .map(|block_number| (BlockNumberOrTag::Number(block_number), false))
+                .collect(),
+            self.rpc_batch_size,
+            RPC_BATCH_CONCURRENCY,
+        )
+        .await?;
+
+        (start..=end)
+            .zip(blocks)
+            .map(|(block_number, block)| {
+                block.ok_or_else(|| anyhow!("Failed to get L2 block {block_number}"))
+            })
+            .collect()
     }
 
     /// Get the aggregate block statistics for a range of blocks exclusive of the start block.
@@ -349,56 +653,55 @@ impl OPSuccinctDataFetcher {
     /// to "prove" the start block. This is why the start block is not included in the range for which
     /// we fetch block data.
     pub async fn get_l2_block_data_range(&self, start: u64, end: u64) -> Result<Vec<BlockInfo>> {
-        use futures::stream::{self, StreamExt};
+        let blocks = self.get_l2_blocks_in_range(start + 1, end).await?;
+        let receipts = self.get_l2_receipts_in_range(start + 1, end).await?;
 
-        let block_data = stream::iter(start + 1..=end)
-            .map(|block_number| async move {
-                let block = self
-                    .l2_provider
-                    .get_block_by_number(block_number.into(), BlockTransactionsKind::Hashes)
-                    .await?
-                    .unwrap();
-                let receipts = self
-                    .l2_provider
-                    .get_block_receipts(block_number.into())
-                    .await?
-                    .unwrap();
+        (start + 1..=end)
+            .zip(blocks.into_iter().zip(receipts))
+            .map(|(block_number, (block, receipts))| {
                 let total_l1_fees: u128 = receipts
                     .iter()
-                    .map(|tx| tx.l1_block_info.l1_fee.unwrap_or(0))
+                    .map(|tx| N::l1_block_info(tx).l1_fee.unwrap_or(0))
                     .sum();
                 let total_tx_fees: u128 = receipts
                     .iter()
                     .map(|tx| {
-                        // tx.inner.effective_gas_price * tx.inner.gas_used + tx.l1_block_info.l1_fee is the total fee for the transaction.
-                        // tx.inner.effective_gas_price * tx.inner.gas_used is the tx fee on L2.
-                        tx.inner.effective_gas_price * tx.inner.gas_used as u128
-                            + tx.l1_block_info.l1_fee.unwrap_or(0)
+                        // tx.effective_gas_price() * tx.gas_used() + the L1 fee is the total fee for the transaction.
+                        // tx.effective_gas_price() * tx.gas_used() is the tx fee on L2.
+                        tx.effective_gas_price() * tx.gas_used() as u128
+                            + N::l1_block_info(tx).l1_fee.unwrap_or(0)
                     })
                     .sum();
 
                 Ok(BlockInfo {
                     block_number,
-                    transaction_count: block.transactions.len() as u64,
-                    gas_used: block.header.gas_used,
+                    transaction_count: block.transactions().len() as u64,
+                    gas_used: block.header().gas_used(),
                     total_l1_fees,
                     total_tx_fees,
                 })
             })
-            .buffered(100)
-            .collect::<Vec<Result<BlockInfo>>>()
-            .await;
-
-        block_data.into_iter().collect()
+            .collect()
     }
 
+    /// Get an L1 header. Headers requested by hash are served from the L1 cache if it is open.
     pub async fn get_l1_header(&self, block_number: BlockId) -> Result<Header> {
+        let cache = l1_cache();
+        if let (BlockId::Hash(hash), Some(cache)) = (block_number, cache) {
+            if let Some(header) = cache.header(hash.block_hash) {
+                return Ok(header);
+            }
+        }
+
         let block = self
             .l1_provider
             .get_block(block_number, alloy_rpc_types::BlockTransactionsKind::Hashes)
             .await?;
 
         if let Some(block) = block {
+            if let Some(cache) = cache {
+                cache_l1_header(cache, &block.header.inner);
+            }
             Ok(block.header.inner)
         } else {
             bail!("Failed to get L1 header for block {block_number}");
@@ -412,7 +715,7 @@ impl OPSuccinctDataFetcher {
             .await?;
 
         if let Some(block) = block {
-            Ok(block.header.inner)
+            Ok(N::consensus_header(block.header()))
         } else {
             bail!("Failed to get L1 header for block {block_number}");
         }
@@ -491,17 +794,39 @@ impl OPSuccinctDataFetcher {
     }
 
     /// Fetch the rollup config. Combines the rollup config from `optimism_rollupConfig` and the
-    /// chain config from `debug_chainConfig`. Saves the rollup config to the rollup config file and
-    /// in memory.
+    /// chain config from `debug_chainConfig`, with the fields recorded on chain read from the
+    /// `SystemConfig` contract if `source` is [`RollupConfigSource::Chain`]. Saves the rollup config
+    /// to the rollup config file and in memory.
     async fn fetch_and_save_rollup_config(
         rpc_config: &RPCConfig,
+        l1_provider: &RootProvider,
+        l2_provider: &RootProvider<N>,
+        source: RollupConfigSource,
         run_context: RunContext,
     ) -> Result<RollupConfig> {
         let rollup_config =
             Self::fetch_rpc_data(&rpc_config.l2_node_rpc, "optimism_rollupConfig", vec![]).await?;
         let chain_config =
             Self::fetch_rpc_data(&rpc_config.l2_rpc, "debug_chainConfig", vec![]).await?;
-        let rollup_config = merge_rollup_config(&rollup_config, &chain_config)?;
+        check_supported_forks(
+            &rollup_config,
+            SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        )?;
+        let mut rollup_config = merge_rollup_config(&rollup_config, &chain_config)?;
+
+        if let RollupConfigSource::Chain { system_config } = source {
+            let on_chain = fetch_on_chain_rollup_config(
+                l1_provider,
+                l2_provider,
+                system_config.unwrap_or(rollup_config.l1_system_config_address),
+                rollup_config.genesis.l2.number,
+            )
+            .await?;
+            for drift in diff_rollup_config(&rollup_config, &on_chain) {
+                warn!("The L2 node's rollup config differs from the chain's: {drift}");
+            }
+            apply_on_chain_rollup_config(&mut rollup_config, &on_chain);
+        }
 
         // Save rollup config to the rollup config file.
         let rollup_config_path = get_rollup_config_path(rollup_config.l2_chain_id, run_context)?;
@@ -523,8 +848,7 @@ impl OPSuccinctDataFetcher {
     where
         T: serde::de::DeserializeOwned,
     {
-        let client = reqwest::Client::new();
-        let response = client
+        let response = HTTP_CLIENT
             .post(url.clone())
             .json(&json!({
                 "jsonrpc": "2.0",
@@ -600,17 +924,46 @@ impl OPSuccinctDataFetcher {
         }
     }
 
-    /// Fetch headers for a range of blocks inclusive.
+    /// Fetch headers for a range of blocks inclusive. Finalized headers are served from the L1
+    /// cache if it is open, so consecutive aggregation proofs only fetch the headers after the
+    /// previous one's.
     pub async fn fetch_headers_in_range(&self, start: u64, end: u64) -> Result<Vec<Header>> {
-        // Note: Original implementation was using a buffered stream, but this was causing
-        // issues with the RPC requests timing out/receiving no response for 20+ minutes.
-        let mut headers = Vec::new();
-        for block_number in start..=end {
-            let header = self.get_l1_header(block_number.into()).await?;
-            headers.push(header);
+        let cache = l1_cache();
+        let mut headers: Vec<Option<Header>> = (start..=end)
+            .map(|block_number| cache.and_then(|cache| cache.header_by_number(block_number)))
+            .collect();
+        let missing: Vec<u64> = (start..=end)
+            .zip(&headers)
+            .filter(|(_, header)| header.is_none())
+            .map(|(block_number, _)| block_number)
+            .collect();
+
+        // Note: Sending many individual requests concurrently caused RPC requests to time out or
+        // receive no response for 20+ minutes, so the headers are fetched in batches, with only a
+        // few batches in flight at once.
+        let blocks: Vec<Option<alloy_rpc_types::Block>> = batch_request(
+            &self.l1_provider,
+            "eth_getBlockByNumber",
+            missing
+                .iter()
+                .map(|block_number| (BlockNumberOrTag::Number(*block_number), false))
+                .collect(),
+            self.rpc_batch_size,
+            RPC_BATCH_CONCURRENCY,
+        )
+        .await?;
+
+        for (block_number, block) in missing.into_iter().zip(blocks) {
+            let Some(block) = block else {
+                bail!("Failed to get L1 header for block {block_number}");
+            };
+            if let Some(cache) = cache {
+                cache_l1_header(cache, &block.header.inner);
+            }
+            headers[(block_number - start) as usize] = Some(block.header.inner);
         }
 
-        Ok(headers)
+        Ok(headers.into_iter().flatten().collect())
     }
 
     /// Get the preimages for the headers corresponding to the boot infos. Specifically, fetch the
@@ -620,52 +973,85 @@ impl OPSuccinctDataFetcher {
         boot_infos: &Vec<BootInfoStruct>,
         checkpoint_block_hash: B256,
     ) -> Result<Vec<Header>> {
+        if let Some(rollup_config) = &self.rollup_config {
+            self.refresh_l1_cache(&self.workspace()?, rollup_config.l2_chain_id)
+                .await?;
+        }
+
         // Get the earliest L1 Head from the boot_infos.
         let start_header = self.get_earliest_l1_head_in_batch(boot_infos).await?;
 
         // Fetch the full header for the latest L1 Head (which is validated on chain).
         let latest_header = self.get_l1_header(checkpoint_block_hash.into()).await?;
 
-        // Create a vector of futures for fetching all headers
+        // The header chain runs from the earliest span L1 head to the checkpointed block, so it
+        // only reaches every span L1 head if the checkpointed block is not older than any of them.
+        let latest_span_header = self.get_latest_l1_head_in_batch(boot_infos).await?;
+        check_checkpoint_after_span_l1_heads(latest_header.number, latest_span_header.number)?;
+
         let headers = self
             .fetch_headers_in_range(start_header.number, latest_header.number)
             .await?;
 
+        // The batches are fetched concurrently, possibly from different nodes behind the RPC, so
+        // check that they form a single chain ending at the checkpointed block before proving.
+        verify_header_chain(&headers, checkpoint_block_hash)?;
+
         Ok(headers)
     }
 
-    /// Get the data directory for the given program type and run context.
+    /// Open the chain's L1 cache if `L1_CACHE` is enabled, and let it cache the blocks finalized
+    /// since it was last refreshed.
+    async fn refresh_l1_cache(&self, workspace: &Workspace, l2_chain_id: u64) -> Result<()> {
+        if let Some(cache) = L1Cache::open(workspace, l2_chain_id)? {
+            cache.set_finalized(&self.get_l1_header(BlockId::finalized()).await?);
+        }
+        Ok(())
+    }
+
+    /// The workspace the fetcher's run context writes to.
+    pub fn workspace(&self) -> Result<Workspace> {
+        Workspace::from_env(self.run_context)
+    }
+
+    /// Get the custom gas token of the L2 chain at the given block, or `None` if the chain uses ETH.
     ///
-    /// If the RunContext is Dev, prepend the workspace root.
-    fn get_data_directory(
-        &self,
-        l2_chain_id: u64,
-        l2_start_block: u64,
-        l2_end_block: u64,
-        multi_block: ProgramType,
-    ) -> Result<String> {
-        let mut data_directory = match multi_block {
-            ProgramType::Single => {
-                format!("data/{}/{}", l2_chain_id, l2_end_block)
-            }
-            ProgramType::Multi => {
-                format!("data/{}/{}-{}", l2_chain_id, l2_start_block, l2_end_block)
-            }
-        };
+    /// Fetches a storage proof for every gas paying token slot in the L1Block predeploy, so this
+    /// also fails if the L2 node cannot serve the token config needed for witness generation.
+    pub async fn get_gas_paying_token(&self, block_number: u64) -> Result<Option<GasPayingToken>> {
+        let proof = self
+            .l2_provider
+            .get_proof(L1_BLOCK_ADDRESS, gas_paying_token_slots().to_vec())
+            .block_id(block_number.into())
+            .await?;
+        let token_slot = proof
+            .storage_proof
+            .first()
+            .ok_or_else(|| anyhow!("Missing gas paying token storage proof"))?;
 
-        // If the run context is Dev, prepend the workspace root.
-        match self.run_context {
-            RunContext::Dev => {
-                let metadata = MetadataCommand::new().exec().unwrap();
-                let workspace_root = metadata.workspace_root;
-                data_directory = format!("{}/{}", workspace_root, data_directory);
-                Ok(data_directory)
-            }
-            RunContext::Docker => {
-                data_directory = format!("/usr/local/{}", data_directory);
-                Ok(data_directory)
-            }
-        }
+        Ok(GasPayingToken::decode(token_slot.value))
+    }
+
+    /// Compute the output root of an L2 block from the block's header and the storage root of the
+    /// `L2ToL1MessagePasser`.
+    ///
+    /// Computed locally rather than with `optimism_outputAtBlock`, which fails for older blocks if
+    /// the L2 node isn't fully synced.
+    pub async fn get_l2_output_at_block(&self, block_number: u64) -> Result<B256> {
+        let header = self.get_l2_header_by_number(block_number).await?;
+        let message_passer_storage_root = self
+            .l2_provider
+            .get_proof(L2_TO_L1_MESSAGE_PASSER_ADDRESS, Vec::new())
+            .block_id(block_number.into())
+            .await?
+            .storage_hash;
+
+        Ok(compute_output_root(
+            OUTPUT_ROOT_VERSION_V0,
+            header.state_root,
+            message_passer_storage_root,
+            header.hash_slow(),
+        ))
     }
 
     /// Get the L2 output data for a given block number and save the boot info to a file in the data
@@ -693,87 +1079,56 @@ impl OPSuccinctDataFetcher {
             ));
         }
 
-        let l2_provider = self.l2_provider.clone();
+        chaos::inject(FailurePoint::Rpc)?;
 
         // Get L2 output data.
-        let l2_output_block = l2_provider
-            .get_block_by_number(l2_start_block.into(), BlockTransactionsKind::Hashes)
+        let agreed_l2_head_hash = self
+            .get_l2_header_by_number(l2_start_block)
             .await?
-            .ok_or_else(|| {
-                anyhow::anyhow!("Block not found for block number {}", l2_start_block)
-            })?;
-        let l2_output_state_root = l2_output_block.header.state_root;
-        let agreed_l2_head_hash = l2_output_block.header.hash;
-        let l2_output_storage_hash = l2_provider
-            .get_proof(
-                Address::from_str("0x4200000000000000000000000000000000000016")?,
-                Vec::new(),
-            )
-            .block_id(l2_start_block.into())
-            .await?
-            .storage_hash;
-
-        let l2_output_encoded = L2Output {
-            zero: 0,
-            l2_state_root: l2_output_state_root.0.into(),
-            l2_storage_hash: l2_output_storage_hash.0.into(),
-            l2_claim_hash: agreed_l2_head_hash.0.into(),
-        };
-        let agreed_l2_output_root = keccak256(l2_output_encoded.abi_encode());
+            .hash_slow();
+        let agreed_l2_output_root = self.get_l2_output_at_block(l2_start_block).await?;
+
+        // The client reads the custom gas token config from the L1Block predeploy and rejects
+        // tokens it can't charge fees in. Check it up front so an L2 node that cannot serve it, or
+        // an unsupported token, fails before witness generation.
+        if let Some(token) = self.get_gas_paying_token(l2_start_block).await? {
+            token.check()?;
+            info!("L2 chain uses custom gas token {}", token.token);
+        }
 
         // Get L2 claim data.
-        let l2_claim_block = l2_provider
-            .get_block_by_number(l2_end_block.into(), BlockTransactionsKind::Hashes)
-            .await?
-            .unwrap();
-        let l2_claim_state_root = l2_claim_block.header.state_root;
-        let l2_claim_hash = l2_claim_block.header.hash;
-        let l2_claim_storage_hash = l2_provider
-            .get_proof(
-                Address::from_str("0x4200000000000000000000000000000000000016")?,
-                Vec::new(),
-            )
-            .block_id(l2_end_block.into())
-            .await?
-            .storage_hash;
+        let claimed_l2_output_root = self.get_l2_output_at_block(l2_end_block).await?;
 
-        let l2_claim_encoded = L2Output {
-            zero: 0,
-            l2_state_root: l2_claim_state_root.0.into(),
-            l2_storage_hash: l2_claim_storage_hash.0.into(),
-            l2_claim_hash: l2_claim_hash.0.into(),
-        };
-        let claimed_l2_output_root = keccak256(l2_claim_encoded.abi_encode());
-
-        let l1_head_hash = match l1_head_hash {
-            Some(l1_head_hash) => l1_head_hash,
+        let (l1_head_hash, l1_head_selection) = match l1_head_hash {
+            Some(l1_head_hash) => (l1_head_hash, None),
             None => {
-                let (_, l1_head_number) = self.get_l1_head(l2_end_block).await?;
-
-                // FIXME: Investigate requirement for L1 head offset beyond batch posting block with safe head > L2 end block.
-                let l1_head_number = l1_head_number + 20;
-                // The new L1 header requested should not be greater than the finalized L1 header minus 10 blocks.
-                let finalized_l1_header = self.get_l1_header(BlockId::finalized()).await?;
-
-                match l1_head_number > finalized_l1_header.number {
-                    true => self
-                        .get_l1_header(finalized_l1_header.number.into())
-                        .await?
-                        .hash_slow(),
-                    false => self.get_l1_header(l1_head_number.into()).await?.hash_slow(),
-                }
+                let selection = self.select_l1_head(l2_end_block).await?;
+                info!(
+                    "Chose L1 head {} (block {}) for L2 blocks {}-{}: {}",
+                    selection.l1_head,
+                    selection.l1_head_number,
+                    l2_start_block,
+                    l2_end_block,
+                    selection.rationale
+                );
+                (selection.l1_head, Some(selection))
             }
         };
 
-        // Get the workspace root, which is where the data directory is.
+        let derivation_mode = self.derivation_mode(l2_start_block, l1_head_hash).await?;
+
+        let workspace = self.workspace()?;
         let data_directory =
-            self.get_data_directory(l2_chain_id, l2_start_block, l2_end_block, multi_block)?;
+            workspace.witness_dir(l2_chain_id, l2_start_block, l2_end_block, multi_block);
+
+        // Let the run cache the L1 data of blocks finalized since the last run.
+        self.refresh_l1_cache(&workspace, l2_chain_id).await?;
 
         // Delete the data directory if the cache mode is DeleteCache.
         match cache_mode {
             CacheMode::KeepCache => (),
             CacheMode::DeleteCache => {
-                if Path::new(&data_directory).exists() {
+                if data_directory.exists() {
                     fs::remove_dir_all(&data_directory)?;
                 }
             }
@@ -809,21 +1164,88 @@ impl OPSuccinctDataFetcher {
                         .trim_end_matches('/')
                         .to_string(),
                 ),
+                // Read blobs through the blob archive if one is served, so ranges whose blobs
+                // have expired on the beacon node can still be proven.
                 l1_beacon_address: Some(
-                    self.rpc_config
-                        .l1_beacon_rpc
-                        .as_str()
+                    env::var("BLOB_ARCHIVE_RPC")
+                        .unwrap_or_else(|_| self.rpc_config.l1_beacon_rpc.to_string())
                         .trim_end_matches('/')
                         .to_string(),
                 ),
-                data_dir: Some(data_directory.into()),
+                data_dir: Some(data_directory),
                 native: false,
                 server: true,
                 rollup_config_path: Some(rollup_config_path),
             },
+            shared_kv_store: SharedKvStoreConfig::from_env()?,
+            witness_key: WitnessKey::from_env()?,
+            l1_head_selection,
+            progress: WitnessGenProgress::new(l2_start_block, l2_end_block),
+            claim_mode: ClaimMode::Check,
+            derivation_mode,
+            intermediate_outputs: intermediate_outputs_from_env()?,
         })
     }
 
+    /// The [`DerivationMode`] of the span after `l2_start_block` derived up to the L1 head: the
+    /// empty batch fast path if no batches were posted for it, unless `EMPTY_BATCH_FAST_PATH` is
+    /// `false`. The client checks the L1 data itself, so a failed scan only costs the fast path.
+    async fn derivation_mode(
+        &self,
+        l2_start_block: u64,
+        l1_head_hash: B256,
+    ) -> Result<DerivationMode> {
+        if env_var::<bool>("EMPTY_BATCH_FAST_PATH")? == Some(false) {
+            return Ok(DerivationMode::Full);
+        }
+
+        match self.span_has_no_batches(l2_start_block, l1_head_hash).await {
+            Ok(true) => {
+                info!("No batches posted for the span after L2 block {l2_start_block}, enabling the empty batch fast path");
+                Ok(DerivationMode::EmptyBatches)
+            }
+            Ok(false) => Ok(DerivationMode::Full),
+            Err(e) => {
+                warn!("Failed to scan the L1 blocks of the span after L2 block {l2_start_block} for batches: {e}");
+                Ok(DerivationMode::Full)
+            }
+        }
+    }
+
+    /// Whether no transaction was sent to the batch inbox in the L1 blocks the derivation of the
+    /// span after `l2_start_block` may read: from a channel timeout before the L1 origin of the
+    /// start block up to the L1 head. The blocks are scanned in order, so a span with batches
+    /// stops at the first one.
+    pub async fn span_has_no_batches(
+        &self,
+        l2_start_block: u64,
+        l1_head_hash: B256,
+    ) -> Result<bool> {
+        let rollup_config = self
+            .rollup_config
+            .as_ref()
+            .ok_or_else(|| anyhow!("Rollup config not loaded."))?;
+        let start = self.l2_block_info_by_number(l2_start_block).await?;
+        let channel_timeout = rollup_config.channel_timeout(start.block_info.timestamp);
+        let l1_head = self.get_l1_header(l1_head_hash.into()).await?;
+
+        for number in start.l1_origin.number.saturating_sub(channel_timeout)..=l1_head.number {
+            let block = self
+                .l1_provider
+                .get_block_by_number(number.into(), alloy_rpc_types::BlockTransactionsKind::Full)
+                .await?
+                .ok_or_else(|| anyhow!("L1 block {number} not found"))?;
+            if block
+                .transactions
+                .txns()
+                .any(|tx| tx.to() == Some(rollup_config.batch_inbox_address))
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
     /// Get the L1 block time in seconds.
     #[allow(dead_code)]
     async fn get_l1_block_time(&self) -> Result<u64> {
@@ -849,8 +1271,23 @@ impl OPSuccinctDataFetcher {
 
     /// Get the L1 block from which the `l2_end_block` can be derived.
     pub async fn get_l1_head_with_safe_head(&self, l2_end_block: u64) -> Result<(B256, u64)> {
-        let latest_l1_header = self.get_l1_header(BlockId::finalized()).await?;
+        let finalized_l1_header = self.get_l1_header(BlockId::finalized()).await?;
+        self.find_batch_inclusion_block(l2_end_block, finalized_l1_header.number)
+            .await?
+            .ok_or_else(|| {
+                anyhow!(
+                    "Could not find an L1 block with an L2 safe head greater than the L2 end block."
+                )
+            })
+    }
 
+    /// Get the first L1 block up to `ceiling` from which the `l2_end_block` can be derived, i.e.
+    /// which includes the batches of every block up to it. Returns `None` if there is none.
+    async fn find_batch_inclusion_block(
+        &self,
+        l2_end_block: u64,
+        ceiling: u64,
+    ) -> Result<Option<(B256, u64)>> {
         // Get the l1 origin of the l2 end block.
         let l2_end_block_hex = format!("0x{:x}", l2_end_block);
         let optimism_output_data: OutputResponse = self
@@ -864,15 +1301,7 @@ impl OPSuccinctDataFetcher {
         let l1_origin = optimism_output_data.block_ref.l1_origin;
 
         // Search forward from the l1Origin, checking each L1 block until we find one with an L2 safe head greater than l2_end_block
-        let mut current_l1_block_number = l1_origin.number;
-        loop {
-            // If the current L1 block number is greater than the latest L1 header number, then return an error.
-            if current_l1_block_number > latest_l1_header.number {
-                return Err(anyhow::anyhow!(
-                    "Could not find an L1 block with an L2 safe head greater than the L2 end block."
-                ));
-            }
-
+        for current_l1_block_number in l1_origin.number..=ceiling {
             let l1_block_number_hex = format!("0x{:x}", current_l1_block_number);
             let result: SafeHeadResponse = self
                 .fetch_rpc_data_with_mode(
@@ -884,50 +1313,92 @@ impl OPSuccinctDataFetcher {
             let l2_safe_head = result.safe_head.number;
             // If the safe head is GTE to the L2 end block at this L1 block, then we can derive the L2 end block from this L1 block.
             if l2_safe_head >= l2_end_block {
-                return Ok((result.l1_block.hash, result.l1_block.number));
+                return Ok(Some((result.l1_block.hash, result.l1_block.number)));
             }
-
-            current_l1_block_number += 1;
         }
+
+        Ok(None)
     }
 
     /// For OP Sepolia, OP Mainnet and Base, the batcher posts at least every 10 minutes. Otherwise,
     /// the batcher may post as infrequently as every couple hours. The l1Head is set as the l1 block from which all of the
     /// relevant L2 block data can be derived.
     /// E.g. Origin Advance Error: BlockInfoFetch(Block number past L1 head.).
-    async fn get_l1_head(&self, l2_end_block: u64) -> Result<(B256, u64)> {
+    pub(crate) async fn get_l1_head(&self, l2_end_block: u64) -> Result<(B256, u64)> {
+        // See if optimism_safeHeadAtL1Block is available. If there's an error, then estimate the L1 block necessary based on the chain config.
+        let result = self.get_l1_head_with_safe_head(l2_end_block).await;
+
+        if let Ok(safe_head_at_l1_block) = result {
+            Ok(safe_head_at_l1_block)
+        } else {
+            self.estimate_l1_head(l2_end_block).await
+        }
+    }
+
+    /// Estimate the L1 block necessary based on the chain config. This is based on the maximum
+    /// delay between batches being posted on the L2 chain.
+    async fn estimate_l1_head(&self, l2_end_block: u64) -> Result<(B256, u64)> {
         // If the rollup config is not already loaded, fetch and save it.
         if self.rollup_config.is_none() {
             return Err(anyhow::anyhow!("Rollup config not loaded."));
         }
         let l2_chain_id = self.rollup_config.as_ref().unwrap().l2_chain_id;
 
-        // See if optimism_safeHeadAtL1Block is available. If there's an error, then estimate the L1 block necessary based on the chain config.
-        let result = self.get_l1_head_with_safe_head(l2_end_block).await;
+        let max_batch_post_delay_minutes = match l2_chain_id {
+            11155420 => 10,
+            10 => 10,
+            8453 => 10,
+            _ => 60,
+        };
 
-        if let Ok(safe_head_at_l1_block) = result {
-            Ok(safe_head_at_l1_block)
-        } else {
-            // Estimate the L1 block necessary based on the chain config. This is based on the maximum
-            // delay between batches being posted on the L2 chain.
-            let max_batch_post_delay_minutes = match l2_chain_id {
-                11155420 => 10,
-                10 => 10,
-                8453 => 10,
-                _ => 60,
-            };
+        // Get L1 head.
+        let l2_block_timestamp = self.get_l2_header(l2_end_block.into()).await?.timestamp;
+        let finalized_l1_timestamp = self.get_l1_header(BlockId::finalized()).await?.timestamp;
 
-            // Get L1 head.
-            let l2_block_timestamp = self.get_l2_header(l2_end_block.into()).await?.timestamp;
-            let finalized_l1_timestamp = self.get_l1_header(BlockId::finalized()).await?.timestamp;
+        // Ensure that the target timestamp is not greater than the finalized L1 timestamp.
+        let target_timestamp = min(
+            l2_block_timestamp + (max_batch_post_delay_minutes * 60),
+            finalized_l1_timestamp,
+        );
+        self.find_l1_block_by_timestamp(target_timestamp).await
+    }
 
-            // Ensure that the target timestamp is not greater than the finalized L1 timestamp.
-            let target_timestamp = min(
-                l2_block_timestamp + (max_batch_post_delay_minutes * 60),
-                finalized_l1_timestamp,
-            );
-            Ok(self.find_l1_block_by_timestamp(target_timestamp).await?)
-        }
+    /// Choose the L1 head of a range ending at `l2_end_block` following the [`L1HeadPolicy`] set
+    /// with `L1_HEAD_POLICY`. See [`crate::l1_head`].
+    pub async fn select_l1_head(&self, l2_end_block: u64) -> Result<L1HeadSelection> {
+        let policy = L1HeadPolicy::from_env()?;
+        let margin = margin_blocks_from_env()?;
+        let ceiling = match policy {
+            L1HeadPolicy::Finalized => self.get_l1_header(BlockId::finalized()).await?.number,
+            L1HeadPolicy::BehindTip(blocks) => self
+                .l1_provider
+                .get_block_number()
+                .await?
+                .saturating_sub(blocks),
+        };
+
+        let inclusion = match self.find_batch_inclusion_block(l2_end_block, ceiling).await {
+            Ok(Some((_, block))) => BatchInclusion::Included(block),
+            Ok(None) => BatchInclusion::NotIncluded,
+            Err(e) => {
+                warn!("Failed to find the batch inclusion block of L2 block {l2_end_block}: {e}");
+                BatchInclusion::Estimated(self.estimate_l1_head(l2_end_block).await?.1)
+            }
+        };
+        let (l1_head_number, rationale) =
+            choose_l1_head_number(policy, ceiling, margin, inclusion)?;
+
+        Ok(L1HeadSelection {
+            l1_head: self.get_l1_header(l1_head_number.into()).await?.hash_slow(),
+            l1_head_number,
+            policy,
+            ceiling,
+            batch_inclusion_block: match inclusion {
+                BatchInclusion::Included(block) => Some(block),
+                _ => None,
+            },
+            rationale,
+        })
     }
 
     // Source from: https://github.com/anton-rs/kona/blob/85b1c88b44e5f54edfc92c781a313717bad5dfc7/crates/derive-alloy/src/alloy_providers.rs#L225.
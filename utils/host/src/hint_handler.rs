@@ -0,0 +1,280 @@
+//! The host's hint handler, which stores the receipts of an L1 block as a single preimage.
+//!
+//! Kona's hint handler stores every node of a block's receipts trie as its own preimage, which
+//! the client walks one by one. The client instead rebuilds the trie from the block's raw receipts
+//! (see [`L1ReceiptsOracle`]), so only the receipts are fetched and put in the witness.
+//!
+//! When the L2 node serves `debug_executionWitness` (see [`l2_node`](crate::l2_node)), the
+//! witness of each L2 block is also read from the block the node already executed, rather than
+//! re-executing its payload on the node.
+//!
+//! When the L1 cache is open (see [`l1_cache`](crate::l1_cache)), the preimages of L1 hints are
+//! served from it, and those of finalized blocks fetched from the RPCs are added to it.
+//!
+//! [`L1ReceiptsOracle`]: op_succinct_client_utils::L1ReceiptsOracle
+
+use std::sync::{Arc, Mutex};
+
+use alloy_consensus::Header;
+use alloy_primitives::{keccak256, map::HashMap, Bytes, B256, U64};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rlp::{Decodable, Encodable};
+use anyhow::{ensure, Result};
+use async_trait::async_trait;
+use kona_host::{
+    kv::KeyValueStore,
+    single::{SingleChainHintHandler, SingleChainHost},
+    HintHandler, OnlineHostBackendCfg, SharedKeyValueStore,
+};
+use kona_preimage::PreimageKey;
+use kona_proof::{Hint, HintType};
+use log::warn;
+use op_alloy_network::{primitives::BlockTransactionsKind, Optimism};
+use op_succinct_client_utils::l1_receipts_key;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+
+use crate::{
+    l1_cache::{header_key, l1_cache, L1Cache, L1CacheEntry},
+    l2_node::{witness_strategy, WitnessStrategy},
+};
+
+/// Handles hints like kona's [`SingleChainHintHandler`], except for L1 receipts, which are stored
+/// as the RLP list of the block's raw receipts under [`l1_receipts_key`], and L2 payload witnesses
+/// when the [`WitnessStrategy::ExecutionWitness`] strategy is selected.
+#[derive(Debug, Clone, Copy)]
+pub struct OPSuccinctHintHandler;
+
+#[async_trait]
+impl HintHandler for OPSuccinctHintHandler {
+    type Cfg = SingleChainHost;
+
+    async fn fetch_hint(
+        hint: Hint<<Self::Cfg as OnlineHostBackendCfg>::HintType>,
+        cfg: &Self::Cfg,
+        providers: &<Self::Cfg as OnlineHostBackendCfg>::Providers,
+        kv: SharedKeyValueStore,
+    ) -> Result<()> {
+        match (l1_cache(), L1CacheEntry::from_hint(&hint.ty, &hint.data)) {
+            (Some(cache), Some(entry)) => {
+                fetch_cached_hint(hint, cfg, providers, kv, cache, entry).await
+            }
+            _ => fetch_uncached_hint(hint, cfg, providers, kv).await,
+        }
+    }
+}
+
+/// Fetch the preimages of a hint from the RPCs.
+async fn fetch_uncached_hint(
+    hint: Hint<HintType>,
+    cfg: &SingleChainHost,
+    providers: &<SingleChainHost as OnlineHostBackendCfg>::Providers,
+    kv: SharedKeyValueStore,
+) -> Result<()> {
+    match hint.ty {
+        HintType::L1Receipts => store_l1_receipts(&hint.data, &providers.l1, &kv).await,
+        HintType::L2PayloadWitness if witness_strategy() == WitnessStrategy::ExecutionWitness => {
+            if store_execution_witness(&hint.data, &providers.l2, &kv).await? {
+                Ok(())
+            } else {
+                SingleChainHintHandler::fetch_hint(hint, cfg, providers, kv).await
+            }
+        }
+        _ => SingleChainHintHandler::fetch_hint(hint, cfg, providers, kv).await,
+    }
+}
+
+/// Serve the preimages of an L1 hint from the L1 cache. On a miss, fetch them from the RPCs and
+/// cache them if the block they belong to is finalized.
+///
+/// Failing to write to the cache is logged rather than returned, so a full disk slows witness
+/// generation down instead of failing it.
+async fn fetch_cached_hint(
+    hint: Hint<HintType>,
+    cfg: &SingleChainHost,
+    providers: &<SingleChainHost as OnlineHostBackendCfg>::Providers,
+    kv: SharedKeyValueStore,
+    cache: &L1Cache,
+    entry: L1CacheEntry,
+) -> Result<()> {
+    if let Some(preimages) = cache.get(&entry) {
+        return store_preimages(&kv, preimages).await;
+    }
+
+    let recorder = RecordingKeyValueStore::default();
+    let recorded = recorder.preimages.clone();
+    fetch_uncached_hint(hint, cfg, providers, Arc::new(RwLock::new(recorder))).await?;
+    let preimages = std::mem::take(&mut *recorded.lock().unwrap());
+
+    let cached = match entry {
+        // Headers are also indexed by number, for the fetcher.
+        L1CacheEntry::Header(hash) => match find_header(hash, &preimages, &kv).await {
+            Some(header) => cache.put_header(&header),
+            None => Ok(false),
+        },
+        L1CacheEntry::Transactions(hash) | L1CacheEntry::Receipts(hash) => {
+            // The client reads a block's header before its transactions and receipts, so the
+            // header is already in the run's store.
+            match find_header(hash, &preimages, &kv).await {
+                Some(header) if cache.is_final(header.timestamp) => {
+                    cache.put(&entry, &preimages).map(|_| true)
+                }
+                _ => Ok(false),
+            }
+        }
+        L1CacheEntry::Blob { timestamp, .. } if cache.is_final(timestamp) => {
+            cache.put(&entry, &preimages).map(|_| true)
+        }
+        L1CacheEntry::Blob { .. } => Ok(false),
+    };
+    if let Err(e) = cached {
+        warn!("Failed to write {entry:?} to the L1 cache: {e}");
+    }
+
+    store_preimages(&kv, preimages).await
+}
+
+/// The header of the L1 block with the given hash, from the given preimages or the run's store.
+async fn find_header(
+    hash: B256,
+    preimages: &[(B256, Bytes)],
+    kv: &SharedKeyValueStore,
+) -> Option<Header> {
+    let key = header_key(hash);
+    let rlp = match preimages.iter().find(|(k, _)| *k == key) {
+        Some((_, rlp)) => rlp.to_vec(),
+        None => kv.read().await.get(key)?,
+    };
+    Header::decode(&mut rlp.as_slice()).ok()
+}
+
+/// Store the given preimages in the run's store.
+async fn store_preimages(kv: &SharedKeyValueStore, preimages: Vec<(B256, Bytes)>) -> Result<()> {
+    let mut kv = kv.write().await;
+    for (key, value) in preimages {
+        kv.set(key, value.into())?;
+    }
+    Ok(())
+}
+
+/// A key-value store that records the preimages set in it, so the preimages fetched for a hint can
+/// be cached.
+#[derive(Default)]
+struct RecordingKeyValueStore {
+    preimages: Arc<Mutex<Vec<(B256, Bytes)>>>,
+}
+
+impl KeyValueStore for RecordingKeyValueStore {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        self.preimages
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value.to_vec())
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        self.preimages.lock().unwrap().push((key, value.into()));
+        Ok(())
+    }
+}
+
+/// Store the raw receipts of the L1 block in the hint as a single preimage.
+async fn store_l1_receipts(
+    data: &[u8],
+    provider: &RootProvider,
+    kv: &SharedKeyValueStore,
+) -> Result<()> {
+    ensure!(data.len() == 32, "Invalid L1 receipts hint data length");
+    let block_hash = B256::from_slice(data);
+    let receipts: Vec<Bytes> = provider
+        .client()
+        .request("debug_getRawReceipts", [block_hash])
+        .await?;
+
+    let mut list = Vec::new();
+    receipts.encode(&mut list);
+    let key = <[u8; 32]>::from(l1_receipts_key(block_hash));
+    kv.write().await.set(key.into(), list)
+}
+
+/// The preimages of an L2 block's witness, as returned by `debug_executionWitness`. Nodes return
+/// each field either as a list of preimages or as a map from their hashes, which are recomputed
+/// anyway.
+#[derive(Debug, Deserialize)]
+struct ExecutionWitness {
+    #[serde(default)]
+    state: Preimages,
+    #[serde(default)]
+    codes: Preimages,
+    #[serde(default)]
+    keys: Preimages,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Preimages {
+    List(Vec<Bytes>),
+    Map(HashMap<B256, Bytes>),
+}
+
+impl Default for Preimages {
+    fn default() -> Self {
+        Self::List(Vec::new())
+    }
+}
+
+impl Preimages {
+    fn into_vec(self) -> Vec<Bytes> {
+        match self {
+            Self::List(preimages) => preimages,
+            Self::Map(preimages) => preimages.into_values().collect(),
+        }
+    }
+}
+
+/// Store the witness of the canonical L2 block built on the parent in the hint, read with
+/// `debug_executionWitness`. Returns `false` without storing anything if the node has no block
+/// built on the parent, in which case the payload is re-executed on the node instead.
+async fn store_execution_witness(
+    data: &[u8],
+    provider: &RootProvider<Optimism>,
+    kv: &SharedKeyValueStore,
+) -> Result<bool> {
+    ensure!(
+        data.len() >= 32,
+        "Invalid L2 payload witness hint data length"
+    );
+    let parent_hash = B256::from_slice(&data[..32]);
+    let Some(parent) = provider
+        .get_block_by_hash(parent_hash, BlockTransactionsKind::Hashes)
+        .await?
+    else {
+        return Ok(false);
+    };
+    let number = parent.header.number + 1;
+    let Some(block) = provider
+        .get_block_by_number(number.into(), BlockTransactionsKind::Hashes)
+        .await?
+    else {
+        return Ok(false);
+    };
+    if block.header.parent_hash != parent_hash {
+        return Ok(false);
+    }
+
+    let witness: ExecutionWitness = provider
+        .client()
+        .request("debug_executionWitness", [U64::from(number)])
+        .await?;
+    let mut kv = kv.write().await;
+    for preimage in [witness.state, witness.codes, witness.keys]
+        .into_iter()
+        .flat_map(Preimages::into_vec)
+    {
+        let key = <[u8; 32]>::from(PreimageKey::new_keccak256(*keccak256(&preimage)));
+        kv.set(key.into(), preimage.into())?;
+    }
+    Ok(true)
+}
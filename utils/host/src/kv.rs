@@ -0,0 +1,187 @@
+//! Preimage caches shared across witness generation runs.
+//!
+//! Each run stores its preimages in a key-value store under its own data directory, which is
+//! deleted once the witness has been generated. A shared store sits behind it, so preimages
+//! fetched for one range (e.g. L1 blocks and blobs that overlapping ranges derive from) are served
+//! from the cache for the next one instead of being fetched from the RPCs again.
+
+use alloy_primitives::B256;
+use anyhow::{bail, Result};
+use kona_host::kv::{DiskKeyValueStore, KeyValueStore};
+use log::warn;
+use std::{
+    env,
+    path::PathBuf,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use crate::encryption::{EncryptedKeyValueStore, WitnessKey};
+
+/// A key-value store shared by every witness generation run in the process.
+pub type SharedStore = Arc<Mutex<Box<dyn KeyValueStore + Send + Sync>>>;
+
+/// The backend of the shared preimage cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SharedKvStoreConfig {
+    /// A RocksDB database shared by the witness generation runs of a single process.
+    RocksDb(PathBuf),
+    /// A Redis server shared by witness generation workers on different machines.
+    #[cfg(feature = "redis-kv")]
+    Redis(String),
+}
+
+impl SharedKvStoreConfig {
+    /// Read the shared preimage cache from `SHARED_KV_STORE`, either `rocksdb://<path>` or
+    /// `redis://<host>:<port>`. Returns `None` if unset.
+    pub fn from_env() -> Result<Option<Self>> {
+        match env::var("SHARED_KV_STORE") {
+            Ok(url) if !url.is_empty() => Self::parse(&url).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn parse(url: &str) -> Result<Self> {
+        if let Some(path) = url.strip_prefix("rocksdb://") {
+            return Ok(Self::RocksDb(PathBuf::from(path)));
+        }
+        if url.starts_with("redis://") || url.starts_with("rediss://") {
+            #[cfg(feature = "redis-kv")]
+            return Ok(Self::Redis(url.to_string()));
+            #[cfg(not(feature = "redis-kv"))]
+            bail!("SHARED_KV_STORE is a Redis URL, but the `redis-kv` feature is not enabled");
+        }
+        bail!("Unsupported SHARED_KV_STORE: {url}. Expected rocksdb://<path> or redis://<host>:<port>")
+    }
+
+    /// Open the shared store, encrypting its values with `key` if set. The store is opened once and
+    /// reused by every run in the process, as RocksDB only allows a single handle to a database.
+    pub fn open(&self, key: Option<&WitnessKey>) -> Result<SharedStore> {
+        static SHARED_STORE: OnceLock<(SharedKvStoreConfig, SharedStore)> = OnceLock::new();
+
+        if let Some((config, store)) = SHARED_STORE.get() {
+            if config != self {
+                bail!("The shared preimage cache is already open as {config:?}");
+            }
+            return Ok(store.clone());
+        }
+
+        let store: Box<dyn KeyValueStore + Send + Sync> = match self {
+            Self::RocksDb(path) => Box::new(DiskKeyValueStore::new(path.clone())),
+            #[cfg(feature = "redis-kv")]
+            Self::Redis(url) => Box::new(RedisKeyValueStore::new(url)?),
+        };
+        let store = match key {
+            Some(key) => Box::new(EncryptedKeyValueStore::new(store, key.clone())),
+            None => store,
+        };
+        let (_, store) = SHARED_STORE.get_or_init(|| (self.clone(), Arc::new(Mutex::new(store))));
+        Ok(store.clone())
+    }
+}
+
+/// A key-value store that reads through to a shared store on a miss, and writes to both.
+///
+/// Failing to write to the shared store is logged rather than returned, so an unavailable cache
+/// slows witness generation down instead of failing it.
+pub struct CachedKeyValueStore {
+    local: Box<dyn KeyValueStore + Send + Sync>,
+    shared: SharedStore,
+}
+
+impl CachedKeyValueStore {
+    pub fn new(local: Box<dyn KeyValueStore + Send + Sync>, shared: SharedStore) -> Self {
+        Self { local, shared }
+    }
+}
+
+impl KeyValueStore for CachedKeyValueStore {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        self.local
+            .get(key)
+            .or_else(|| self.shared.lock().unwrap().get(key))
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        if let Err(e) = self.shared.lock().unwrap().set(key, value.clone()) {
+            warn!("Failed to write preimage {key} to the shared cache: {e}");
+        }
+        self.local.set(key, value)
+    }
+}
+
+/// A key-value store backed by Redis.
+#[cfg(feature = "redis-kv")]
+pub struct RedisKeyValueStore {
+    connection: Mutex<redis::Connection>,
+}
+
+#[cfg(feature = "redis-kv")]
+impl RedisKeyValueStore {
+    /// The prefix of the Redis keys, so the cache can share a Redis instance.
+    const KEY_PREFIX: &'static str = "op-succinct:preimage:";
+
+    pub fn new(url: &str) -> Result<Self> {
+        let connection = redis::Client::open(url)?.get_connection()?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn redis_key(key: B256) -> String {
+        format!("{}{}", Self::KEY_PREFIX, key)
+    }
+}
+
+#[cfg(feature = "redis-kv")]
+impl KeyValueStore for RedisKeyValueStore {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        use redis::Commands;
+
+        match self.connection.lock().unwrap().get(Self::redis_key(key)) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!("Failed to read preimage {key} from Redis: {e}");
+                None
+            }
+        }
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        use redis::Commands;
+
+        self.connection
+            .lock()
+            .unwrap()
+            .set::<_, _, ()>(Self::redis_key(key), value)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_host::kv::MemoryKeyValueStore;
+
+    #[test]
+    fn test_parse_shared_kv_store() {
+        assert_eq!(
+            SharedKvStoreConfig::parse("rocksdb:///var/cache/preimages").unwrap(),
+            SharedKvStoreConfig::RocksDb(PathBuf::from("/var/cache/preimages"))
+        );
+        assert!(SharedKvStoreConfig::parse("memory").is_err());
+    }
+
+    #[test]
+    fn test_cached_store_reads_through_to_shared_store() {
+        let shared: SharedStore = Arc::new(Mutex::new(Box::new(MemoryKeyValueStore::new())));
+        let key = B256::repeat_byte(1);
+
+        let mut first =
+            CachedKeyValueStore::new(Box::new(MemoryKeyValueStore::new()), shared.clone());
+        first.set(key, vec![1, 2, 3]).unwrap();
+
+        let second = CachedKeyValueStore::new(Box::new(MemoryKeyValueStore::new()), shared);
+        assert_eq!(second.get(key), Some(vec![1, 2, 3]));
+        assert_eq!(second.get(B256::repeat_byte(2)), None);
+    }
+}
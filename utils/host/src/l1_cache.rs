@@ -0,0 +1,276 @@
+//! A disk cache of L1 data shared by every range of a chain.
+//!
+//! Consecutive spans derive from overlapping L1 blocks, so each witness generation run used to
+//! fetch the same headers, transactions, receipts and blobs as the run before it. The cache keeps
+//! them in the chain's workspace directory:
+//!
+//! ```text
+//! <root>/<l2_chain_id>/l1-cache/
+//!     headers/<block hash>
+//!     transactions/<block hash>
+//!     receipts/<block hash>
+//!     blobs/<blob hash>
+//!     canonical/<block number>
+//! ```
+//!
+//! Each entry holds the preimages the host stores for one hint, keyed by the block or blob hash it
+//! was fetched for. The data behind a hash never changes, but only blocks at or below the L1
+//! finalized block are cached, so blocks that may still be reorged out don't accumulate, and the
+//! `canonical` index from block numbers to hashes never goes stale.
+//!
+//! Enable the cache with `L1_CACHE=true`. Receipts and blobs take up most of the space, so delete
+//! the directory, or old entries in it, to reclaim it.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+};
+
+use alloy_consensus::Header;
+use alloy_primitives::{Bytes, B256};
+use alloy_rlp::{Decodable, Encodable};
+use anyhow::{bail, Context, Result};
+use kona_preimage::PreimageKey;
+use kona_proof::HintType;
+
+use crate::{config::env_var, workspace::Workspace};
+
+/// The cache opened by [`L1Cache::open`], read by the hint handler.
+static L1_CACHE: OnceLock<L1Cache> = OnceLock::new();
+
+/// The L1 cache of the process, if one was opened.
+pub fn l1_cache() -> Option<&'static L1Cache> {
+    L1_CACHE.get()
+}
+
+/// Whether the L1 cache is enabled, from `L1_CACHE`.
+pub fn l1_cache_enabled_from_env() -> Result<bool> {
+    Ok(env_var("L1_CACHE")?.unwrap_or(false))
+}
+
+/// The L1 data fetched for a hint, and the hash it is cached under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L1CacheEntry {
+    Header(B256),
+    Transactions(B256),
+    Receipts(B256),
+    /// A blob, and the timestamp of the block it was included in.
+    Blob {
+        hash: B256,
+        timestamp: u64,
+    },
+}
+
+impl L1CacheEntry {
+    /// The entry a hint's preimages are cached under, or `None` if they aren't L1 data.
+    pub fn from_hint(ty: &HintType, data: &[u8]) -> Option<Self> {
+        let hash = || (data.len() >= 32).then(|| B256::from_slice(&data[..32]));
+        match ty {
+            HintType::L1BlockHeader => hash().map(Self::Header),
+            HintType::L1Transactions => hash().map(Self::Transactions),
+            HintType::L1Receipts => hash().map(Self::Receipts),
+            // The blob's versioned hash, its index and the timestamp of its block.
+            HintType::L1Blob if data.len() == 48 => Some(Self::Blob {
+                hash: B256::from_slice(&data[..32]),
+                timestamp: u64::from_be_bytes(data[40..48].try_into().unwrap()),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The hash of the L1 block the entry belongs to, if it isn't a blob.
+    pub fn block_hash(&self) -> Option<B256> {
+        match self {
+            Self::Header(hash) | Self::Transactions(hash) | Self::Receipts(hash) => Some(*hash),
+            Self::Blob { .. } => None,
+        }
+    }
+
+    fn path(&self, dir: &Path) -> PathBuf {
+        match self {
+            Self::Header(hash) => dir.join("headers").join(hash.to_string()),
+            Self::Transactions(hash) => dir.join("transactions").join(hash.to_string()),
+            Self::Receipts(hash) => dir.join("receipts").join(hash.to_string()),
+            Self::Blob { hash, .. } => dir.join("blobs").join(hash.to_string()),
+        }
+    }
+}
+
+/// The key of an L1 header's preimage, which is the block hash.
+pub fn header_key(hash: B256) -> B256 {
+    <[u8; 32]>::from(PreimageKey::new_keccak256(*hash)).into()
+}
+
+/// A chain's cache of finalized L1 data.
+#[derive(Debug)]
+pub struct L1Cache {
+    dir: PathBuf,
+    /// The timestamp of the L1 finalized block, the latest a cached block may have.
+    finalized_timestamp: AtomicU64,
+}
+
+impl L1Cache {
+    /// A cache in `dir`, which caches nothing until the finalized block is set.
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            finalized_timestamp: AtomicU64::new(0),
+        }
+    }
+
+    /// The directory of the chain's L1 cache in the workspace.
+    pub fn dir(workspace: &Workspace, l2_chain_id: u64) -> PathBuf {
+        workspace.chain_dir(l2_chain_id).join("l1-cache")
+    }
+
+    /// Open the chain's L1 cache for the rest of the process if `L1_CACHE` is enabled. The cache
+    /// is shared by every run in the process, as the hint handler has no other way to reach it.
+    pub fn open(workspace: &Workspace, l2_chain_id: u64) -> Result<Option<&'static Self>> {
+        if !l1_cache_enabled_from_env()? {
+            return Ok(None);
+        }
+        let dir = Self::dir(workspace, l2_chain_id);
+        let cache = L1_CACHE.get_or_init(|| Self::new(dir.clone()));
+        if cache.dir != dir {
+            bail!("The L1 cache is already open at {}", cache.dir.display());
+        }
+        Ok(Some(cache))
+    }
+
+    /// Cache blocks up to the given L1 finalized block from now on.
+    pub fn set_finalized(&self, finalized: &Header) {
+        self.finalized_timestamp
+            .fetch_max(finalized.timestamp, Ordering::Relaxed);
+    }
+
+    /// Whether an L1 block with the given timestamp is finalized, and so may be cached.
+    pub fn is_final(&self, timestamp: u64) -> bool {
+        timestamp <= self.finalized_timestamp.load(Ordering::Relaxed)
+    }
+
+    /// The preimages cached for an entry.
+    pub fn get(&self, entry: &L1CacheEntry) -> Option<Vec<(B256, Bytes)>> {
+        let bytes = fs::read(entry.path(&self.dir)).ok()?;
+        serde_cbor::from_slice(&bytes).ok()
+    }
+
+    /// Cache the preimages of an entry. Callers check the entry's block is finalized first.
+    pub fn put(&self, entry: &L1CacheEntry, preimages: &[(B256, Bytes)]) -> Result<()> {
+        write_atomic(&entry.path(&self.dir), &serde_cbor::to_vec(preimages)?)
+    }
+
+    /// The cached header of the L1 block with the given hash.
+    pub fn header(&self, hash: B256) -> Option<Header> {
+        let preimages = self.get(&L1CacheEntry::Header(hash))?;
+        let key = header_key(hash);
+        let (_, rlp) = preimages.iter().find(|(k, _)| *k == key)?;
+        Header::decode(&mut rlp.as_ref()).ok()
+    }
+
+    /// The cached header of the canonical L1 block with the given number.
+    pub fn header_by_number(&self, number: u64) -> Option<Header> {
+        let hash = fs::read(self.dir.join("canonical").join(number.to_string())).ok()?;
+        self.header(B256::try_from(hash.as_slice()).ok()?)
+    }
+
+    /// Cache a header, and index it by number, if it is finalized. Returns whether it was cached.
+    pub fn put_header(&self, header: &Header) -> Result<bool> {
+        if !self.is_final(header.timestamp) {
+            return Ok(false);
+        }
+        let hash = header.hash_slow();
+        let mut rlp = Vec::new();
+        header.encode(&mut rlp);
+        self.put(
+            &L1CacheEntry::Header(hash),
+            &[(header_key(hash), rlp.into())],
+        )?;
+        write_atomic(
+            &self.dir.join("canonical").join(header.number.to_string()),
+            hash.as_slice(),
+        )?;
+        Ok(true)
+    }
+}
+
+/// Write a file through a temporary file, so concurrent runs never read a partial entry.
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = path
+        .parent()
+        .context("Cache entry has no parent directory")?;
+    fs::create_dir_all(dir)?;
+    let tmp = dir.join(format!(
+        ".{}.{}.tmp",
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        std::process::id()
+    ));
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(number: u64, timestamp: u64) -> Header {
+        Header {
+            number,
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_caches_finalized_headers_only() {
+        let dir = std::env::temp_dir().join(format!("l1-cache-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let cache = L1Cache::new(dir.clone());
+        let finalized = header(100, 1_200);
+        let unfinalized = header(101, 1_212);
+
+        // Nothing is cached before the finalized block is known.
+        assert!(!cache.put_header(&finalized).unwrap());
+
+        cache.set_finalized(&finalized);
+        assert!(cache.put_header(&finalized).unwrap());
+        assert!(!cache.put_header(&unfinalized).unwrap());
+
+        assert_eq!(cache.header(finalized.hash_slow()), Some(finalized.clone()));
+        assert_eq!(cache.header_by_number(100), Some(finalized));
+        assert_eq!(cache.header(unfinalized.hash_slow()), None);
+        assert_eq!(cache.header_by_number(101), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_entry_from_hint() {
+        let hash = B256::repeat_byte(1);
+        assert_eq!(
+            L1CacheEntry::from_hint(&HintType::L1Receipts, hash.as_slice()),
+            Some(L1CacheEntry::Receipts(hash))
+        );
+        let blob_data = [
+            hash.as_slice(),
+            &7u64.to_be_bytes(),
+            &1_200u64.to_be_bytes(),
+        ]
+        .concat();
+        assert_eq!(
+            L1CacheEntry::from_hint(&HintType::L1Blob, &blob_data),
+            Some(L1CacheEntry::Blob {
+                hash,
+                timestamp: 1_200
+            })
+        );
+        assert_eq!(
+            L1CacheEntry::from_hint(&HintType::L2BlockHeader, hash.as_slice()),
+            None
+        );
+    }
+}
@@ -0,0 +1,153 @@
+//! The policy for choosing the L1 head of a range proof.
+//!
+//! The L1 head must include the batches of every block in the range, or derivation runs past it.
+//! An L1 head close to the chain tip can be reorged out, invalidating the proof's boot info, so by
+//! default it is chosen at or below the L1 finalized block. `L1_HEAD_POLICY` trades safety for
+//! latency by allowing any L1 block a number of confirmations behind the tip instead.
+//!
+//! The chosen head is the first L1 block at which the L2 node's safe head reaches the end of the
+//! range, plus `L1_HEAD_MARGIN_BLOCKS`, capped at the policy's ceiling. If the batches aren't
+//! included by the ceiling, no head is chosen. Every choice comes with an [`L1HeadSelection`]
+//! explaining it, which is logged and kept with the host arguments of the request.
+
+use std::{fmt, str::FromStr};
+
+use alloy_primitives::B256;
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+use crate::config::env_var;
+
+/// The number of L1 blocks past the batches' inclusion block the L1 head is chosen at.
+const DEFAULT_MARGIN_BLOCKS: u64 = 20;
+
+/// The highest L1 block an L1 head may be chosen at, set with `L1_HEAD_POLICY`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum L1HeadPolicy {
+    /// The L1 finalized block.
+    #[default]
+    Finalized,
+    /// The L1 block the given number of blocks behind the tip.
+    BehindTip(u64),
+}
+
+impl FromStr for L1HeadPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once(':') {
+            None if s == "finalized" => Ok(Self::Finalized),
+            Some(("behind-tip", blocks)) => Ok(Self::BehindTip(blocks.parse()?)),
+            _ => bail!("Unknown L1 head policy {s}, expected finalized or behind-tip:<blocks>"),
+        }
+    }
+}
+
+impl fmt::Display for L1HeadPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Finalized => write!(f, "finalized"),
+            Self::BehindTip(blocks) => write!(f, "behind-tip:{blocks}"),
+        }
+    }
+}
+
+impl L1HeadPolicy {
+    /// Read the policy from `L1_HEAD_POLICY`, defaulting to finalized.
+    pub fn from_env() -> Result<Self> {
+        Ok(env_var("L1_HEAD_POLICY")?.unwrap_or_default())
+    }
+}
+
+/// Read the number of blocks past the batches' inclusion block to choose the L1 head at from
+/// `L1_HEAD_MARGIN_BLOCKS`.
+pub fn margin_blocks_from_env() -> Result<u64> {
+    Ok(env_var("L1_HEAD_MARGIN_BLOCKS")?.unwrap_or(DEFAULT_MARGIN_BLOCKS))
+}
+
+/// Where the L1 head of a range was chosen, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct L1HeadSelection {
+    pub l1_head: B256,
+    pub l1_head_number: u64,
+    pub policy: L1HeadPolicy,
+    /// The highest L1 block the policy allowed.
+    pub ceiling: u64,
+    /// The first L1 block at which the L2 safe head reached the end of the range, if the L2 node
+    /// could tell.
+    pub batch_inclusion_block: Option<u64>,
+    pub rationale: String,
+}
+
+/// Where the batches of a range were included on L1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BatchInclusion {
+    /// By the given L1 block, the first at which the L2 safe head reached the end of the range.
+    Included(u64),
+    /// Not by the policy's ceiling.
+    NotIncluded,
+    /// The L2 node can't report its safe head by L1 block, so the inclusion block is estimated.
+    Estimated(u64),
+}
+
+/// Choose the number of the L1 head for the batches' inclusion, returning it with the rationale.
+pub(crate) fn choose_l1_head_number(
+    policy: L1HeadPolicy,
+    ceiling: u64,
+    margin: u64,
+    inclusion: BatchInclusion,
+) -> Result<(u64, String)> {
+    match inclusion {
+        BatchInclusion::Included(block) => Ok((
+            (block + margin).min(ceiling),
+            format!(
+                "The batches of the range are included by L1 block {block}; chose {margin} \
+                 blocks past it, capped at the {policy} ceiling {ceiling}"
+            ),
+        )),
+        BatchInclusion::NotIncluded => bail!(
+            "The batches of the range are not included by the {policy} ceiling, L1 block {ceiling}"
+        ),
+        BatchInclusion::Estimated(block) => Ok((
+            block.min(ceiling),
+            format!(
+                "The L2 node can't report its safe head by L1 block, so the batch inclusion was \
+                 not verified; chose the estimated L1 block {block}, capped at the {policy} \
+                 ceiling {ceiling}"
+            ),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose_l1_head_number() {
+        let policy = L1HeadPolicy::BehindTip(64);
+        let choose = |inclusion| choose_l1_head_number(policy, 1000, 20, inclusion);
+
+        assert_eq!(choose(BatchInclusion::Included(900)).unwrap().0, 920);
+        assert_eq!(choose(BatchInclusion::Included(990)).unwrap().0, 1000);
+        assert!(choose(BatchInclusion::NotIncluded).is_err());
+        let (number, rationale) = choose(BatchInclusion::Estimated(1200)).unwrap();
+        assert_eq!(number, 1000);
+        assert!(rationale.contains("not verified"));
+    }
+
+    #[test]
+    fn test_parse_l1_head_policy() {
+        assert_eq!(
+            "finalized".parse::<L1HeadPolicy>().unwrap(),
+            L1HeadPolicy::Finalized
+        );
+        assert_eq!(
+            "behind-tip:64".parse::<L1HeadPolicy>().unwrap(),
+            L1HeadPolicy::BehindTip(64)
+        );
+        assert!("behind-tip".parse::<L1HeadPolicy>().is_err());
+        assert!("latest".parse::<L1HeadPolicy>().is_err());
+    }
+}
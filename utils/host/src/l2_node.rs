@@ -0,0 +1,282 @@
+//! Detection of the L2 node's kind and the debug APIs it serves, which decide how witnesses are
+//! generated.
+//!
+//! L2 nodes serve different debug APIs: op-geth and op-reth serve `debug_executionWitness`, which
+//! returns the witness of a block the node already executed, and `debug_executePayload`, which
+//! re-executes a payload on top of its parent, while Erigon serves neither. Servers and scripts
+//! probe the node once at startup, pick the cheapest strategy it supports, and fail with
+//! instructions for the node's kind when it lacks the APIs witness generation needs, instead of
+//! failing on the first witness generation.
+
+use std::{fmt, str::FromStr, sync::OnceLock};
+
+use alloy_provider::{Provider, RootProvider};
+use anyhow::{anyhow, bail, Result};
+use log::info;
+use op_alloy_network::Optimism;
+
+use crate::{
+    config::env_var,
+    fetcher::{http_provider, RPCConfig},
+};
+
+/// The strategy selected by [`select_witness_strategy`], read by the hint handler.
+static WITNESS_STRATEGY: OnceLock<WitnessStrategy> = OnceLock::new();
+
+/// The strategy witness generation uses, [`WitnessStrategy::ExecutePayload`] unless the L2 node was
+/// probed.
+pub fn witness_strategy() -> WitnessStrategy {
+    WITNESS_STRATEGY
+        .get()
+        .copied()
+        .unwrap_or(WitnessStrategy::ExecutePayload)
+}
+
+const EXECUTION_WITNESS: &str = "debug_executionWitness";
+const EXECUTE_PAYLOAD: &str = "debug_executePayload";
+const DB_GET: &str = "debug_dbGet";
+
+/// The APIs the host calls on the L2 node whatever the strategy.
+const REQUIRED_APIS: &[&str] = &[
+    "debug_chainConfig",
+    "debug_getRawBlock",
+    "debug_getRawTransaction",
+    "eth_getProof",
+];
+
+/// The JSON-RPC error code of a method the node doesn't serve.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The kind of L2 execution client `L2_RPC` points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L2NodeKind {
+    OpGeth,
+    OpReth,
+    Erigon,
+    Unknown,
+}
+
+impl L2NodeKind {
+    /// The kind of node reporting `client_version` from `web3_clientVersion`, such as
+    /// `Geth/v1.101411.4-stable/linux-amd64/go1.22.7`.
+    pub fn from_client_version(client_version: &str) -> Self {
+        let name = client_version.split('/').next().unwrap_or_default();
+        match name.to_lowercase().as_str() {
+            "geth" | "op-geth" => Self::OpGeth,
+            "reth" | "op-reth" => Self::OpReth,
+            "erigon" | "op-erigon" => Self::Erigon,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// How to make a node of this kind serve the APIs witness generation needs.
+    fn remediation(&self) -> &'static str {
+        match self {
+            Self::OpGeth => {
+                "Enable the debug namespace on op-geth with `--http.api eth,debug`, and upgrade it if \
+                 it still lacks the APIs."
+            }
+            Self::OpReth => {
+                "Enable the debug namespace on op-reth with `--http.api eth,debug`, and upgrade it if \
+                 it still lacks the APIs."
+            }
+            Self::Erigon => {
+                "Erigon doesn't serve execution witnesses. Point L2_RPC at an op-geth or op-reth node \
+                 of the chain."
+            }
+            Self::Unknown => {
+                "Point L2_RPC at an op-geth or op-reth node of the chain with the debug namespace \
+                 enabled, or set L2_NODE_KIND if it is one."
+            }
+        }
+    }
+}
+
+impl FromStr for L2NodeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "op-geth" | "geth" => Ok(Self::OpGeth),
+            "op-reth" | "reth" => Ok(Self::OpReth),
+            "erigon" | "op-erigon" => Ok(Self::Erigon),
+            _ => Err(format!("expected op-geth, op-reth or erigon, got {s}")),
+        }
+    }
+}
+
+impl fmt::Display for L2NodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OpGeth => write!(f, "op-geth"),
+            Self::OpReth => write!(f, "op-reth"),
+            Self::Erigon => write!(f, "erigon"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// How the witness of each L2 block is fetched from the L2 node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WitnessStrategy {
+    /// Fetch the witness of the canonical block with `debug_executionWitness`, which the node reads
+    /// from the block it already executed.
+    ExecutionWitness,
+    /// Kona's default: re-execute the derived payload on the node with `debug_executePayload`, and
+    /// read any state it misses with `debug_dbGet`.
+    ExecutePayload,
+}
+
+impl WitnessStrategy {
+    /// The cheapest strategy served by a node, or `None` if it serves neither.
+    fn select(serves: impl Fn(&str) -> bool) -> Option<Self> {
+        if serves(EXECUTION_WITNESS) {
+            Some(Self::ExecutionWitness)
+        } else if serves(EXECUTE_PAYLOAD) && serves(DB_GET) {
+            Some(Self::ExecutePayload)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for WitnessStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExecutionWitness => write!(f, "{EXECUTION_WITNESS}"),
+            Self::ExecutePayload => write!(f, "{EXECUTE_PAYLOAD}"),
+        }
+    }
+}
+
+/// The L2 node `L2_RPC` points to, and the witness strategy selected for it.
+#[derive(Debug, Clone)]
+pub struct L2Node {
+    pub kind: L2NodeKind,
+    pub client_version: String,
+    pub strategy: WitnessStrategy,
+}
+
+impl L2Node {
+    /// Probe the node behind `provider` for the APIs witness generation uses. `kind` overrides the
+    /// kind detected from the node's client version.
+    pub async fn probe(
+        provider: &RootProvider<Optimism>,
+        kind: Option<L2NodeKind>,
+    ) -> Result<Self> {
+        let client_version = provider.get_client_version().await?;
+        let kind = kind.unwrap_or_else(|| L2NodeKind::from_client_version(&client_version));
+
+        let mut served = Vec::new();
+        for method in REQUIRED_APIS
+            .iter()
+            .chain(&[EXECUTION_WITNESS, EXECUTE_PAYLOAD, DB_GET])
+        {
+            if serves(provider, *method).await? {
+                served.push(*method);
+            }
+        }
+
+        let mut missing: Vec<&str> = REQUIRED_APIS
+            .iter()
+            .copied()
+            .filter(|method| !served.contains(method))
+            .collect();
+        let strategy = WitnessStrategy::select(|method| served.contains(&method));
+        if strategy.is_none() {
+            missing.push("debug_executionWitness (or debug_executePayload and debug_dbGet)");
+        }
+        match strategy {
+            Some(strategy) if missing.is_empty() => Ok(Self {
+                kind,
+                client_version,
+                strategy,
+            }),
+            _ => bail!(
+                "The L2 node ({kind}, {client_version}) doesn't serve {}, which witness generation \
+                 needs. {}",
+                missing.join(", "),
+                kind.remediation()
+            ),
+        }
+    }
+}
+
+/// Whether the node serves `method`. Methods are called without parameters, so a node that serves
+/// one rejects the call with an invalid parameters error rather than a method not found error.
+async fn serves(provider: &RootProvider<Optimism>, method: &'static str) -> Result<bool> {
+    match provider
+        .client()
+        .request_noparams::<serde_json::Value>(method)
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(e) => match e.as_error_resp() {
+            Some(payload) => Ok(payload.code != METHOD_NOT_FOUND),
+            None => Err(anyhow!("Failed to probe the L2 node for {method}: {e}")),
+        },
+    }
+}
+
+/// Probe the L2 node `L2_RPC` points to, and generate witnesses with the strategy selected for it
+/// for the rest of the process. `kind` overrides `L2_NODE_KIND`, which overrides the detected kind.
+pub async fn select_witness_strategy(kind: Option<L2NodeKind>) -> Result<L2Node> {
+    let kind = match kind {
+        Some(kind) => Some(kind),
+        None => env_var("L2_NODE_KIND")?,
+    };
+    let provider = http_provider(&RPCConfig::from_env()?.l2_rpc);
+    let node = L2Node::probe(&provider, kind).await?;
+    info!(
+        "The L2 node is {} ({}). Generating witnesses with {}.",
+        node.kind, node.client_version, node.strategy
+    );
+    WITNESS_STRATEGY
+        .set(node.strategy)
+        .map_err(|_| anyhow!("The witness strategy was already selected"))?;
+    Ok(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_client_version() {
+        assert_eq!(
+            L2NodeKind::from_client_version(
+                "Geth/v1.101411.4-stable-efa05b1b/linux-amd64/go1.22.7"
+            ),
+            L2NodeKind::OpGeth
+        );
+        assert_eq!(
+            L2NodeKind::from_client_version("reth/v1.1.5-2a1d4ef/x86_64-unknown-linux-gnu"),
+            L2NodeKind::OpReth
+        );
+        assert_eq!(
+            L2NodeKind::from_client_version("erigon/2.60.10/linux-amd64/go1.22.5"),
+            L2NodeKind::Erigon
+        );
+        assert_eq!(
+            L2NodeKind::from_client_version("Nethermind/v1.29.0"),
+            L2NodeKind::Unknown
+        );
+    }
+
+    #[test]
+    fn test_select_strategy() {
+        assert_eq!(
+            WitnessStrategy::select(|_| true),
+            Some(WitnessStrategy::ExecutionWitness)
+        );
+        assert_eq!(
+            WitnessStrategy::select(|method| method != EXECUTION_WITNESS),
+            Some(WitnessStrategy::ExecutePayload)
+        );
+        assert_eq!(
+            WitnessStrategy::select(|method| method == EXECUTE_PAYLOAD),
+            None
+        );
+        assert_eq!(WitnessStrategy::select(|_| false), None);
+    }
+}
@@ -1,22 +1,79 @@
+pub mod agg_stdin;
+pub mod batch_posting;
+pub mod beacon;
+pub mod blob_archive;
 pub mod block_range;
+pub mod chain_config;
+pub mod chaos;
+pub mod config;
+pub mod da;
+pub mod datadir;
+pub mod encryption;
 pub mod fetcher;
+pub mod hint_handler;
+pub mod kv;
+pub mod l1_cache;
+pub mod l1_head;
+pub mod l2_node;
+pub mod minimize;
+pub mod output_root;
+pub mod panic_report;
+pub mod preimage_trace;
+pub mod progress;
+pub mod provider_pool;
 pub mod rollup_config;
+pub mod settlement;
 pub mod stats;
+pub mod supervisor;
+pub mod system_config;
+pub mod vkeys;
+pub mod workspace;
 
+use agg_stdin::{check_proof_count, proof_mode, verify_header_chain, AggStdinError};
 use alloy_consensus::Header;
 use alloy_primitives::B256;
 use alloy_sol_types::sol;
-use anyhow::Result;
-use kona_host::single::SingleChainHost;
-use kona_preimage::{BidirectionalChannel, HintWriter, NativeChannel, OracleReader};
-use log::info;
-use op_succinct_client_utils::client::run_opsuccinct_client;
+use anyhow::{Context, Result};
+use chaos::FailurePoint;
+use config::env_var;
+use encryption::{EncryptedKeyValueStore, WitnessKey};
+use hint_handler::OPSuccinctHintHandler;
+use kona_host::{
+    kv::{
+        DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore, SharedKeyValueStore,
+        SplitKeyValueStore,
+    },
+    single::{SingleChainHost, SingleChainLocalInputs},
+    OnlineHostBackend, PreimageServer,
+};
+use kona_preimage::{
+    BidirectionalChannel, CommsClient, HintReader, HintWriter, NativeChannel, OracleReader,
+    OracleServer, PreimageKey,
+};
+use kona_proof::{FlushableCache, HintType};
+use kv::{CachedKeyValueStore, SharedKvStoreConfig};
+use l1_head::L1HeadSelection;
+use log::{info, warn};
+use op_succinct_client_utils::client::{
+    run_opsuccinct_client, run_opsuccinct_client_with_intermediate_outputs,
+};
 use op_succinct_client_utils::precompiles::zkvm_handle_register;
-use op_succinct_client_utils::{boot::BootInfoStruct, types::AggregationInputs};
-use op_succinct_client_utils::{InMemoryOracle, StoreOracle};
-use rkyv::to_bytes;
+use op_succinct_client_utils::{
+    boot::{BootInfoStruct, ClaimMode, DerivationMode, CLAIM_MODE_KEY, DERIVATION_MODE_KEY},
+    public_values::PublicValuesVersion,
+    types::{AggregationInputs, CheckpointOverride, IntermediateOutput},
+};
+use op_succinct_client_utils::{witness::WitnessFormat, InMemoryOracle, StoreOracle};
+use preimage_trace::{PreimageTrace, TracingKeyValueStore, TracingOracle};
+use progress::{ProgressBackend, WitnessGenProgress};
+use sha2::{Digest, Sha256};
 use sp1_sdk::{HashableKey, SP1Proof, SP1Stdin};
 use std::sync::Arc;
+use supervisor::{record_stall, supervise, HintSource, WitnessGenLimits, WitnessGenStalled};
+use tokio::{
+    sync::RwLock,
+    task::{AbortHandle, JoinHandle},
+};
 
 sol! {
     #[allow(missing_docs)]
@@ -38,73 +95,300 @@ pub enum ProgramType {
     Multi,
 }
 
-sol! {
-    struct L2Output {
-        uint64 zero;
-        bytes32 l2_state_root;
-        bytes32 l2_storage_hash;
-        bytes32 l2_claim_hash;
-    }
-}
-
 #[derive(Debug, Clone)]
 pub struct OPSuccinctHost {
     pub kona_args: SingleChainHost,
+    /// The preimage cache shared with other witness generation runs, if any.
+    pub shared_kv_store: Option<SharedKvStoreConfig>,
+    /// Encrypts the preimages written to the run's witness directory and the shared preimage
+    /// cache, if set.
+    pub witness_key: Option<WitnessKey>,
+    /// Where the L1 head was chosen and why, unless it was given by the caller.
+    pub l1_head_selection: Option<L1HeadSelection>,
+    /// The progress of the run, shared with whoever reports it.
+    pub progress: WitnessGenProgress,
+    /// How the client treats the claimed output root in `kona_args`.
+    pub claim_mode: ClaimMode,
+    /// How the client derives the blocks of the span.
+    pub derivation_mode: DerivationMode,
+    /// Whether the client reads the fields of the output root of every block, for a range program
+    /// built with `intermediate-outputs`.
+    pub intermediate_outputs: bool,
 }
 
-/// Get the stdin to generate a proof for the given L2 claim.
+/// Get the stdin to generate a proof for the given L2 claim, with the witness serialized in the
+/// format set by `WITNESS_FORMAT`.
 pub fn get_proof_stdin(oracle: InMemoryOracle) -> Result<SP1Stdin> {
+    get_proof_stdin_with_format(oracle, WitnessFormat::from_env()?)
+}
+
+/// Get the stdin to generate a proof for the given L2 claim, with the witness serialized in the
+/// given format.
+pub fn get_proof_stdin_with_format(
+    oracle: InMemoryOracle,
+    format: WitnessFormat,
+) -> Result<SP1Stdin> {
     let mut stdin = SP1Stdin::new();
 
-    // Serialize the underlying KV store.
-    let buffer = to_bytes::<rkyv::rancor::Error>(&oracle)?;
+    // The range program reads the format's tag before the witness.
+    stdin.write(&format.tag());
 
-    let kv_store_bytes = buffer.into_vec();
-    stdin.write_slice(&kv_store_bytes);
+    // Serialize the underlying KV store.
+    for buffer in format.serialize(&oracle)? {
+        stdin.write_slice(&buffer);
+    }
 
     Ok(stdin)
 }
 
-/// Get the stdin for the aggregation proof.
+/// The SHA-256 hash of the witness in a span proof's stdin: the witness format's tag followed by the
+/// buffers of the serialized oracle, as read by the range program.
+pub fn witness_sha256(stdin: &SP1Stdin) -> B256 {
+    let mut hasher = Sha256::new();
+    for buffer in &stdin.buffer {
+        hasher.update(buffer);
+    }
+    B256::from(<[u8; 32]>::from(hasher.finalize()))
+}
+
+/// The size in bytes of the buffers written to the stdin, which is what is uploaded to the prover
+/// network. For span proofs this is the size of the serialized witness.
+pub fn stdin_size(stdin: &SP1Stdin) -> usize {
+    stdin.buffer.iter().map(Vec::len).sum()
+}
+
+/// Whether witnesses are generated for a range program built with `intermediate-outputs`, from
+/// `INTERMEDIATE_OUTPUTS`. Default: false.
+pub fn intermediate_outputs_from_env() -> Result<bool> {
+    Ok(env_var("INTERMEDIATE_OUTPUTS")?.unwrap_or(false))
+}
+
+/// Run the client natively to generate a witness. With `intermediate_outputs`, the client also
+/// reads the fields of the output root of every block, which the range program built with
+/// `intermediate-outputs` commits to.
+pub(crate) async fn run_native_client<O>(oracle: Arc<O>, intermediate_outputs: bool) -> Result<()>
+where
+    O: CommsClient + FlushableCache + Send + Sync + std::fmt::Debug,
+{
+    if intermediate_outputs {
+        run_opsuccinct_client_with_intermediate_outputs(oracle, Some(zkvm_handle_register)).await?;
+    } else {
+        run_opsuccinct_client(oracle, Some(zkvm_handle_register)).await?;
+    }
+    Ok(())
+}
+
+/// Get the stdin for the aggregation proof. `checkpoint_overrides` are the governance-approved gaps
+/// in the chain of spans, and are empty for a normal aggregation. `range_public_values_version` is
+/// the version the range proofs commit to their boot infos with, as decoded by
+/// [`op_succinct_client_utils::public_values::decode_boot_infos`], and
+/// `range_intermediate_outputs` the intermediate outputs they commit to after them, as decoded by
+/// [`op_succinct_client_utils::public_values::decode_range_intermediate_outputs`].
+///
+/// Fails with an [`AggStdinError`] on inputs the aggregation program
+/// would fail on.
 pub fn get_agg_proof_stdin(
     proofs: Vec<SP1Proof>,
     boot_infos: Vec<BootInfoStruct>,
+    range_public_values_version: PublicValuesVersion,
+    range_intermediate_outputs: Option<Vec<Vec<IntermediateOutput>>>,
     headers: Vec<Header>,
     multi_block_vkey: &sp1_sdk::SP1VerifyingKey,
     latest_checkpoint_head: B256,
+    checkpoint_overrides: Vec<CheckpointOverride>,
 ) -> Result<SP1Stdin> {
+    check_proof_count(proofs.len(), boot_infos.len())?;
+    verify_header_chain(&headers, latest_checkpoint_head)?;
     let mut stdin = SP1Stdin::new();
-    for proof in proofs {
-        let SP1Proof::Compressed(compressed_proof) = proof else {
-            panic!();
+    for (index, proof) in proofs.into_iter().enumerate() {
+        let compressed_proof = match proof {
+            SP1Proof::Compressed(proof) => proof,
+            proof => return Err(wrong_proof_mode(index, &proof).into()),
         };
         stdin.write_proof(*compressed_proof, multi_block_vkey.vk.clone());
     }
 
-    // Write the aggregation inputs to the stdin.
-    stdin.write(&AggregationInputs {
-        boot_infos,
-        latest_l1_checkpoint_head: latest_checkpoint_head,
-        multi_block_vkey: multi_block_vkey.hash_u32(),
-    });
-    // The headers have issues serializing with bincode, so use serde_json instead.
-    let headers_bytes = serde_cbor::to_vec(&headers).unwrap();
-    stdin.write_vec(headers_bytes);
+    write_agg_inputs(
+        &mut stdin,
+        AggregationInputs {
+            boot_infos,
+            latest_l1_checkpoint_head: latest_checkpoint_head,
+            multi_block_vkey: multi_block_vkey.hash_u32(),
+            secondary_multi_block_vkey: None,
+            checkpoint_overrides,
+            range_public_values_version,
+            range_intermediate_outputs,
+        },
+        headers,
+    )?;
+    Ok(stdin)
+}
 
+/// Get the stdin for an aggregation proof over spans that were each proven by both the range
+/// program and a secondary range program. `proofs` holds the `(primary, secondary)` proofs of each
+/// span.
+pub fn get_quorum_agg_proof_stdin(
+    proofs: Vec<(SP1Proof, SP1Proof)>,
+    boot_infos: Vec<BootInfoStruct>,
+    range_public_values_version: PublicValuesVersion,
+    range_intermediate_outputs: Option<Vec<Vec<IntermediateOutput>>>,
+    headers: Vec<Header>,
+    multi_block_vkey: &sp1_sdk::SP1VerifyingKey,
+    secondary_multi_block_vkey: &sp1_sdk::SP1VerifyingKey,
+    latest_checkpoint_head: B256,
+    checkpoint_overrides: Vec<CheckpointOverride>,
+) -> Result<SP1Stdin> {
+    check_proof_count(proofs.len(), boot_infos.len())?;
+    verify_header_chain(&headers, latest_checkpoint_head)?;
+    let mut stdin = SP1Stdin::new();
+    // The aggregation program verifies each span's primary proof and then its secondary proof.
+    for (index, (primary, secondary)) in proofs.into_iter().enumerate() {
+        let (primary, secondary) = match (primary, secondary) {
+            (SP1Proof::Compressed(primary), SP1Proof::Compressed(secondary)) => {
+                (primary, secondary)
+            }
+            (SP1Proof::Compressed(_), proof) | (proof, _) => {
+                return Err(wrong_proof_mode(index, &proof).into())
+            }
+        };
+        stdin.write_proof(*primary, multi_block_vkey.vk.clone());
+        stdin.write_proof(*secondary, secondary_multi_block_vkey.vk.clone());
+    }
+
+    write_agg_inputs(
+        &mut stdin,
+        AggregationInputs {
+            boot_infos,
+            latest_l1_checkpoint_head: latest_checkpoint_head,
+            multi_block_vkey: multi_block_vkey.hash_u32(),
+            secondary_multi_block_vkey: Some(secondary_multi_block_vkey.hash_u32()),
+            checkpoint_overrides,
+            range_public_values_version,
+            range_intermediate_outputs,
+        },
+        headers,
+    )?;
     Ok(stdin)
 }
 
+fn wrong_proof_mode(index: usize, proof: &SP1Proof) -> AggStdinError {
+    AggStdinError::WrongProofMode {
+        index,
+        mode: proof_mode(proof),
+    }
+}
+
+/// Write the aggregation inputs and the L1 headers to the stdin, after the range proofs.
+fn write_agg_inputs(
+    stdin: &mut SP1Stdin,
+    inputs: AggregationInputs,
+    headers: Vec<Header>,
+) -> Result<()> {
+    stdin.write(&inputs);
+    // The headers have issues serializing with bincode, so use serde_json instead.
+    let headers_bytes = serde_cbor::to_vec(&headers).context("Failed to serialize L1 headers")?;
+    stdin.write_vec(headers_bytes);
+    Ok(())
+}
+
 /// Start the server and native client. Each server is tied to a single client.
 pub async fn start_server_and_native_client(
     cfg: OPSuccinctHost,
 ) -> Result<InMemoryOracle, anyhow::Error> {
     info!("Starting preimage server and client program.");
-    let in_memory_oracle = cfg.run().await?;
+    let limits = WitnessGenLimits::from_env()?;
+    chaos::inject(FailurePoint::WitnessGen)?;
+    // A stalled run is cancelled and restarted. Preimages it fetched into the disk or shared cache
+    // aren't fetched again.
+    let mut restarts = 0;
+    let mut in_memory_oracle = loop {
+        let error = match supervise(&limits, &cfg.progress, cfg.run()).await {
+            Ok(in_memory_oracle) => break in_memory_oracle,
+            Err(error) => error,
+        };
+        let Some(stalled) = error.downcast_ref::<WitnessGenStalled>() else {
+            return Err(error);
+        };
+        record_stall(&cfg.stalled_provider(stalled));
+        if restarts >= limits.stall_retries {
+            return Err(error);
+        }
+        restarts += 1;
+        warn!(
+            "Restarting stalled witness generation ({restarts}/{}).",
+            limits.stall_retries
+        );
+        cfg.progress.restart();
+    };
+
+    if minimize::minimize_from_env()? {
+        let (minimized, minimization) =
+            minimize::minimize_witness(in_memory_oracle, cfg.intermediate_outputs).await?;
+        info!(
+            "Minimized the witness from {} preimages ({} bytes) to {} preimages ({} bytes).",
+            minimization.preimages_before,
+            minimization.bytes_before,
+            minimization.preimages_after,
+            minimization.bytes_after
+        );
+        in_memory_oracle = minimized;
+    }
 
     Ok(in_memory_oracle)
 }
 
+/// The key value store key of a local preimage.
+fn local_key(key: u64) -> B256 {
+    B256::from(<[u8; 32]>::from(PreimageKey::new_local(key)))
+}
+
+/// The local inputs of a run: kona's boot info, the [`ClaimMode`] and the [`DerivationMode`].
+struct LocalInputs {
+    boot_info: SingleChainLocalInputs,
+    claim_mode: ClaimMode,
+    derivation_mode: DerivationMode,
+}
+
+impl KeyValueStore for LocalInputs {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        if key == local_key(CLAIM_MODE_KEY) {
+            return Some(vec![self.claim_mode as u8]);
+        }
+        if key == local_key(DERIVATION_MODE_KEY) {
+            return Some(vec![self.derivation_mode as u8]);
+        }
+        self.boot_info.get(key)
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        self.boot_info.set(key, value)
+    }
+}
+
+/// Aborts a task when dropped.
+struct AbortOnDrop(AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
 impl OPSuccinctHost {
+    /// The redacted URL of the RPC a run stalled on, or `unknown`.
+    fn stalled_provider(&self, stalled: &WitnessGenStalled) -> String {
+        let address = match stalled.source {
+            Some(HintSource::L1) => &self.kona_args.l1_node_address,
+            Some(HintSource::L1Beacon) => &self.kona_args.l1_beacon_address,
+            Some(HintSource::L2) => &self.kona_args.l2_node_address,
+            None => &None,
+        };
+        address
+            .as_deref()
+            .and_then(|address| address.parse().ok())
+            .map_or_else(|| "unknown".to_string(), |url| config::redact_url(&url))
+    }
+
     /// Run the host and client program.
     ///
     /// Returns the in-memory oracle which can be supplied to the zkVM.
@@ -112,18 +396,127 @@ impl OPSuccinctHost {
         let hint = BidirectionalChannel::new()?;
         let preimage = BidirectionalChannel::new()?;
 
+        let trace_dir = PreimageTrace::dir_from_env()?;
+        let trace = trace_dir.as_ref().map(|_| PreimageTrace::default());
+
         let server_task = self
-            .kona_args
-            .start_server(hint.host, preimage.host)
-            .await?;
+            .start_server(trace.clone(), hint.host, preimage.host)
+            .await?
+            .abort_handle();
 
-        let in_memory_oracle = self
-            .run_witnessgen_client(preimage.client, hint.client)
-            .await?;
         // Unlike the upstream, manually abort the server task, as it will hang if you wait for both tasks to complete.
-        server_task.abort();
+        // The guard also aborts it if the run is cancelled.
+        let _server_task = AbortOnDrop(server_task);
+        let _progress_log =
+            AbortOnDrop(tokio::spawn(self.progress.clone().log_periodically()).abort_handle());
 
-        Ok(in_memory_oracle)
+        let result = match &trace {
+            Some(trace) => {
+                self.run_traced_witnessgen_client(preimage.client, hint.client, trace.clone())
+                    .await
+            }
+            None => {
+                self.run_witnessgen_client(preimage.client, hint.client)
+                    .await
+            }
+        };
+
+        // Write the access log whether or not the run succeeded, as failed runs are the ones
+        // worth debugging.
+        if let (Some(dir), Some(trace)) = (trace_dir, trace) {
+            match trace.write(&dir, self.kona_args.claimed_l2_block_number) {
+                Ok(path) => info!("Wrote the preimage access log to {}", path.display()),
+                Err(e) => warn!("Failed to write the preimage access log: {e}"),
+            }
+        }
+
+        result
+    }
+
+    /// Start the preimage server like [`SingleChainHost::start_server`], with the
+    /// [`OPSuccinctHintHandler`], the beacon chain's slot timing (see [`beacon`]), and with the run's key-value store backed by the shared preimage
+    /// cache if configured, and recording the preimages it fetches if traced. The run's progress is
+    /// recorded from the hints and preimages the server handles.
+    async fn start_server(
+        &self,
+        trace: Option<PreimageTrace>,
+        hint: NativeChannel,
+        preimage: NativeChannel,
+    ) -> Result<JoinHandle<Result<(), anyhow::Error>>> {
+        let kv_store = match (&self.shared_kv_store, trace) {
+            (Some(shared_kv_store), None) => self.split_kv_store(CachedKeyValueStore::new(
+                self.local_kv_store(),
+                shared_kv_store.open(self.witness_key.as_ref())?,
+            )),
+            (None, Some(trace)) => {
+                self.split_kv_store(TracingKeyValueStore::new(self.local_kv_store(), trace))
+            }
+            (Some(shared_kv_store), Some(trace)) => self.split_kv_store(TracingKeyValueStore::new(
+                Box::new(CachedKeyValueStore::new(
+                    self.local_kv_store(),
+                    shared_kv_store.open(self.witness_key.as_ref())?,
+                )),
+                trace,
+            )),
+            (None, None) => match (&self.kona_args.data_dir, &self.witness_key) {
+                (Some(data_dir), Some(key)) => self.split_kv_store(EncryptedKeyValueStore::new(
+                    Box::new(DiskKeyValueStore::new(data_dir.clone())),
+                    key.clone(),
+                )),
+                (Some(data_dir), None) => {
+                    self.split_kv_store(DiskKeyValueStore::new(data_dir.clone()))
+                }
+                (None, _) => self.split_kv_store(MemoryKeyValueStore::new()),
+            },
+        };
+
+        let providers = beacon::create_providers(&self.kona_args).await?;
+        let backend = OnlineHostBackend::new(
+            self.kona_args.clone(),
+            kv_store,
+            providers,
+            OPSuccinctHintHandler,
+        )
+        .with_proactive_hint(HintType::L2PayloadWitness);
+
+        Ok(tokio::task::spawn(async move {
+            PreimageServer::new(
+                OracleServer::new(preimage),
+                HintReader::new(hint),
+                Arc::new(ProgressBackend::new(backend, self.progress.clone())),
+            )
+            .start()
+            .await
+            .map_err(anyhow::Error::from)
+        }))
+    }
+
+    /// The key-value store of the run, on disk if the host has a data directory, and encrypted if
+    /// a witness key is set.
+    fn local_kv_store(&self) -> Box<dyn KeyValueStore + Send + Sync> {
+        match (&self.kona_args.data_dir, &self.witness_key) {
+            (Some(data_dir), Some(key)) => Box::new(EncryptedKeyValueStore::new(
+                Box::new(DiskKeyValueStore::new(data_dir.clone())),
+                key.clone(),
+            )),
+            (Some(data_dir), None) => Box::new(DiskKeyValueStore::new(data_dir.clone())),
+            (None, _) => Box::new(MemoryKeyValueStore::new()),
+        }
+    }
+
+    /// Serve the local inputs of the run, and everything else from the given store.
+    fn split_kv_store<R>(&self, store: R) -> SharedKeyValueStore
+    where
+        R: KeyValueStore + Send + Sync + 'static,
+    {
+        Arc::new(RwLock::new(SplitKeyValueStore::new(
+            LocalInputs {
+                boot_info: SingleChainLocalInputs::new(self.kona_args.clone()),
+                claim_mode: self.claim_mode,
+                derivation_mode: self.derivation_mode,
+            },
+            store,
+        )))
     }
 
     /// Run the witness generation client.
@@ -136,8 +529,24 @@ impl OPSuccinctHost {
             OracleReader::new(preimage_chan),
             HintWriter::new(hint_chan),
         ));
-        let _ = run_opsuccinct_client(oracle.clone(), Some(zkvm_handle_register)).await?;
+        run_native_client(oracle.clone(), self.intermediate_outputs).await?;
         let in_memory_oracle = InMemoryOracle::populate_from_store(oracle.as_ref())?;
         Ok(in_memory_oracle)
     }
+
+    /// Run the witness generation client, recording its hint and preimage accesses.
+    async fn run_traced_witnessgen_client(
+        &self,
+        preimage_chan: NativeChannel,
+        hint_chan: NativeChannel,
+        trace: PreimageTrace,
+    ) -> Result<InMemoryOracle> {
+        let oracle = Arc::new(TracingOracle::new(
+            StoreOracle::new(OracleReader::new(preimage_chan), HintWriter::new(hint_chan)),
+            trace,
+        ));
+        run_native_client(oracle.clone(), self.intermediate_outputs).await?;
+        let in_memory_oracle = InMemoryOracle::populate_from_store(oracle.inner())?;
+        Ok(in_memory_oracle)
+    }
 }
@@ -0,0 +1,146 @@
+//! Witness minimization: strip the preimages the range program never reads from the witness.
+//!
+//! Witness generation collects every preimage the host fetched in response to the client's hints,
+//! and hints can over-collect (e.g. a proactive execution witness covering more state than the
+//! block touches). When `MINIMIZE_WITNESS` is set, the client is replayed natively against the
+//! collected witness, recording the preimages it reads, and the rest are removed before the stdin
+//! is serialized. This reduces the upload size and the cycles the zkVM spends deserializing the
+//! witness.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use kona_preimage::{
+    errors::PreimageOracleResult, HintWriterClient, PreimageKey, PreimageKeyType,
+    PreimageOracleClient,
+};
+use kona_proof::FlushableCache;
+use op_succinct_client_utils::{BytesHasherBuilder, InMemoryOracle};
+
+use crate::{config::env_var, run_native_client};
+
+type Cache = HashMap<[u8; 32], Vec<u8>, BytesHasherBuilder>;
+
+/// Whether to minimize witnesses, from `MINIMIZE_WITNESS`. Default: false.
+pub fn minimize_from_env() -> Result<bool> {
+    Ok(env_var("MINIMIZE_WITNESS")?.unwrap_or(false))
+}
+
+/// The size of a witness before and after minimizing it.
+#[derive(Debug, Clone, Copy)]
+pub struct WitnessMinimization {
+    pub preimages_before: usize,
+    pub preimages_after: usize,
+    pub bytes_before: usize,
+    pub bytes_after: usize,
+}
+
+/// An [`InMemoryOracle`] that records the keys read from it.
+#[derive(Debug)]
+struct ReadRecorder {
+    oracle: InMemoryOracle,
+    read: Mutex<HashSet<[u8; 32]>>,
+}
+
+#[async_trait]
+impl PreimageOracleClient for ReadRecorder {
+    async fn get(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        self.read.lock().unwrap().insert(key.into());
+        self.oracle.get(key).await
+    }
+
+    async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<()> {
+        self.read.lock().unwrap().insert(key.into());
+        self.oracle.get_exact(key, buf).await
+    }
+}
+
+#[async_trait]
+impl HintWriterClient for ReadRecorder {
+    async fn write(&self, _hint: &str) -> PreimageOracleResult<()> {
+        Ok(())
+    }
+}
+
+impl FlushableCache for ReadRecorder {
+    fn flush(&self) {}
+}
+
+/// Replay the client against a witness and remove the preimages it doesn't read.
+/// `intermediate_outputs` is whether the witness was generated for a range program built with
+/// `intermediate-outputs`.
+pub async fn minimize_witness(
+    oracle: InMemoryOracle,
+    intermediate_outputs: bool,
+) -> Result<(InMemoryOracle, WitnessMinimization)> {
+    let recorder = Arc::new(ReadRecorder {
+        oracle,
+        read: Mutex::default(),
+    });
+    run_native_client(recorder.clone(), intermediate_outputs).await?;
+    let ReadRecorder { oracle, read } = Arc::try_unwrap(recorder)
+        .map_err(|_| anyhow!("The replayed client still holds the witness"))?;
+
+    let read_order = oracle.read_order().to_vec();
+    let mut cache = oracle.cache;
+    let size = |cache: &Cache| cache.values().map(Vec::len).sum::<usize>();
+    let (preimages_before, bytes_before) = (cache.len(), size(&cache));
+    prune(&mut cache, &read.into_inner().unwrap());
+    let minimization = WitnessMinimization {
+        preimages_before,
+        preimages_after: cache.len(),
+        bytes_before,
+        bytes_after: size(&cache),
+    };
+
+    Ok((
+        InMemoryOracle::with_read_order(cache, read_order),
+        minimization,
+    ))
+}
+
+/// Remove the preimages that were not read. Blobs are kept whole, as the range program verifies
+/// every blob in the witness with a single KZG proof over all of its elements, whether they are
+/// read or not.
+fn prune(cache: &mut Cache, read: &HashSet<[u8; 32]>) {
+    let is_blob = |key: &[u8; 32]| {
+        PreimageKey::try_from(*key).is_ok_and(|key| key.key_type() == PreimageKeyType::Blob)
+    };
+    // The commitment and index of each blob element, which are needed to place the element.
+    let blob_data_keys = cache
+        .keys()
+        .filter(|key| is_blob(key))
+        .map(|key| PreimageKey::new(*key, PreimageKeyType::Keccak256).into())
+        .collect::<HashSet<[u8; 32]>>();
+
+    cache.retain(|key, _| read.contains(key) || is_blob(key) || blob_data_keys.contains(key));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_keeps_read_preimages_and_blobs() {
+        let read = <[u8; 32]>::from(PreimageKey::new([2; 32], PreimageKeyType::Keccak256));
+        let unread = <[u8; 32]>::from(PreimageKey::new([3; 32], PreimageKeyType::Keccak256));
+        let blob = <[u8; 32]>::from(PreimageKey::new([1; 32], PreimageKeyType::Blob));
+        let blob_data = <[u8; 32]>::from(PreimageKey::new(blob, PreimageKeyType::Keccak256));
+
+        let mut cache = Cache::with_hasher(BytesHasherBuilder);
+        for key in [read, unread, blob, blob_data] {
+            cache.insert(key, vec![0]);
+        }
+        prune(&mut cache, &HashSet::from([read]));
+
+        let mut kept = cache.into_keys().collect::<Vec<_>>();
+        kept.sort();
+        let mut expected = vec![read, blob, blob_data];
+        expected.sort();
+        assert_eq!(kept, expected);
+    }
+}
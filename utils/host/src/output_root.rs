@@ -0,0 +1,143 @@
+//! Checking the L2 node's output roots against output roots computed locally.
+//!
+//! Witness generation claims the output root computed from the L2 execution client's block header
+//! and the storage root of the L2ToL1MessagePasser, while the proposer's contracts are checked
+//! against the L2 node's `optimism_outputAtBlock`. When the two disagree, proofs fail or prove roots
+//! the node doesn't recognize. [`OutputRootFields::local`] recomputes each field from the block's
+//! header and a Merkle proof of the message passer account, verified against the header's state
+//! root, so a divergence can be traced to the field that differs.
+
+use alloy_primitives::{keccak256, B256};
+use alloy_provider::Provider;
+use alloy_rlp::Encodable;
+use alloy_trie::{proof::verify_proof, Nibbles, TrieAccount};
+use anyhow::{anyhow, Result};
+use maili_rpc::OutputResponse;
+use op_succinct_client_utils::types::{compute_output_root, OUTPUT_ROOT_VERSION_V0};
+use serde::Serialize;
+
+use crate::fetcher::{OPSuccinctDataFetcher, RPCMode, L2_TO_L1_MESSAGE_PASSER_ADDRESS};
+
+/// The fields an output root commits to, and the output root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputRootFields {
+    pub block_hash: B256,
+    pub state_root: B256,
+    pub message_passer_storage_root: B256,
+    pub output_root: B256,
+}
+
+/// A field of an output root that differs between the L2 node and the local computation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldDivergence {
+    pub field: &'static str,
+    pub node: B256,
+    pub local: B256,
+}
+
+impl OutputRootFields {
+    /// Compute the fields of the output root at `block_number` from the block's header and a proof
+    /// of the L2ToL1MessagePasser account against the header's state root.
+    pub async fn local(fetcher: &OPSuccinctDataFetcher, block_number: u64) -> Result<Self> {
+        let header = fetcher.get_l2_header_by_number(block_number).await?;
+        let proof = fetcher
+            .l2_provider
+            .get_proof(L2_TO_L1_MESSAGE_PASSER_ADDRESS, Vec::new())
+            .block_id(block_number.into())
+            .await?;
+
+        let account = TrieAccount {
+            nonce: proof.nonce,
+            balance: proof.balance,
+            storage_root: proof.storage_hash,
+            code_hash: proof.code_hash,
+        };
+        let mut encoded = Vec::new();
+        account.encode(&mut encoded);
+        verify_proof(
+            header.state_root,
+            Nibbles::unpack(keccak256(L2_TO_L1_MESSAGE_PASSER_ADDRESS)),
+            Some(encoded),
+            &proof.account_proof,
+        )
+        .map_err(|e| {
+            anyhow!("The L2ToL1MessagePasser proof of block {block_number} doesn't verify: {e}")
+        })?;
+
+        let block_hash = header.hash_slow();
+        Ok(Self {
+            block_hash,
+            state_root: header.state_root,
+            message_passer_storage_root: proof.storage_hash,
+            output_root: compute_output_root(
+                OUTPUT_ROOT_VERSION_V0,
+                header.state_root,
+                proof.storage_hash,
+                block_hash,
+            ),
+        })
+    }
+
+    /// Get the fields of the output root at `block_number` from the L2 node.
+    pub async fn node(fetcher: &OPSuccinctDataFetcher, block_number: u64) -> Result<Self> {
+        let output: OutputResponse = fetcher
+            .fetch_rpc_data_with_mode(
+                RPCMode::L2Node,
+                "optimism_outputAtBlock",
+                vec![format!("0x{:x}", block_number).into()],
+            )
+            .await?;
+        Ok(Self {
+            block_hash: output.block_ref.block_info.hash,
+            state_root: output.state_root,
+            message_passer_storage_root: output.withdrawal_storage_root,
+            output_root: output.output_root,
+        })
+    }
+
+    /// The fields that differ from the local computation.
+    pub fn diff(&self, local: &Self) -> Vec<FieldDivergence> {
+        [
+            ("block_hash", self.block_hash, local.block_hash),
+            ("state_root", self.state_root, local.state_root),
+            (
+                "message_passer_storage_root",
+                self.message_passer_storage_root,
+                local.message_passer_storage_root,
+            ),
+            ("output_root", self.output_root, local.output_root),
+        ]
+        .into_iter()
+        .filter(|(_, node, local)| node != local)
+        .map(|(field, node, local)| FieldDivergence { field, node, local })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let fields = OutputRootFields {
+            block_hash: B256::repeat_byte(1),
+            state_root: B256::repeat_byte(2),
+            message_passer_storage_root: B256::repeat_byte(3),
+            output_root: B256::repeat_byte(4),
+        };
+        assert!(fields.diff(&fields).is_empty());
+
+        let local = OutputRootFields {
+            state_root: B256::repeat_byte(5),
+            output_root: B256::repeat_byte(6),
+            ..fields
+        };
+        let diff = fields.diff(&local);
+        assert_eq!(
+            diff.iter().map(|d| d.field).collect::<Vec<_>>(),
+            ["state_root", "output_root"]
+        );
+        assert_eq!(diff[0].local, B256::repeat_byte(5));
+    }
+}
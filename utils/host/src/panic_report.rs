@@ -0,0 +1,47 @@
+use std::sync::{Arc, Mutex};
+
+use op_succinct_client_utils::panic_report::{ClientPanicReport, FD_PANIC_REPORT};
+use sp1_sdk::HookEnv;
+
+/// Collects the [`ClientPanicReport`] a program writes when it panics in the zkVM, so execution
+/// errors can say why the program failed rather than only that it halted.
+///
+/// ```ignore
+/// let panic_report = PanicReportCollector::default();
+/// let (pv, report) = prover
+///     .execute(RANGE_ELF, &stdin)
+///     .with_hook(PanicReportCollector::FD, panic_report.hook())
+///     .run()
+///     .map_err(|e| panic_report.explain(e))?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PanicReportCollector(Arc<Mutex<Option<ClientPanicReport>>>);
+
+impl PanicReportCollector {
+    /// The file descriptor to register [`Self::hook`] on.
+    pub const FD: u32 = FD_PANIC_REPORT;
+
+    /// The executor hook that records the panic report.
+    pub fn hook(&self) -> impl FnMut(HookEnv, &[u8]) -> Vec<Vec<u8>> + Send + Sync + 'static {
+        let report = self.0.clone();
+        move |_, bytes| {
+            *report.lock().unwrap() = ClientPanicReport::from_bytes(bytes);
+            vec![]
+        }
+    }
+
+    /// The panic report, if the program panicked.
+    pub fn report(&self) -> Option<ClientPanicReport> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Convert an execution error into an error including the program's panic report, if it
+    /// panicked.
+    pub fn explain(&self, error: impl Into<anyhow::Error>) -> anyhow::Error {
+        let error = error.into();
+        match self.report() {
+            Some(report) => error.context(format!("Execution failed, {}", report)),
+            None => error,
+        }
+    }
+}
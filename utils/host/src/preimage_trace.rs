@@ -0,0 +1,280 @@
+//! A log of the hints and preimages accessed during witness generation, for debugging witnesses.
+//!
+//! When `PREIMAGE_TRACE_DIR` is set, every witness generation run records each hint the client
+//! sends (with how long the host took to process it) and each preimage it reads (with its size,
+//! latency and whether it was already in the client's cache), along with every preimage the host
+//! fetched into its key-value store. The log is written to the directory as JSON when the run
+//! completes, so the preimages the host fetched can be diffed against those the client read.
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use alloy_primitives::B256;
+use anyhow::Result;
+use async_trait::async_trait;
+use kona_host::kv::KeyValueStore;
+use kona_preimage::{
+    errors::PreimageOracleResult, HintWriterClient, PreimageKey, PreimageOracleClient,
+};
+use kona_proof::FlushableCache;
+use op_succinct_client_utils::StoreOracle;
+use serde::Serialize;
+
+use crate::config::env_var;
+
+/// A hint sent by the client.
+#[derive(Debug, Clone, Serialize)]
+pub struct HintEvent {
+    pub hint_type: String,
+    /// How long the host took to process the hint, including fetching the data it hints at.
+    pub latency_us: u64,
+}
+
+/// A preimage read by the client.
+#[derive(Debug, Clone, Serialize)]
+pub struct PreimageEvent {
+    pub key: B256,
+    pub key_type: String,
+    pub size: usize,
+    pub latency_us: u64,
+    /// Whether the preimage was served from the client's cache rather than the host.
+    pub hit: bool,
+}
+
+#[derive(Debug, Default)]
+struct Trace {
+    hints: Vec<HintEvent>,
+    preimages: Vec<PreimageEvent>,
+    fetched: Vec<B256>,
+}
+
+/// The totals of a [`PreimageTraceReport`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PreimageTraceSummary {
+    pub hints: usize,
+    pub reads: usize,
+    pub hits: usize,
+    pub misses: usize,
+    pub bytes_read: usize,
+    pub fetched: usize,
+    pub fetched_not_read: usize,
+}
+
+/// The access log of a witness generation run.
+#[derive(Debug, Clone, Serialize)]
+pub struct PreimageTraceReport {
+    pub summary: PreimageTraceSummary,
+    pub hints: Vec<HintEvent>,
+    pub preimages: Vec<PreimageEvent>,
+    /// The preimages the host fetched into its key-value store that the client never read.
+    pub fetched_not_read: Vec<B256>,
+}
+
+/// Records the hint and preimage accesses of a witness generation run. Clones share the log.
+#[derive(Debug, Clone, Default)]
+pub struct PreimageTrace(Arc<Mutex<Trace>>);
+
+impl PreimageTrace {
+    /// The directory to write the access logs to, from `PREIMAGE_TRACE_DIR`. Tracing is disabled if
+    /// unset.
+    pub fn dir_from_env() -> Result<Option<PathBuf>> {
+        env_var("PREIMAGE_TRACE_DIR")
+    }
+
+    fn record_hint(&self, hint: &str, start: Instant) {
+        self.0.lock().unwrap().hints.push(HintEvent {
+            hint_type: hint
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            latency_us: start.elapsed().as_micros() as u64,
+        });
+    }
+
+    fn record_preimage(&self, key: PreimageKey, size: usize, start: Instant, hit: bool) {
+        self.0.lock().unwrap().preimages.push(PreimageEvent {
+            key: B256::from(<[u8; 32]>::from(key)),
+            key_type: format!("{:?}", key.key_type()),
+            size,
+            latency_us: start.elapsed().as_micros() as u64,
+            hit,
+        });
+    }
+
+    fn record_fetch(&self, key: B256) {
+        self.0.lock().unwrap().fetched.push(key);
+    }
+
+    pub fn report(&self) -> PreimageTraceReport {
+        let trace = self.0.lock().unwrap();
+        let read = trace
+            .preimages
+            .iter()
+            .map(|event| event.key)
+            .collect::<HashSet<_>>();
+        let fetched = trace.fetched.iter().copied().collect::<HashSet<_>>();
+        let mut fetched_not_read = fetched.difference(&read).copied().collect::<Vec<_>>();
+        fetched_not_read.sort();
+
+        let hits = trace.preimages.iter().filter(|event| event.hit).count();
+        PreimageTraceReport {
+            summary: PreimageTraceSummary {
+                hints: trace.hints.len(),
+                reads: trace.preimages.len(),
+                hits,
+                misses: trace.preimages.len() - hits,
+                bytes_read: trace.preimages.iter().map(|event| event.size).sum(),
+                fetched: fetched.len(),
+                fetched_not_read: fetched_not_read.len(),
+            },
+            hints: trace.hints.clone(),
+            preimages: trace.preimages.clone(),
+            fetched_not_read,
+        }
+    }
+
+    /// Write the access log to `{dir}/{l2_block}-{timestamp}.json`, returning its path.
+    pub fn write(&self, dir: &Path, l2_block: u64) -> Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        let path = dir.join(format!("{l2_block}-{timestamp}.json"));
+        fs::write(&path, serde_json::to_vec_pretty(&self.report())?)?;
+        Ok(path)
+    }
+}
+
+/// A [`StoreOracle`] that records the client's accesses in a [`PreimageTrace`].
+#[derive(Debug)]
+pub struct TracingOracle<OR, HW>
+where
+    OR: PreimageOracleClient,
+    HW: HintWriterClient,
+{
+    inner: StoreOracle<OR, HW>,
+    trace: PreimageTrace,
+}
+
+impl<OR, HW> TracingOracle<OR, HW>
+where
+    OR: PreimageOracleClient,
+    HW: HintWriterClient,
+{
+    pub fn new(inner: StoreOracle<OR, HW>, trace: PreimageTrace) -> Self {
+        Self { inner, trace }
+    }
+
+    pub fn inner(&self) -> &StoreOracle<OR, HW> {
+        &self.inner
+    }
+}
+
+impl<OR, HW> FlushableCache for TracingOracle<OR, HW>
+where
+    OR: PreimageOracleClient,
+    HW: HintWriterClient,
+{
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+#[async_trait]
+impl<OR, HW> PreimageOracleClient for TracingOracle<OR, HW>
+where
+    OR: PreimageOracleClient + Sync,
+    HW: HintWriterClient + Sync,
+{
+    async fn get(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        let hit = self.inner.cache.lock().contains_key(&key);
+        let start = Instant::now();
+        let value = self.inner.get(key).await?;
+        self.trace.record_preimage(key, value.len(), start, hit);
+        Ok(value)
+    }
+
+    async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<()> {
+        let hit = self.inner.cache.lock().contains_key(&key);
+        let start = Instant::now();
+        self.inner.get_exact(key, buf).await?;
+        self.trace.record_preimage(key, buf.len(), start, hit);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<OR, HW> HintWriterClient for TracingOracle<OR, HW>
+where
+    OR: PreimageOracleClient + Sync,
+    HW: HintWriterClient + Sync,
+{
+    async fn write(&self, hint: &str) -> PreimageOracleResult<()> {
+        let start = Instant::now();
+        self.inner.write(hint).await?;
+        self.trace.record_hint(hint, start);
+        Ok(())
+    }
+}
+
+/// A key-value store that records the preimages the host fetches in a [`PreimageTrace`].
+pub struct TracingKeyValueStore {
+    inner: Box<dyn KeyValueStore + Send + Sync>,
+    trace: PreimageTrace,
+}
+
+impl TracingKeyValueStore {
+    pub fn new(inner: Box<dyn KeyValueStore + Send + Sync>, trace: PreimageTrace) -> Self {
+        Self { inner, trace }
+    }
+}
+
+impl KeyValueStore for TracingKeyValueStore {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        self.inner.get(key)
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        self.trace.record_fetch(key);
+        self.inner.set(key, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_host::kv::MemoryKeyValueStore;
+    use kona_preimage::PreimageKeyType;
+
+    #[test]
+    fn test_report_diffs_fetched_and_read_preimages() {
+        let trace = PreimageTrace::default();
+        let read = PreimageKey::new([1; 32], PreimageKeyType::Keccak256);
+        let unread = PreimageKey::new([2; 32], PreimageKeyType::Keccak256);
+
+        let mut store =
+            TracingKeyValueStore::new(Box::new(MemoryKeyValueStore::new()), trace.clone());
+        store
+            .set(B256::from(<[u8; 32]>::from(read)), vec![1])
+            .unwrap();
+        store
+            .set(B256::from(<[u8; 32]>::from(unread)), vec![2])
+            .unwrap();
+        trace.record_hint("l2-payload-witness 0x00", Instant::now());
+        trace.record_preimage(read, 1, Instant::now(), false);
+        trace.record_preimage(read, 1, Instant::now(), true);
+
+        let report = trace.report();
+        assert_eq!(report.summary.reads, 2);
+        assert_eq!(report.summary.hits, 1);
+        assert_eq!(report.hints[0].hint_type, "l2-payload-witness");
+        assert_eq!(
+            report.fetched_not_read,
+            vec![B256::from(<[u8; 32]>::from(unread))]
+        );
+    }
+}
@@ -0,0 +1,397 @@
+//! Progress of witness generation runs.
+//!
+//! Witness generation of a large span takes many minutes, with nothing to show for it until it
+//! completes. Each run counts the blocks the client starts executing (from the
+//! `l2-payload-witness` hint it sends before each block) and the preimages the host serves it, and
+//! estimates the time remaining from the blocks completed so far. The run logs its progress every
+//! [`PROGRESS_LOG_INTERVAL`], and the server streams the progress of its in-flight runs to clients.
+//!
+//! The preimages served from a block's `l2-payload-witness` hint until the next block's are
+//! attributed to the block, which approximates its contribution to the witness. The per-block limit
+//! of [`WitnessGenLimits`](crate::supervisor::WitnessGenLimits) is checked against it.
+//!
+//! Every hint and preimage is also a heartbeat: a run that goes without one for the stall timeout
+//! of the limits is waiting on an RPC that stopped responding, and is restarted.
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use kona_preimage::{errors::PreimageOracleResult, HintRouter, PreimageFetcher, PreimageKey};
+use log::info;
+use serde::{Deserialize, Serialize};
+
+/// How often a run logs its progress.
+pub const PROGRESS_LOG_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The hint the client sends before executing each block.
+const PAYLOAD_WITNESS_HINT: &str = "l2-payload-witness";
+
+#[derive(Debug)]
+struct Counters {
+    start_block: u64,
+    end_block: u64,
+    started_at: Instant,
+    blocks_started: AtomicU64,
+    preimages: AtomicU64,
+    bytes: AtomicU64,
+    /// The bytes of the preimages served for each block started, in order.
+    block_bytes: Mutex<Vec<u64>>,
+    /// When the last hint or preimage was served, in milliseconds since `started_at`.
+    last_activity_ms: AtomicU64,
+    /// The type of the last hint the client sent, which the host fetches the next preimages for.
+    last_hint: Mutex<Option<String>>,
+}
+
+/// The progress of a witness generation run for the L2 blocks after `start_block` up to
+/// `end_block`. Clones share the counters.
+#[derive(Debug, Clone)]
+pub struct WitnessGenProgress(Arc<Counters>);
+
+/// The preimage bytes served while a block was derived and executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockWitnessSize {
+    pub block: u64,
+    pub bytes: u64,
+}
+
+/// A point-in-time view of a [`WitnessGenProgress`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProgressSnapshot {
+    pub start_block: u64,
+    pub end_block: u64,
+    /// The block being executed, or `start_block` before the first block.
+    pub current_block: u64,
+    pub blocks_completed: u64,
+    pub total_blocks: u64,
+    /// The preimages served to the client, and their total size.
+    pub preimages: u64,
+    pub bytes_fetched: u64,
+    pub elapsed_secs: u64,
+    /// The time since the last hint or preimage was served.
+    pub idle_secs: u64,
+    /// The estimated time until the last block completes, once a block has completed.
+    pub eta_secs: Option<u64>,
+}
+
+impl WitnessGenProgress {
+    pub fn new(start_block: u64, end_block: u64) -> Self {
+        Self(Arc::new(Counters {
+            start_block,
+            end_block,
+            started_at: Instant::now(),
+            blocks_started: AtomicU64::new(0),
+            preimages: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            block_bytes: Mutex::new(Vec::new()),
+            last_activity_ms: AtomicU64::new(0),
+            last_hint: Mutex::new(None),
+        }))
+    }
+
+    pub(crate) fn record_hint(&self, hint: &str) {
+        self.touch();
+        let hint_type = hint.split_whitespace().next();
+        if hint_type == Some(PAYLOAD_WITNESS_HINT) {
+            self.0.blocks_started.fetch_add(1, Ordering::Relaxed);
+            self.0.block_bytes.lock().unwrap().push(0);
+        }
+        *self.0.last_hint.lock().unwrap() = hint_type.map(str::to_string);
+    }
+
+    pub(crate) fn record_preimage(&self, size: usize) {
+        self.touch();
+        self.0.preimages.fetch_add(1, Ordering::Relaxed);
+        self.0.bytes.fetch_add(size as u64, Ordering::Relaxed);
+        if let Some(bytes) = self.0.block_bytes.lock().unwrap().last_mut() {
+            *bytes += size as u64;
+        }
+    }
+
+    /// The preimage bytes served for each block started so far, the last one still executing.
+    pub fn block_witness_sizes(&self) -> Vec<BlockWitnessSize> {
+        self.0
+            .block_bytes
+            .lock()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(i, &bytes)| BlockWitnessSize {
+                block: self.0.start_block + 1 + i as u64,
+                bytes,
+            })
+            .collect()
+    }
+
+    fn touch(&self) {
+        self.0.last_activity_ms.store(
+            self.0.started_at.elapsed().as_millis() as u64,
+            Ordering::Relaxed,
+        );
+    }
+
+    /// The time since the last hint or preimage was served, or since the run started.
+    pub fn idle_for(&self) -> Duration {
+        self.idle_at(self.0.started_at.elapsed())
+    }
+
+    fn idle_at(&self, elapsed: Duration) -> Duration {
+        elapsed.saturating_sub(Duration::from_millis(
+            self.0.last_activity_ms.load(Ordering::Relaxed),
+        ))
+    }
+
+    /// The type of the last hint the client sent, if any.
+    pub fn last_hint(&self) -> Option<String> {
+        self.0.last_hint.lock().unwrap().clone()
+    }
+
+    /// Clear the counters for a new attempt of the run, which starts from the first block again.
+    /// The elapsed time still counts from the first attempt.
+    pub(crate) fn restart(&self) {
+        self.0.blocks_started.store(0, Ordering::Relaxed);
+        self.0.preimages.store(0, Ordering::Relaxed);
+        self.0.bytes.store(0, Ordering::Relaxed);
+        self.0.block_bytes.lock().unwrap().clear();
+        *self.0.last_hint.lock().unwrap() = None;
+        self.touch();
+    }
+
+    /// The first block whose preimages exceed `limit` bytes, if any.
+    pub fn oversized_block(&self, limit: u64) -> Option<BlockWitnessSize> {
+        self.block_witness_sizes()
+            .into_iter()
+            .find(|size| size.bytes > limit)
+    }
+
+    pub fn snapshot(&self) -> ProgressSnapshot {
+        self.snapshot_at(self.0.started_at.elapsed())
+    }
+
+    fn snapshot_at(&self, elapsed: Duration) -> ProgressSnapshot {
+        let total_blocks = self.0.end_block - self.0.start_block;
+        let blocks_started = self
+            .0
+            .blocks_started
+            .load(Ordering::Relaxed)
+            .min(total_blocks);
+        let blocks_completed = blocks_started.saturating_sub(1);
+        let eta_secs = (blocks_completed > 0).then(|| {
+            let per_block = elapsed.as_secs_f64() / blocks_completed as f64;
+            (per_block * (total_blocks - blocks_completed) as f64) as u64
+        });
+        ProgressSnapshot {
+            start_block: self.0.start_block,
+            end_block: self.0.end_block,
+            current_block: self.0.start_block + blocks_started,
+            blocks_completed,
+            total_blocks,
+            preimages: self.0.preimages.load(Ordering::Relaxed),
+            bytes_fetched: self.0.bytes.load(Ordering::Relaxed),
+            elapsed_secs: elapsed.as_secs(),
+            idle_secs: self.idle_at(elapsed).as_secs(),
+            eta_secs,
+        }
+    }
+
+    /// Log the progress every [`PROGRESS_LOG_INTERVAL`], until the returned future is dropped.
+    pub async fn log_periodically(self) {
+        let mut interval = tokio::time::interval(PROGRESS_LOG_INTERVAL);
+        // The first tick completes immediately, when there is no progress to log.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let snapshot = self.snapshot();
+            info!(
+                "Witness generation for L2 blocks {}-{}: block {} ({}/{} completed), {} preimages \
+                 ({} bytes) fetched in {}s, ETA {}",
+                snapshot.start_block,
+                snapshot.end_block,
+                snapshot.current_block,
+                snapshot.blocks_completed,
+                snapshot.total_blocks,
+                snapshot.preimages,
+                snapshot.bytes_fetched,
+                snapshot.elapsed_secs,
+                snapshot
+                    .eta_secs
+                    .map_or("unknown".to_string(), |eta| format!("{eta}s"))
+            );
+        }
+    }
+}
+
+/// A preimage server backend that records the client's hints and the preimages served to it in
+/// a [`WitnessGenProgress`].
+pub struct ProgressBackend<B> {
+    inner: B,
+    progress: WitnessGenProgress,
+}
+
+impl<B> ProgressBackend<B> {
+    pub fn new(inner: B, progress: WitnessGenProgress) -> Self {
+        Self { inner, progress }
+    }
+}
+
+#[async_trait]
+impl<B: PreimageFetcher + Send + Sync> PreimageFetcher for ProgressBackend<B> {
+    async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        let value = self.inner.get_preimage(key).await?;
+        self.progress.record_preimage(value.len());
+        Ok(value)
+    }
+}
+
+#[async_trait]
+impl<B: HintRouter + Send + Sync> HintRouter for ProgressBackend<B> {
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        self.progress.record_hint(&hint);
+        self.inner.route_hint(hint).await
+    }
+}
+
+#[derive(Debug, Default)]
+struct Runs {
+    last_id: u64,
+    by_id: BTreeMap<u64, WitnessGenProgress>,
+}
+
+/// The witness generation runs in flight, by an ID unique to the process.
+#[derive(Debug, Clone, Default)]
+pub struct InFlightRuns(Arc<Mutex<Runs>>);
+
+impl InFlightRuns {
+    /// Track a run until the returned guard is dropped.
+    pub fn track(&self, progress: WitnessGenProgress) -> TrackedRun {
+        let mut runs = self.0.lock().unwrap();
+        runs.last_id += 1;
+        let id = runs.last_id;
+        runs.by_id.insert(id, progress);
+        TrackedRun {
+            runs: self.clone(),
+            id,
+        }
+    }
+
+    /// The progress of each run in flight, by ID.
+    pub fn snapshots(&self) -> Vec<(u64, ProgressSnapshot)> {
+        let runs = self.0.lock().unwrap();
+        runs.by_id
+            .iter()
+            .map(|(id, progress)| (*id, progress.snapshot()))
+            .collect()
+    }
+}
+
+/// Removes a run from the [`InFlightRuns`] when dropped.
+pub struct TrackedRun {
+    runs: InFlightRuns,
+    id: u64,
+}
+
+impl TrackedRun {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl Drop for TrackedRun {
+    fn drop(&mut self) {
+        self.runs.0.lock().unwrap().by_id.remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        let progress = WitnessGenProgress::new(100, 110);
+        let snapshot = progress.snapshot_at(Duration::from_secs(10));
+        assert_eq!(snapshot.current_block, 100);
+        assert_eq!(snapshot.eta_secs, None);
+
+        for _ in 0..3 {
+            progress.record_hint("l2-payload-witness 0x00");
+        }
+        progress.record_hint("l1-block-header 0x00");
+        progress.record_preimage(32);
+        progress.record_preimage(64);
+
+        // Two blocks completed in 10s, and the third is executing.
+        let snapshot = progress.snapshot_at(Duration::from_secs(10));
+        assert_eq!(snapshot.current_block, 103);
+        assert_eq!(snapshot.blocks_completed, 2);
+        assert_eq!(snapshot.preimages, 2);
+        assert_eq!(snapshot.bytes_fetched, 96);
+        assert_eq!(snapshot.eta_secs, Some(40));
+
+        // The preimages are attributed to the third block, the one executing.
+        assert_eq!(
+            progress.block_witness_sizes(),
+            [
+                BlockWitnessSize {
+                    block: 101,
+                    bytes: 0
+                },
+                BlockWitnessSize {
+                    block: 102,
+                    bytes: 0
+                },
+                BlockWitnessSize {
+                    block: 103,
+                    bytes: 96
+                },
+            ]
+        );
+        assert_eq!(
+            progress.oversized_block(64),
+            Some(BlockWitnessSize {
+                block: 103,
+                bytes: 96
+            })
+        );
+        assert_eq!(progress.oversized_block(96), None);
+        assert_eq!(progress.last_hint().as_deref(), Some("l1-block-header"));
+
+        progress.restart();
+        let snapshot = progress.snapshot();
+        assert_eq!(snapshot.current_block, 100);
+        assert_eq!(snapshot.preimages, 0);
+        assert!(progress.block_witness_sizes().is_empty());
+        assert_eq!(progress.last_hint(), None);
+    }
+
+    #[test]
+    fn test_idle() {
+        let progress = WitnessGenProgress::new(100, 110);
+        assert_eq!(
+            progress.idle_at(Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
+        progress.record_preimage(32);
+        assert!(progress.idle_for() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_in_flight_runs() {
+        let runs = InFlightRuns::default();
+        let first = runs.track(WitnessGenProgress::new(0, 10));
+        let second = runs.track(WitnessGenProgress::new(10, 20));
+        assert_ne!(first.id(), second.id());
+        assert_eq!(runs.snapshots().len(), 2);
+
+        drop(first);
+        let snapshots = runs.snapshots();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].0, second.id());
+    }
+}
@@ -0,0 +1,454 @@
+//! Pools of RPC endpoints serving the same chain, with requests routed by endpoint health.
+//!
+//! `L1_RPC_POOL`, `L1_BEACON_RPC_POOL` and `L2_RPC_POOL` list endpoints to use alongside
+//! `L1_RPC`, `L1_BEACON_RPC` and `L2_RPC`. The pool of each tracks the rolling latency and error
+//! rate of every endpoint per [`RequestClass`], as providers differ per class (an archive node may
+//! serve proofs faster than a pruned one, and a blob provider may be slow on headers). Each request
+//! goes to the endpoint with the best score for its class, and fails over to the next best on a
+//! transport error. Endpoints that haven't served a class recently are probed again, so one that
+//! recovers from an outage is used again.
+//!
+//! Kona's host takes a single URL per RPC, so witness generation's own requests go to `L1_RPC`,
+//! `L1_BEACON_RPC` and `L2_RPC`. The fetcher's providers, and the beacon API proxy, use the pools.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::{Arc, LazyLock, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_transport::{TransportError, TransportFut};
+use alloy_transport_http::Http;
+use anyhow::{anyhow, Result};
+use log::warn;
+use reqwest::Url;
+use serde::Serialize;
+use tower::Service;
+
+use crate::config::{env_var, redact_url};
+
+/// The weight of the latest request in an endpoint's rolling latency and error rate.
+const EWMA_ALPHA: f64 = 0.2;
+
+/// The latency, in seconds, an endpoint's error rate adds to its score. An endpoint failing half
+/// its requests scores as if it were 2.5s slower.
+const ERROR_PENALTY_SECS: f64 = 5.0;
+
+/// How long an endpoint goes without serving a class before it is probed again.
+const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The kinds of requests the fetcher makes, which endpoints are ranked on separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestClass {
+    Headers,
+    Proofs,
+    Receipts,
+    Blobs,
+    Other,
+}
+
+impl RequestClass {
+    /// The class of a JSON-RPC method.
+    pub fn of_method(method: &str) -> Self {
+        match method {
+            "eth_getBlockByNumber"
+            | "eth_getBlockByHash"
+            | "eth_blockNumber"
+            | "debug_getRawHeader"
+            | "debug_getRawBlock" => Self::Headers,
+            "eth_getProof" | "debug_executionWitness" | "debug_executePayload" | "debug_dbGet" => {
+                Self::Proofs
+            }
+            "eth_getBlockReceipts" | "eth_getTransactionReceipt" | "debug_getRawReceipts" => {
+                Self::Receipts
+            }
+            _ => Self::Other,
+        }
+    }
+
+    /// The class of a JSON-RPC request, or of the first call of a batch.
+    pub fn of_packet(packet: &RequestPacket) -> Self {
+        packet
+            .method_names()
+            .next()
+            .map_or(Self::Other, Self::of_method)
+    }
+
+    /// The class of a beacon API request.
+    pub fn of_beacon_path(path: &str) -> Self {
+        if path.starts_with("eth/v1/beacon/blob_sidecars/") {
+            Self::Blobs
+        } else {
+            Self::Other
+        }
+    }
+}
+
+impl fmt::Display for RequestClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Headers => "headers",
+            Self::Proofs => "proofs",
+            Self::Receipts => "receipts",
+            Self::Blobs => "blobs",
+            Self::Other => "other",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The rolling health of an endpoint for a class of requests.
+#[derive(Debug, Clone, Default)]
+struct EndpointHealth {
+    requests: u64,
+    errors: u64,
+    /// The rolling latency in seconds, `None` until the first request.
+    latency: Option<f64>,
+    error_rate: f64,
+    last_request: Option<Instant>,
+}
+
+impl EndpointHealth {
+    fn record(&mut self, latency: Duration, ok: bool, now: Instant) {
+        let latency = latency.as_secs_f64();
+        let error = if ok { 0.0 } else { 1.0 };
+        self.requests += 1;
+        self.errors += u64::from(!ok);
+        self.latency = Some(match self.latency {
+            Some(rolling) => rolling + EWMA_ALPHA * (latency - rolling),
+            None => latency,
+        });
+        self.error_rate += EWMA_ALPHA * (error - self.error_rate);
+        self.last_request = Some(now);
+    }
+
+    /// Lower is better. Endpoints without a recent request score 0, so they are tried next.
+    fn score(&self, now: Instant) -> f64 {
+        match (self.latency, self.last_request) {
+            (Some(latency), Some(last)) if now.duration_since(last) < PROBE_INTERVAL => {
+                latency + ERROR_PENALTY_SECS * self.error_rate
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// The utilization and health of an endpoint for a class of requests.
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointStats {
+    /// The endpoint, redacted.
+    pub endpoint: String,
+    pub class: RequestClass,
+    pub requests: u64,
+    pub errors: u64,
+    pub latency_secs: f64,
+    pub error_rate: f64,
+    /// The endpoint's share of the pool's requests of the class.
+    pub share: f64,
+}
+
+/// Endpoints serving the same chain, ranked per class of request by their rolling latency and
+/// error rate.
+#[derive(Debug)]
+pub struct ProviderPool {
+    name: String,
+    endpoints: Vec<Url>,
+    health: Mutex<HashMap<(usize, RequestClass), EndpointHealth>>,
+}
+
+impl ProviderPool {
+    /// A pool of `primary` and `others`. Duplicate endpoints are dropped.
+    pub fn new(name: &str, primary: Url, others: Vec<Url>) -> Self {
+        let mut endpoints = vec![primary];
+        for url in others {
+            if !endpoints.contains(&url) {
+                endpoints.push(url);
+            }
+        }
+        Self {
+            name: name.to_string(),
+            endpoints,
+            health: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The endpoint the pool was created for.
+    pub fn primary(&self) -> &Url {
+        &self.endpoints[0]
+    }
+
+    pub fn endpoints(&self) -> &[Url] {
+        &self.endpoints
+    }
+
+    /// The endpoints, best first for the class. Ties keep the configured order, so the primary is
+    /// preferred until the others are measured faster.
+    pub fn ranked(&self, class: RequestClass) -> Vec<usize> {
+        let now = Instant::now();
+        let health = self.health.lock().unwrap();
+        let mut ranked: Vec<(usize, f64)> = (0..self.endpoints.len())
+            .map(|index| {
+                let score = health
+                    .get(&(index, class))
+                    .map_or(0.0, |health| health.score(now));
+                (index, score)
+            })
+            .collect();
+        ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+        ranked.into_iter().map(|(index, _)| index).collect()
+    }
+
+    /// Record the outcome of a request to an endpoint.
+    pub fn record(&self, index: usize, class: RequestClass, latency: Duration, ok: bool) {
+        self.health
+            .lock()
+            .unwrap()
+            .entry((index, class))
+            .or_default()
+            .record(latency, ok, Instant::now());
+    }
+
+    /// Send a request to the best endpoint for its class, failing over to the next best on an
+    /// error. `send` is called with the index of the endpoint to send to.
+    pub async fn route<T, E, F, Fut>(&self, class: RequestClass, mut send: F) -> Result<T, E>
+    where
+        E: fmt::Display,
+        F: FnMut(usize) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut last_error = None;
+        for index in self.ranked(class) {
+            let start = Instant::now();
+            let result = send(index).await;
+            self.record(index, class, start.elapsed(), result.is_ok());
+            match result {
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    if self.endpoints.len() > 1 {
+                        warn!(
+                            "{} request to {} ({}) failed, trying the next endpoint: {}",
+                            self.name,
+                            redact_url(&self.endpoints[index]),
+                            class,
+                            e
+                        );
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.expect("a pool has at least one endpoint"))
+    }
+
+    /// The utilization and health of every endpoint, per class it has served.
+    pub fn stats(&self) -> Vec<EndpointStats> {
+        let health = self.health.lock().unwrap();
+        let mut class_requests: HashMap<RequestClass, u64> = HashMap::new();
+        for ((_, class), health) in health.iter() {
+            *class_requests.entry(*class).or_default() += health.requests;
+        }
+
+        let mut stats: Vec<EndpointStats> = health
+            .iter()
+            .map(|((index, class), health)| EndpointStats {
+                endpoint: redact_url(&self.endpoints[*index]),
+                class: *class,
+                requests: health.requests,
+                errors: health.errors,
+                latency_secs: health.latency.unwrap_or_default(),
+                error_rate: health.error_rate,
+                share: health.requests as f64 / class_requests[class].max(1) as f64,
+            })
+            .collect();
+        stats.sort_by(|a, b| (a.class as u8, &a.endpoint).cmp(&(b.class as u8, &b.endpoint)));
+        stats
+    }
+}
+
+/// The pools of the process, by the URL of their primary endpoint.
+static POOLS: LazyLock<Mutex<HashMap<Url, Arc<ProviderPool>>>> = LazyLock::new(Default::default);
+
+/// Register the pool of a primary endpoint, reading the other endpoints from the comma-separated
+/// URLs of `{name}_POOL`. The pool is kept if already registered, so its health carries over when
+/// the RPC config is read again.
+pub fn register_pool_from_env(name: &str, primary: &Url) -> Result<Arc<ProviderPool>> {
+    let others = url_list_var(&format!("{name}_POOL"))?;
+    let mut pools = POOLS.lock().unwrap();
+    let pool = pools
+        .entry(primary.clone())
+        .or_insert_with(|| Arc::new(ProviderPool::new(name, primary.clone(), others)));
+    Ok(pool.clone())
+}
+
+/// The registered pool of a primary endpoint, or a pool of the endpoint alone.
+pub fn pool_for(url: &Url) -> Arc<ProviderPool> {
+    POOLS
+        .lock()
+        .unwrap()
+        .get(url)
+        .cloned()
+        .unwrap_or_else(|| Arc::new(ProviderPool::new(url.as_str(), url.clone(), vec![])))
+}
+
+/// Every registered pool.
+pub fn registered_pools() -> Vec<Arc<ProviderPool>> {
+    let mut pools: Vec<_> = POOLS.lock().unwrap().values().cloned().collect();
+    pools.sort_by(|a, b| a.name.cmp(&b.name));
+    pools
+}
+
+/// Read a comma-separated list of HTTP(S) URLs. Empty if unset.
+fn url_list_var(name: &str) -> Result<Vec<Url>> {
+    let Some(urls) = env_var::<String>(name)? else {
+        return Ok(vec![]);
+    };
+    urls.split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(|url| {
+            let parsed: Url = url
+                .parse()
+                .map_err(|e| anyhow!("Invalid {name} entry {}: {e}", redact_str(url)))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(anyhow!("Invalid {name}: expected http or https URLs"));
+            }
+            Ok(parsed)
+        })
+        .collect()
+}
+
+fn redact_str(url: &str) -> String {
+    url.parse::<Url>()
+        .map_or_else(|_| "<redacted>".to_string(), |url| redact_url(&url))
+}
+
+/// A JSON-RPC transport sending each request through a [`ProviderPool`].
+#[derive(Debug, Clone)]
+pub struct PooledTransport {
+    pool: Arc<ProviderPool>,
+    transports: Vec<Http<reqwest::Client>>,
+}
+
+impl PooledTransport {
+    pub fn new(pool: Arc<ProviderPool>, client: reqwest::Client) -> Self {
+        let transports = pool
+            .endpoints()
+            .iter()
+            .map(|url| Http::with_client(client.clone(), url.clone()))
+            .collect();
+        Self { pool, transports }
+    }
+}
+
+impl Service<RequestPacket> for PooledTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let this = self.clone();
+        Box::pin(async move {
+            let class = RequestClass::of_packet(&request);
+            this.pool
+                .route(class, |index| {
+                    let mut transport = this.transports[index].clone();
+                    transport.call(request.clone())
+                })
+                .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool() -> ProviderPool {
+        ProviderPool::new(
+            "L1_RPC",
+            "http://primary:8545".parse().unwrap(),
+            vec![
+                "http://secondary:8545".parse().unwrap(),
+                "http://primary:8545".parse().unwrap(),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_ranks_by_latency_and_errors() {
+        let pool = pool();
+        assert_eq!(pool.endpoints().len(), 2);
+        // Unmeasured endpoints keep the configured order.
+        assert_eq!(pool.ranked(RequestClass::Headers), [0, 1]);
+
+        pool.record(0, RequestClass::Headers, Duration::from_millis(200), true);
+        pool.record(1, RequestClass::Headers, Duration::from_millis(50), true);
+        assert_eq!(pool.ranked(RequestClass::Headers), [1, 0]);
+        // Classes are ranked separately.
+        pool.record(0, RequestClass::Proofs, Duration::from_millis(100), true);
+        pool.record(1, RequestClass::Proofs, Duration::from_millis(300), true);
+        assert_eq!(pool.ranked(RequestClass::Proofs), [0, 1]);
+
+        // Errors outweigh the faster endpoint's latency.
+        for _ in 0..3 {
+            pool.record(1, RequestClass::Headers, Duration::from_millis(50), false);
+        }
+        assert_eq!(pool.ranked(RequestClass::Headers), [0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_route_fails_over() {
+        let pool = pool();
+        let result: Result<usize, String> = pool
+            .route(RequestClass::Receipts, |index| async move {
+                if index == 0 {
+                    Err("connection refused".to_string())
+                } else {
+                    Ok(index)
+                }
+            })
+            .await;
+        assert_eq!(result, Ok(1));
+
+        let stats = pool.stats();
+        assert_eq!(stats.len(), 2);
+        assert!(stats.iter().all(|stat| stat.share == 0.5));
+        assert_eq!(stats.iter().map(|stat| stat.errors).sum::<u64>(), 1);
+        // The failing endpoint is now ranked last.
+        assert_eq!(pool.ranked(RequestClass::Receipts), [1, 0]);
+    }
+
+    #[test]
+    fn test_request_class() {
+        assert_eq!(
+            RequestClass::of_method("eth_getBlockByNumber"),
+            RequestClass::Headers
+        );
+        assert_eq!(
+            RequestClass::of_method("eth_getProof"),
+            RequestClass::Proofs
+        );
+        assert_eq!(
+            RequestClass::of_method("eth_getBlockReceipts"),
+            RequestClass::Receipts
+        );
+        assert_eq!(RequestClass::of_method("eth_chainId"), RequestClass::Other);
+        assert_eq!(
+            RequestClass::of_beacon_path("eth/v1/beacon/blob_sidecars/10"),
+            RequestClass::Blobs
+        );
+    }
+}
@@ -1,17 +1,21 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use alloy_eips::eip1559::BaseFeeParams;
 use alloy_primitives::Address;
-use anyhow::Result;
+use anyhow::{bail, Result};
+use log::warn;
 use maili_genesis::ChainGenesis;
 use maili_genesis::RollupConfig;
+use op_succinct_client_utils::forks::{build_forks, unsupported_forks};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::fetcher::RunContext;
 
 /// Matches the output of the optimism_rollupConfig RPC call.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub(crate) struct OptimismRollupConfigRPC {
     genesis: ChainGenesis,
     block_time: u64,
@@ -32,10 +36,59 @@ pub(crate) struct OptimismRollupConfigRPC {
     l1_system_config_address: Address,
     protocol_versions_address: Address,
     da_challenge_contract_address: Option<Address>,
+    /// The fields the embedded kona version doesn't know, including the activation times of
+    /// hardforks newer than it.
+    #[serde(flatten)]
+    unknown: BTreeMap<String, Value>,
+}
+
+impl OptimismRollupConfigRPC {
+    /// The hardforks the rollup config schedules that this build's kona derivation pipeline and
+    /// executor don't support, with their activation times. These are the hardforks newer than the
+    /// build's fork features, and the ones the embedded kona version doesn't know.
+    pub(crate) fn unsupported_forks(&self) -> Vec<(&str, u64)> {
+        let known = [
+            ("regolith", self.regolith_time),
+            ("canyon", self.canyon_time),
+            ("delta", self.delta_time),
+            ("ecotone", self.ecotone_time),
+            ("fjord", self.fjord_time),
+            ("granite", self.granite_time),
+            ("holocene", self.holocene_time),
+        ];
+        let unknown = self.unknown.iter().filter_map(|(field, value)| {
+            Some((field.strip_suffix("_time")?, Some(value.as_u64()?)))
+        });
+        unsupported_forks(known.into_iter().chain(unknown), build_forks(), u64::MAX)
+    }
+}
+
+/// Check that this build supports every hardfork active at `now`. Deriving blocks after a hardfork
+/// with the rules before it produces a different chain, so the range program would fail on them,
+/// or prove outputs the L2 chain never had.
+pub(crate) fn check_supported_forks(
+    op_rollup_config_rpc: &OptimismRollupConfigRPC,
+    now: u64,
+) -> Result<()> {
+    for (fork, activation) in op_rollup_config_rpc.unsupported_forks() {
+        if activation <= now {
+            bail!(
+                "The rollup config activated the {fork} hardfork at {activation}, which this \
+                 build doesn't support. Build with the `{fork}` feature, or upgrade OP Succinct \
+                 to a version that supports {fork}."
+            );
+        }
+        warn!(
+            "The rollup config schedules the {fork} hardfork at {activation}, which this build \
+             doesn't support. Rebuild or upgrade OP Succinct before it activates, or blocks after \
+             it can't be proven."
+        );
+    }
+    Ok(())
 }
 
 /// The chain config returned by the `debug_chainConfig` RPC call.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct ChainConfig {
     chain_id: u64,
@@ -66,7 +119,7 @@ pub(crate) struct ChainConfig {
     optimism: OptimismConfig,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct OptimismConfig {
     eip1559_elasticity: u128,
@@ -139,8 +192,57 @@ pub fn get_rollup_config_path(l2_chain_id: u64, run_context: RunContext) -> Resu
 
 /// Read rollup config from the rollup config file.
 pub fn read_rollup_config(l2_chain_id: u64, run_context: RunContext) -> Result<RollupConfig> {
-    let rollup_config_path = get_rollup_config_path(l2_chain_id, run_context)?;
-    let rollup_config_str = fs::read_to_string(rollup_config_path)?;
+    read_rollup_config_file(&get_rollup_config_path(l2_chain_id, run_context)?)
+}
+
+/// Read rollup config from the given file.
+pub fn read_rollup_config_file(path: &Path) -> Result<RollupConfig> {
+    let rollup_config_str = fs::read_to_string(path)?;
     let rollup_config: RollupConfig = serde_json::from_str(&rollup_config_str)?;
     Ok(rollup_config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOLOCENE_TIME: u64 = 1_732_633_200;
+
+    fn holocene_rollup_config() -> OptimismRollupConfigRPC {
+        OptimismRollupConfigRPC {
+            block_time: 2,
+            granite_time: Some(HOLOCENE_TIME - 7_200),
+            holocene_time: Some(HOLOCENE_TIME),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_keeps_holocene_activation() {
+        let rollup_config =
+            merge_rollup_config(&holocene_rollup_config(), &ChainConfig::default()).unwrap();
+        assert!(rollup_config.is_granite_active(HOLOCENE_TIME - 1));
+        assert!(!rollup_config.is_holocene_active(HOLOCENE_TIME - 1));
+        assert!(rollup_config.is_holocene_active(HOLOCENE_TIME));
+    }
+
+    #[test]
+    fn test_check_supported_forks() {
+        let mut rpc = holocene_rollup_config();
+        assert!(rpc.unsupported_forks().is_empty());
+        check_supported_forks(&rpc, HOLOCENE_TIME).unwrap();
+
+        rpc.unknown = BTreeMap::from([
+            ("future_time".to_string(), Value::from(HOLOCENE_TIME + 100)),
+            ("future_time_note".to_string(), Value::from("ignored")),
+        ]);
+        assert_eq!(
+            rpc.unsupported_forks(),
+            vec![("future", HOLOCENE_TIME + 100)]
+        );
+        // A fork scheduled after now only warns.
+        check_supported_forks(&rpc, HOLOCENE_TIME).unwrap();
+        let err = check_supported_forks(&rpc, HOLOCENE_TIME + 100).unwrap_err();
+        assert!(err.to_string().contains("future hardfork"));
+    }
+}
@@ -0,0 +1,141 @@
+//! The chain a rollup settles on and derives its batches from. The fetcher, host and contracts call
+//! it L1 throughout, but for an L3 it is an OP Stack L2.
+//!
+//! Everything the proposer reads from L1 works the same on an OP Stack settlement layer: L1 heads
+//! are chosen from its `finalized` block, which it only finalizes once its own batches are final
+//! on Ethereum, and the L2 Output Oracle checkpoints its block hashes with `blockhash`. The
+//! difference is data availability. OP Stack chains don't support blob transactions, so the L3's
+//! batches are posted as calldata and there is no beacon node to read blobs from.
+
+use std::{env, str::FromStr};
+
+use alloy_eips::BlockId;
+use anyhow::{anyhow, bail, Result};
+use serde_json::json;
+
+use crate::{config::env_var, fetcher::OPSuccinctDataFetcher};
+
+/// The kind of chain the rollup settles on, set with `SETTLEMENT_LAYER`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SettlementLayer {
+    /// Ethereum or another chain with a beacon node, for L2s.
+    #[default]
+    Ethereum,
+    /// An OP Stack L2, for L3s.
+    OpStack,
+}
+
+impl FromStr for SettlementLayer {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "ethereum" => Ok(Self::Ethereum),
+            "op-stack" => Ok(Self::OpStack),
+            _ => bail!("Unknown settlement layer {s}, expected ethereum or op-stack"),
+        }
+    }
+}
+
+impl SettlementLayer {
+    /// Read the settlement layer from `SETTLEMENT_LAYER`, defaulting to Ethereum.
+    pub fn from_env() -> Result<Self> {
+        match env::var("SETTLEMENT_LAYER") {
+            Ok(settlement_layer) => settlement_layer.parse(),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Whether batches can be posted as blobs, which requires a beacon node to read them.
+    pub fn has_blobs(&self) -> bool {
+        matches!(self, Self::Ethereum)
+    }
+}
+
+/// Serves the beacon API requests witness generation makes, for settlement layers without a beacon
+/// node. The host reads the genesis time and slot duration for every run (see [`BeaconTiming`])
+/// even if the range has no blobs. Blob sidecars are never requested, since batches are posted as
+/// calldata, and would be answered with no blobs.
+///
+/// [`BeaconTiming`]: crate::beacon::BeaconTiming
+#[derive(Debug, Clone)]
+pub struct NoBlobsBeacon {
+    genesis_time: u64,
+    seconds_per_slot: u64,
+}
+
+impl NoBlobsBeacon {
+    /// Use the settlement layer's genesis timestamp as the beacon chain's genesis time, and the
+    /// rollup's block time as its slot duration. `L1_BEACON_GENESIS_TIME` and
+    /// `L1_SECONDS_PER_SLOT` override them, as for a beacon node.
+    ///
+    /// The spacing of the settlement layer's first blocks isn't used as the slot duration, as its
+    /// genesis block can be timestamped arbitrarily long before the next block.
+    pub async fn new(fetcher: &OPSuccinctDataFetcher) -> Result<Self> {
+        let genesis_time = match env_var("L1_BEACON_GENESIS_TIME")? {
+            Some(genesis_time) => genesis_time,
+            None => fetcher.get_l1_header(BlockId::number(0)).await?.timestamp,
+        };
+        let seconds_per_slot = match env_var("L1_SECONDS_PER_SLOT")? {
+            Some(seconds_per_slot) => seconds_per_slot,
+            None => {
+                fetcher
+                    .rollup_config
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("The slot duration requires the rollup config"))?
+                    .block_time
+            }
+        };
+        if seconds_per_slot == 0 {
+            bail!("The settlement layer's seconds per slot must be positive");
+        }
+        Ok(Self {
+            genesis_time,
+            seconds_per_slot,
+        })
+    }
+
+    /// The response to a GET request for the beacon API path.
+    pub fn get(&self, path: &str) -> Result<Vec<u8>> {
+        let response = match path {
+            "eth/v1/beacon/genesis" => json!({
+                "data": {
+                    "genesis_time": self.genesis_time.to_string(),
+                    "genesis_validators_root": format!("0x{}", "00".repeat(32)),
+                    "genesis_fork_version": "0x00000000",
+                }
+            }),
+            "eth/v1/config/spec" => json!({
+                "data": { "SECONDS_PER_SLOT": self.seconds_per_slot.to_string() }
+            }),
+            _ if path.starts_with("eth/v1/beacon/blob_sidecars/") => json!({ "data": [] }),
+            _ => bail!("Unsupported beacon API request {path}"),
+        };
+        Ok(serde_json::to_vec(&response)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_blobs_beacon() {
+        let beacon = NoBlobsBeacon {
+            genesis_time: 1000,
+            seconds_per_slot: 2,
+        };
+        let spec: serde_json::Value =
+            serde_json::from_slice(&beacon.get("eth/v1/config/spec").unwrap()).unwrap();
+        assert_eq!(spec["data"]["SECONDS_PER_SLOT"], "2");
+        let sidecars: serde_json::Value =
+            serde_json::from_slice(&beacon.get("eth/v1/beacon/blob_sidecars/10").unwrap()).unwrap();
+        assert_eq!(sidecars["data"], json!([]));
+        assert!(beacon.get("eth/v1/node/version").is_err());
+
+        assert_eq!(
+            "op-stack".parse::<SettlementLayer>().unwrap(),
+            SettlementLayer::OpStack
+        );
+    }
+}
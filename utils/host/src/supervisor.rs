@@ -0,0 +1,482 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    future::Future,
+    str::FromStr,
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Result};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sysinfo::{get_current_pid, Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+use crate::{
+    config::env_var,
+    progress::{BlockWitnessSize, WitnessGenProgress},
+};
+
+/// How often the memory usage of the process is sampled while witness generation runs.
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Limits applied to a witness generation run.
+#[derive(Debug, Clone, Default)]
+pub struct WitnessGenLimits {
+    /// How long witness generation may take before it is cancelled.
+    pub timeout: Option<Duration>,
+    /// The resident memory of the process above which witness generation is cancelled. Witness
+    /// generation runs in-process, so this includes concurrent runs and the rest of the process.
+    pub max_memory_bytes: Option<u64>,
+    /// The preimage bytes a single block may contribute to the witness before witness generation
+    /// is cancelled. A block with a huge state access can make any span containing it unprovable.
+    pub max_block_witness_bytes: Option<u64>,
+    /// What to do with the span when a block exceeds `max_block_witness_bytes`.
+    pub oversized_block_policy: OversizedBlockPolicy,
+    /// How long witness generation may go without a hint or preimage before it is considered
+    /// stalled and cancelled.
+    pub stall_timeout: Option<Duration>,
+    /// How many times a stalled run is restarted before it fails.
+    pub stall_retries: u32,
+}
+
+impl WitnessGenLimits {
+    /// Read the limits from the environment. Unset limits are disabled.
+    ///
+    /// - `WITNESSGEN_TIMEOUT_SECS`
+    /// - `WITNESSGEN_MAX_MEMORY_MB`
+    /// - `WITNESSGEN_MAX_BLOCK_WITNESS_MB`
+    /// - `WITNESSGEN_OVERSIZED_BLOCK_POLICY`: `split` or `abort`. Default: `split`.
+    /// - `WITNESSGEN_STALL_TIMEOUT_SECS`
+    /// - `WITNESSGEN_STALL_RETRIES`: Default: [`DEFAULT_STALL_RETRIES`].
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            timeout: env_var("WITNESSGEN_TIMEOUT_SECS")?.map(Duration::from_secs),
+            max_memory_bytes: env_var::<u64>("WITNESSGEN_MAX_MEMORY_MB")?
+                .map(|mb| mb * 1024 * 1024),
+            max_block_witness_bytes: env_var::<u64>("WITNESSGEN_MAX_BLOCK_WITNESS_MB")?
+                .map(|mb| mb * 1024 * 1024),
+            oversized_block_policy: env_var("WITNESSGEN_OVERSIZED_BLOCK_POLICY")?
+                .unwrap_or_default(),
+            stall_timeout: env_var("WITNESSGEN_STALL_TIMEOUT_SECS")?.map(Duration::from_secs),
+            stall_retries: env_var("WITNESSGEN_STALL_RETRIES")?.unwrap_or(DEFAULT_STALL_RETRIES),
+        })
+    }
+}
+
+/// The number of times a stalled run is restarted by default.
+pub const DEFAULT_STALL_RETRIES: u32 = 2;
+
+/// The RPC the host fetches a hint's preimages from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HintSource {
+    L1,
+    L1Beacon,
+    L2,
+}
+
+impl HintSource {
+    /// The RPC serving a hint type, or `None` for hints the host answers without one.
+    pub fn of_hint(hint_type: &str) -> Option<Self> {
+        match hint_type {
+            "l1-blob" => Some(Self::L1Beacon),
+            "l1-precompile" => None,
+            hint if hint.starts_with("l1-") => Some(Self::L1),
+            hint if hint.starts_with("l2-") || hint == "starting-l2-output" => Some(Self::L2),
+            _ => None,
+        }
+    }
+}
+
+/// Witness generation went without a hint or preimage for longer than the stall timeout, which
+/// usually means the host is waiting on an RPC that stopped responding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WitnessGenStalled {
+    pub start: u64,
+    pub end: u64,
+    /// The block being executed when the run stalled.
+    pub block: u64,
+    pub idle_secs: u64,
+    /// The type of the last hint the client sent, whose preimages the host was likely fetching.
+    pub last_hint: Option<String>,
+    /// The RPC serving the last hint.
+    pub source: Option<HintSource>,
+}
+
+impl WitnessGenStalled {
+    fn new(progress: &WitnessGenProgress) -> Self {
+        let snapshot = progress.snapshot();
+        let last_hint = progress.last_hint();
+        Self {
+            start: snapshot.start_block,
+            end: snapshot.end_block,
+            block: snapshot.current_block,
+            idle_secs: snapshot.idle_secs,
+            source: last_hint.as_deref().and_then(HintSource::of_hint),
+            last_hint,
+        }
+    }
+}
+
+impl fmt::Display for WitnessGenStalled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Witness generation for span {}-{} stalled at block {}: no progress for {}s",
+            self.start, self.end, self.block, self.idle_secs
+        )?;
+        if let Some(hint) = &self.last_hint {
+            write!(f, " after a {hint} hint")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for WitnessGenStalled {}
+
+/// The number of stalled runs of the process, by the redacted URL of the RPC they stalled on, or
+/// `unknown`.
+static STALLS: LazyLock<Mutex<BTreeMap<String, u64>>> = LazyLock::new(Default::default);
+
+/// Count a stalled run against the RPC it stalled on.
+pub fn record_stall(provider: &str) {
+    *STALLS
+        .lock()
+        .unwrap()
+        .entry(provider.to_string())
+        .or_default() += 1;
+}
+
+/// The number of stalled runs of the process, by RPC.
+pub fn stall_counts() -> Vec<(String, u64)> {
+    STALLS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(provider, count)| (provider.clone(), *count))
+        .collect()
+}
+
+/// What to do with a span when one of its blocks exceeds the per-block witness limit. Either way,
+/// witness generation is cancelled as soon as the block exceeds it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OversizedBlockPolicy {
+    /// Split the span around the block, so the blocks before and after it are proven while the
+    /// block is isolated in a span of its own.
+    #[default]
+    Split,
+    /// Fail the span with a report of the block, leaving the span as is.
+    Abort,
+}
+
+impl FromStr for OversizedBlockPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "split" => Ok(Self::Split),
+            "abort" => Ok(Self::Abort),
+            _ => bail!("expected split or abort"),
+        }
+    }
+}
+
+impl fmt::Display for OversizedBlockPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Split => write!(f, "split"),
+            Self::Abort => write!(f, "abort"),
+        }
+    }
+}
+
+/// The number of largest blocks listed in an [`OversizedBlockWitness`].
+const LARGEST_BLOCKS_REPORTED: usize = 5;
+
+/// A block of the span contributed more to the witness than the per-block limit allows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OversizedBlockWitness {
+    pub start: u64,
+    pub end: u64,
+    pub block: u64,
+    pub bytes: u64,
+    pub limit: u64,
+    pub policy: OversizedBlockPolicy,
+    /// The blocks of the span executed so far that contributed the most to the witness, largest
+    /// first.
+    pub largest_blocks: Vec<BlockWitnessSize>,
+}
+
+impl OversizedBlockWitness {
+    fn new(
+        progress: &WitnessGenProgress,
+        oversized: BlockWitnessSize,
+        limit: u64,
+        policy: OversizedBlockPolicy,
+    ) -> Self {
+        let snapshot = progress.snapshot();
+        let mut largest_blocks = progress.block_witness_sizes();
+        largest_blocks.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+        largest_blocks.truncate(LARGEST_BLOCKS_REPORTED);
+        Self {
+            start: snapshot.start_block,
+            end: snapshot.end_block,
+            block: oversized.block,
+            bytes: oversized.bytes,
+            limit,
+            policy,
+            largest_blocks,
+        }
+    }
+
+    /// The block boundaries to split the span at, isolating the oversized block in a span of its
+    /// own. Empty if the policy is [`OversizedBlockPolicy::Abort`] or the span is the block alone.
+    pub fn split_points(&self) -> Vec<u64> {
+        if self.policy == OversizedBlockPolicy::Abort {
+            return vec![];
+        }
+        // A span proves the blocks after its start block, so the block's own span starts at the
+        // block before it.
+        [self.block - 1, self.block]
+            .into_iter()
+            .filter(|&point| point > self.start && point < self.end)
+            .collect()
+    }
+}
+
+impl fmt::Display for OversizedBlockWitness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Witness generation for span {}-{} cancelled: block {} contributed {} bytes to the \
+             witness, which exceeds the per-block limit of {} bytes (largest blocks: {})",
+            self.start,
+            self.end,
+            self.block,
+            self.bytes,
+            self.limit,
+            self.largest_blocks
+                .iter()
+                .map(|size| format!("{} ({} bytes)", size.block, size.bytes))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl std::error::Error for OversizedBlockWitness {}
+
+/// Samples the resident memory of the current process.
+struct MemoryMonitor {
+    system: System,
+    pid: Pid,
+    peak_bytes: u64,
+}
+
+impl MemoryMonitor {
+    fn new() -> Result<Self> {
+        Ok(Self {
+            system: System::new(),
+            pid: get_current_pid().map_err(|e| anyhow!(e))?,
+            peak_bytes: 0,
+        })
+    }
+
+    fn sample(&mut self) -> u64 {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[self.pid]),
+            false,
+            ProcessRefreshKind::new().with_memory(),
+        );
+        let bytes = self
+            .system
+            .process(self.pid)
+            .map(|process| process.memory())
+            .unwrap_or_default();
+        self.peak_bytes = self.peak_bytes.max(bytes);
+        bytes
+    }
+}
+
+fn to_mb(bytes: u64) -> u64 {
+    bytes / 1024 / 1024
+}
+
+/// Check the blocks of a run against the per-block witness limit, if there is one.
+fn check_block_witness_sizes(
+    limits: &WitnessGenLimits,
+    progress: &WitnessGenProgress,
+) -> Result<(), OversizedBlockWitness> {
+    let Some(limit) = limits.max_block_witness_bytes else {
+        return Ok(());
+    };
+    match progress.oversized_block(limit) {
+        Some(oversized) => {
+            let error = OversizedBlockWitness::new(
+                progress,
+                oversized,
+                limit,
+                limits.oversized_block_policy,
+            );
+            warn!("{error}");
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Run witness generation within the limits, cancelling it if it exceeds them or stalls. Errors,
+/// including the limit that was hit, report how long the run took and the peak memory of the
+/// process.
+pub async fn supervise<T>(
+    limits: &WitnessGenLimits,
+    progress: &WitnessGenProgress,
+    witness_generation: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let start = Instant::now();
+    let mut monitor = MemoryMonitor::new()?;
+    let mut interval = tokio::time::interval(MEMORY_SAMPLE_INTERVAL);
+    let deadline = limits.timeout.map(|timeout| start + timeout);
+    tokio::pin!(witness_generation);
+
+    loop {
+        tokio::select! {
+            result = &mut witness_generation => {
+                let result = result.map_err(|e| {
+                    e.context(format!(
+                        "Witness generation failed after {}s (peak memory {} MB)",
+                        start.elapsed().as_secs(),
+                        to_mb(monitor.peak_bytes)
+                    ))
+                })?;
+                // The run may have completed between two checks.
+                check_block_witness_sizes(limits, progress)?;
+                return Ok(result);
+            }
+            _ = interval.tick() => {
+                check_block_witness_sizes(limits, progress)?;
+                let memory_bytes = monitor.sample();
+                if let Some(max_memory_bytes) = limits.max_memory_bytes {
+                    if memory_bytes > max_memory_bytes {
+                        bail!(
+                            "Witness generation cancelled after {}s: memory usage of {} MB exceeds the limit of {} MB",
+                            start.elapsed().as_secs(),
+                            to_mb(memory_bytes),
+                            to_mb(max_memory_bytes)
+                        );
+                    }
+                }
+                if limits
+                    .stall_timeout
+                    .is_some_and(|stall_timeout| progress.idle_for() >= stall_timeout)
+                {
+                    let error = WitnessGenStalled::new(progress);
+                    warn!("{error}");
+                    return Err(error.into());
+                }
+                if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                    bail!(
+                        "Witness generation timed out after {}s (peak memory {} MB)",
+                        start.elapsed().as_secs(),
+                        to_mb(monitor.peak_bytes)
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oversized(block: u64, policy: OversizedBlockPolicy) -> OversizedBlockWitness {
+        OversizedBlockWitness {
+            start: 100,
+            end: 110,
+            block,
+            bytes: 2048,
+            limit: 1024,
+            policy,
+            largest_blocks: vec![],
+        }
+    }
+
+    #[test]
+    fn test_split_points() {
+        // The block gets a span of its own, between the spans before and after it.
+        assert_eq!(
+            oversized(105, OversizedBlockPolicy::Split).split_points(),
+            [104, 105]
+        );
+        // The first and last blocks only need one split.
+        assert_eq!(
+            oversized(101, OversizedBlockPolicy::Split).split_points(),
+            [101]
+        );
+        assert_eq!(
+            oversized(110, OversizedBlockPolicy::Split).split_points(),
+            [109]
+        );
+        assert!(oversized(105, OversizedBlockPolicy::Abort)
+            .split_points()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_hint_source() {
+        assert_eq!(HintSource::of_hint("l1-blob"), Some(HintSource::L1Beacon));
+        assert_eq!(HintSource::of_hint("l1-receipts"), Some(HintSource::L1));
+        assert_eq!(HintSource::of_hint("l2-state-node"), Some(HintSource::L2));
+        assert_eq!(
+            HintSource::of_hint("starting-l2-output"),
+            Some(HintSource::L2)
+        );
+        assert_eq!(HintSource::of_hint("l1-precompile"), None);
+    }
+
+    #[tokio::test]
+    async fn test_supervise_cancels_stalled_run() {
+        let limits = WitnessGenLimits {
+            stall_timeout: Some(Duration::from_millis(1500)),
+            ..Default::default()
+        };
+        let progress = WitnessGenProgress::new(100, 110);
+        let run = {
+            let progress = progress.clone();
+            async move {
+                progress.record_hint("l2-payload-witness 0x00");
+                progress.record_hint("l1-receipts 0x00");
+                std::future::pending::<Result<()>>().await
+            }
+        };
+        let error = supervise(&limits, &progress, run).await.unwrap_err();
+        let stalled = error.downcast_ref::<WitnessGenStalled>().unwrap();
+        assert_eq!(stalled.block, 101);
+        assert_eq!(stalled.last_hint.as_deref(), Some("l1-receipts"));
+        assert_eq!(stalled.source, Some(HintSource::L1));
+    }
+
+    #[tokio::test]
+    async fn test_supervise_cancels_oversized_block() {
+        let limits = WitnessGenLimits {
+            max_block_witness_bytes: Some(64),
+            ..Default::default()
+        };
+        let progress = WitnessGenProgress::new(100, 110);
+        let run = {
+            let progress = progress.clone();
+            async move {
+                progress.record_hint("l2-payload-witness 0x00");
+                progress.record_preimage(128);
+                Ok(())
+            }
+        };
+        let error = supervise(&limits, &progress, run).await.unwrap_err();
+        let oversized = error.downcast_ref::<OversizedBlockWitness>().unwrap();
+        assert_eq!(oversized.block, 101);
+        assert_eq!(oversized.bytes, 128);
+    }
+}
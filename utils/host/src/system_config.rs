@@ -0,0 +1,243 @@
+//! The `ConfigUpdate` events of the L1 `SystemConfig` contract.
+//!
+//! Changes to the batcher, the fee scalars, the gas limit, the EIP-1559 parameters or the operator
+//! fee take effect in derivation from the L1 block whose receipts carry the event. Derivation reads
+//! the receipts of every L1 block it traverses, so an update landing in the middle of a span is
+//! applied by the range program like any other. Listing the updates within a span's L1 blocks shows
+//! which spans straddle a config change, e.g. when diagnosing a span that fails to derive.
+
+use alloy_primitives::{Address, Log, TxHash, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::Filter;
+use alloy_sol_types::{sol, SolEvent};
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::fetcher::OPSuccinctDataFetcher;
+
+sol! {
+    event ConfigUpdate(uint256 indexed version, uint8 indexed updateType, bytes data);
+}
+
+/// The L1 blocks queried for logs per request, to stay below the RPC's limits.
+const LOG_QUERY_BLOCKS: u64 = 5_000;
+
+/// A change to the system config, as decoded from a `ConfigUpdate` event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SystemConfigChange {
+    Batcher {
+        address: Address,
+    },
+    /// The L1 fee parameters. Since Ecotone, the scalar packs the base fee and blob base fee
+    /// scalars.
+    GasConfig {
+        overhead: U256,
+        scalar: U256,
+    },
+    GasLimit {
+        gas_limit: u64,
+    },
+    UnsafeBlockSigner {
+        address: Address,
+    },
+    Eip1559Params {
+        denominator: u32,
+        elasticity: u32,
+    },
+    OperatorFee {
+        scalar: u32,
+        constant: u64,
+    },
+    /// An update type this version doesn't decode.
+    Unknown {
+        update_type: u8,
+    },
+}
+
+/// A `ConfigUpdate` event emitted in an L1 block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemConfigUpdate {
+    pub l1_block_number: u64,
+    pub tx_hash: Option<TxHash>,
+    pub change: SystemConfigChange,
+}
+
+/// The 32-byte words of an update's data, checking there are exactly `count`.
+fn words(data: &[u8], count: usize) -> Result<Vec<U256>> {
+    if data.len() != count * 32 {
+        bail!(
+            "expected {} bytes of update data, got {}",
+            count * 32,
+            data.len()
+        );
+    }
+    Ok(data.chunks(32).map(U256::from_be_slice).collect())
+}
+
+fn address(word: U256) -> Address {
+    Address::from_word(B256::from(word))
+}
+
+/// Decode the change of a `ConfigUpdate` log.
+pub fn decode_config_update(log: &Log) -> Result<SystemConfigChange> {
+    let event = ConfigUpdate::decode_log(log, true)?.data;
+    if event.version != U256::ZERO {
+        bail!("unsupported ConfigUpdate version {}", event.version);
+    }
+    let data = event.data.as_ref();
+    Ok(match event.updateType {
+        0 => SystemConfigChange::Batcher {
+            address: address(words(data, 1)?[0]),
+        },
+        1 => {
+            let words = words(data, 2)?;
+            SystemConfigChange::GasConfig {
+                overhead: words[0],
+                scalar: words[1],
+            }
+        }
+        2 => SystemConfigChange::GasLimit {
+            gas_limit: words(data, 1)?[0].try_into()?,
+        },
+        3 => SystemConfigChange::UnsafeBlockSigner {
+            address: address(words(data, 1)?[0]),
+        },
+        4 => {
+            let params: u64 = words(data, 1)?[0].try_into()?;
+            SystemConfigChange::Eip1559Params {
+                denominator: (params >> 32) as u32,
+                elasticity: params as u32,
+            }
+        }
+        5 => {
+            let params: u128 = words(data, 1)?[0].try_into()?;
+            SystemConfigChange::OperatorFee {
+                scalar: (params >> 64) as u32,
+                constant: params as u64,
+            }
+        }
+        update_type => SystemConfigChange::Unknown { update_type },
+    })
+}
+
+/// Get the system config updates emitted in the L1 blocks from `l1_start` to `l1_end`, inclusive,
+/// in order.
+pub async fn get_system_config_updates(
+    fetcher: &OPSuccinctDataFetcher,
+    l1_start: u64,
+    l1_end: u64,
+) -> Result<Vec<SystemConfigUpdate>> {
+    let system_config = fetcher
+        .rollup_config
+        .as_ref()
+        .ok_or_else(|| anyhow!("Listing system config updates requires the rollup config"))?
+        .l1_system_config_address;
+
+    let mut updates = Vec::new();
+    let mut from = l1_start;
+    while from <= l1_end {
+        let to = (from + LOG_QUERY_BLOCKS - 1).min(l1_end);
+        let filter = Filter::new()
+            .address(system_config)
+            .event_signature(ConfigUpdate::SIGNATURE_HASH)
+            .from_block(from)
+            .to_block(to);
+        for log in fetcher.l1_provider.get_logs(&filter).await? {
+            updates.push(SystemConfigUpdate {
+                l1_block_number: log
+                    .block_number
+                    .ok_or_else(|| anyhow!("ConfigUpdate log without a block number"))?,
+                tx_hash: log.transaction_hash,
+                change: decode_config_update(&log.inner)?,
+            });
+        }
+        from = to + 1;
+    }
+    Ok(updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Bytes};
+
+    fn update_log(update_type: u8, data: Vec<u8>) -> Log {
+        let event = ConfigUpdate {
+            version: U256::ZERO,
+            updateType: update_type,
+            data: Bytes::from(data),
+        };
+        Log {
+            address: Address::ZERO,
+            data: event.encode_log_data(),
+        }
+    }
+
+    fn word(value: U256) -> Vec<u8> {
+        value.to_be_bytes::<32>().to_vec()
+    }
+
+    #[test]
+    fn test_decode_batcher_and_signer() {
+        let batcher = address!("6887246668a3b87F54DeB3b94Ba47a6f63F32985");
+        let data = batcher.into_word().to_vec();
+        assert_eq!(
+            decode_config_update(&update_log(0, data.clone())).unwrap(),
+            SystemConfigChange::Batcher { address: batcher }
+        );
+        assert_eq!(
+            decode_config_update(&update_log(3, data)).unwrap(),
+            SystemConfigChange::UnsafeBlockSigner { address: batcher }
+        );
+    }
+
+    #[test]
+    fn test_decode_fee_updates() {
+        let scalar = (U256::from(1) << 248) | (U256::from(810_949) << 32) | U256::from(1_368);
+        let data = [word(U256::ZERO), word(scalar)].concat();
+        assert_eq!(
+            decode_config_update(&update_log(1, data)).unwrap(),
+            SystemConfigChange::GasConfig {
+                overhead: U256::ZERO,
+                scalar
+            }
+        );
+
+        let gas_limit = decode_config_update(&update_log(2, word(U256::from(60_000_000))));
+        assert_eq!(
+            gas_limit.unwrap(),
+            SystemConfigChange::GasLimit {
+                gas_limit: 60_000_000
+            }
+        );
+
+        let params = U256::from((250u64 << 32) | 6);
+        assert_eq!(
+            decode_config_update(&update_log(4, word(params))).unwrap(),
+            SystemConfigChange::Eip1559Params {
+                denominator: 250,
+                elasticity: 6
+            }
+        );
+
+        let operator_fee = U256::from((7u128 << 64) | 100);
+        assert_eq!(
+            decode_config_update(&update_log(5, word(operator_fee))).unwrap(),
+            SystemConfigChange::OperatorFee {
+                scalar: 7,
+                constant: 100
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed_updates() {
+        // The gas config is two words.
+        assert!(decode_config_update(&update_log(1, word(U256::ZERO))).is_err());
+        assert_eq!(
+            decode_config_update(&update_log(9, vec![])).unwrap(),
+            SystemConfigChange::Unknown { update_type: 9 }
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! The verifying key commitments the L2 Output Oracle is configured with.
+//!
+//! SP1 verifying keys have two digests, and the contracts use both. The aggregation vkey is the
+//! `bytes32` digest, which the SP1 verifier checks aggregation proofs against. The range vkey
+//! commitment is built from the `hash_u32` digest of the range program's vkey, which the
+//! aggregation program verifies span proofs against inside the zkVM and commits to (see
+//! [`range_vkey_commitment`]). Mixing the two up deploys a contract that rejects every proof, so
+//! every binary computes them with [`vkey_commitment`].
+
+use std::str::FromStr;
+
+use alloy_primitives::B256;
+use op_succinct_client_utils::types::range_vkey_commitment;
+use serde::Serialize;
+use sp1_sdk::{HashableKey, SP1VerifyingKey};
+
+/// The values the L2 Output Oracle's `aggregationVkey` and `rangeVkeyCommitment` must be set to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VkeyCommitment {
+    pub aggregation_vkey: B256,
+    pub range_vkey_commitment: B256,
+}
+
+/// The vkey commitment of the aggregation program and the range program, or both range programs
+/// in quorum mode.
+pub fn vkey_commitment(
+    agg_vk: &SP1VerifyingKey,
+    range_vk: &SP1VerifyingKey,
+    secondary_range_vk: Option<&SP1VerifyingKey>,
+) -> VkeyCommitment {
+    VkeyCommitment {
+        aggregation_vkey: B256::from_str(&agg_vk.bytes32())
+            .expect("bytes32 is a 0x-prefixed 32 byte hex string"),
+        range_vkey_commitment: range_vkey_commitment(
+            range_vk.hash_u32(),
+            secondary_range_vk.map(|vk| vk.hash_u32()),
+        ),
+    }
+}
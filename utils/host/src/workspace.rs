@@ -0,0 +1,163 @@
+//! The directory layout of the files the host and proposer write.
+//!
+//! Witness data, failure reports, execution reports and proof artifacts used to be written to
+//! separate directories, some relative to the working directory and some not namespaced by chain,
+//! so runs against different chains overwrote each other's files. Everything now lives under one
+//! root, set with `WORKSPACE_DIR`:
+//!
+//! ```text
+//! <root>/<l2_chain_id>/
+//!     artifacts/<proof_id>.json
+//!     checkpoint-overrides/<hash>.json
+//!     l1-cache/
+//!     <start>-<end>/
+//!         witness/
+//!         witness-single/
+//!         witness-stdin.bin
+//!         failure-report.json
+//!         execution-report.csv
+//!         proof.bin
+//! ```
+//!
+//! The root defaults to `data` in the cargo workspace in development, and `/usr/local/data` in
+//! Docker.
+
+use std::path::{Path, PathBuf};
+
+use alloy_primitives::B256;
+use anyhow::Result;
+use cargo_metadata::MetadataCommand;
+
+use crate::{config::env_var, fetcher::RunContext, ProgramType};
+
+/// The root directory of the files written for every chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    root: PathBuf,
+}
+
+impl Workspace {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// The root directory, from `WORKSPACE_DIR`. Uses the default for the run context if unset.
+    pub fn dir_from_env() -> Result<Option<PathBuf>> {
+        env_var("WORKSPACE_DIR")
+    }
+
+    /// Read the workspace from `WORKSPACE_DIR`, or use the default root for the run context.
+    pub fn from_env(run_context: RunContext) -> Result<Self> {
+        let root = match Self::dir_from_env()? {
+            Some(root) => root,
+            None => match run_context {
+                RunContext::Dev => MetadataCommand::new()
+                    .exec()?
+                    .workspace_root
+                    .join("data")
+                    .into(),
+                RunContext::Docker => PathBuf::from("/usr/local/data"),
+            },
+        };
+        Ok(Self::new(root))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The directory of the files written for a chain.
+    pub fn chain_dir(&self, l2_chain_id: u64) -> PathBuf {
+        self.root.join(l2_chain_id.to_string())
+    }
+
+    /// The directory of the files written for the blocks after `start` up to `end`.
+    pub fn range_dir(&self, l2_chain_id: u64, start: u64, end: u64) -> PathBuf {
+        self.chain_dir(l2_chain_id).join(format!("{start}-{end}"))
+    }
+
+    /// The directory witness generation stores the range's preimages in. The single and multi
+    /// block programs keep separate copies.
+    pub fn witness_dir(
+        &self,
+        l2_chain_id: u64,
+        start: u64,
+        end: u64,
+        program_type: ProgramType,
+    ) -> PathBuf {
+        let range_dir = self.range_dir(l2_chain_id, start, end);
+        match program_type {
+            ProgramType::Single => range_dir.join("witness-single"),
+            ProgramType::Multi => range_dir.join("witness"),
+        }
+    }
+
+    /// The cached stdin of the range's last span proof, when the witness cache is enabled.
+    pub fn witness_stdin_path(&self, l2_chain_id: u64, start: u64, end: u64) -> PathBuf {
+        self.range_dir(l2_chain_id, start, end)
+            .join("witness-stdin.bin")
+    }
+
+    /// The report of the range's last failed witness generation run.
+    pub fn failure_report_path(&self, l2_chain_id: u64, start: u64, end: u64) -> PathBuf {
+        self.range_dir(l2_chain_id, start, end)
+            .join("failure-report.json")
+    }
+
+    /// The execution stats of the range's last execution.
+    pub fn execution_report_path(&self, l2_chain_id: u64, start: u64, end: u64) -> PathBuf {
+        self.range_dir(l2_chain_id, start, end)
+            .join("execution-report.csv")
+    }
+
+    /// The range's proof, when proven locally.
+    pub fn proof_path(&self, l2_chain_id: u64, start: u64, end: u64) -> PathBuf {
+        self.range_dir(l2_chain_id, start, end).join("proof.bin")
+    }
+
+    /// The directory the artifacts of the chain's fulfilled span proofs are stored in.
+    pub fn artifacts_dir(&self, l2_chain_id: u64) -> PathBuf {
+        self.chain_dir(l2_chain_id).join("artifacts")
+    }
+
+    /// The pause state of the server proving the chain, while it is paused.
+    pub fn pause_state_path(&self, l2_chain_id: u64) -> PathBuf {
+        self.chain_dir(l2_chain_id).join("pause.json")
+    }
+
+    /// The proof IDs of the aggregation proofs the server requested, by request.
+    pub fn agg_requests_path(&self, l2_chain_id: u64) -> PathBuf {
+        self.chain_dir(l2_chain_id).join("agg-requests.json")
+    }
+
+    /// The record of an aggregation request over gaps force-resolved by governance, by the hash of
+    /// its checkpoint overrides.
+    pub fn checkpoint_overrides_path(&self, l2_chain_id: u64, hash: B256) -> PathBuf {
+        self.chain_dir(l2_chain_id)
+            .join("checkpoint-overrides")
+            .join(format!("{hash}.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        let workspace = Workspace::new(PathBuf::from("/data"));
+        assert_eq!(
+            workspace.witness_dir(10, 100, 110, ProgramType::Multi),
+            Path::new("/data/10/100-110/witness")
+        );
+        assert_eq!(
+            workspace.failure_report_path(8453, 100, 110),
+            Path::new("/data/8453/100-110/failure-report.json")
+        );
+        assert_ne!(
+            workspace.range_dir(10, 100, 110),
+            workspace.range_dir(8453, 100, 110)
+        );
+        assert_eq!(workspace.artifacts_dir(10), Path::new("/data/10/artifacts"));
+    }
+}